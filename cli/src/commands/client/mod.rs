@@ -0,0 +1,469 @@
+use ledger::{
+    scan_state::transaction_logic::{signed_command, valid, GenericCommand},
+    transaction_pool::ValidCommandWithHash,
+    Account,
+};
+use mina_p2p_messages::v2::{MinaBaseUserCommandStableV2, TokenIdKeyHash};
+use node::{
+    block_producer::{StagedLedgerDiffCreationLog, StagedLedgerDiffCreationResources},
+    p2p::connection::outgoing::P2pConnectionOutgoingInitOpts,
+    rpc::{
+        PeerConnectionStatus, RpcBlockProducerStatsGetResponse, RpcPeerInfo, RpcScanStateSummary,
+        RpcScanStateSummaryScanStateJob,
+    },
+    stats::block_producer::BlockProductionAttempt,
+};
+use openmina_node_account::AccountPublicKey;
+use reqwest::{blocking::Client as HttpClient, Url};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// Operate a running node through its HTTP server.
+#[derive(Debug, clap::Args)]
+pub struct Client {
+    /// Address of the node's HTTP server.
+    #[arg(
+        long,
+        short,
+        global = true,
+        env = "OPENMINA_NODE_URL",
+        default_value = "http://127.0.0.1:3000"
+    )]
+    pub node: Url,
+
+    /// Print the response as JSON instead of the human-readable output.
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: ClientCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ClientCommand {
+    /// Sync status, best tip and pool sizes of the node.
+    Status,
+    /// List or connect peers.
+    #[command(subcommand)]
+    Peers(PeersCommand),
+    /// Latest blocks of the best chain.
+    BestChain {
+        /// Number of blocks to show.
+        #[arg(long, short, default_value_t = 10)]
+        limit: u32,
+    },
+    /// Balance and nonce of the account.
+    Balance {
+        public_key: AccountPublicKey,
+        /// Token id of the account. Accounts for all tokens are shown if
+        /// not set.
+        #[arg(long)]
+        token: Option<String>,
+    },
+    /// Transactions in the transaction pool.
+    TransactionPool,
+    /// Jobs in the snark pool, with their commitments and snarks.
+    SnarkPool,
+    /// Summary of the scan state.
+    ScanState {
+        /// Hash or height of the block. Best tip if not set.
+        block: Option<String>,
+    },
+    /// Block production attempts. With the hash of a produced block, why
+    /// commands and snark work were left out of it.
+    BlockProducer {
+        /// Hash of the block produced by the node.
+        block: Option<String>,
+    },
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum PeersCommand {
+    /// List known peers.
+    List,
+    /// Connect to the peer.
+    Connect {
+        /// Address of the peer, e.g. `/dns4/host/tcp/8302/p2p/<peer_id>`.
+        address: P2pConnectionOutgoingInitOpts,
+    },
+}
+
+impl Client {
+    pub fn run(self) -> anyhow::Result<()> {
+        let client = NodeClient {
+            http: HttpClient::new(),
+            url: self.node,
+        };
+        let json = self.json;
+
+        match self.command {
+            ClientCommand::Status => {
+                let status: Value = client.get("status")?;
+                if json {
+                    return print_json(&status);
+                }
+                print_status(&status);
+            }
+            ClientCommand::Peers(PeersCommand::List) => {
+                let peers: Vec<RpcPeerInfo> = client.get("state/peers")?;
+                if json {
+                    return print_json(&peers);
+                }
+                print_peers(&peers);
+            }
+            ClientCommand::Peers(PeersCommand::Connect { address }) => {
+                let res: Value = client.post("state/peers/connect", address.to_string())?;
+                if json {
+                    return print_json(&res);
+                }
+                println!("connecting to peer {}", display(&res["peer_id"]));
+            }
+            ClientCommand::BestChain { limit } => {
+                let blocks: Vec<Value> = client.get(&format!("best-chain?limit={limit}"))?;
+                if json {
+                    return print_json(&blocks);
+                }
+                print_best_chain(&blocks);
+            }
+            ClientCommand::Balance { public_key, token } => {
+                let path = match token {
+                    None => format!("accounts/{public_key}"),
+                    Some(token) => format!("accounts/{public_key}/{token}"),
+                };
+                let accounts: Vec<Account> = client.get(&path)?;
+                if json {
+                    return print_json(&accounts);
+                }
+                print_accounts(&accounts);
+            }
+            ClientCommand::TransactionPool => {
+                let transactions: Vec<ValidCommandWithHash> = client.get("transaction-pool")?;
+                if json {
+                    return print_json(&transactions);
+                }
+                print_transaction_pool(&transactions);
+            }
+            ClientCommand::SnarkPool => {
+                let jobs: Vec<Value> = client.get("snark-pool/jobs")?;
+                if json {
+                    return print_json(&jobs);
+                }
+                print_snark_pool(&jobs);
+            }
+            ClientCommand::ScanState { block } => {
+                let path = match block {
+                    None => "scan-state/summary".to_owned(),
+                    Some(block) => format!("scan-state/summary/{block}"),
+                };
+                let summary: RpcScanStateSummary = client.get(&path)?;
+                if json {
+                    return print_json(&summary);
+                }
+                print_scan_state(&summary);
+            }
+            ClientCommand::BlockProducer { block } => {
+                let stats: RpcBlockProducerStatsGetResponse = client.get("stats/block_producer")?;
+                let Some(stats) = stats else {
+                    anyhow::bail!("block producer isn't enabled on the node");
+                };
+                let Some(hash) = block else {
+                    if json {
+                        return print_json(&stats.attempts);
+                    }
+                    print_block_production_attempts(&stats.attempts);
+                    return Ok(());
+                };
+                let Some(block) = stats
+                    .attempts
+                    .into_iter()
+                    .filter_map(|attempt| attempt.block)
+                    .find(|block| block.hash.to_string() == hash)
+                else {
+                    anyhow::bail!("block {hash} isn't in the block producer stats of the node");
+                };
+                if json {
+                    return print_json(&block.diff_creation_log);
+                }
+                print_diff_creation_log(&block.diff_creation_log);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct NodeClient {
+    http: HttpClient,
+    url: Url,
+}
+
+impl NodeClient {
+    fn get<T: DeserializeOwned>(&self, path: &str) -> anyhow::Result<T> {
+        let url = self.url.join(path)?;
+        let response = self.http.get(url.clone()).send()?;
+        Self::parse(url, response)
+    }
+
+    fn post<T: DeserializeOwned>(&self, path: &str, body: String) -> anyhow::Result<T> {
+        let url = self.url.join(path)?;
+        let response = self.http.post(url.clone()).body(body).send()?;
+        Self::parse(url, response)
+    }
+
+    fn parse<T: DeserializeOwned>(
+        url: Url,
+        response: reqwest::blocking::Response,
+    ) -> anyhow::Result<T> {
+        let status = response.status();
+        let body = response.text()?;
+        if !status.is_success() {
+            anyhow::bail!("request to {url} failed with {status}: {body}");
+        }
+        serde_json::from_str(&body)
+            .map_err(|err| anyhow::anyhow!("unexpected response from {url}: {err}"))
+    }
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Displays the json value without quotes around strings.
+fn display(value: &Value) -> String {
+    match value {
+        Value::Null => "-".to_owned(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+fn print_status(status: &Value) {
+    if status.is_null() {
+        println!("node is not initialized yet");
+        return;
+    }
+    let frontier = &status["transition_frontier"];
+    let best_tip = &frontier["best_tip"];
+    let sync = &frontier["sync"];
+    let peers = status["peers"].as_array().map_or(&[][..], |v| v);
+    let connected = peers
+        .iter()
+        .filter(|peer| peer["connection_status"] == "Connected")
+        .count();
+
+    println!("chain id:          {}", display(&status["chain_id"]));
+    println!("sync status:       {}", display(&sync["status"]));
+    println!("sync phase:        {}", display(&sync["phase"]));
+    println!(
+        "best tip:          {} (height: {}, slot: {})",
+        display(&best_tip["hash"]),
+        display(&best_tip["height"]),
+        display(&best_tip["global_slot"]),
+    );
+    if !sync["target"].is_null() {
+        println!("sync target:       {}", display(&sync["target"]["height"]));
+    }
+    println!(
+        "peers:             {connected} connected, {} known",
+        peers.len()
+    );
+    println!(
+        "transaction pool:  {}",
+        display(&status["transaction_pool"]["transactions"])
+    );
+    println!(
+        "snark pool:        {} jobs, {} snarks",
+        display(&status["snark_pool"]["total_jobs"]),
+        display(&status["snark_pool"]["snarks"]),
+    );
+}
+
+fn print_peers(peers: &[RpcPeerInfo]) {
+    for peer in peers {
+        let status = match peer.connection_status {
+            PeerConnectionStatus::Disconnected => "disconnected",
+            PeerConnectionStatus::Connecting => "connecting",
+            PeerConnectionStatus::Connected => "connected",
+        };
+        let best_tip = peer
+            .best_tip_height
+            .map_or_else(|| "-".to_owned(), |height| height.to_string());
+        println!(
+            "{}  {status:<12}  height: {best_tip:<8}  score: {:<4}  {}",
+            peer.peer_id,
+            peer.score,
+            peer.address.as_deref().unwrap_or("-"),
+        );
+    }
+    println!("{} peers", peers.len());
+}
+
+fn print_best_chain(blocks: &[Value]) {
+    for block in blocks {
+        println!(
+            "{:<8} slot: {:<8} {}  txs: {:<4} snarks: {:<4} producer: {}",
+            display(&block["height"]),
+            display(&block["global_slot"]),
+            display(&block["hash"]),
+            display(&block["transactions"]),
+            display(&block["completed_works"]),
+            display(&block["producer"]),
+        );
+    }
+}
+
+fn print_accounts(accounts: &[Account]) {
+    if accounts.is_empty() {
+        println!("account not found");
+    }
+    for account in accounts {
+        println!(
+            "token:   {}",
+            TokenIdKeyHash::from(account.token_id.clone())
+        );
+        println!("balance: {} nanomina", account.balance.as_u64());
+        println!("nonce:   {}", account.nonce.as_u32());
+    }
+}
+
+fn print_transaction_pool(transactions: &[ValidCommandWithHash]) {
+    for tx in transactions {
+        let cmd = &tx.data;
+        let kind = match cmd {
+            valid::UserCommand::SignedCommand(cmd)
+                if matches!(cmd.payload.body, signed_command::Body::Payment(_)) =>
+            {
+                "payment"
+            }
+            valid::UserCommand::SignedCommand(_) => "delegation",
+            valid::UserCommand::ZkAppCommand(_) => "zkapp",
+        };
+        let hash = MinaBaseUserCommandStableV2::from(&cmd.forget_check())
+            .hash()
+            .map_or_else(|_| "-".to_owned(), |hash| hash.to_string());
+        let nonce = cmd
+            .forget_check()
+            .applicable_at_nonce()
+            .as_u32()
+            .to_string();
+        println!(
+            "{hash}  {kind:<10}  sender: {}  nonce: {nonce:<6}  fee: {}",
+            AccountPublicKey::from(cmd.fee_payer().public_key),
+            cmd.fee().as_u64(),
+        );
+    }
+    println!("{} transactions", transactions.len());
+}
+
+fn print_snark_pool(jobs: &[Value]) {
+    let mut snarks = 0;
+    for job in jobs {
+        let snark = &job["snark"];
+        let work = if snark.is_null() {
+            let committed = !job["commitment"].is_null();
+            if committed { "committed" } else { "-" }.to_owned()
+        } else {
+            snarks += 1;
+            format!(
+                "snark by {} for fee {}",
+                display(&snark["snarker"]),
+                display(&snark["fee"]),
+            )
+        };
+        println!("{}  {work}", display(&job["id"]));
+    }
+    println!("{} jobs, {snarks} snarks", jobs.len());
+}
+
+fn print_scan_state(summary: &RpcScanStateSummary) {
+    let block = &summary.block;
+    println!(
+        "block:           {} (height: {}, slot: {})",
+        block.hash, block.height, block.global_slot
+    );
+    println!("transactions:    {}", block.transactions.len());
+    println!("completed works: {}", block.completed_works.len());
+    for (i, tree) in summary.scan_state.iter().enumerate() {
+        let (mut todo, mut pending, mut done) = (0, 0, 0);
+        for job in tree {
+            match job {
+                RpcScanStateSummaryScanStateJob::Empty => {}
+                RpcScanStateSummaryScanStateJob::Todo { .. } => todo += 1,
+                RpcScanStateSummaryScanStateJob::Pending { .. } => pending += 1,
+                RpcScanStateSummaryScanStateJob::Done { .. } => done += 1,
+            }
+        }
+        println!("tree {i:<2}  todo: {todo:<4}  pending: {pending:<4}  done: {done:<4}");
+    }
+}
+
+fn print_block_production_attempts(attempts: &[BlockProductionAttempt]) {
+    for attempt in attempts {
+        let status = serde_json::to_value(&attempt.status).unwrap_or_default();
+        let block = match &attempt.block {
+            None => "-".to_owned(),
+            Some(block) => {
+                let discarded = |f: fn(&StagedLedgerDiffCreationLog) -> u64| {
+                    block.diff_creation_log.iter().map(f).sum::<u64>()
+                };
+                format!(
+                    "{}  height: {:<8} txs: {:<4} discarded txs: {:<4} discarded snarks: {}",
+                    block.hash,
+                    block.height,
+                    block.transactions.payments
+                        + block.transactions.delegations
+                        + block.transactions.zkapps,
+                    discarded(StagedLedgerDiffCreationLog::discarded_commands),
+                    discarded(StagedLedgerDiffCreationLog::discarded_completed_works),
+                )
+            }
+        };
+        println!(
+            "slot: {:<8} {:<30} {block}",
+            attempt.won_slot.global_slot,
+            display(&status["status"]),
+        );
+    }
+    println!("{} attempts", attempts.len());
+}
+
+fn print_diff_creation_log(logs: &[StagedLedgerDiffCreationLog]) {
+    let resources = |r: &StagedLedgerDiffCreationResources| {
+        format!(
+            "txs: {:<4} (fees: {:<12}) snarks: {:<4} (fees: {:<12}) coinbase snark fees: {:?}",
+            r.commands,
+            r.commands_fees,
+            r.completed_works,
+            r.completed_works_fees,
+            r.coinbase_work_fees,
+        )
+    };
+    for log in logs {
+        let summary = &log.summary;
+        println!("partition:            {:?}", summary.partition);
+        println!("available slots:      {}", summary.available_slots);
+        println!("required work:        {}", summary.required_work_count);
+        println!(
+            "start:                {}",
+            resources(&summary.start_resources)
+        );
+        println!(
+            "end:                  {}",
+            resources(&summary.end_resources)
+        );
+        println!(
+            "discarded txs:        no work: {}, no space: {}",
+            summary.discarded_commands_no_work, summary.discarded_commands_no_space
+        );
+        println!(
+            "discarded snarks:     insufficient fees: {}, extra work: {}",
+            summary.discarded_completed_work_insufficient_fees,
+            summary.discarded_completed_work_extra_work
+        );
+        for line in &log.details {
+            let reason = format!("{:?}", line.reason);
+            println!("  {reason:<18} {}", resources(&line.resources));
+        }
+    }
+}
@@ -1,8 +1,22 @@
 pub mod build_info;
+pub mod client;
 pub mod misc;
 pub mod node;
 pub mod replay;
 pub mod snark;
+pub mod wallet;
+
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use ::node::daemon_json::DaemonJson;
+use ::node::p2p::connection::outgoing::P2pConnectionOutgoingInitOpts;
+use anyhow::Context;
+use openmina_core::{constants::ForkConstants, network::CustomNetworkConfig, NetworkConfig};
 
 #[derive(Debug, clap::Parser)]
 #[command(name = "openmina", about = "Openmina Cli")]
@@ -10,21 +24,69 @@ pub struct OpenminaCli {
     #[arg(
         global = true,
         long,
-        value_enum,
-        default_value_t = Network::Devnet,
+        default_value = "devnet",
         env = "OPENMINA_NETWORK"
     )]
-    /// Select the network (devnet or mainnet)
+    /// Select the network (devnet, mainnet or a path to the TOML or JSON
+    /// file with the config of a custom network)
     pub network: Network,
 
     #[command(subcommand)]
     pub command: Command,
 }
 
-#[derive(Debug, Clone, clap::ValueEnum)]
+#[derive(Debug, Clone)]
 pub enum Network {
     Devnet,
     Mainnet,
+    Custom(PathBuf),
+}
+
+impl FromStr for Network {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "devnet" => Self::Devnet,
+            "mainnet" => Self::Mainnet,
+            path => Self::Custom(path.into()),
+        })
+    }
+}
+
+impl Network {
+    /// Initializes the global network config. `fork` is where the chain
+    /// is restarted from, if the node runs a hard fork of the network.
+    pub fn init(&self, fork: Option<ForkConstants>) -> anyhow::Result<()> {
+        match self {
+            Self::Devnet => NetworkConfig::init("devnet", fork),
+            Self::Mainnet => NetworkConfig::init("mainnet", fork),
+            Self::Custom(path) => NetworkConfig::init_custom(Self::load_custom(path)?, fork),
+        }
+        .map_err(anyhow::Error::msg)
+    }
+
+    fn load_custom(path: &Path) -> anyhow::Result<CustomNetworkConfig> {
+        if !path.is_file() {
+            anyhow::bail!(
+                "unknown network {path:?}, expected devnet, mainnet or a path to the network config file"
+            );
+        }
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read network config {path:?}"))?;
+        let config: CustomNetworkConfig = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => toml::from_str(&content)
+                .with_context(|| format!("invalid network config {path:?}"))?,
+            Some("json") => serde_json::from_str(&content)
+                .with_context(|| format!("invalid network config {path:?}"))?,
+            _ => anyhow::bail!("network config {path:?} must be a .toml or .json file"),
+        };
+        for peer in &config.default_peers {
+            P2pConnectionOutgoingInitOpts::from_str(peer)
+                .map_err(|err| anyhow::anyhow!("invalid default peer {peer:?}: {err}"))?;
+        }
+        Ok(config)
+    }
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -37,9 +99,31 @@ pub enum Command {
     Misc(misc::Misc),
     Replay(replay::Replay),
     BuildInfo(build_info::Command),
+    /// Operate a running node.
+    Client(client::Client),
+    /// Offline wallet.
+    Wallet(wallet::Wallet),
 }
 
 impl Command {
+    /// Fork constants of the daemon config (`--config`) the command runs
+    /// with. Only they are read, the config is loaded by the command.
+    pub fn hard_fork(&self) -> anyhow::Result<Option<ForkConstants>> {
+        let config = match self {
+            Self::Node(v) => v.config.as_deref(),
+            Self::Replay(replay::Replay {
+                command: replay::ReplayCommand::Blocks(v),
+            }) => v.config.as_deref(),
+            _ => None,
+        };
+        let Some(path) = config else {
+            return Ok(None);
+        };
+        let reader = File::open(path).with_context(|| format!("config file {path:?}"))?;
+        DaemonJson::read_fork(BufReader::new(reader))
+            .with_context(|| format!("config file {path:?}"))
+    }
+
     pub fn run(self) -> anyhow::Result<()> {
         match self {
             Self::Snark(v) => v.run(),
@@ -47,6 +131,8 @@ impl Command {
             Self::Misc(v) => v.run(),
             Self::Replay(v) => v.run(),
             Self::BuildInfo(v) => v.run(),
+            Self::Client(v) => v.run(),
+            Self::Wallet(v) => v.run(),
         }
     }
 }
@@ -1,9 +1,10 @@
-use std::{fs::File, path::PathBuf, sync::Arc};
+use std::{fs::File, net::IpAddr, path::PathBuf, sync::Arc};
 
 use anyhow::Context;
 use ledger::proofs::provers::BlockProver;
 use node::{
     account::AccountSecretKey,
+    block_producer::TransactionSelectionPolicy,
     snark::{BlockVerifier, TransactionVerifier},
     transition_frontier::genesis::GenesisConfig,
 };
@@ -17,7 +18,7 @@ use node::p2p::identity::SecretKey;
 use node::service::Recorder;
 use node::SnarkerStrategy;
 
-use openmina_node_native::{tracing, NodeBuilder};
+use openmina_node_native::{tracing, NodeBuilder, SnarkWorkerBackend};
 
 /// Openmina node
 #[derive(Debug, clap::Args)]
@@ -88,11 +89,32 @@ pub struct Node {
     #[arg(long, env, default_value = "seq", requires = "snarker")]
     pub snarker_strategy: SnarkerStrategy,
 
+    /// Number of local snark worker processes.
+    #[arg(long, env, default_value_t = 1, requires = "snarker")]
+    pub snarker_workers: u16,
+
+    /// Implementation used by local snark workers: `external` runs the
+    /// OCaml snark worker processes, `rust` proves in-process.
+    #[arg(long, env, default_value = "external", requires = "snarker")]
+    pub snarker_backend: SnarkWorkerBackend,
+
+    /// Port on which remote snark workers can connect to get jobs,
+    /// using the OCaml snark worker protocol.
+    #[arg(long, env, requires = "snarker")]
+    pub snarker_remote_workers_port: Option<u16>,
+
+    /// Address on which remote snark workers can connect. Workers aren't
+    /// authenticated, so only bind to an address reachable by trusted
+    /// hosts.
+    #[arg(long, env, default_value = "127.0.0.1", requires = "snarker")]
+    pub snarker_remote_workers_host: IpAddr,
+
     /// Enable block producer with this key file
     ///
-    /// MINA_PRIVKEY_PASS must be set to decrypt the keyfile
-    #[arg(long, env, group = "producer")]
-    pub producer_key: Option<PathBuf>,
+    /// Can be repeated to produce blocks with several keys.
+    /// MINA_PRIVKEY_PASS must be set to decrypt the keyfiles
+    #[arg(long, env, group = "producer", value_delimiter = ',')]
+    pub producer_key: Vec<PathBuf>,
     #[arg(env = "MINA_PRIVKEY_PASS")]
     pub producer_key_password: Option<String>,
 
@@ -105,6 +127,38 @@ pub struct Node {
     #[arg(long, requires = "producer")]
     pub coinbase_receiver: Option<AccountPublicKey>,
 
+    /// JSON file with the policy used to select transactions for the
+    /// produced blocks. Options below override values from the file.
+    #[arg(long, env, requires = "producer")]
+    pub transaction_selection_config: Option<PathBuf>,
+
+    /// Maximum number of transactions included in a produced block.
+    #[arg(long, requires = "producer")]
+    pub max_block_transactions: Option<usize>,
+
+    /// Minimum fee (in nanomina) of transactions included in a produced block.
+    #[arg(long, requires = "producer")]
+    pub min_transaction_fee: Option<u64>,
+
+    /// Include transactions from this sender before any other transactions.
+    /// Can be repeated.
+    #[arg(long, requires = "producer")]
+    pub priority_sender: Vec<AccountPublicKey>,
+
+    /// Never include transactions from this sender. Can be repeated.
+    #[arg(long, requires = "producer")]
+    pub excluded_sender: Vec<AccountPublicKey>,
+
+    /// Maximum ratio (0.0 - 1.0) of zkApp commands to all transactions
+    /// included in a produced block.
+    #[arg(long, requires = "producer")]
+    pub max_zkapp_ratio: Option<f64>,
+
+    /// Maximum sum of the fees (in nanomina) paid for the snark work
+    /// included in a produced block.
+    #[arg(long, requires = "producer")]
+    pub snark_fee_budget: Option<u64>,
+
     #[arg(long, default_value = "none", env)]
     pub record: String,
 
@@ -112,6 +166,16 @@ pub struct Node {
     #[arg(long)]
     pub no_peers_discovery: bool,
 
+    /// Persist the transition frontier and ledgers in the work directory,
+    /// so that after a restart the node only needs to sync the delta.
+    #[arg(long, env)]
+    pub persistence: bool,
+
+    /// Archive blocks of the best chain, with their transactions and the
+    /// accounts they changed, into a database in the work directory.
+    #[arg(long, env)]
+    pub archive: bool,
+
     /// Config JSON file to load at startup.
     // TODO: make this argument required.
     #[arg(short = 'c', long, env)]
@@ -136,6 +200,9 @@ impl Node {
                 let reader = File::open(config).context("config file {config:?}")?;
                 let config: node::daemon_json::DaemonJson =
                     serde_json::from_reader(reader).context("config file {config:?}")?;
+                config
+                    .check_network_config(openmina_core::NetworkConfig::global())
+                    .context("config file doesn't match the network")?;
                 (
                     config
                         .daemon
@@ -144,6 +211,9 @@ impl Node {
                     Arc::new(GenesisConfig::DaemonJson(Box::new(config))),
                 )
             }
+            None if !openmina_core::NetworkConfig::global().is_builtin() => {
+                anyhow::bail!("config file (--config) is required for a custom network")
+            }
             None => (
                 node::daemon_json::Daemon::DEFAULT,
                 node::config::DEVNET_CONFIG.clone(),
@@ -212,27 +282,78 @@ impl Node {
             .block_verifier_index(block_verifier_index.clone())
             .work_verifier_index(work_verifier_index.clone());
 
-        if let (Some(producer_key_path), Some(pasword)) =
-            (self.producer_key, &self.producer_key_password)
+        if let Some(pasword) = self
+            .producer_key_password
+            .as_ref()
+            .filter(|_| !self.producer_key.is_empty())
         {
             node::core::info!(node::core::log::system_time(); summary = "loading provers index");
             let provers = BlockProver::make(Some(block_verifier_index), Some(work_verifier_index));
             node::core::info!(node::core::log::system_time(); summary = "loaded provers index");
-            node_builder.block_producer_from_file(provers, producer_key_path, pasword)?;
+            node_builder.block_producer_from_files(provers, &self.producer_key, pasword)?;
 
             if let Some(pub_key) = self.coinbase_receiver {
                 node_builder
                     .custom_coinbase_receiver(pub_key.into())
                     .unwrap();
             }
+
+            let mut policy = match &self.transaction_selection_config {
+                Some(path) => {
+                    let reader = File::open(path)
+                        .with_context(|| format!("transaction selection config {path:?}"))?;
+                    serde_json::from_reader(reader)
+                        .with_context(|| format!("transaction selection config {path:?}"))?
+                }
+                None => TransactionSelectionPolicy::default(),
+            };
+            if let Some(max) = self.max_block_transactions {
+                policy.max_transactions = Some(max);
+            }
+            if let Some(fee) = self.min_transaction_fee {
+                policy.min_fee = Some(fee);
+            }
+            policy
+                .priority_senders
+                .extend(self.priority_sender.into_iter().map(Into::into));
+            policy
+                .excluded_senders
+                .extend(self.excluded_sender.into_iter().map(Into::into));
+            if let Some(ratio) = self.max_zkapp_ratio {
+                policy.max_zkapp_ratio = Some(ratio);
+            }
+            if let Some(budget) = self.snark_fee_budget {
+                policy.snark_fee_budget = Some(budget);
+            }
+            node_builder.transaction_selection_policy(policy)?;
         }
 
         if let Some(sec_key) = self.run_snarker {
             node_builder.snarker(sec_key, self.snarker_fee, self.snarker_strategy);
+            node_builder.snarker_local_workers(self.snarker_workers)?;
+            node_builder.snarker_backend(self.snarker_backend)?;
+            if let Some(port) = self.snarker_remote_workers_port {
+                node_builder
+                    .snarker_remote_workers((self.snarker_remote_workers_host, port).into())?;
+            }
         }
 
         openmina_core::set_work_dir(work_dir.clone().into());
 
+        node_builder.p2p_ban_list(PathBuf::from(&work_dir).join("banned_peers.json"));
+        node_builder
+            .transaction_pool_persistence(PathBuf::from(&work_dir).join("local_transactions.json"));
+
+        if self.persistence {
+            let network = openmina_core::NetworkConfig::global().name;
+            node_builder.persistence(PathBuf::from(&work_dir).join("frontier").join(network));
+        }
+
+        if self.archive {
+            let network = openmina_core::NetworkConfig::global().name;
+            node_builder.archive(PathBuf::from(&work_dir).join("archive").join(network));
+        }
+
         node_builder
             .http_server(self.port)
             .gather_stats()
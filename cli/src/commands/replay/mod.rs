@@ -1,3 +1,6 @@
+pub mod replay_blocks;
+pub use replay_blocks::ReplayBlocks;
+
 pub mod replay_state_with_input_actions;
 pub use replay_state_with_input_actions::ReplayStateWithInputActions;
 
@@ -10,12 +13,14 @@ pub struct Replay {
 #[derive(Debug, clap::Subcommand)]
 pub enum ReplayCommand {
     StateWithInputActions(ReplayStateWithInputActions),
+    Blocks(ReplayBlocks),
 }
 
 impl Replay {
     pub fn run(self) -> anyhow::Result<()> {
         match self.command {
             ReplayCommand::StateWithInputActions(v) => v.run(),
+            ReplayCommand::Blocks(v) => v.run(),
         }
     }
 }
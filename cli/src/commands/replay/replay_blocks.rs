@@ -0,0 +1,623 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use anyhow::Context;
+use ledger::{
+    scan_state::{
+        currency::Slot,
+        transaction_logic::{
+            apply_transaction_first_pass, apply_transaction_second_pass,
+            protocol_state::protocol_state_view, Transaction, WithStatus,
+        },
+    },
+    staged_ledger::{
+        diff::Diff,
+        staged_ledger::{SkipVerification, StagedLedger},
+    },
+    verifier::Verifier,
+    Account, AccountId, BaseLedger, FpExt, Mask,
+};
+use mina_p2p_messages::{
+    binprot::BinProtRead,
+    list::List,
+    v2::{self, LedgerHash, MinaBaseStagedLedgerHashStableV1},
+};
+use node::{account::AccountPublicKey, daemon_json, transition_frontier::genesis::GenesisConfig};
+use openmina_core::{
+    block::{ArcBlockWithHash, BlockWithHash},
+    constants::{constraint_constants, PROTOCOL_VERSION},
+    NetworkConfig,
+};
+use serde_json::Value;
+
+#[derive(Debug, clap::Args)]
+/// Replay blocks on top of the genesis ledger, checking the ledger hashes
+/// of each block against the ones in its protocol state.
+///
+/// Blocks that aren't on the chain of the highest block are skipped.
+/// Proofs aren't verified.
+pub struct ReplayBlocks {
+    /// Directory with the blocks, binprot encoded (`.bin`) or in the
+    /// precomputed block format of the mina daemon (`.json`).
+    pub dir: PathBuf,
+
+    /// Daemon config (daemon.json) with the genesis ledger of the blocks.
+    #[arg(long, short)]
+    pub config: Option<PathBuf>,
+}
+
+impl ReplayBlocks {
+    pub fn run(self) -> anyhow::Result<()> {
+        let (mut masks, genesis) = genesis_config(self.config.as_deref())?
+            .load()
+            .context("failed to load the genesis ledger")?;
+        let (_, mut pred_state, genesis_hash) = genesis
+            .protocol_states()
+            .map_err(|err| anyhow::anyhow!("invalid genesis config: {err:?}"))?;
+
+        let blocks = read_blocks(&self.dir)?;
+        let found = blocks.len();
+        let chain = best_chain(blocks, &genesis_hash)?;
+        eprintln!(
+            "{found} blocks found, replaying {} of them from genesis {genesis_hash}",
+            chain.len()
+        );
+
+        let mut staged_ledger =
+            StagedLedger::create_exn(constraint_constants().clone(), masks.swap_remove(0))
+                .map_err(anyhow::Error::msg)?;
+        for block in &chain {
+            let pred_staged_ledger = staged_ledger.clone();
+            match apply_block(&mut staged_ledger, block, &pred_state) {
+                Ok(just_emitted_a_proof) => println!(
+                    "{} {}: ok{}",
+                    block.height(),
+                    block.hash(),
+                    if just_emitted_a_proof {
+                        ", ledger proof emitted"
+                    } else {
+                        ""
+                    }
+                ),
+                Err(err) => {
+                    println!("{} {}: {err:#}", block.height(), block.hash());
+                    report_failing_transaction(&pred_staged_ledger, block, &pred_state)?;
+                    anyhow::bail!("failed to replay block {}", block.hash());
+                }
+            }
+            // Keeps the chain of masks from growing with each block.
+            staged_ledger.commit_and_reparent_to_root();
+            pred_state = block.header().protocol_state.clone();
+        }
+
+        Ok(())
+    }
+}
+
+fn genesis_config(path: Option<&Path>) -> anyhow::Result<Arc<GenesisConfig>> {
+    let Some(path) = path else {
+        if !NetworkConfig::global().is_builtin() {
+            anyhow::bail!("config file (--config) is required for a custom network");
+        }
+        return Ok(node::config::DEVNET_CONFIG.clone());
+    };
+    let reader = File::open(path).with_context(|| format!("config file {path:?}"))?;
+    let config: daemon_json::DaemonJson =
+        serde_json::from_reader(reader).with_context(|| format!("config file {path:?}"))?;
+    config
+        .check_network_config(NetworkConfig::global())
+        .context("config file doesn't match the network")?;
+    Ok(Arc::new(GenesisConfig::DaemonJson(Box::new(config))))
+}
+
+fn read_blocks(dir: &Path) -> anyhow::Result<Vec<ArcBlockWithHash>> {
+    let mut blocks = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("failed to read {dir:?}"))? {
+        let path = entry?.path();
+        let block = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("bin") => {
+                v2::MinaBlockBlockStableV2::binprot_read(&mut fs::read(&path)?.as_slice())
+                    .map_err(anyhow::Error::from)
+            }
+            Some("json") => read_precomputed_block(&path),
+            _ => continue,
+        }
+        .with_context(|| format!("invalid block {path:?}"))?;
+        let block = BlockWithHash::try_new(Arc::new(block))
+            .map_err(|err| anyhow::anyhow!("invalid block {path:?}: {err:?}"))?;
+        blocks.push(block);
+    }
+    Ok(blocks)
+}
+
+/// Reads the parts of a precomputed block needed to apply it. Its proof
+/// isn't, the dummy one is used instead.
+fn read_precomputed_block(path: &Path) -> anyhow::Result<v2::MinaBlockBlockStableV2> {
+    let mut value = serde_json::from_slice::<Value>(&fs::read(path)?)?;
+    // Blocks exported by the daemon are wrapped with their version.
+    if let Some(data) = value.get_mut("data") {
+        value = data.take();
+    }
+    let mut field = |name: &str| value.get_mut(name).map_or(Value::Null, Value::take);
+    let protocol_state: v2::MinaStateProtocolStateValueStableV2 =
+        serde_json::from_value(field("protocol_state")).context("protocol_state")?;
+    let staged_ledger_diff =
+        serde_json::from_value(field("staged_ledger_diff")).context("staged_ledger_diff")?;
+
+    Ok(v2::MinaBlockBlockStableV2 {
+        header: v2::MinaBlockHeaderStableV2 {
+            delta_block_chain_proof: (protocol_state.previous_state_hash.clone(), List::new()),
+            protocol_state,
+            protocol_state_proof: (*v2::dummy_blockchain_proof()).clone(),
+            current_protocol_version: PROTOCOL_VERSION.clone(),
+            proposed_protocol_version_opt: None,
+        },
+        body: v2::StagedLedgerDiffBodyStableV1 { staged_ledger_diff },
+    })
+}
+
+/// Blocks from the one after genesis to the highest block.
+fn best_chain(
+    blocks: Vec<ArcBlockWithHash>,
+    genesis_hash: &v2::StateHash,
+) -> anyhow::Result<Vec<ArcBlockWithHash>> {
+    let Some(best_tip) = blocks.iter().max_by_key(|block| block.height()).cloned() else {
+        anyhow::bail!("no blocks found");
+    };
+    let mut blocks = blocks
+        .into_iter()
+        .map(|block| (block.hash().clone(), block))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut chain = Vec::new();
+    let mut hash = best_tip.hash().clone();
+    while &hash != genesis_hash {
+        let block = blocks.remove(&hash).ok_or_else(|| {
+            anyhow::anyhow!("missing block {hash}, blocks must start right after genesis")
+        })?;
+        hash = block.pred_hash().clone();
+        chain.push(block);
+    }
+    chain.reverse();
+    Ok(chain)
+}
+
+/// Applies the block on top of `staged_ledger` and checks the resulting
+/// hashes. Returns whether a ledger proof was emitted.
+fn apply_block(
+    staged_ledger: &mut StagedLedger,
+    block: &ArcBlockWithHash,
+    pred_state: &v2::MinaStateProtocolStateValueStableV2,
+) -> anyhow::Result<bool> {
+    let prev_state_view = protocol_state_view(pred_state).map_err(invalid)?;
+    let prev_protocol_state: ledger::proofs::block::ProtocolState =
+        pred_state.try_into().map_err(invalid)?;
+    let consensus_state = &block.header().protocol_state.body.consensus_state;
+    let diff: Diff = (&block.body().staged_ledger_diff)
+        .try_into()
+        .map_err(invalid)?;
+
+    let result = staged_ledger
+        .apply(
+            Some(SkipVerification::All),
+            constraint_constants(),
+            Slot::from_u32(block.global_slot_since_genesis()),
+            diff,
+            (),
+            &Verifier,
+            &prev_state_view,
+            prev_protocol_state.hashes(),
+            (&consensus_state.coinbase_receiver)
+                .try_into()
+                .map_err(invalid)?,
+            consensus_state.supercharge_coinbase,
+        )
+        .map_err(|err| anyhow::anyhow!("failed to apply the staged ledger diff: {err:?}"))?;
+
+    let hashes = MinaBaseStagedLedgerHashStableV1::from(&result.hash_after_applying);
+    let expected = block.staged_ledger_hashes();
+    let snarked_ledger_hash = match &result.ledger_proof {
+        Some((proof, _)) => LedgerHash::from_fp(proof.statement().target.first_pass_ledger),
+        None => pred_state
+            .body
+            .blockchain_state
+            .ledger_proof_statement
+            .target
+            .first_pass_ledger
+            .clone(),
+    };
+
+    let mismatches = [
+        mismatch(
+            "staged ledger hash",
+            &hashes.non_snark.ledger_hash,
+            &expected.non_snark.ledger_hash,
+        ),
+        mismatch(
+            "scan state hash",
+            &hashes.non_snark.aux_hash,
+            &expected.non_snark.aux_hash,
+        ),
+        mismatch(
+            "pending coinbase aux",
+            &hashes.non_snark.pending_coinbase_aux,
+            &expected.non_snark.pending_coinbase_aux,
+        ),
+        mismatch(
+            "pending coinbase hash",
+            &hashes.pending_coinbase_hash,
+            &expected.pending_coinbase_hash,
+        ),
+        mismatch(
+            "snarked ledger hash",
+            &snarked_ledger_hash,
+            block.snarked_ledger_hash(),
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    if !mismatches.is_empty() {
+        anyhow::bail!("{}", mismatches.join(", "));
+    }
+    Ok(result.ledger_proof.is_some())
+}
+
+fn mismatch<T: PartialEq + std::fmt::Display>(
+    name: &str,
+    found: &T,
+    expected: &T,
+) -> Option<String> {
+    (found != expected).then(|| format!("{name} mismatch, found: {found}, expected: {expected}"))
+}
+
+/// Applies the transactions of the block one by one on top of the ledger
+/// of its predecessor and prints the first one that doesn't end with the
+/// status recorded in the block, with the accounts it references before
+/// and after it was applied.
+fn report_failing_transaction(
+    pred_staged_ledger: &StagedLedger,
+    block: &ArcBlockWithHash,
+    pred_state: &v2::MinaStateProtocolStateValueStableV2,
+) -> anyhow::Result<()> {
+    let constraint_constants = constraint_constants();
+    let txn_state_view = protocol_state_view(pred_state).map_err(invalid)?;
+    let global_slot = Slot::from_u32(block.global_slot_since_genesis());
+    let consensus_state = &block.header().protocol_state.body.consensus_state;
+    let diff: Diff = (&block.body().staged_ledger_diff)
+        .try_into()
+        .map_err(invalid)?;
+    let transactions = diff
+        .get_transactions(
+            constraint_constants,
+            (&consensus_state.coinbase_receiver)
+                .try_into()
+                .map_err(invalid)?,
+            consensus_state.supercharge_coinbase,
+        )
+        .map_err(|err| anyhow::anyhow!("failed to get the transactions: {err:?}"))?;
+
+    let mut ledger = pred_staged_ledger.ledger().make_child();
+    // As in the staged ledger, the first pass is applied to all the
+    // transactions before the second one.
+    let mut partially_applied = Vec::with_capacity(transactions.len());
+    for (index, transaction) in transactions.iter().enumerate() {
+        let ids = transaction.data.accounts_referenced();
+        let before = accounts(&ledger, &ids);
+        match apply_transaction_first_pass(
+            constraint_constants,
+            global_slot,
+            &txn_state_view,
+            &mut ledger,
+            &transaction.data,
+        ) {
+            Ok(partial) => partially_applied.push((partial, ids, before)),
+            Err(err) => {
+                let after = accounts(&ledger, &ids);
+                let error = format!("first pass failed: {err}");
+                return print_transaction(index, transaction, &error, &ids, &before, &after);
+            }
+        }
+    }
+    for (index, (transaction, (partial, ids, before))) in
+        transactions.iter().zip(partially_applied).enumerate()
+    {
+        let error = match apply_transaction_second_pass(constraint_constants, &mut ledger, partial)
+        {
+            Ok(applied) if applied.transaction_status() == &transaction.status => continue,
+            Ok(applied) => format!(
+                "status: {:?}, expected: {:?}",
+                applied.transaction_status(),
+                transaction.status
+            ),
+            Err(err) => format!("second pass failed: {err}"),
+        };
+        let after = accounts(&ledger, &ids);
+        return print_transaction(index, transaction, &error, &ids, &before, &after);
+    }
+
+    println!(
+        "all the {} transactions applied with the status recorded in the block, ledger hash: {}",
+        transactions.len(),
+        LedgerHash::from_fp(ledger.merkle_root())
+    );
+    Ok(())
+}
+
+fn accounts(ledger: &Mask, ids: &[AccountId]) -> Vec<Option<Box<Account>>> {
+    ids.iter()
+        .map(|id| {
+            ledger
+                .location_of_account(id)
+                .and_then(|addr| ledger.get(addr))
+        })
+        .collect()
+}
+
+fn print_transaction(
+    index: usize,
+    transaction: &WithStatus<Transaction>,
+    error: &str,
+    ids: &[AccountId],
+    before: &[Option<Box<Account>>],
+    after: &[Option<Box<Account>>],
+) -> anyhow::Result<()> {
+    let kind = match &transaction.data {
+        Transaction::Command(command) => {
+            match v2::MinaBaseUserCommandStableV2::from(command).hash() {
+                Ok(hash) => format!("user command {hash}"),
+                Err(_) => "user command".to_owned(),
+            }
+        }
+        Transaction::FeeTransfer(_) => "fee transfer".to_owned(),
+        Transaction::Coinbase(_) => "coinbase".to_owned(),
+    };
+    println!("transaction #{index} ({kind}) failed, {error}");
+
+    // Accounts are compared in the daemon.json format.
+    let fields = |account: &Account| match serde_json::to_value(daemon_json::Account::from(account))
+    {
+        Ok(Value::Object(fields)) => Ok(fields),
+        Ok(_) => unreachable!("accounts are serialized as objects"),
+        Err(err) => Err(err),
+    };
+    for ((id, before), after) in ids.iter().zip(before).zip(after) {
+        let name = format!(
+            "{} (token: {})",
+            AccountPublicKey::from(id.public_key.clone()),
+            id.token_id.0.to_decimal()
+        );
+        let (before, after) = match (before, after) {
+            (Some(before), Some(after)) => (fields(before)?, fields(after)?),
+            (None, Some(_)) => {
+                println!("+ {name}");
+                continue;
+            }
+            (Some(_), None) => {
+                println!("- {name}");
+                continue;
+            }
+            (None, None) => {
+                println!("  {name} doesn't exist");
+                continue;
+            }
+        };
+        let changed = before
+            .iter()
+            .filter(|(field, value)| after.get(*field) != Some(*value))
+            .map(|(field, _)| field.as_str())
+            .chain(
+                after
+                    .keys()
+                    .filter(|field| !before.contains_key(*field))
+                    .map(String::as_str),
+            )
+            .collect::<Vec<_>>();
+        if changed.is_empty() {
+            println!("  {name} unchanged");
+            continue;
+        }
+        println!("~ {name}");
+        for field in changed {
+            let value = |fields: &serde_json::Map<String, Value>| {
+                fields
+                    .get(field)
+                    .map_or("none".to_owned(), Value::to_string)
+            };
+            println!("    {field}: {} -> {}", value(&before), value(&after));
+        }
+    }
+    Ok(())
+}
+
+fn invalid<E: std::fmt::Debug>(err: E) -> anyhow::Error {
+    anyhow::anyhow!("invalid block: {err:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::{
+        proofs::block::ProtocolState, staged_ledger::diff::with_valid_signatures_and_proofs,
+    };
+    use mina_p2p_messages::binprot::BinProtWrite;
+    use node::account::AccountSecretKey;
+
+    use super::*;
+
+    /// Genesis config with a few accounts. Tests use different balances,
+    /// so that they don't share the cached genesis ledger.
+    fn write_config(dir: &Path, balance: &str) -> PathBuf {
+        let accounts = (0..4)
+            .map(|i| {
+                serde_json::json!({
+                    "pk": AccountSecretKey::deterministic(i).public_key().to_string(),
+                    "balance": balance,
+                })
+            })
+            .collect::<Vec<_>>();
+        let config = serde_json::json!({ "ledger": { "accounts": accounts } });
+        let path = dir.join("daemon.json");
+        fs::write(&path, serde_json::to_vec(&config).unwrap()).unwrap();
+        path
+    }
+
+    /// Records a chain of blocks with empty diffs on top of the genesis of
+    /// the config, with the hashes resulting from applying them.
+    fn record_chain(config: &Path, len: usize) -> Vec<v2::MinaBlockBlockStableV2> {
+        let (mut masks, genesis) = genesis_config(Some(config)).unwrap().load().unwrap();
+        let (_, mut pred_state, mut pred_hash) = genesis.protocol_states().unwrap();
+        let mut staged_ledger =
+            StagedLedger::create_exn(constraint_constants().clone(), masks.swap_remove(0)).unwrap();
+        let body: v2::StagedLedgerDiffDiffStableV2 =
+            (&with_valid_signatures_and_proofs::Diff::empty()).into();
+
+        let mut blocks = Vec::with_capacity(len);
+        for _ in 0..len {
+            let mut protocol_state = pred_state.clone();
+            protocol_state.previous_state_hash = pred_hash;
+            let consensus_state = &mut protocol_state.body.consensus_state;
+            consensus_state.blockchain_length =
+                (consensus_state.blockchain_length.as_u32() + 1).into();
+            let v2::MinaNumbersGlobalSlotSinceHardForkMStableV1::SinceHardFork(slot) =
+                &mut consensus_state.curr_global_slot_since_hard_fork.slot_number;
+            *slot = (slot.as_u32() + 1).into();
+            let v2::MinaNumbersGlobalSlotSinceGenesisMStableV1::SinceGenesis(global_slot) =
+                &mut consensus_state.global_slot_since_genesis;
+            *global_slot = (global_slot.as_u32() + 1).into();
+            let global_slot = Slot::from_u32(global_slot.as_u32());
+
+            let pred_protocol_state: ProtocolState = (&pred_state).try_into().unwrap();
+            let result = staged_ledger
+                .apply(
+                    Some(SkipVerification::All),
+                    constraint_constants(),
+                    global_slot,
+                    (&body).try_into().unwrap(),
+                    (),
+                    &Verifier,
+                    &protocol_state_view(&pred_state).unwrap(),
+                    pred_protocol_state.hashes(),
+                    (&consensus_state.coinbase_receiver).try_into().unwrap(),
+                    consensus_state.supercharge_coinbase,
+                )
+                .unwrap();
+            staged_ledger.commit_and_reparent_to_root();
+            protocol_state.body.blockchain_state.staged_ledger_hash =
+                (&result.hash_after_applying).into();
+
+            pred_hash = protocol_state.try_hash().unwrap();
+            pred_state = protocol_state.clone();
+            blocks.push(v2::MinaBlockBlockStableV2 {
+                header: v2::MinaBlockHeaderStableV2 {
+                    delta_block_chain_proof: (
+                        protocol_state.previous_state_hash.clone(),
+                        List::new(),
+                    ),
+                    protocol_state,
+                    protocol_state_proof: (*v2::dummy_blockchain_proof()).clone(),
+                    current_protocol_version: PROTOCOL_VERSION.clone(),
+                    proposed_protocol_version_opt: None,
+                },
+                body: v2::StagedLedgerDiffBodyStableV1 {
+                    staged_ledger_diff: body.clone(),
+                },
+            });
+        }
+        blocks
+    }
+
+    /// Writes the first block in the precomputed format, the rest as
+    /// binprot.
+    fn write_blocks(dir: &Path, blocks: &[v2::MinaBlockBlockStableV2]) {
+        fs::create_dir_all(dir).unwrap();
+        for (i, block) in blocks.iter().enumerate() {
+            if i == 0 {
+                let precomputed = serde_json::json!({
+                    "version": 3,
+                    "data": {
+                        "protocol_state": block.header.protocol_state,
+                        "staged_ledger_diff": block.body.staged_ledger_diff,
+                    },
+                });
+                let data = serde_json::to_vec(&precomputed).unwrap();
+                fs::write(dir.join(format!("{i}.json")), data).unwrap();
+            } else {
+                let mut data = Vec::new();
+                block.binprot_write(&mut data).unwrap();
+                fs::write(dir.join(format!("{i}.bin")), data).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn test_replay_blocks() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let config = write_config(dir, "1000");
+        write_blocks(&dir.join("blocks"), &record_chain(&config, 3));
+
+        let replay = ReplayBlocks {
+            dir: dir.join("blocks"),
+            config: Some(config),
+        };
+        replay.run().unwrap();
+    }
+
+    #[test]
+    fn test_replay_blocks_wrong_ledger_hash() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let config = write_config(dir, "2000");
+        let mut blocks = record_chain(&config, 3);
+        // The tip claims a staged ledger other than the one its diff
+        // results in.
+        let tip = &mut blocks[2].header.protocol_state;
+        tip.body
+            .blockchain_state
+            .staged_ledger_hash
+            .non_snark
+            .ledger_hash = LedgerHash::zero();
+        let tip_hash = tip.try_hash().unwrap();
+        write_blocks(&dir.join("blocks"), &blocks);
+
+        let replay = ReplayBlocks {
+            dir: dir.join("blocks"),
+            config: Some(config),
+        };
+        let err = replay.run().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("failed to replay block {tip_hash}")
+        );
+    }
+
+    #[test]
+    fn test_best_chain() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let config = write_config(dir, "3000");
+        let (_, genesis) = genesis_config(Some(&config)).unwrap().load().unwrap();
+        let (_, _, genesis_hash) = genesis.protocol_states().unwrap();
+        let blocks = record_chain(&config, 3)
+            .into_iter()
+            .map(|block| BlockWithHash::try_new(Arc::new(block)).unwrap())
+            .collect::<Vec<_>>();
+
+        let mut shuffled = blocks.clone();
+        shuffled.reverse();
+        let chain = best_chain(shuffled, &genesis_hash).unwrap();
+        let hashes = |blocks: &[ArcBlockWithHash]| {
+            blocks.iter().map(|b| b.hash().clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(hashes(&chain), hashes(&blocks));
+
+        // a gap in the chain.
+        let gap = vec![blocks[0].clone(), blocks[2].clone()];
+        assert!(best_chain(gap, &genesis_hash).is_err());
+        assert!(best_chain(Vec::new(), &genesis_hash).is_err());
+    }
+}
@@ -0,0 +1,228 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use anyhow::Context;
+use ledger::scan_state::{
+    currency::{Amount, Fee, Nonce, Slot},
+    transaction_logic::{
+        signed_command::{
+            Body, PaymentPayload, SignedCommand, SignedCommandPayload, StakeDelegationPayload,
+        },
+        Memo,
+    },
+};
+use mina_p2p_messages::v2::MinaBaseUserCommandStableV2;
+use node::account::AccountSecretKey;
+use openmina_node_account::AccountPublicKey;
+use reqwest::Url;
+
+/// Build and sign payments and delegations offline, and submit them to a node.
+#[derive(Debug, clap::Args)]
+pub struct Wallet {
+    #[command(subcommand)]
+    pub command: WalletCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum WalletCommand {
+    /// Create and sign a payment.
+    Payment(Payment),
+    /// Create and sign a stake delegation.
+    Delegation(Delegation),
+    /// Submit the signed command exported by `payment` or `delegation`.
+    Submit(Submit),
+}
+
+impl Wallet {
+    pub fn run(self) -> anyhow::Result<()> {
+        match self.command {
+            WalletCommand::Payment(v) => v.run(),
+            WalletCommand::Delegation(v) => v.run(),
+            WalletCommand::Submit(v) => v.run(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Base58,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct SignArgs {
+    /// Encrypted secret key file of the sender.
+    #[arg(long, short = 'k', env = "OPENMINA_WALLET_KEY")]
+    pub key: PathBuf,
+
+    /// Password of the key file. Asked for if not set.
+    #[arg(long, env = "MINA_PRIVKEY_PASS", hide_env_values = true)]
+    pub password: Option<String>,
+
+    /// Fee in nanomina.
+    #[arg(long)]
+    pub fee: u64,
+
+    /// Nonce of the sender's account.
+    #[arg(long)]
+    pub nonce: u32,
+
+    /// Memo, up to 32 bytes.
+    #[arg(long, default_value = "")]
+    pub memo: String,
+
+    /// Global slot since genesis until which the command is valid.
+    #[arg(long)]
+    pub valid_until: Option<u32>,
+
+    #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+    pub format: ExportFormat,
+
+    /// File to export the signed command to, instead of stdout.
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Submit the signed command to the node's HTTP server at this address.
+    #[arg(long)]
+    pub submit: Option<Url>,
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Payment {
+    #[command(flatten)]
+    pub sign: SignArgs,
+
+    /// Receiver of the payment.
+    #[arg(long)]
+    pub to: AccountPublicKey,
+
+    /// Amount in nanomina.
+    #[arg(long)]
+    pub amount: u64,
+}
+
+impl Payment {
+    pub fn run(self) -> anyhow::Result<()> {
+        let receiver_pk = self.to.try_into().map_err(|_| invalid_public_key())?;
+        self.sign.sign_and_export(Body::Payment(PaymentPayload {
+            receiver_pk,
+            amount: Amount::from_u64(self.amount),
+        }))
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Delegation {
+    #[command(flatten)]
+    pub sign: SignArgs,
+
+    /// New delegate.
+    #[arg(long)]
+    pub to: AccountPublicKey,
+}
+
+impl Delegation {
+    pub fn run(self) -> anyhow::Result<()> {
+        let new_delegate = self.to.try_into().map_err(|_| invalid_public_key())?;
+        self.sign
+            .sign_and_export(Body::StakeDelegation(StakeDelegationPayload::SetDelegate {
+                new_delegate,
+            }))
+    }
+}
+
+#[derive(Debug, clap::Args)]
+pub struct Submit {
+    /// File with the signed command, either JSON or base58.
+    pub file: PathBuf,
+
+    /// Address of the node's HTTP server.
+    #[arg(
+        long,
+        short,
+        env = "OPENMINA_NODE_URL",
+        default_value = "http://127.0.0.1:3000"
+    )]
+    pub node: Url,
+}
+
+impl Submit {
+    pub fn run(self) -> anyhow::Result<()> {
+        let content = fs::read_to_string(&self.file)
+            .with_context(|| format!("failed to read {:?}", self.file))?;
+        let content = content.trim();
+        let command = if content.starts_with('{') {
+            serde_json::from_str(content).context("invalid JSON command")?
+        } else {
+            MinaBaseUserCommandStableV2::from_base58check(content)
+                .context("invalid base58 command")?
+        };
+        submit(&self.node, command)
+    }
+}
+
+impl SignArgs {
+    fn sign_and_export(self, body: Body) -> anyhow::Result<()> {
+        let password = match self.password {
+            Some(password) => password,
+            None => dialoguer::Password::new()
+                .with_prompt("Key file password")
+                .interact()?,
+        };
+        let secret_key = AccountSecretKey::from_encrypted_file(&self.key, &password)
+            .context("failed to decrypt secret key file")?;
+
+        if self.memo.len() > Memo::DIGEST_LENGTH {
+            anyhow::bail!("memo can't be longer than {} bytes", Memo::DIGEST_LENGTH);
+        }
+        let memo = Memo::from_str(&self.memo).map_err(|_| anyhow::anyhow!("invalid memo"))?;
+
+        let payload = SignedCommandPayload::create(
+            Fee::from_u64(self.fee),
+            secret_key.public_key_compressed(),
+            Nonce::from_u32(self.nonce),
+            self.valid_until.map(Slot::from_u32),
+            memo,
+            body,
+        );
+        let command = SignedCommand::sign(&secret_key.into(), payload);
+        let command = MinaBaseUserCommandStableV2::SignedCommand((&command).into());
+
+        let exported = match self.format {
+            ExportFormat::Json => serde_json::to_string_pretty(&command)?,
+            ExportFormat::Base58 => command.to_base58check(),
+        };
+        match &self.output {
+            Some(path) => fs::write(path, exported + "\n")
+                .with_context(|| format!("failed to write {path:?}"))?,
+            None => println!("{exported}"),
+        }
+        if let Ok(hash) = command.hash() {
+            eprintln!("transaction hash: {hash}");
+        }
+
+        match &self.submit {
+            Some(node) => submit(node, command),
+            None => Ok(()),
+        }
+    }
+}
+
+fn invalid_public_key() -> anyhow::Error {
+    anyhow::anyhow!("invalid public key")
+}
+
+fn submit(node: &Url, command: MinaBaseUserCommandStableV2) -> anyhow::Result<()> {
+    let url = node.join("send-user-commands")?;
+    let response = reqwest::blocking::Client::new()
+        .post(url.clone())
+        .json(&[command])
+        .send()?;
+    let status = response.status();
+    let body = response.text()?;
+    if !status.is_success() {
+        anyhow::bail!("request to {url} failed with {status}: {body}");
+    }
+    let response: serde_json::Value = serde_json::from_str(&body)?;
+    eprintln!("{}", serde_json::to_string_pretty(&response)?);
+    Ok(())
+}
@@ -43,12 +43,10 @@ fn main() -> anyhow::Result<()> {
     unsafe_signal_handlers::setup();
     let app = commands::OpenminaCli::parse();
 
-    let network_init_result = match app.network {
-        commands::Network::Devnet => openmina_core::NetworkConfig::init("devnet"),
-        commands::Network::Mainnet => openmina_core::NetworkConfig::init("mainnet"),
-    };
-
-    network_init_result.expect("Failed to initialize network configuration");
+    let fork = app.command.hard_fork()?;
+    app.network
+        .init(fork)
+        .expect("Failed to initialize network configuration");
 
     app.command.run()
 }
@@ -3,8 +3,6 @@ use mina_p2p_messages::v2;
 use redux::Timestamp;
 use serde::{Deserialize, Serialize};
 
-use crate::constants::constraint_constants;
-
 use super::{Block, BlockHash, BlockHeader};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -113,12 +111,11 @@ impl<T: AsRef<Block>> BlockWithHash<T> {
         staged_ledger_hashes(self.header())
     }
 
+    /// Whether the block is the genesis block of the chain, or of the chain
+    /// restarted from a hard fork. It's the only block in the slot 0 since
+    /// the hard fork.
     pub fn is_genesis(&self) -> bool {
-        self.height() == 1
-            || constraint_constants()
-                .fork
-                .as_ref()
-                .map_or(false, |fork| fork.blockchain_length + 1 == self.height())
+        self.curr_global_slot_since_hard_fork().slot_number.as_u32() == 0
     }
 
     pub fn root_block_height(&self) -> u32 {
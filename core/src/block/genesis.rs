@@ -1,10 +1,13 @@
 use ark_ff::fields::arithmetic::InvalidBigInt;
 use mina_p2p_messages::v2::{self, StateHash};
 
-use crate::constants::{constraint_constants, slots_per_window};
+use crate::constants::{constraint_constants, slots_per_window, ForkConstants};
 
+/// Protocol states of the genesis block and of the one before it. If
+/// `fork` is set, genesis block continues the chain it was forked from.
 #[allow(clippy::too_many_arguments)]
 pub fn genesis_and_negative_one_protocol_states(
+    fork: Option<&ForkConstants>,
     constants: v2::MinaBaseProtocolConstantsCheckedValueStableV1,
     genesis_ledger_hash: v2::LedgerHash,
     genesis_total_currency: v2::CurrencyAmountStableV1,
@@ -29,6 +32,7 @@ pub fn genesis_and_negative_one_protocol_states(
     InvalidBigInt,
 > {
     let negative_one = protocol_state(
+        fork,
         constants.clone(),
         genesis_ledger_hash.clone(),
         genesis_total_currency.clone(),
@@ -46,6 +50,7 @@ pub fn genesis_and_negative_one_protocol_states(
     );
     let negative_one_hash = negative_one.try_hash()?;
     let mut genesis = protocol_state(
+        fork,
         constants,
         genesis_ledger_hash,
         genesis_total_currency,
@@ -61,7 +66,7 @@ pub fn genesis_and_negative_one_protocol_states(
         updated_next_epoch_seed.clone(),
         false,
     );
-    if constraint_constants().fork.is_none() {
+    if fork.is_none() {
         genesis.previous_state_hash = negative_one_hash.clone();
     }
     genesis.body.genesis_state_hash = negative_one_hash.clone();
@@ -80,6 +85,7 @@ pub fn genesis_and_negative_one_protocol_states(
 
 #[allow(clippy::too_many_arguments)]
 fn protocol_state(
+    fork: Option<&ForkConstants>,
     constants: v2::MinaBaseProtocolConstantsCheckedValueStableV1,
     genesis_ledger_hash: v2::LedgerHash,
     genesis_total_currency: v2::CurrencyAmountStableV1,
@@ -96,7 +102,7 @@ fn protocol_state(
     negative_one: bool,
 ) -> v2::MinaStateProtocolStateValueStableV2 {
     v2::MinaStateProtocolStateValueStableV2 {
-        previous_state_hash: match constraint_constants().fork.as_ref() {
+        previous_state_hash: match fork {
             None => StateHash::zero(),
             Some(_) if negative_one => StateHash::zero(),
             Some(fork) => StateHash::from_fp(fork.state_hash),
@@ -111,6 +117,7 @@ fn protocol_state(
                 empty_body_hash,
             ),
             consensus_state: consensus_state(
+                fork,
                 &constants,
                 genesis_total_currency,
                 staking_epoch_ledger_hash,
@@ -174,6 +181,7 @@ fn blockchain_state(
 
 #[allow(clippy::too_many_arguments)]
 fn consensus_state(
+    fork: Option<&ForkConstants>,
     constants: &v2::MinaBaseProtocolConstantsCheckedValueStableV1,
     genesis_total_currency: v2::CurrencyAmountStableV1,
     staking_epoch_ledger_hash: v2::LedgerHash,
@@ -186,8 +194,7 @@ fn consensus_state(
     negative_one: bool,
 ) -> v2::ConsensusProofOfStakeDataConsensusStateValueStableV2 {
     let is_genesis = if negative_one { 0 } else { 1 };
-    let (blockchain_length, global_slot_since_genesis) = match constraint_constants().fork.as_ref()
-    {
+    let (blockchain_length, global_slot_since_genesis) = match fork {
         None => (is_genesis, 0),
         Some(fork) => (
             fork.blockchain_length + is_genesis,
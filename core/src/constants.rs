@@ -1,6 +1,7 @@
 use binprot_derive::BinProtWrite;
 use mina_hasher::Fp;
 use mina_p2p_messages::{bigint, number, v2};
+use serde::{Deserialize, Serialize};
 
 pub const GENESIS_PRODUCER_SK: &str = "EKFKgDtU3rcuFTVSEpmpXSkukjmX4cKefYREi6Sdsk7E7wsT7KRw";
 
@@ -14,14 +15,15 @@ pub fn constraint_constants() -> &'static ConstraintConstants {
     NetworkConfig::global().constraint_constants
 }
 
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ForkConstants {
+    #[serde(with = "fork_state_hash")]
     pub state_hash: Fp,
     pub blockchain_length: u32,
     pub global_slot_since_genesis: u32,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct ConstraintConstants {
     pub sub_windows_per_window: u64,
     pub ledger_depth: u64,
@@ -34,6 +36,25 @@ pub struct ConstraintConstants {
     pub account_creation_fee: u64,
     pub fork: Option<ForkConstants>,
 }
+
+/// Fork state hash is (de)serialized as base58check, the same way as in
+/// the daemon.json.
+mod fork_state_hash {
+    use mina_hasher::Fp;
+    use mina_p2p_messages::v2::StateHash;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(state_hash: &Fp, serializer: S) -> Result<S::Ok, S::Error> {
+        StateHash::from_fp(*state_hash).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Fp, D::Error> {
+        StateHash::deserialize(deserializer)?
+            .to_field()
+            .map_err(|_| D::Error::custom("invalid fork state hash"))
+    }
+}
+
 #[derive(Clone, Debug, BinProtWrite)]
 pub struct ForkConstantsUnversioned {
     previous_state_hash: bigint::BigInt,
@@ -1,9 +1,11 @@
 use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
 
-use crate::constants::ConstraintConstants;
+use crate::constants::{ConstraintConstants, ForkConstants};
 
 // From mina-signer, to avoid dependency
-#[derive(Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum NetworkId {
     /// Id for all testnets
     TESTNET = 0x00,
@@ -38,6 +40,41 @@ pub struct CircuitsConfig {
     pub step_transaction_proved_gates: &'static str,
 }
 
+/// Configuration of a network other than devnet and mainnet, e.g. a
+/// lightnet or a private testnet, loaded from a TOML or JSON file.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomNetworkConfig {
+    pub name: String,
+    pub network_id: NetworkId,
+    /// Domain string of the signatures, up to 20 bytes.
+    pub signature_prefix: String,
+    /// Domain string of the account update hashes, up to 20 bytes.
+    pub account_update_hash_param: String,
+    /// Hex encoded digests of the transaction-merge, transaction-base and
+    /// blockchain-step constraint systems.
+    pub constraint_system_digests: [String; 3],
+    #[serde(default)]
+    pub default_peers: Vec<String>,
+    pub circuits: CustomCircuitsConfig,
+    pub constraint_constants: ConstraintConstants,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CustomCircuitsConfig {
+    /// Directory with the circuit blobs. Can be an absolute path, relative
+    /// paths are looked up in the same places as for devnet and mainnet.
+    pub directory_name: String,
+
+    pub step_transaction_gates: String,
+    pub wrap_transaction_gates: String,
+    pub step_merge_gates: String,
+    pub step_blockchain_gates: String,
+    pub wrap_blockchain_gates: String,
+    pub step_transaction_opt_signed_opt_signed_gates: String,
+    pub step_transaction_opt_signed_gates: String,
+    pub step_transaction_proved_gates: String,
+}
+
 static CONFIG: OnceCell<NetworkConfig> = OnceCell::new();
 
 impl NetworkConfig {
@@ -54,13 +91,26 @@ impl NetworkConfig {
         })
     }
 
-    pub fn init(network_name: &str) -> Result<(), String> {
+    /// Initializes the network, restarted from a hard fork at `fork` if
+    /// it's set.
+    pub fn init(network_name: &str, fork: Option<ForkConstants>) -> Result<(), String> {
         let config = match network_name {
             "devnet" => Self::devnet_config(),
             "mainnet" => Self::mainnet_config(),
             other => Err(format!("Unknown network {other}"))?,
         };
 
+        Self::set(config.with_fork(fork))
+    }
+
+    pub fn init_custom(
+        config: CustomNetworkConfig,
+        fork: Option<ForkConstants>,
+    ) -> Result<(), String> {
+        Self::set(Self::custom_config(config)?.with_fork(fork))
+    }
+
+    fn set(config: Self) -> Result<(), String> {
         CONFIG
             .set(config)
             .map_err(|_| "Double network configuration initialization".to_owned())?;
@@ -68,6 +118,28 @@ impl NetworkConfig {
         Ok(())
     }
 
+    /// Config of the network restarted from a hard fork at `fork`. The
+    /// genesis block and the proofs depend on the fork constants, so the
+    /// network must be forked before it's initialized.
+    fn with_fork(self, fork: Option<ForkConstants>) -> Self {
+        let Some(fork) = fork else {
+            return self;
+        };
+        let constraint_constants = ConstraintConstants {
+            fork: Some(fork),
+            ..self.constraint_constants.clone()
+        };
+        Self {
+            constraint_constants: Box::leak(Box::new(constraint_constants)),
+            ..self
+        }
+    }
+
+    /// Whether the network is devnet or mainnet.
+    pub fn is_builtin(&self) -> bool {
+        [devnet::NAME, mainnet::NAME].contains(&self.name)
+    }
+
     fn default_config() -> Self {
         Self::devnet_config()
     }
@@ -97,6 +169,64 @@ impl NetworkConfig {
             constraint_constants: &devnet::CONSTRAINT_CONSTANTS,
         }
     }
+
+    /// Builtin configs are made of constants, so the custom one is leaked
+    /// to live as long as them.
+    fn custom_config(config: CustomNetworkConfig) -> Result<Self, String> {
+        fn leak(s: String) -> &'static str {
+            Box::leak(s.into_boxed_str())
+        }
+
+        if config.name.is_empty() {
+            return Err("Network name can't be empty".to_owned());
+        }
+        for (field, value) in [
+            ("signature_prefix", &config.signature_prefix),
+            (
+                "account_update_hash_param",
+                &config.account_update_hash_param,
+            ),
+        ] {
+            if value.len() > 20 {
+                return Err(format!("{field} can't be longer than 20 bytes: {value:?}"));
+            }
+        }
+
+        let mut constraint_system_digests = [[0; 16]; 3];
+        for (digest, hex_digest) in constraint_system_digests
+            .iter_mut()
+            .zip(&config.constraint_system_digests)
+        {
+            hex::decode_to_slice(hex_digest, digest)
+                .map_err(|err| format!("Invalid constraint system digest {hex_digest:?}: {err}"))?;
+        }
+
+        let circuits = config.circuits;
+        let circuits_config = CircuitsConfig {
+            directory_name: leak(circuits.directory_name),
+            step_transaction_gates: leak(circuits.step_transaction_gates),
+            wrap_transaction_gates: leak(circuits.wrap_transaction_gates),
+            step_merge_gates: leak(circuits.step_merge_gates),
+            step_blockchain_gates: leak(circuits.step_blockchain_gates),
+            wrap_blockchain_gates: leak(circuits.wrap_blockchain_gates),
+            step_transaction_opt_signed_opt_signed_gates: leak(
+                circuits.step_transaction_opt_signed_opt_signed_gates,
+            ),
+            step_transaction_opt_signed_gates: leak(circuits.step_transaction_opt_signed_gates),
+            step_transaction_proved_gates: leak(circuits.step_transaction_proved_gates),
+        };
+
+        Ok(Self {
+            name: leak(config.name),
+            network_id: config.network_id,
+            signature_prefix: leak(config.signature_prefix),
+            account_update_hash_param: leak(config.account_update_hash_param),
+            constraint_system_digests: Box::leak(Box::new(constraint_system_digests)),
+            default_peers: config.default_peers.into_iter().map(leak).collect(),
+            circuits_config: Box::leak(Box::new(circuits_config)),
+            constraint_constants: Box::leak(Box::new(config.constraint_constants)),
+        })
+    }
 }
 
 // Network constants
@@ -294,3 +424,107 @@ pub mod mainnet {
         ]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_config_json() -> serde_json::Value {
+        serde_json::json!({
+            "name": "lightnet",
+            "network_id": "testnet",
+            "signature_prefix": "CodaSignature",
+            "account_update_hash_param": "TestnetZkappBody",
+            "constraint_system_digests": [
+                "b8879f677f622a1d86648030701f43e1",
+                "3bf6bb8a97665fe7a9df6fc146e4f942",
+                "d024a9ac78d4c93a888b63fc85eeb66a",
+            ],
+            "default_peers": [
+                "/dns4/seed.lightnet/tcp/8302/p2p/12D3KooWAdgYL6hv18M3iDBdaK1dRygPivSfAfBNDzie6YqydVbs",
+            ],
+            "circuits": {
+                "directory_name": "/opt/lightnet/circuit-blobs",
+                "step_transaction_gates": "step-transaction",
+                "wrap_transaction_gates": "wrap-transaction",
+                "step_merge_gates": "step-merge",
+                "step_blockchain_gates": "step-blockchain",
+                "wrap_blockchain_gates": "wrap-blockchain",
+                "step_transaction_opt_signed_opt_signed_gates": "step-opt-signed-opt-signed",
+                "step_transaction_opt_signed_gates": "step-opt-signed",
+                "step_transaction_proved_gates": "step-proved",
+            },
+            "constraint_constants": {
+                "sub_windows_per_window": 11,
+                "ledger_depth": 35,
+                "work_delay": 2,
+                "block_window_duration_ms": 20000,
+                "transaction_capacity_log_2": 7,
+                "pending_coinbase_depth": 5,
+                "coinbase_amount": 720000000000u64,
+                "supercharged_coinbase_factor": 1,
+                "account_creation_fee": 1000000000,
+                "fork": null,
+            },
+        })
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let config: CustomNetworkConfig = serde_json::from_value(custom_config_json()).unwrap();
+        let config = NetworkConfig::custom_config(config).unwrap();
+
+        assert_eq!(config.name, "lightnet");
+        assert!(!config.is_builtin());
+        assert_eq!(config.network_id, NetworkId::TESTNET);
+        assert_eq!(
+            config.constraint_system_digests,
+            &devnet::CONSTRAINT_SYSTEM_DIGESTS
+        );
+        assert_eq!(config.default_peers.len(), 1);
+        assert_eq!(
+            config.circuits_config.directory_name,
+            "/opt/lightnet/circuit-blobs"
+        );
+        assert_eq!(config.constraint_constants.block_window_duration_ms, 20000);
+    }
+
+    #[test]
+    fn test_with_fork() {
+        let config = NetworkConfig::devnet_config().with_fork(None);
+        assert_eq!(
+            config.constraint_constants.fork,
+            devnet::CONSTRAINT_CONSTANTS.fork
+        );
+
+        let fork = ForkConstants {
+            state_hash: 1u64.into(),
+            blockchain_length: 100,
+            global_slot_since_genesis: 150,
+        };
+        let config = NetworkConfig::devnet_config().with_fork(Some(fork.clone()));
+        assert_eq!(config.constraint_constants.fork, Some(fork));
+        assert_eq!(
+            config.constraint_constants.ledger_depth,
+            devnet::CONSTRAINT_CONSTANTS.ledger_depth
+        );
+        // the builtin network itself isn't forked.
+        assert_ne!(
+            devnet::CONSTRAINT_CONSTANTS.fork,
+            config.constraint_constants.fork
+        );
+    }
+
+    #[test]
+    fn test_custom_config_invalid() {
+        let mut json = custom_config_json();
+        json["signature_prefix"] = "TooLongSignaturePrefix".into();
+        let config: CustomNetworkConfig = serde_json::from_value(json).unwrap();
+        assert!(NetworkConfig::custom_config(config).is_err());
+
+        let mut json = custom_config_json();
+        json["constraint_system_digests"][1] = "b8879f".into();
+        let config: CustomNetworkConfig = serde_json::from_value(json).unwrap();
+        assert!(NetworkConfig::custom_config(config).is_err());
+    }
+}
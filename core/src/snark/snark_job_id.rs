@@ -2,8 +2,10 @@ use std::str::FromStr;
 
 use mina_p2p_messages::binprot::macros::{BinProtRead, BinProtWrite};
 use mina_p2p_messages::v2::{
-    LedgerHash, MinaStateBlockchainStateValueStableV2LedgerProofStatementSource,
-    TransactionSnarkWorkTStableV2Proofs,
+    LedgerHash, MinaStateBlockchainStateValueStableV2LedgerProofStatement,
+    MinaStateBlockchainStateValueStableV2LedgerProofStatementSource,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single, TransactionSnarkWorkTStableV2Proofs,
 };
 use serde::{ser::SerializeStruct, Deserialize, Serialize};
 
@@ -114,6 +116,28 @@ impl From<&TransactionSnarkWorkTStableV2Proofs> for SnarkJobId {
     }
 }
 
+impl From<&SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances> for SnarkJobId {
+    fn from(value: &SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances) -> Self {
+        use SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances as Instances;
+        use SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single as Single;
+
+        fn statement(
+            single: &Single,
+        ) -> &MinaStateBlockchainStateValueStableV2LedgerProofStatement {
+            match single {
+                Single::Transition(statement, _) => &statement.0,
+                Single::Merge(merge) => &merge.0 .0,
+            }
+        }
+        let (first, second) = match value {
+            Instances::One(j) => (j, j),
+            Instances::Two((j1, j2)) => (j1, j2),
+        };
+
+        (&statement(first).source, &statement(second).target).into()
+    }
+}
+
 impl
     From<(
         &MinaStateBlockchainStateValueStableV2LedgerProofStatementSource,
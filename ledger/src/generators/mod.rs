@@ -51,8 +51,6 @@ const MAX_TOKEN_UPDATES: usize = 2;
 /// Value when we run `dune runtest src/lib/staged_ledger -f`
 const ACCOUNT_CREATION_FEE: Fee = Fee::from_u64(1000000000);
 
-const MINIMUM_USER_COMMAND_FEE: Fee = Fee::from_u64(1000000);
-
 /// Value of `ledger_depth` when we run `dune runtest src/lib/staged_ledger -f`
 ///
 /// https://github.com/MinaProtocol/mina/blob/3753a8593cc1577bcf4da16620daf9946d88e8e5/src/lib/mina_generators/user_command_generators.ml#L15
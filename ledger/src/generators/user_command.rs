@@ -11,6 +11,7 @@ use crate::{
             for_tests::HashableCompressedPubKey,
             valid,
             zkapp_command::{self, verifiable},
+            MINIMUM_USER_COMMAND_FEE,
         },
     },
     util, Account, AccountId, AuthRequired, BaseLedger, Mask, MyCowMut, Permissions, TokenId,
@@ -19,7 +20,7 @@ use crate::{
 
 use super::{
     zkapp_command::GenZkappCommandParams, Failure, Role, LEDGER_DEPTH, MAX_ACCOUNT_UPDATES,
-    MAX_TOKEN_UPDATES, MINIMUM_USER_COMMAND_FEE,
+    MAX_TOKEN_UPDATES,
 };
 
 fn zkapp_command_with_ledger(
@@ -31,7 +31,7 @@ use crate::{
                 ClosedInterval, Control, FeePayer, FeePayerBody, MayUseToken, Numeric, OrIgnore,
                 Preconditions, SetOrKeep, Update, WithStackHash, ZkAppCommand, ZkAppPreconditions,
             },
-            Memo,
+            Memo, MINIMUM_USER_COMMAND_FEE,
         },
     },
     zkapps::{
@@ -1204,9 +1204,6 @@ fn gen_account_update_from(params: AccountUpdateParams) -> AccountUpdateSimple {
     }
 }
 
-/// Value of `Mina_compile_config.minimum_user_command_fee` when we run `dune runtest src/lib/staged_ledger -f`
-const MINIMUM_USER_COMMAND_FEE: Fee = Fee::from_u64(1000000);
-
 fn gen_fee(account: &Account) -> Fee {
     let mut rng = rand::thread_rng();
 
@@ -87,6 +87,14 @@ pub fn fetch(filename: &impl AsRef<Path>) -> std::io::Result<Vec<u8>> {
         return std::fs::read(path);
     }
 
+    // Circuit-blobs of custom networks are not released on github.
+    if filename.as_ref().is_absolute() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("circuit-blobs not found at {:?}", filename.as_ref()),
+        ));
+    }
+
     openmina_core::info!(
         openmina_core::log::system_time();
         kind = "ledger proofs",
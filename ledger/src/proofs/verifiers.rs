@@ -58,6 +58,33 @@ impl std::fmt::Display for Kind {
     }
 }
 
+/// Source verifier index of a custom network, read from its circuits
+/// directory.
+#[cfg(not(target_family = "wasm"))]
+fn custom_src_json(kind: Kind) -> &'static str {
+    static BLOCK_VERIFIER_SRC: OnceCell<String> = OnceCell::new();
+    static TX_VERIFIER_SRC: OnceCell<String> = OnceCell::new();
+
+    let src = match kind {
+        Kind::BlockVerifier => &BLOCK_VERIFIER_SRC,
+        Kind::TransactionVerifier => &TX_VERIFIER_SRC,
+    };
+    src.get_or_init(|| {
+        let circuits_config = openmina_core::NetworkConfig::global().circuits_config;
+        let path = Path::new(circuits_config.directory_name).join(format!("{kind}.json"));
+        let bytes = super::circuit_blobs::fetch(&path)
+            .unwrap_or_else(|err| panic!("get_verifier_index: cannot read {path:?}: {err}"));
+        String::from_utf8(bytes)
+            .unwrap_or_else(|err| panic!("get_verifier_index: invalid {path:?}: {err}"))
+    })
+}
+
+#[cfg(target_family = "wasm")]
+fn custom_src_json(kind: Kind) -> &'static str {
+    let network_name = openmina_core::NetworkConfig::global().name;
+    panic!("get_verifier_index: {kind} of custom network '{network_name}' is not supported")
+}
+
 fn cache_filename(kind: Kind) -> PathBuf {
     let circuits_config = openmina_core::NetworkConfig::global().circuits_config;
     Path::new(circuits_config.directory_name).join(kind.filename())
@@ -191,7 +218,7 @@ impl BlockVerifier {
         match network_name {
             "mainnet" => include_str!("data/mainnet_blockchain_verifier_index.json"),
             "devnet" => include_str!("data/devnet_blockchain_verifier_index.json"),
-            other => panic!("get_verifier_index: unknown network '{other}'"),
+            _ => custom_src_json(Self::kind()),
         }
     }
 }
@@ -206,7 +233,7 @@ impl TransactionVerifier {
         match network_name {
             "mainnet" => include_str!("data/mainnet_transaction_verifier_index.json"),
             "devnet" => include_str!("data/devnet_transaction_verifier_index.json"),
-            other => panic!("get_verifier_index: unknown network '{other}'"),
+            _ => custom_src_json(Self::kind()),
         }
     }
 
@@ -462,7 +462,7 @@ macro_rules! impl_number {
         impl $name {
             pub const NBITS: usize = <$inner>::BITS as usize;
 
-            pub fn $as_name(&self) -> $inner {
+            pub const fn $as_name(&self) -> $inner {
                 self.0
             }
 
@@ -488,41 +488,52 @@ macro_rules! impl_number {
 
             /// https://github.com/MinaProtocol/mina/blob/2ff0292b637684ce0372e7b8e23ec85404dc5091/src/lib/currency/currency.ml#L124
             pub fn of_mina_string_exn(input: &str) -> Self {
+                Self::of_mina_string(input)
+                    .expect("Currency.of_mina_string_exn: Invalid currency input")
+            }
+
+            /// Parses a decimal amount of mina, e.g. `"1.5"`. Digits past the
+            /// precision are dropped. `None` if the input isn't valid.
+            pub fn of_mina_string(input: &str) -> Option<Self> {
                 const PRECISION: usize = 9;
 
-                let mut s = String::with_capacity(input.len() + 9);
-
-                if !input.contains('.') {
-                    let append = "000000000";
-                    assert_eq!(append.len(), PRECISION);
-
-                    s.push_str(input);
-                    s.push_str(append);
-                } else {
-                    let (whole, decimal) = {
-                        let mut splitted = input.split('.');
-                        let whole = splitted.next().unwrap();
-                        let decimal = splitted.next().unwrap();
-                        assert!(splitted.next().is_none(), "Currency.of_mina_string_exn: Invalid currency input");
-                        (whole, decimal)
-                    };
-
-                    let decimal_length = decimal.len();
-
-                    if decimal_length > PRECISION {
-                        s.push_str(whole);
-                        s.push_str(&decimal[0..PRECISION]);
-                    } else {
-                        s.push_str(whole);
-                        s.push_str(decimal);
-                        for _ in 0..PRECISION - decimal_length {
-                            s.push('0');
-                        }
-                    }
+                let (whole, decimal) = match input.split_once('.') {
+                    Some((whole, decimal)) => (whole, decimal),
+                    None => (input, ""),
+                };
+                let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+                if (whole.is_empty() && decimal.is_empty()) || !is_digits(whole) || !is_digits(decimal) {
+                    return None;
                 }
+                let decimal = &decimal[..decimal.len().min(PRECISION)];
 
-                let n = s.parse::<$inner>().unwrap();
-                Self(n)
+                let mut s = String::with_capacity(whole.len() + PRECISION);
+                s.push_str(whole);
+                s.push_str(decimal);
+                for _ in decimal.len()..PRECISION {
+                    s.push('0');
+                }
+                s.parse::<$inner>().ok().map(Self)
+            }
+
+            /// https://github.com/MinaProtocol/mina/blob/2ff0292b637684ce0372e7b8e23ec85404dc5091/src/lib/currency/currency.ml#L106
+            pub fn to_mina_string(&self) -> String {
+                const PRECISION: u32 = 9;
+                const PRECISION_EXP: $inner = (10 as $inner).pow(PRECISION);
+
+                let whole = self.0 / PRECISION_EXP;
+                let mut remainder = self.0 % PRECISION_EXP;
+                if remainder == 0 {
+                    return whole.to_string();
+                }
+
+                let mut num_stripped_zeros = 0;
+                while remainder % 10 == 0 {
+                    remainder /= 10;
+                    num_stripped_zeros += 1;
+                }
+                let width = (PRECISION - num_stripped_zeros) as usize;
+                format!("{whole}.{remainder:0width$}")
             }
 
             pub fn to_bits(&self) -> [bool; <$inner>::BITS as usize] {
@@ -577,3 +588,50 @@ impl_number!(
     32: { Length, Slot, Nonce, Index, SlotSpan, TxnVersion, Epoch, },
     64: { Amount, Balance, Fee, BlockTime, BlockTimeSpan, N, },
 );
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(target_family = "wasm")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn test_to_mina_string() {
+        for (nanomina, expected) in [
+            (0, "0"),
+            (1, "0.000000001"),
+            (10, "0.00000001"),
+            (1_000_000_000, "1"),
+            (1_500_000_000, "1.5"),
+            (720_000_000_000, "720"),
+            (1_234_567_890, "1.23456789"),
+            (100_000_000_001, "100.000000001"),
+            (u64::MAX, "18446744073.709551615"),
+        ] {
+            let amount = Amount::from_u64(nanomina);
+            assert_eq!(amount.to_mina_string(), expected);
+            assert_eq!(Amount::of_mina_string(expected), Some(amount));
+        }
+    }
+
+    #[test]
+    fn test_of_mina_string() {
+        assert_eq!(
+            Amount::of_mina_string("1."),
+            Some(Amount::from_u64(1_000_000_000))
+        );
+        assert_eq!(
+            Amount::of_mina_string(".5"),
+            Some(Amount::from_u64(500_000_000))
+        );
+        // digits past the precision are dropped.
+        assert_eq!(
+            Amount::of_mina_string("0.0000000019"),
+            Some(Amount::from_u64(1))
+        );
+        for invalid in ["", ".", "1.2.3", "abc", "-1", "1e9", "18446744074"] {
+            assert_eq!(Amount::of_mina_string(invalid), None, "{invalid:?}");
+        }
+    }
+}
@@ -18,4 +18,11 @@ impl FeeRate {
             q: fraction::Fraction::new(fee.as_u64(), weight),
         }
     }
+
+    pub fn to_f64(&self) -> f64 {
+        match (self.q.numer(), self.q.denom()) {
+            (Some(numer), Some(denom)) if *denom != 0 => *numer as f64 / *denom as f64,
+            _ => 0.0,
+        }
+    }
 }
@@ -47,6 +47,9 @@ use super::{
 };
 use crate::zkapps::zkapp_logic::ZkAppCommandElt;
 
+/// `Mina_compile_config.minimum_user_command_fee`
+pub const MINIMUM_USER_COMMAND_FEE: Fee = Fee::from_u64(1000000);
+
 /// https://github.com/MinaProtocol/mina/blob/2ee6e004ba8c6a0541056076aab22ea162f7eb3a/src/lib/mina_base/transaction_status.ml#L9
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 pub enum TransactionFailure {
@@ -560,14 +563,13 @@ impl std::str::FromStr for Memo {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let length = std::cmp::min(s.len(), Self::DIGEST_LENGTH) as u8;
-        let mut memo: [u8; Self::MEMO_LENGTH] = std::array::from_fn(|i| (i == 0) as u8);
+        if s.len() > Self::DIGEST_LENGTH {
+            return Err(());
+        }
+        let mut memo: [u8; Self::MEMO_LENGTH] = [0; Self::MEMO_LENGTH];
         memo[Self::TAG_INDEX] = Self::BYTES_TAG;
-        memo[Self::LENGTH_INDEX] = length;
-        let padded = format!("{s:\0<32}");
-        memo[2..].copy_from_slice(
-            &padded.as_bytes()[..std::cmp::min(padded.len(), Self::DIGEST_LENGTH)],
-        );
+        memo[Self::LENGTH_INDEX] = s.len() as u8;
+        memo[2..2 + s.len()].copy_from_slice(s.as_bytes());
         Ok(Memo(memo))
     }
 }
@@ -594,7 +596,7 @@ impl Memo {
     const DIGEST_TAG: u8 = 0x00;
     const BYTES_TAG: u8 = 0x01;
 
-    const DIGEST_LENGTH: usize = 32; // Blake2.digest_size_in_bytes
+    pub const DIGEST_LENGTH: usize = 32; // Blake2.digest_size_in_bytes
     const DIGEST_LENGTH_BYTE: u8 = Self::DIGEST_LENGTH as u8;
 
     /// +2 for tag and length bytes
@@ -816,6 +818,21 @@ pub mod signed_command {
     }
 
     impl SignedCommand {
+        /// Signs the payload with the signature prefix of the configured
+        /// network.
+        pub fn sign(keypair: &mina_signer::Keypair, payload: SignedCommandPayload) -> Self {
+            let signature = signature::sign(
+                keypair,
+                &TransactionUnionPayload::of_user_command_payload(&payload),
+            );
+
+            Self {
+                payload,
+                signer: keypair.public.into_compressed(),
+                signature,
+            }
+        }
+
         pub fn valid_until(&self) -> Slot {
             self.payload.common.valid_until
         }
@@ -915,6 +932,185 @@ pub mod signed_command {
                 .all(|pk| decompress_pk(pk).is_some())
         }
     }
+
+    /// Legacy signatures of the signed commands.
+    ///
+    /// `mina_signer` only knows the signature prefixes of devnet and mainnet,
+    /// so for networks with another prefix the same scheme is implemented
+    /// here.
+    pub mod signature {
+        use mina_hasher::{DomainParameter, Hashable, ROInput};
+        use mina_signer::{Keypair, PubKey, Signature, Signer};
+        use openmina_core::{
+            network::{devnet, mainnet, NetworkId},
+            NetworkConfig,
+        };
+
+        use crate::scan_state::transaction_logic::transaction_union_payload::TransactionUnionPayload;
+
+        /// Domain of the signatures on a network. `mina_signer::NetworkId` only
+        /// knows the mainnet and testnet signature prefixes, so signers are
+        /// created with this domain instead, to support custom networks.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct SignatureDomain {
+            pub prefix: &'static str,
+            pub network_id: NetworkId,
+        }
+
+        impl SignatureDomain {
+            pub const DEVNET: Self = Self {
+                prefix: devnet::SIGNATURE_PREFIX,
+                network_id: NetworkId::TESTNET,
+            };
+            pub const MAINNET: Self = Self {
+                prefix: mainnet::SIGNATURE_PREFIX,
+                network_id: NetworkId::MAINNET,
+            };
+
+            /// Domain of the configured network.
+            pub fn global() -> Self {
+                let config = NetworkConfig::global();
+                Self {
+                    prefix: config.signature_prefix,
+                    network_id: config.network_id,
+                }
+            }
+        }
+
+        impl DomainParameter for SignatureDomain {
+            /// Same bytes as `mina_signer::NetworkId`, used when deriving the nonce.
+            fn into_bytes(self) -> Vec<u8> {
+                vec![self.network_id as u8]
+            }
+        }
+
+        #[derive(Clone)]
+        struct DomainPayload(TransactionUnionPayload);
+
+        impl Hashable for DomainPayload {
+            type D = SignatureDomain;
+
+            fn to_roinput(&self) -> ROInput {
+                self.0.to_roinput()
+            }
+
+            fn domain_string(domain: SignatureDomain) -> Option<String> {
+                Some(domain.prefix.to_owned())
+            }
+        }
+
+        /// Signs the payload for the configured network.
+        pub fn sign(keypair: &Keypair, payload: &TransactionUnionPayload) -> Signature {
+            sign_in_domain(SignatureDomain::global(), keypair, payload)
+        }
+
+        /// Verifies the signature of the payload for the configured network.
+        pub fn verify(
+            signature: &Signature,
+            pubkey: &PubKey,
+            payload: &TransactionUnionPayload,
+        ) -> bool {
+            verify_in_domain(SignatureDomain::global(), signature, pubkey, payload)
+        }
+
+        pub fn sign_in_domain(
+            domain: SignatureDomain,
+            keypair: &Keypair,
+            payload: &TransactionUnionPayload,
+        ) -> Signature {
+            mina_signer::create_legacy::<DomainPayload>(domain)
+                .sign(keypair, &DomainPayload(payload.clone()))
+        }
+
+        pub fn verify_in_domain(
+            domain: SignatureDomain,
+            signature: &Signature,
+            pubkey: &PubKey,
+            payload: &TransactionUnionPayload,
+        ) -> bool {
+            mina_signer::create_legacy::<DomainPayload>(domain).verify(
+                signature,
+                pubkey,
+                &DomainPayload(payload.clone()),
+            )
+        }
+
+        #[cfg(test)]
+        mod tests {
+            use std::str::FromStr;
+
+            use crate::scan_state::{
+                currency::{Amount, Fee, Nonce},
+                transaction_logic::{
+                    signed_command::{Body, PaymentPayload, SignedCommandPayload},
+                    Memo,
+                },
+            };
+
+            use super::*;
+
+            fn payload(keypair: &Keypair) -> TransactionUnionPayload {
+                let payload = SignedCommandPayload::create(
+                    Fee::from_u64(10_000_000),
+                    keypair.public.into_compressed(),
+                    Nonce::from_u32(1),
+                    None,
+                    Memo::from_str("memo").unwrap(),
+                    Body::Payment(PaymentPayload {
+                        receiver_pk: crate::gen_compressed(),
+                        amount: Amount::from_u64(1_000_000_000),
+                    }),
+                );
+                TransactionUnionPayload::of_user_command_payload(&payload)
+            }
+
+            #[test]
+            fn test_sign_in_domain_matches_mina_signer() {
+                let keypair = crate::gen_keypair();
+                let payload = payload(&keypair);
+
+                for (domain, network_id) in [
+                    (SignatureDomain::DEVNET, mina_signer::NetworkId::TESTNET),
+                    (SignatureDomain::MAINNET, mina_signer::NetworkId::MAINNET),
+                ] {
+                    let signature = sign_in_domain(domain, &keypair, &payload);
+                    let mut signer = mina_signer::create_legacy(network_id);
+                    assert_eq!(signature, signer.sign(&keypair, &payload));
+                    assert!(signer.verify(&signature, &keypair.public, &payload));
+                    assert!(verify_in_domain(
+                        domain,
+                        &signature,
+                        &keypair.public,
+                        &payload
+                    ));
+                }
+            }
+
+            #[test]
+            fn test_sign_in_custom_domain() {
+                let keypair = crate::gen_keypair();
+                let payload = payload(&keypair);
+                let domain = SignatureDomain {
+                    prefix: "LightnetSignature",
+                    network_id: NetworkId::TESTNET,
+                };
+
+                let signature = sign_in_domain(domain, &keypair, &payload);
+                assert!(verify_in_domain(
+                    domain,
+                    &signature,
+                    &keypair.public,
+                    &payload
+                ));
+                assert!(!verify_in_domain(
+                    SignatureDomain::DEVNET,
+                    &signature,
+                    &keypair.public,
+                    &payload
+                ));
+            }
+        }
+    }
 }
 
 pub mod zkapp_command {
@@ -4146,6 +4342,7 @@ pub mod zkapp_command {
 
 pub mod zkapp_statement {
     use super::{
+        signed_command::signature::SignatureDomain,
         zkapp_command::{CallForest, Tree, ACCOUNT_UPDATE_CONS_HASH_PARAM},
         *,
     };
@@ -4173,20 +4370,14 @@ pub mod zkapp_statement {
     }
 
     impl mina_hasher::Hashable for TransactionCommitment {
-        type D = mina_signer::NetworkId;
+        type D = SignatureDomain;
 
         fn to_roinput(&self) -> mina_hasher::ROInput {
             mina_hasher::ROInput::new().append_field(self.0)
         }
 
-        fn domain_string(network_id: mina_signer::NetworkId) -> Option<String> {
-            // Domain strings must have length <= 20
-            match network_id {
-                mina_signer::NetworkId::MAINNET => "MinaSignatureMainnet",
-                mina_signer::NetworkId::TESTNET => "CodaSignature",
-            }
-            .to_string()
-            .into()
+        fn domain_string(domain: SignatureDomain) -> Option<String> {
+            Some(domain.prefix.to_owned())
         }
     }
 
@@ -4282,13 +4473,7 @@ pub mod verifiable {
         let payload = TransactionUnionPayload::of_user_command_payload(payload);
         let pubkey = compressed_to_pubkey(pubkey);
 
-        let network_id = match openmina_core::NetworkConfig::global().network_id {
-            openmina_core::network::NetworkId::TESTNET => mina_signer::NetworkId::TESTNET,
-            openmina_core::network::NetworkId::MAINNET => mina_signer::NetworkId::MAINNET,
-        };
-        let mut signer = mina_signer::create_legacy(network_id);
-
-        if signer.verify(signature, &pubkey, &payload) {
+        if signed_command::signature::verify(signature, &pubkey, &payload) {
             Ok(valid::UserCommand::SignedCommand(cmd))
         } else {
             Err(cmd)
@@ -4533,8 +4718,6 @@ impl UserCommand {
     }
 
     fn has_insufficient_fee(&self) -> bool {
-        /// `minimum_user_command_fee`
-        const MINIMUM_USER_COMMAND_FEE: Fee = Fee::from_u64(1000000);
         self.fee() < MINIMUM_USER_COMMAND_FEE
     }
 
@@ -8230,4 +8413,42 @@ mod tests {
         let result = cons_signed_command_payload(&tx, prev_receipt_chain_hash);
         assert_eq!(result, next_receipt_chain_hash);
     }
+
+    #[test]
+    fn test_memo_from_str() {
+        let memo = Memo::from_str("memo").unwrap();
+        assert_eq!(memo.to_string(), "memo");
+
+        let max = "a".repeat(Memo::DIGEST_LENGTH);
+        assert_eq!(Memo::from_str(&max).unwrap().to_string(), max);
+
+        let too_long = "a".repeat(Memo::DIGEST_LENGTH + 1);
+        assert!(Memo::from_str(&too_long).is_err());
+    }
+
+    #[test]
+    fn test_signed_command_sign() {
+        let keypair = crate::gen_keypair();
+        let from = keypair.public.into_compressed();
+        let to = pub_key("B62qjVQLxt9nYMWGn45mkgwYfcz8e8jvjNCBo11VKJb7vxDNwv5QLPS");
+
+        let payload = SignedCommandPayload::create(
+            Fee::from_u64(10_000_000),
+            from,
+            Nonce::from_u32(3),
+            None,
+            Memo::from_str("memo").unwrap(),
+            Body::Payment(PaymentPayload {
+                receiver_pk: to,
+                amount: Amount::from_u64(1_000_000_000),
+            }),
+        );
+        let cmd = signed_command::SignedCommand::sign(&keypair, payload);
+
+        assert!(verifiable::check_only_for_signature(Box::new(cmd.clone())).is_ok());
+
+        let mut tampered = cmd;
+        tampered.payload.common.nonce = Nonce::from_u32(4);
+        assert!(verifiable::check_only_for_signature(Box::new(tampered)).is_err());
+    }
 }
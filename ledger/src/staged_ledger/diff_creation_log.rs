@@ -34,7 +34,7 @@ pub enum Partition {
     Second,
 }
 
-mod summary {
+pub mod summary {
 
     use super::*;
 
@@ -47,25 +47,25 @@ mod summary {
 
     #[derive(Clone, Debug)]
     pub struct CommandConstraints {
-        insufficient_work: u64,
-        insufficient_space: u64,
+        pub insufficient_work: u64,
+        pub insufficient_space: u64,
     }
 
     #[derive(Clone, Debug)]
     pub struct CompletedWorkConstraints {
-        insufficient_fees: u64,
-        extra_work: u64,
+        pub insufficient_fees: u64,
+        pub extra_work: u64,
     }
 
     #[derive(Clone, Debug)]
     pub struct Summary {
-        partition: Partition,
-        start_resources: Resources,
-        available_slots: u64,
-        required_work_count: u64,
-        discarded_commands: CommandConstraints,
-        discarded_completed_work: CompletedWorkConstraints,
-        end_resources: Resources,
+        pub partition: Partition,
+        pub start_resources: Resources,
+        pub available_slots: u64,
+        pub required_work_count: u64,
+        pub discarded_commands: CommandConstraints,
+        pub discarded_completed_work: CompletedWorkConstraints,
+        pub end_resources: Resources,
     }
 
     pub fn coinbase_fees(coinbase: &AtMostTwo<CoinbaseFeeTransfer>) -> AtMostTwo<FeeSummable> {
@@ -183,19 +183,19 @@ mod summary {
     }
 }
 
-mod detail {
+pub mod detail {
     use super::*;
 
     #[derive(Debug, Clone)]
-    struct Line {
-        reason: Reason,
-        commands: CountAndFee,
-        completed_work: CountAndFee,
-        coinbase: AtMostTwo<Fee>,
+    pub struct Line {
+        pub reason: Reason,
+        pub commands: CountAndFee,
+        pub completed_work: CountAndFee,
+        pub coinbase: AtMostTwo<Fee>,
     }
 
     #[derive(Clone, Debug)]
-    pub struct Detail(Vec<Line>);
+    pub struct Detail(pub Vec<Line>);
 
     impl Detail {
         pub fn init(
@@ -309,7 +309,7 @@ impl DiffCreationLog {
 
     pub fn discard_completed_work(&mut self, why: Reason, completed_work: &work::Unchecked) {
         self.detail.discard_completed_work(why, completed_work);
-        self.summary.discard_command(why);
+        self.summary.discard_completed_work(why);
     }
 
     pub fn end_log(
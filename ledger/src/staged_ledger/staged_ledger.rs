@@ -1788,6 +1788,7 @@ impl StagedLedger {
         (
             with_valid_signatures_and_proofs::Diff,
             Vec<(valid::UserCommand, String)>,
+            Vec<DiffCreationLog>,
         ),
         PreDiffError,
     >
@@ -1891,7 +1892,7 @@ impl StagedLedger {
 
             let _valid_on_this_ledger_len = valid_on_this_ledger.len();
 
-            let (diff, _log) = Self::generate(
+            let (diff, log) = Self::generate(
                 constraint_constants,
                 logger,
                 completed_works_seq,
@@ -1946,7 +1947,7 @@ impl StagedLedger {
 
             let diff = with_valid_signatures_and_proofs::Diff { diff };
 
-            Ok((diff, invalid_on_this_ledger))
+            Ok((diff, invalid_on_this_ledger, log))
         })
     }
 
@@ -2004,7 +2005,6 @@ mod tests_ocaml {
     use ark_ec::{AffineCurve, ProjectiveCurve};
     use ark_ff::Zero;
     use mina_curves::pasta::Fq;
-    use mina_signer::Signer;
     use mina_signer::{Keypair, Signature};
     use o1_utils::FieldHelpers;
     use once_cell::sync::Lazy;
@@ -2108,7 +2108,7 @@ mod tests_ocaml {
 
         let supercharge_coinbase = supercharge_coinbase(sl.ledger.clone(), winner, global_slot);
 
-        let (diff, _invalid_txns) = sl
+        let (diff, _invalid_txns, _log) = sl
             .create_diff(
                 &CONSTRAINT_CONSTANTS,
                 global_slot,
@@ -2377,9 +2377,7 @@ mod tests_ocaml {
                         let payload_to_sign =
                             TransactionUnionPayload::of_user_command_payload(&payload);
 
-                        let mut signer =
-                            mina_signer::create_legacy(mina_signer::NetworkId::TESTNET);
-                        signer.sign(sender_pk, &payload_to_sign)
+                        signed_command::signature::sign(sender_pk, &payload_to_sign)
                     }
                 };
 
@@ -4585,7 +4583,7 @@ mod tests_ocaml {
                     |_cmds_left, _count_opt, cmds_this_iter, _| {
                         let current_state_view = dummy_state_view(Some(global_slot));
 
-                        let (diff, _invalid_txns) = sl
+                        let (diff, _invalid_txns, _log) = sl
                             .create_diff(
                                 &CONSTRAINT_CONSTANTS,
                                 global_slot,
@@ -5575,8 +5573,7 @@ mod tests_ocaml {
 
         let payload_to_sign = TransactionUnionPayload::of_user_command_payload(&payload);
 
-        let mut signer = mina_signer::create_legacy(mina_signer::NetworkId::TESTNET);
-        let signature = signer.sign(kp, &payload_to_sign);
+        let signature = signed_command::signature::sign(kp, &payload_to_sign);
 
         let signed_command = SignedCommand {
             payload,
@@ -5602,7 +5599,7 @@ mod tests_ocaml {
             |_snarked_ledger, sl, _test_mask| {
                 let current_state_view = dummy_state_view(Some(global_slot));
 
-                let (diff, _invalid_txns) = sl
+                let (diff, _invalid_txns, _log) = sl
                     .create_diff(
                         &CONSTRAINT_CONSTANTS,
                         global_slot,
@@ -5671,8 +5668,7 @@ mod tests_ocaml {
 
             let payload_to_sign = TransactionUnionPayload::of_user_command_payload(&payload);
 
-            let mut signer = mina_signer::create_legacy(mina_signer::NetworkId::TESTNET);
-            let signature = signer.sign(&kp, &payload_to_sign);
+            let signature = signed_command::signature::sign(&kp, &payload_to_sign);
 
             let signed_command = SignedCommand {
                 payload,
@@ -5701,7 +5697,7 @@ mod tests_ocaml {
                 let (current_state, current_state_view) = dummy_state_and_view(Some(global_slot));
                 let state_and_body_hash = { hashes_abstract(&current_state) };
 
-                let (diff, _invalid_txns) = sl
+                let (diff, _invalid_txns, _log) = sl
                     .create_diff(
                         &CONSTRAINT_CONSTANTS,
                         global_slot,
@@ -1505,6 +1505,24 @@ impl IndexedPool {
             .collect()
     }
 
+    /// Minimum fees of a command replacing the sender's pending command at
+    /// `nonce`, `None` if there is no such command. The first one is enough
+    /// if the commands queued after it can be added back, the second one
+    /// also pays for them in case they get dropped.
+    fn replace_fee(&self, sender: &AccountId, nonce: Nonce) -> Option<(Fee, Fee)> {
+        let (queue, _) = self.all_by_sender.get(sender)?;
+        let index = queue
+            .iter()
+            .position(|cmd| cmd.data.forget_check().applicable_at_nonce() == nonce)?;
+        let mut fees = queue
+            .iter()
+            .skip(index)
+            .map(|cmd| cmd.data.forget_check().fee());
+        let min_fee = fees.next()?.checked_add(&REPLACE_FEE)?;
+        let min_fee_with_dependents = fees.try_fold(min_fee, |acc, fee| acc.checked_add(&fee))?;
+        Some((min_fee, min_fee_with_dependents))
+    }
+
     fn get_pending_amount_and_nonce(&self) -> HashMap<AccountId, (Option<Nonce>, Amount)> {
         // TODO(adonagy): clone too expensive here?
         self.all_by_sender
@@ -1645,6 +1663,15 @@ impl TransactionPool {
         self.pool.get_pending_amount_and_nonce()
     }
 
+    pub fn replace_fee(&self, sender: &AccountId, nonce: Nonce) -> Option<(Fee, Fee)> {
+        self.pool.replace_fee(sender, nonce)
+    }
+
+    /// Lowest fee per weight unit of the pooled commands.
+    pub fn min_fee_rate(&self) -> Option<FeeRate> {
+        self.pool.min_fee()
+    }
+
     pub fn transactions(&mut self, limit: usize) -> Vec<ValidCommandWithHash> {
         self.pool.transactions(limit)
     }
@@ -2225,35 +2252,23 @@ impl TransactionPool {
         }
     }
 
-    fn get_rebroadcastable<F>(&mut self, has_timed_out: F) -> Vec<Vec<UserCommand>>
+    pub fn locally_generated_uncommitted(&self) -> impl Iterator<Item = &ValidCommandWithHash> {
+        self.locally_generated_uncommitted.keys()
+    }
+
+    /// Locally generated commands which aren't yet included in the best chain,
+    /// grouped by batch.
+    ///
+    /// Commands added before `has_timed_out` are no longer tracked, whether they
+    /// were committed or not.
+    pub fn get_rebroadcastable<F>(&mut self, has_timed_out: F) -> Vec<Vec<UserCommand>>
     where
         F: Fn(&redux::Timestamp) -> bool,
     {
-        let log = |has_timed_out: bool, s: &str, cmd: &ValidCommandWithHash| -> bool {
-            if has_timed_out {
-                eprintln!("{}: {:?}", s, cmd);
-                false
-            } else {
-                true
-            }
-        };
-
         self.locally_generated_uncommitted
-            .retain(|key, (time, _batch)| {
-                log(
-                    has_timed_out(time),
-                    "No longer rebroadcasting uncommitted expired command",
-                    key,
-                )
-            });
+            .retain(|_, (time, _batch)| !has_timed_out(time));
         self.locally_generated_committed
-            .retain(|key, (time, _batch)| {
-                log(
-                    has_timed_out(time),
-                    "Removing committed locally generated expired command",
-                    key,
-                )
-            });
+            .retain(|_, (time, _batch)| !has_timed_out(time));
 
         let mut rebroadcastable_txs = self
             .locally_generated_uncommitted
@@ -2292,8 +2307,104 @@ impl TransactionPool {
 
 #[cfg(test)]
 mod tests {
+    use mina_signer::{CompressedPubKey, Signature};
+
+    use crate::scan_state::transaction_logic::{
+        signed_command::{self, SignedCommand, SignedCommandPayload},
+        Memo,
+    };
+
     use super::*;
 
+    fn indexed_pool() -> IndexedPool {
+        let protocol_constants = v2::MinaBaseProtocolConstantsCheckedValueStableV1 {
+            k: 290.into(),
+            slots_per_epoch: 7140.into(),
+            slots_per_sub_window: 7.into(),
+            grace_period_slots: 2160.into(),
+            delta: 0.into(),
+            genesis_state_timestamp: v2::BlockTimeTimeStableV1(
+                v2::UnsignedExtendedUInt64Int64ForVersionTagsStableV1(0.into()),
+            ),
+        };
+        let constants = ConsensusConstants::create(
+            openmina_core::constants::constraint_constants(),
+            &protocol_constants,
+        );
+        IndexedPool::new(&constants)
+    }
+
+    fn payment(sender: &CompressedPubKey, nonce: u32, fee: u64) -> ValidCommandWithHash {
+        let payload = SignedCommandPayload::create(
+            Fee::from_u64(fee),
+            sender.clone(),
+            Nonce::from_u32(nonce),
+            None,
+            Memo::empty(),
+            signed_command::Body::Payment(signed_command::PaymentPayload {
+                receiver_pk: sender.clone(),
+                amount: Amount::zero(),
+            }),
+        );
+        transaction_hash::hash_command(valid::UserCommand::SignedCommand(Box::new(SignedCommand {
+            payload,
+            signer: sender.clone(),
+            signature: Signature::dummy(),
+        })))
+    }
+
+    /// Queues payments of `sender` paying `fees`, starting at nonce 0.
+    fn add_payments(pool: &mut IndexedPool, sender: &CompressedPubKey, fees: &[u64]) {
+        for (nonce, fee) in fees.iter().enumerate() {
+            let cmd = payment(sender, nonce as u32, *fee);
+            pool.add_from_gossip_exn(
+                Slot::zero(),
+                Slot::zero(),
+                &cmd,
+                Nonce::zero(),
+                Balance::max(),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_replace_fee() {
+        let mut pool = indexed_pool();
+        let sender = Account::rand().public_key;
+        add_payments(&mut pool, &sender, &[10, 20, 30]);
+        let sender = AccountId::new(sender, TokenId::default());
+        let fees = |nonce: u32| {
+            pool.replace_fee(&sender, Nonce::from_u32(nonce))
+                .map(|(min_fee, with_dependents)| (min_fee.as_u64(), with_dependents.as_u64()))
+        };
+
+        let replace_fee = REPLACE_FEE.as_u64();
+        assert_eq!(
+            fees(0),
+            Some((10 + replace_fee, 10 + replace_fee + 20 + 30))
+        );
+        assert_eq!(fees(1), Some((20 + replace_fee, 20 + replace_fee + 30)));
+        assert_eq!(fees(2), Some((30 + replace_fee, 30 + replace_fee)));
+        // Nothing to replace.
+        assert_eq!(fees(3), None);
+        let unknown = AccountId::new(Account::rand().public_key, TokenId::default());
+        assert_eq!(pool.replace_fee(&unknown, Nonce::zero()), None);
+    }
+
+    #[test]
+    fn test_replace_fee_overflow() {
+        let mut pool = indexed_pool();
+        let sender = Account::rand().public_key;
+        add_payments(&mut pool, &sender, &[u64::MAX - 1, 1]);
+        let sender = AccountId::new(sender, TokenId::default());
+
+        // Only the fee with the dependents overflows.
+        let (min_fee, _) = pool.replace_fee(&sender, Nonce::from_u32(1)).unwrap();
+        assert_eq!(min_fee.as_u64(), 1 + REPLACE_FEE.as_u64());
+        assert_eq!(pool.replace_fee(&sender, Nonce::zero()), None);
+    }
+
     /// Make sure that the merge in `TransactionPool::verify` is correct
     #[test]
     fn test_map_merge() {
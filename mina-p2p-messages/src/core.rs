@@ -64,8 +64,18 @@ impl TryFrom<SexpString> for Info {
 /// Represents error processing an RPC request.
 pub type Error = Info;
 
-// TODO
-#[derive(Clone, Debug, Serialize, Deserialize, BinProtRead, BinProtWrite, PartialEq)]
+/// Unix time in seconds.
+#[derive(
+    Clone,
+    Debug,
+    Serialize,
+    Deserialize,
+    BinProtRead,
+    BinProtWrite,
+    PartialEq,
+    derive_more::From,
+    derive_more::Into,
+)]
 pub struct Time(f64);
 
 pub type InetAddrV1Versioned = Versioned<InetAddrV1, 1>;
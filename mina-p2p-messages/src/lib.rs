@@ -37,6 +37,7 @@ pub mod b58version {
     pub const STAGED_LEDGER_HASH_PENDING_COINBASE_AUX: u8 = 0x0f;
     pub const STATE_HASH: u8 = 0x10;
     pub const STATE_BODY_HASH: u8 = 0x11;
+    pub const USER_COMMAND: u8 = 0x13;
     pub const USER_COMMAND_MEMO: u8 = 0x14;
     pub const VRF_TRUNCATED_OUTPUT: u8 = 0x15;
     pub const COINBASE_STACK_DATA: u8 = 0x17;
@@ -156,25 +156,57 @@ mina_rpc!(GetBestTipV2, "get_best_tip", 2, (), GetBestTipV2Response);
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, BinProtRead, BinProtWrite)]
 pub struct NodeStatusV2 {
-    node_ip_addr: InetAddrV1Versioned,
-    node_peer_id: v2::NetworkPeerPeerIdStableV1,
-    sync_status: v2::SyncStatusTStableV1,
-    peers: List<v2::NetworkPeerPeerIdStableV1>,
-    block_producers: List<v2::NonZeroCurvePoint>,
-    protocol_state_hash: v2::StateHash,
-    ban_statuses: List<(
+    pub node_ip_addr: InetAddrV1Versioned,
+    pub node_peer_id: v2::NetworkPeerPeerIdStableV1,
+    pub sync_status: v2::SyncStatusTStableV1,
+    pub peers: List<v2::NetworkPeerPeerIdStableV1>,
+    pub block_producers: List<v2::NonZeroCurvePoint>,
+    pub protocol_state_hash: v2::StateHash,
+    pub ban_statuses: List<(
         v2::NetworkPeerPeerIdStableV1,
         v2::TrustSystemPeerStatusStableV1,
     )>,
-    k_block_hashes_and_timestamps: List<(v2::StateHash, CharString)>,
-    git_commit: CharString,
-    uptime_minutes: i32,
-    block_height_opt: Option<i32>,
+    pub k_block_hashes_and_timestamps: List<(v2::StateHash, CharString)>,
+    pub git_commit: CharString,
+    pub uptime_minutes: i32,
+    pub block_height_opt: Option<i32>,
 }
 mina_rpc!(GetNodeStatusV2, "get_node_status", 2, (), RpcResult<NodeStatusV2, core::Error>);
 
 mina_rpc!(GetEpochLedgerV2, "get_epoch_ledger", 2, LedgerHashV1, RpcResult<MinaBaseSparseLedgerBaseStableV2, CharString>);
 
+// Methods served by the snark coordinator to the snark workers.
+
+mina_rpc!(
+    SnarkWorkerGetWorkV2,
+    "Get_work",
+    2,
+    (),
+    v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse
+);
+
+mina_rpc!(
+    SnarkWorkerSubmitWorkV2,
+    "Submit_work",
+    2,
+    v2::SnarkWorkerWorkerRpcsVersionedSubmitWorkV2TQuery,
+    ()
+);
+
+pub type SnarkWorkerFailedToGenerateSnarkV2Query = (
+    core::Error,
+    v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0,
+    v2::NonZeroCurvePoint,
+);
+
+mina_rpc!(
+    SnarkWorkerFailedToGenerateSnarkV2,
+    "Failed_to_generate_snark",
+    2,
+    SnarkWorkerFailedToGenerateSnarkV2Query,
+    ()
+);
+
 /// Registry for uniformly JSONifying RPC payload data.
 ///
 /// ```
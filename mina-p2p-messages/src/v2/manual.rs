@@ -9,7 +9,7 @@ use time::OffsetDateTime;
 
 use crate::{
     b58::{self, Base58CheckOfBinProt, Base58CheckOfBytes},
-    b58version::USER_COMMAND_MEMO,
+    b58version::{USER_COMMAND, USER_COMMAND_MEMO},
     bigint::BigInt,
     number::Number,
     string::ByteString,
@@ -54,6 +54,21 @@ impl MinaBaseSignedCommandMemoStableV1 {
     }
 }
 
+impl MinaBaseUserCommandStableV2 {
+    /// Base58check of the binprot encoding of the command.
+    pub fn to_base58check(&self) -> String {
+        let mut binprot = Vec::new();
+        // Writing to `Vec` can't fail.
+        self.binprot_write(&mut binprot).unwrap();
+        b58::encode(&binprot, USER_COMMAND)
+    }
+
+    pub fn from_base58check(s: &str) -> Result<Self, b58::FromBase58CheckError> {
+        let decoded = b58::decode(s, USER_COMMAND)?;
+        Ok(Self::binprot_read(&mut &decoded[1..])?)
+    }
+}
+
 impl Serialize for MinaBaseSignedCommandMemoStableV1 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
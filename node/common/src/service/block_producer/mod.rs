@@ -1,5 +1,7 @@
 mod vrf_evaluator;
 
+use std::collections::BTreeMap;
+
 use ledger::proofs::{
     block::BlockParams, generate_block_proof, provers::BlockProver, transaction::ProofError,
 };
@@ -8,28 +10,31 @@ use mina_p2p_messages::{
     v2::{MinaBaseProofStableV2, ProverExtendBlockchainInputStableV2, StateHash},
 };
 use node::{
-    account::AccountSecretKey,
+    account::{AccountPublicKey, AccountSecretKey},
     block_producer::{vrf_evaluator::VrfEvaluatorInput, BlockProducerEvent},
-    core::{channels::mpsc, constants::constraint_constants, thread},
+    core::{channels::mpsc, thread},
 };
 
 use crate::EventSender;
 
 pub struct BlockProducerService {
     provers: BlockProver,
-    keypair: AccountSecretKey,
+    keypairs: BTreeMap<AccountPublicKey, AccountSecretKey>,
     vrf_evaluation_sender: mpsc::UnboundedSender<VrfEvaluatorInput>,
 }
 
 impl BlockProducerService {
     pub fn new(
         provers: BlockProver,
-        keypair: AccountSecretKey,
+        keypairs: Vec<AccountSecretKey>,
         vrf_evaluation_sender: mpsc::UnboundedSender<VrfEvaluatorInput>,
     ) -> Self {
         Self {
             provers,
-            keypair,
+            keypairs: keypairs
+                .into_iter()
+                .map(|keypair| (keypair.public_key(), keypair))
+                .collect(),
             vrf_evaluation_sender,
         }
     }
@@ -37,49 +42,73 @@ impl BlockProducerService {
     pub fn start(
         provers: BlockProver,
         event_sender: EventSender,
-        keypair: AccountSecretKey,
+        keypairs: Vec<AccountSecretKey>,
     ) -> Self {
         let (vrf_evaluation_sender, vrf_evaluation_receiver) =
             mpsc::unbounded_channel::<VrfEvaluatorInput>();
 
-        let producer_keypair = keypair.clone();
+        let producer_keypairs = keypairs
+            .iter()
+            .map(|keypair| (keypair.public_key(), keypair.clone().into()))
+            .collect();
         thread::Builder::new()
             .name("openmina_vrf_evaluator".to_owned())
             .spawn(move || {
                 vrf_evaluator::vrf_evaluator(
                     event_sender,
                     vrf_evaluation_receiver,
-                    producer_keypair.into(),
+                    producer_keypairs,
                 );
             })
             .unwrap();
 
-        BlockProducerService::new(provers, keypair, vrf_evaluation_sender)
+        BlockProducerService::new(provers, keypairs, vrf_evaluation_sender)
     }
 
-    pub fn keypair(&self) -> AccountSecretKey {
-        self.keypair.clone()
+    /// Keypair of the block creator, used to sign the produced block.
+    ///
+    /// `None` for the genesis block, which isn't signed by the block
+    /// producer. Error if the block creator isn't one of our keys.
+    pub fn keypair(
+        &self,
+        input: &ProverExtendBlockchainInputStableV2,
+    ) -> Result<Option<AccountSecretKey>, String> {
+        if is_genesis(input) {
+            return Ok(None);
+        }
+        let block_creator =
+            AccountPublicKey::from(input.next_state.body.consensus_state.block_creator.clone());
+        match self.keypairs.get(&block_creator) {
+            Some(keypair) => Ok(Some(keypair.clone())),
+            None => Err(format!(
+                "block creator {block_creator} is not one of the block producer keys"
+            )),
+        }
     }
 }
 
+/// Whether the block is the genesis block of the chain, or of the chain
+/// restarted from the hard fork.
+pub fn is_genesis(input: &ProverExtendBlockchainInputStableV2) -> bool {
+    input
+        .next_state
+        .body
+        .consensus_state
+        .curr_global_slot_since_hard_fork
+        .slot_number
+        .as_u32()
+        == 0
+}
+
+/// Proves the block, signing it with the `keypair` unless it's the
+/// genesis block.
 pub fn prove(
     provers: BlockProver,
     mut input: Box<ProverExtendBlockchainInputStableV2>,
-    keypair: AccountSecretKey,
+    keypair: Option<AccountSecretKey>,
     only_verify_constraints: bool,
 ) -> Result<Box<MinaBaseProofStableV2>, ProofError> {
-    let height = input
-        .next_state
-        .body
-        .consensus_state
-        .blockchain_length
-        .as_u32();
-    let is_genesis = height == 1
-        || constraint_constants()
-            .fork
-            .as_ref()
-            .map_or(false, |fork| fork.blockchain_length + 1 == height);
-    if !is_genesis {
+    if let Some(keypair) = keypair {
         input.prover_state.producer_private_key = keypair.into();
     }
 
@@ -110,9 +139,15 @@ impl node::service::BlockProducerService for crate::NodeService {
             return;
         }
         let provers = self.provers();
-        let keypair = self.block_producer.as_ref().unwrap().keypair();
-
         let tx = self.event_sender().clone();
+        let keypair = match self.block_producer.as_ref().unwrap().keypair(&input) {
+            Ok(keypair) => keypair,
+            Err(error) => {
+                let _ = tx.send(BlockProducerEvent::BlockProve(block_hash, Err(error)).into());
+                return;
+            }
+        };
+
         thread::spawn(move || {
             let res =
                 prove(provers, input.clone(), keypair, false).map_err(|err| format!("{err:?}"));
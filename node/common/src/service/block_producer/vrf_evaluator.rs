@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+
 use mina_signer::Keypair;
 use node::{
+    account::AccountPublicKey,
     block_producer::BlockProducerVrfEvaluatorEvent,
     block_producer::{
         vrf_evaluator::{VrfEvaluationOutputWithHash, VrfEvaluatorInput},
@@ -15,12 +18,17 @@ use crate::NodeService;
 pub fn vrf_evaluator(
     event_sender: UnboundedSender<Event>,
     mut vrf_evaluation_receiver: UnboundedReceiver<VrfEvaluatorInput>,
-    keypair: Keypair,
+    keypairs: BTreeMap<AccountPublicKey, Keypair>,
 ) {
     while let Some(vrf_evaluator_input) = vrf_evaluation_receiver.blocking_recv() {
         let mut vrf_result = VrfEvaluationOutput::SlotLost(vrf_evaluator_input.global_slot);
 
-        for (index, (pub_key, stake)) in vrf_evaluator_input.delegator_table.iter() {
+        let delegators = vrf_evaluator_input
+            .delegator_tables
+            .iter()
+            .filter_map(|(producer, table)| Some((keypairs.get(producer)?, table)))
+            .flat_map(|(keypair, table)| table.iter().map(move |entry| (keypair, entry)));
+        for (keypair, (index, (pub_key, stake))) in delegators {
             let vrf_input = VrfEvaluationInput::new(
                 keypair.clone(),
                 vrf_evaluator_input.epoch_seed.clone(),
@@ -32,7 +40,7 @@ pub fn vrf_evaluator(
             );
             vrf_result = vrf::evaluate_vrf(vrf_input).unwrap();
 
-            // the first delegate that won the slot
+            // the first delegate, of any of our producers, that won the slot
             if let VrfEvaluationOutput::SlotWon(_) = vrf_result {
                 break;
             }
@@ -1,8 +1,10 @@
+use std::path::{Path, PathBuf};
+
 use ledger::proofs::provers::BlockProver;
 use node::{
     account::AccountSecretKey,
     core::channels::mpsc,
-    ledger::{LedgerCtx, LedgerManager},
+    ledger::{LedgerCtx, LedgerManager, RestoredFrontier},
     p2p::{
         identity::SecretKey as P2pSecretKey,
         service_impl::{
@@ -23,7 +25,7 @@ use crate::{
     EventReceiver, EventSender, NodeService,
 };
 
-use super::block_producer::BlockProducerService;
+use super::{block_producer::BlockProducerService, snark_worker::SnarkWorkerSpawner};
 
 pub struct NodeServiceCommonBuilder {
     rng_seed: [u8; 32],
@@ -33,8 +35,10 @@ pub struct NodeServiceCommonBuilder {
     event_sender: EventSender,
     event_receiver: EventReceiver,
     ledger_manager: Option<LedgerManager>,
+    archive_path: Option<PathBuf>,
     block_producer: Option<BlockProducerService>,
     p2p: Option<P2pServiceCtx>,
+    snark_worker_spawner: Option<Box<dyn SnarkWorkerSpawner>>,
     gather_stats: bool,
     rpc: RpcService,
 }
@@ -56,8 +60,10 @@ impl NodeServiceCommonBuilder {
             event_sender,
             event_receiver: event_receiver.into(),
             ledger_manager: None,
+            archive_path: None,
             block_producer: None,
             p2p: None,
+            snark_worker_spawner: None,
             rpc: RpcService::new(),
             gather_stats: false,
         }
@@ -71,22 +77,65 @@ impl NodeServiceCommonBuilder {
         self.rpc.req_sender()
     }
 
-    pub fn ledger_init(&mut self) -> &mut Self {
+    /// Archive the blocks of the best chain into the database under `path`.
+    ///
+    /// Must be called before the ledger is initialized.
+    pub fn archive_init(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.archive_path = Some(path.into());
+        self
+    }
+
+    fn ledger_ctx(&self) -> LedgerCtx {
         let mut ctx = LedgerCtx::default();
+        if let Some(path) = &self.archive_path {
+            if let Err(error) = ctx.archive_init(path) {
+                openmina_core::error!(
+                    openmina_core::log::system_time();
+                    message = "Failed to open the archive",
+                    error = error
+                );
+            }
+        }
         ctx.set_event_sender(self.event_sender.clone());
+        ctx
+    }
+
+    pub fn ledger_init(&mut self) -> &mut Self {
+        let ctx = self.ledger_ctx();
         self.ledger_manager = Some(LedgerManager::spawn(ctx));
         self
     }
 
+    /// Same as [`Self::ledger_init`], but the transition frontier and the
+    /// ledgers backing it are also persisted under `path`.
+    ///
+    /// Returns the transition frontier restored from there, if any.
+    pub fn ledger_init_with_persistence(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Option<RestoredFrontier> {
+        let mut ctx = self.ledger_ctx();
+        let restored = ctx.persistence_init(path).unwrap_or_else(|error| {
+            openmina_core::warn!(
+                openmina_core::log::system_time();
+                message = "Failed to restore persisted transition frontier",
+                error = error
+            );
+            None
+        });
+        self.ledger_manager = Some(LedgerManager::spawn(ctx));
+        restored
+    }
+
     pub fn block_producer_init(
         &mut self,
         provers: BlockProver,
-        keypair: AccountSecretKey,
+        keypairs: Vec<AccountSecretKey>,
     ) -> &mut Self {
         self.block_producer = Some(BlockProducerService::start(
             provers,
             self.event_sender.clone(),
-            keypair,
+            keypairs,
         ));
         self
     }
@@ -103,6 +152,12 @@ impl NodeServiceCommonBuilder {
         self
     }
 
+    /// Set the way local snark workers of the snarker get started.
+    pub fn snark_worker_init<S: 'static + SnarkWorkerSpawner>(&mut self, spawner: S) -> &mut Self {
+        self.snark_worker_spawner = Some(Box::new(spawner));
+        self
+    }
+
     pub fn gather_stats(&mut self) -> &mut Self {
         self.gather_stats = true;
         self
@@ -130,6 +185,9 @@ impl NodeServiceCommonBuilder {
             ledger_manager,
             block_producer: self.block_producer,
             p2p,
+            snark_worker_spawner: self.snark_worker_spawner,
+            snark_workers: Default::default(),
+            transaction_pool_path: None,
             stats: self.gather_stats.then(Stats::new),
             rpc: self.rpc,
             recorder: Default::default(),
@@ -8,6 +8,7 @@ pub mod replay;
 pub mod rpc;
 pub mod snark_worker;
 mod snarks;
+pub mod transaction_pool;
 
 mod builder;
 pub use builder::*;
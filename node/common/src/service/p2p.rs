@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::Path};
 
 use node::{
     core::channels::mpsc,
@@ -62,6 +62,10 @@ impl webrtc_with_libp2p::P2pServiceWebrtcWithLibp2p for NodeService {
     fn mio(&mut self) -> &mut mio::MioService {
         &mut self.p2p.mio
     }
+
+    fn ban_list_path(&self) -> Option<&Path> {
+        self.p2p.ban_list_path.as_deref()
+    }
 }
 
 #[cfg(feature = "p2p-libp2p")]
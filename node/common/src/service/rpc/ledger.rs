@@ -1,5 +1,7 @@
 #[cfg(target_family = "wasm")]
 use gloo_utils::format::JsValueSerdeExt;
+use mina_p2p_messages::v2::{LedgerHash, TokenIdKeyHash};
+use node::account::AccountPublicKey;
 use node::rpc::*;
 #[cfg(target_family = "wasm")]
 use wasm_bindgen::prelude::*;
@@ -18,6 +20,8 @@ pub struct Ledger {
 pub struct LedgerSelected {
     #[allow(unused)]
     sender: RpcSender,
+    /// `None` for the best tip ledger.
+    ledger_hash: Option<LedgerHash>,
 }
 
 #[derive(Clone)]
@@ -25,6 +29,7 @@ pub struct LedgerSelected {
 pub struct LedgerAccounts {
     #[allow(unused)]
     sender: RpcSender,
+    ledger_hash: Option<LedgerHash>,
 }
 
 impl Ledger {
@@ -38,6 +43,16 @@ impl Ledger {
     pub fn latest(&self) -> LedgerSelected {
         LedgerSelected {
             sender: self.sender.clone(),
+            ledger_hash: None,
+        }
+    }
+}
+
+impl Ledger {
+    pub fn at(&self, ledger_hash: LedgerHash) -> LedgerSelected {
+        LedgerSelected {
+            sender: self.sender.clone(),
+            ledger_hash: Some(ledger_hash),
         }
     }
 }
@@ -47,6 +62,7 @@ impl LedgerSelected {
     pub fn accounts(&self) -> LedgerAccounts {
         LedgerAccounts {
             sender: self.sender.clone(),
+            ledger_hash: self.ledger_hash.clone(),
         }
     }
 }
@@ -54,7 +70,36 @@ impl LedgerSelected {
 impl LedgerAccounts {
     async fn _all(&self) -> Option<RpcLedgerSlimAccountsResponse> {
         self.sender
-            .oneshot_request(RpcRequest::LedgerAccountsGet(AccountQuery::All))
+            .oneshot_request(RpcRequest::LedgerAccountsGet(
+                AccountQuery::All,
+                self.ledger_hash.clone(),
+            ))
+            .await
+    }
+
+    async fn _ledger(&self) -> Option<RpcLedgerAccountsResponse> {
+        self.sender
+            .oneshot_request(RpcRequest::LedgerAccountsGet(
+                AccountQuery::Ledger,
+                self.ledger_hash.clone(),
+            ))
+            .await
+    }
+
+    async fn _get(
+        &self,
+        public_key: AccountPublicKey,
+        token_id: Option<TokenIdKeyHash>,
+    ) -> Option<RpcLedgerAccountsResponse> {
+        let query = match token_id {
+            Some(token_id) => AccountQuery::PubKeyWithTokenId(public_key, token_id),
+            None => AccountQuery::SinglePublicKey(public_key),
+        };
+        self.sender
+            .oneshot_request(RpcRequest::LedgerAccountsGet(
+                query,
+                self.ledger_hash.clone(),
+            ))
             .await
     }
 }
@@ -64,6 +109,21 @@ impl LedgerAccounts {
     pub async fn all(&self) -> Option<RpcLedgerSlimAccountsResponse> {
         self._all().await
     }
+
+    /// All the accounts, in the ledger order.
+    pub async fn ledger(&self) -> Option<RpcLedgerAccountsResponse> {
+        self._ledger().await
+    }
+
+    /// Account of the `public_key` with the `token_id`, or with the
+    /// default token if `None`.
+    pub async fn get(
+        &self,
+        public_key: AccountPublicKey,
+        token_id: Option<TokenIdKeyHash>,
+    ) -> Option<RpcLedgerAccountsResponse> {
+        self._get(public_key, token_id).await
+    }
 }
 
 #[cfg(target_family = "wasm")]
@@ -72,4 +132,23 @@ impl LedgerAccounts {
     pub async fn all(&self) -> JsValue {
         JsValue::from_serde(&self._all().await).unwrap_or_default()
     }
+
+    pub async fn ledger(&self) -> JsValue {
+        JsValue::from_serde(&self._ledger().await).unwrap_or_default()
+    }
+
+    pub async fn get(
+        &self,
+        public_key: String,
+        token_id: Option<String>,
+    ) -> Result<JsValue, JsValue> {
+        let public_key = public_key
+            .parse::<AccountPublicKey>()
+            .map_err(|err| err.to_string())?;
+        let token_id = token_id
+            .map(|token_id| token_id.parse::<TokenIdKeyHash>())
+            .transpose()
+            .map_err(|err| err.to_string())?;
+        Ok(JsValue::from_serde(&self._get(public_key, token_id).await).unwrap_or_default())
+    }
 }
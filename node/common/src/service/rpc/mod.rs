@@ -9,11 +9,13 @@ pub mod transition_frontier;
 
 use node::rpc::{
     RpcBestChainResponse, RpcBlockProducerStatsGetResponse, RpcConsensusConstantsGetResponse,
-    RpcDiscoveryBoostrapStatsResponse, RpcDiscoveryRoutingTableResponse, RpcHealthCheckResponse,
-    RpcLedgerAccountsResponse, RpcLedgerSlimAccountsResponse, RpcMessageProgressResponse,
-    RpcPeersGetResponse, RpcReadinessCheckResponse, RpcRequest, RpcStateGetError,
-    RpcStatusGetResponse, RpcTransactionInjectResponse, RpcTransactionPoolResponse,
-    RpcTransactionStatusGetResponse, RpcTransitionFrontierUserCommandsResponse,
+    RpcDiscoveryBoostrapStatsResponse, RpcDiscoveryRoutingTableResponse, RpcGenesisBlockResponse,
+    RpcGetBlockResponse, RpcHealthCheckResponse, RpcLedgerAccountsResponse,
+    RpcLedgerSlimAccountsResponse, RpcMessageProgressResponse, RpcMetricsGetResponse,
+    RpcPeersGetResponse, RpcPooledUserCommandsResponse, RpcPooledZkappCommandsResponse,
+    RpcReadinessCheckResponse, RpcRequest, RpcStateGetError, RpcStatusGetResponse,
+    RpcTransactionInjectResponse, RpcTransactionPoolResponse, RpcTransactionStatusGetResponse,
+    RpcTransitionFrontierUserCommandsResponse,
 };
 use serde::{Deserialize, Serialize};
 
@@ -109,6 +111,28 @@ macro_rules! rpc_service_impl {
     };
 }
 
+/// Like [`rpc_service_impl`], but keeps the subscription open for
+/// further updates.
+macro_rules! rpc_service_subscription_impl {
+    ($name:ident, $ty:ty) => {
+        fn $name(&mut self, rpc_id: RpcId, response: $ty) -> Result<(), RespondError> {
+            let entry = self.rpc.pending.get(rpc_id);
+            let chan = entry.ok_or(RespondError::UnknownRpcId)?;
+            let chan = chan
+                .downcast_ref::<mpsc::Sender<$ty>>()
+                .ok_or(RespondError::UnexpectedResponseType)?;
+            match chan.try_send(response) {
+                // A subscriber that does not keep up misses the update.
+                Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => Ok(()),
+                Err(mpsc::error::TrySendError::Closed(_)) => {
+                    self.rpc.pending.remove(rpc_id);
+                    Err(RespondError::RespondingFailed)
+                }
+            }
+        }
+    };
+}
+
 macro_rules! state_field_filter {
     ($state:expr, $($part:ident)|*, $filter:expr ) => {
         $(
@@ -219,6 +243,7 @@ impl node::rpc::RpcService for NodeService {
         respond_block_producer_stats_get,
         RpcBlockProducerStatsGetResponse
     );
+    rpc_service_impl!(respond_metrics_get, RpcMetricsGetResponse);
     rpc_service_impl!(
         respond_message_progress_stats_get,
         RpcMessageProgressResponse
@@ -275,6 +300,18 @@ impl node::rpc::RpcService for NodeService {
         respond_snarker_workers,
         node::rpc::RpcSnarkerWorkersResponse
     );
+    rpc_service_impl!(
+        respond_snarker_worker_get_work,
+        node::rpc::RpcSnarkerWorkerGetWorkResponse
+    );
+    rpc_service_impl!(
+        respond_snarker_worker_submit_work,
+        node::rpc::RpcSnarkerWorkerSubmitWorkResponse
+    );
+    rpc_service_impl!(
+        respond_snarker_worker_failed,
+        node::rpc::RpcSnarkerWorkerFailedResponse
+    );
     rpc_service_impl!(
         respond_snarker_config_get,
         node::rpc::RpcSnarkerConfigGetResponse
@@ -298,11 +335,53 @@ impl node::rpc::RpcService for NodeService {
         RpcTransitionFrontierUserCommandsResponse
     );
     rpc_service_impl!(respond_best_chain, RpcBestChainResponse);
+    rpc_service_subscription_impl!(
+        respond_best_tip_subscribe,
+        node::rpc::RpcBestTipSubscribeResponse
+    );
     rpc_service_impl!(
         respond_consensus_constants,
         RpcConsensusConstantsGetResponse
     );
     rpc_service_impl!(respond_transaction_status, RpcTransactionStatusGetResponse);
+    rpc_service_impl!(respond_block_get, RpcGetBlockResponse);
+    rpc_service_impl!(respond_genesis_block, RpcGenesisBlockResponse);
+    rpc_service_impl!(respond_pooled_user_commands, RpcPooledUserCommandsResponse);
+    rpc_service_impl!(
+        respond_pooled_zkapp_commands,
+        RpcPooledZkappCommandsResponse
+    );
+    rpc_service_impl!(
+        respond_hard_fork_config_get,
+        node::rpc::RpcHardForkConfigGetResponse
+    );
+    rpc_service_impl!(
+        respond_watched_accounts_add,
+        node::rpc::RpcWatchedAccountsAddResponse
+    );
+    rpc_service_impl!(
+        respond_watched_accounts_remove,
+        node::rpc::RpcWatchedAccountsRemoveResponse
+    );
+    rpc_service_impl!(
+        respond_watched_accounts_get,
+        node::rpc::RpcWatchedAccountsGetResponse
+    );
+    rpc_service_subscription_impl!(
+        respond_watched_account_subscribe,
+        node::rpc::RpcWatchedAccountSubscribeResponse
+    );
+
+    fn respond_subscription_end(&mut self, rpc_id: RpcId) {
+        self.rpc.pending.remove(rpc_id);
+    }
+    rpc_service_impl!(
+        respond_transaction_simulate,
+        node::rpc::RpcTransactionSimulateResponse
+    );
+    rpc_service_impl!(respond_fee_estimate, node::rpc::RpcFeeEstimateResponse);
+    rpc_service_impl!(respond_zkapp_events_get, node::rpc::RpcZkappEventsResponse);
+    rpc_service_impl!(respond_archive_get, node::rpc::RpcArchiveGetResponse);
 }
 
 #[cfg(test)]
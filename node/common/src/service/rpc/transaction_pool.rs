@@ -58,16 +58,20 @@ impl TransactionPool {
 }
 
 impl TransactionPoolInject {
-    async fn _payment(
+    async fn _inject<T>(
         &self,
-        payments: Vec<RpcInjectPayment>,
-    ) -> Result<Option<RpcTransactionInjectResponse>, String> {
+        commands: Vec<T>,
+    ) -> Result<Option<RpcTransactionInjectResponse>, String>
+    where
+        T: TryInto<v2::MinaBaseUserCommandStableV2>,
+        T::Error: ToString,
+    {
         let res = self
             .sender
             .oneshot_request(RpcRequest::TransactionInject(
-                payments
+                commands
                     .into_iter()
-                    .map(v2::MinaBaseUserCommandStableV2::try_from)
+                    .map(TryInto::try_into)
                     .collect::<Result<_, _>>()
                     .map_err(|err| err.to_string())?,
             ))
@@ -82,7 +86,32 @@ impl TransactionPoolInject {
         &self,
         payments: Vec<RpcInjectPayment>,
     ) -> Result<Option<RpcTransactionInjectResponse>, String> {
-        self._payment(payments).await
+        self._inject(payments).await
+    }
+
+    pub async fn delegation(
+        &self,
+        delegations: Vec<RpcInjectDelegation>,
+    ) -> Result<Option<RpcTransactionInjectResponse>, String> {
+        self._inject(delegations).await
+    }
+
+    /// Inject already signed user commands.
+    pub async fn commands(
+        &self,
+        commands: Vec<v2::MinaBaseUserCommandStableV2>,
+    ) -> Result<Option<RpcTransactionInjectResponse>, String> {
+        self._inject(commands).await
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn from_js_list<T: serde::de::DeserializeOwned>(value: JsValue) -> Result<Vec<T>, JsValue> {
+    if value.is_array() {
+        Ok(value.into_serde().map_err(|err| err.to_string())?)
+    } else {
+        let item = value.into_serde().map_err(|err| err.to_string())?;
+        Ok(vec![item])
     }
 }
 
@@ -90,14 +119,18 @@ impl TransactionPoolInject {
 #[cfg_attr(target_family = "wasm", wasm_bindgen)]
 impl TransactionPoolInject {
     pub async fn payment(&self, payments: JsValue) -> Result<JsValue, JsValue> {
-        let payments: Vec<RpcInjectPayment> = if payments.is_array() {
-            payments.into_serde().map_err(|err| err.to_string())?
-        } else {
-            let payment = payments.into_serde().map_err(|err| err.to_string())?;
-            vec![payment]
-        };
-
-        self._payment(payments)
+        let payments: Vec<RpcInjectPayment> = from_js_list(payments)?;
+
+        self._inject(payments)
+            .await
+            .map(|res| JsValue::from_serde(&res).unwrap_or_default())
+            .map_err(Into::into)
+    }
+
+    pub async fn delegation(&self, delegations: JsValue) -> Result<JsValue, JsValue> {
+        let delegations: Vec<RpcInjectDelegation> = from_js_list(delegations)?;
+
+        self._inject(delegations)
             .await
             .map(|res| JsValue::from_serde(&res).unwrap_or_default())
             .map_err(Into::into)
@@ -1,8 +1,9 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 
 use node::{
     core::{channels::mpsc, invariants::InvariantsState},
     event_source::Event,
+    external_snark_worker::ExternalSnarkWorkerId,
     ledger::LedgerManager,
     p2p::identity::SecretKey as P2pSecretKey,
     service::Recorder,
@@ -22,6 +23,7 @@ use super::{
     p2p::webrtc_with_libp2p::P2pServiceCtx,
     replay::ReplayerState,
     rpc::{RpcSender, RpcService},
+    snark_worker::{SnarkWorker, SnarkWorkerSpawner},
     EventReceiver, EventSender,
 };
 
@@ -39,6 +41,10 @@ pub struct NodeService {
     pub ledger_manager: LedgerManager,
     pub block_producer: Option<BlockProducerService>,
     pub p2p: P2pServiceCtx,
+    pub snark_worker_spawner: Option<Box<dyn SnarkWorkerSpawner>>,
+    pub snark_workers: BTreeMap<ExternalSnarkWorkerId, Box<dyn SnarkWorker>>,
+    /// File where the locally generated transactions are persisted.
+    pub transaction_pool_path: Option<PathBuf>,
 
     pub stats: Option<Stats>,
     pub rpc: RpcService,
@@ -108,6 +114,9 @@ impl NodeService {
             ledger_manager: LedgerManager::spawn(Default::default()),
             block_producer: None,
             p2p: P2pServiceCtx::mocked(p2p_sec_key),
+            snark_worker_spawner: None,
+            snark_workers: Default::default(),
+            transaction_pool_path: None,
             stats: Some(Stats::new()),
             rpc: RpcService::new(),
             recorder: Recorder::None,
@@ -0,0 +1,89 @@
+use mina_p2p_messages::v2;
+use node::external_snark_worker::{ExternalSnarkWorkerError, ExternalSnarkWorkerId, SnarkWorkSpec};
+
+use crate::{EventSender, NodeService};
+
+#[cfg(not(target_family = "wasm"))]
+mod rust_snark_worker;
+#[cfg(not(target_family = "wasm"))]
+pub use rust_snark_worker::{RustSnarkWorker, RustSnarkWorkerSpawner};
+
+/// Running local snark worker.
+pub trait SnarkWorker: Send {
+    fn submit(&mut self, spec: SnarkWorkSpec) -> Result<(), ExternalSnarkWorkerError>;
+
+    fn cancel(&mut self) -> Result<(), ExternalSnarkWorkerError>;
+
+    fn kill(self: Box<Self>) -> Result<(), ExternalSnarkWorkerError>;
+}
+
+/// Starts local snark workers. Started worker must report its events
+/// using [`node::event_source::Event::ExternalSnarkWorker`] with the
+/// given `worker_id`.
+pub trait SnarkWorkerSpawner: Send {
+    fn spawn(
+        &self,
+        worker_id: ExternalSnarkWorkerId,
+        public_key: v2::NonZeroCurvePoint,
+        fee: v2::CurrencyFeeStableV1,
+        event_sender: EventSender,
+    ) -> Result<Box<dyn SnarkWorker>, ExternalSnarkWorkerError>;
+}
+
+impl NodeService {
+    fn snark_worker(
+        &mut self,
+        worker_id: ExternalSnarkWorkerId,
+    ) -> Result<&mut Box<dyn SnarkWorker>, ExternalSnarkWorkerError> {
+        self.snark_workers
+            .get_mut(&worker_id)
+            .ok_or(ExternalSnarkWorkerError::NotRunning)
+    }
+}
+
+impl node::service::ExternalSnarkWorkerService for NodeService {
+    fn start(
+        &mut self,
+        worker_id: ExternalSnarkWorkerId,
+        public_key: v2::NonZeroCurvePoint,
+        fee: v2::CurrencyFeeStableV1,
+    ) -> Result<(), ExternalSnarkWorkerError> {
+        if self.replayer.is_some() {
+            return Ok(());
+        }
+        let spawner = self.snark_worker_spawner.as_ref().ok_or_else(|| {
+            ExternalSnarkWorkerError::Error("snark workers are not supported".to_owned())
+        })?;
+        let worker = spawner.spawn(worker_id, public_key, fee, self.event_sender.clone())?;
+        self.snark_workers.insert(worker_id, worker);
+        Ok(())
+    }
+
+    fn kill(&mut self, worker_id: ExternalSnarkWorkerId) -> Result<(), ExternalSnarkWorkerError> {
+        if self.replayer.is_some() {
+            return Ok(());
+        }
+        self.snark_workers
+            .remove(&worker_id)
+            .ok_or(ExternalSnarkWorkerError::NotRunning)?
+            .kill()
+    }
+
+    fn submit(
+        &mut self,
+        worker_id: ExternalSnarkWorkerId,
+        spec: SnarkWorkSpec,
+    ) -> Result<(), ExternalSnarkWorkerError> {
+        if self.replayer.is_some() {
+            return Ok(());
+        }
+        self.snark_worker(worker_id)?.submit(spec)
+    }
+
+    fn cancel(&mut self, worker_id: ExternalSnarkWorkerId) -> Result<(), ExternalSnarkWorkerError> {
+        if self.replayer.is_some() {
+            return Ok(());
+        }
+        self.snark_worker(worker_id)?.cancel()
+    }
+}
@@ -0,0 +1,365 @@
+//! Snark worker that produces transaction snarks in-process, using the
+//! Rust provers instead of the external OCaml worker.
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    mpsc::{self, SyncSender, TrySendError},
+    Arc,
+};
+
+use ledger::{
+    proofs::{
+        generate_merge_proof, generate_tx_proof, generate_zkapp_proof,
+        merge::MergeParams,
+        provers::{TransactionProver, ZkappProver},
+        transaction::{ProofError, TransactionParams},
+        verification::verify_transaction,
+        zkapp::{LedgerProof, ZkappParams},
+    },
+    scan_state::scan_state::transaction_snark::{SokMessage, Statement},
+};
+use mina_p2p_messages::v2;
+use node::{
+    account::AccountPublicKey,
+    core::thread,
+    event_source::Event,
+    external_snark_worker::{
+        ExternalSnarkWorkerError, ExternalSnarkWorkerEvent, ExternalSnarkWorkerId,
+        ExternalSnarkWorkerWorkError, SnarkWorkSpec,
+    },
+    snark::{TransactionVerifier, VerifierSRS},
+};
+
+use super::{SnarkWorker, SnarkWorkerSpawner};
+use crate::EventSender;
+
+enum Command {
+    Submit(SnarkWorkSpec),
+    Kill,
+}
+
+/// Worker proving jobs on its own thread.
+///
+/// Proving can't be interrupted, so cancelled job is still finished, but
+/// its result is dropped and reported as cancelled.
+pub struct RustSnarkWorker {
+    commands: SyncSender<Command>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl SnarkWorker for RustSnarkWorker {
+    fn submit(&mut self, spec: SnarkWorkSpec) -> Result<(), ExternalSnarkWorkerError> {
+        self.cancelled.store(false, Ordering::SeqCst);
+        self.commands
+            .try_send(Command::Submit(spec))
+            .map_err(|err| match err {
+                TrySendError::Full(_) => ExternalSnarkWorkerError::Busy,
+                TrySendError::Disconnected(_) => ExternalSnarkWorkerError::NotRunning,
+            })
+    }
+
+    fn cancel(&mut self) -> Result<(), ExternalSnarkWorkerError> {
+        self.cancelled.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn kill(self: Box<Self>) -> Result<(), ExternalSnarkWorkerError> {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.commands
+            .send(Command::Kill)
+            .map_err(|_| ExternalSnarkWorkerError::NotRunning)
+    }
+}
+
+/// Runs local snark workers in-process. Produced snarks are checked
+/// with the transaction verifier before being reported.
+#[derive(Clone)]
+pub struct RustSnarkWorkerSpawner {
+    verifier_index: TransactionVerifier,
+    verifier_srs: Arc<VerifierSRS>,
+}
+
+impl RustSnarkWorkerSpawner {
+    pub fn new(verifier_index: TransactionVerifier, verifier_srs: Arc<VerifierSRS>) -> Self {
+        Self {
+            verifier_index,
+            verifier_srs,
+        }
+    }
+}
+
+impl SnarkWorkerSpawner for RustSnarkWorkerSpawner {
+    fn spawn(
+        &self,
+        worker_id: ExternalSnarkWorkerId,
+        public_key: v2::NonZeroCurvePoint,
+        fee: v2::CurrencyFeeStableV1,
+        event_sender: EventSender,
+    ) -> Result<Box<dyn SnarkWorker>, ExternalSnarkWorkerError> {
+        let prover = AccountPublicKey::from(public_key)
+            .try_into()
+            .map_err(|_| ExternalSnarkWorkerError::Error("invalid public key".to_owned()))?;
+        let message = SokMessage::create((&fee).into(), prover);
+        let (commands, commands_rx) = mpsc::sync_channel(1);
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let mut prover = Prover {
+            message,
+            verifier_index: self.verifier_index.clone(),
+            verifier_srs: self.verifier_srs.clone(),
+            tx_prover: None,
+            zkapp_prover: None,
+        };
+        let worker_cancelled = cancelled.clone();
+        thread::Builder::new()
+            .name(format!("rust-snark-worker-{worker_id}"))
+            .spawn(move || {
+                let send = |event: ExternalSnarkWorkerEvent| {
+                    let _ = event_sender.send(Event::ExternalSnarkWorker(worker_id, event));
+                };
+                // Loading circuits takes a while, worker isn't ready before that.
+                prover.tx_prover();
+                send(ExternalSnarkWorkerEvent::Started);
+
+                while let Ok(Command::Submit(spec)) = commands_rx.recv() {
+                    let result = prover.prove(spec);
+                    let event = if worker_cancelled.swap(false, Ordering::SeqCst) {
+                        ExternalSnarkWorkerEvent::WorkCancelled
+                    } else {
+                        match result {
+                            Ok(proofs) => ExternalSnarkWorkerEvent::WorkResult(Arc::new(proofs)),
+                            Err(err) => ExternalSnarkWorkerWorkError::Error(err).into(),
+                        }
+                    };
+                    send(event);
+                }
+                send(ExternalSnarkWorkerEvent::Killed);
+            })
+            .map_err(|err| ExternalSnarkWorkerError::IOError(err.to_string()))?;
+
+        Ok(Box::new(RustSnarkWorker {
+            commands,
+            cancelled,
+        }))
+    }
+}
+
+struct Prover {
+    message: SokMessage,
+    verifier_index: TransactionVerifier,
+    verifier_srs: Arc<VerifierSRS>,
+    tx_prover: Option<TransactionProver>,
+    /// Only made when the first zkapp command needs to be proven, as it
+    /// requires quite a few more circuits.
+    zkapp_prover: Option<ZkappProver>,
+}
+
+impl Prover {
+    fn tx_prover(&mut self) -> &TransactionProver {
+        let verifier_index = &self.verifier_index;
+        self.tx_prover
+            .get_or_insert_with(|| TransactionProver::make(Some(verifier_index.clone())))
+    }
+
+    fn zkapp_prover(&mut self) -> &ZkappProver {
+        let verifier_index = &self.verifier_index;
+        self.zkapp_prover
+            .get_or_insert_with(|| ZkappProver::make(Some(verifier_index.clone())))
+    }
+
+    fn prove(
+        &mut self,
+        spec: SnarkWorkSpec,
+    ) -> Result<v2::TransactionSnarkWorkTStableV2Proofs, String> {
+        let singles = match spec {
+            SnarkWorkSpec::One(single) => vec![single],
+            SnarkWorkSpec::Two((single1, single2)) => vec![single1, single2],
+        };
+        let proofs = singles
+            .into_iter()
+            .map(|single| {
+                let proof = self
+                    .prove_single(single)
+                    .map_err(|err| format!("{err:?}"))?;
+                Ok((
+                    proof.statement.clone(),
+                    v2::LedgerProofProdStableV2::from(&proof),
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let verified = verify_transaction(
+            proofs
+                .iter()
+                .map(|(statement, proof)| (statement, &proof.0.proof)),
+            &self.verifier_index,
+            &self.verifier_srs,
+        );
+        if !verified {
+            return Err("produced snark failed verification".to_owned());
+        }
+
+        let mut proofs = proofs.into_iter().map(|(_, proof)| proof);
+        match (proofs.next(), proofs.next()) {
+            (Some(proof1), Some(proof2)) => Ok(v2::TransactionSnarkWorkTStableV2Proofs::Two((
+                proof1, proof2,
+            ))),
+            (Some(proof), None) => Ok(v2::TransactionSnarkWorkTStableV2Proofs::One(proof)),
+            _ => Err("spec has no instances".to_owned()),
+        }
+    }
+
+    fn prove_single(
+        &mut self,
+        single: v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single,
+    ) -> Result<LedgerProof, ProofError> {
+        match single {
+            v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single::Transition(
+                statement,
+                tx_witness,
+            ) => {
+                if is_zkapp_command(&tx_witness.transaction) {
+                    let message = self.message.clone();
+                    let provers = self.zkapp_prover();
+                    return generate_zkapp_proof(ZkappParams {
+                        statement: &statement,
+                        tx_witness: &tx_witness,
+                        message: &message,
+                        step_opt_signed_opt_signed_prover: &provers
+                            .step_opt_signed_opt_signed_prover,
+                        step_opt_signed_prover: &provers.step_opt_signed_prover,
+                        step_proof_prover: &provers.step_proof_prover,
+                        merge_step_prover: &provers.merge_step_prover,
+                        tx_wrap_prover: &provers.tx_wrap_prover,
+                        opt_signed_path: None,
+                        proved_path: None,
+                    });
+                }
+
+                let message = self.message.clone();
+                let provers = self.tx_prover();
+                let proof = generate_tx_proof(TransactionParams {
+                    statement: &statement,
+                    tx_witness: &tx_witness,
+                    message: &message,
+                    tx_step_prover: &provers.tx_step_prover,
+                    tx_wrap_prover: &provers.tx_wrap_prover,
+                    only_verify_constraints: false,
+                    expected_step_proof: None,
+                    ocaml_wrap_witness: None,
+                })?;
+                let statement = Statement::<()>::try_from(&*statement)?;
+                Ok(LedgerProof {
+                    statement: statement.with_digest(message.digest()),
+                    proof,
+                })
+            }
+            v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Single::Merge(merge) => {
+                let (statement, proof1, proof2) = *merge;
+                let statement = Statement::<()>::try_from(&*statement)?;
+                let message = self.message.clone();
+                let provers = self.tx_prover();
+                let proof = generate_merge_proof(MergeParams {
+                    statement: statement.clone(),
+                    proofs: &[proof1, proof2],
+                    message: &message,
+                    step_prover: &provers.merge_step_prover,
+                    wrap_prover: &provers.tx_wrap_prover,
+                    only_verify_constraints: false,
+                    expected_step_proof: None,
+                    ocaml_wrap_witness: None,
+                })?;
+                Ok(LedgerProof {
+                    statement: statement.with_digest(message.digest()),
+                    proof,
+                })
+            }
+        }
+    }
+}
+
+fn is_zkapp_command(transaction: &v2::MinaTransactionTransactionStableV2) -> bool {
+    matches!(
+        transaction,
+        v2::MinaTransactionTransactionStableV2::Command(command)
+            if matches!(**command, v2::MinaBaseUserCommandStableV2::ZkappCommand(_))
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use ledger::proofs::provers::devnet_circuit_directory;
+    use mina_p2p_messages::binprot::BinProtRead;
+    use node::core::snark::Snark;
+
+    use super::*;
+    use crate::service::snarks::verify_work;
+
+    /// Reads job from the external worker requests dumped next to the
+    /// circuits: 8 bytes le size, `PerformJob` tag, then the job itself.
+    fn read_job(
+        filename: &str,
+    ) -> Option<(
+        SnarkWorkSpec,
+        v2::CurrencyFeeStableV1,
+        v2::NonZeroCurvePoint,
+    )> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../ledger")
+            .join(devnet_circuit_directory())
+            .join("tests")
+            .join(filename);
+        let Ok(bytes) = std::fs::read(path) else {
+            eprintln!("request not found");
+            return None;
+        };
+        let mut bytes = &bytes[std::mem::size_of::<u64>() + 1..];
+        let v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse(Some((job, prover))) =
+            v2::SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse::binprot_read(&mut bytes).unwrap()
+        else {
+            panic!("request without a job");
+        };
+        Some((job.instances, job.fee, prover))
+    }
+
+    fn prove_and_verify(filename: &str) {
+        let Some((spec, fee, snarker)) = read_job(filename) else {
+            return;
+        };
+        let verifier_index = TransactionVerifier::make();
+        let verifier_srs = node::snark::get_srs();
+        let mut prover = Prover {
+            message: SokMessage::create(
+                (&fee).into(),
+                AccountPublicKey::from(snarker.clone()).try_into().unwrap(),
+            ),
+            verifier_index: verifier_index.clone(),
+            verifier_srs: verifier_srs.clone(),
+            tx_prover: None,
+            zkapp_prover: None,
+        };
+
+        let job_id = node::core::snark::SnarkJobId::from(&spec);
+        let proofs = prover.prove(spec).unwrap();
+        assert_eq!(node::core::snark::SnarkJobId::from(&proofs), job_id);
+
+        let snark = Snark {
+            snarker,
+            fee,
+            proofs: Arc::new(proofs),
+        };
+        verify_work(vec![snark], &verifier_index, &verifier_srs).unwrap();
+    }
+
+    #[test]
+    fn test_prove_base() {
+        prove_and_verify("command-0-1.bin");
+    }
+
+    #[test]
+    fn test_prove_merge() {
+        prove_and_verify("merge-100-0.bin");
+    }
+}
@@ -64,43 +64,47 @@ impl node::service::SnarkWorkVerifyService for NodeService {
         }
         let tx = self.event_sender().clone();
         rayon::spawn_fifo(move || {
-            let result = (|| {
-                let conv = |proof: &v2::LedgerProofProdStableV2| -> Result<_, InvalidBigInt> {
-                    Ok((
-                        Statement::<SokDigest>::try_from(&proof.0.statement)?,
-                        proof.proof.clone(),
-                    ))
-                };
-                let Ok(works) = work
-                    .into_iter()
-                    .flat_map(|work| match &*work.proofs {
-                        v2::TransactionSnarkWorkTStableV2Proofs::One(v) => {
-                            [conv(v).map(Some), Ok(None)]
-                        }
-                        v2::TransactionSnarkWorkTStableV2Proofs::Two((v1, v2)) => {
-                            [conv(v1).map(Some), conv(v2).map(Some)]
-                        }
-                    })
-                    .collect::<Result<Vec<_>, _>>()
-                else {
-                    return Err(SnarkWorkVerifyError::VerificationFailed);
-                };
-                if !ledger::proofs::verification::verify_transaction(
-                    works.iter().flatten().map(|(v1, v2)| (v1, v2)),
-                    &verifier_index,
-                    &verifier_srs,
-                ) {
-                    Err(SnarkWorkVerifyError::VerificationFailed)
-                } else {
-                    Ok(())
-                }
-            })();
+            let result = verify_work(work, &verifier_index, &verifier_srs);
 
             let _ = tx.send(SnarkEvent::WorkVerify(req_id, result).into());
         });
     }
 }
 
+pub(crate) fn verify_work(
+    work: Vec<Snark>,
+    verifier_index: &TransactionVerifier,
+    verifier_srs: &VerifierSRS,
+) -> Result<(), SnarkWorkVerifyError> {
+    let conv = |proof: &v2::LedgerProofProdStableV2| -> Result<_, InvalidBigInt> {
+        Ok((
+            Statement::<SokDigest>::try_from(&proof.0.statement)?,
+            proof.proof.clone(),
+        ))
+    };
+    let Ok(works) = work
+        .into_iter()
+        .flat_map(|work| match &*work.proofs {
+            v2::TransactionSnarkWorkTStableV2Proofs::One(v) => [conv(v).map(Some), Ok(None)],
+            v2::TransactionSnarkWorkTStableV2Proofs::Two((v1, v2)) => {
+                [conv(v1).map(Some), conv(v2).map(Some)]
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+    else {
+        return Err(SnarkWorkVerifyError::VerificationFailed);
+    };
+    if !ledger::proofs::verification::verify_transaction(
+        works.iter().flatten().map(|(v1, v2)| (v1, v2)),
+        verifier_index,
+        verifier_srs,
+    ) {
+        Err(SnarkWorkVerifyError::VerificationFailed)
+    } else {
+        Ok(())
+    }
+}
+
 impl node::service::SnarkUserCommandVerifyService for NodeService {
     fn verify_init(
         &mut self,
@@ -127,3 +131,63 @@ impl node::service::SnarkPoolService for NodeService {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ark_ff::Zero;
+    use ledger::scan_state::{
+        currency::{Amount, Fee, Signed},
+        fee_excess::FeeExcess,
+        pending_coinbase::Stack,
+        scan_state::transaction_snark::{LedgerHash, Registers},
+        transaction_logic::local_state::LocalState,
+    };
+    use ledger::Account;
+    use node::core::dummy::dummy_transaction_proof;
+
+    use super::*;
+
+    fn dummy_snark(proofs: usize) -> Snark {
+        let registers = |ledger: u64| Registers {
+            first_pass_ledger: LedgerHash::from(ledger),
+            second_pass_ledger: LedgerHash::from(ledger),
+            pending_coinbase_stack: Stack::empty(),
+            local_state: LocalState::dummy(),
+        };
+        let statement = Statement {
+            source: registers(1),
+            target: registers(2),
+            connecting_ledger_left: LedgerHash::zero(),
+            connecting_ledger_right: LedgerHash::zero(),
+            supply_increase: Signed::<Amount>::zero(),
+            fee_excess: FeeExcess::empty(),
+            sok_digest: SokDigest::default(),
+        };
+        let proof = v2::LedgerProofProdStableV2(v2::TransactionSnarkStableV2 {
+            statement: (&statement).into(),
+            proof: (*dummy_transaction_proof()).clone(),
+        });
+        let proofs = match proofs {
+            1 => v2::TransactionSnarkWorkTStableV2Proofs::One(proof),
+            _ => v2::TransactionSnarkWorkTStableV2Proofs::Two((proof.clone(), proof)),
+        };
+        Snark {
+            snarker: (&Account::rand().public_key).into(),
+            fee: (&Fee::from_u64(10_000_000)).into(),
+            proofs: Arc::new(proofs),
+        }
+    }
+
+    #[test]
+    fn test_verify_work_rejects_dummy_proofs() {
+        let verifier_index = TransactionVerifier::make();
+        let verifier_srs = node::snark::get_srs();
+
+        for proofs in [1, 2] {
+            assert!(matches!(
+                verify_work(vec![dummy_snark(proofs)], &verifier_index, &verifier_srs),
+                Err(SnarkWorkVerifyError::VerificationFailed)
+            ));
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use mina_p2p_messages::v2;
+
+use crate::NodeService;
+
+impl node::service::TransactionPoolService for NodeService {
+    fn locally_generated_persist(&mut self, commands: Vec<v2::MinaBaseUserCommandStableV2>) {
+        let Some(path) = self.transaction_pool_path.as_deref() else {
+            return;
+        };
+        if let Err(error) = locally_generated_save(path, &commands) {
+            openmina_core::warn!(openmina_core::log::system_time(); summary = "failed to persist locally generated transactions", path = display(path.display()), error = display(error));
+        }
+    }
+}
+
+/// Loads the locally generated commands persisted by
+/// [`node::service::TransactionPoolService::locally_generated_persist`].
+/// Missing file means that there are no such commands.
+pub fn locally_generated_load(
+    path: &Path,
+) -> std::io::Result<Vec<v2::MinaBaseUserCommandStableV2>> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error),
+    }
+}
+
+fn locally_generated_save(
+    path: &Path,
+    commands: &[v2::MinaBaseUserCommandStableV2],
+) -> std::io::Result<()> {
+    // write to a temporary file first, so that the commands aren't lost
+    // if we get killed in the middle of writing.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec(commands)?)?;
+    std::fs::rename(tmp_path, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::scan_state::currency::{Amount, Fee, Nonce};
+    use ledger::scan_state::transaction_logic::signed_command::{
+        self, SignedCommand, SignedCommandPayload,
+    };
+    use ledger::scan_state::transaction_logic::Memo;
+    use ledger::Account;
+    use mina_p2p_messages::bigint::BigInt;
+
+    use super::*;
+
+    fn payment(nonce: u32) -> v2::MinaBaseUserCommandStableV2 {
+        let signer = Account::rand().public_key;
+        let command = SignedCommand {
+            payload: SignedCommandPayload::create(
+                Fee::from_u64(10_000_000),
+                signer.clone(),
+                Nonce::from_u32(nonce),
+                None,
+                Memo::empty(),
+                signed_command::Body::Payment(signed_command::PaymentPayload {
+                    receiver_pk: Account::rand().public_key,
+                    amount: Amount::from_u64(1_000_000_000),
+                }),
+            ),
+            signer,
+            signature: mina_signer::Signature {
+                rx: BigInt::one().try_into().unwrap(),
+                s: BigInt::one().try_into().unwrap(),
+            },
+        };
+        v2::MinaBaseUserCommandStableV2::SignedCommand(command.into())
+    }
+
+    #[test]
+    fn test_locally_generated_save_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("locally_generated.json");
+        assert!(locally_generated_load(&path).unwrap().is_empty());
+
+        let commands = vec![payment(0), payment(1)];
+        locally_generated_save(&path, &commands).unwrap();
+        assert_eq!(locally_generated_load(&path).unwrap(), commands);
+        assert!(!path.with_extension("tmp").exists());
+
+        // saving replaces the previously persisted commands.
+        locally_generated_save(&path, &commands[1..]).unwrap();
+        assert_eq!(locally_generated_load(&path).unwrap(), commands[1..]);
+    }
+
+    #[test]
+    fn test_locally_generated_load_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("locally_generated.json");
+        std::fs::write(&path, b"not json").unwrap();
+        let error = locally_generated_load(&path).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
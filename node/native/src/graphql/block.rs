@@ -1,6 +1,7 @@
 use juniper::GraphQLObject;
 use openmina_core::block::AppliedBlock;
 
+use crate::graphql::user_command::GraphQLUserCommand;
 use crate::graphql::zkapp::{GraphQLFailureReason, GraphQLFeePayer, GraphQLZkappCommand};
 
 use super::{zkapp::GraphQLZkapp, ConversionError};
@@ -10,11 +11,14 @@ use super::{zkapp::GraphQLZkapp, ConversionError};
 pub struct GraphQLBestChainBlock {
     pub protocol_state: GraphQLProtocolState,
     pub state_hash: String,
+    /// Public key of the block producer
+    pub creator: String,
     pub transactions: GraphQLTransactions,
 }
 
 #[derive(GraphQLObject, Debug)]
 pub struct GraphQLTransactions {
+    pub user_commands: Vec<GraphQLUserCommand>,
     pub zkapp_commands: Vec<GraphQLZkapp>,
 }
 
@@ -61,6 +65,7 @@ impl TryFrom<AppliedBlock> for GraphQLBestChainBlock {
         Ok(Self {
             protocol_state,
             state_hash: block.hash.to_string(),
+            creator: block.producer().to_string(),
             transactions: block.body().diff().clone().try_into()?,
         })
     }
@@ -115,19 +120,57 @@ impl TryFrom<mina_p2p_messages::v2::StagedLedgerDiffDiffDiffStableV2> for GraphQ
     fn try_from(
         value: mina_p2p_messages::v2::StagedLedgerDiffDiffDiffStableV2,
     ) -> Result<Self, Self::Error> {
-        use mina_p2p_messages::v2::{
-            MinaBaseTransactionStatusStableV2, MinaBaseUserCommandStableV2,
-        };
-
         let also_zkapp_commands = value
             .1
             .map_or_else(Vec::new, |v| v.commands.into_iter().collect::<Vec<_>>());
 
-        let zkapp_commands = value
+        value
             .0
             .commands
             .into_iter()
             .chain(also_zkapp_commands)
+            .collect::<Vec<_>>()
+            .try_into()
+    }
+}
+
+impl TryFrom<Vec<mina_p2p_messages::v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B>>
+    for GraphQLTransactions
+{
+    type Error = ConversionError;
+    fn try_from(
+        commands: Vec<
+            mina_p2p_messages::v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B,
+        >,
+    ) -> Result<Self, Self::Error> {
+        use mina_p2p_messages::v2::{
+            MinaBaseTransactionStatusStableV2, MinaBaseUserCommandStableV2,
+        };
+
+        let user_commands = commands
+            .iter()
+            .filter_map(|cmd| match &cmd.data {
+                MinaBaseUserCommandStableV2::SignedCommand(signed_cmd) => {
+                    Some((signed_cmd.clone(), &cmd.status))
+                }
+                MinaBaseUserCommandStableV2::ZkappCommand(_) => None,
+            })
+            .map(|(signed_cmd, status)| {
+                let mut user_command = GraphQLUserCommand::try_from(signed_cmd)?;
+                if let MinaBaseTransactionStatusStableV2::Failed(failure_collection) = status {
+                    user_command.failure_reason = failure_collection
+                        .0
+                        .iter()
+                        .flat_map(|failures| failures.iter())
+                        .next()
+                        .map(|failure| failure.to_string());
+                }
+                Ok(user_command)
+            })
+            .collect::<Result<Vec<_>, Self::Error>>()?;
+
+        let zkapp_commands = commands
+            .into_iter()
             .rev()
             .map(|cmd| {
                 // std::fs::create_dir_all("zkapps").unwrap();
@@ -186,7 +229,10 @@ impl TryFrom<mina_p2p_messages::v2::StagedLedgerDiffDiffDiffStableV2> for GraphQ
             .into_iter()
             .flatten()
             .collect::<Vec<_>>();
-        Ok(Self { zkapp_commands })
+        Ok(Self {
+            user_commands,
+            zkapp_commands,
+        })
     }
 }
 
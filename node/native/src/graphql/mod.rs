@@ -1,19 +1,34 @@
+use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::Arc;
 
+use futures::{future, Stream, StreamExt};
 use juniper::{graphql_value, FieldError};
-use juniper::{EmptySubscription, GraphQLEnum, RootNode};
-use ledger::Account;
+use juniper::{GraphQLEnum, GraphQLObject, RootNode};
+use juniper_graphql_ws::ConnectionConfig;
+use ledger::scan_state::currency::Nonce;
 use mina_p2p_messages::v2::MinaBaseSignedCommandStableV2;
 use mina_p2p_messages::v2::MinaBaseUserCommandStableV2;
 use mina_p2p_messages::v2::MinaBaseZkappCommandTStableV1WireStableV1;
 use mina_p2p_messages::v2::TokenIdKeyHash;
+use mina_p2p_messages::v2::TransactionHash;
 use node::rpc::RpcTransactionInjectResponse;
+use node::rpc::RpcTransactionInjectSuccess;
 use node::rpc::RpcTransactionInjectedCommand;
 use node::rpc::RpcTransactionStatusGetResponse;
+use node::rpc::{
+    GetBlockQuery, PooledCommandsQuery, RpcBestTipSubscribeResponse, RpcGenesisBlockResponse,
+    RpcGetBlockResponse, RpcLedgerAccountsResponse, RpcPooledUserCommandsResponse,
+    RpcPooledZkappCommandsResponse, RpcSnarkPoolGetResponse, RpcSnarkerConfigGetResponse,
+    RpcTransactionSimulateResponse, RpcWatchedAccount, RpcWatchedAccountSubscribeResponse,
+    RpcWatchedAccountsAddResponse, RpcWatchedAccountsGetResponse, RpcWatchedAccountsRemoveResponse,
+    RpcZkappEventsQuery, RpcZkappEventsResponse,
+};
 use node::{
     account::AccountPublicKey,
     rpc::{AccountQuery, RpcRequest, RpcSyncStatsGetResponse, SyncStatsQuery},
     stats::sync::SyncKind,
+    BuildEnv,
 };
 use openmina_core::block::AppliedBlock;
 use openmina_core::consensus::ConsensusConstants;
@@ -24,7 +39,15 @@ use warp::{Filter, Rejection, Reply};
 pub mod account;
 pub mod block;
 pub mod constants;
+pub mod transaction_simulation;
+pub mod user_command;
+pub mod watched_account;
 pub mod zkapp;
+pub mod zkapp_events;
+
+/// How many updates are buffered for a subscriber before it starts missing
+/// them.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 16;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
@@ -66,6 +89,7 @@ pub enum ConversionError {
     Custom(String),
 }
 
+#[derive(Clone)]
 struct Context(RpcSender);
 
 impl juniper::Context for Context {}
@@ -139,6 +163,24 @@ impl BestChain {
     }
 }
 
+#[derive(Clone, Copy, Debug, GraphQLEnum)]
+#[allow(clippy::upper_case_acronyms)]
+enum ChainReorganizationStatus {
+    CHANGED,
+}
+
+#[derive(GraphQLObject, Debug)]
+struct GraphQLSnarkWorker {
+    key: String,
+    fee: String,
+}
+
+#[derive(GraphQLObject, Debug)]
+struct GraphQLCompletedWork {
+    prover: String,
+    fee: String,
+}
+
 #[derive(Clone, Copy, Debug)]
 struct Query;
 
@@ -151,13 +193,15 @@ impl Query {
     ) -> juniper::FieldResult<account::GraphQLAccount> {
         let token_id = TokenIdKeyHash::from_str(&token)?;
         let public_key = AccountPublicKey::from_str(&public_key)?;
-        let accounts: Vec<Account> = context
+        let accounts: RpcLedgerAccountsResponse = context
             .0
             .oneshot_request(RpcRequest::LedgerAccountsGet(
                 AccountQuery::PubKeyWithTokenId(public_key, token_id),
+                None,
             ))
             .await
             .ok_or(Error::StateMachineEmptyResponse)?;
+        let accounts = accounts.map_err(|err| Error::Custom(err.to_string()))?;
 
         Ok(accounts
             .first()
@@ -264,6 +308,350 @@ impl Query {
             .ok_or(Error::StateMachineEmptyResponse)?;
         Ok(res.to_string())
     }
+
+    async fn block(
+        height: Option<i32>,
+        state_hash: Option<String>,
+        context: &Context,
+    ) -> juniper::FieldResult<block::GraphQLBestChainBlock> {
+        let query = match (height, state_hash) {
+            (Some(height), None) => GetBlockQuery::Height(height.try_into()?),
+            (None, Some(state_hash)) => GetBlockQuery::Hash(state_hash.parse()?),
+            _ => {
+                return Err(Error::Custom(
+                    "Must provide exactly one of state hash or height".to_string(),
+                )
+                .into())
+            }
+        };
+        let block: RpcGetBlockResponse = context
+            .0
+            .oneshot_request(RpcRequest::BlockGet(query))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(block
+            .ok_or_else(|| Error::Custom("Block not found in the transition frontier".to_string()))?
+            .try_into()?)
+    }
+
+    async fn genesis_block(
+        context: &Context,
+    ) -> juniper::FieldResult<block::GraphQLBestChainBlock> {
+        let block: RpcGenesisBlockResponse = context
+            .0
+            .oneshot_request(RpcRequest::GenesisBlockGet)
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+        let block = block.ok_or_else(|| Error::Custom("Genesis block not ready".to_string()))?;
+
+        Ok(AppliedBlock {
+            block,
+            just_emitted_a_proof: false,
+        }
+        .try_into()?)
+    }
+
+    async fn pooled_user_commands(
+        public_key: Option<String>,
+        hashes: Option<Vec<String>>,
+        ids: Option<Vec<String>>,
+        context: &Context,
+    ) -> juniper::FieldResult<Vec<user_command::GraphQLUserCommand>> {
+        let query = pooled_commands_query(public_key, hashes, ids, |id| {
+            Ok(MinaBaseSignedCommandStableV2::from_base64(id)?.hash()?)
+        })?;
+        let commands: RpcPooledUserCommandsResponse = context
+            .0
+            .oneshot_request(RpcRequest::PooledUserCommands(query))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(commands
+            .into_iter()
+            .map(|cmd| cmd.try_into())
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn pooled_zkapp_commands(
+        public_key: Option<String>,
+        hashes: Option<Vec<String>>,
+        ids: Option<Vec<String>>,
+        context: &Context,
+    ) -> juniper::FieldResult<Vec<zkapp::GraphQLZkapp>> {
+        let query = pooled_commands_query(public_key, hashes, ids, |id| {
+            Ok(MinaBaseZkappCommandTStableV1WireStableV1::from_base64(id)?.hash()?)
+        })?;
+        let commands: RpcPooledZkappCommandsResponse = context
+            .0
+            .oneshot_request(RpcRequest::PooledZkappCommands(query))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(commands
+            .into_iter()
+            .map(|cmd| cmd.try_into())
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    async fn snark_pool(context: &Context) -> juniper::FieldResult<Vec<GraphQLCompletedWork>> {
+        let jobs: RpcSnarkPoolGetResponse = context
+            .0
+            .oneshot_request(RpcRequest::SnarkPoolGet)
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(jobs
+            .into_iter()
+            .filter_map(|job| job.snark)
+            .map(|snark| GraphQLCompletedWork {
+                prover: snark.snarker.to_string(),
+                fee: snark.fee.as_u64().to_string(),
+            })
+            .collect())
+    }
+
+    async fn token_owner(
+        token_id: String,
+        context: &Context,
+    ) -> juniper::FieldResult<Option<account::GraphQLAccount>> {
+        let token_id = TokenIdKeyHash::from_str(&token_id)?;
+        let accounts: RpcLedgerAccountsResponse = context
+            .0
+            .oneshot_request(RpcRequest::LedgerAccountsGet(
+                AccountQuery::TokenOwner(token_id),
+                None,
+            ))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+        let accounts = accounts.map_err(|err| Error::Custom(err.to_string()))?;
+
+        Ok(accounts
+            .first()
+            .cloned()
+            .map(|account| account.try_into())
+            .transpose()?)
+    }
+
+    /// Accounts watched by the node, or only `public_key` if it's watched.
+    async fn watched_accounts(
+        public_key: Option<String>,
+        context: &Context,
+    ) -> juniper::FieldResult<Vec<watched_account::GraphQLWatchedAccount>> {
+        let public_key = public_key
+            .map(|public_key| AccountPublicKey::from_str(&public_key))
+            .transpose()?;
+        let accounts: RpcWatchedAccountsGetResponse = context
+            .0
+            .oneshot_request(RpcRequest::WatchedAccountsGet(public_key))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(accounts
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?)
+    }
+
+    /// Applies the command on top of the best tip without committing or
+    /// broadcasting it. Expects either `zkapp`, or `payment` or `delegation`
+    /// along with their `signature`.
+    async fn simulate_transaction(
+        zkapp: Option<zkapp::SendZkappInput>,
+        payment: Option<user_command::SendPaymentInput>,
+        delegation: Option<user_command::SendDelegationInput>,
+        signature: Option<user_command::SignatureInput>,
+        context: &Context,
+    ) -> juniper::FieldResult<transaction_simulation::GraphQLTransactionSimulation> {
+        let command: MinaBaseUserCommandStableV2 = match (zkapp, payment, delegation, signature) {
+            (Some(zkapp), None, None, None) => zkapp.try_into()?,
+            (None, Some(input), None, Some(signature)) => {
+                let nonce = match &input.nonce {
+                    Some(nonce) => Nonce::from_u32(nonce.parse()?),
+                    None => infer_nonce(context, &input.from).await?,
+                };
+                input.create_user_command(nonce, &signature)?
+            }
+            (None, None, Some(input), Some(signature)) => {
+                let nonce = match &input.nonce {
+                    Some(nonce) => Nonce::from_u32(nonce.parse()?),
+                    None => infer_nonce(context, &input.from).await?,
+                };
+                input.create_user_command(nonce, &signature)?
+            }
+            _ => {
+                return Err(Error::Custom(
+                    "expected either a zkapp, or a payment or a delegation with its signature"
+                        .to_owned(),
+                )
+                .into())
+            }
+        };
+        let simulation: RpcTransactionSimulateResponse = context
+            .0
+            .oneshot_request(RpcRequest::TransactionSimulate(command))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(simulation.map_err(Error::Custom)?.try_into()?)
+    }
+
+    /// Events emitted by the zkApp account, from the transition frontier
+    /// and from the archive if it's enabled.
+    async fn events(
+        input: zkapp_events::EventFilterOptionsInput,
+        context: &Context,
+    ) -> juniper::FieldResult<Vec<zkapp_events::GraphQLEventOutput>> {
+        let blocks = zkapp_events_get(context, input.try_into()?).await?;
+        Ok(zkapp_events::events_output(&blocks))
+    }
+
+    /// Actions added to the action state of the zkApp account, from the
+    /// transition frontier and from the archive if it's enabled.
+    async fn actions(
+        input: zkapp_events::ActionFilterOptionsInput,
+        context: &Context,
+    ) -> juniper::FieldResult<Vec<zkapp_events::GraphQLActionOutput>> {
+        let blocks = zkapp_events_get(context, input.try_into()?).await?;
+        Ok(zkapp_events::actions_output(&blocks))
+    }
+
+    fn version() -> String {
+        BuildEnv::get().git.commit_hash
+    }
+
+    async fn current_snark_worker(
+        context: &Context,
+    ) -> juniper::FieldResult<Option<GraphQLSnarkWorker>> {
+        let config: RpcSnarkerConfigGetResponse = context
+            .0
+            .oneshot_request(RpcRequest::SnarkerConfig)
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+
+        Ok(config.map(|config| GraphQLSnarkWorker {
+            key: config.public_key.to_string(),
+            fee: config.fee.as_u64().to_string(),
+        }))
+    }
+}
+
+fn pooled_commands_query(
+    public_key: Option<String>,
+    hashes: Option<Vec<String>>,
+    ids: Option<Vec<String>>,
+    id_to_hash: impl Fn(&str) -> Result<TransactionHash, ConversionError>,
+) -> Result<PooledCommandsQuery, ConversionError> {
+    let public_key = public_key
+        .map(|public_key| AccountPublicKey::from_str(&public_key))
+        .transpose()?;
+    let hashes = hashes
+        .map(|hashes| {
+            hashes
+                .iter()
+                .map(|hash| {
+                    TransactionHash::from_str(hash)
+                        .map_err(|err| ConversionError::Custom(err.to_string()))
+                })
+                .collect::<Result<Vec<_>, ConversionError>>()
+        })
+        .transpose()?;
+    let id_hashes = ids
+        .map(|ids| {
+            ids.iter()
+                .map(|id| id_to_hash(id))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?;
+    let hashes = match (hashes, id_hashes) {
+        (Some(hashes), Some(id_hashes)) => Some(hashes.into_iter().chain(id_hashes).collect()),
+        (hashes, id_hashes) => hashes.or(id_hashes),
+    };
+
+    Ok(PooledCommandsQuery { public_key, hashes })
+}
+
+/// Events and actions of the zkApp account matching the query.
+async fn zkapp_events_get(
+    context: &Context,
+    query: RpcZkappEventsQuery,
+) -> Result<Vec<node::rpc::RpcZkappBlockEvents>, Error> {
+    let events: RpcZkappEventsResponse = context
+        .0
+        .oneshot_request(RpcRequest::ZkappEventsGet(query))
+        .await
+        .ok_or(Error::StateMachineEmptyResponse)?;
+    events.map_err(Error::Custom)
+}
+
+/// Next nonce of the account, taking into account the commands that are
+/// already in the transaction pool.
+async fn infer_nonce(context: &Context, public_key: &str) -> juniper::FieldResult<Nonce> {
+    let public_key = AccountPublicKey::from_str(public_key)?;
+    let accounts: RpcLedgerAccountsResponse = context
+        .0
+        .oneshot_request(RpcRequest::LedgerAccountsGet(
+            AccountQuery::PubKeyWithTokenId(public_key.clone(), TokenIdKeyHash::default()),
+            None,
+        ))
+        .await
+        .ok_or(Error::StateMachineEmptyResponse)?;
+    let accounts = accounts.map_err(|err| Error::Custom(err.to_string()))?;
+    let account = accounts
+        .first()
+        .ok_or_else(|| Error::Custom("Account not found".to_string()))?;
+
+    let pooled: RpcPooledUserCommandsResponse = context
+        .0
+        .oneshot_request(RpcRequest::PooledUserCommands(PooledCommandsQuery {
+            public_key: Some(public_key),
+            hashes: None,
+        }))
+        .await
+        .ok_or(Error::StateMachineEmptyResponse)?;
+
+    let nonce = pooled
+        .iter()
+        .map(|cmd| cmd.payload.common.nonce.as_u32().saturating_add(1))
+        .fold(account.nonce.as_u32(), u32::max);
+    Ok(Nonce::from_u32(nonce))
+}
+
+async fn inject_user_command(
+    command: MinaBaseUserCommandStableV2,
+    context: &Context,
+) -> juniper::FieldResult<RpcTransactionInjectSuccess> {
+    let res: RpcTransactionInjectResponse = context
+        .0
+        .oneshot_request(RpcRequest::TransactionInject(vec![command]))
+        .await
+        .ok_or(Error::StateMachineEmptyResponse)?;
+
+    match res {
+        RpcTransactionInjectResponse::Success(res) => Ok(res),
+        RpcTransactionInjectResponse::Rejected(rejected) => {
+            let error_list = rejected
+                .into_iter()
+                .map(|(_, err)| graphql_value!({ "message": err.to_string() }))
+                .collect::<Vec<_>>();
+
+            Err(FieldError::new(
+                "Transaction rejected",
+                graphql_value!(juniper::Value::List(error_list)),
+            ))
+        }
+        RpcTransactionInjectResponse::Failure(failure) => {
+            let error_list = failure
+                .into_iter()
+                .map(|err| graphql_value!({ "message": err.to_string() }))
+                .collect::<Vec<_>>();
+
+            Err(FieldError::new(
+                "Transaction failed",
+                graphql_value!(juniper::Value::List(error_list)),
+            ))
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -275,56 +663,199 @@ impl Mutation {
         input: zkapp::SendZkappInput,
         context: &Context,
     ) -> juniper::FieldResult<zkapp::GraphQLSendZkappResponse> {
-        let res: RpcTransactionInjectResponse = context
+        let res = inject_user_command(input.try_into()?, context).await?;
+        let zkapp_cmd: MinaBaseUserCommandStableV2 = match res.first().cloned() {
+            Some(RpcTransactionInjectedCommand::Zkapp(zkapp_cmd)) => zkapp_cmd.into(),
+            _ => unreachable!(),
+        };
+        Ok(zkapp_cmd.try_into()?)
+    }
+
+    async fn send_payment(
+        input: user_command::SendPaymentInput,
+        signature: user_command::SignatureInput,
+        context: &Context,
+    ) -> juniper::FieldResult<user_command::GraphQLSendPaymentResponse> {
+        let nonce = match &input.nonce {
+            Some(nonce) => Nonce::from_u32(nonce.parse()?),
+            None => infer_nonce(context, &input.from).await?,
+        };
+        let command = input.create_user_command(nonce, &signature)?;
+        inject_user_command(command.clone(), context).await?;
+
+        let MinaBaseUserCommandStableV2::SignedCommand(command) = command else {
+            unreachable!()
+        };
+        Ok(user_command::GraphQLSendPaymentResponse {
+            payment: command.try_into()?,
+        })
+    }
+
+    async fn send_delegation(
+        input: user_command::SendDelegationInput,
+        signature: user_command::SignatureInput,
+        context: &Context,
+    ) -> juniper::FieldResult<user_command::GraphQLSendDelegationResponse> {
+        let nonce = match &input.nonce {
+            Some(nonce) => Nonce::from_u32(nonce.parse()?),
+            None => infer_nonce(context, &input.from).await?,
+        };
+        let command = input.create_user_command(nonce, &signature)?;
+        inject_user_command(command.clone(), context).await?;
+
+        let MinaBaseUserCommandStableV2::SignedCommand(command) = command else {
+            unreachable!()
+        };
+        Ok(user_command::GraphQLSendDelegationResponse {
+            delegation: command.try_into()?,
+        })
+    }
+
+    /// Starts watching the account, returns `false` if it was already watched.
+    async fn add_watched_account(
+        public_key: String,
+        context: &Context,
+    ) -> juniper::FieldResult<bool> {
+        let public_key = AccountPublicKey::from_str(&public_key)?;
+        let added: RpcWatchedAccountsAddResponse = context
             .0
-            .oneshot_request(RpcRequest::TransactionInject(vec![input.try_into()?]))
+            .oneshot_request(RpcRequest::WatchedAccountsAdd(public_key))
             .await
             .ok_or(Error::StateMachineEmptyResponse)?;
+        Ok(added)
+    }
 
-        match res {
-            RpcTransactionInjectResponse::Success(res) => {
-                let zkapp_cmd: MinaBaseUserCommandStableV2 = match res.first().cloned() {
-                    Some(RpcTransactionInjectedCommand::Zkapp(zkapp_cmd)) => zkapp_cmd.into(),
-                    _ => unreachable!(),
-                };
-                Ok(zkapp_cmd.try_into()?)
-            }
-            RpcTransactionInjectResponse::Rejected(rejected) => {
-                let error_list = rejected
-                    .into_iter()
-                    .map(|(_, err)| graphql_value!({ "message": err.to_string() }))
-                    .collect::<Vec<_>>();
-
-                Err(FieldError::new(
-                    "Transaction rejected",
-                    graphql_value!(juniper::Value::List(error_list)),
-                ))
-            }
-            RpcTransactionInjectResponse::Failure(failure) => {
-                let error_list = failure
-                    .into_iter()
-                    .map(|err| graphql_value!({ "message": err.to_string() }))
-                    .collect::<Vec<_>>();
-
-                Err(FieldError::new(
-                    "Transaction failed",
-                    graphql_value!(juniper::Value::List(error_list)),
-                ))
-            }
+    /// Stops watching the account, returns `false` if it wasn't watched.
+    async fn remove_watched_account(
+        public_key: String,
+        context: &Context,
+    ) -> juniper::FieldResult<bool> {
+        let public_key = AccountPublicKey::from_str(&public_key)?;
+        let removed: RpcWatchedAccountsRemoveResponse = context
+            .0
+            .oneshot_request(RpcRequest::WatchedAccountsRemove(public_key))
+            .await
+            .ok_or(Error::StateMachineEmptyResponse)?;
+        Ok(removed)
+    }
+}
+
+type GraphQLStream<T> = Pin<Box<dyn Stream<Item = juniper::FieldResult<T>> + Send>>;
+
+#[derive(Clone, Copy, Debug)]
+struct Subscription;
+
+#[juniper::graphql_subscription(context = Context)]
+impl Subscription {
+    /// New best tip, optionally only the ones produced by `public_key`.
+    async fn new_block(
+        public_key: Option<String>,
+        context: &Context,
+    ) -> juniper::FieldResult<GraphQLStream<block::GraphQLBestChainBlock>> {
+        if let Some(public_key) = &public_key {
+            AccountPublicKey::from_str(public_key)?;
         }
+        let stream = best_tip_updates(context.0.clone())
+            .filter(move |(_, best_tip)| {
+                future::ready(public_key.as_ref().map_or(true, |public_key| {
+                    &best_tip.producer().to_string() == public_key
+                }))
+            })
+            .map(|(_, best_tip)| {
+                block::GraphQLBestChainBlock::try_from(best_tip).map_err(FieldError::from)
+            });
+        Ok(Box::pin(stream))
+    }
+
+    /// Emitted when the new best tip isn't a child of the previous one.
+    async fn chain_reorganization(context: &Context) -> GraphQLStream<ChainReorganizationStatus> {
+        let stream = best_tip_updates(context.0.clone())
+            .filter(|(prev, best_tip)| {
+                future::ready(
+                    prev.as_ref()
+                        .map_or(false, |prev| best_tip.pred_hash() != prev.hash()),
+                )
+            })
+            .map(|_| Ok::<_, FieldError>(ChainReorganizationStatus::CHANGED));
+        Box::pin(stream)
+    }
+
+    /// Watched account every time it's read from the ledger of a new best
+    /// tip. Ends when the account is no longer watched.
+    async fn watched_account_updated(
+        public_key: String,
+        context: &Context,
+    ) -> juniper::FieldResult<GraphQLStream<watched_account::GraphQLWatchedAccount>> {
+        let public_key = AccountPublicKey::from_str(&public_key)?;
+        let stream = watched_account_updates(context.0.clone(), public_key).map(|account| {
+            watched_account::GraphQLWatchedAccount::try_from(account).map_err(FieldError::from)
+        });
+        Ok(Box::pin(stream))
     }
 }
 
+/// Subscribes to the best tip and yields `(previous, new)` pair every time
+/// it changes. The previous one is `None` for the first update.
+fn best_tip_updates(
+    rpc_sender: RpcSender,
+) -> impl Stream<Item = (Option<AppliedBlock>, AppliedBlock)> + Send {
+    subscribe::<RpcBestTipSubscribeResponse>(rpc_sender, RpcRequest::BestTipSubscribe).scan(
+        None::<AppliedBlock>,
+        |prev, best_tip| {
+            let prev = prev.replace(best_tip.clone());
+            future::ready(Some((prev, best_tip)))
+        },
+    )
+}
+
+/// Subscribes to the watched account and yields it every time it's read
+/// from the ledger. Ends when the account is no longer watched.
+fn watched_account_updates(
+    rpc_sender: RpcSender,
+    public_key: AccountPublicKey,
+) -> impl Stream<Item = RpcWatchedAccount> + Send {
+    subscribe::<RpcWatchedAccountSubscribeResponse>(
+        rpc_sender,
+        RpcRequest::WatchedAccountSubscribe(public_key),
+    )
+}
+
+/// Sends the subscription request and turns the updates pushed by the
+/// state machine into a stream, which ends when the subscription does.
+fn subscribe<T>(rpc_sender: RpcSender, req: RpcRequest) -> impl Stream<Item = T> + Send
+where
+    T: 'static + Send + serde::Serialize,
+{
+    futures::stream::once(async move {
+        rpc_sender
+            .multishot_request::<T>(SUBSCRIPTION_BUFFER_SIZE, req)
+            .await
+    })
+    .flat_map(|rx| {
+        futures::stream::unfold(rx, |mut rx| async move {
+            let update = rx.recv().await?;
+            Some((update, rx))
+        })
+    })
+}
+
 pub fn routes(
     rpc_sernder: RpcSender,
 ) -> impl Filter<Error = Rejection, Extract = impl Reply> + Clone {
-    let state = warp::any().map(move || Context(rpc_sernder.clone()));
-    let schema = RootNode::new(Query, Mutation, EmptySubscription::<Context>::new());
-    let graphql_filter = juniper_warp::make_graphql_filter(schema, state.boxed());
-    let graphiql_filter = juniper_warp::graphiql_filter("/graphql", None);
-    let playground_filter = juniper_warp::playground_filter("/graphql", None);
-
-    (warp::post().and(warp::path("graphql")).and(graphql_filter))
+    let context = Context(rpc_sernder);
+    let state = {
+        let context = context.clone();
+        warp::any().map(move || context.clone())
+    };
+    let schema = Arc::new(RootNode::new(Query, Mutation, Subscription));
+    let graphql_filter = juniper_warp::make_graphql_filter(schema.clone(), state.boxed());
+    let subscriptions_filter =
+        juniper_warp::subscriptions::make_ws_filter(schema, ConnectionConfig::new(context));
+    let graphiql_filter = juniper_warp::graphiql_filter("/graphql", Some("/graphql"));
+    let playground_filter = juniper_warp::playground_filter("/graphql", Some("/graphql"));
+
+    (warp::path("graphql").and(subscriptions_filter))
+        .or(warp::post().and(warp::path("graphql")).and(graphql_filter))
         .or(warp::get()
             .and(warp::path("playground"))
             .and(playground_filter))
@@ -362,3 +893,80 @@ pub fn routes(
 //         )))
 //     .or(homepage)
 //     .with(log);
+
+#[cfg(test)]
+mod tests {
+    use mina_p2p_messages::v2::{LedgerHash, StateHash};
+    use node::account::AccountSecretKey;
+    use node::core::channels::mpsc;
+    use node::watched_accounts::WatchedAccountBlockInfo;
+    use openmina_node_common::rpc::NodeRpcRequest;
+
+    use super::*;
+
+    fn update(public_key: &AccountPublicKey, level: u32) -> RpcWatchedAccount {
+        RpcWatchedAccount {
+            public_key: public_key.clone(),
+            block: Some(WatchedAccountBlockInfo {
+                level,
+                hash: StateHash::zero(),
+                pred_hash: StateHash::zero(),
+                staged_ledger_hash: LedgerHash::zero(),
+            }),
+            account: None,
+            blocks: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn watched_account_updates_until_unwatched() {
+        let (tx, mut rx) = mpsc::channel::<NodeRpcRequest>(1);
+        let public_key = AccountSecretKey::deterministic(0).public_key();
+
+        let node = {
+            let public_key = public_key.clone();
+            tokio::spawn(async move {
+                let NodeRpcRequest { req, responder } = rx.recv().await.unwrap();
+                assert!(matches!(
+                    req,
+                    RpcRequest::WatchedAccountSubscribe(key) if key == public_key
+                ));
+                let updates = responder
+                    .downcast::<mpsc::Sender<RpcWatchedAccountSubscribeResponse>>()
+                    .unwrap();
+                for level in 1..=3 {
+                    updates.send(update(&public_key, level)).await.unwrap();
+                }
+                // dropped once the account is no longer watched, which ends
+                // the stream.
+            })
+        };
+
+        let updates = watched_account_updates(RpcSender::new(tx), public_key.clone())
+            .map(|account| watched_account::GraphQLWatchedAccount::try_from(account).unwrap())
+            .collect::<Vec<_>>()
+            .await;
+        node.await.unwrap();
+
+        let levels = updates
+            .iter()
+            .map(|account| account.blockchain_length)
+            .collect::<Vec<_>>();
+        assert_eq!(levels, [Some(1), Some(2), Some(3)]);
+        assert!(updates
+            .iter()
+            .all(|account| account.public_key == public_key.to_string()));
+    }
+
+    #[tokio::test]
+    async fn watched_account_updates_end_with_the_node() {
+        let (tx, rx) = mpsc::channel::<NodeRpcRequest>(1);
+        drop(rx);
+        let public_key = AccountSecretKey::deterministic(0).public_key();
+
+        let updates = watched_account_updates(RpcSender::new(tx), public_key)
+            .collect::<Vec<_>>()
+            .await;
+        assert!(updates.is_empty());
+    }
+}
@@ -0,0 +1,69 @@
+use juniper::GraphQLObject;
+use ledger::scan_state::transaction_logic::TransactionStatus;
+use mina_p2p_messages::v2::TokenIdKeyHash;
+use node::account::AccountPublicKey;
+use node::rpc::{RpcAccountDiff, RpcTransactionSimulation};
+
+use super::account::GraphQLAccount;
+use super::ConversionError;
+
+#[derive(GraphQLObject, Debug)]
+#[graphql(
+    description = "Result of applying a command on top of the best tip, without committing it"
+)]
+pub struct GraphQLTransactionSimulation {
+    /// State hash of the best tip the command was applied on top of
+    pub state_hash: String,
+    pub applied: bool,
+    /// Failures of each account update, starting with the fee payer
+    pub failures: Vec<Vec<String>>,
+    /// Fee charged to the fee payer, even if the command failed
+    pub fee: String,
+    /// Accounts changed by the command
+    pub account_diffs: Vec<GraphQLAccountDiff>,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLAccountDiff {
+    pub public_key: String,
+    pub token_id: String,
+    /// Null if the account was created by the command
+    pub before: Option<GraphQLAccount>,
+    pub after: Option<GraphQLAccount>,
+}
+
+impl TryFrom<RpcTransactionSimulation> for GraphQLTransactionSimulation {
+    type Error = ConversionError;
+    fn try_from(value: RpcTransactionSimulation) -> Result<Self, Self::Error> {
+        let failures = match &value.status {
+            TransactionStatus::Applied => Vec::new(),
+            TransactionStatus::Failed(failures) => failures
+                .iter()
+                .map(|failures| failures.iter().map(ToString::to_string).collect())
+                .collect(),
+        };
+        Ok(Self {
+            state_hash: value.block_hash.to_string(),
+            applied: value.status.is_applied(),
+            failures,
+            fee: value.fee.as_u64().to_string(),
+            account_diffs: value
+                .account_diffs
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<RpcAccountDiff> for GraphQLAccountDiff {
+    type Error = ConversionError;
+    fn try_from(value: RpcAccountDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
+            public_key: AccountPublicKey::from(value.account_id.public_key).to_string(),
+            token_id: TokenIdKeyHash::from(value.account_id.token_id).to_string(),
+            before: value.before.map(TryInto::try_into).transpose()?,
+            after: value.after.map(TryInto::try_into).transpose()?,
+        })
+    }
+}
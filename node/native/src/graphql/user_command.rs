@@ -0,0 +1,263 @@
+use std::str::FromStr;
+
+use juniper::{GraphQLInputObject, GraphQLObject};
+use ledger::scan_state::currency::{Amount, Fee, Nonce, Slot};
+use ledger::scan_state::transaction_logic::{signed_command, Memo};
+use mina_p2p_messages::bigint::BigInt;
+use mina_p2p_messages::v2::{
+    MinaBaseSignedCommandPayloadBodyStableV2, MinaBaseSignedCommandStableV2,
+    MinaBaseStakeDelegationStableV2, MinaBaseUserCommandStableV2,
+};
+use mina_signer::CompressedPubKey;
+use node::account::AccountPublicKey;
+
+use super::ConversionError;
+
+#[derive(GraphQLInputObject, Debug)]
+pub struct SendPaymentInput {
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+    pub fee: String,
+    pub valid_until: Option<String>,
+    pub memo: Option<String>,
+    /// Inferred from the ledger and the transaction pool if not provided.
+    pub nonce: Option<String>,
+}
+
+#[derive(GraphQLInputObject, Debug)]
+pub struct SendDelegationInput {
+    pub from: String,
+    pub to: String,
+    pub fee: String,
+    pub valid_until: Option<String>,
+    pub memo: Option<String>,
+    /// Inferred from the ledger and the transaction pool if not provided.
+    pub nonce: Option<String>,
+}
+
+#[derive(GraphQLInputObject, Debug)]
+pub struct SignatureInput {
+    /// Field component of the signature, as a decimal string.
+    pub field: String,
+    /// Scalar component of the signature, as a decimal string.
+    pub scalar: String,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLSendPaymentResponse {
+    pub payment: GraphQLUserCommand,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLSendDelegationResponse {
+    pub delegation: GraphQLUserCommand,
+}
+
+#[derive(GraphQLObject, Debug)]
+#[graphql(description = "A payment or a stake delegation")]
+pub struct GraphQLUserCommand {
+    /// Command represented as base64 string
+    pub id: String,
+    pub hash: String,
+    pub kind: String,
+    pub nonce: i32,
+    pub from: String,
+    pub to: String,
+    pub amount: String,
+    pub fee: String,
+    pub memo: String,
+    pub valid_until: String,
+    pub is_delegation: bool,
+    pub failure_reason: Option<String>,
+}
+
+impl SendPaymentInput {
+    pub fn create_user_command(
+        &self,
+        nonce: Nonce,
+        signature: &SignatureInput,
+    ) -> Result<MinaBaseUserCommandStableV2, ConversionError> {
+        let body = signed_command::Body::Payment(signed_command::PaymentPayload {
+            receiver_pk: parse_public_key(&self.to)?,
+            amount: Amount::from_u64(self.amount.parse()?),
+        });
+        create_signed_command(
+            &self.from,
+            &self.fee,
+            nonce,
+            self.valid_until.as_deref(),
+            self.memo.as_deref(),
+            body,
+            signature,
+        )
+    }
+}
+
+impl SendDelegationInput {
+    pub fn create_user_command(
+        &self,
+        nonce: Nonce,
+        signature: &SignatureInput,
+    ) -> Result<MinaBaseUserCommandStableV2, ConversionError> {
+        let body = signed_command::Body::StakeDelegation(
+            signed_command::StakeDelegationPayload::SetDelegate {
+                new_delegate: parse_public_key(&self.to)?,
+            },
+        );
+        create_signed_command(
+            &self.from,
+            &self.fee,
+            nonce,
+            self.valid_until.as_deref(),
+            self.memo.as_deref(),
+            body,
+            signature,
+        )
+    }
+}
+
+fn create_signed_command(
+    from: &str,
+    fee: &str,
+    nonce: Nonce,
+    valid_until: Option<&str>,
+    memo: Option<&str>,
+    body: signed_command::Body,
+    signature: &SignatureInput,
+) -> Result<MinaBaseUserCommandStableV2, ConversionError> {
+    let from = parse_public_key(from)?;
+    let valid_until = valid_until
+        .map(|slot| slot.parse().map(Slot::from_u32))
+        .transpose()?;
+    let memo = Memo::from_str(memo.unwrap_or_default())
+        .map_err(|_| ConversionError::Custom("Invalid memo".to_string()))?;
+    let signature = mina_signer::Signature {
+        rx: BigInt::from_decimal(&signature.field)?
+            .try_into()
+            .map_err(|_| ConversionError::InvalidBigInt)?,
+        s: BigInt::from_decimal(&signature.scalar)?
+            .try_into()
+            .map_err(|_| ConversionError::InvalidBigInt)?,
+    };
+
+    let command = signed_command::SignedCommand {
+        payload: signed_command::SignedCommandPayload::create(
+            Fee::from_u64(fee.parse()?),
+            from.clone(),
+            nonce,
+            valid_until,
+            memo,
+            body,
+        ),
+        signer: from,
+        signature,
+    };
+    Ok(MinaBaseUserCommandStableV2::SignedCommand(command.into()))
+}
+
+pub fn parse_public_key(public_key: &str) -> Result<CompressedPubKey, ConversionError> {
+    AccountPublicKey::from_str(public_key)?
+        .try_into()
+        .map_err(|_| ConversionError::Custom(format!("Invalid public key: {public_key}")))
+}
+
+impl TryFrom<MinaBaseSignedCommandStableV2> for GraphQLUserCommand {
+    type Error = ConversionError;
+    fn try_from(value: MinaBaseSignedCommandStableV2) -> Result<Self, Self::Error> {
+        let common = &value.payload.common;
+        let (kind, to, amount, is_delegation) = match &value.payload.body {
+            MinaBaseSignedCommandPayloadBodyStableV2::Payment(payment) => (
+                "PAYMENT",
+                payment.receiver_pk.to_string(),
+                payment.amount.as_u64(),
+                false,
+            ),
+            MinaBaseSignedCommandPayloadBodyStableV2::StakeDelegation(
+                MinaBaseStakeDelegationStableV2::SetDelegate { new_delegate },
+            ) => ("STAKE_DELEGATION", new_delegate.to_string(), 0, true),
+        };
+
+        Ok(Self {
+            id: value.to_base64()?,
+            hash: value.hash()?.to_string(),
+            kind: kind.to_string(),
+            nonce: common.nonce.as_u32() as i32,
+            from: common.fee_payer_pk.to_string(),
+            to,
+            amount: amount.to_string(),
+            fee: common.fee.as_u64().to_string(),
+            memo: common.memo.to_base58check(),
+            valid_until: common.valid_until.as_u32().to_string(),
+            is_delegation,
+            failure_reason: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PUBLIC_KEY: &str = "B62qpD75xH5R19wxZG2uz8whNsHPTioVoYcPV3zfjjSbzTmaHQHKKEV";
+
+    #[test]
+    fn test_send_payment_input_into_user_command() {
+        let input = SendPaymentInput {
+            from: PUBLIC_KEY.to_string(),
+            to: PUBLIC_KEY.to_string(),
+            amount: "1000000000".to_string(),
+            fee: "10000000".to_string(),
+            valid_until: None,
+            memo: Some("hello".to_string()),
+            nonce: None,
+        };
+        let signature = SignatureInput {
+            field: "1".to_string(),
+            scalar: "2".to_string(),
+        };
+        let command = input
+            .create_user_command(Nonce::from_u32(3), &signature)
+            .expect("Failed to create command");
+        let MinaBaseUserCommandStableV2::SignedCommand(command) = command else {
+            panic!("Expected signed command");
+        };
+        let command = GraphQLUserCommand::try_from(command).expect("Failed to convert command");
+
+        assert_eq!(command.kind, "PAYMENT");
+        assert_eq!(command.nonce, 3);
+        assert_eq!(command.from, PUBLIC_KEY);
+        assert_eq!(command.to, PUBLIC_KEY);
+        assert_eq!(command.amount, "1000000000");
+        assert_eq!(command.fee, "10000000");
+        assert!(!command.is_delegation);
+    }
+
+    #[test]
+    fn test_send_delegation_input_into_user_command() {
+        let input = SendDelegationInput {
+            from: PUBLIC_KEY.to_string(),
+            to: PUBLIC_KEY.to_string(),
+            fee: "10000000".to_string(),
+            valid_until: Some("100".to_string()),
+            memo: None,
+            nonce: None,
+        };
+        let signature = SignatureInput {
+            field: "1".to_string(),
+            scalar: "2".to_string(),
+        };
+        let command = input
+            .create_user_command(Nonce::from_u32(0), &signature)
+            .expect("Failed to create command");
+        let MinaBaseUserCommandStableV2::SignedCommand(command) = command else {
+            panic!("Expected signed command");
+        };
+        let command = GraphQLUserCommand::try_from(command).expect("Failed to convert command");
+
+        assert_eq!(command.kind, "STAKE_DELEGATION");
+        assert_eq!(command.valid_until, "100");
+        assert_eq!(command.amount, "0");
+        assert!(command.is_delegation);
+    }
+}
@@ -0,0 +1,85 @@
+use juniper::GraphQLObject;
+use mina_p2p_messages::v2::{
+    MinaBaseAccountBinableArgStableV2, StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B,
+};
+use node::rpc::RpcWatchedAccount;
+use node::watched_accounts::WatchedAccountBlockState;
+
+use super::account::GraphQLAccount;
+use super::block::GraphQLTransactions;
+use super::ConversionError;
+
+#[derive(GraphQLObject, Debug)]
+#[graphql(description = "An account watched by the node")]
+pub struct GraphQLWatchedAccount {
+    pub public_key: String,
+    /// State hash of the block at which the account was last read
+    pub state_hash: Option<String>,
+    pub blockchain_length: Option<i32>,
+    /// Account at the block, null if it isn't in the ledger or wasn't read yet
+    pub account: Option<GraphQLAccount>,
+    /// Recent blocks affecting the account, from the oldest
+    pub blocks: Vec<GraphQLWatchedAccountBlock>,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLWatchedAccountBlock {
+    pub state_hash: String,
+    pub blockchain_length: i32,
+    /// Transactions of the block affecting the account
+    pub transactions: GraphQLTransactions,
+    /// Account after the block, null if it isn't in the ledger or wasn't read yet
+    pub account: Option<GraphQLAccount>,
+}
+
+impl TryFrom<RpcWatchedAccount> for GraphQLWatchedAccount {
+    type Error = ConversionError;
+    fn try_from(value: RpcWatchedAccount) -> Result<Self, Self::Error> {
+        Ok(Self {
+            public_key: value.public_key.to_string(),
+            state_hash: value.block.as_ref().map(|block| block.hash.to_string()),
+            blockchain_length: value
+                .block
+                .as_ref()
+                .map(|block| block.level.try_into())
+                .transpose()?,
+            account: value.account.as_ref().map(graphql_account).transpose()?,
+            blocks: value
+                .blocks
+                .iter()
+                .map(GraphQLWatchedAccountBlock::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<&WatchedAccountBlockState> for GraphQLWatchedAccountBlock {
+    type Error = ConversionError;
+    fn try_from(value: &WatchedAccountBlockState) -> Result<Self, Self::Error> {
+        let block = value.block();
+        let commands = value
+            .transactions()
+            .iter()
+            .map(
+                |tx| StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B {
+                    data: tx.data.clone(),
+                    status: tx.status.clone(),
+                },
+            )
+            .collect::<Vec<_>>();
+        Ok(Self {
+            state_hash: block.hash.to_string(),
+            blockchain_length: block.level.try_into()?,
+            transactions: commands.try_into()?,
+            account: value.ledger_account().map(graphql_account).transpose()?,
+        })
+    }
+}
+
+fn graphql_account(
+    account: &MinaBaseAccountBinableArgStableV2,
+) -> Result<GraphQLAccount, ConversionError> {
+    ledger::Account::try_from(account)
+        .map_err(|_| ConversionError::InvalidBigInt)?
+        .try_into()
+}
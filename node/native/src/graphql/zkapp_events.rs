@@ -0,0 +1,487 @@
+use std::str::FromStr;
+
+use juniper::{GraphQLEnum, GraphQLInputObject, GraphQLObject};
+use mina_p2p_messages::bigint::BigInt;
+use mina_p2p_messages::v2::{MinaBaseTransactionStatusStableV2, TokenIdKeyHash};
+use node::account::AccountPublicKey;
+use node::ledger::ZkappAccountUpdateEvents;
+use node::rpc::{RpcZkappBlockEvents, RpcZkappChainStatus, RpcZkappEventsQuery};
+
+use super::ConversionError;
+
+#[derive(Clone, Copy, Debug, GraphQLEnum)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum BlockStatusFilter {
+    ALL,
+    PENDING,
+    CANONICAL,
+}
+
+#[derive(GraphQLInputObject, Debug)]
+pub struct EventFilterOptionsInput {
+    pub address: String,
+    pub token_id: Option<String>,
+    pub status: Option<BlockStatusFilter>,
+    /// Lowest block height, inclusive.
+    pub from: Option<i32>,
+    /// Highest block height, exclusive.
+    pub to: Option<i32>,
+}
+
+#[derive(GraphQLInputObject, Debug)]
+pub struct ActionFilterOptionsInput {
+    pub address: String,
+    pub token_id: Option<String>,
+    pub status: Option<BlockStatusFilter>,
+    /// Lowest block height, inclusive.
+    pub from: Option<i32>,
+    /// Highest block height, exclusive.
+    pub to: Option<i32>,
+    /// Only the actions added after this action state.
+    pub from_action_state: Option<String>,
+    /// Only the actions added up to this action state, inclusive.
+    pub end_action_state: Option<String>,
+}
+
+impl TryFrom<EventFilterOptionsInput> for RpcZkappEventsQuery {
+    type Error = ConversionError;
+    fn try_from(value: EventFilterOptionsInput) -> Result<Self, Self::Error> {
+        ActionFilterOptionsInput {
+            address: value.address,
+            token_id: value.token_id,
+            status: value.status,
+            from: value.from,
+            to: value.to,
+            from_action_state: None,
+            end_action_state: None,
+        }
+        .try_into()
+    }
+}
+
+impl TryFrom<ActionFilterOptionsInput> for RpcZkappEventsQuery {
+    type Error = ConversionError;
+    fn try_from(value: ActionFilterOptionsInput) -> Result<Self, Self::Error> {
+        let height = |height: Option<i32>| height.map(u32::try_from).transpose();
+        let action_state =
+            |state: Option<String>| state.as_deref().map(BigInt::from_decimal).transpose();
+        Ok(Self {
+            public_key: AccountPublicKey::from_str(&value.address)?,
+            token_id: value
+                .token_id
+                .as_deref()
+                .map(TokenIdKeyHash::from_str)
+                .transpose()?,
+            status: match value.status {
+                None | Some(BlockStatusFilter::ALL) => None,
+                Some(BlockStatusFilter::PENDING) => Some(RpcZkappChainStatus::Pending),
+                Some(BlockStatusFilter::CANONICAL) => Some(RpcZkappChainStatus::Canonical),
+            },
+            from: height(value.from)?,
+            to: height(value.to)?,
+            from_action_state: action_state(value.from_action_state)?,
+            end_action_state: action_state(value.end_action_state)?,
+        })
+    }
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLBlockInfo {
+    pub height: i32,
+    pub state_hash: String,
+    pub parent_hash: String,
+    /// Either `pending` or `canonical`
+    pub chain_status: String,
+    pub timestamp: String,
+    pub global_slot_since_genesis: i32,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLTransactionInfo {
+    pub status: String,
+    pub hash: String,
+    pub memo: String,
+    /// Index of the command among the user commands of the block
+    pub sequence_number: i32,
+    pub zkapp_account_update_ids: Vec<i32>,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLEventData {
+    pub account_update_id: String,
+    pub transaction_info: GraphQLTransactionInfo,
+    pub data: Vec<String>,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLEventOutput {
+    pub block_info: GraphQLBlockInfo,
+    pub event_data: Vec<GraphQLEventData>,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLActionStates {
+    /// Action state of the account after the last actions of the block
+    pub action_state_one: String,
+}
+
+#[derive(GraphQLObject, Debug)]
+pub struct GraphQLActionOutput {
+    pub block_info: GraphQLBlockInfo,
+    pub action_data: Vec<GraphQLEventData>,
+    pub action_state: GraphQLActionStates,
+}
+
+impl From<&RpcZkappBlockEvents> for GraphQLBlockInfo {
+    fn from(value: &RpcZkappBlockEvents) -> Self {
+        Self {
+            height: value.block.height as i32,
+            state_hash: value.block.hash.to_string(),
+            parent_hash: value.block.pred_hash.to_string(),
+            chain_status: match value.chain_status {
+                RpcZkappChainStatus::Pending => "pending".to_owned(),
+                RpcZkappChainStatus::Canonical => "canonical".to_owned(),
+            },
+            timestamp: value.block.timestamp.to_string(),
+            global_slot_since_genesis: value.block.global_slot_since_genesis as i32,
+        }
+    }
+}
+
+impl From<&ZkappAccountUpdateEvents> for GraphQLTransactionInfo {
+    fn from(value: &ZkappAccountUpdateEvents) -> Self {
+        Self {
+            status: match value.transaction_status {
+                MinaBaseTransactionStatusStableV2::Applied => "applied".to_owned(),
+                MinaBaseTransactionStatusStableV2::Failed(_) => "failed".to_owned(),
+            },
+            hash: value.transaction_hash.to_string(),
+            memo: value.memo.to_base58check(),
+            sequence_number: value.sequence_number as i32,
+            zkapp_account_update_ids: vec![value.account_update_id as i32],
+        }
+    }
+}
+
+fn event_data(update: &ZkappAccountUpdateEvents, events: &[Vec<BigInt>]) -> Vec<GraphQLEventData> {
+    events
+        .iter()
+        .map(|event| GraphQLEventData {
+            account_update_id: update.account_update_id.to_string(),
+            transaction_info: update.into(),
+            data: event.iter().map(BigInt::to_decimal).collect(),
+        })
+        .collect()
+}
+
+/// Events of the blocks, leaving out the blocks without any.
+pub fn events_output(blocks: &[RpcZkappBlockEvents]) -> Vec<GraphQLEventOutput> {
+    blocks
+        .iter()
+        .filter_map(|block| {
+            let data = block
+                .block
+                .account_updates
+                .iter()
+                .flat_map(|update| event_data(update, &update.events))
+                .collect::<Vec<_>>();
+            (!data.is_empty()).then(|| GraphQLEventOutput {
+                block_info: block.into(),
+                event_data: data,
+            })
+        })
+        .collect()
+}
+
+/// Actions added to the action state by the blocks, leaving out the
+/// blocks without any.
+pub fn actions_output(blocks: &[RpcZkappBlockEvents]) -> Vec<GraphQLActionOutput> {
+    blocks
+        .iter()
+        .filter_map(|block| {
+            let updates = block
+                .block
+                .account_updates
+                .iter()
+                .filter(|update| update.action_state.is_some());
+            let (_, action_state) = updates.clone().last()?.action_state.as_ref()?;
+            Some(GraphQLActionOutput {
+                block_info: block.into(),
+                action_data: updates
+                    .flat_map(|update| event_data(update, &update.actions))
+                    .collect(),
+                action_state: GraphQLActionStates {
+                    action_state_one: action_state.to_decimal(),
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::scan_state::transaction_logic::Memo;
+    use mina_p2p_messages::v2::{
+        DataHashLibStateHashStableV1, MinaBaseSignedCommandMemoStableV1, StateHash, TransactionHash,
+    };
+    use node::account::AccountSecretKey;
+    use node::ledger::ZkappBlockEvents;
+
+    use super::*;
+
+    fn address() -> AccountPublicKey {
+        AccountSecretKey::deterministic(0).public_key()
+    }
+
+    fn action_filter() -> ActionFilterOptionsInput {
+        ActionFilterOptionsInput {
+            address: address().to_string(),
+            token_id: None,
+            status: None,
+            from: None,
+            to: None,
+            from_action_state: None,
+            end_action_state: None,
+        }
+    }
+
+    fn big_int(value: u64) -> BigInt {
+        BigInt::from_decimal(&value.to_string()).unwrap()
+    }
+
+    fn state_hash(value: u64) -> StateHash {
+        DataHashLibStateHashStableV1(big_int(value)).into()
+    }
+
+    #[test]
+    fn action_filter_to_query() {
+        let token_id = TokenIdKeyHash::default();
+        let input = ActionFilterOptionsInput {
+            token_id: Some(token_id.to_string()),
+            status: Some(BlockStatusFilter::CANONICAL),
+            from: Some(5),
+            to: Some(10),
+            from_action_state: Some("12".to_owned()),
+            end_action_state: Some("34".to_owned()),
+            ..action_filter()
+        };
+        let query = RpcZkappEventsQuery::try_from(input).unwrap();
+        assert_eq!(
+            query,
+            RpcZkappEventsQuery {
+                public_key: address(),
+                token_id: Some(token_id),
+                status: Some(RpcZkappChainStatus::Canonical),
+                from: Some(5),
+                to: Some(10),
+                from_action_state: Some(big_int(12)),
+                end_action_state: Some(big_int(34)),
+            }
+        );
+    }
+
+    #[test]
+    fn event_filter_to_query() {
+        let input = EventFilterOptionsInput {
+            address: address().to_string(),
+            token_id: None,
+            status: Some(BlockStatusFilter::PENDING),
+            from: None,
+            to: Some(3),
+        };
+        let query = RpcZkappEventsQuery::try_from(input).unwrap();
+        assert_eq!(
+            query,
+            RpcZkappEventsQuery {
+                public_key: address(),
+                token_id: None,
+                status: Some(RpcZkappChainStatus::Pending),
+                from: None,
+                to: Some(3),
+                from_action_state: None,
+                end_action_state: None,
+            }
+        );
+    }
+
+    #[test]
+    fn status_filter_all_is_no_filter() {
+        for status in [None, Some(BlockStatusFilter::ALL)] {
+            let input = ActionFilterOptionsInput {
+                status,
+                ..action_filter()
+            };
+            let query = RpcZkappEventsQuery::try_from(input).unwrap();
+            assert_eq!(query.status, None);
+        }
+    }
+
+    #[test]
+    fn invalid_filters_rejected() {
+        let invalid = [
+            ActionFilterOptionsInput {
+                address: "B62qinvalid".to_owned(),
+                ..action_filter()
+            },
+            ActionFilterOptionsInput {
+                token_id: Some("invalid".to_owned()),
+                ..action_filter()
+            },
+            ActionFilterOptionsInput {
+                from: Some(-1),
+                ..action_filter()
+            },
+            ActionFilterOptionsInput {
+                to: Some(-1),
+                ..action_filter()
+            },
+            ActionFilterOptionsInput {
+                from_action_state: Some("0x12".to_owned()),
+                ..action_filter()
+            },
+            ActionFilterOptionsInput {
+                end_action_state: Some("abc".to_owned()),
+                ..action_filter()
+            },
+        ];
+        for input in invalid {
+            let debug = format!("{input:?}");
+            assert!(
+                RpcZkappEventsQuery::try_from(input).is_err(),
+                "accepted {debug}"
+            );
+        }
+    }
+
+    fn account_update(
+        account_update_id: u32,
+        events: &[u64],
+        actions: &[u64],
+        action_state: Option<(u64, u64)>,
+    ) -> ZkappAccountUpdateEvents {
+        ZkappAccountUpdateEvents {
+            public_key: address().into(),
+            token_id: TokenIdKeyHash::default(),
+            transaction_hash: TransactionHash::from(&[account_update_id as u8; 32]),
+            transaction_status: MinaBaseTransactionStatusStableV2::Applied,
+            memo: (&Memo::empty()).into(),
+            sequence_number: 2,
+            account_update_id,
+            events: events.iter().map(|event| vec![big_int(*event)]).collect(),
+            actions: actions
+                .iter()
+                .map(|action| vec![big_int(*action)])
+                .collect(),
+            action_state: action_state.map(|(before, after)| (big_int(before), big_int(after))),
+        }
+    }
+
+    fn block(
+        height: u32,
+        chain_status: RpcZkappChainStatus,
+        account_updates: Vec<ZkappAccountUpdateEvents>,
+    ) -> RpcZkappBlockEvents {
+        RpcZkappBlockEvents {
+            chain_status,
+            block: ZkappBlockEvents {
+                hash: state_hash(height as u64),
+                pred_hash: state_hash(height as u64 - 1),
+                height,
+                global_slot_since_genesis: height * 2,
+                timestamp: 1_700_000_000_000,
+                account_updates,
+            },
+        }
+    }
+
+    fn blocks() -> Vec<RpcZkappBlockEvents> {
+        vec![
+            block(
+                1,
+                RpcZkappChainStatus::Canonical,
+                vec![account_update(1, &[10, 11], &[], None)],
+            ),
+            block(
+                2,
+                RpcZkappChainStatus::Pending,
+                vec![
+                    account_update(1, &[], &[20], Some((1, 2))),
+                    account_update(2, &[21], &[22, 23], Some((2, 3))),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn events_output_of_blocks() {
+        let output = events_output(&blocks());
+        assert_eq!(output.len(), 2);
+
+        let info = &output[0].block_info;
+        assert_eq!(info.height, 1);
+        assert_eq!(info.chain_status, "canonical");
+        assert_eq!(info.state_hash, state_hash(1).to_string());
+        assert_eq!(info.parent_hash, state_hash(0).to_string());
+        assert_eq!(info.timestamp, "1700000000000");
+        assert_eq!(info.global_slot_since_genesis, 2);
+        let data = output[0]
+            .event_data
+            .iter()
+            .map(|event| event.data.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(data, [["10"], ["11"]]);
+
+        let event = &output[0].event_data[0];
+        assert_eq!(event.account_update_id, "1");
+        let transaction = &event.transaction_info;
+        assert_eq!(transaction.status, "applied");
+        assert_eq!(
+            transaction.hash,
+            TransactionHash::from(&[1; 32]).to_string()
+        );
+        assert_eq!(
+            transaction.memo,
+            MinaBaseSignedCommandMemoStableV1::from(&Memo::empty()).to_base58check()
+        );
+        assert_eq!(transaction.sequence_number, 2);
+        assert_eq!(transaction.zkapp_account_update_ids, [1]);
+
+        assert_eq!(output[1].block_info.chain_status, "pending");
+        let [event] = output[1].event_data.as_slice() else {
+            panic!("unexpected events: {:?}", output[1].event_data);
+        };
+        assert_eq!(event.account_update_id, "2");
+        assert_eq!(event.data, ["21"]);
+    }
+
+    #[test]
+    fn actions_output_of_blocks() {
+        // The first block has no actions.
+        let output = actions_output(&blocks());
+        let [output] = output.as_slice() else {
+            panic!("unexpected actions: {output:?}");
+        };
+        assert_eq!(output.block_info.height, 2);
+        let data = output
+            .action_data
+            .iter()
+            .map(|action| (action.account_update_id.as_str(), action.data.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            data,
+            [
+                ("1", vec!["20".to_owned()]),
+                ("2", vec!["22".to_owned()]),
+                ("2", vec!["23".to_owned()]),
+            ]
+        );
+        // Action state after the last account update of the block.
+        assert_eq!(output.action_state.action_state_one, "3");
+    }
+
+    #[test]
+    fn output_of_no_blocks() {
+        assert!(events_output(&[]).is_empty());
+        assert!(actions_output(&[]).is_empty());
+    }
+}
@@ -10,6 +10,7 @@ use warp::{
 };
 
 use node::core::snark::SnarkJobId;
+use node::p2p::connection::outgoing::P2pConnectionOutgoingInitOpts;
 use node::rpc::*;
 
 use openmina_node_common::rpc::{
@@ -176,6 +177,36 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
             }
         });
 
+    let rpc_sender_clone = rpc_sender.clone();
+    let peer_connect = warp::path!("state" / "peers" / "connect")
+        .and(warp::post())
+        .and(warp::filters::body::bytes())
+        .then(move |body: bytes::Bytes| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                let opts = match String::from_utf8(body.to_vec())
+                    .map_err(|err| err.to_string())
+                    .and_then(|s| {
+                        P2pConnectionOutgoingInitOpts::from_str(s.trim())
+                            .map_err(|err| err.to_string())
+                    }) {
+                    Ok(opts) => opts,
+                    Err(err) => {
+                        return with_json_reply(
+                            &format!("invalid peer address: {err}"),
+                            StatusCode::BAD_REQUEST,
+                        );
+                    }
+                };
+                match rpc_sender_clone.peer_connect(opts).await {
+                    Ok(peer_id) => {
+                        with_json_reply(&serde_json::json!({ "peer_id": peer_id }), StatusCode::OK)
+                    }
+                    Err(err) => with_json_reply(&err, StatusCode::BAD_REQUEST),
+                }
+            }
+        });
+
     let rpc_sender_clone = rpc_sender.clone();
     let message_progress_get = warp::path!("state" / "message-progress")
         .and(warp::get())
@@ -482,7 +513,7 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
         });
 
     let rpc_sender_clone = rpc_sender.clone();
-    let accounts = warp::path("accounts").and(warp::get()).then(move || {
+    let accounts = warp::path!("accounts").and(warp::get()).then(move || {
         let rpc_sender_clone = rpc_sender_clone.clone();
 
         async move {
@@ -529,6 +560,126 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
             }
         });
 
+    let rpc_sender_clone = rpc_sender.clone();
+    let delegation_post = warp::path("send-delegation")
+        .and(warp::post())
+        .and(warp::filters::body::json())
+        .then(move |body: Vec<RpcInjectDelegation>| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+
+            async move {
+                match rpc_sender_clone
+                    .transaction_pool()
+                    .inject()
+                    .delegation(body)
+                    .await
+                {
+                    Err(err) => with_status(
+                        warp::reply::json(&serde_json::json!({"error": err})),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ),
+                    Ok(res) => res.map_or_else(
+                        dropped_channel_response,
+                        |reply: node::rpc::RpcTransactionInjectResponse| {
+                            with_json_reply(&reply, StatusCode::OK)
+                        },
+                    ),
+                }
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let user_commands_post = warp::path("send-user-commands")
+        .and(warp::post())
+        .and(warp::filters::body::json())
+        .then(
+            move |body: Vec<mina_p2p_messages::v2::MinaBaseUserCommandStableV2>| {
+                let rpc_sender_clone = rpc_sender_clone.clone();
+
+                async move {
+                    match rpc_sender_clone
+                        .transaction_pool()
+                        .inject()
+                        .commands(body)
+                        .await
+                    {
+                        Err(err) => with_status(
+                            warp::reply::json(&serde_json::json!({"error": err})),
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                        ),
+                        Ok(res) => res.map_or_else(
+                            dropped_channel_response,
+                            |reply: node::rpc::RpcTransactionInjectResponse| {
+                                with_json_reply(&reply, StatusCode::OK)
+                            },
+                        ),
+                    }
+                }
+            },
+        );
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let transaction_simulate = warp::path("simulate-transaction")
+        .and(warp::post())
+        .and(warp::path::end())
+        .and(warp::filters::body::json())
+        .then(
+            move |command: mina_p2p_messages::v2::MinaBaseUserCommandStableV2| {
+                let rpc_sender_clone = rpc_sender_clone.clone();
+
+                async move {
+                    let res: Option<RpcTransactionSimulateResponse> = rpc_sender_clone
+                        .oneshot_request(RpcRequest::TransactionSimulate(command))
+                        .await;
+                    match res {
+                        None => with_json_reply(
+                            &"response channel dropped",
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                        ),
+                        Some(Err(err)) => with_json_reply(&err, StatusCode::BAD_REQUEST),
+                        Some(Ok(simulation)) => with_json_reply(&simulation, StatusCode::OK),
+                    }
+                }
+            },
+        );
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let fee_estimate = warp::path!("fee-estimate")
+        .and(warp::get())
+        .and(optq::<RpcFeeEstimateQuery>())
+        .then(move |query: RpcFeeEstimateQuery| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::FeeEstimate(query))
+                    .await
+                    .map_or_else(dropped_channel_response, |reply: RpcFeeEstimateResponse| {
+                        with_json_reply(&reply, StatusCode::OK)
+                    })
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let zkapp_events = warp::path!("zkapp-events")
+        .and(warp::get())
+        .and(warp::query())
+        .then(move |query: RpcZkappEventsQuery| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                let res: Option<RpcZkappEventsResponse> = rpc_sender_clone
+                    .oneshot_request(RpcRequest::ZkappEventsGet(query))
+                    .await;
+                match res {
+                    None => with_json_reply(
+                        &"response channel dropped",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ),
+                    Some(Err(err)) => with_json_reply(&err, StatusCode::BAD_REQUEST),
+                    Some(Ok(events)) => with_json_reply(&events, StatusCode::OK),
+                }
+            }
+        });
+
     let rpc_sender_clone = rpc_sender.clone();
     let transition_frontier_user_commands = warp::path("best-chain-user-commands")
         .and(warp::get())
@@ -547,6 +698,86 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
             }
         });
 
+    #[derive(Deserialize, Default)]
+    struct BestChainQueryParams {
+        limit: Option<u32>,
+    }
+
+    #[derive(Serialize)]
+    struct BestChainBlockSummary {
+        hash: node::core::block::BlockHash,
+        height: u32,
+        global_slot: u32,
+        timestamp: u64,
+        producer: mina_p2p_messages::v2::NonZeroCurvePoint,
+        transactions: usize,
+        completed_works: usize,
+    }
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let best_chain = warp::path!("best-chain")
+        .and(warp::get())
+        .and(optq::<BestChainQueryParams>())
+        .then(move |query: BestChainQueryParams| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            async move {
+                let limit = query.limit.unwrap_or(10);
+                rpc_sender_clone
+                    .oneshot_request(RpcRequest::BestChain(limit))
+                    .await
+                    .map_or_else(dropped_channel_response, |reply: RpcBestChainResponse| {
+                        let summaries = reply
+                            .iter()
+                            .rev()
+                            .map(|block| BestChainBlockSummary {
+                                hash: block.hash().clone(),
+                                height: block.height(),
+                                global_slot: block.global_slot(),
+                                timestamp: u64::from(block.timestamp()),
+                                producer: block.producer().clone(),
+                                transactions: block.body().commands_iter().count(),
+                                completed_works: block.body().completed_works_count(),
+                            })
+                            .collect::<Vec<_>>();
+                        with_json_reply(&summaries, StatusCode::OK)
+                    })
+            }
+        });
+
+    let rpc_sender_clone = rpc_sender.clone();
+    let hard_fork_config_get = warp::path!("hardfork" / "config" / ..)
+        .and(warp::get())
+        .and(
+            warp::path::param::<String>()
+                .map(Some)
+                .or_else(|_| async { Ok::<(Option<String>,), std::convert::Infallible>((None,)) }),
+        )
+        .and(warp::path::end())
+        .then(move |block_hash: Option<String>| {
+            let rpc_sender_clone = rpc_sender_clone.clone();
+            let block_hash = block_hash.map(|hash| hash.parse()).transpose();
+            async move {
+                let Ok(block_hash) = block_hash else {
+                    return JsonOrBinary::error(
+                        "invalid arg! Expected block hash",
+                        StatusCode::BAD_REQUEST,
+                    );
+                };
+                let res: Option<RpcHardForkConfigGetResponse> = rpc_sender_clone
+                    .oneshot_request(RpcRequest::HardForkConfigGet(block_hash))
+                    .await;
+                match res {
+                    None => JsonOrBinary::error(
+                        "response channel dropped",
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ),
+                    Some(Err(err)) => JsonOrBinary::error(err, StatusCode::INTERNAL_SERVER_ERROR),
+                    // Already serialized by the ledger service.
+                    Some(Ok(config)) => JsonOrBinary::Json(config.into_bytes()),
+                }
+            }
+        });
+
     let cors = warp::cors()
         .allow_any_origin()
         .allow_methods(["GET", "POST", "PUT", "DELETE", "OPTIONS"])
@@ -567,6 +798,7 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
         routes,
         status,
         peers_get,
+        peer_connect,
         message_progress_get,
         stats,
         scan_state_summary_get,
@@ -578,12 +810,32 @@ pub async fn run(port: u16, rpc_sender: RpcSender) {
         snark_workers,
         transaction_pool,
         accounts,
+        accounts::account(rpc_sender.clone()),
+        accounts::token_account(rpc_sender.clone()),
+        accounts::ledger(rpc_sender.clone()),
+        watched_accounts::list(rpc_sender.clone()),
+        watched_accounts::account(rpc_sender.clone()),
+        watched_accounts::add(rpc_sender.clone()),
+        watched_accounts::remove(rpc_sender.clone()),
+        archive::best_tip(rpc_sender.clone()),
+        archive::block(rpc_sender.clone()),
+        archive::block_at_height(rpc_sender.clone()),
+        archive::transaction_block(rpc_sender.clone()),
+        archive::account_history(rpc_sender.clone()),
         transaction_post,
+        delegation_post,
+        user_commands_post,
+        transaction_simulate,
+        fee_estimate,
+        zkapp_events,
         transition_frontier_user_commands,
+        best_chain,
+        hard_fork_config_get,
         healthcheck(rpc_sender.clone()),
         readiness(rpc_sender.clone()),
         discovery::routing_table(rpc_sender.clone()),
         discovery::bootstrap_stats(rpc_sender.clone()),
+        metrics::metrics(rpc_sender.clone()),
         super::graphql::routes(rpc_sender),
     );
 
@@ -692,6 +944,1053 @@ mod discovery {
     }
 }
 
+mod accounts {
+    use std::str::FromStr;
+
+    use mina_p2p_messages::v2::{LedgerHash, TokenIdKeyHash};
+    use node::account::AccountPublicKey;
+    use node::rpc::RpcLedgerAccountsResponse;
+    use openmina_node_common::rpc::RpcSender;
+    use serde::Deserialize;
+    use warp::{http::StatusCode, Filter};
+
+    use super::{optq, with_json_reply, with_rpc_sender, DroppedChannel};
+
+    #[derive(Deserialize, Default)]
+    struct AccountQueryParams {
+        /// Ledger to query instead of the best tip one, e.g. staking
+        /// or next epoch ledger.
+        ledger_hash: Option<String>,
+    }
+
+    /// Account of the public key with the default token.
+    pub fn account(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("accounts" / String)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and(optq::<AccountQueryParams>())
+            .and_then(|public_key, rpc_sender, query| {
+                get_accounts(rpc_sender, public_key, None, query)
+            })
+    }
+
+    pub fn token_account(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("accounts" / String / String)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and(optq::<AccountQueryParams>())
+            .and_then(|public_key, token_id, rpc_sender, query| {
+                get_accounts(rpc_sender, public_key, Some(token_id), query)
+            })
+    }
+
+    /// All the accounts of the ledger, in the ledger order.
+    pub fn ledger(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("ledger" / String / "accounts")
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(get_ledger)
+    }
+
+    async fn get_ledger(
+        ledger_hash: String,
+        rpc_sender: RpcSender,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let ledger_hash = match LedgerHash::from_str(&ledger_hash) {
+            Ok(ledger_hash) => ledger_hash,
+            Err(err) => {
+                return Ok(with_json_reply(
+                    &format!("invalid ledger hash: {err}"),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        rpc_sender
+            .ledger()
+            .at(ledger_hash)
+            .accounts()
+            .ledger()
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcLedgerAccountsResponse| match reply {
+                    Ok(accounts) => Ok(with_json_reply(&accounts, StatusCode::OK)),
+                    Err(err) => Ok(with_json_reply(&err.to_string(), StatusCode::NOT_FOUND)),
+                },
+            )
+    }
+
+    async fn get_accounts(
+        rpc_sender: RpcSender,
+        public_key: String,
+        token_id: Option<String>,
+        AccountQueryParams { ledger_hash }: AccountQueryParams,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let Ok(public_key) = AccountPublicKey::from_str(&public_key) else {
+            return Ok(with_json_reply(
+                &format!("invalid public key: {public_key}"),
+                StatusCode::BAD_REQUEST,
+            ));
+        };
+        let token_id = match token_id.map(|token_id| TokenIdKeyHash::from_str(&token_id)) {
+            None => None,
+            Some(Ok(token_id)) => Some(token_id),
+            Some(Err(err)) => {
+                return Ok(with_json_reply(
+                    &format!("invalid token id: {err}"),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+        let ledger = rpc_sender.ledger();
+        let ledger = match ledger_hash.map(|hash| LedgerHash::from_str(&hash)) {
+            None => ledger.latest(),
+            Some(Ok(ledger_hash)) => ledger.at(ledger_hash),
+            Some(Err(err)) => {
+                return Ok(with_json_reply(
+                    &format!("invalid ledger hash: {err}"),
+                    StatusCode::BAD_REQUEST,
+                ));
+            }
+        };
+
+        ledger
+            .accounts()
+            .get(public_key, token_id)
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcLedgerAccountsResponse| match reply {
+                    Ok(accounts) if accounts.is_empty() => {
+                        Ok(with_json_reply(&"account not found", StatusCode::NOT_FOUND))
+                    }
+                    Ok(accounts) => Ok(with_json_reply(&accounts, StatusCode::OK)),
+                    Err(err) => Ok(with_json_reply(&err.to_string(), StatusCode::NOT_FOUND)),
+                },
+            )
+    }
+}
+
+mod watched_accounts {
+    use std::str::FromStr;
+
+    use node::account::AccountPublicKey;
+    use node::rpc::{
+        RpcRequest, RpcWatchedAccountsAddResponse, RpcWatchedAccountsGetResponse,
+        RpcWatchedAccountsRemoveResponse,
+    };
+    use openmina_node_common::rpc::RpcSender;
+    use warp::{http::StatusCode, Filter};
+
+    use super::{with_json_reply, with_rpc_sender, DroppedChannel};
+
+    /// Lists the watched accounts, their latest state and the recent
+    /// blocks affecting them.
+    pub fn list(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("watched-accounts")
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|rpc_sender| get(rpc_sender, None))
+    }
+
+    pub fn account(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("watched-accounts" / String)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|public_key, rpc_sender| get(rpc_sender, Some(public_key)))
+    }
+
+    pub fn add(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("watched-accounts" / String)
+            .and(warp::post())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|public_key: String, rpc_sender: RpcSender| async move {
+                let Ok(public_key) = AccountPublicKey::from_str(&public_key) else {
+                    return Ok(invalid_public_key(&public_key));
+                };
+                rpc_sender
+                    .oneshot_request(RpcRequest::WatchedAccountsAdd(public_key))
+                    .await
+                    .map_or_else(
+                        || Err(warp::reject::custom(DroppedChannel)),
+                        |added: RpcWatchedAccountsAddResponse| {
+                            Ok(with_json_reply(&added, StatusCode::OK))
+                        },
+                    )
+            })
+    }
+
+    pub fn remove(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("watched-accounts" / String)
+            .and(warp::delete())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|public_key: String, rpc_sender: RpcSender| async move {
+                let Ok(public_key) = AccountPublicKey::from_str(&public_key) else {
+                    return Ok(invalid_public_key(&public_key));
+                };
+                rpc_sender
+                    .oneshot_request(RpcRequest::WatchedAccountsRemove(public_key))
+                    .await
+                    .map_or_else(
+                        || Err(warp::reject::custom(DroppedChannel)),
+                        |removed: RpcWatchedAccountsRemoveResponse| {
+                            Ok(with_json_reply(&removed, StatusCode::OK))
+                        },
+                    )
+            })
+    }
+
+    async fn get(
+        rpc_sender: RpcSender,
+        public_key: Option<String>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let public_key = match public_key {
+            None => None,
+            Some(public_key) => match AccountPublicKey::from_str(&public_key) {
+                Ok(public_key) => Some(public_key),
+                Err(_) => return Ok(invalid_public_key(&public_key)),
+            },
+        };
+        let is_single = public_key.is_some();
+        let accounts: RpcWatchedAccountsGetResponse = rpc_sender
+            .oneshot_request(RpcRequest::WatchedAccountsGet(public_key))
+            .await
+            .ok_or_else(|| warp::reject::custom(DroppedChannel))?;
+        if !is_single {
+            return Ok(with_json_reply(&accounts, StatusCode::OK));
+        }
+        match accounts.first() {
+            Some(account) => Ok(with_json_reply(account, StatusCode::OK)),
+            None => Ok(with_json_reply(
+                &"account is not watched",
+                StatusCode::NOT_FOUND,
+            )),
+        }
+    }
+
+    fn invalid_public_key(public_key: &str) -> warp::reply::WithStatus<warp::reply::Json> {
+        with_json_reply(
+            &format!("invalid public key: {public_key}"),
+            StatusCode::BAD_REQUEST,
+        )
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::{any::Any, collections::BTreeSet};
+
+        use node::account::AccountSecretKey;
+        use node::core::channels::{mpsc, oneshot};
+        use node::rpc::RpcWatchedAccount;
+        use openmina_node_common::rpc::NodeRpcRequest;
+
+        use super::*;
+
+        /// Node keeping only the set of watched accounts.
+        fn node() -> RpcSender {
+            let (tx, mut rx) = mpsc::channel::<NodeRpcRequest>(8);
+            tokio::spawn(async move {
+                let mut watched = BTreeSet::<AccountPublicKey>::new();
+                while let Some(NodeRpcRequest { req, responder }) = rx.recv().await {
+                    match req {
+                        RpcRequest::WatchedAccountsAdd(key) => {
+                            respond(responder, watched.insert(key))
+                        }
+                        RpcRequest::WatchedAccountsRemove(key) => {
+                            respond(responder, watched.remove(&key))
+                        }
+                        RpcRequest::WatchedAccountsGet(key) => {
+                            let accounts: RpcWatchedAccountsGetResponse = watched
+                                .iter()
+                                .filter(|k| key.as_ref().map_or(true, |key| key == *k))
+                                .map(|k| RpcWatchedAccount {
+                                    public_key: k.clone(),
+                                    block: None,
+                                    account: None,
+                                    blocks: Vec::new(),
+                                })
+                                .collect();
+                            respond(responder, accounts)
+                        }
+                        req => panic!("unexpected request: {req:?}"),
+                    }
+                }
+            });
+            RpcSender::new(tx)
+        }
+
+        fn respond<T: 'static>(responder: Box<dyn Send + Any>, response: T) {
+            let _ = responder
+                .downcast::<oneshot::Sender<T>>()
+                .unwrap()
+                .send(response);
+        }
+
+        async fn request(
+            rpc_sender: &RpcSender,
+            method: &str,
+            path: &str,
+        ) -> (StatusCode, serde_json::Value) {
+            let filter = list(rpc_sender.clone())
+                .or(account(rpc_sender.clone()))
+                .or(add(rpc_sender.clone()))
+                .or(remove(rpc_sender.clone()));
+            let response = warp::test::request()
+                .method(method)
+                .path(path)
+                .reply(&filter)
+                .await;
+            (
+                response.status(),
+                serde_json::from_slice(response.body()).unwrap(),
+            )
+        }
+
+        fn public_key(i: u64) -> String {
+            AccountSecretKey::deterministic(i).public_key().to_string()
+        }
+
+        #[tokio::test]
+        async fn add_and_remove() {
+            let rpc_sender = node();
+            let (key, other) = (public_key(0), public_key(1));
+            let path = format!("/watched-accounts/{key}");
+
+            let (status, added) = request(&rpc_sender, "POST", &path).await;
+            assert_eq!((status, added), (StatusCode::OK, true.into()));
+            let (_, added) = request(&rpc_sender, "POST", &path).await;
+            assert_eq!(added, false);
+            request(&rpc_sender, "POST", &format!("/watched-accounts/{other}")).await;
+
+            let (status, removed) = request(&rpc_sender, "DELETE", &path).await;
+            assert_eq!((status, removed), (StatusCode::OK, true.into()));
+            let (_, removed) = request(&rpc_sender, "DELETE", &path).await;
+            assert_eq!(removed, false);
+
+            let (_, accounts) = request(&rpc_sender, "GET", "/watched-accounts").await;
+            let keys = accounts
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|account| account["public_key"].as_str().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, [other.as_str()]);
+        }
+
+        #[tokio::test]
+        async fn get_single_account() {
+            let rpc_sender = node();
+            let key = public_key(0);
+            let path = format!("/watched-accounts/{key}");
+
+            let (status, _) = request(&rpc_sender, "GET", &path).await;
+            assert_eq!(status, StatusCode::NOT_FOUND);
+
+            request(&rpc_sender, "POST", &path).await;
+            let (status, account) = request(&rpc_sender, "GET", &path).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(account["public_key"], key.as_str());
+            // not read from the ledger yet.
+            assert!(account["block"].is_null());
+            assert!(account["account"].is_null());
+            assert_eq!(account["blocks"], serde_json::json!([]));
+        }
+
+        #[tokio::test]
+        async fn invalid_public_key_rejected() {
+            let rpc_sender = node();
+            for method in ["GET", "POST", "DELETE"] {
+                let (status, error) =
+                    request(&rpc_sender, method, "/watched-accounts/B62qinvalid").await;
+                assert_eq!(status, StatusCode::BAD_REQUEST, "{method}");
+                assert_eq!(error, "invalid public key: B62qinvalid");
+            }
+        }
+    }
+}
+
+mod archive {
+    use std::str::FromStr;
+
+    use mina_p2p_messages::v2::{StateHash, TokenIdKeyHash, TransactionHash};
+    use node::account::AccountPublicKey;
+    use node::rpc::{RpcArchiveData, RpcArchiveGetResponse, RpcArchiveQuery, RpcRequest};
+    use openmina_node_common::rpc::RpcSender;
+    use serde::Deserialize;
+    use warp::{http::StatusCode, Filter};
+
+    use super::{optq, with_json_reply, with_rpc_sender, DroppedChannel};
+
+    #[derive(Deserialize, Default)]
+    struct AccountHistoryQueryParams {
+        /// Default token if not provided.
+        token_id: Option<String>,
+    }
+
+    pub fn best_tip(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("archive" / "best-tip")
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|rpc_sender| get(rpc_sender, Ok(RpcArchiveQuery::BestTip)))
+    }
+
+    pub fn block(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("archive" / "blocks" / String)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|hash: String, rpc_sender| {
+                let query = StateHash::from_str(&hash)
+                    .map(RpcArchiveQuery::Block)
+                    .map_err(|_| format!("invalid block hash: {hash}"));
+                get(rpc_sender, query)
+            })
+    }
+
+    /// Block at the height on the best chain.
+    pub fn block_at_height(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("archive" / "heights" / u32)
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|height, rpc_sender| {
+                get(rpc_sender, Ok(RpcArchiveQuery::BlockAtHeight(height)))
+            })
+    }
+
+    /// Block which included the user command.
+    pub fn transaction_block(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("archive" / "transactions" / String / "block")
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(|hash: String, rpc_sender| {
+                let query = TransactionHash::from_str(&hash)
+                    .map(RpcArchiveQuery::TransactionBlock)
+                    .map_err(|_| format!("invalid transaction hash: {hash}"));
+                get(rpc_sender, query)
+            })
+    }
+
+    /// Hashes of the blocks which changed the account, oldest first.
+    pub fn account_history(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("archive" / "accounts" / String / "history")
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and(optq::<AccountHistoryQueryParams>())
+            .and_then(
+                |public_key: String, rpc_sender, query: AccountHistoryQueryParams| {
+                    let query = account_history_query(public_key, query.token_id);
+                    get(rpc_sender, query)
+                },
+            )
+    }
+
+    fn account_history_query(
+        public_key: String,
+        token_id: Option<String>,
+    ) -> Result<RpcArchiveQuery, String> {
+        let public_key = AccountPublicKey::from_str(&public_key)
+            .map_err(|_| format!("invalid public key: {public_key}"))?;
+        let token_id = token_id
+            .map(|token_id| {
+                TokenIdKeyHash::from_str(&token_id)
+                    .map_err(|err| format!("invalid token id: {err}"))
+            })
+            .transpose()?;
+        Ok(RpcArchiveQuery::AccountHistory(public_key, token_id))
+    }
+
+    async fn get(
+        rpc_sender: RpcSender,
+        query: Result<RpcArchiveQuery, String>,
+    ) -> Result<impl warp::Reply, warp::Rejection> {
+        let query = match query {
+            Ok(query) => query,
+            Err(err) => return Ok(with_json_reply(&err, StatusCode::BAD_REQUEST)),
+        };
+        let reply: RpcArchiveGetResponse = rpc_sender
+            .oneshot_request(RpcRequest::ArchiveGet(query))
+            .await
+            .ok_or_else(|| warp::reject::custom(DroppedChannel))?;
+        match reply {
+            Ok(RpcArchiveData::Block(None)) => Ok(with_json_reply(
+                &"block is not archived",
+                StatusCode::NOT_FOUND,
+            )),
+            Ok(RpcArchiveData::Block(Some(block))) => Ok(with_json_reply(&block, StatusCode::OK)),
+            Ok(RpcArchiveData::AccountHistory(hashes)) => {
+                Ok(with_json_reply(&hashes, StatusCode::OK))
+            }
+            Err(err) => Ok(with_json_reply(&err, StatusCode::INTERNAL_SERVER_ERROR)),
+        }
+    }
+}
+
+mod metrics {
+    use std::fmt::{Display, Write};
+    use std::time::Duration;
+
+    use node::rpc::{RpcMetrics, RpcMetricsGetResponse, RpcRequest, RpcSnarkWorkerStatus};
+    use node::stats::sync::SyncLedger;
+    use openmina_node_common::rpc::RpcSender;
+    use warp::{hyper::header::CONTENT_TYPE, Filter};
+
+    use super::{with_rpc_sender, DroppedChannel};
+
+    const CONTENT_TYPE_PROMETHEUS: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+    /// Node stats in the Prometheus text exposition format.
+    pub fn metrics(
+        rpc_sender: RpcSender,
+    ) -> impl warp::Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+        warp::path!("metrics")
+            .and(warp::get())
+            .and(with_rpc_sender(rpc_sender))
+            .and_then(get_metrics)
+    }
+
+    async fn get_metrics(rpc_sender: RpcSender) -> Result<impl warp::Reply, warp::Rejection> {
+        rpc_sender
+            .oneshot_request(RpcRequest::MetricsGet)
+            .await
+            .map_or_else(
+                || Err(warp::reject::custom(DroppedChannel)),
+                |reply: RpcMetricsGetResponse| {
+                    Ok(warp::reply::with_header(
+                        encode(&reply),
+                        CONTENT_TYPE,
+                        CONTENT_TYPE_PROMETHEUS,
+                    ))
+                },
+            )
+    }
+
+    /// Minimal writer for the Prometheus text format.
+    #[derive(Default)]
+    struct Encoder {
+        out: String,
+    }
+
+    impl Encoder {
+        fn family(&mut self, name: &str, kind: &str, help: &str) {
+            let _ = writeln!(self.out, "# HELP openmina_{name} {help}");
+            let _ = writeln!(self.out, "# TYPE openmina_{name} {kind}");
+        }
+
+        fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+            let _ = write!(self.out, "openmina_{name}");
+            if !labels.is_empty() {
+                self.out.push('{');
+                for (i, (key, val)) in labels.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    let _ = write!(self.out, "{key}=\"");
+                    for c in val.chars() {
+                        match c {
+                            '\\' => self.out.push_str("\\\\"),
+                            '"' => self.out.push_str("\\\""),
+                            '\n' => self.out.push_str("\\n"),
+                            c => self.out.push(c),
+                        }
+                    }
+                    self.out.push('"');
+                }
+                self.out.push('}');
+            }
+            let _ = writeln!(self.out, " {value}");
+        }
+    }
+
+    fn seconds(nanos: u64) -> f64 {
+        nanos as f64 / 1_000_000_000.0
+    }
+
+    fn encode(metrics: &RpcMetrics) -> String {
+        let mut enc = Encoder::default();
+
+        if let Some(action_stats) = &metrics.action_stats {
+            enc.family(
+                "action_duration_seconds",
+                "histogram",
+                "Time from an action until the next one, by action kind.",
+            );
+            for (kind, ranges) in action_stats.iter() {
+                if ranges.iter().all(|(_, range)| range.total_calls == 0) {
+                    continue;
+                }
+                let action = format!("{kind:?}");
+                let (mut count, mut sum) = (0, 0);
+                for (bound, range) in ranges.iter() {
+                    count += range.total_calls;
+                    sum += range.total_duration;
+                    let le = bound.map_or_else(|| "+Inf".to_owned(), |b| seconds(b).to_string());
+                    enc.sample(
+                        "action_duration_seconds_bucket",
+                        &[("action", &action), ("le", &le)],
+                        count,
+                    );
+                }
+                enc.sample(
+                    "action_duration_seconds_sum",
+                    &[("action", &action)],
+                    seconds(sum),
+                );
+                enc.sample(
+                    "action_duration_seconds_count",
+                    &[("action", &action)],
+                    count,
+                );
+            }
+        }
+
+        enc.family(
+            "sync_phase",
+            "gauge",
+            "Current transition frontier sync phase.",
+        );
+        enc.sample("sync_phase", &[("phase", &metrics.sync_phase)], 1);
+        if let Some(sync) = &metrics.sync_stats {
+            let kind = format!("{:?}", sync.kind);
+            if let Some(duration) = sync
+                .synced
+                .and_then(|t| t.checked_sub(sync.best_tip_received))
+            {
+                enc.family(
+                    "sync_duration_seconds",
+                    "gauge",
+                    "Duration of the latest completed sync, from receiving the best tip.",
+                );
+                enc.sample(
+                    "sync_duration_seconds",
+                    &[("kind", &kind)],
+                    duration.as_secs_f64(),
+                );
+            }
+            enc.family(
+                "sync_ledger_stage_duration_seconds",
+                "gauge",
+                "Duration of finished ledger sync stages of the latest sync.",
+            );
+            let ledgers = [
+                ("staking_epoch", &sync.ledgers.staking_epoch),
+                ("next_epoch", &sync.ledgers.next_epoch),
+                ("root", &sync.ledgers.root),
+            ];
+            for (ledger, stages) in ledgers {
+                let Some(stages) = stages else { continue };
+                for (stage, duration) in ledger_stage_durations(stages) {
+                    enc.sample(
+                        "sync_ledger_stage_duration_seconds",
+                        &[("kind", &kind), ("ledger", ledger), ("stage", stage)],
+                        duration.as_secs_f64(),
+                    );
+                }
+            }
+            enc.family(
+                "sync_blocks",
+                "gauge",
+                "Blocks of the latest sync, by status.",
+            );
+            let mut statuses = std::collections::BTreeMap::<_, usize>::new();
+            for block in &sync.blocks {
+                *statuses.entry(format!("{:?}", block.status)).or_default() += 1;
+            }
+            for (status, count) in statuses {
+                enc.sample(
+                    "sync_blocks",
+                    &[("kind", &kind), ("status", &status)],
+                    count,
+                );
+            }
+        }
+
+        enc.family("peers", "gauge", "Known peers, by connection state.");
+        enc.sample(
+            "peers",
+            &[("state", "connecting")],
+            metrics.peers.connecting,
+        );
+        enc.sample("peers", &[("state", "connected")], metrics.peers.connected);
+        enc.sample(
+            "peers",
+            &[("state", "disconnected")],
+            metrics.peers.disconnected,
+        );
+
+        enc.family(
+            "transaction_pool_size",
+            "gauge",
+            "Transactions in the transaction pool.",
+        );
+        enc.sample(
+            "transaction_pool_size",
+            &[],
+            metrics.transaction_pool.transactions,
+        );
+        enc.family("snark_pool_jobs", "gauge", "Jobs in the snark pool.");
+        enc.sample("snark_pool_jobs", &[], metrics.snark_pool.total_jobs);
+        enc.family(
+            "snark_pool_snarks",
+            "gauge",
+            "Snark pool jobs that already have a snark.",
+        );
+        enc.sample("snark_pool_snarks", &[], metrics.snark_pool.snarks);
+
+        if !metrics.snark_workers.is_empty() {
+            enc.family(
+                "snark_worker_status",
+                "gauge",
+                "Current status of each snark worker.",
+            );
+            for worker in &metrics.snark_workers {
+                let id = worker.id.as_deref().unwrap_or_default();
+                enc.sample(
+                    "snark_worker_status",
+                    &[
+                        ("worker", id),
+                        ("status", snark_worker_status(&worker.status)),
+                    ],
+                    1,
+                );
+            }
+            enc.family(
+                "snark_worker_completed_total",
+                "counter",
+                "Snarks produced by each snark worker.",
+            );
+            for worker in &metrics.snark_workers {
+                let id = worker.id.as_deref().unwrap_or_default();
+                enc.sample(
+                    "snark_worker_completed_total",
+                    &[("worker", id)],
+                    worker.health.completed,
+                );
+            }
+            enc.family(
+                "snark_worker_failed_total",
+                "counter",
+                "Failed jobs and errors of each snark worker.",
+            );
+            for worker in &metrics.snark_workers {
+                let id = worker.id.as_deref().unwrap_or_default();
+                enc.sample(
+                    "snark_worker_failed_total",
+                    &[("worker", id)],
+                    worker.health.failed,
+                );
+            }
+        }
+
+        if let Some(producers) = &metrics.block_producer {
+            enc.family(
+                "block_producer_attempts_total",
+                "counter",
+                "Block production attempts, by producer key and outcome.",
+            );
+            for (producer, stats) in producers {
+                let producer = producer.to_string();
+                let outcomes = [
+                    ("won_slot", stats.won_slots),
+                    ("produced", stats.produced),
+                    ("canonical", stats.canonical),
+                    ("orphaned", stats.orphaned),
+                    ("discarded", stats.discarded),
+                ];
+                for (outcome, count) in outcomes {
+                    enc.sample(
+                        "block_producer_attempts_total",
+                        &[("producer", &producer), ("outcome", outcome)],
+                        count,
+                    );
+                }
+            }
+        }
+
+        enc.family(
+            "ledger_read_pending",
+            "gauge",
+            "Pending ledger manager read requests.",
+        );
+        enc.sample("ledger_read_pending", &[], metrics.ledger.pending_reads);
+        enc.family(
+            "ledger_read_pending_cost",
+            "gauge",
+            "Total cost of pending ledger manager read requests.",
+        );
+        enc.sample(
+            "ledger_read_pending_cost",
+            &[],
+            metrics.ledger.pending_reads_cost,
+        );
+        enc.family(
+            "ledger_write_pending",
+            "gauge",
+            "Pending ledger manager write requests.",
+        );
+        enc.sample("ledger_write_pending", &[], metrics.ledger.pending_writes);
+
+        if let Some(traffic) = &metrics.p2p_traffic {
+            enc.family(
+                "p2p_received_bytes_total",
+                "counter",
+                "Bytes received over p2p streams, by protocol.",
+            );
+            for (protocol, traffic) in traffic {
+                enc.sample(
+                    "p2p_received_bytes_total",
+                    &[("protocol", protocol)],
+                    traffic.bytes_received,
+                );
+            }
+            enc.family(
+                "p2p_sent_bytes_total",
+                "counter",
+                "Bytes sent over p2p streams, by protocol.",
+            );
+            for (protocol, traffic) in traffic {
+                enc.sample(
+                    "p2p_sent_bytes_total",
+                    &[("protocol", protocol)],
+                    traffic.bytes_sent,
+                );
+            }
+        }
+
+        enc.out
+    }
+
+    fn ledger_stage_durations(ledger: &SyncLedger) -> Vec<(&'static str, Duration)> {
+        let snarked = &ledger.snarked;
+        let staged = &ledger.staged;
+        [
+            (
+                "snarked_fetch_hashes",
+                snarked.fetch_hashes_start,
+                snarked.fetch_hashes_end,
+            ),
+            (
+                "snarked_fetch_accounts",
+                snarked.fetch_accounts_start,
+                snarked.fetch_accounts_end,
+            ),
+            (
+                "staged_fetch_parts",
+                staged.fetch_parts_start,
+                staged.fetch_parts_end,
+            ),
+            (
+                "staged_reconstruct",
+                staged.reconstruct_start,
+                staged.reconstruct_end,
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(stage, start, end)| Some((stage, end?.checked_sub(start?)?)))
+        .collect()
+    }
+
+    fn snark_worker_status(status: &RpcSnarkWorkerStatus) -> &'static str {
+        match status {
+            RpcSnarkWorkerStatus::None => "none",
+            RpcSnarkWorkerStatus::Starting => "starting",
+            RpcSnarkWorkerStatus::Idle => "idle",
+            RpcSnarkWorkerStatus::Working { .. } => "working",
+            RpcSnarkWorkerStatus::WorkReady { .. } => "work_ready",
+            RpcSnarkWorkerStatus::WorkError { .. } => "work_error",
+            RpcSnarkWorkerStatus::Cancelling { .. } => "cancelling",
+            RpcSnarkWorkerStatus::Cancelled { .. } => "cancelled",
+            RpcSnarkWorkerStatus::Error { .. } => "error",
+            RpcSnarkWorkerStatus::Killing => "killing",
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeMap;
+
+        use node::account::AccountSecretKey;
+        use node::external_snark_worker::ExternalSnarkWorkerHealth;
+        use node::rpc::{
+            RpcMetrics, RpcMetricsLedger, RpcMetricsPeers, RpcNodeStatusSnarkPool,
+            RpcNodeStatusTransactionPool, RpcSnarkWorker, RpcSnarkWorkerStatus,
+        };
+        use node::stats::block_producer::BlockProducerKeyStats;
+
+        use super::{encode, Encoder};
+
+        fn metrics() -> RpcMetrics {
+            RpcMetrics {
+                action_stats: None,
+                sync_stats: None,
+                sync_phase: "Synced".to_owned(),
+                peers: RpcMetricsPeers {
+                    connecting: 1,
+                    connected: 5,
+                    disconnected: 2,
+                },
+                transaction_pool: RpcNodeStatusTransactionPool { transactions: 3 },
+                snark_pool: RpcNodeStatusSnarkPool {
+                    total_jobs: 8,
+                    snarks: 6,
+                },
+                snark_workers: Vec::new(),
+                block_producer: None,
+                ledger: RpcMetricsLedger {
+                    pending_reads: 4,
+                    pending_reads_cost: 40,
+                    pending_writes: 1,
+                },
+                p2p_traffic: None,
+            }
+        }
+
+        /// Sample lines, without the `# HELP` and `# TYPE` lines.
+        fn samples(out: &str) -> Vec<&str> {
+            out.lines().filter(|line| !line.starts_with('#')).collect()
+        }
+
+        #[test]
+        fn test_encoder_family_and_samples() {
+            let mut enc = Encoder::default();
+            enc.family("peers", "gauge", "Known peers.");
+            enc.sample("peers", &[], 3);
+            enc.sample("peers", &[("state", "connected"), ("kind", "rust")], 2);
+
+            assert_eq!(
+                enc.out,
+                "# HELP openmina_peers Known peers.\n\
+                 # TYPE openmina_peers gauge\n\
+                 openmina_peers 3\n\
+                 openmina_peers{state=\"connected\",kind=\"rust\"} 2\n"
+            );
+        }
+
+        #[test]
+        fn test_encoder_escapes_label_values() {
+            let mut enc = Encoder::default();
+            enc.sample("sync_phase", &[("phase", "a\\b\"c\nd")], 1);
+
+            assert_eq!(
+                enc.out,
+                "openmina_sync_phase{phase=\"a\\\\b\\\"c\\nd\"} 1\n"
+            );
+        }
+
+        #[test]
+        fn test_encode_node_metrics() {
+            let out = encode(&metrics());
+            let samples = samples(&out);
+
+            for expected in [
+                "openmina_sync_phase{phase=\"Synced\"} 1",
+                "openmina_peers{state=\"connecting\"} 1",
+                "openmina_peers{state=\"connected\"} 5",
+                "openmina_peers{state=\"disconnected\"} 2",
+                "openmina_transaction_pool_size 3",
+                "openmina_snark_pool_jobs 8",
+                "openmina_snark_pool_snarks 6",
+                "openmina_ledger_read_pending 4",
+                "openmina_ledger_read_pending_cost 40",
+                "openmina_ledger_write_pending 1",
+            ] {
+                assert!(
+                    samples.contains(&expected),
+                    "missing `{expected}` in:\n{out}"
+                );
+            }
+            assert!(!out.contains("openmina_block_producer_attempts_total"));
+            assert!(!out.contains("openmina_snark_worker_status"));
+        }
+
+        #[test]
+        fn test_encode_families_declared_once_before_samples() {
+            let mut metrics = metrics();
+            metrics.snark_workers = vec![RpcSnarkWorker {
+                time: None,
+                id: Some("worker".to_owned()),
+                status: RpcSnarkWorkerStatus::Idle,
+                health: ExternalSnarkWorkerHealth::default(),
+            }];
+            let out = encode(&metrics);
+
+            let mut declared = Vec::new();
+            for line in out.lines() {
+                if let Some(family) = line.strip_prefix("# TYPE ") {
+                    let (name, kind) = family.split_once(' ').unwrap();
+                    assert!(
+                        ["gauge", "counter", "histogram"].contains(&kind),
+                        "unexpected type: {line}"
+                    );
+                    assert!(!declared.contains(&name), "declared twice: {name}");
+                    declared.push(name);
+                } else if !line.starts_with("# HELP ") {
+                    let (name, value) = line.rsplit_once(' ').unwrap();
+                    let name = name.split('{').next().unwrap();
+                    assert_eq!(declared.last(), Some(&name), "undeclared sample: {line}");
+                    assert!(value.parse::<f64>().is_ok(), "invalid value: {line}");
+                }
+            }
+        }
+
+        #[test]
+        fn test_encode_block_producer_counters() {
+            let producer = AccountSecretKey::genesis_producer().public_key();
+            let stats = BlockProducerKeyStats {
+                won_slots: 5,
+                produced: 4,
+                canonical: 3,
+                orphaned: 1,
+                discarded: 1,
+            };
+            let mut metrics = metrics();
+            metrics.block_producer = Some(BTreeMap::from([(producer.clone(), stats)]));
+            let out = encode(&metrics);
+
+            assert!(out.contains("# TYPE openmina_block_producer_attempts_total counter\n"));
+            let samples = samples(&out);
+            for (outcome, count) in [
+                ("won_slot", 5),
+                ("produced", 4),
+                ("canonical", 3),
+                ("orphaned", 1),
+                ("discarded", 1),
+            ] {
+                let expected = format!(
+                    "openmina_block_producer_attempts_total{{producer=\"{producer}\",outcome=\"{outcome}\"}} {count}"
+                );
+                assert!(
+                    samples.contains(&expected.as_str()),
+                    "missing `{expected}` in:\n{out}"
+                );
+            }
+        }
+    }
+}
+
 fn with_rpc_sender(
     rpc_sender: RpcSender,
 ) -> impl warp::Filter<Extract = (RpcSender,), Error = Infallible> + Clone {
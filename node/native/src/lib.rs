@@ -2,6 +2,7 @@ pub use openmina_node_common::*;
 
 pub mod graphql;
 pub mod http_server;
+pub mod snark_worker_server;
 
 mod service;
 pub use service::{NodeService, *};
@@ -1,7 +1,8 @@
 use std::{
     fs::File,
     io::{BufRead, BufReader, Read},
-    path::Path,
+    net::SocketAddr,
+    path::{Path, PathBuf},
     sync::Arc,
     time::Duration,
 };
@@ -11,6 +12,7 @@ use ledger::proofs::provers::BlockProver;
 use mina_p2p_messages::v2::{self, NonZeroCurvePoint};
 use node::{
     account::AccountSecretKey,
+    block_producer::TransactionSelectionPolicy,
     daemon_json::Daemon,
     p2p::{
         channels::ChannelId, connection::outgoing::P2pConnectionOutgoingInitOpts,
@@ -23,10 +25,16 @@ use node::{
     SnarkerStrategy, TransitionFrontierConfig,
 };
 use openmina_core::{consensus::ConsensusConstants, constants::constraint_constants};
-use openmina_node_common::p2p::TaskSpawner;
+use openmina_node_common::{
+    p2p::{webrtc_with_libp2p::ban_list_load, TaskSpawner},
+    transaction_pool::locally_generated_load,
+};
 use rand::Rng;
 
-use crate::NodeServiceBuilder;
+use crate::{
+    snark_worker::RustSnarkWorkerSpawner, ExternalSnarkWorkerSpawner, NodeServiceBuilder,
+    SnarkWorkerBackend,
+};
 
 use super::Node;
 
@@ -39,14 +47,19 @@ pub struct NodeBuilder {
     p2p_is_seed: bool,
     p2p_no_discovery: bool,
     p2p_is_started: bool,
+    p2p_ban_list_path: Option<PathBuf>,
     initial_peers: Vec<P2pConnectionOutgoingInitOpts>,
     block_producer: Option<BlockProducerConfig>,
     snarker: Option<SnarkerConfig>,
+    snark_worker_backend: SnarkWorkerBackend,
     service: NodeServiceBuilder,
     verifier_srs: Option<Arc<VerifierSRS>>,
     block_verifier_index: Option<BlockVerifier>,
     work_verifier_index: Option<TransactionVerifier>,
     http_port: Option<u16>,
+    persistence_dir: Option<PathBuf>,
+    archive_dir: Option<PathBuf>,
+    transaction_pool_path: Option<PathBuf>,
     daemon_conf: Daemon,
 }
 
@@ -72,14 +85,19 @@ impl NodeBuilder {
             p2p_is_seed: false,
             p2p_no_discovery: false,
             p2p_is_started: false,
+            p2p_ban_list_path: None,
             initial_peers: Vec::new(),
             block_producer: None,
             snarker: None,
+            snark_worker_backend: SnarkWorkerBackend::default(),
             service: NodeServiceBuilder::new(rng_seed),
             verifier_srs: None,
             block_verifier_index: None,
             work_verifier_index: None,
             http_port: None,
+            persistence_dir: None,
+            archive_dir: None,
+            transaction_pool_path: None,
             daemon_conf,
         }
     }
@@ -112,6 +130,20 @@ impl NodeBuilder {
         self
     }
 
+    /// Persist banned peers in the file at `path`, so that the bans
+    /// are still in effect after a restart.
+    pub fn p2p_ban_list(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.p2p_ban_list_path = Some(path.into());
+        self
+    }
+
+    /// Persist locally generated transactions in the file at `path`, so that
+    /// the ones not yet included in a block are restored after a restart.
+    pub fn transaction_pool_persistence(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.transaction_pool_path = Some(path.into());
+        self
+    }
+
     /// Extend p2p initial peers from an iterable.
     pub fn initial_peers(
         &mut self,
@@ -167,13 +199,29 @@ impl NodeBuilder {
 
     /// Set up block producer.
     pub fn block_producer(&mut self, provers: BlockProver, key: AccountSecretKey) -> &mut Self {
+        self.block_producers(provers, key, Vec::new())
+    }
+
+    /// Set up block producer running several producer keys.
+    pub fn block_producers(
+        &mut self,
+        provers: BlockProver,
+        key: AccountSecretKey,
+        extra_keys: Vec<AccountSecretKey>,
+    ) -> &mut Self {
         let config = BlockProducerConfig {
             pub_key: key.public_key().into(),
+            extra_pub_keys: extra_keys
+                .iter()
+                .map(|key| key.public_key().into())
+                .collect(),
             custom_coinbase_receiver: None,
             proposed_protocol_version: None,
+            transaction_selection: Default::default(),
         };
         self.block_producer = Some(config);
-        self.service.block_producer_init(provers, key);
+        let keys = std::iter::once(key).chain(extra_keys).collect();
+        self.service.block_producer_init(provers, keys);
         self
     }
 
@@ -189,6 +237,32 @@ impl NodeBuilder {
         Ok(self.block_producer(provers, key))
     }
 
+    /// Set up block producer using keys from several files, encrypted
+    /// with the same password.
+    pub fn block_producer_from_files<P: AsRef<Path>>(
+        &mut self,
+        provers: BlockProver,
+        paths: &[P],
+        password: &str,
+    ) -> anyhow::Result<&mut Self> {
+        let mut keys = paths
+            .iter()
+            .map(|path| {
+                AccountSecretKey::from_encrypted_file(path, password).with_context(|| {
+                    format!(
+                        "Failed to decrypt secret key file {}",
+                        path.as_ref().display()
+                    )
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .into_iter();
+        let key = keys
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("no block producer key files"))?;
+        Ok(self.block_producers(provers, key, keys.collect()))
+    }
+
     /// Receive block producer's coinbase reward to another account.
     pub fn custom_coinbase_receiver(
         &mut self,
@@ -203,6 +277,20 @@ impl NodeBuilder {
         Ok(self)
     }
 
+    /// Set the policy used to select transactions included in the produced blocks.
+    pub fn transaction_selection_policy(
+        &mut self,
+        policy: TransactionSelectionPolicy,
+    ) -> anyhow::Result<&mut Self> {
+        let bp = self.block_producer.as_mut().ok_or_else(|| {
+            anyhow::anyhow!(
+                "can't set transaction_selection_policy when block producer is not initialized."
+            )
+        })?;
+        bp.transaction_selection = policy;
+        Ok(self)
+    }
+
     pub fn custom_block_producer_config(
         &mut self,
         config: BlockProducerConfig,
@@ -226,11 +314,43 @@ impl NodeBuilder {
             )),
             strategy,
             auto_commit: true,
+            local_workers: 1,
         };
         self.snarker = Some(config);
         self
     }
 
+    /// Set the implementation used by local snark workers. Defaults to
+    /// the external OCaml worker.
+    pub fn snarker_backend(&mut self, backend: SnarkWorkerBackend) -> anyhow::Result<&mut Self> {
+        if self.snarker.is_none() {
+            anyhow::bail!("snarker not initialized! Call `snarker` function first.");
+        }
+        self.snark_worker_backend = backend;
+        Ok(self)
+    }
+
+    /// Set the number of local snark worker processes.
+    pub fn snarker_local_workers(&mut self, count: u16) -> anyhow::Result<&mut Self> {
+        self.snarker
+            .as_mut()
+            .ok_or_else(|| {
+                anyhow::anyhow!("snarker not initialized! Call `snarker` function first.")
+            })?
+            .local_workers = count;
+        Ok(self)
+    }
+
+    /// Accept remote snark workers, which use the OCaml snark worker
+    /// protocol, on `addr`.
+    pub fn snarker_remote_workers(&mut self, addr: SocketAddr) -> anyhow::Result<&mut Self> {
+        if self.snarker.is_none() {
+            anyhow::bail!("snarker not initialized! Call `snarker` function first.");
+        }
+        self.service.snark_worker_server_init(addr);
+        Ok(self)
+    }
+
     /// Set verifier srs. If not set, default will be used.
     pub fn verifier_srs(&mut self, srs: Arc<VerifierSRS>) -> &mut Self {
         self.verifier_srs = Some(srs);
@@ -263,6 +383,20 @@ impl NodeBuilder {
         self
     }
 
+    /// Persist the transition frontier and ledgers under `dir`, and resume
+    /// from them (if present) at startup instead of syncing from scratch.
+    pub fn persistence(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.persistence_dir = Some(dir.into());
+        self
+    }
+
+    /// Archive blocks of the best chain, together with their transactions
+    /// and the accounts they changed, into the database under `dir`.
+    pub fn archive(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.archive_dir = Some(dir.into());
+        self
+    }
+
     pub fn build(self) -> anyhow::Result<Node> {
         let p2p_sec_key = self.p2p_sec_key.unwrap_or_else(P2pSecretKey::rand);
         let initial_peers = if self.initial_peers.is_empty() && !self.p2p_is_seed {
@@ -282,6 +416,40 @@ impl NodeBuilder {
             })
             .collect();
 
+        let initial_banned_peers = self
+            .p2p_ban_list_path
+            .as_ref()
+            .and_then(|path| {
+                ban_list_load(path)
+                    .map_err(|error| {
+                        openmina_core::warn!(
+                            openmina_core::log::system_time();
+                            message = "Failed to load banned peers",
+                            path = path.display().to_string(),
+                            error = error.to_string()
+                        );
+                    })
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        let restored_transactions = self
+            .transaction_pool_path
+            .as_ref()
+            .and_then(|path| {
+                locally_generated_load(path)
+                    .map_err(|error| {
+                        openmina_core::warn!(
+                            openmina_core::log::system_time();
+                            message = "Failed to load locally generated transactions",
+                            path = path.display().to_string(),
+                            error = error.to_string()
+                        );
+                    })
+                    .ok()
+            })
+            .unwrap_or_default();
+
         let srs = self.verifier_srs.unwrap_or_else(get_srs);
         let block_verifier_index = self
             .block_verifier_index
@@ -311,6 +479,7 @@ impl NodeBuilder {
                 listen_port: self.http_port,
                 identity_pub_key: p2p_sec_key.public_key(),
                 initial_peers,
+                initial_banned_peers,
                 ask_initial_peers_interval: Duration::from_secs(3600),
                 enabled_channels: ChannelId::iter_all().collect(),
                 peer_discovery: !self.p2p_no_discovery,
@@ -341,14 +510,42 @@ impl NodeBuilder {
 
         // build service
         let mut service = self.service;
-        service.ledger_init();
+        if node_config.global.snarker.is_some() {
+            match self.snark_worker_backend {
+                SnarkWorkerBackend::External => {
+                    service.snark_worker_init(ExternalSnarkWorkerSpawner {});
+                }
+                SnarkWorkerBackend::Rust => {
+                    service.snark_worker_init(RustSnarkWorkerSpawner::new(
+                        node_config.snark.work_verifier_index.clone(),
+                        node_config.snark.work_verifier_srs.clone(),
+                    ));
+                }
+            }
+        }
+        if let Some(dir) = self.archive_dir {
+            service.archive_init(dir);
+        }
+        let restored_frontier = match &self.persistence_dir {
+            Some(dir) => service.ledger_init_with_persistence(dir),
+            None => {
+                service.ledger_init();
+                None
+            }
+        };
 
         if !self.p2p_is_started {
             service.p2p_init(p2p_sec_key);
         }
 
-        let service = service.build()?;
-        let state = node::State::new(node_config, &consensus_consts, initial_time);
+        let mut service = service.build()?;
+        service.p2p.ban_list_path = self.p2p_ban_list_path;
+        service.transaction_pool_path = self.transaction_pool_path;
+        let mut state = node::State::new(node_config, &consensus_consts, initial_time);
+        if let Some(restored_frontier) = restored_frontier {
+            state.transition_frontier.restore(restored_frontier);
+        }
+        state.transaction_pool.restore(restored_transactions);
 
         Ok(Node::new(self.rng_seed, state, service, None))
     }
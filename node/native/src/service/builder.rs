@@ -1,14 +1,18 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
 use ledger::proofs::provers::BlockProver;
 use node::{
-    account::AccountSecretKey, core::thread, p2p::identity::SecretKey as P2pSecretKey,
-    service::Recorder,
+    account::AccountSecretKey, core::thread, ledger::RestoredFrontier,
+    p2p::identity::SecretKey as P2pSecretKey, service::Recorder,
 };
 pub use openmina_node_common::NodeServiceCommonBuildError;
 use openmina_node_common::{
-    p2p::TaskSpawner, rpc::RpcSender, EventSender, NodeServiceCommonBuilder,
+    p2p::TaskSpawner, rpc::RpcSender, snark_worker::SnarkWorkerSpawner, EventSender,
+    NodeServiceCommonBuilder,
 };
 
-use crate::{http_server, NodeService, P2pTaskSpawner};
+use crate::{http_server, snark_worker_server, NodeService, P2pTaskSpawner};
 
 pub struct NodeServiceBuilder {
     common: NodeServiceCommonBuilder,
@@ -39,17 +43,29 @@ impl NodeServiceBuilder {
         self.common.rpc_sender()
     }
 
+    pub fn archive_init(&mut self, path: impl Into<PathBuf>) -> &mut Self {
+        self.common.archive_init(path);
+        self
+    }
+
     pub fn ledger_init(&mut self) -> &mut Self {
         self.common.ledger_init();
         self
     }
 
+    pub fn ledger_init_with_persistence(
+        &mut self,
+        path: impl AsRef<Path>,
+    ) -> Option<RestoredFrontier> {
+        self.common.ledger_init_with_persistence(path)
+    }
+
     pub fn block_producer_init(
         &mut self,
         provers: BlockProver,
-        keypair: AccountSecretKey,
+        keypairs: Vec<AccountSecretKey>,
     ) -> &mut Self {
-        self.common.block_producer_init(provers, keypair);
+        self.common.block_producer_init(provers, keypairs);
         self
     }
 
@@ -67,6 +83,11 @@ impl NodeServiceBuilder {
         self
     }
 
+    pub fn snark_worker_init<S: 'static + SnarkWorkerSpawner>(&mut self, spawner: S) -> &mut Self {
+        self.common.snark_worker_init(spawner);
+        self
+    }
+
     pub fn gather_stats(&mut self) -> &mut Self {
         self.common.gather_stats();
         self
@@ -94,6 +115,20 @@ impl NodeServiceBuilder {
         self
     }
 
+    /// Accept remote snark workers on `addr`.
+    pub fn snark_worker_server_init(&mut self, addr: SocketAddr) -> &mut Self {
+        let rpc_sender = self.rpc_sender();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        thread::Builder::new()
+            .name("openmina_snark_worker_server".to_owned())
+            .spawn(move || runtime.block_on(snark_worker_server::run(addr, rpc_sender)))
+            .unwrap();
+        self
+    }
+
     pub fn build(self) -> Result<NodeService, NodeServiceBuildError> {
         let mut service = self.common.build()?;
         service.recorder = self.recorder;
@@ -20,15 +20,14 @@ use mina_p2p_messages::{
 use node::core::channels::{mpsc, oneshot};
 use node::event_source::Event;
 use node::external_snark_worker::{
-    ExternalSnarkWorkerError, ExternalSnarkWorkerEvent, ExternalSnarkWorkerService,
+    ExternalSnarkWorkerError, ExternalSnarkWorkerEvent, ExternalSnarkWorkerId,
     ExternalSnarkWorkerWorkError, SnarkWorkSpec,
 };
+use openmina_node_common::snark_worker::{SnarkWorker, SnarkWorkerSpawner};
 
 use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
 use tokio::process::Command;
 
-use super::NodeService;
-
 /// Error generated by external snarker controller.
 #[derive(Debug, thiserror::Error)]
 enum SnarkerError {
@@ -194,13 +193,16 @@ async fn stderr_reader<R: AsyncRead + Unpin>(r: R) -> Result<(), SnarkerError> {
 }
 
 macro_rules! send_event {
-    ($channel:expr, $event:expr) => {
-        _ = $channel.send(node::event_source::Event::ExternalSnarkWorker($event));
+    ($channel:expr, $worker_id:expr, $event:expr) => {
+        _ = $channel.send(node::event_source::Event::ExternalSnarkWorker(
+            $worker_id, $event,
+        ));
     };
 }
 
 impl ExternalSnarkWorkerFacade {
     fn start(
+        worker_id: ExternalSnarkWorkerId,
         public_key: NonZeroCurvePoint,
         fee: CurrencyFeeStableV1,
         event_sender: mpsc::UnboundedSender<Event>,
@@ -223,7 +225,7 @@ impl ExternalSnarkWorkerFacade {
 
         // TODO(akoptelov) make the block return terminal errors instead of sending them down the channel and exit.
         std::thread::Builder::new()
-            .name("external-snark-worker".into())
+            .name(format!("external-snark-worker-{worker_id}"))
             .spawn(move || {
                 let runtime = tokio::runtime::Builder::new_current_thread()
                     .enable_all()
@@ -240,7 +242,11 @@ impl ExternalSnarkWorkerFacade {
                     {
                         Ok(v) => v,
                         Err(err) => {
-                            send_event!(event_sender_clone, SnarkerError::from(err).into());
+                            send_event!(
+                                event_sender_clone,
+                                worker_id,
+                                SnarkerError::from(err).into()
+                            );
                             return;
                         }
                     };
@@ -254,7 +260,7 @@ impl ExternalSnarkWorkerFacade {
                             // readiness
                             let request = ExternalSnarkWorkerRequest::await_readiness();
                             if let Err(err) = write_binprot(request, &mut child_stdin).await {
-                                send_event!(event_sender_clone, err.into());
+                                send_event!(event_sender_clone, worker_id, err.into());
                                 return;
                             }
                             let response = read_binprot(&mut child_stdout).await;
@@ -262,12 +268,14 @@ impl ExternalSnarkWorkerFacade {
                                 Ok(v) if v => {
                                     send_event!(
                                         event_sender_clone,
+                                        worker_id,
                                         ExternalSnarkWorkerEvent::Started
                                     );
                                 }
                                 Ok(_) => {
                                     send_event!(
                                         event_sender_clone,
+                                        worker_id,
                                         SnarkerError::Broken(
                                             "snarker responded `false` on readiness request".into()
                                         )
@@ -276,7 +284,7 @@ impl ExternalSnarkWorkerFacade {
                                     return;
                                 }
                                 Err(err) => {
-                                    send_event!(event_sender_clone, err.into());
+                                    send_event!(event_sender_clone, worker_id, err.into());
                                     return;
                                 }
                             }
@@ -291,24 +299,30 @@ impl ExternalSnarkWorkerFacade {
                                     fee.clone(),
                                 );
                                 if let Err(err) = write_binprot(request, &mut child_stdin).await {
-                                    send_event!(event_sender_clone, err.into());
+                                    send_event!(event_sender_clone, worker_id, err.into());
                                     return;
                                 }
                                 let response = read_binprot(&mut child_stdout).await;
                                 match response {
                                     Ok(result) => match result {
                                         ExternalSnarkWorkerResult::Ok(Some(v)) => {
-                                            send_event!(event_sender_clone, Arc::new(v).into());
+                                            send_event!(
+                                                event_sender_clone,
+                                                worker_id,
+                                                Arc::new(v).into()
+                                            );
                                         }
                                         ExternalSnarkWorkerResult::Ok(None) => {
                                             send_event!(
                                                 event_sender_clone,
+                                                worker_id,
                                                 ExternalSnarkWorkerEvent::WorkCancelled
                                             );
                                         }
                                         ExternalSnarkWorkerResult::Err(err) => {
                                             send_event!(
                                                 event_sender_clone,
+                                                worker_id,
                                                 ExternalSnarkWorkerWorkError::Error(
                                                     err.to_string()
                                                 )
@@ -317,7 +331,7 @@ impl ExternalSnarkWorkerFacade {
                                         }
                                     },
                                     Err(err) => {
-                                        send_event!(event_sender_clone, err.into());
+                                        send_event!(event_sender_clone, worker_id, err.into());
                                     }
                                 }
                             }
@@ -333,7 +347,11 @@ impl ExternalSnarkWorkerFacade {
                                 if let Err(err) =
                                     nix::sys::signal::kill(pid, nix::sys::signal::Signal::SIGINT)
                                 {
-                                    send_event!(event_sender_clone, SnarkerError::from(err).into());
+                                    send_event!(
+                                        event_sender_clone,
+                                        worker_id,
+                                        SnarkerError::from(err).into()
+                                    );
                                 }
                             }
                         });
@@ -343,16 +361,24 @@ impl ExternalSnarkWorkerFacade {
                         let event_sender_clone = event_sender.clone();
                         tokio::spawn(async move {
                             if let Err(err) = stderr_reader(child_stderr).await {
-                                send_event!(event_sender_clone, err.into());
+                                send_event!(event_sender_clone, worker_id, err.into());
                             }
                         });
 
                         tokio::select! {
                             _ = kill_rx => {
                                 if let Err(err) = child.kill().await {
-                                    send_event!(event_sender, SnarkerError::from(err).into());
+                                    send_event!(
+                                        event_sender,
+                                        worker_id,
+                                        SnarkerError::from(err).into()
+                                    );
                                 } else {
-                                    send_event!(event_sender, ExternalSnarkWorkerEvent::Killed);
+                                    send_event!(
+                                        event_sender,
+                                        worker_id,
+                                        ExternalSnarkWorkerEvent::Killed
+                                    );
                                 }
                             }
                             _ = child.wait() => {
@@ -388,55 +414,34 @@ impl ExternalSnarkWorkerFacade {
     }
 }
 
-impl ExternalSnarkWorkerService for NodeService {
-    fn start(
-        &mut self,
-        public_key: NonZeroCurvePoint,
-        fee: CurrencyFeeStableV1,
-    ) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
-        if self.common.replayer.is_some() {
-            return Ok(());
-        }
-        let cmd_sender =
-            ExternalSnarkWorkerFacade::start(public_key, fee, self.common.event_sender().clone())?;
-        self.snark_worker_sender = Some(cmd_sender);
-        Ok(())
+impl SnarkWorker for ExternalSnarkWorkerFacade {
+    fn submit(&mut self, spec: SnarkWorkSpec) -> Result<(), ExternalSnarkWorkerError> {
+        Ok(ExternalSnarkWorkerFacade::submit(self, spec)?)
     }
 
-    fn submit(
-        &mut self,
-        spec: SnarkWorkSpec,
-    ) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
-        if self.common.replayer.is_some() {
-            return Ok(());
-        }
-        self.snark_worker_sender
-            .as_mut()
-            .ok_or(SnarkerError::NotRunning)
-            .and_then(|sender| sender.submit(spec))?;
-        Ok(())
+    fn cancel(&mut self) -> Result<(), ExternalSnarkWorkerError> {
+        Ok(ExternalSnarkWorkerFacade::cancel(self)?)
     }
 
-    fn cancel(&mut self) -> Result<(), ExternalSnarkWorkerError> {
-        if self.common.replayer.is_some() {
-            return Ok(());
-        }
-        self.snark_worker_sender
-            .as_mut()
-            .ok_or(SnarkerError::NotRunning)
-            .and_then(|sender| sender.cancel())?;
-        Ok(())
+    fn kill(self: Box<Self>) -> Result<(), ExternalSnarkWorkerError> {
+        Ok(ExternalSnarkWorkerFacade::kill(*self)?)
     }
+}
 
-    fn kill(&mut self) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
-        if self.common.replayer.is_some() {
-            return Ok(());
-        }
-        self.snark_worker_sender
-            .take()
-            .ok_or(SnarkerError::NotRunning)
-            .and_then(|sender| sender.kill())?;
-        Ok(())
+/// Runs local snark workers as external processes.
+#[derive(Clone)]
+pub struct ExternalSnarkWorkerSpawner {}
+
+impl SnarkWorkerSpawner for ExternalSnarkWorkerSpawner {
+    fn spawn(
+        &self,
+        worker_id: ExternalSnarkWorkerId,
+        public_key: NonZeroCurvePoint,
+        fee: CurrencyFeeStableV1,
+        event_sender: mpsc::UnboundedSender<Event>,
+    ) -> Result<Box<dyn SnarkWorker>, ExternalSnarkWorkerError> {
+        let worker = ExternalSnarkWorkerFacade::start(worker_id, public_key, fee, event_sender)?;
+        Ok(Box::new(worker))
     }
 }
 
@@ -453,16 +458,18 @@ mod tests {
     use node::core::log::inner::Level;
     use node::{
         event_source::Event,
-        external_snark_worker::{ExternalSnarkWorkerEvent, SnarkWorkSpec},
+        external_snark_worker::{ExternalSnarkWorkerEvent, ExternalSnarkWorkerId, SnarkWorkSpec},
     };
     use openmina_node_common::tracing;
 
     use super::ExternalSnarkWorkerFacade;
 
+    const WORKER_ID: ExternalSnarkWorkerId = ExternalSnarkWorkerId::Local(0);
+
     macro_rules! expect_event {
         ($source:expr, $event:pat) => {
             let result = $source.recv().await.expect("failed to receive an event");
-            let Event::ExternalSnarkWorker(result) = result else {
+            let Event::ExternalSnarkWorker(_, result) = result else {
                 panic!("unexpected event kind");
             };
             let $event = result else {
@@ -475,6 +482,7 @@ mod tests {
     async fn test_kill() {
         let (event_tx, mut event_rx) = mpsc::unbounded_channel();
         let cmd_sender = ExternalSnarkWorkerFacade::start(
+            WORKER_ID,
             NonZeroCurvePoint::default(),
             CurrencyFeeStableV1(
                 mina_p2p_messages::v2::UnsignedExtendedUInt64Int64ForVersionTagsStableV1(
@@ -514,7 +522,8 @@ mod tests {
         let (public_key, fee, instances) = read_input(&mut r);
 
         let (event_tx, mut event_rx) = mpsc::unbounded_channel();
-        let mut cmd_sender = ExternalSnarkWorkerFacade::start(public_key, fee, event_tx).unwrap();
+        let mut cmd_sender =
+            ExternalSnarkWorkerFacade::start(WORKER_ID, public_key, fee, event_tx).unwrap();
 
         expect_event!(event_rx, ExternalSnarkWorkerEvent::Started);
 
@@ -532,7 +541,8 @@ mod tests {
         let (public_key, fee, instances) = read_input(&mut r);
 
         let (event_tx, mut event_rx) = mpsc::unbounded_channel();
-        let mut cmd_sender = ExternalSnarkWorkerFacade::start(public_key, fee, event_tx).unwrap();
+        let mut cmd_sender =
+            ExternalSnarkWorkerFacade::start(WORKER_ID, public_key, fee, event_tx).unwrap();
 
         expect_event!(event_rx, ExternalSnarkWorkerEvent::Started);
 
@@ -562,7 +572,8 @@ mod tests {
         let (public_key, fee, instances) = read_input(&mut r);
 
         let (event_tx, mut event_rx) = mpsc::unbounded_channel();
-        let mut cmd_sender = ExternalSnarkWorkerFacade::start(public_key, fee, event_tx).unwrap();
+        let mut cmd_sender =
+            ExternalSnarkWorkerFacade::start(WORKER_ID, public_key, fee, event_tx).unwrap();
 
         expect_event!(event_rx, ExternalSnarkWorkerEvent::Started);
 
@@ -1,11 +1,40 @@
 mod builder;
 pub use builder::*;
 
+mod ext_snark_worker;
+pub use ext_snark_worker::ExternalSnarkWorkerSpawner;
+
 use openmina_node_common::p2p::TaskSpawner;
 pub use openmina_node_common::NodeService;
 
 use node::core::thread;
 
+/// Implementation used by local snark workers to produce snarks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SnarkWorkerBackend {
+    /// OCaml snark worker, run as an external process.
+    #[default]
+    External,
+    /// Rust provers, run in-process.
+    Rust,
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("invalid snark worker backend: {0}! expected one of: external/rust")]
+pub struct SnarkWorkerBackendParseError(String);
+
+impl std::str::FromStr for SnarkWorkerBackend {
+    type Err = SnarkWorkerBackendParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "external" | "ocaml" => Self::External,
+            "rust" => Self::Rust,
+            other => return Err(SnarkWorkerBackendParseError(other.to_owned())),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct P2pTaskSpawner {}
 
@@ -0,0 +1,192 @@
+//! Server side of the OCaml snark worker protocol, so that remote snark
+//! workers can fetch jobs from the node and submit the produced snarks.
+
+use std::{io, mem::size_of, net::SocketAddr};
+
+use mina_p2p_messages::{
+    binprot::{self, BinProtRead, BinProtWrite},
+    rpc::{SnarkWorkerFailedToGenerateSnarkV2, SnarkWorkerGetWorkV2, SnarkWorkerSubmitWorkV2},
+    rpc_kernel::{
+        Error as RpcError, MessageHeader, NeedsLength, PayloadBinprotReader, QueryHeader,
+        ResponseHeader, ResponsePayload, RpcMethod, RpcQueryReadError, RpcResult,
+    },
+};
+use node::{
+    core::snark::SnarkJobId,
+    p2p::network::rpc::RpcMessage,
+    rpc::{
+        RpcRequest, RpcSnarkerWorkerConnectionId, RpcSnarkerWorkerFailedResponse,
+        RpcSnarkerWorkerGetWorkResponse, RpcSnarkerWorkerSubmitWorkResponse,
+    },
+};
+use openmina_node_common::rpc::RpcSender;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Largest message accepted from a worker. Submitted proofs are the
+/// only big ones.
+const MAX_MESSAGE_SIZE: u64 = 32 * 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(transparent)]
+    IO(#[from] io::Error),
+    #[error(transparent)]
+    Binprot(#[from] binprot::Error),
+    #[error(transparent)]
+    Query(#[from] RpcQueryReadError),
+    #[error("message of {0} bytes exceeds the limit")]
+    TooLarge(u64),
+    #[error("state machine shut down")]
+    Shutdown,
+}
+
+pub async fn run(addr: SocketAddr, rpc_sender: RpcSender) {
+    let listener = match TcpListener::bind(addr).await {
+        Ok(v) => v,
+        Err(err) => {
+            node::core::log::error!(
+                node::core::log::system_time();
+                summary = "failed to start snark worker server",
+                addr = addr.to_string(),
+                error = err.to_string()
+            );
+            return;
+        }
+    };
+
+    let mut next_connection_id: RpcSnarkerWorkerConnectionId = 0;
+    loop {
+        let (stream, addr) = match listener.accept().await {
+            Ok(v) => v,
+            Err(err) => {
+                node::core::log::warn!(
+                    node::core::log::system_time();
+                    summary = "failed to accept snark worker connection",
+                    error = err.to_string()
+                );
+                continue;
+            }
+        };
+        let connection_id = next_connection_id;
+        next_connection_id += 1;
+        let rpc_sender = rpc_sender.clone();
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, connection_id, rpc_sender).await {
+                node::core::log::debug!(
+                    node::core::log::system_time();
+                    summary = "snark worker connection closed",
+                    worker = addr.to_string(),
+                    connection_id = connection_id,
+                    error = err.to_string()
+                );
+            }
+        });
+    }
+}
+
+async fn handle_connection(
+    mut stream: TcpStream,
+    connection_id: RpcSnarkerWorkerConnectionId,
+    rpc_sender: RpcSender,
+) -> Result<(), Error> {
+    // Both sides start by sending the same handshake.
+    stream
+        .write_all(&RpcMessage::Handshake.into_bytes())
+        .await?;
+    read_message(&mut stream).await?;
+
+    loop {
+        let message = read_message(&mut stream).await?;
+        let mut bytes = message.as_slice();
+        let QueryHeader { tag, version, id } = match MessageHeader::binprot_read(&mut bytes)? {
+            MessageHeader::Heartbeat => {
+                stream
+                    .write_all(&RpcMessage::Heartbeat.into_bytes())
+                    .await?;
+                continue;
+            }
+            MessageHeader::Response(_) => continue,
+            MessageHeader::Query(header) => header,
+        };
+
+        let response = match (tag.as_ref(), version) {
+            (SnarkWorkerGetWorkV2::NAME, SnarkWorkerGetWorkV2::VERSION) => {
+                SnarkWorkerGetWorkV2::query_payload(&mut bytes)?;
+                let work = rpc_sender
+                    .oneshot_request::<RpcSnarkerWorkerGetWorkResponse>(
+                        RpcRequest::SnarkerWorkerGetWork(connection_id),
+                    )
+                    .await
+                    .ok_or(Error::Shutdown)?;
+                encode_response(id, ok(work))?
+            }
+            (SnarkWorkerSubmitWorkV2::NAME, SnarkWorkerSubmitWorkV2::VERSION) => {
+                let work = SnarkWorkerSubmitWorkV2::query_payload(&mut bytes)?;
+                let response = rpc_sender
+                    .oneshot_request::<RpcSnarkerWorkerSubmitWorkResponse>(
+                        RpcRequest::SnarkerWorkerSubmitWork(connection_id, Box::new(work)),
+                    )
+                    .await
+                    .ok_or(Error::Shutdown)?;
+                if let RpcSnarkerWorkerSubmitWorkResponse::NotAssigned = response {
+                    node::core::log::warn!(
+                        node::core::log::system_time();
+                        summary = "rejected snark from worker, job isn't assigned to it",
+                        connection_id = connection_id
+                    );
+                }
+                encode_response(id, ok(()))?
+            }
+            (
+                SnarkWorkerFailedToGenerateSnarkV2::NAME,
+                SnarkWorkerFailedToGenerateSnarkV2::VERSION,
+            ) => {
+                let (error, spec, _) =
+                    SnarkWorkerFailedToGenerateSnarkV2::query_payload(&mut bytes)?;
+                let job_id = SnarkJobId::from(&spec.instances);
+                rpc_sender
+                    .oneshot_request::<RpcSnarkerWorkerFailedResponse>(
+                        RpcRequest::SnarkerWorkerFailed(connection_id, job_id, error.to_string()),
+                    )
+                    .await
+                    .ok_or(Error::Shutdown)?;
+                encode_response(id, ok(()))?
+            }
+            _ => encode_response::<()>(
+                id,
+                RpcResult(Err(RpcError::Unimplemented_rpc(tag, version))),
+            )?,
+        };
+        stream.write_all(&response).await?;
+    }
+}
+
+fn ok<T>(v: T) -> ResponsePayload<T> {
+    RpcResult(Ok(NeedsLength(v)))
+}
+
+/// Encodes response, prefixed with 8-bytes le size.
+fn encode_response<T: BinProtWrite>(id: u64, data: ResponsePayload<T>) -> Result<Vec<u8>, Error> {
+    let mut buf = vec![0; size_of::<u64>()];
+    MessageHeader::Response(ResponseHeader { id }).binprot_write(&mut buf)?;
+    data.binprot_write(&mut buf)?;
+    let len = (buf.len() - size_of::<u64>()) as u64;
+    buf[..size_of::<u64>()].copy_from_slice(&len.to_le_bytes());
+    Ok(buf)
+}
+
+/// Reads a message, prefixed with 8-bytes le size.
+async fn read_message(stream: &mut TcpStream) -> Result<Vec<u8>, Error> {
+    let mut len_buf = [0; size_of::<u64>()];
+    stream.read_exact(&mut len_buf).await?;
+    let len = u64::from_le_bytes(len_buf);
+    if len > MAX_MESSAGE_SIZE {
+        return Err(Error::TooLarge(len));
+    }
+    let mut buf = vec![0; len as usize];
+    stream.read_exact(&mut buf).await?;
+    Ok(buf)
+}
@@ -343,6 +343,7 @@ pub enum ActionKind {
     P2pConnectionOutgoingEffectfulRandomInit,
     P2pDisconnectionFinish,
     P2pDisconnectionInit,
+    P2pDisconnectionEffectfulBanListPersist,
     P2pDisconnectionEffectfulInit,
     P2pEffectfulInitialize,
     P2pIdentifyNewRequest,
@@ -467,23 +468,39 @@ pub enum ActionKind {
     P2pNetworkYamuxOutgoingData,
     P2pNetworkYamuxOutgoingFrame,
     P2pNetworkYamuxPingStream,
+    P2pPeerBan,
     P2pPeerBestTipUpdate,
     P2pPeerDiscovered,
     P2pPeerReady,
     P2pPeerRemove,
+    P2pPeerUnban,
     RpcActionStatsGet,
+    RpcArchiveGetInit,
+    RpcArchiveGetLedgerInit,
+    RpcArchiveGetPending,
+    RpcArchiveGetSuccess,
     RpcBestChain,
+    RpcBestTipSubscribe,
+    RpcBestTipSubscribersNotify,
+    RpcBlockGet,
     RpcBlockProducerStatsGet,
     RpcConsensusConstantsGet,
     RpcDiscoveryBoostrapStats,
     RpcDiscoveryRoutingTable,
+    RpcFeeEstimate,
     RpcFinish,
+    RpcGenesisBlockGet,
     RpcGlobalStateGet,
+    RpcHardForkConfigGetInit,
+    RpcHardForkConfigGetPending,
+    RpcHardForkConfigGetSuccess,
+    RpcHardForkConfigLedgersGetInit,
     RpcHealthCheck,
     RpcLedgerAccountsGetInit,
     RpcLedgerAccountsGetPending,
     RpcLedgerAccountsGetSuccess,
     RpcMessageProgressGet,
+    RpcMetricsGet,
     RpcP2pConnectionIncomingAnswerReady,
     RpcP2pConnectionIncomingError,
     RpcP2pConnectionIncomingInit,
@@ -495,6 +512,8 @@ pub enum ActionKind {
     RpcP2pConnectionOutgoingPending,
     RpcP2pConnectionOutgoingSuccess,
     RpcPeersGet,
+    RpcPooledUserCommands,
+    RpcPooledZkappCommands,
     RpcReadinessCheck,
     RpcScanStateSummaryGetInit,
     RpcScanStateSummaryGetPending,
@@ -505,8 +524,12 @@ pub enum ActionKind {
     RpcSnarkerConfigGet,
     RpcSnarkerJobCommit,
     RpcSnarkerJobSpec,
+    RpcSnarkerWorkerFailed,
+    RpcSnarkerWorkerGetWork,
+    RpcSnarkerWorkerSubmitWork,
     RpcSnarkerWorkersGet,
     RpcStatusGet,
+    RpcSubscriptionEnd,
     RpcSyncStatsGet,
     RpcTransactionInjectFailure,
     RpcTransactionInjectInit,
@@ -514,8 +537,21 @@ pub enum ActionKind {
     RpcTransactionInjectRejected,
     RpcTransactionInjectSuccess,
     RpcTransactionPool,
+    RpcTransactionSimulateInit,
+    RpcTransactionSimulateLedgerInit,
+    RpcTransactionSimulatePending,
+    RpcTransactionSimulateSuccess,
     RpcTransactionStatusGet,
     RpcTransitionFrontierUserCommandsGet,
+    RpcWatchedAccountSubscribe,
+    RpcWatchedAccountSubscribersNotify,
+    RpcWatchedAccountsAdd,
+    RpcWatchedAccountsGet,
+    RpcWatchedAccountsRemove,
+    RpcZkappEventsGetInit,
+    RpcZkappEventsGetLedgerInit,
+    RpcZkappEventsGetPending,
+    RpcZkappEventsGetSuccess,
     SnarkBlockVerifyError,
     SnarkBlockVerifyFinish,
     SnarkBlockVerifyInit,
@@ -563,10 +599,12 @@ pub enum ActionKind {
     TransactionPoolBestTipChangedWithAccounts,
     TransactionPoolCollectTransactionsByFee,
     TransactionPoolRebroadcast,
+    TransactionPoolRebroadcastLocallyGenerated,
     TransactionPoolStartVerify,
     TransactionPoolStartVerifyWithAccounts,
     TransactionPoolVerifyError,
     TransactionPoolEffectfulFetchAccounts,
+    TransactionPoolEffectfulLocallyGeneratedPersist,
     TransitionFrontierGenesisInject,
     TransitionFrontierSyncFailed,
     TransitionFrontierSynced,
@@ -653,11 +691,12 @@ pub enum ActionKind {
     WatchedAccountsLedgerInitialStateGetPending,
     WatchedAccountsLedgerInitialStateGetRetry,
     WatchedAccountsLedgerInitialStateGetSuccess,
+    WatchedAccountsRemove,
     WatchedAccountsTransactionsIncludedInBlock,
 }
 
 impl ActionKind {
-    pub const COUNT: u16 = 545;
+    pub const COUNT: u16 = 584;
 }
 
 impl std::fmt::Display for ActionKind {
@@ -863,6 +902,9 @@ impl ActionKindGet for TransactionPoolAction {
                 ActionKind::TransactionPoolApplyTransitionFrontierDiffWithAccounts
             }
             Self::Rebroadcast { .. } => ActionKind::TransactionPoolRebroadcast,
+            Self::RebroadcastLocallyGenerated => {
+                ActionKind::TransactionPoolRebroadcastLocallyGenerated
+            }
             Self::CollectTransactionsByFee => ActionKind::TransactionPoolCollectTransactionsByFee,
         }
     }
@@ -872,6 +914,9 @@ impl ActionKindGet for TransactionPoolEffectfulAction {
     fn kind(&self) -> ActionKind {
         match self {
             Self::FetchAccounts { .. } => ActionKind::TransactionPoolEffectfulFetchAccounts,
+            Self::LocallyGeneratedPersist { .. } => {
+                ActionKind::TransactionPoolEffectfulLocallyGeneratedPersist
+            }
         }
     }
 }
@@ -879,18 +924,18 @@ impl ActionKindGet for TransactionPoolEffectfulAction {
 impl ActionKindGet for ExternalSnarkWorkerAction {
     fn kind(&self) -> ActionKind {
         match self {
-            Self::Start => ActionKind::ExternalSnarkWorkerStart,
-            Self::Started => ActionKind::ExternalSnarkWorkerStarted,
+            Self::Start { .. } => ActionKind::ExternalSnarkWorkerStart,
+            Self::Started { .. } => ActionKind::ExternalSnarkWorkerStarted,
             Self::StartTimeout { .. } => ActionKind::ExternalSnarkWorkerStartTimeout,
-            Self::Kill => ActionKind::ExternalSnarkWorkerKill,
-            Self::Killed => ActionKind::ExternalSnarkWorkerKilled,
+            Self::Kill { .. } => ActionKind::ExternalSnarkWorkerKill,
+            Self::Killed { .. } => ActionKind::ExternalSnarkWorkerKilled,
             Self::SubmitWork { .. } => ActionKind::ExternalSnarkWorkerSubmitWork,
             Self::WorkResult { .. } => ActionKind::ExternalSnarkWorkerWorkResult,
             Self::WorkError { .. } => ActionKind::ExternalSnarkWorkerWorkError,
             Self::WorkTimeout { .. } => ActionKind::ExternalSnarkWorkerWorkTimeout,
-            Self::CancelWork => ActionKind::ExternalSnarkWorkerCancelWork,
-            Self::WorkCancelled => ActionKind::ExternalSnarkWorkerWorkCancelled,
-            Self::PruneWork => ActionKind::ExternalSnarkWorkerPruneWork,
+            Self::CancelWork { .. } => ActionKind::ExternalSnarkWorkerCancelWork,
+            Self::WorkCancelled { .. } => ActionKind::ExternalSnarkWorkerWorkCancelled,
+            Self::PruneWork { .. } => ActionKind::ExternalSnarkWorkerPruneWork,
             Self::Error { .. } => ActionKind::ExternalSnarkWorkerError,
         }
     }
@@ -937,6 +982,7 @@ impl ActionKindGet for RpcAction {
             Self::SyncStatsGet { .. } => ActionKind::RpcSyncStatsGet,
             Self::BlockProducerStatsGet { .. } => ActionKind::RpcBlockProducerStatsGet,
             Self::MessageProgressGet { .. } => ActionKind::RpcMessageProgressGet,
+            Self::MetricsGet { .. } => ActionKind::RpcMetricsGet,
             Self::PeersGet { .. } => ActionKind::RpcPeersGet,
             Self::P2pConnectionOutgoingInit { .. } => ActionKind::RpcP2pConnectionOutgoingInit,
             Self::P2pConnectionOutgoingPending { .. } => {
@@ -972,6 +1018,9 @@ impl ActionKindGet for RpcAction {
             Self::SnarkerJobCommit { .. } => ActionKind::RpcSnarkerJobCommit,
             Self::SnarkerJobSpec { .. } => ActionKind::RpcSnarkerJobSpec,
             Self::SnarkerWorkersGet { .. } => ActionKind::RpcSnarkerWorkersGet,
+            Self::SnarkerWorkerGetWork { .. } => ActionKind::RpcSnarkerWorkerGetWork,
+            Self::SnarkerWorkerSubmitWork { .. } => ActionKind::RpcSnarkerWorkerSubmitWork,
+            Self::SnarkerWorkerFailed { .. } => ActionKind::RpcSnarkerWorkerFailed,
             Self::HealthCheck { .. } => ActionKind::RpcHealthCheck,
             Self::ReadinessCheck { .. } => ActionKind::RpcReadinessCheck,
             Self::DiscoveryRoutingTable { .. } => ActionKind::RpcDiscoveryRoutingTable,
@@ -989,8 +1038,43 @@ impl ActionKindGet for RpcAction {
                 ActionKind::RpcTransitionFrontierUserCommandsGet
             }
             Self::BestChain { .. } => ActionKind::RpcBestChain,
+            Self::BestTipSubscribe { .. } => ActionKind::RpcBestTipSubscribe,
+            Self::BestTipSubscribersNotify => ActionKind::RpcBestTipSubscribersNotify,
             Self::ConsensusConstantsGet { .. } => ActionKind::RpcConsensusConstantsGet,
             Self::TransactionStatusGet { .. } => ActionKind::RpcTransactionStatusGet,
+            Self::BlockGet { .. } => ActionKind::RpcBlockGet,
+            Self::GenesisBlockGet { .. } => ActionKind::RpcGenesisBlockGet,
+            Self::PooledUserCommands { .. } => ActionKind::RpcPooledUserCommands,
+            Self::PooledZkappCommands { .. } => ActionKind::RpcPooledZkappCommands,
+            Self::HardForkConfigGetInit { .. } => ActionKind::RpcHardForkConfigGetInit,
+            Self::HardForkConfigLedgersGetInit { .. } => {
+                ActionKind::RpcHardForkConfigLedgersGetInit
+            }
+            Self::HardForkConfigGetPending { .. } => ActionKind::RpcHardForkConfigGetPending,
+            Self::HardForkConfigGetSuccess { .. } => ActionKind::RpcHardForkConfigGetSuccess,
+            Self::WatchedAccountsAdd { .. } => ActionKind::RpcWatchedAccountsAdd,
+            Self::WatchedAccountsRemove { .. } => ActionKind::RpcWatchedAccountsRemove,
+            Self::WatchedAccountsGet { .. } => ActionKind::RpcWatchedAccountsGet,
+            Self::WatchedAccountSubscribe { .. } => ActionKind::RpcWatchedAccountSubscribe,
+            Self::WatchedAccountSubscribersNotify { .. } => {
+                ActionKind::RpcWatchedAccountSubscribersNotify
+            }
+            Self::TransactionSimulateInit { .. } => ActionKind::RpcTransactionSimulateInit,
+            Self::TransactionSimulateLedgerInit { .. } => {
+                ActionKind::RpcTransactionSimulateLedgerInit
+            }
+            Self::TransactionSimulatePending { .. } => ActionKind::RpcTransactionSimulatePending,
+            Self::TransactionSimulateSuccess { .. } => ActionKind::RpcTransactionSimulateSuccess,
+            Self::FeeEstimate { .. } => ActionKind::RpcFeeEstimate,
+            Self::ZkappEventsGetInit { .. } => ActionKind::RpcZkappEventsGetInit,
+            Self::ZkappEventsGetLedgerInit { .. } => ActionKind::RpcZkappEventsGetLedgerInit,
+            Self::ZkappEventsGetPending { .. } => ActionKind::RpcZkappEventsGetPending,
+            Self::ZkappEventsGetSuccess { .. } => ActionKind::RpcZkappEventsGetSuccess,
+            Self::ArchiveGetInit { .. } => ActionKind::RpcArchiveGetInit,
+            Self::ArchiveGetLedgerInit { .. } => ActionKind::RpcArchiveGetLedgerInit,
+            Self::ArchiveGetPending { .. } => ActionKind::RpcArchiveGetPending,
+            Self::ArchiveGetSuccess { .. } => ActionKind::RpcArchiveGetSuccess,
+            Self::SubscriptionEnd { .. } => ActionKind::RpcSubscriptionEnd,
             Self::Finish { .. } => ActionKind::RpcFinish,
         }
     }
@@ -1000,6 +1084,7 @@ impl ActionKindGet for WatchedAccountsAction {
     fn kind(&self) -> ActionKind {
         match self {
             Self::Add { .. } => ActionKind::WatchedAccountsAdd,
+            Self::Remove { .. } => ActionKind::WatchedAccountsRemove,
             Self::LedgerInitialStateGetInit { .. } => {
                 ActionKind::WatchedAccountsLedgerInitialStateGetInit
             }
@@ -1087,6 +1172,8 @@ impl ActionKindGet for P2pPeerAction {
             Self::Ready { .. } => ActionKind::P2pPeerReady,
             Self::BestTipUpdate { .. } => ActionKind::P2pPeerBestTipUpdate,
             Self::Remove { .. } => ActionKind::P2pPeerRemove,
+            Self::Ban { .. } => ActionKind::P2pPeerBan,
+            Self::Unban { .. } => ActionKind::P2pPeerUnban,
         }
     }
 }
@@ -1135,6 +1222,7 @@ impl ActionKindGet for P2pDisconnectionEffectfulAction {
     fn kind(&self) -> ActionKind {
         match self {
             Self::Init { .. } => ActionKind::P2pDisconnectionEffectfulInit,
+            Self::BanListPersist => ActionKind::P2pDisconnectionEffectfulBanListPersist,
         }
     }
 }
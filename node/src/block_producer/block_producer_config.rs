@@ -1,25 +1,45 @@
 use mina_p2p_messages::v2::{NonZeroCurvePoint, ProtocolVersionStableV2};
 use serde::{Deserialize, Serialize};
 
+use super::TransactionSelectionPolicy;
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BlockProducerConfig {
     pub pub_key: NonZeroCurvePoint,
+    /// Additional producer keys run by this node, next to `pub_key`.
+    #[serde(default)]
+    pub extra_pub_keys: Vec<NonZeroCurvePoint>,
     pub custom_coinbase_receiver: Option<NonZeroCurvePoint>,
     pub proposed_protocol_version: Option<ProtocolVersionStableV2>,
+    #[serde(default)]
+    pub transaction_selection: TransactionSelectionPolicy,
 }
 
 impl BlockProducerConfig {
     pub fn new(pub_key: NonZeroCurvePoint) -> Self {
         Self {
             pub_key,
+            extra_pub_keys: Vec::new(),
             custom_coinbase_receiver: None,
             proposed_protocol_version: None,
+            transaction_selection: Default::default(),
         }
     }
 
-    pub fn coinbase_receiver(&self) -> &NonZeroCurvePoint {
-        self.custom_coinbase_receiver
-            .as_ref()
-            .unwrap_or(&self.pub_key)
+    /// All producer keys run by this node.
+    pub fn pub_keys(&self) -> impl Iterator<Item = &NonZeroCurvePoint> {
+        std::iter::once(&self.pub_key).chain(&self.extra_pub_keys)
+    }
+
+    pub fn is_producer(&self, pub_key: &NonZeroCurvePoint) -> bool {
+        self.pub_keys().any(|key| key == pub_key)
+    }
+
+    /// Coinbase receiver for the block produced by `producer`.
+    pub fn coinbase_receiver<'a>(
+        &'a self,
+        producer: &'a NonZeroCurvePoint,
+    ) -> &'a NonZeroCurvePoint {
+        self.custom_coinbase_receiver.as_ref().unwrap_or(producer)
     }
 }
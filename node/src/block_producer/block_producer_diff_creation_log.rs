@@ -0,0 +1,301 @@
+use ledger::{
+    scan_state::currency::Fee,
+    staged_ledger::{
+        diff::AtMostTwo,
+        diff_creation_log::{
+            detail::Line,
+            summary::{Resources, Summary},
+            DiffCreationLog, Partition, Reason,
+        },
+    },
+};
+use serde::{Deserialize, Serialize};
+
+/// Log of the staged ledger diff creation for one partition of the scan
+/// state, with the reasons why commands and completed work were left out.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedLedgerDiffCreationLog {
+    pub summary: StagedLedgerDiffCreationSummary,
+    /// Resources left after each discarded command or completed work, from
+    /// the initial ones to the ones included in the diff.
+    pub details: Vec<StagedLedgerDiffCreationLine>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StagedLedgerDiffCreationPartition {
+    First,
+    Second,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StagedLedgerDiffCreationReason {
+    /// Command discarded because there wasn't enough completed work for it.
+    NoWork,
+    /// Command discarded because there wasn't enough space for it.
+    NoSpace,
+    /// Completed work discarded because the fees couldn't pay for it.
+    InsufficientFees,
+    /// Completed work discarded because it wasn't needed.
+    ExtraWork,
+    Init,
+    End,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedLedgerDiffCreationSummary {
+    pub partition: StagedLedgerDiffCreationPartition,
+    pub available_slots: u64,
+    pub required_work_count: u64,
+    pub start_resources: StagedLedgerDiffCreationResources,
+    pub end_resources: StagedLedgerDiffCreationResources,
+    pub discarded_commands_no_work: u64,
+    pub discarded_commands_no_space: u64,
+    pub discarded_completed_work_insufficient_fees: u64,
+    pub discarded_completed_work_extra_work: u64,
+}
+
+/// Fees are in nanomina.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedLedgerDiffCreationResources {
+    pub commands: u64,
+    pub commands_fees: u64,
+    pub completed_works: u64,
+    pub completed_works_fees: u64,
+    pub coinbase_work_fees: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedLedgerDiffCreationLine {
+    pub reason: StagedLedgerDiffCreationReason,
+    #[serde(flatten)]
+    pub resources: StagedLedgerDiffCreationResources,
+}
+
+impl StagedLedgerDiffCreationLog {
+    pub fn discarded_commands(&self) -> u64 {
+        self.summary.discarded_commands_no_work + self.summary.discarded_commands_no_space
+    }
+
+    pub fn discarded_completed_works(&self) -> u64 {
+        self.summary.discarded_completed_work_insufficient_fees
+            + self.summary.discarded_completed_work_extra_work
+    }
+}
+
+impl From<&DiffCreationLog> for StagedLedgerDiffCreationLog {
+    fn from(log: &DiffCreationLog) -> Self {
+        Self {
+            summary: (&log.summary).into(),
+            details: log.detail.0.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&Summary> for StagedLedgerDiffCreationSummary {
+    fn from(summary: &Summary) -> Self {
+        Self {
+            partition: match summary.partition {
+                Partition::First => StagedLedgerDiffCreationPartition::First,
+                Partition::Second => StagedLedgerDiffCreationPartition::Second,
+            },
+            available_slots: summary.available_slots,
+            required_work_count: summary.required_work_count,
+            start_resources: (&summary.start_resources).into(),
+            end_resources: (&summary.end_resources).into(),
+            discarded_commands_no_work: summary.discarded_commands.insufficient_work,
+            discarded_commands_no_space: summary.discarded_commands.insufficient_space,
+            discarded_completed_work_insufficient_fees: summary
+                .discarded_completed_work
+                .insufficient_fees,
+            discarded_completed_work_extra_work: summary.discarded_completed_work.extra_work,
+        }
+    }
+}
+
+impl From<&Resources> for StagedLedgerDiffCreationResources {
+    fn from(resources: &Resources) -> Self {
+        Self {
+            commands: resources.commands.0,
+            commands_fees: resources.commands.1.as_u64(),
+            completed_works: resources.completed_work.0,
+            completed_works_fees: resources.completed_work.1.as_u64(),
+            coinbase_work_fees: coinbase_work_fees(&resources.coinbase_work_fees),
+        }
+    }
+}
+
+impl From<&Line> for StagedLedgerDiffCreationLine {
+    fn from(line: &Line) -> Self {
+        Self {
+            reason: match line.reason {
+                Reason::NoWork => StagedLedgerDiffCreationReason::NoWork,
+                Reason::NoSpace => StagedLedgerDiffCreationReason::NoSpace,
+                Reason::InsufficientFees => StagedLedgerDiffCreationReason::InsufficientFees,
+                Reason::ExtraWork => StagedLedgerDiffCreationReason::ExtraWork,
+                Reason::Init => StagedLedgerDiffCreationReason::Init,
+                Reason::End => StagedLedgerDiffCreationReason::End,
+            },
+            resources: StagedLedgerDiffCreationResources {
+                commands: line.commands.0,
+                commands_fees: line.commands.1.as_u64(),
+                completed_works: line.completed_work.0,
+                completed_works_fees: line.completed_work.1.as_u64(),
+                coinbase_work_fees: coinbase_work_fees(&line.coinbase),
+            },
+        }
+    }
+}
+
+fn coinbase_work_fees(fees: &AtMostTwo<Fee>) -> Vec<u64> {
+    match fees {
+        AtMostTwo::Zero | AtMostTwo::One(None) | AtMostTwo::Two(None) => vec![],
+        AtMostTwo::One(Some(fee)) | AtMostTwo::Two(Some((fee, None))) => vec![fee.as_u64()],
+        AtMostTwo::Two(Some((fee1, Some(fee2)))) => vec![fee1.as_u64(), fee2.as_u64()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::staged_ledger::diff_creation_log::{
+        detail::Detail,
+        summary::{CommandConstraints, CompletedWorkConstraints},
+    };
+
+    use super::*;
+
+    fn fee(nanomina: u64) -> Fee {
+        Fee::from_u64(nanomina)
+    }
+
+    fn line(reason: Reason, commands: (u64, u64), completed_work: (u64, u64)) -> Line {
+        Line {
+            reason,
+            commands: (commands.0, fee(commands.1)),
+            completed_work: (completed_work.0, fee(completed_work.1)),
+            coinbase: AtMostTwo::One(Some(fee(5))),
+        }
+    }
+
+    /// 3 commands and 3 completed works, one command discarded for lack of
+    /// work, one for lack of space and one completed work not needed.
+    fn diff_creation_log() -> DiffCreationLog {
+        DiffCreationLog {
+            summary: Summary {
+                partition: Partition::Second,
+                start_resources: Resources {
+                    completed_work: (3, fee(30)),
+                    commands: (3, fee(300)),
+                    coinbase_work_fees: AtMostTwo::Two(Some((fee(5), Some(fee(7))))),
+                },
+                available_slots: 4,
+                required_work_count: 2,
+                discarded_commands: CommandConstraints {
+                    insufficient_work: 1,
+                    insufficient_space: 1,
+                },
+                discarded_completed_work: CompletedWorkConstraints {
+                    insufficient_fees: 0,
+                    extra_work: 1,
+                },
+                end_resources: Resources {
+                    completed_work: (2, fee(20)),
+                    commands: (1, fee(100)),
+                    coinbase_work_fees: AtMostTwo::One(Some(fee(5))),
+                },
+            },
+            detail: Detail(vec![
+                line(Reason::Init, (3, 300), (3, 30)),
+                line(Reason::NoWork, (2, 200), (3, 30)),
+                line(Reason::NoSpace, (1, 100), (3, 30)),
+                line(Reason::ExtraWork, (1, 100), (2, 20)),
+                line(Reason::End, (1, 100), (2, 20)),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_from_diff_creation_log() {
+        let log = StagedLedgerDiffCreationLog::from(&diff_creation_log());
+
+        let summary = &log.summary;
+        assert_eq!(summary.partition, StagedLedgerDiffCreationPartition::Second);
+        assert_eq!(summary.available_slots, 4);
+        assert_eq!(summary.required_work_count, 2);
+        assert_eq!(summary.start_resources.commands, 3);
+        assert_eq!(summary.start_resources.commands_fees, 300);
+        assert_eq!(summary.start_resources.completed_works, 3);
+        assert_eq!(summary.start_resources.completed_works_fees, 30);
+        assert_eq!(summary.start_resources.coinbase_work_fees, vec![5, 7]);
+        assert_eq!(summary.end_resources.commands, 1);
+        assert_eq!(summary.end_resources.completed_works_fees, 20);
+        assert_eq!(summary.end_resources.coinbase_work_fees, vec![5]);
+        assert_eq!(log.discarded_commands(), 2);
+        assert_eq!(log.discarded_completed_works(), 1);
+
+        let reasons = log
+            .details
+            .iter()
+            .map(|line| line.reason)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            [
+                StagedLedgerDiffCreationReason::Init,
+                StagedLedgerDiffCreationReason::NoWork,
+                StagedLedgerDiffCreationReason::NoSpace,
+                StagedLedgerDiffCreationReason::ExtraWork,
+                StagedLedgerDiffCreationReason::End,
+            ]
+        );
+        let no_space = &log.details[2].resources;
+        assert_eq!(no_space.commands, 1);
+        assert_eq!(no_space.commands_fees, 100);
+        assert_eq!(no_space.completed_works, 3);
+        assert_eq!(no_space.completed_works_fees, 30);
+    }
+
+    #[test]
+    fn test_coinbase_work_fees() {
+        assert!(coinbase_work_fees(&AtMostTwo::Zero).is_empty());
+        assert!(coinbase_work_fees(&AtMostTwo::One(None)).is_empty());
+        assert!(coinbase_work_fees(&AtMostTwo::Two(None)).is_empty());
+        assert_eq!(coinbase_work_fees(&AtMostTwo::One(Some(fee(1)))), vec![1]);
+        assert_eq!(
+            coinbase_work_fees(&AtMostTwo::Two(Some((fee(1), None)))),
+            vec![1]
+        );
+        assert_eq!(
+            coinbase_work_fees(&AtMostTwo::Two(Some((fee(1), Some(fee(2)))))),
+            vec![1, 2]
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let log = StagedLedgerDiffCreationLog::from(&diff_creation_log());
+
+        let json = serde_json::to_value(&log).unwrap();
+        assert_eq!(json["summary"]["partition"], "Second");
+        assert_eq!(json["summary"]["discarded_commands_no_work"], 1);
+        assert_eq!(
+            json["summary"]["start_resources"]["coinbase_work_fees"],
+            serde_json::json!([5, 7])
+        );
+        // the resources of a line are flattened next to its reason.
+        assert_eq!(
+            json["details"][1],
+            serde_json::json!({
+                "reason": "NoWork",
+                "commands": 2,
+                "commands_fees": 200,
+                "completed_works": 3,
+                "completed_works_fees": 30,
+                "coinbase_work_fees": [5],
+            })
+        );
+
+        let deserialized: StagedLedgerDiffCreationLog =
+            serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), json);
+    }
+}
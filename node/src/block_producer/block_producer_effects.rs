@@ -136,21 +136,22 @@ pub fn block_producer_effects<S: crate::Service>(
         }
         BlockProducerAction::StagedLedgerDiffCreateInit => {
             if let Some(stats) = store.service.stats() {
+                let rejected_transactions = store
+                    .state
+                    .get()
+                    .block_producer
+                    .rejected_transactions()
+                    .to_vec();
                 stats
                     .block_producer()
-                    .staged_ledger_diff_create_start(meta.time());
+                    .staged_ledger_diff_create_start(meta.time(), rejected_transactions);
             }
             let state = store.state.get();
-            let Some((won_slot, pred_block, producer, coinbase_receiver)) = None.or_else(|| {
+            let Some((won_slot, pred_block, config)) = None.or_else(|| {
                 let pred_block = state.block_producer.current_parent_chain()?.last()?;
                 let won_slot = state.block_producer.current_won_slot()?;
                 let config = state.block_producer.config()?;
-                Some((
-                    won_slot,
-                    pred_block,
-                    &config.pub_key,
-                    config.coinbase_receiver(),
-                ))
+                Some((won_slot, pred_block, config))
             }) else {
                 return;
             };
@@ -181,12 +182,13 @@ pub fn block_producer_effects<S: crate::Service>(
                     global_slot_since_genesis: won_slot
                         .global_slot_since_genesis(pred_block.global_slot_diff()),
                     is_new_epoch,
-                    producer: producer.clone(),
+                    producer: won_slot.producer.clone(),
                     delegator: won_slot.delegator.0.clone(),
-                    coinbase_receiver: coinbase_receiver.clone(),
+                    coinbase_receiver: config.coinbase_receiver(&won_slot.producer).clone(),
                     completed_snarks,
                     supercharge_coinbase,
                     transactions_by_fee,
+                    snark_fee_budget: config.transaction_selection.snark_fee_budget,
                 },
                 on_init: redux::callback!(
                     on_staged_ledger_diff_create_init(_request: LedgerWriteRequest) -> crate::Action {
@@ -196,11 +198,13 @@ pub fn block_producer_effects<S: crate::Service>(
             });
         }
         BlockProducerAction::StagedLedgerDiffCreatePending => {}
-        BlockProducerAction::StagedLedgerDiffCreateSuccess { .. } => {
+        BlockProducerAction::StagedLedgerDiffCreateSuccess { output } => {
             if let Some(stats) = store.service.stats() {
-                stats
-                    .block_producer()
-                    .staged_ledger_diff_create_end(meta.time());
+                stats.block_producer().staged_ledger_diff_create_end(
+                    meta.time(),
+                    output.diff_creation_log,
+                    output.rejected_transactions,
+                );
             }
             store.dispatch(BlockProducerAction::BlockUnprovenBuild);
         }
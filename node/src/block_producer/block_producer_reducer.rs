@@ -103,11 +103,17 @@ impl BlockProducerEnabled {
                     return;
                 };
 
+                let (transactions_by_fee, rejected_transactions) = self
+                    .config
+                    .transaction_selection
+                    .select(transactions_by_fee.clone());
+
                 self.current = BlockProducerCurrentState::WonSlotTransactionsSuccess {
                     time: meta.time(),
                     won_slot: won_slot.clone(),
                     chain: chain.clone(),
-                    transactions_by_fee: transactions_by_fee.clone(),
+                    transactions_by_fee,
+                    rejected_transactions,
                 }
             }
             BlockProducerAction::WonSlotProduceInit => {
@@ -213,8 +219,8 @@ impl BlockProducerEnabled {
                 let vrf_truncated_output: ConsensusVrfOutputTruncatedStableV1 =
                     (*won_slot.vrf_output).clone().into();
                 let vrf_hash = won_slot.vrf_output.hash();
-                let block_creator = self.config.pub_key.clone();
-                let coinbase_receiver = self.config.coinbase_receiver().clone();
+                let block_creator = won_slot.producer.clone();
+                let coinbase_receiver = self.config.coinbase_receiver(&block_creator).clone();
                 let proposed_protocol_version_opt = self.config.proposed_protocol_version.clone();
 
                 let ledger_proof_statement = ledger_proof_statement_from_emitted_proof(
@@ -7,6 +7,8 @@ use mina_p2p_messages::v2::{
 };
 use serde::{Deserialize, Serialize};
 
+use super::{StagedLedgerDiffCreationLog, TransactionSelectionRejected};
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct StagedLedgerDiffCreateOutput {
     pub diff: StagedLedgerDiffDiffStableV2,
@@ -17,6 +19,10 @@ pub struct StagedLedgerDiffCreateOutput {
     pub pending_coinbase_update: MinaBasePendingCoinbaseUpdateStableV1,
     pub pending_coinbase_witness: MinaBasePendingCoinbaseWitnessStableV2,
     pub stake_proof_sparse_ledger: MinaBaseSparseLedgerBaseStableV2,
+    pub diff_creation_log: Vec<StagedLedgerDiffCreationLog>,
+    /// Commands dropped while creating the diff, because the snark work
+    /// they needed was over the snark fee budget.
+    pub rejected_transactions: Vec<TransactionSelectionRejected>,
 }
 
 pub trait BlockProducerService {
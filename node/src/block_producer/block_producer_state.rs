@@ -12,7 +12,7 @@ use crate::account::AccountPublicKey;
 
 use super::{
     vrf_evaluator::BlockProducerVrfEvaluatorState, BlockProducerConfig, BlockProducerWonSlot,
-    BlockWithoutProof,
+    BlockWithoutProof, TransactionSelectionRejected,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -64,6 +64,8 @@ pub enum BlockProducerCurrentState {
         /// Chain that we are extending.
         chain: Vec<AppliedBlock>,
         transactions_by_fee: Vec<valid::UserCommand>,
+        /// Transactions rejected by the transaction selection policy.
+        rejected_transactions: Vec<TransactionSelectionRejected>,
     },
     StagedLedgerDiffCreatePending {
         time: redux::Timestamp,
@@ -177,13 +179,13 @@ impl BlockProducerState {
     }
 
     pub fn is_me(&self, producer: &v2::NonZeroCurvePoint) -> bool {
-        self.with(false, |this| producer == &this.config.pub_key)
+        self.with(false, |this| this.config.is_producer(producer))
     }
 
     /// Checks if the block was produced by us recently.
     pub fn is_produced_by_me(&self, block: &ArcBlockWithHash) -> bool {
         self.with(false, |this| {
-            block.producer() == &this.config.pub_key && this.injected_blocks.contains(block.hash())
+            this.config.is_producer(block.producer()) && this.injected_blocks.contains(block.hash())
         })
     }
 
@@ -223,13 +225,19 @@ impl BlockProducerState {
     }
 
     /// If we need to construct delegator table, get it's inputs.
-    pub fn vrf_delegator_table_inputs(&self) -> Option<(&v2::LedgerHash, &AccountPublicKey)> {
+    pub fn vrf_delegator_table_inputs(&self) -> Option<(&v2::LedgerHash, &[AccountPublicKey])> {
         self.vrf_evaluator()?.vrf_delegator_table_inputs()
     }
 
     pub fn pending_transactions(&self) -> Vec<valid::UserCommand> {
         self.with(Vec::new(), |this| this.current.pending_transactions())
     }
+
+    pub fn rejected_transactions(&self) -> &[TransactionSelectionRejected] {
+        self.with(Default::default(), |this| {
+            this.current.rejected_transactions()
+        })
+    }
 }
 
 impl BlockProducerCurrentState {
@@ -381,6 +389,16 @@ impl BlockProducerCurrentState {
             _ => vec![],
         }
     }
+
+    pub fn rejected_transactions(&self) -> &[TransactionSelectionRejected] {
+        match self {
+            Self::WonSlotTransactionsSuccess {
+                rejected_transactions,
+                ..
+            } => rejected_transactions,
+            _ => &[],
+        }
+    }
 }
 
 impl Default for BlockProducerCurrentState {
@@ -0,0 +1,380 @@
+use std::collections::BTreeSet;
+
+use ledger::{
+    scan_state::transaction_logic::{valid, GenericCommand},
+    AccountId,
+};
+use mina_p2p_messages::v2::{MinaBaseUserCommandStableV2, NonZeroCurvePoint, TransactionHash};
+use mina_signer::CompressedPubKey;
+use serde::{Deserialize, Serialize};
+
+/// Policies applied to the transactions collected from the transaction pool,
+/// before they are passed to the staged ledger diff creation.
+///
+/// Default policy doesn't reject anything.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct TransactionSelectionPolicy {
+    /// Maximum number of user commands included in a block.
+    pub max_transactions: Option<usize>,
+    /// Minimum fee (in nanomina) of the included user commands.
+    pub min_fee: Option<u64>,
+    /// Commands from these senders are included before any other commands.
+    pub priority_senders: Vec<NonZeroCurvePoint>,
+    /// Commands from these senders are never included.
+    pub excluded_senders: Vec<NonZeroCurvePoint>,
+    /// Maximum ratio (`0.0..=1.0`) of zkApp commands to all included commands.
+    pub max_zkapp_ratio: Option<f64>,
+    /// Maximum sum of the fees (in nanomina) paid for the snark work
+    /// included in a block.
+    pub snark_fee_budget: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionSelectionRejectReason {
+    ExcludedSender,
+    MinFee,
+    MaxTransactions,
+    MaxZkappRatio,
+    /// Command with a lower nonce from the same sender was rejected.
+    PreviousNonceRejected,
+    /// Command was dropped while creating the staged ledger diff, because
+    /// the snark work needed to include it was over the `snark_fee_budget`.
+    SnarkFeeBudget,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TransactionSelectionRejected {
+    pub hash: Option<TransactionHash>,
+    pub reason: TransactionSelectionRejectReason,
+}
+
+impl TransactionSelectionRejected {
+    pub fn new(cmd: &valid::UserCommand, reason: TransactionSelectionRejectReason) -> Self {
+        Self {
+            hash: user_command_hash(cmd),
+            reason,
+        }
+    }
+}
+
+impl TransactionSelectionPolicy {
+    /// Selects the commands to be included in the block out of the
+    /// `transactions_by_fee`, which are ordered the way the transaction pool
+    /// returns them.
+    ///
+    /// Returns the selected commands and the rejected ones, with the reason.
+    pub fn select(
+        &self,
+        transactions_by_fee: Vec<valid::UserCommand>,
+    ) -> (Vec<valid::UserCommand>, Vec<TransactionSelectionRejected>) {
+        let priority_senders = compressed_pub_keys(&self.priority_senders);
+        let is_priority =
+            |cmd: &valid::UserCommand| priority_senders.contains(&cmd.fee_payer().public_key);
+
+        // Partition is stable, so the order of commands from the same
+        // sender (by nonce) is kept.
+        let (prioritized, rest): (Vec<_>, Vec<_>) =
+            transactions_by_fee.into_iter().partition(is_priority);
+        let commands = prioritized.into_iter().chain(rest).collect::<Vec<_>>();
+
+        // The ratio is checked against the selected commands, which change
+        // when zkApp commands are rejected, so lowest priority zkApp commands
+        // over the ratio are rejected until the selection satisfies it.
+        let mut over_zkapp_ratio = BTreeSet::new();
+        let (selected, rejected) = loop {
+            let (selected, rejected) = self.select_indexes(&commands, &over_zkapp_ratio);
+            let Some(ratio) = self.max_zkapp_ratio else {
+                break (selected, rejected);
+            };
+            let max_zkapps = (ratio.clamp(0.0, 1.0) * selected.len() as f64).floor() as usize;
+            let zkapps = selected
+                .iter()
+                .copied()
+                .filter(|&i| is_zkapp(&commands[i]))
+                .collect::<Vec<_>>();
+            if zkapps.len() <= max_zkapps {
+                break (selected, rejected);
+            }
+            over_zkapp_ratio.extend(&zkapps[max_zkapps..]);
+        };
+
+        let rejected = rejected
+            .into_iter()
+            .map(|(i, reason)| TransactionSelectionRejected::new(&commands[i], reason))
+            .collect();
+        let selected = selected.into_iter().collect::<BTreeSet<_>>();
+        let selected = commands
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| selected.contains(i))
+            .map(|(_, cmd)| cmd)
+            .collect();
+        (selected, rejected)
+    }
+
+    /// Selects the commands in order, rejecting the ones at `over_zkapp_ratio`
+    /// indexes. Returns indexes of the selected and rejected commands.
+    fn select_indexes(
+        &self,
+        commands: &[valid::UserCommand],
+        over_zkapp_ratio: &BTreeSet<usize>,
+    ) -> (Vec<usize>, Vec<(usize, TransactionSelectionRejectReason)>) {
+        let excluded_senders = compressed_pub_keys(&self.excluded_senders);
+        let max_transactions = self.max_transactions.unwrap_or(usize::MAX);
+
+        let mut selected = Vec::new();
+        let mut rejected = Vec::new();
+        let mut rejected_senders = BTreeSet::<AccountId>::new();
+
+        for (i, cmd) in commands.iter().enumerate() {
+            let sender = cmd.fee_payer();
+
+            let reason = if rejected_senders.contains(&sender) {
+                Some(TransactionSelectionRejectReason::PreviousNonceRejected)
+            } else if excluded_senders.contains(&sender.public_key) {
+                Some(TransactionSelectionRejectReason::ExcludedSender)
+            } else if self.min_fee.map_or(false, |min| cmd.fee().as_u64() < min) {
+                Some(TransactionSelectionRejectReason::MinFee)
+            } else if over_zkapp_ratio.contains(&i) {
+                Some(TransactionSelectionRejectReason::MaxZkappRatio)
+            } else if selected.len() >= max_transactions {
+                Some(TransactionSelectionRejectReason::MaxTransactions)
+            } else {
+                None
+            };
+
+            match reason {
+                None => selected.push(i),
+                Some(reason) => {
+                    rejected.push((i, reason));
+                    rejected_senders.insert(sender);
+                }
+            }
+        }
+
+        (selected, rejected)
+    }
+}
+
+fn is_zkapp(cmd: &valid::UserCommand) -> bool {
+    matches!(cmd, valid::UserCommand::ZkAppCommand(_))
+}
+
+pub(crate) fn user_command_hash(cmd: &valid::UserCommand) -> Option<TransactionHash> {
+    MinaBaseUserCommandStableV2::from(&cmd.forget_check())
+        .hash()
+        .ok()
+}
+
+fn compressed_pub_keys(keys: &[NonZeroCurvePoint]) -> Vec<CompressedPubKey> {
+    keys.iter().filter_map(|key| key.try_into().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::{
+        scan_state::{
+            currency::{Amount, Fee, Nonce},
+            transaction_logic::{
+                signed_command::{self, SignedCommand, SignedCommandPayload},
+                zkapp_command::{self, CallForest, FeePayer, FeePayerBody, ZkAppCommand},
+                Memo,
+            },
+        },
+        Account,
+    };
+    use mina_signer::Signature;
+
+    use super::*;
+    use TransactionSelectionRejectReason as Reason;
+
+    fn sender() -> CompressedPubKey {
+        Account::rand().public_key
+    }
+
+    /// Commands are identified by their fee in the tests.
+    fn payment(sender: &CompressedPubKey, nonce: u32, fee: u64) -> valid::UserCommand {
+        let payload = SignedCommandPayload::create(
+            Fee::from_u64(fee),
+            sender.clone(),
+            Nonce::from_u32(nonce),
+            None,
+            Memo::empty(),
+            signed_command::Body::Payment(signed_command::PaymentPayload {
+                receiver_pk: sender.clone(),
+                amount: Amount::from_u64(1),
+            }),
+        );
+        valid::UserCommand::SignedCommand(Box::new(SignedCommand {
+            payload,
+            signer: sender.clone(),
+            signature: Signature::dummy(),
+        }))
+    }
+
+    fn zkapp(sender: &CompressedPubKey, nonce: u32, fee: u64) -> valid::UserCommand {
+        let zkapp_command = ZkAppCommand {
+            fee_payer: FeePayer {
+                body: FeePayerBody {
+                    public_key: sender.clone(),
+                    fee: Fee::from_u64(fee),
+                    valid_until: None,
+                    nonce: Nonce::from_u32(nonce),
+                },
+                authorization: Signature::dummy(),
+            },
+            account_updates: CallForest::new(),
+            memo: Memo::empty(),
+        };
+        valid::UserCommand::ZkAppCommand(Box::new(zkapp_command::valid::ZkAppCommand {
+            zkapp_command,
+        }))
+    }
+
+    fn fees(commands: &[valid::UserCommand]) -> Vec<u64> {
+        commands.iter().map(|cmd| cmd.fee().as_u64()).collect()
+    }
+
+    fn reasons(rejected: &[TransactionSelectionRejected]) -> Vec<Reason> {
+        rejected.iter().map(|rejected| rejected.reason).collect()
+    }
+
+    #[test]
+    fn test_default_policy_selects_everything() {
+        let (a, b) = (sender(), sender());
+        let commands = vec![payment(&a, 0, 3), zkapp(&b, 0, 2), payment(&a, 1, 1)];
+
+        let (selected, rejected) = TransactionSelectionPolicy::default().select(commands);
+        assert_eq!(fees(&selected), [3, 2, 1]);
+        assert!(rejected.is_empty());
+    }
+
+    #[test]
+    fn test_max_transactions() {
+        let (a, b) = (sender(), sender());
+        let commands = vec![payment(&a, 0, 3), payment(&b, 0, 2), payment(&a, 1, 1)];
+        let policy = TransactionSelectionPolicy {
+            max_transactions: Some(2),
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands.clone());
+        assert_eq!(fees(&selected), [3, 2]);
+        assert_eq!(reasons(&rejected), [Reason::MaxTransactions]);
+        assert_eq!(rejected[0].hash, user_command_hash(&commands[2]));
+    }
+
+    #[test]
+    fn test_min_fee() {
+        let (a, b) = (sender(), sender());
+        let commands = vec![payment(&a, 0, 5), payment(&b, 0, 2), payment(&b, 1, 4)];
+        let policy = TransactionSelectionPolicy {
+            min_fee: Some(3),
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands);
+        assert_eq!(fees(&selected), [5]);
+        // the later command of the same sender can't be applied without
+        // the rejected one.
+        assert_eq!(
+            reasons(&rejected),
+            [Reason::MinFee, Reason::PreviousNonceRejected]
+        );
+    }
+
+    #[test]
+    fn test_priority_senders() {
+        let (a, b) = (sender(), sender());
+        let commands = vec![
+            payment(&a, 0, 6),
+            payment(&b, 0, 5),
+            payment(&a, 1, 4),
+            payment(&b, 1, 3),
+        ];
+        let policy = TransactionSelectionPolicy {
+            max_transactions: Some(3),
+            priority_senders: vec![NonZeroCurvePoint::from(&b)],
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands);
+        assert_eq!(fees(&selected), [5, 3, 6]);
+        assert_eq!(reasons(&rejected), [Reason::MaxTransactions]);
+    }
+
+    #[test]
+    fn test_excluded_senders() {
+        let (a, b) = (sender(), sender());
+        let commands = vec![payment(&a, 0, 4), zkapp(&b, 0, 3), payment(&b, 1, 2)];
+        let policy = TransactionSelectionPolicy {
+            excluded_senders: vec![NonZeroCurvePoint::from(&b)],
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands);
+        assert_eq!(fees(&selected), [4]);
+        assert_eq!(
+            reasons(&rejected),
+            [Reason::ExcludedSender, Reason::PreviousNonceRejected]
+        );
+    }
+
+    #[test]
+    fn test_max_zkapp_ratio_of_selected_commands() {
+        let commands = vec![
+            zkapp(&sender(), 0, 10),
+            zkapp(&sender(), 0, 9),
+            zkapp(&sender(), 0, 8),
+            payment(&sender(), 0, 7),
+        ];
+        let policy = TransactionSelectionPolicy {
+            max_zkapp_ratio: Some(0.5),
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands);
+        assert_eq!(fees(&selected), [10, 7]);
+        assert_eq!(
+            reasons(&rejected),
+            [Reason::MaxZkappRatio, Reason::MaxZkappRatio]
+        );
+    }
+
+    #[test]
+    fn test_max_zkapp_ratio_frees_space_for_other_commands() {
+        let commands = vec![
+            zkapp(&sender(), 0, 10),
+            zkapp(&sender(), 0, 9),
+            payment(&sender(), 0, 8),
+            payment(&sender(), 0, 7),
+        ];
+        let policy = TransactionSelectionPolicy {
+            max_transactions: Some(3),
+            max_zkapp_ratio: Some(0.4),
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands);
+        assert_eq!(fees(&selected), [10, 8, 7]);
+        assert_eq!(reasons(&rejected), [Reason::MaxZkappRatio]);
+    }
+
+    #[test]
+    fn test_zero_zkapp_ratio() {
+        let a = sender();
+        let commands = vec![zkapp(&a, 0, 3), payment(&a, 1, 2), payment(&sender(), 0, 1)];
+        let policy = TransactionSelectionPolicy {
+            max_zkapp_ratio: Some(0.0),
+            ..Default::default()
+        };
+
+        let (selected, rejected) = policy.select(commands);
+        assert_eq!(fees(&selected), [1]);
+        assert_eq!(
+            reasons(&rejected),
+            [Reason::MaxZkappRatio, Reason::PreviousNonceRejected]
+        );
+    }
+}
@@ -15,7 +15,7 @@ use serde::{Deserialize, Serialize};
 use vrf::VrfEvaluationOutput;
 use vrf::VrfWonSlot;
 
-use super::DelegatorTable;
+use super::DelegatorTables;
 use super::InterruptReason;
 use super::{EpochData, VrfEvaluatorInput};
 
@@ -64,7 +64,7 @@ pub enum BlockProducerVrfEvaluatorAction {
         best_tip_global_slot: u32,
         next_epoch_first_slot: u32,
         staking_epoch_data: EpochData,
-        producer: AccountPublicKey,
+        producers: Vec<AccountPublicKey>,
     },
     /// Constructing delegator table.
     #[action_event(level = info)]
@@ -72,7 +72,7 @@ pub enum BlockProducerVrfEvaluatorAction {
     /// Delegator table constructed.
     #[action_event(level = info)]
     FinalizeDelegatorTableConstruction {
-        delegator_table: Arc<DelegatorTable>,
+        delegator_tables: Arc<DelegatorTables>,
     },
     /// Selecting starting slot.
     #[action_event(level = info, fields(current_global_slot, best_tip_height))]
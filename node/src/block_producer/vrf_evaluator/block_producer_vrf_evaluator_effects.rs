@@ -121,7 +121,7 @@ impl BlockProducerVrfEvaluatorAction {
                         store.dispatch(
                             BlockProducerVrfEvaluatorAction::InitializeEpochEvaluation {
                                 staking_epoch_data: epoch_data,
-                                producer: config.pub_key.clone().into(),
+                                producers: config.pub_keys().cloned().map(Into::into).collect(),
                                 best_tip_global_slot,
                                 best_tip_epoch,
                                 best_tip_slot,
@@ -142,13 +142,13 @@ impl BlockProducerVrfEvaluatorAction {
                 store.dispatch(BlockProducerVrfEvaluatorAction::BeginDelegatorTableConstruction);
             }
             BlockProducerVrfEvaluatorAction::BeginDelegatorTableConstruction => {
-                let (staking_ledger_hash, producer) =
+                let (staking_ledger_hash, producers) =
                     match store.state().block_producer.vrf_delegator_table_inputs() {
-                        Some((v1, v2)) => (v1.clone(), v2.clone()),
+                        Some((v1, v2)) => (v1.clone(), v2.to_vec()),
                         None => return,
                     };
                 if store.dispatch(LedgerReadAction::Init {
-                    request: LedgerReadRequest::DelegatorTable(staking_ledger_hash, producer),
+                    request: LedgerReadRequest::DelegatorTable(staking_ledger_hash, producers),
                 }) {
                     // TODO(binier): have pending action.
                 } else {
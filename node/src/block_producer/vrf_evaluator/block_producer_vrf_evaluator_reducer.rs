@@ -95,7 +95,7 @@ impl BlockProducerVrfEvaluatorState {
                 best_tip_global_slot,
                 next_epoch_first_slot,
                 staking_epoch_data,
-                producer,
+                producers,
             } => {
                 self.status = BlockProducerVrfEvaluatorStatus::ReadyToEvaluate {
                     time: meta.time(),
@@ -106,7 +106,7 @@ impl BlockProducerVrfEvaluatorState {
                     best_tip_global_slot: *best_tip_global_slot,
                     next_epoch_first_slot: *next_epoch_first_slot,
                     staking_epoch_data: staking_epoch_data.clone(),
-                    producer: producer.clone(),
+                    producers: producers.clone(),
                 }
             }
             BlockProducerVrfEvaluatorAction::BeginDelegatorTableConstruction => {
@@ -116,7 +116,7 @@ impl BlockProducerVrfEvaluatorState {
                     best_tip_global_slot,
                     next_epoch_first_slot,
                     staking_epoch_data,
-                    producer,
+                    producers,
                     time: _,
                     is_current_epoch_evaluated: _,
                     is_next_epoch_evaluated: _,
@@ -132,11 +132,11 @@ impl BlockProducerVrfEvaluatorState {
                     best_tip_global_slot: *best_tip_global_slot,
                     next_epoch_first_slot: *next_epoch_first_slot,
                     staking_epoch_data: staking_epoch_data.clone(),
-                    producer: producer.clone(),
+                    producers: producers.clone(),
                 }
             }
             BlockProducerVrfEvaluatorAction::FinalizeDelegatorTableConstruction {
-                delegator_table,
+                delegator_tables,
             } => {
                 let BlockProducerVrfEvaluatorStatus::EpochDelegatorTablePending {
                     best_tip_epoch,
@@ -144,7 +144,7 @@ impl BlockProducerVrfEvaluatorState {
                     best_tip_global_slot,
                     next_epoch_first_slot,
                     staking_epoch_data,
-                    producer,
+                    producers,
                     time: _,
                     staking_epoch_ledger_hash: _,
                 } = &self.status
@@ -153,7 +153,7 @@ impl BlockProducerVrfEvaluatorState {
                 };
 
                 let mut staking_epoch_data = staking_epoch_data.clone();
-                staking_epoch_data.delegator_table = delegator_table.clone();
+                staking_epoch_data.delegator_tables = delegator_tables.clone();
 
                 self.status = BlockProducerVrfEvaluatorStatus::EpochDelegatorTableSuccess {
                     time: meta.time(),
@@ -163,7 +163,7 @@ impl BlockProducerVrfEvaluatorState {
                     best_tip_global_slot: *best_tip_global_slot,
                     next_epoch_first_slot: *next_epoch_first_slot,
                     staking_epoch_data: staking_epoch_data.clone(),
-                    producer: producer.clone(),
+                    producers: producers.clone(),
                 }
             }
             BlockProducerVrfEvaluatorAction::BeginEpochEvaluation {
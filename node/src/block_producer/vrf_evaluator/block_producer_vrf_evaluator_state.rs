@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::{account::AccountPublicKey, block_producer::BlockProducerWonSlot};
 
-use super::{DelegatorTable, VrfEvaluatorInput, VrfWonSlotWithHash};
+use super::{DelegatorTables, VrfEvaluatorInput, VrfWonSlotWithHash};
 
 pub const SLOTS_PER_EPOCH: u32 = 7140;
 /// Vrf evaluator sub-state
@@ -332,7 +332,7 @@ impl BlockProducerVrfEvaluatorState {
         if let Some(pending_evaluation) = self.current_evaluation() {
             Some(VrfEvaluatorInput::new(
                 pending_evaluation.epoch_data.seed,
-                pending_evaluation.epoch_data.delegator_table,
+                pending_evaluation.epoch_data.delegator_tables,
                 pending_evaluation.latest_evaluated_slot + 1,
                 pending_evaluation.epoch_data.total_currency,
                 pending_evaluation.epoch_data.ledger,
@@ -355,13 +355,13 @@ impl BlockProducerVrfEvaluatorState {
     }
 
     /// If we need to construct delegator table, get it's inputs.
-    pub fn vrf_delegator_table_inputs(&self) -> Option<(&v2::LedgerHash, &AccountPublicKey)> {
+    pub fn vrf_delegator_table_inputs(&self) -> Option<(&v2::LedgerHash, &[AccountPublicKey])> {
         match &self.status {
             BlockProducerVrfEvaluatorStatus::EpochDelegatorTablePending {
                 staking_epoch_ledger_hash,
-                producer,
+                producers,
                 ..
-            } => Some((staking_epoch_ledger_hash, producer)),
+            } => Some((staking_epoch_ledger_hash, producers.as_slice())),
             _ => None,
         }
     }
@@ -371,7 +371,7 @@ impl BlockProducerVrfEvaluatorState {
 pub struct EpochData {
     pub seed: v2::EpochSeed,
     pub ledger: v2::LedgerHash,
-    pub delegator_table: Arc<DelegatorTable>,
+    pub delegator_tables: Arc<DelegatorTables>,
     pub total_currency: u64,
 }
 
@@ -381,7 +381,7 @@ impl EpochData {
             seed,
             ledger,
             total_currency,
-            delegator_table: Default::default(),
+            delegator_tables: Default::default(),
         }
     }
 }
@@ -393,7 +393,7 @@ impl From<v2::ConsensusProofOfStakeDataEpochDataStakingValueVersionedValueStable
         Self {
             seed: value.seed,
             ledger: value.ledger.hash,
-            delegator_table: Default::default(),
+            delegator_tables: Default::default(),
             total_currency: value.ledger.total_currency.as_u64(),
         }
     }
@@ -404,7 +404,7 @@ impl From<v2::ConsensusProofOfStakeDataEpochDataNextValueVersionedValueStableV1>
         Self {
             seed: value.seed,
             ledger: value.ledger.hash,
-            delegator_table: Default::default(),
+            delegator_tables: Default::default(),
             total_currency: value.ledger.total_currency.as_u64(),
         }
     }
@@ -436,7 +436,7 @@ pub enum BlockProducerVrfEvaluatorStatus {
         best_tip_global_slot: u32,
         next_epoch_first_slot: u32,
         staking_epoch_data: EpochData,
-        producer: AccountPublicKey,
+        producers: Vec<AccountPublicKey>,
     },
     /// Waiting for delegator table building
     EpochDelegatorTablePending {
@@ -448,7 +448,7 @@ pub enum BlockProducerVrfEvaluatorStatus {
         best_tip_global_slot: u32,
         next_epoch_first_slot: u32,
         staking_epoch_data: EpochData,
-        producer: AccountPublicKey,
+        producers: Vec<AccountPublicKey>,
     },
     /// Delegator table built successfully
     EpochDelegatorTableSuccess {
@@ -460,7 +460,7 @@ pub enum BlockProducerVrfEvaluatorStatus {
         best_tip_global_slot: u32,
         next_epoch_first_slot: u32,
         staking_epoch_data: EpochData,
-        producer: AccountPublicKey,
+        producers: Vec<AccountPublicKey>,
     },
     InitialSlotSelection {
         time: redux::Timestamp,
@@ -25,11 +25,13 @@ use vrf::{VrfEvaluationOutput, VrfWonSlot};
 use crate::account::AccountPublicKey;
 
 pub type DelegatorTable = BTreeMap<AccountIndex, (AccountPublicKey, u64)>;
+/// Delegator tables of every producer key the node runs.
+pub type DelegatorTables = BTreeMap<AccountPublicKey, DelegatorTable>;
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct VrfEvaluatorInput {
     pub epoch_seed: EpochSeed,
-    pub delegator_table: Arc<DelegatorTable>,
+    pub delegator_tables: Arc<DelegatorTables>,
     pub global_slot: u32,
     pub total_currency: u64,
     pub staking_ledger_hash: LedgerHash,
@@ -82,14 +84,14 @@ impl VrfEvaluationOutputWithHash {
 impl VrfEvaluatorInput {
     pub fn new(
         epoch_seed: EpochSeed,
-        delegator_table: Arc<DelegatorTable>,
+        delegator_tables: Arc<DelegatorTables>,
         global_slot: u32,
         total_currency: u64,
         staking_ledger_hash: LedgerHash,
     ) -> Self {
         Self {
             epoch_seed,
-            delegator_table,
+            delegator_tables,
             global_slot,
             total_currency,
             staking_ledger_hash,
@@ -42,6 +42,8 @@ pub struct SnarkerConfig {
     pub fee: CurrencyFeeStableV1,
     pub strategy: SnarkerStrategy,
     pub auto_commit: bool,
+    /// Number of local snark worker processes to run.
+    pub local_workers: u16,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
@@ -160,4 +162,28 @@ mod tests {
                 .into()
         );
     }
+
+    #[test]
+    fn devnet_genesis_block() {
+        let (_mask, config) = DEVNET_CONFIG.load().expect("should be loadable");
+        let (_, genesis, genesis_hash) = config.protocol_states().unwrap();
+
+        // https://devnet.minaexplorer.com/block/3NL93SipJfAMNDBRfQ8Uo8LPovC74mnJZfZYB5SK7mTtkL72dsPx
+        assert_eq!(
+            genesis_hash,
+            "3NL93SipJfAMNDBRfQ8Uo8LPovC74mnJZfZYB5SK7mTtkL72dsPx"
+                .parse()
+                .unwrap()
+        );
+        // devnet continues the chain it was hard forked from.
+        let fork = config.fork.expect("devnet is a hard fork");
+        assert_eq!(
+            genesis.previous_state_hash.to_field().unwrap(),
+            fork.state_hash
+        );
+        assert_eq!(
+            genesis.body.consensus_state.blockchain_length.as_u32(),
+            fork.blockchain_length + 1
+        );
+    }
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use mina_p2p_messages::v2::{MinaBlockBlockStableV2, StateHash};
 use openmina_core::block::{ArcBlockWithHash, BlockWithHash};
 use openmina_core::{action_event, ActionEvent};
+use p2p::PeerId;
 use serde::{Deserialize, Serialize};
 use snark::block_verify::SnarkBlockVerifyError;
 
@@ -22,6 +23,7 @@ pub enum ConsensusAction {
         hash: StateHash,
         block: Arc<MinaBlockBlockStableV2>,
         chain_proof: Option<(Vec<StateHash>, ArcBlockWithHash)>,
+        sender: PeerId,
     },
     BlockChainProofUpdate {
         hash: StateHash,
@@ -54,6 +56,7 @@ pub enum ConsensusAction {
         hash: StateHash,
     },
     P2pBestTipUpdate {
+        peer_id: PeerId,
         best_tip: BlockWithHash<Arc<MinaBlockBlockStableV2>>,
     },
     Prune,
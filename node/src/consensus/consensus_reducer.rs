@@ -2,6 +2,7 @@ use openmina_core::{
     block::BlockHash,
     consensus::{is_short_range_fork, long_range_fork_take, short_range_fork_take},
 };
+use p2p::disconnection::{P2pDisconnectionAction, P2pDisconnectionReason};
 use snark::block_verify::{SnarkBlockVerifyAction, SnarkBlockVerifyError};
 
 use crate::{
@@ -12,7 +13,7 @@ use crate::{
         },
         TransitionFrontierSyncAction,
     },
-    Action, State, WatchedAccountsAction,
+    Action, State,
 };
 
 use super::{
@@ -36,6 +37,7 @@ impl ConsensusState {
                 hash,
                 block,
                 chain_proof,
+                sender,
             } => {
                 state.blocks.insert(
                     hash.clone(),
@@ -43,6 +45,7 @@ impl ConsensusState {
                         block: block.clone(),
                         status: ConsensusBlockStatus::Received { time: meta.time() },
                         chain_proof: chain_proof.clone(),
+                        sender: *sender,
                     },
                 );
 
@@ -98,8 +101,20 @@ impl ConsensusState {
                 let dispatcher = state_context.into_dispatcher();
                 dispatcher.push(ConsensusAction::DetectForkRange { hash });
             }
-            ConsensusAction::BlockSnarkVerifyError { .. } => {
-                // TODO: handle block verification error.
+            ConsensusAction::BlockSnarkVerifyError { hash, .. } => {
+                // block is kept, so that it isn't verified again if received again.
+                let Some(block) = state.blocks.get_mut(hash) else {
+                    return;
+                };
+                block.status = ConsensusBlockStatus::SnarkVerifyError { time: meta.time() };
+                let peer_id = block.sender;
+
+                // Dispatch
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(P2pDisconnectionAction::Init {
+                    peer_id,
+                    reason: P2pDisconnectionReason::InvalidBlockProof,
+                });
             }
             ConsensusAction::DetectForkRange { hash } => {
                 let candidate_hash = hash;
@@ -227,27 +242,15 @@ impl ConsensusState {
 
                 // Dispatch
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
-                let Some(block) = global_state.consensus.best_tip_block_with_hash() else {
-                    return;
-                };
-                for pub_key in global_state.watched_accounts.accounts() {
-                    dispatcher.push(WatchedAccountsAction::LedgerInitialStateGetInit {
-                        pub_key: pub_key.clone(),
-                    });
-                    dispatcher.push(WatchedAccountsAction::TransactionsIncludedInBlock {
-                        pub_key,
-                        block: block.clone(),
-                    });
-                }
-
                 transition_frontier_new_best_tip_handler(global_state, dispatcher);
             }
-            ConsensusAction::P2pBestTipUpdate { best_tip } => {
+            ConsensusAction::P2pBestTipUpdate { peer_id, best_tip } => {
                 let dispatcher = state_context.into_dispatcher();
                 dispatcher.push(ConsensusAction::BlockReceived {
                     hash: best_tip.hash.clone(),
                     block: best_tip.block.clone(),
                     chain_proof: None,
+                    sender: *peer_id,
                 });
 
                 dispatcher.push(TransitionFrontierSyncLedgerSnarkedAction::PeersQuery);
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use mina_p2p_messages::v2::{
     MinaBlockBlockStableV2, MinaBlockHeaderStableV2, StagedLedgerDiffDiffStableV2, StateHash,
 };
+use p2p::PeerId;
 use serde::{Deserialize, Serialize};
 
 use openmina_core::block::{ArcBlockWithHash, BlockWithHash};
@@ -50,6 +51,9 @@ pub enum ConsensusBlockStatus {
     SnarkVerifySuccess {
         time: redux::Timestamp,
     },
+    SnarkVerifyError {
+        time: redux::Timestamp,
+    },
     ForkRangeDetected {
         time: redux::Timestamp,
         compared_with: Option<StateHash>,
@@ -97,6 +101,8 @@ pub struct ConsensusBlockState {
     pub block: Arc<MinaBlockBlockStableV2>,
     pub status: ConsensusBlockStatus,
     pub chain_proof: Option<(Vec<StateHash>, ArcBlockWithHash)>,
+    /// Peer from which the block was received.
+    pub sender: PeerId,
 }
 
 impl ConsensusBlockState {
@@ -168,6 +174,7 @@ impl ConsensusState {
             ConsensusBlockStatus::Received { .. } => false,
             ConsensusBlockStatus::SnarkVerifyPending { .. } => false,
             ConsensusBlockStatus::SnarkVerifySuccess { .. } => false,
+            ConsensusBlockStatus::SnarkVerifyError { .. } => false,
             ConsensusBlockStatus::ForkRangeDetected { .. } => false,
             ConsensusBlockStatus::ShortRangeForkResolve {
                 compared_with,
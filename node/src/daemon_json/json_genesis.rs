@@ -36,6 +36,10 @@ impl Genesis {
     value_or_protocol_default!(delta, UnsignedExtendedUInt32StableV1);
     value_or_protocol_default!(genesis_state_timestamp, BlockTimeTimeStableV1);
 
+    pub fn set_genesis_state_timestamp(&mut self, timestamp: OffsetDateTime) {
+        self.genesis_state_timestamp = Some(timestamp);
+    }
+
     pub fn protocol_constants(&self) -> MinaBaseProtocolConstantsCheckedValueStableV1 {
         MinaBaseProtocolConstantsCheckedValueStableV1 {
             k: self.k(),
@@ -47,3 +51,20 @@ impl Genesis {
         }
     }
 }
+
+impl From<&MinaBaseProtocolConstantsCheckedValueStableV1> for Genesis {
+    fn from(constants: &MinaBaseProtocolConstantsCheckedValueStableV1) -> Self {
+        let timestamp_ms = constants.genesis_state_timestamp.as_u64();
+        Genesis {
+            k: Some(constants.k.as_u32()),
+            slots_per_epoch: Some(constants.slots_per_epoch.as_u32()),
+            slots_per_sub_window: Some(constants.slots_per_sub_window.as_u32()),
+            grace_period_slots: Some(constants.grace_period_slots.as_u32()),
+            delta: Some(constants.delta.as_u32()),
+            genesis_state_timestamp: OffsetDateTime::from_unix_timestamp(
+                (timestamp_ms / 1000) as i64,
+            )
+            .ok(),
+        }
+    }
+}
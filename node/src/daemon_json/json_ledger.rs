@@ -8,9 +8,10 @@ use std::fmt::{self, Display, Formatter};
 
 use ledger::{
     scan_state::currency::{Amount, Balance, Magnitude, Nonce, Slot, SlotSpan, TxnVersion},
-    AuthRequired, Permissions, ReceiptChainHash, SetVerificationKey, Timing, TokenId, TokenSymbol,
-    VotingFor, ZkAppAccount, ZkAppUri,
+    AuthRequired, FpExt, Permissions, ReceiptChainHash, SetVerificationKey, Timing, TokenId,
+    TokenSymbol, VerificationKey, VerificationKeyWire, VotingFor, ZkAppAccount, ZkAppUri,
 };
+use mina_p2p_messages::v2::{self, MinaBaseVerificationKeyWireStableV1};
 use openmina_node_account::{AccountPublicKey, AccountSecretKey};
 
 use crate::ledger::LEDGER_DEPTH;
@@ -267,6 +268,32 @@ impl Account {
     }
 }
 
+impl From<&ledger::Account> for Account {
+    fn from(account: &ledger::Account) -> Self {
+        let token_id = (!account.token_id.is_default()).then(|| account.token_id.0.to_decimal());
+        Account {
+            pk: AccountPublicKey::from(account.public_key.clone()).to_string(),
+            sk: None,
+            balance: account.balance.to_mina_string(),
+            delegate: account
+                .delegate
+                .clone()
+                .map(|delegate| AccountPublicKey::from(delegate).to_string()),
+            token_id,
+            token_symbol: (!account.token_symbol.is_empty())
+                .then(|| account.token_symbol.as_bytes().to_vec()),
+            nonce: Some(account.nonce.as_u32()),
+            receipt_chain_hash: Some(
+                v2::ReceiptChainHash::from(account.receipt_chain_hash.clone()).to_string(),
+            ),
+            voting_for: Some(account.voting_for.to_base58check()),
+            timing: AccountTiming::from_timing(&account.timing),
+            permissions: Some((&account.permissions).into()),
+            zkapp: account.zkapp.as_deref().map(Zkapp::from),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccountTiming {
     initial_minimum_balance: RawCurrency,
@@ -277,6 +304,25 @@ pub struct AccountTiming {
 }
 
 impl AccountTiming {
+    fn from_timing(timing: &Timing) -> Option<Self> {
+        match timing {
+            Timing::Untimed => None,
+            Timing::Timed {
+                initial_minimum_balance,
+                cliff_time,
+                cliff_amount,
+                vesting_period,
+                vesting_increment,
+            } => Some(AccountTiming {
+                initial_minimum_balance: initial_minimum_balance.to_mina_string(),
+                cliff_time: GlobalSlotSinceGenesis(cliff_time.as_u32()),
+                cliff_amount: cliff_amount.to_mina_string(),
+                vesting_period: GlobalSlotSpan(vesting_period.as_u32()),
+                vesting_increment: vesting_increment.to_mina_string(),
+            }),
+        }
+    }
+
     fn to_timing(&self) -> Result<Timing, AccountConfigError> {
         let initial_minimum_balance = Balance::of_mina_string_exn(&self.initial_minimum_balance);
         let GlobalSlotSinceGenesis(cliff_time) = self.cliff_time;
@@ -349,6 +395,29 @@ pub struct AccountPermissions {
     set_timing: Option<AuthRequired>,
 }
 
+impl From<&Permissions<AuthRequired>> for AccountPermissions {
+    fn from(permissions: &Permissions<AuthRequired>) -> Self {
+        AccountPermissions {
+            access: Some(permissions.access),
+            edit_state: Some(permissions.edit_state),
+            send: Some(permissions.send),
+            receive: Some(permissions.receive),
+            set_delegate: Some(permissions.set_delegate),
+            set_permissions: Some(permissions.set_permissions),
+            set_verification_key: SetVrfKeyPerm {
+                auth: permissions.set_verification_key.auth,
+                txn_version: permissions.set_verification_key.txn_version.as_u32(),
+            },
+            set_zkapp_uri: Some(permissions.set_zkapp_uri),
+            edit_action_state: Some(permissions.edit_action_state),
+            set_token_symbol: Some(permissions.set_token_symbol),
+            increment_nonce: Some(permissions.increment_nonce),
+            set_voting_for: Some(permissions.set_voting_for),
+            set_timing: Some(permissions.set_timing),
+        }
+    }
+}
+
 impl AccountPermissions {
     fn to_permissions(&self) -> Permissions<AuthRequired> {
         // Defaults from https://github.com/MinaProtocol/mina/blob/3.0.0devnet/src/lib/mina_base/permissions.ml#L580-L594
@@ -376,11 +445,8 @@ impl AccountPermissions {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Zkapp {
     app_state: Vec<String>,
-    // These verification keys currently don't appear in configs, but
-    // they could in the future. We ignore them for now, but should they
-    // appear, serialization is likely to crash and we'll have to update
-    // this type and relevant conversion functions.
-    verification_key: Option<()>,
+    /// Base64 of the bin_prot encoded verification key.
+    verification_key: Option<String>,
     zkapp_version: u32,
     action_state: Vec<String>,
     last_action_slot: RawSlot,
@@ -388,6 +454,25 @@ pub struct Zkapp {
     zkapp_uri: Vec<u8>,
 }
 
+impl From<&ZkAppAccount> for Zkapp {
+    fn from(zkapp: &ZkAppAccount) -> Self {
+        Zkapp {
+            app_state: zkapp.app_state.iter().map(FpExt::to_decimal).collect(),
+            verification_key: zkapp.verification_key.as_ref().map(|vk| {
+                MinaBaseVerificationKeyWireStableV1::from(vk.vk())
+                    .to_base64()
+                    // Writing to `Vec` can't fail.
+                    .expect("verification key encoding")
+            }),
+            zkapp_version: zkapp.zkapp_version,
+            action_state: zkapp.action_state.iter().map(FpExt::to_decimal).collect(),
+            last_action_slot: zkapp.last_action_slot.as_u32().to_string(),
+            proved_state: zkapp.proved_state,
+            zkapp_uri: zkapp.zkapp_uri.to_vec(),
+        }
+    }
+}
+
 fn parse_fp(str: &str) -> Result<Fp, AccountConfigError> {
     Fp::from_str(str).map_err(|_| AccountConfigError::MalformedFp(str.to_owned()))
 }
@@ -427,12 +512,20 @@ impl Zkapp {
             .parse::<u32>()
             .map(Slot::from_u32)
             .map_err(|_| AccountConfigError::MalformedSlot(self.last_action_slot.clone()))?;
-        if self.verification_key.is_some() {
-            return Err(AccountConfigError::VerificationKeyParsingNotSupported);
-        };
+        let verification_key = self
+            .verification_key
+            .as_ref()
+            .map(|vk| {
+                MinaBaseVerificationKeyWireStableV1::from_base64(vk)
+                    .ok()
+                    .and_then(|vk| VerificationKey::try_from(&vk).ok())
+                    .map(VerificationKeyWire::new)
+                    .ok_or_else(|| AccountConfigError::MalformedVerificationKey(vk.clone()))
+            })
+            .transpose()?;
         Ok(ZkAppAccount {
             app_state,
-            verification_key: None,
+            verification_key,
             zkapp_version: self.zkapp_version,
             action_state,
             last_action_slot,
@@ -453,7 +546,7 @@ pub enum AccountConfigError {
     MalformedSlot(String),
     MalformedFp(String),
     ZkAppStateTooLong(Vec<String>),
-    VerificationKeyParsingNotSupported,
+    MalformedVerificationKey(String),
     DelegateSetOnNonDefaultTokenAccount,
     InvalidBigInt,
 }
@@ -563,8 +656,8 @@ impl Display for AccountConfigError {
             Self::ZkAppStateTooLong(app_state) => {
                 write!(f, "zkapp app state too long ('{:?}')", app_state)
             }
-            Self::VerificationKeyParsingNotSupported => {
-                write!(f, "verification key parsing not supported yet!")
+            Self::MalformedVerificationKey(vk) => {
+                write!(f, "malformed verification key ('{}')", vk)
             }
             Self::DelegateSetOnNonDefaultTokenAccount => {
                 write!(f, "delegate set on non-default token account")
@@ -0,0 +1,173 @@
+use ledger::scan_state::currency::Amount;
+use mina_p2p_messages::v2::StateHash;
+use openmina_core::constants::{ConstraintConstants, ForkConstants};
+use serde::{Deserialize, Serialize};
+
+type RawCurrency = String;
+
+/// Constraint constants of the network, as set in the daemon.json.
+/// Unset values are not checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proof {
+    pub level: Option<String>,
+    pub sub_windows_per_window: Option<u64>,
+    pub ledger_depth: Option<u64>,
+    pub work_delay: Option<u64>,
+    pub block_window_duration_ms: Option<u64>,
+    pub transaction_capacity: Option<TransactionCapacity>,
+    pub coinbase_amount: Option<RawCurrency>,
+    pub supercharged_coinbase_factor: Option<u64>,
+    pub account_creation_fee: Option<RawCurrency>,
+    pub fork: Option<ForkConstants>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TransactionCapacity {
+    #[serde(rename = "2_to_the")]
+    TwoToThe(u64),
+    #[serde(rename = "txns_per_second_x10")]
+    TxnsPerSecondX10(u64),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConstraintConstantsError {
+    #[error("`{field}` of the genesis config is {genesis}, but {network} in the network config")]
+    Mismatch {
+        field: &'static str,
+        genesis: String,
+        network: String,
+    },
+    #[error("`{field}` of the genesis config is not a valid amount: {value:?}")]
+    InvalidAmount { field: &'static str, value: String },
+}
+
+impl TransactionCapacity {
+    /// https://github.com/MinaProtocol/mina/blob/compatible/src/lib/runtime_config/runtime_config.ml
+    pub fn log_2(&self, block_window_duration_ms: u64) -> u64 {
+        match self {
+            Self::TwoToThe(log_2) => *log_2,
+            Self::TxnsPerSecondX10(txns_per_second_x10) => {
+                const MAX_COINBASES: u64 = 2;
+                let max_user_commands_per_block =
+                    txns_per_second_x10 * block_window_duration_ms / (1000 * 10);
+                let transactions = max_user_commands_per_block + MAX_COINBASES;
+                transactions.next_power_of_two().trailing_zeros() as u64
+            }
+        }
+    }
+}
+
+impl Proof {
+    /// Constraint constants of the network forked at `fork`.
+    pub fn hard_fork(constants: &ConstraintConstants, fork: ForkConstants) -> Self {
+        Self {
+            level: None,
+            sub_windows_per_window: Some(constants.sub_windows_per_window),
+            ledger_depth: Some(constants.ledger_depth),
+            work_delay: Some(constants.work_delay),
+            block_window_duration_ms: Some(constants.block_window_duration_ms),
+            transaction_capacity: Some(TransactionCapacity::TwoToThe(
+                constants.transaction_capacity_log_2,
+            )),
+            coinbase_amount: Some(Amount::from_u64(constants.coinbase_amount).to_mina_string()),
+            supercharged_coinbase_factor: Some(constants.supercharged_coinbase_factor),
+            account_creation_fee: Some(
+                Amount::from_u64(constants.account_creation_fee).to_mina_string(),
+            ),
+            fork: Some(fork),
+        }
+    }
+
+    pub fn check_constraint_constants(
+        &self,
+        constants: &ConstraintConstants,
+    ) -> Result<(), ConstraintConstantsError> {
+        check(
+            "sub_windows_per_window",
+            self.sub_windows_per_window,
+            constants.sub_windows_per_window,
+        )?;
+        check("ledger_depth", self.ledger_depth, constants.ledger_depth)?;
+        check("work_delay", self.work_delay, constants.work_delay)?;
+        check(
+            "block_window_duration_ms",
+            self.block_window_duration_ms,
+            constants.block_window_duration_ms,
+        )?;
+        check(
+            "transaction_capacity",
+            self.transaction_capacity
+                .as_ref()
+                .map(|capacity| capacity.log_2(constants.block_window_duration_ms)),
+            constants.transaction_capacity_log_2,
+        )?;
+        check(
+            "coinbase_amount",
+            parse_amount("coinbase_amount", self.coinbase_amount.as_deref())?,
+            constants.coinbase_amount,
+        )?;
+        check(
+            "supercharged_coinbase_factor",
+            self.supercharged_coinbase_factor,
+            constants.supercharged_coinbase_factor,
+        )?;
+        check(
+            "account_creation_fee",
+            parse_amount("account_creation_fee", self.account_creation_fee.as_deref())?,
+            constants.account_creation_fee,
+        )?;
+        if let Some(fork) = &self.fork {
+            check(
+                "fork",
+                Some(display_fork(Some(fork))),
+                display_fork(constants.fork.as_ref()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn check<T: ToString>(
+    field: &'static str,
+    genesis: Option<T>,
+    network: T,
+) -> Result<(), ConstraintConstantsError> {
+    match genesis.map(|genesis| genesis.to_string()) {
+        Some(genesis) if genesis != network.to_string() => {
+            Err(ConstraintConstantsError::Mismatch {
+                field,
+                genesis,
+                network: network.to_string(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+fn parse_amount(
+    field: &'static str,
+    amount: Option<&str>,
+) -> Result<Option<u64>, ConstraintConstantsError> {
+    amount
+        .map(|amount| {
+            Amount::of_mina_string(amount)
+                .map(|amount| amount.as_u64())
+                .ok_or_else(|| ConstraintConstantsError::InvalidAmount {
+                    field,
+                    value: amount.to_owned(),
+                })
+        })
+        .transpose()
+}
+
+fn display_fork(fork: Option<&ForkConstants>) -> String {
+    match fork {
+        None => "none".to_owned(),
+        Some(fork) => format!(
+            "{} (length: {}, slot: {})",
+            StateHash::from_fp(fork.state_hash),
+            fork.blockchain_length,
+            fork.global_slot_since_genesis
+        ),
+    }
+}
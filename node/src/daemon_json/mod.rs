@@ -1,14 +1,24 @@
+use ark_ff::fields::arithmetic::InvalidBigInt;
+use ledger::{BaseLedger, Mask};
+use openmina_core::{
+    block::ArcBlockWithHash,
+    constants::{constraint_constants, ForkConstants},
+};
 use serde::{Deserialize, Serialize};
 
+use crate::ledger::read::HardForkLedgers;
+
 mod json_daemon;
 mod json_genesis;
 mod json_ledger;
+mod json_proof;
 pub use json_daemon::Daemon;
 pub use json_genesis::Genesis;
 pub use json_ledger::{
     build_ledger_name, Account, AccountConfigError, AccountPermissions, AccountTiming, Ledger,
     Zkapp,
 };
+pub use json_proof::{ConstraintConstantsError, Proof, TransactionCapacity};
 
 /// This type represents a JSON object loaded from daemon.json
 /// file. It does not describe its full structure, as it's not
@@ -25,9 +35,147 @@ pub struct DaemonJson {
     pub daemon: Option<Daemon>,
     pub ledger: Option<Ledger>,
     pub genesis: Option<Genesis>,
+    pub proof: Option<Proof>,
     pub epoch_data: Option<Epochs>,
 }
 
+impl DaemonJson {
+    /// Checks that the constraint constants set in the daemon.json are the
+    /// ones of the network.
+    pub fn check_network_config(
+        &self,
+        network: &openmina_core::NetworkConfig,
+    ) -> Result<(), ConstraintConstantsError> {
+        match &self.proof {
+            Some(proof) => proof.check_constraint_constants(network.constraint_constants),
+            None => Ok(()),
+        }
+    }
+
+    /// Fork constants of the config, without reading its ledgers.
+    pub fn read_fork<R: std::io::Read>(reader: R) -> serde_json::Result<Option<ForkConstants>> {
+        #[derive(Deserialize)]
+        struct ForkOnly {
+            proof: Option<ProofForkOnly>,
+        }
+        #[derive(Deserialize)]
+        struct ProofForkOnly {
+            fork: Option<ForkConstants>,
+        }
+
+        let config: ForkOnly = serde_json::from_reader(reader)?;
+        Ok(config.proof.and_then(|proof| proof.fork))
+    }
+
+    /// Writes the config of the network hard forked at `block`. Its genesis
+    /// ledger is the staged ledger of the block and its epoch ledgers are
+    /// the ones of the block. Accounts are written straight from the
+    /// ledgers, in the ledger order.
+    pub fn hard_fork<W: std::io::Write>(
+        writer: W,
+        block: &ArcBlockWithHash,
+        ledgers: HardForkLedgers<&Mask>,
+    ) -> Result<(), HardForkConfigError> {
+        let fork = ForkConstants {
+            state_hash: block
+                .hash()
+                .to_field()
+                .map_err(HardForkConfigError::InvalidStateHash)?,
+            blockchain_length: block.height(),
+            global_slot_since_genesis: block.global_slot_since_genesis(),
+        };
+        let mut constants = block.constants().clone();
+        constants.genesis_state_timestamp = block
+            .header()
+            .protocol_state
+            .body
+            .blockchain_state
+            .timestamp;
+        let consensus_state = block.consensus_state();
+
+        let config = HardForkDaemonJson {
+            ledger: HardForkLedger {
+                accounts: LedgerAccounts(ledgers.staged_ledger),
+                hash: block.merkle_root_hash().to_string(),
+                add_genesis_winner: false,
+            },
+            genesis: (&constants).into(),
+            proof: Proof::hard_fork(constraint_constants(), fork),
+            epoch_data: HardForkEpochs {
+                staking: HardForkEpochData {
+                    accounts: LedgerAccounts(ledgers.staking_epoch_ledger),
+                    hash: block.staking_epoch_ledger_hash().to_string(),
+                    seed: consensus_state.staking_epoch_data.seed.to_string(),
+                },
+                next: HardForkEpochData {
+                    accounts: LedgerAccounts(ledgers.next_epoch_ledger),
+                    hash: block.next_epoch_ledger_hash().to_string(),
+                    seed: consensus_state.next_epoch_data.seed.to_string(),
+                },
+            },
+        };
+        serde_json::to_writer(writer, &config)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HardForkConfigError {
+    #[error("invalid state hash of the block: {0:?}")]
+    InvalidStateHash(InvalidBigInt),
+    #[error("failed to write the config: {0}")]
+    Write(#[from] serde_json::Error),
+}
+
+/// [`DaemonJson`] written by [`DaemonJson::hard_fork`].
+#[derive(Serialize)]
+struct HardForkDaemonJson<'a> {
+    ledger: HardForkLedger<'a>,
+    genesis: Genesis,
+    proof: Proof,
+    epoch_data: HardForkEpochs<'a>,
+}
+
+#[derive(Serialize)]
+struct HardForkLedger<'a> {
+    accounts: LedgerAccounts<'a>,
+    hash: String,
+    add_genesis_winner: bool,
+}
+
+#[derive(Serialize)]
+struct HardForkEpochs<'a> {
+    staking: HardForkEpochData<'a>,
+    next: HardForkEpochData<'a>,
+}
+
+#[derive(Serialize)]
+struct HardForkEpochData<'a> {
+    accounts: LedgerAccounts<'a>,
+    hash: String,
+    seed: String,
+}
+
+/// Serializes the accounts of the ledger as they are visited, without
+/// collecting them first.
+struct LedgerAccounts<'a>(&'a Mask);
+
+impl Serialize for LedgerAccounts<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeSeq;
+
+        let mut seq = serializer.serialize_seq(Some(self.0.num_accounts()))?;
+        let mut res = Ok(());
+        self.0.iter(|account| {
+            if res.is_ok() {
+                res = seq.serialize_element(&Account::from(account));
+            }
+        });
+        res?;
+        seq.end()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Epochs {
     pub staking: EpochData,
@@ -62,10 +210,15 @@ impl EpochData {
 mod test {
 
     use ledger::{scan_state::currency::Balance, Timing};
+    use openmina_core::constants::ForkConstants;
     use openmina_node_account::AccountPublicKey;
     use std::str::FromStr;
 
     use crate::daemon_json::DaemonJson;
+    use crate::ledger::read::HardForkLedgers;
+    use crate::transition_frontier::genesis::{
+        GenesisConfig, NonStakers, TransitionFrontierGenesisState,
+    };
 
     #[test]
     fn test_daemon_json_read() {
@@ -110,4 +263,121 @@ mod test {
         assert_eq!(daemon.slot_tx_end(), None);
         assert_eq!(daemon.slot_chain_end(), None);
     }
+
+    #[test]
+    fn test_daemon_json_check_network_config() {
+        let test_file = std::fs::File::open("testing/data/daemon.json").unwrap();
+        let mut daemon_json: DaemonJson = serde_json::from_reader(test_file).unwrap();
+        let devnet = openmina_core::NetworkConfig::global();
+        daemon_json.check_network_config(devnet).unwrap();
+
+        daemon_json.proof.as_mut().unwrap().ledger_depth = Some(20);
+        let err = daemon_json.check_network_config(devnet).unwrap_err();
+        assert!(matches!(
+            err,
+            ConstraintConstantsError::Mismatch {
+                field: "ledger_depth",
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_daemon_json_check_network_config_invalid_amount() {
+        let test_file = std::fs::File::open("testing/data/daemon.json").unwrap();
+        let mut daemon_json: DaemonJson = serde_json::from_reader(test_file).unwrap();
+        let devnet = openmina_core::NetworkConfig::global();
+
+        for amount in ["1.2.3", "abc", ""] {
+            daemon_json.proof.as_mut().unwrap().account_creation_fee = Some(amount.to_owned());
+            let err = daemon_json.check_network_config(devnet).unwrap_err();
+            assert!(matches!(
+                err,
+                ConstraintConstantsError::InvalidAmount {
+                    field: "account_creation_fee",
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_daemon_json_hard_fork_round_trip() {
+        let (masks, genesis) = GenesisConfig::Counts {
+            whales: 2,
+            fish: 2,
+            non_stakers: NonStakers::None,
+            constants: GenesisConfig::default_constants(1_700_000_000_000),
+        }
+        .load()
+        .unwrap();
+        let (negative_one, genesis_state, genesis_hash) = genesis.protocol_states().unwrap();
+        let block = TransitionFrontierGenesisState::Produced {
+            time: redux::Timestamp::ZERO,
+            negative_one,
+            genesis: genesis_state,
+            genesis_hash,
+            genesis_producer_stake_proof: genesis.genesis_producer_stake_proof.clone(),
+        }
+        .block_with_dummy_proof()
+        .unwrap();
+        // all the ledgers of the genesis block are the genesis ledger.
+        let ledgers = HardForkLedgers {
+            staged_ledger: &masks[0],
+            staking_epoch_ledger: &masks[0],
+            next_epoch_ledger: &masks[0],
+        };
+
+        let mut config = Vec::new();
+        DaemonJson::hard_fork(&mut config, &block, ledgers).unwrap();
+        let config: DaemonJson = serde_json::from_slice(&config).unwrap();
+        let (_, forked) = GenesisConfig::DaemonJson(Box::new(config)).load().unwrap();
+
+        let fork = ForkConstants {
+            state_hash: block.hash().to_field().unwrap(),
+            blockchain_length: block.height(),
+            global_slot_since_genesis: block.global_slot_since_genesis(),
+        };
+        assert_eq!(forked.fork, Some(fork));
+        assert_eq!(forked.genesis_ledger_hash, genesis.genesis_ledger_hash);
+        assert_eq!(
+            forked.genesis_total_currency,
+            genesis.genesis_total_currency
+        );
+        assert_eq!(
+            forked.staking_epoch_ledger_hash,
+            genesis.staking_epoch_ledger_hash
+        );
+        assert_eq!(
+            forked.next_epoch_ledger_hash,
+            genesis.next_epoch_ledger_hash
+        );
+        assert_eq!(forked.staking_epoch_seed, genesis.staking_epoch_seed);
+        assert_eq!(forked.next_epoch_seed, genesis.next_epoch_seed);
+        assert_eq!(
+            forked.constants.genesis_state_timestamp,
+            block
+                .header()
+                .protocol_state
+                .body
+                .blockchain_state
+                .timestamp
+        );
+
+        // genesis block of the forked network continues the chain.
+        let (_, forked_genesis, _) = forked.protocol_states().unwrap();
+        assert_eq!(&forked_genesis.previous_state_hash, block.hash());
+        let consensus_state = &forked_genesis.body.consensus_state;
+        assert_eq!(
+            consensus_state.blockchain_length.as_u32(),
+            block.height() + 1
+        );
+        assert_eq!(
+            consensus_state
+                .curr_global_slot_since_hard_fork
+                .slot_number
+                .as_u32(),
+            0
+        );
+    }
 }
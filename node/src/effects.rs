@@ -2,15 +2,16 @@ use openmina_core::log::system_time;
 
 use crate::block_producer::{block_producer_effects, BlockProducerAction};
 use crate::event_source::event_source_effects;
-use crate::external_snark_worker::external_snark_worker_effects;
+use crate::external_snark_worker::{external_snark_worker_effects, ExternalSnarkWorkerId};
 use crate::ledger::ledger_effects;
 use crate::ledger::read::LedgerReadAction;
 use crate::logger::logger_effects;
-use crate::p2p::node_p2p_effects;
+use crate::p2p::{node_p2p_effects, P2pAction};
 use crate::rpc::rpc_effects;
 use crate::snark::snark_effects;
 use crate::snark_pool::candidate::SnarkPoolCandidateAction;
 use crate::snark_pool::{snark_pool_effects, SnarkPoolAction};
+use crate::transaction_pool::TransactionPoolAction;
 use crate::transition_frontier::genesis::TransitionFrontierGenesisAction;
 use crate::transition_frontier::transition_frontier_effects;
 use crate::{p2p_ready, Action, ActionWithMeta, ExternalSnarkWorkerAction, Service, Store};
@@ -24,6 +25,11 @@ pub fn effects<S: Service>(store: &mut Store<S>, action: ActionWithMeta) {
 
     if let Some(stats) = store.service.stats() {
         stats.new_action(action.kind(), meta.clone());
+        if let (Action::P2p(P2pAction::Network(action)), Some(p2p)) =
+            (&action, store.state.get().p2p.ready())
+        {
+            stats.new_p2p_network_action(p2p, action);
+        }
     }
 
     logger_effects(store, meta.clone().with_action(&action));
@@ -33,7 +39,15 @@ pub fn effects<S: Service>(store: &mut Store<S>, action: ActionWithMeta) {
         Action::CheckTimeouts(_) => {
             // TODO(binier): create init action and dispatch these there.
             store.dispatch(TransitionFrontierGenesisAction::LedgerLoadInit);
-            store.dispatch(ExternalSnarkWorkerAction::Start);
+            let local_workers = store
+                .state()
+                .external_snark_worker
+                .ids()
+                .filter(ExternalSnarkWorkerId::is_local)
+                .collect::<Vec<_>>();
+            for worker_id in local_workers {
+                store.dispatch(ExternalSnarkWorkerAction::Start { worker_id });
+            }
 
             if store.state().p2p.ready().is_some() {
                 p2p_request_best_tip_if_needed(store);
@@ -44,11 +58,26 @@ pub fn effects<S: Service>(store: &mut Store<S>, action: ActionWithMeta) {
             store.dispatch(SnarkPoolAction::CheckTimeouts);
             store.dispatch(SnarkPoolAction::P2pSendAll);
 
+            store.dispatch(TransactionPoolAction::RebroadcastLocallyGenerated);
+
             store.dispatch(SnarkPoolCandidateAction::WorkFetchAll);
             store.dispatch(SnarkPoolCandidateAction::WorkVerifyNext);
 
-            store.dispatch(ExternalSnarkWorkerAction::StartTimeout { now: meta.time() });
-            store.dispatch(ExternalSnarkWorkerAction::WorkTimeout { now: meta.time() });
+            let workers = store
+                .state()
+                .external_snark_worker
+                .ids()
+                .collect::<Vec<_>>();
+            for worker_id in workers {
+                store.dispatch(ExternalSnarkWorkerAction::StartTimeout {
+                    worker_id,
+                    now: meta.time(),
+                });
+                store.dispatch(ExternalSnarkWorkerAction::WorkTimeout {
+                    worker_id,
+                    now: meta.time(),
+                });
+            }
 
             store.dispatch(BlockProducerAction::WonSlotProduceInit);
             store.dispatch(BlockProducerAction::BlockInject);
@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 pub use crate::block_producer::BlockProducerEvent;
-pub use crate::external_snark_worker::ExternalSnarkWorkerEvent;
+pub use crate::external_snark_worker::{ExternalSnarkWorkerEvent, ExternalSnarkWorkerId};
 pub use crate::ledger::LedgerEvent;
 pub use crate::p2p::{P2pConnectionEvent, P2pEvent};
 pub use crate::rpc::{RpcId, RpcRequest};
@@ -15,7 +15,7 @@ pub enum Event {
     Ledger(LedgerEvent),
     Snark(SnarkEvent),
     Rpc(RpcId, Box<RpcRequest>),
-    ExternalSnarkWorker(ExternalSnarkWorkerEvent),
+    ExternalSnarkWorker(ExternalSnarkWorkerId, ExternalSnarkWorkerEvent),
     BlockProducerEvent(BlockProducerEvent),
 
     GenesisLoad(Result<GenesisConfigLoaded, String>),
@@ -35,6 +35,7 @@ impl std::fmt::Display for Event {
                     RpcRequest::ActionStatsGet(query) => write!(f, "ActionStatsGet, {query:?}"),
                     RpcRequest::SyncStatsGet(query) => write!(f, "SyncStatsGet, {query:?}"),
                     RpcRequest::BlockProducerStatsGet => write!(f, "BlockProducerStatsGet"),
+                    RpcRequest::MetricsGet => write!(f, "MetricsGet"),
                     RpcRequest::PeersGet => write!(f, "PeersGet"),
                     RpcRequest::MessageProgressGet => write!(f, "MessageProgressGet"),
                     RpcRequest::P2pConnectionOutgoing(opts) => {
@@ -56,25 +57,60 @@ impl std::fmt::Display for Event {
                     }
                     RpcRequest::SnarkerJobSpec { job_id } => write!(f, "SnarkerJobSpec, {job_id}"),
                     RpcRequest::SnarkerWorkers => write!(f, "SnarkerWorkers"),
+                    RpcRequest::SnarkerWorkerGetWork(connection_id) => {
+                        write!(f, "SnarkerWorkerGetWork, {connection_id}")
+                    }
+                    RpcRequest::SnarkerWorkerSubmitWork(connection_id, _) => {
+                        write!(f, "SnarkerWorkerSubmitWork, {connection_id}")
+                    }
+                    RpcRequest::SnarkerWorkerFailed(connection_id, job_id, _) => {
+                        write!(f, "SnarkerWorkerFailed, {connection_id}, {job_id}")
+                    }
                     RpcRequest::HealthCheck => write!(f, "HealthCheck"),
                     RpcRequest::ReadinessCheck => write!(f, "ReadinessCheck"),
                     RpcRequest::DiscoveryRoutingTable => write!(f, "DiscoveryRoutingTable"),
                     RpcRequest::DiscoveryBoostrapStats => write!(f, "DiscoveryBoostrapStats"),
                     RpcRequest::TransactionPoolGet => write!(f, "TransactionPool"),
-                    RpcRequest::LedgerAccountsGet(account_query) => {
-                        write!(f, "LedgerAccountsGet, {account_query:?}")
+                    RpcRequest::LedgerAccountsGet(account_query, ledger_hash) => {
+                        write!(f, "LedgerAccountsGet, {account_query:?}, {ledger_hash:?}")
                     }
                     RpcRequest::TransactionInject(..) => write!(f, "TransactionInject"),
                     RpcRequest::TransitionFrontierUserCommandsGet => {
                         write!(f, "TransitionFrontierUserCommandsGet")
                     }
                     RpcRequest::BestChain(..) => write!(f, "BestChain"),
+                    RpcRequest::BestTipSubscribe => write!(f, "BestTipSubscribe"),
                     RpcRequest::ConsensusConstantsGet => write!(f, "ConsensusConstantsGet"),
                     RpcRequest::TransactionStatusGet(..) => write!(f, "TransactionStatusGet"),
+                    RpcRequest::BlockGet(query) => write!(f, "BlockGet, {query:?}"),
+                    RpcRequest::GenesisBlockGet => write!(f, "GenesisBlockGet"),
+                    RpcRequest::PooledUserCommands(..) => write!(f, "PooledUserCommands"),
+                    RpcRequest::PooledZkappCommands(..) => write!(f, "PooledZkappCommands"),
+                    RpcRequest::HardForkConfigGet(block_hash) => {
+                        write!(f, "HardForkConfigGet, {block_hash:?}")
+                    }
+                    RpcRequest::WatchedAccountsAdd(public_key) => {
+                        write!(f, "WatchedAccountsAdd, {public_key}")
+                    }
+                    RpcRequest::WatchedAccountsRemove(public_key) => {
+                        write!(f, "WatchedAccountsRemove, {public_key}")
+                    }
+                    RpcRequest::WatchedAccountsGet(public_key) => {
+                        write!(f, "WatchedAccountsGet, {public_key:?}")
+                    }
+                    RpcRequest::WatchedAccountSubscribe(public_key) => {
+                        write!(f, "WatchedAccountSubscribe, {public_key}")
+                    }
+                    RpcRequest::TransactionSimulate(..) => write!(f, "TransactionSimulate"),
+                    RpcRequest::FeeEstimate(query) => write!(f, "FeeEstimate, {query:?}"),
+                    RpcRequest::ZkappEventsGet(query) => {
+                        write!(f, "ZkappEventsGet, {}", query.public_key)
+                    }
+                    RpcRequest::ArchiveGet(query) => write!(f, "ArchiveGet, {query:?}"),
                 }
             }
-            Self::ExternalSnarkWorker(event) => {
-                write!(f, "ExternalSnarkWorker, ")?;
+            Self::ExternalSnarkWorker(worker_id, event) => {
+                write!(f, "ExternalSnarkWorker, {worker_id}, ")?;
 
                 match event {
                     ExternalSnarkWorkerEvent::Started => write!(f, "Started"),
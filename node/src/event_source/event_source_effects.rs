@@ -311,6 +311,9 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::BlockProducerStatsGet => {
                     store.dispatch(RpcAction::BlockProducerStatsGet { rpc_id });
                 }
+                RpcRequest::MetricsGet => {
+                    store.dispatch(RpcAction::MetricsGet { rpc_id });
+                }
                 RpcRequest::PeersGet => {
                     store.dispatch(RpcAction::PeersGet { rpc_id });
                 }
@@ -344,6 +347,27 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::SnarkerWorkers => {
                     store.dispatch(RpcAction::SnarkerWorkersGet { rpc_id });
                 }
+                RpcRequest::SnarkerWorkerGetWork(connection_id) => {
+                    store.dispatch(RpcAction::SnarkerWorkerGetWork {
+                        rpc_id,
+                        connection_id,
+                    });
+                }
+                RpcRequest::SnarkerWorkerSubmitWork(connection_id, work) => {
+                    store.dispatch(RpcAction::SnarkerWorkerSubmitWork {
+                        rpc_id,
+                        connection_id,
+                        work,
+                    });
+                }
+                RpcRequest::SnarkerWorkerFailed(connection_id, job_id, error) => {
+                    store.dispatch(RpcAction::SnarkerWorkerFailed {
+                        rpc_id,
+                        connection_id,
+                        job_id,
+                        error,
+                    });
+                }
                 RpcRequest::HealthCheck => {
                     store.dispatch(RpcAction::HealthCheck { rpc_id });
                 }
@@ -359,10 +383,11 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::TransactionPoolGet => {
                     store.dispatch(RpcAction::TransactionPool { rpc_id });
                 }
-                RpcRequest::LedgerAccountsGet(account_query) => {
+                RpcRequest::LedgerAccountsGet(account_query, ledger_hash) => {
                     store.dispatch(RpcAction::LedgerAccountsGetInit {
                         rpc_id,
                         account_query,
+                        ledger_hash,
                     });
                 }
                 RpcRequest::TransactionInject(commands) => {
@@ -374,31 +399,74 @@ pub fn event_source_effects<S: Service>(store: &mut Store<S>, action: EventSourc
                 RpcRequest::BestChain(max_length) => {
                     store.dispatch(RpcAction::BestChain { rpc_id, max_length });
                 }
+                RpcRequest::BestTipSubscribe => {
+                    store.dispatch(RpcAction::BestTipSubscribe { rpc_id });
+                }
                 RpcRequest::ConsensusConstantsGet => {
                     store.dispatch(RpcAction::ConsensusConstantsGet { rpc_id });
                 }
                 RpcRequest::TransactionStatusGet(tx) => {
                     store.dispatch(RpcAction::TransactionStatusGet { rpc_id, tx });
                 }
+                RpcRequest::BlockGet(query) => {
+                    store.dispatch(RpcAction::BlockGet { rpc_id, query });
+                }
+                RpcRequest::GenesisBlockGet => {
+                    store.dispatch(RpcAction::GenesisBlockGet { rpc_id });
+                }
+                RpcRequest::PooledUserCommands(query) => {
+                    store.dispatch(RpcAction::PooledUserCommands { rpc_id, query });
+                }
+                RpcRequest::PooledZkappCommands(query) => {
+                    store.dispatch(RpcAction::PooledZkappCommands { rpc_id, query });
+                }
+                RpcRequest::HardForkConfigGet(block_hash) => {
+                    store.dispatch(RpcAction::HardForkConfigGetInit { rpc_id, block_hash });
+                }
+                RpcRequest::WatchedAccountsAdd(public_key) => {
+                    store.dispatch(RpcAction::WatchedAccountsAdd { rpc_id, public_key });
+                }
+                RpcRequest::WatchedAccountsRemove(public_key) => {
+                    store.dispatch(RpcAction::WatchedAccountsRemove { rpc_id, public_key });
+                }
+                RpcRequest::WatchedAccountsGet(public_key) => {
+                    store.dispatch(RpcAction::WatchedAccountsGet { rpc_id, public_key });
+                }
+                RpcRequest::WatchedAccountSubscribe(public_key) => {
+                    store.dispatch(RpcAction::WatchedAccountSubscribe { rpc_id, public_key });
+                }
+                RpcRequest::TransactionSimulate(command) => {
+                    store.dispatch(RpcAction::TransactionSimulateInit { rpc_id, command });
+                }
+                RpcRequest::FeeEstimate(query) => {
+                    store.dispatch(RpcAction::FeeEstimate { rpc_id, query });
+                }
+                RpcRequest::ZkappEventsGet(query) => {
+                    store.dispatch(RpcAction::ZkappEventsGetInit { rpc_id, query });
+                }
+                RpcRequest::ArchiveGet(query) => {
+                    store.dispatch(RpcAction::ArchiveGetInit { rpc_id, query });
+                }
             },
-            Event::ExternalSnarkWorker(e) => match e {
+            Event::ExternalSnarkWorker(worker_id, e) => match e {
                 ExternalSnarkWorkerEvent::Started => {
-                    store.dispatch(ExternalSnarkWorkerAction::Started);
+                    store.dispatch(ExternalSnarkWorkerAction::Started { worker_id });
                 }
                 ExternalSnarkWorkerEvent::Killed => {
-                    store.dispatch(ExternalSnarkWorkerAction::Killed);
+                    store.dispatch(ExternalSnarkWorkerAction::Killed { worker_id });
                 }
                 ExternalSnarkWorkerEvent::WorkResult(result) => {
-                    store.dispatch(ExternalSnarkWorkerAction::WorkResult { result });
+                    store.dispatch(ExternalSnarkWorkerAction::WorkResult { worker_id, result });
                 }
                 ExternalSnarkWorkerEvent::WorkError(error) => {
-                    store.dispatch(ExternalSnarkWorkerAction::WorkError { error });
+                    store.dispatch(ExternalSnarkWorkerAction::WorkError { worker_id, error });
                 }
                 ExternalSnarkWorkerEvent::WorkCancelled => {
-                    store.dispatch(ExternalSnarkWorkerAction::WorkCancelled);
+                    store.dispatch(ExternalSnarkWorkerAction::WorkCancelled { worker_id });
                 }
                 ExternalSnarkWorkerEvent::Error(error) => {
                     store.dispatch(ExternalSnarkWorkerAction::Error {
+                        worker_id,
                         error,
                         permanent: false,
                     });
@@ -7,125 +7,159 @@ use serde::{Deserialize, Serialize};
 use crate::{snark_pool::JobSummary, State};
 
 use super::{
-    ExternalSnarkWorkerError, ExternalSnarkWorkerState, ExternalSnarkWorkerWorkError,
-    SnarkWorkResult,
+    ExternalSnarkWorkerError, ExternalSnarkWorkerId, ExternalSnarkWorkerState,
+    ExternalSnarkWorkerWorkError, SnarkWorkResult,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ActionEvent)]
-#[action_event(fields(display(job_id), display(error)))]
+#[action_event(fields(display(worker_id), display(job_id), display(error)))]
 pub enum ExternalSnarkWorkerAction {
-    Start,
-    Started,
+    Start {
+        worker_id: ExternalSnarkWorkerId,
+    },
+    Started {
+        worker_id: ExternalSnarkWorkerId,
+    },
     StartTimeout {
+        worker_id: ExternalSnarkWorkerId,
         now: Timestamp,
     },
-    Kill,
-    Killed,
+    Kill {
+        worker_id: ExternalSnarkWorkerId,
+    },
+    Killed {
+        worker_id: ExternalSnarkWorkerId,
+    },
 
     SubmitWork {
+        worker_id: ExternalSnarkWorkerId,
         job_id: SnarkJobId,
         summary: JobSummary,
     },
     WorkResult {
+        worker_id: ExternalSnarkWorkerId,
         result: SnarkWorkResult,
     },
     WorkError {
+        worker_id: ExternalSnarkWorkerId,
         error: ExternalSnarkWorkerWorkError,
     },
     WorkTimeout {
+        worker_id: ExternalSnarkWorkerId,
         now: Timestamp,
     },
 
-    CancelWork,
-    WorkCancelled,
+    CancelWork {
+        worker_id: ExternalSnarkWorkerId,
+    },
+    WorkCancelled {
+        worker_id: ExternalSnarkWorkerId,
+    },
 
-    PruneWork,
+    PruneWork {
+        worker_id: ExternalSnarkWorkerId,
+    },
 
     Error {
+        worker_id: ExternalSnarkWorkerId,
         error: ExternalSnarkWorkerError,
         permanent: bool,
     },
 }
 
+impl ExternalSnarkWorkerAction {
+    pub fn worker_id(&self) -> &ExternalSnarkWorkerId {
+        match self {
+            Self::Start { worker_id }
+            | Self::Started { worker_id }
+            | Self::StartTimeout { worker_id, .. }
+            | Self::Kill { worker_id }
+            | Self::Killed { worker_id }
+            | Self::SubmitWork { worker_id, .. }
+            | Self::WorkResult { worker_id, .. }
+            | Self::WorkError { worker_id, .. }
+            | Self::WorkTimeout { worker_id, .. }
+            | Self::CancelWork { worker_id }
+            | Self::WorkCancelled { worker_id }
+            | Self::PruneWork { worker_id }
+            | Self::Error { worker_id, .. } => worker_id,
+        }
+    }
+}
+
 pub type ExternalSnarkWorkerActionWithMeta = redux::ActionWithMeta<ExternalSnarkWorkerAction>;
 pub type ExternalSnarkWorkerActionWithMetaRef<'a> =
     redux::ActionWithMeta<&'a ExternalSnarkWorkerAction>;
 
 impl EnablingCondition<State> for ExternalSnarkWorkerAction {
-    fn is_enabled(&self, state: &State, _time: redux::Timestamp) -> bool {
+    fn is_enabled(&self, state: &State, time: redux::Timestamp) -> bool {
+        let workers = &state.external_snark_worker;
+        let worker_id = self.worker_id();
+        let Some(worker) = workers.get(worker_id) else {
+            // Remote workers are added to the pool when they first ask for work.
+            return matches!(
+                (self, worker_id),
+                (
+                    ExternalSnarkWorkerAction::Started { .. },
+                    ExternalSnarkWorkerId::Remote(_)
+                )
+            ) && state.config.snarker.is_some();
+        };
         match self {
-            ExternalSnarkWorkerAction::Start => {
+            ExternalSnarkWorkerAction::Start { .. } => {
                 state.config.snarker.is_some()
-                    && matches!(
-                        state.external_snark_worker.0.state,
-                        ExternalSnarkWorkerState::None
-                    )
+                    && worker_id.is_local()
+                    && matches!(worker.state, ExternalSnarkWorkerState::None)
+                    && !worker.health.is_backing_off(time)
             }
-            ExternalSnarkWorkerAction::Started => {
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Starting
-                )
-            }
-            ExternalSnarkWorkerAction::StartTimeout { now } => {
+            ExternalSnarkWorkerAction::Started { .. } => match worker_id {
+                ExternalSnarkWorkerId::Local(_) => {
+                    matches!(worker.state, ExternalSnarkWorkerState::Starting)
+                }
+                ExternalSnarkWorkerId::Remote(_) => {
+                    matches!(worker.state, ExternalSnarkWorkerState::None)
+                }
+            },
+            ExternalSnarkWorkerAction::StartTimeout { now, .. } => {
                 const TIMEOUT: Duration = Duration::from_secs(120);
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Starting
-                ) && now
-                    .checked_sub(state.external_snark_worker.0.timestamp)
-                    .map_or(false, |d| d > TIMEOUT)
+                matches!(worker.state, ExternalSnarkWorkerState::Starting)
+                    && now
+                        .checked_sub(worker.timestamp)
+                        .map_or(false, |d| d > TIMEOUT)
             }
-            ExternalSnarkWorkerAction::Kill => !matches!(
-                state.external_snark_worker.0.state,
+            ExternalSnarkWorkerAction::Kill { .. } => !matches!(
+                worker.state,
                 ExternalSnarkWorkerState::Error(_, false)
                     | ExternalSnarkWorkerState::None
                     | ExternalSnarkWorkerState::Killing
             ),
-            ExternalSnarkWorkerAction::Killed => {
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Killing
-                )
+            ExternalSnarkWorkerAction::Killed { .. } => {
+                matches!(worker.state, ExternalSnarkWorkerState::Killing)
             }
-            ExternalSnarkWorkerAction::SubmitWork { .. } => state.external_snark_worker.is_idle(),
+            ExternalSnarkWorkerAction::SubmitWork { .. } => worker.is_idle(),
             ExternalSnarkWorkerAction::WorkResult { .. } => {
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Working(..)
-                )
+                matches!(worker.state, ExternalSnarkWorkerState::Working(..))
             }
             ExternalSnarkWorkerAction::WorkError { .. } => {
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Working(..)
-                )
+                matches!(worker.state, ExternalSnarkWorkerState::Working(..))
             }
-            ExternalSnarkWorkerAction::WorkTimeout { now } => {
-                if let ExternalSnarkWorkerState::Working(_, summary) =
-                    &state.external_snark_worker.0.state
-                {
-                    now.checked_sub(state.external_snark_worker.0.timestamp)
+            ExternalSnarkWorkerAction::WorkTimeout { now, .. } => {
+                if let ExternalSnarkWorkerState::Working(_, summary) = &worker.state {
+                    now.checked_sub(worker.timestamp)
                         .map_or(false, |d| d > summary.estimated_duration())
                 } else {
                     false
                 }
             }
-            ExternalSnarkWorkerAction::CancelWork => {
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Working(..)
-                )
+            ExternalSnarkWorkerAction::CancelWork { .. } => {
+                matches!(worker.state, ExternalSnarkWorkerState::Working(..))
             }
-            ExternalSnarkWorkerAction::WorkCancelled => {
-                matches!(
-                    state.external_snark_worker.0.state,
-                    ExternalSnarkWorkerState::Cancelling(_)
-                )
+            ExternalSnarkWorkerAction::WorkCancelled { .. } => {
+                matches!(worker.state, ExternalSnarkWorkerState::Cancelling(_))
             }
-            ExternalSnarkWorkerAction::PruneWork => {
+            ExternalSnarkWorkerAction::PruneWork { .. } => {
                 matches!(
-                    state.external_snark_worker.0.state,
+                    worker.state,
                     ExternalSnarkWorkerState::WorkReady(..)
                         | ExternalSnarkWorkerState::WorkError(..)
                         | ExternalSnarkWorkerState::Cancelled(..)
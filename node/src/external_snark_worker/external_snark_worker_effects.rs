@@ -13,41 +13,53 @@ pub fn external_snark_worker_effects<S: crate::Service>(
 ) {
     let (action, meta) = action.split();
     match action {
-        ExternalSnarkWorkerAction::Start => {
+        ExternalSnarkWorkerAction::Start { worker_id } => {
             let Some(config) = &store.state.get().config.snarker else {
                 return;
             };
             let public_key = config.public_key.clone().into();
             let fee = config.fee.clone();
-            if let Err(err) = store.service.start(public_key, fee) {
+            if let Err(err) = store.service.start(worker_id, public_key, fee) {
                 store.dispatch(ExternalSnarkWorkerAction::Error {
+                    worker_id,
                     error: err,
                     permanent: true,
                 });
             }
         }
-        ExternalSnarkWorkerAction::Started => {
+        ExternalSnarkWorkerAction::Started { .. } => {
             store.dispatch(SnarkPoolAction::AutoCreateCommitment);
         }
-        ExternalSnarkWorkerAction::StartTimeout { .. } => {
+        ExternalSnarkWorkerAction::StartTimeout { worker_id, .. } => {
             store.dispatch(ExternalSnarkWorkerAction::Error {
+                worker_id,
                 error: super::ExternalSnarkWorkerError::StartTimeout,
                 permanent: true,
             });
         }
-        ExternalSnarkWorkerAction::Kill => {
-            if let Err(err) = store.service().kill() {
+        ExternalSnarkWorkerAction::Kill { worker_id } => {
+            if !worker_id.is_local() {
+                // Nothing to kill, remote worker just gets forgotten.
+                store.dispatch(ExternalSnarkWorkerAction::Killed { worker_id });
+            } else if let Err(err) = store.service().kill(worker_id) {
                 store.dispatch(ExternalSnarkWorkerAction::Error {
+                    worker_id,
                     error: err,
                     permanent: true,
                 });
             }
         }
-        ExternalSnarkWorkerAction::Killed => {}
-        ExternalSnarkWorkerAction::Error { .. } => {
-            store.dispatch(ExternalSnarkWorkerAction::Kill);
+        ExternalSnarkWorkerAction::Killed { .. } => {}
+        ExternalSnarkWorkerAction::Error { worker_id, .. } => {
+            store.dispatch(ExternalSnarkWorkerAction::Kill { worker_id });
         }
-        ExternalSnarkWorkerAction::SubmitWork { job_id, .. } => {
+        ExternalSnarkWorkerAction::SubmitWork {
+            worker_id, job_id, ..
+        } => {
+            // Remote workers fetch the job spec themselves.
+            if !worker_id.is_local() {
+                return;
+            }
             let Some(job) = store.state().snark_pool.get(&job_id) else {
                 return;
             };
@@ -57,15 +69,21 @@ pub fn external_snark_worker_effects<S: crate::Service>(
             ) {
                 Ok(v) => v,
                 Err(err) => {
-                    store.dispatch(ExternalSnarkWorkerAction::WorkError { error: err.into() });
+                    store.dispatch(ExternalSnarkWorkerAction::WorkError {
+                        worker_id,
+                        error: err.into(),
+                    });
                     return;
                 }
             };
-            if let Err(err) = store.service().submit(input) {
-                store.dispatch(ExternalSnarkWorkerAction::WorkError { error: err.into() });
+            if let Err(err) = store.service().submit(worker_id, input) {
+                store.dispatch(ExternalSnarkWorkerAction::WorkError {
+                    worker_id,
+                    error: err.into(),
+                });
             }
         }
-        ExternalSnarkWorkerAction::WorkResult { result } => {
+        ExternalSnarkWorkerAction::WorkResult { worker_id, result } => {
             let Some(config) = &store.state().config.snarker else {
                 return;
             };
@@ -78,28 +96,38 @@ pub fn external_snark_worker_effects<S: crate::Service>(
                 proofs: result.clone(),
             };
             let sender = p2p.my_id();
-            // Directly add snark to the snark pool as it's produced by us.
+            // Directly add snark to the snark pool as it's produced by us,
+            // results of remote workers are verified before getting here.
             store.dispatch(SnarkPoolAction::WorkAdd { snark, sender });
-            store.dispatch(ExternalSnarkWorkerAction::PruneWork);
+            store.dispatch(ExternalSnarkWorkerAction::PruneWork { worker_id });
         }
-        ExternalSnarkWorkerAction::WorkError { .. } => {
-            store.dispatch(ExternalSnarkWorkerAction::PruneWork);
+        ExternalSnarkWorkerAction::WorkError { worker_id, .. } => {
+            store.dispatch(ExternalSnarkWorkerAction::PruneWork { worker_id });
         }
-        ExternalSnarkWorkerAction::WorkTimeout { .. } => {
-            store.dispatch(ExternalSnarkWorkerAction::CancelWork);
+        ExternalSnarkWorkerAction::WorkTimeout { worker_id, .. } => {
+            store.dispatch(ExternalSnarkWorkerAction::CancelWork { worker_id });
         }
-        ExternalSnarkWorkerAction::CancelWork => {
-            if let Err(error) = store.service().cancel() {
+        ExternalSnarkWorkerAction::CancelWork { worker_id } => {
+            if !worker_id.is_local() {
+                // Remote worker can't be interrupted, its result will
+                // be rejected if it's submitted anyway.
+                store.dispatch(ExternalSnarkWorkerAction::WorkCancelled { worker_id });
+            } else if let Err(error) = store.service().cancel(worker_id) {
                 store.dispatch(ExternalSnarkWorkerAction::Error {
+                    worker_id,
                     error,
                     permanent: true,
                 });
             }
         }
-        ExternalSnarkWorkerAction::WorkCancelled => {
-            store.dispatch(ExternalSnarkWorkerAction::PruneWork);
+        ExternalSnarkWorkerAction::WorkCancelled { worker_id } => {
+            store.dispatch(ExternalSnarkWorkerAction::PruneWork { worker_id });
         }
-        ExternalSnarkWorkerAction::PruneWork => {
+        ExternalSnarkWorkerAction::PruneWork { worker_id } => {
+            if !worker_id.is_local() {
+                store.dispatch(ExternalSnarkWorkerAction::Kill { worker_id });
+                return;
+            }
             store.dispatch(SnarkPoolAction::AutoCreateCommitment);
         }
     }
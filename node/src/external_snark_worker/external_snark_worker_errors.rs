@@ -29,6 +29,8 @@ pub enum ExternalSnarkWorkerWorkError {
     WorkerError(ExternalSnarkWorkerError),
     #[error("work is cancelled")]
     Cancelled,
+    #[error("snark verification failed")]
+    VerificationFailed,
     #[error("error producing snark: {_0}")]
     Error(String),
 }
@@ -5,7 +5,19 @@ use super::{
 
 impl ExternalSnarkWorkers {
     pub fn reducer(&mut self, action: ExternalSnarkWorkerActionWithMetaRef<'_>) {
-        self.0.reducer(action)
+        let time = action.meta().time();
+        let worker_id = *action.action().worker_id();
+        self.0
+            .entry(worker_id)
+            .or_insert_with(|| ExternalSnarkWorker::new(time))
+            .reducer(action);
+
+        // Remote workers are only kept while they have a job.
+        if !worker_id.is_local() {
+            if let ExternalSnarkWorkerAction::Killed { .. } = action.action() {
+                self.0.remove(&worker_id);
+            }
+        }
     }
 }
 
@@ -13,55 +25,63 @@ impl ExternalSnarkWorker {
     pub fn reducer(&mut self, action: ExternalSnarkWorkerActionWithMetaRef<'_>) {
         let (action, meta) = action.split();
         match action {
-            ExternalSnarkWorkerAction::Start => {
+            ExternalSnarkWorkerAction::Start { .. } => {
                 self.state = ExternalSnarkWorkerState::Starting;
             }
-            ExternalSnarkWorkerAction::Started => {
+            ExternalSnarkWorkerAction::Started { .. } => {
                 self.state = ExternalSnarkWorkerState::Idle;
             }
             ExternalSnarkWorkerAction::StartTimeout { .. } => {
                 return;
             }
-            ExternalSnarkWorkerAction::Kill => {
+            ExternalSnarkWorkerAction::Kill { .. } => {
                 self.state = ExternalSnarkWorkerState::Killing;
             }
-            ExternalSnarkWorkerAction::Killed => {
+            ExternalSnarkWorkerAction::Killed { .. } => {
                 self.state = ExternalSnarkWorkerState::None;
             }
-            ExternalSnarkWorkerAction::Error { error, permanent } => {
+            ExternalSnarkWorkerAction::Error {
+                error, permanent, ..
+            } => {
                 self.state = ExternalSnarkWorkerState::Error(error.clone(), *permanent);
+                self.health.failure(meta.time());
             }
-            ExternalSnarkWorkerAction::SubmitWork { job_id, summary } => {
+            ExternalSnarkWorkerAction::SubmitWork {
+                job_id, summary, ..
+            } => {
                 self.state = ExternalSnarkWorkerState::Working(job_id.clone(), summary.clone());
             }
-            ExternalSnarkWorkerAction::WorkResult { result } => {
+            ExternalSnarkWorkerAction::WorkResult { result, .. } => {
                 let ExternalSnarkWorkerState::Working(job_id, _) = &self.state else {
                     return;
                 };
                 self.state = ExternalSnarkWorkerState::WorkReady(job_id.clone(), result.clone());
+                self.health.success();
             }
-            ExternalSnarkWorkerAction::WorkError { error } => {
+            ExternalSnarkWorkerAction::WorkError { error, .. } => {
                 let ExternalSnarkWorkerState::Working(job_id, _) = &self.state else {
                     return;
                 };
                 self.state = ExternalSnarkWorkerState::WorkError(job_id.clone(), error.clone());
+                self.health.failure(meta.time());
             }
             ExternalSnarkWorkerAction::WorkTimeout { .. } => {
+                self.health.failure(meta.time());
                 return;
             }
-            ExternalSnarkWorkerAction::CancelWork => {
+            ExternalSnarkWorkerAction::CancelWork { .. } => {
                 let ExternalSnarkWorkerState::Working(job_id, _) = &self.state else {
                     return;
                 };
                 self.state = ExternalSnarkWorkerState::Cancelling(job_id.clone());
             }
-            ExternalSnarkWorkerAction::WorkCancelled => {
+            ExternalSnarkWorkerAction::WorkCancelled { .. } => {
                 let ExternalSnarkWorkerState::Cancelling(job_id) = &self.state else {
                     return;
                 };
                 self.state = ExternalSnarkWorkerState::Cancelled(job_id.clone());
             }
-            ExternalSnarkWorkerAction::PruneWork => {
+            ExternalSnarkWorkerAction::PruneWork { .. } => {
                 self.state = ExternalSnarkWorkerState::Idle;
             }
         }
@@ -2,7 +2,8 @@ use mina_p2p_messages::v2::{CurrencyFeeStableV1, NonZeroCurvePoint};
 use serde::{Deserialize, Serialize};
 
 use super::{
-    ExternalSnarkWorkerError, ExternalSnarkWorkerWorkError, SnarkWorkResult, SnarkWorkSpec,
+    ExternalSnarkWorkerError, ExternalSnarkWorkerId, ExternalSnarkWorkerWorkError, SnarkWorkResult,
+    SnarkWorkSpec,
 };
 
 #[derive(Serialize, Deserialize, Debug, Clone, derive_more::From)]
@@ -15,20 +16,28 @@ pub enum ExternalSnarkWorkerEvent {
     Error(ExternalSnarkWorkerError),
 }
 
+/// Manages the local workers of the pool. Events of a worker are
+/// reported as [`crate::event_source::Event::ExternalSnarkWorker`] along
+/// with its id.
 pub trait ExternalSnarkWorkerService {
     /// Starts external process.
     fn start(
         &mut self,
+        worker_id: ExternalSnarkWorkerId,
         public_key: NonZeroCurvePoint,
         fee: CurrencyFeeStableV1,
     ) -> Result<(), ExternalSnarkWorkerError>;
 
     /// Submits snark work
-    fn submit(&mut self, spec: SnarkWorkSpec) -> Result<(), ExternalSnarkWorkerError>;
+    fn submit(
+        &mut self,
+        worker_id: ExternalSnarkWorkerId,
+        spec: SnarkWorkSpec,
+    ) -> Result<(), ExternalSnarkWorkerError>;
 
     /// Cancel current work
-    fn cancel(&mut self) -> Result<(), ExternalSnarkWorkerError>;
+    fn cancel(&mut self, worker_id: ExternalSnarkWorkerId) -> Result<(), ExternalSnarkWorkerError>;
 
     /// Kills external process.
-    fn kill(&mut self) -> Result<(), ExternalSnarkWorkerError>;
+    fn kill(&mut self, worker_id: ExternalSnarkWorkerId) -> Result<(), ExternalSnarkWorkerError>;
 }
@@ -1,17 +1,29 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
 use redux::Timestamp;
 use serde::{Deserialize, Serialize};
 
 use crate::snark_pool::JobSummary;
 
-use super::{ExternalSnarkWorkerError, ExternalSnarkWorkerWorkError, SnarkWorkId, SnarkWorkResult};
+use super::{
+    ExternalSnarkWorkerError, ExternalSnarkWorkerId, ExternalSnarkWorkerWorkError, SnarkWorkId,
+    SnarkWorkResult,
+};
+
+/// Backoff after the first failure of a worker, doubled with each
+/// consecutive failure.
+const FAILURE_BACKOFF: Duration = Duration::from_secs(10);
+const FAILURE_BACKOFF_MAX: Duration = Duration::from_secs(5 * 60);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ExternalSnarkWorkers(pub(crate) ExternalSnarkWorker);
+pub struct ExternalSnarkWorkers(pub(crate) BTreeMap<ExternalSnarkWorkerId, ExternalSnarkWorker>);
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExternalSnarkWorker {
     pub(crate) state: ExternalSnarkWorkerState,
     pub(crate) timestamp: Timestamp,
+    pub(crate) health: ExternalSnarkWorkerHealth,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,34 +43,142 @@ pub enum ExternalSnarkWorkerState {
     Error(ExternalSnarkWorkerError, bool),
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExternalSnarkWorkerHealth {
+    /// Snarks produced by the worker.
+    pub completed: u64,
+    /// Failed or timed out jobs and worker errors.
+    pub failed: u64,
+    /// Failures since the last produced snark.
+    pub consecutive_failures: u32,
+    pub last_failure: Option<Timestamp>,
+}
+
 impl ExternalSnarkWorkers {
-    pub fn new(now: Timestamp) -> Self {
-        ExternalSnarkWorkers(ExternalSnarkWorker {
-            state: ExternalSnarkWorkerState::None,
-            timestamp: now,
-        })
+    pub fn new(now: Timestamp, local_workers: u16) -> Self {
+        ExternalSnarkWorkers(
+            (0..local_workers)
+                .map(|i| {
+                    (
+                        ExternalSnarkWorkerId::Local(i),
+                        ExternalSnarkWorker::new(now),
+                    )
+                })
+                .collect(),
+        )
     }
 
-    pub fn is_idle(&self) -> bool {
-        matches!(self.0.state, ExternalSnarkWorkerState::Idle)
+    pub fn get(&self, worker_id: &ExternalSnarkWorkerId) -> Option<&ExternalSnarkWorker> {
+        self.0.get(worker_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&ExternalSnarkWorkerId, &ExternalSnarkWorker)> {
+        self.0.iter()
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = ExternalSnarkWorkerId> + '_ {
+        self.0.keys().copied()
+    }
+
+    pub fn is_idle(&self, worker_id: &ExternalSnarkWorkerId) -> bool {
+        self.get(worker_id).map_or(false, |worker| worker.is_idle())
     }
 
     pub fn has_idle(&self) -> bool {
         self.available() > 0
     }
 
+    /// Number of local workers waiting for a job. Remote workers pull
+    /// jobs themselves, so they are never counted.
     pub fn available(&self) -> usize {
-        if matches!(self.0.state, ExternalSnarkWorkerState::Idle) {
-            1
-        } else {
-            0
+        self.idle_local_workers().count()
+    }
+
+    pub fn idle_local_workers(&self) -> impl Iterator<Item = ExternalSnarkWorkerId> + '_ {
+        self.0
+            .iter()
+            .filter(|(id, worker)| id.is_local() && worker.is_idle())
+            .map(|(id, _)| *id)
+    }
+
+    /// Workers along with the jobs they are working on.
+    pub fn working_job_ids(&self) -> impl Iterator<Item = (ExternalSnarkWorkerId, &SnarkWorkId)> {
+        self.0
+            .iter()
+            .filter_map(|(id, worker)| Some((*id, worker.working_job_id()?)))
+    }
+
+    pub fn is_working_on(&self, job_id: &SnarkWorkId) -> bool {
+        self.working_job_ids().any(|(_, id)| id == job_id)
+    }
+
+    /// Whether the worker was given the job and is still working on it.
+    pub fn is_worker_working_on(
+        &self,
+        worker_id: &ExternalSnarkWorkerId,
+        job_id: &SnarkWorkId,
+    ) -> bool {
+        self.get(worker_id)
+            .and_then(|worker| worker.working_job_id())
+            .map_or(false, |id| id == job_id)
+    }
+}
+
+impl ExternalSnarkWorker {
+    pub fn new(now: Timestamp) -> Self {
+        ExternalSnarkWorker {
+            state: ExternalSnarkWorkerState::None,
+            timestamp: now,
+            health: Default::default(),
         }
     }
 
+    pub fn state(&self) -> &ExternalSnarkWorkerState {
+        &self.state
+    }
+
+    pub fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    pub fn health(&self) -> &ExternalSnarkWorkerHealth {
+        &self.health
+    }
+
+    pub fn is_idle(&self) -> bool {
+        matches!(self.state, ExternalSnarkWorkerState::Idle)
+    }
+
     pub fn working_job_id(&self) -> Option<&SnarkWorkId> {
-        match &self.0.state {
+        match &self.state {
             ExternalSnarkWorkerState::Working(job_id, _) => Some(job_id),
             _ => None,
         }
     }
 }
+
+impl ExternalSnarkWorkerHealth {
+    pub(super) fn success(&mut self) {
+        self.completed += 1;
+        self.consecutive_failures = 0;
+    }
+
+    pub(super) fn failure(&mut self, now: Timestamp) {
+        self.failed += 1;
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.last_failure = Some(now);
+    }
+
+    /// Whether the worker failed recently enough that it shouldn't be
+    /// (re)started or given new jobs yet.
+    pub fn is_backing_off(&self, now: Timestamp) -> bool {
+        let Some(last_failure) = self.last_failure.filter(|_| self.consecutive_failures > 0) else {
+            return false;
+        };
+        let backoff = FAILURE_BACKOFF
+            .saturating_mul(1 << (self.consecutive_failures - 1).min(16))
+            .min(FAILURE_BACKOFF_MAX);
+        now.checked_sub(last_failure)
+            .map_or(true, |passed| passed < backoff)
+    }
+}
@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
 use mina_p2p_messages::v2::{
@@ -5,6 +6,7 @@ use mina_p2p_messages::v2::{
     TransactionSnarkWorkTStableV2Proofs,
 };
 use openmina_core::snark::SnarkJobId;
+use serde::{Deserialize, Serialize};
 
 pub type SnarkWorkId = SnarkJobId;
 
@@ -12,3 +14,48 @@ pub type SnarkWorkId = SnarkJobId;
 pub type SnarkWorkSpec = SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0Instances;
 
 pub type SnarkWorkResult = Arc<TransactionSnarkWorkTStableV2Proofs>;
+
+/// Identifier of a worker in the snarker's worker pool.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    derive_more::Display,
+)]
+pub enum ExternalSnarkWorkerId {
+    /// Worker spawned and managed by the node itself.
+    #[display(fmt = "local-{}", _0)]
+    Local(u16),
+    /// Job assigned to a worker connected over the snark worker RPC
+    /// protocol, identified by the connection which asked for it. The
+    /// protocol doesn't identify workers and they reconnect for each
+    /// request, so it only lives until the job is done.
+    #[display(fmt = "remote-{}", _0)]
+    Remote(u64),
+}
+
+impl ExternalSnarkWorkerId {
+    pub fn is_local(&self) -> bool {
+        matches!(self, Self::Local(_))
+    }
+}
+
+impl FromStr for ExternalSnarkWorkerId {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s.split_once('-').ok_or(())?;
+        match kind {
+            "local" => Ok(Self::Local(id.parse().or(Err(()))?)),
+            "remote" => Ok(Self::Remote(id.parse().or(Err(()))?)),
+            _ => Err(()),
+        }
+    }
+}
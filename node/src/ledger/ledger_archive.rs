@@ -0,0 +1,583 @@
+use std::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    ops::Range,
+    path::Path,
+};
+
+use ledger::{
+    ondisk,
+    scan_state::transaction_logic::{Transaction, WithStatus},
+    Account, AccountId, BaseLedger, Mask,
+};
+use mina_p2p_messages::{
+    binprot::macros::{BinProtRead, BinProtWrite},
+    v2::{
+        self, MinaBaseSignedCommandStableV2, MinaBaseTransactionStatusStableV2,
+        MinaBaseUserCommandStableV2, MinaBaseZkappCommandTStableV1WireStableV1,
+        MinaTransactionTransactionStableV2, StateHash, TokenIdKeyHash, TransactionHash,
+    },
+};
+use openmina_core::block::ArcBlockWithHash;
+use serde::{Deserialize, Serialize};
+
+use super::ledger_persistence::{decode, encode};
+use super::ledger_zkapp_events::ZkappBlockEvents;
+use crate::account::AccountPublicKey;
+
+const BEST_TIP_KEY: &[u8] = b"best_tip";
+
+fn block_key(hash: &StateHash) -> Box<[u8]> {
+    format!("block/{hash}").into_bytes().into()
+}
+
+/// Height is zero-padded, so that the keys are ordered by height.
+fn height_key(height: u32) -> Box<[u8]> {
+    format!("height/{height:010}").into_bytes().into()
+}
+
+fn transaction_key(hash: &TransactionHash) -> Box<[u8]> {
+    format!("transaction/{hash}").into_bytes().into()
+}
+
+fn zkapp_events_key(hash: &StateHash) -> Box<[u8]> {
+    format!("zkapp_events/{hash}").into_bytes().into()
+}
+
+fn account_key(account_id: &AccountId) -> Box<[u8]> {
+    let public_key = AccountPublicKey::from(account_id.public_key.clone());
+    let token_id = TokenIdKeyHash::from(&account_id.token_id);
+    format!("account/{public_key}/{token_id}")
+        .into_bytes()
+        .into()
+}
+
+#[derive(BinProtRead, BinProtWrite, Serialize, Deserialize, Debug, Clone)]
+pub struct ArchiveTransaction {
+    pub transaction: MinaTransactionTransactionStableV2,
+    pub status: MinaBaseTransactionStatusStableV2,
+}
+
+/// Block as it's stored in the archive.
+#[derive(BinProtRead, BinProtWrite, Serialize, Deserialize, Debug, Clone)]
+pub struct ArchiveBlock {
+    pub hash: StateHash,
+    pub block: v2::MinaBlockBlockStableV2,
+    /// Transactions of the block in the order they were applied, including
+    /// the internal commands (fee transfers and coinbase).
+    pub transactions: Vec<ArchiveTransaction>,
+    /// State of the accounts touched by the block, after it was applied.
+    pub accounts: Vec<Account>,
+}
+
+impl ArchiveBlock {
+    /// Collects the archive data of the `block`, `ledger` being the staged
+    /// ledger after the block was applied.
+    pub fn new(
+        block: &ArcBlockWithHash,
+        transactions: Vec<WithStatus<Transaction>>,
+        ledger: &Mask,
+    ) -> Self {
+        let account_ids = transactions
+            .iter()
+            .flat_map(|tx| tx.data.accounts_referenced())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        let addrs = ledger
+            .location_of_account_batch(&account_ids)
+            .into_iter()
+            .filter_map(|(_, addr)| addr)
+            .collect::<Vec<_>>();
+        let accounts = ledger
+            .get_batch(&addrs)
+            .into_iter()
+            .filter_map(|(_, account)| account.map(|account| *account))
+            .collect();
+
+        Self {
+            hash: block.hash().clone(),
+            block: (*block.block).clone(),
+            transactions: transactions
+                .iter()
+                .map(|tx| ArchiveTransaction {
+                    transaction: (&tx.data).into(),
+                    status: (&tx.status).into(),
+                })
+                .collect(),
+            accounts,
+        }
+    }
+
+    pub fn height(&self) -> u32 {
+        self.block
+            .header
+            .protocol_state
+            .body
+            .consensus_state
+            .blockchain_length
+            .as_u32()
+    }
+
+    pub fn pred_hash(&self) -> &StateHash {
+        &self.block.header.protocol_state.previous_state_hash
+    }
+
+    fn user_commands_iter(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &MinaBaseUserCommandStableV2,
+            &MinaBaseTransactionStatusStableV2,
+        ),
+    > {
+        self.transactions
+            .iter()
+            .filter_map(|tx| match &tx.transaction {
+                MinaTransactionTransactionStableV2::Command(command) => {
+                    Some((command.as_ref(), &tx.status))
+                }
+                _ => None,
+            })
+    }
+
+    /// Payments and stake delegations.
+    pub fn user_commands(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &MinaBaseSignedCommandStableV2,
+            &MinaBaseTransactionStatusStableV2,
+        ),
+    > {
+        self.user_commands_iter()
+            .filter_map(|(command, status)| match command {
+                MinaBaseUserCommandStableV2::SignedCommand(command) => Some((command, status)),
+                MinaBaseUserCommandStableV2::ZkappCommand(_) => None,
+            })
+    }
+
+    pub fn zkapp_commands(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            &MinaBaseZkappCommandTStableV1WireStableV1,
+            &MinaBaseTransactionStatusStableV2,
+        ),
+    > {
+        self.user_commands_iter()
+            .filter_map(|(command, status)| match command {
+                MinaBaseUserCommandStableV2::ZkappCommand(command) => Some((command, status)),
+                MinaBaseUserCommandStableV2::SignedCommand(_) => None,
+            })
+    }
+
+    /// Fee transfers and coinbase.
+    pub fn internal_commands(&self) -> impl Iterator<Item = &ArchiveTransaction> {
+        self.transactions.iter().filter(|tx| {
+            !matches!(
+                tx.transaction,
+                MinaTransactionTransactionStableV2::Command(_)
+            )
+        })
+    }
+}
+
+/// Archive of the blocks that made it to the best chain, kept in an
+/// embedded database, together with the indexes needed to query it.
+///
+/// Blocks are archived starting from the first one applied by the node,
+/// so the root block of the initial sync and its ancestors are not there.
+pub struct LedgerArchive {
+    db: ondisk::Database,
+    /// Applied blocks that weren't (yet) on the best chain.
+    pending: BTreeMap<StateHash, ArchiveBlock>,
+}
+
+impl LedgerArchive {
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Ok(Self {
+            db: ondisk::Database::create(path)?,
+            pending: Default::default(),
+        })
+    }
+
+    pub fn block_applied(&mut self, block: ArchiveBlock) {
+        self.pending.insert(block.hash.clone(), block);
+    }
+
+    /// Archives the blocks of the new best chain which aren't yet archived,
+    /// and updates the indexes of the ones that are, if the best chain
+    /// switched back to them.
+    ///
+    /// Pending blocks at or below `root_height` can't end up on the best
+    /// chain anymore, so they are dropped.
+    ///
+    /// zkApp events and actions of the new blocks are taken from
+    /// `zkapp_events`.
+    pub fn best_tip_changed(
+        &mut self,
+        best_tip: &StateHash,
+        root_height: u32,
+        zkapp_events: &BTreeMap<StateHash, ZkappBlockEvents>,
+    ) -> std::io::Result<()> {
+        let prev_best_tip_height = self.best_tip()?.map(|block| block.height());
+        let mut new_blocks = Vec::new();
+        let mut canonical = Vec::new();
+        let mut hash = best_tip.clone();
+        loop {
+            let (height, pred_hash) = match self.pending.remove(&hash) {
+                Some(block) => {
+                    let parent = (block.height(), block.pred_hash().clone());
+                    new_blocks.push(block);
+                    parent
+                }
+                // already archived, but not on the best chain anymore.
+                None => match self.block(&hash)? {
+                    Some(block) if self.canonical_hash(block.height())?.as_ref() != Some(&hash) => {
+                        (block.height(), block.pred_hash().clone())
+                    }
+                    _ => break,
+                },
+            };
+            canonical.push((height, std::mem::replace(&mut hash, pred_hash)));
+        }
+        self.pending.retain(|_, block| block.height() > root_height);
+
+        let mut account_history: BTreeMap<Box<[u8]>, Vec<StateHash>> = BTreeMap::new();
+        // `new_blocks` goes from the best tip down, history goes from the oldest block.
+        for block in new_blocks.iter().rev() {
+            for account in &block.accounts {
+                let history = match account_history.entry(account_key(&account.id())) {
+                    Entry::Occupied(entry) => entry.into_mut(),
+                    Entry::Vacant(entry) => {
+                        let history = self.get(entry.key())?.unwrap_or_default();
+                        entry.insert(history)
+                    }
+                };
+                history.push(block.hash.clone());
+            }
+        }
+
+        let mut entries = Vec::new();
+        for block in &new_blocks {
+            for (command, _) in block.user_commands_iter() {
+                entries.push((transaction_key(&command.hash()?), encode(&block.hash)?));
+            }
+            entries.push((block_key(&block.hash), encode(block)?));
+            if let Some(events) = zkapp_events.get(&block.hash) {
+                entries.push((zkapp_events_key(&block.hash), encode(events)?));
+            }
+        }
+        for (height, hash) in &canonical {
+            entries.push((height_key(*height), encode(hash)?));
+        }
+        for (key, history) in account_history {
+            entries.push((key, encode(&history)?));
+        }
+        entries.push((BEST_TIP_KEY.into(), encode(best_tip)?));
+
+        // heights above the new best tip aren't on the best chain anymore.
+        let best_tip_height = match canonical.first() {
+            Some((height, _)) => Some(*height),
+            None => self.block(best_tip)?.map(|block| block.height()),
+        };
+        let removed = match (best_tip_height, prev_best_tip_height) {
+            (Some(height), Some(prev_height)) => {
+                (height + 1..=prev_height).map(height_key).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        self.db.set_batch(entries, removed)
+    }
+
+    fn get<T: mina_p2p_messages::binprot::BinProtRead>(
+        &mut self,
+        key: &[u8],
+    ) -> std::io::Result<Option<T>> {
+        self.db.get(key)?.map(|bytes| decode(&bytes)).transpose()
+    }
+
+    fn canonical_hash(&mut self, height: u32) -> std::io::Result<Option<StateHash>> {
+        self.get(&height_key(height))
+    }
+
+    pub fn best_tip(&mut self) -> std::io::Result<Option<ArchiveBlock>> {
+        match self.get::<StateHash>(BEST_TIP_KEY)? {
+            Some(hash) => self.block(&hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Archived block with the `hash`, whether it's on the best chain or not.
+    pub fn block(&mut self, hash: &StateHash) -> std::io::Result<Option<ArchiveBlock>> {
+        self.get(&block_key(hash))
+    }
+
+    /// Block at the `height` on the best chain.
+    pub fn block_at_height(&mut self, height: u32) -> std::io::Result<Option<ArchiveBlock>> {
+        let Some(best_tip) = self.best_tip()? else {
+            return Ok(None);
+        };
+        if height > best_tip.height() {
+            return Ok(None);
+        }
+        match self.canonical_hash(height)? {
+            Some(hash) => self.block(&hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Block which included the user command with the `hash`. If the command was
+    /// included in more than one block (because of a fork), the last one
+    /// archived is returned.
+    pub fn transaction_block(
+        &mut self,
+        hash: &TransactionHash,
+    ) -> std::io::Result<Option<ArchiveBlock>> {
+        match self.get::<StateHash>(&transaction_key(hash))? {
+            Some(hash) => self.block(&hash),
+            None => Ok(None),
+        }
+    }
+
+    /// zkApp events and actions of the account in the best chain blocks
+    /// with height in the range, oldest first.
+    pub fn zkapp_events(
+        &mut self,
+        account_id: &AccountId,
+        heights: Range<u32>,
+    ) -> std::io::Result<Vec<ZkappBlockEvents>> {
+        let mut blocks = Vec::new();
+        for hash in self.account_history(account_id)? {
+            let Some(events) = self.get::<ZkappBlockEvents>(&zkapp_events_key(&hash))? else {
+                continue;
+            };
+            if heights.contains(&events.height)
+                && self.canonical_hash(events.height)?.as_ref() == Some(&hash)
+            {
+                blocks.push(events.filter_account(account_id));
+            }
+        }
+        blocks.sort_by_key(|events| events.height);
+        Ok(blocks)
+    }
+
+    /// Hashes of the archived blocks which changed the account, oldest first.
+    /// It may include blocks that are no longer on the best chain.
+    pub fn account_history(&mut self, account_id: &AccountId) -> std::io::Result<Vec<StateHash>> {
+        Ok(self.get(&account_key(account_id))?.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::scan_state::{
+        currency::{Amount, Fee, Nonce},
+        transaction_logic::{
+            signed_command::{self, SignedCommand, SignedCommandPayload},
+            Memo,
+        },
+    };
+    use mina_p2p_messages::bigint::BigInt;
+
+    use super::*;
+    use crate::testing::recorded_block;
+
+    /// Block at `height` extending `pred`, with the hash derived from `id`,
+    /// so that forks at the same height get different hashes.
+    fn archive_block(id: u64, height: u32, pred: Option<&ArchiveBlock>) -> ArchiveBlock {
+        let mut block = recorded_block();
+        let protocol_state = &mut block.header.protocol_state;
+        protocol_state.body.consensus_state.blockchain_length = height.into();
+        if let Some(pred) = pred {
+            protocol_state.previous_state_hash = pred.hash.clone();
+        }
+        ArchiveBlock {
+            hash: StateHash::from_fp(id.into()),
+            block,
+            transactions: Vec::new(),
+            accounts: Vec::new(),
+        }
+    }
+
+    fn chain(first_id: u64, len: u32, pred: Option<&ArchiveBlock>) -> Vec<ArchiveBlock> {
+        let mut chain = Vec::<ArchiveBlock>::new();
+        let first_height = pred.map_or(1, |pred| pred.height() + 1);
+        for i in 0..len {
+            let pred = chain.last().or(pred);
+            chain.push(archive_block(first_id + i as u64, first_height + i, pred));
+        }
+        chain
+    }
+
+    fn apply(archive: &mut LedgerArchive, blocks: &[ArchiveBlock]) {
+        for block in blocks {
+            archive.block_applied(block.clone());
+        }
+        let best_tip = &blocks.last().unwrap().hash;
+        archive
+            .best_tip_changed(best_tip, 0, &Default::default())
+            .unwrap();
+    }
+
+    fn hash_at_height(archive: &mut LedgerArchive, height: u32) -> Option<StateHash> {
+        archive
+            .block_at_height(height)
+            .unwrap()
+            .map(|block| block.hash)
+    }
+
+    fn payment(signer: &Account) -> MinaBaseUserCommandStableV2 {
+        let command = SignedCommand {
+            payload: SignedCommandPayload::create(
+                Fee::from_u64(10_000_000),
+                signer.public_key.clone(),
+                Nonce::from_u32(0),
+                None,
+                Memo::empty(),
+                signed_command::Body::Payment(signed_command::PaymentPayload {
+                    receiver_pk: signer.public_key.clone(),
+                    amount: Amount::from_u64(1_000_000_000),
+                }),
+            ),
+            signer: signer.public_key.clone(),
+            signature: mina_signer::Signature {
+                rx: BigInt::one().try_into().unwrap(),
+                s: BigInt::one().try_into().unwrap(),
+            },
+        };
+        MinaBaseUserCommandStableV2::SignedCommand(command.into())
+    }
+
+    #[test]
+    fn test_empty_archive() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = LedgerArchive::open(dir.path()).unwrap();
+
+        assert!(archive.best_tip().unwrap().is_none());
+        assert!(archive.block_at_height(1).unwrap().is_none());
+        assert!(archive
+            .account_history(&Account::rand().id())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_best_chain_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = LedgerArchive::open(dir.path()).unwrap();
+        let blocks = chain(1, 3, None);
+        apply(&mut archive, &blocks);
+
+        assert_eq!(archive.best_tip().unwrap().unwrap().hash, blocks[2].hash);
+        for block in &blocks {
+            let archived = archive.block(&block.hash).unwrap().unwrap();
+            assert_eq!(archived.height(), block.height());
+            assert_eq!(
+                hash_at_height(&mut archive, block.height()),
+                Some(block.hash.clone())
+            );
+        }
+        assert_eq!(hash_at_height(&mut archive, 4), None);
+        assert!(archive
+            .block(&StateHash::from_fp(100u64.into()))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_unapplied_blocks_not_archived() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = LedgerArchive::open(dir.path()).unwrap();
+        let blocks = chain(1, 3, None);
+        for block in &blocks {
+            archive.block_applied(block.clone());
+        }
+        archive
+            .best_tip_changed(&blocks[1].hash, 0, &Default::default())
+            .unwrap();
+
+        assert_eq!(archive.best_tip().unwrap().unwrap().hash, blocks[1].hash);
+        assert!(archive.block(&blocks[2].hash).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fork_switch() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = LedgerArchive::open(dir.path()).unwrap();
+        let main = chain(1, 3, None);
+        let fork = chain(10, 1, Some(&main[0]));
+        apply(&mut archive, &main);
+        apply(&mut archive, &fork);
+
+        assert_eq!(archive.best_tip().unwrap().unwrap().hash, fork[0].hash);
+        assert_eq!(hash_at_height(&mut archive, 1), Some(main[0].hash.clone()));
+        assert_eq!(hash_at_height(&mut archive, 2), Some(fork[0].hash.clone()));
+        assert_eq!(hash_at_height(&mut archive, 3), None);
+        // blocks that left the best chain stay archived.
+        assert!(archive.block(&main[2].hash).unwrap().is_some());
+
+        // switching back to the already archived blocks.
+        archive
+            .best_tip_changed(&main[2].hash, 0, &Default::default())
+            .unwrap();
+        assert_eq!(archive.best_tip().unwrap().unwrap().hash, main[2].hash);
+        for block in &main {
+            assert_eq!(
+                hash_at_height(&mut archive, block.height()),
+                Some(block.hash.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_account_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = LedgerArchive::open(dir.path()).unwrap();
+        let account = Account::rand();
+        let other = Account::rand();
+        let mut blocks = chain(1, 3, None);
+        blocks[0].accounts = vec![account.clone()];
+        blocks[1].accounts = vec![other.clone()];
+        blocks[2].accounts = vec![account.clone(), other.clone()];
+        apply(&mut archive, &blocks[..2]);
+        apply(&mut archive, &blocks[2..]);
+
+        assert_eq!(
+            archive.account_history(&account.id()).unwrap(),
+            vec![blocks[0].hash.clone(), blocks[2].hash.clone()]
+        );
+        assert_eq!(
+            archive.account_history(&other.id()).unwrap(),
+            vec![blocks[1].hash.clone(), blocks[2].hash.clone()]
+        );
+    }
+
+    #[test]
+    fn test_transaction_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut archive = LedgerArchive::open(dir.path()).unwrap();
+        let command = payment(&Account::rand());
+        let mut blocks = chain(1, 2, None);
+        blocks[1].transactions = vec![ArchiveTransaction {
+            transaction: MinaTransactionTransactionStableV2::Command(Box::new(command.clone())),
+            status: MinaBaseTransactionStatusStableV2::Applied,
+        }];
+        apply(&mut archive, &blocks);
+
+        let block = archive
+            .transaction_block(&command.hash().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(block.hash, blocks[1].hash);
+        assert_eq!(block.user_commands().count(), 1);
+        assert_eq!(block.internal_commands().count(), 0);
+
+        let unknown = payment(&Account::rand());
+        assert!(archive
+            .transaction_block(&unknown.hash().unwrap())
+            .unwrap()
+            .is_none());
+    }
+}
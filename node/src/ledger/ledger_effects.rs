@@ -7,7 +7,8 @@ use crate::p2p::channels::rpc::{P2pChannelsRpcAction, P2pRpcId, P2pRpcResponse};
 use crate::p2p::PeerId;
 use crate::transition_frontier::sync::ledger::staged::TransitionFrontierSyncLedgerStagedAction;
 use crate::transition_frontier::sync::TransitionFrontierSyncAction;
-use crate::{BlockProducerAction, RpcAction, Store};
+use crate::watched_accounts::WatchedAccountsLedgerInitialStateGetError;
+use crate::{BlockProducerAction, RpcAction, Store, WatchedAccountsAction};
 
 use super::read::{
     LedgerReadAction, LedgerReadId, LedgerReadRequest, LedgerReadResponse,
@@ -202,6 +203,9 @@ fn next_read_requests_init<S: redux::Service>(store: &mut Store<S>) {
                     P2pRpcRequest::StagedLedgerAuxAndPendingCoinbasesAtBlock(block_hash) => {
                         build_staged_ledger_parts_request(store.state(), block_hash)?
                     }
+                    P2pRpcRequest::EpochLedger(hash) => {
+                        LedgerReadRequest::GetEpochLedger(hash.clone())
+                    }
                     _ => return None,
                 };
 
@@ -247,23 +251,107 @@ fn next_read_requests_init<S: redux::Service>(store: &mut Store<S>) {
         }
     }
 
+    let hard_fork_config_rpcs = store
+        .state()
+        .rpc
+        .hard_fork_config_rpc_ids()
+        .filter(|(_, status)| status.is_init())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    for rpc_id in hard_fork_config_rpcs {
+        store.dispatch(RpcAction::HardForkConfigLedgersGetInit { rpc_id });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
+
+    let transaction_simulate_rpcs = store
+        .state()
+        .rpc
+        .transaction_simulate_rpc_ids()
+        .filter(|(_, status)| status.is_init())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    for rpc_id in transaction_simulate_rpcs {
+        store.dispatch(RpcAction::TransactionSimulateLedgerInit { rpc_id });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
+
+    let zkapp_events_rpcs = store
+        .state()
+        .rpc
+        .zkapp_events_rpc_ids()
+        .filter(|(_, status)| status.is_init())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    for rpc_id in zkapp_events_rpcs {
+        store.dispatch(RpcAction::ZkappEventsGetLedgerInit { rpc_id });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
+
+    let archive_rpcs = store
+        .state()
+        .rpc
+        .archive_rpc_ids()
+        .filter(|(_, status)| status.is_init())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+
+    for rpc_id in archive_rpcs {
+        store.dispatch(RpcAction::ArchiveGetLedgerInit { rpc_id });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
+
     let ledger_account_rpc = store
         .state()
         .rpc
         .accounts_request_rpc_ids()
         .filter(|(.., status)| status.is_init())
-        .map(|(id, req, _)| (id, req))
+        .map(|(id, req, ledger_hash, _)| (id, req, ledger_hash))
         .collect::<Vec<_>>();
 
-    for (rpc_id, req) in ledger_account_rpc {
+    for (rpc_id, req, ledger_hash) in ledger_account_rpc {
         store.dispatch(RpcAction::LedgerAccountsGetInit {
             rpc_id,
             account_query: req,
+            ledger_hash,
         });
         if !store.state().ledger.read.is_total_cost_under_limit() {
             return;
         }
     }
+
+    // watched accounts
+    let watched_accounts = store
+        .state()
+        .watched_accounts
+        .iter()
+        .flat_map(|(pub_key, account)| {
+            account.ledger_queries_pending().map(|block| {
+                LedgerReadRequest::WatchedAccount(
+                    block.staged_ledger_hash.clone(),
+                    pub_key.clone(),
+                    block.hash.clone(),
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for request in watched_accounts {
+        store.dispatch(LedgerReadAction::Init { request });
+        if !store.state().ledger.read.is_total_cost_under_limit() {
+            return;
+        }
+    }
 }
 
 fn build_staged_ledger_parts_request(
@@ -341,6 +429,9 @@ fn find_peers_with_ledger_rpc(
                         .map_or(false, |b| {
                             b.blockchain_state.staged_ledger_hash == data.ledger_hash
                         }),
+                    (LedgerReadRequest::GetEpochLedger(h1), P2pRpcRequest::EpochLedger(h2)) => {
+                        h1 == h2
+                    }
                     _ => false,
                 })
                 .map(|(peer_id, rpc_id, _)| (*peer_id, rpc_id, false));
@@ -378,12 +469,12 @@ fn propagate_read_response<S: redux::Service>(
     };
     match (request, response) {
         (
-            LedgerReadRequest::DelegatorTable(ledger_hash, pub_key),
+            LedgerReadRequest::DelegatorTable(ledger_hash, producers),
             LedgerReadResponse::DelegatorTable(table),
         ) => {
             let expected = store.state().block_producer.vrf_delegator_table_inputs();
-            if !expected.map_or(false, |(expected_hash, producer)| {
-                ledger_hash == expected_hash && pub_key == producer
+            if !expected.map_or(false, |(expected_hash, expected_producers)| {
+                ledger_hash == expected_hash && producers.as_slice() == expected_producers
             }) {
                 eprintln!("delegator table unexpected");
                 return;
@@ -393,7 +484,7 @@ fn propagate_read_response<S: redux::Service>(
                 Some(table) => {
                     store.dispatch(
                         BlockProducerVrfEvaluatorAction::FinalizeDelegatorTableConstruction {
-                            delegator_table: table.into(),
+                            delegator_tables: table.into(),
                         },
                     );
                 }
@@ -468,6 +559,17 @@ fn propagate_read_response<S: redux::Service>(
                 }
             }
         }
+        (req, LedgerReadResponse::GetEpochLedger(resp)) => {
+            for (peer_id, id, _) in find_peers_with_ledger_rpc(store.state(), req) {
+                store.dispatch(P2pChannelsRpcAction::ResponseSend {
+                    peer_id,
+                    id,
+                    response: resp
+                        .clone()
+                        .map(|ledger| Box::new(P2pRpcResponse::EpochLedger(ledger))),
+                });
+            }
+        }
         (
             LedgerReadRequest::ScanStateSummary(ledger_hash),
             LedgerReadResponse::ScanStateSummary(scan_state),
@@ -495,5 +597,41 @@ fn propagate_read_response<S: redux::Service>(
                 account_query,
             });
         }
+        (_, LedgerReadResponse::HardForkConfig(rpc_id, config)) => {
+            store.dispatch(RpcAction::HardForkConfigGetSuccess { rpc_id, config });
+        }
+        (
+            LedgerReadRequest::WatchedAccount(_, pub_key, block_hash),
+            LedgerReadResponse::WatchedAccount(account),
+        ) => {
+            let (pub_key, block_hash) = (pub_key.clone(), block_hash.clone());
+            let Some(account) = account else {
+                store.dispatch(WatchedAccountsAction::LedgerInitialStateGetError {
+                    pub_key,
+                    error: WatchedAccountsLedgerInitialStateGetError::LedgerNotFound,
+                });
+                return;
+            };
+            store.dispatch(WatchedAccountsAction::LedgerInitialStateGetSuccess {
+                pub_key: pub_key.clone(),
+                block_hash: block_hash.clone(),
+                data: account.clone(),
+            });
+            store.dispatch(WatchedAccountsAction::BlockLedgerQuerySuccess {
+                pub_key,
+                block_hash,
+                ledger_account: account,
+            });
+        }
+        (_, LedgerReadResponse::WatchedAccount(..)) => unreachable!(),
+        (_, LedgerReadResponse::TransactionSimulate(rpc_id, result)) => {
+            store.dispatch(RpcAction::TransactionSimulateSuccess { rpc_id, result });
+        }
+        (_, LedgerReadResponse::ZkappEvents(rpc_id, result)) => {
+            store.dispatch(RpcAction::ZkappEventsGetSuccess { rpc_id, result });
+        }
+        (_, LedgerReadResponse::ArchiveGet(rpc_id, result)) => {
+            store.dispatch(RpcAction::ArchiveGetSuccess { rpc_id, result });
+        }
     }
 }
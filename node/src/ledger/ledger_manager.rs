@@ -13,7 +13,7 @@ use crate::account::AccountPublicKey;
 use crate::ledger::LedgerAddress;
 use crate::rpc::AccountQuery;
 use crate::transition_frontier::sync::ledger::snarked::TransitionFrontierSyncLedgerSnarkedService;
-use ledger::{Account, AccountId, Mask};
+use ledger::{Account, AccountId, Mask, TokenId};
 use mina_signer::CompressedPubKey;
 
 /// The type enumerating different requests that can be made to the
@@ -134,6 +134,7 @@ impl LedgerRequest {
                     completed_snarks,
                     supercharge_coinbase,
                     transactions_by_fee,
+                    snark_fee_budget,
                 } => {
                     let pred_block_hash = pred_block.hash().clone();
                     let global_slot_since_genesis = global_slot.clone();
@@ -147,6 +148,7 @@ impl LedgerRequest {
                         completed_snarks,
                         supercharge_coinbase,
                         transactions_by_fee,
+                        snark_fee_budget,
                     );
                     LedgerWriteResponse::StagedLedgerDiffCreate {
                         pred_block_hash,
@@ -192,16 +194,22 @@ impl LedgerRequest {
             Self::Read(id, request) => LedgerResponse::Read(
                 id,
                 match request {
-                    LedgerReadRequest::DelegatorTable(ledger_hash, producer) => {
+                    LedgerReadRequest::DelegatorTable(ledger_hash, producers) => {
                         let res = ledger_ctx
                             .producers_with_delegates(&ledger_hash, |pub_key| {
-                                AccountPublicKey::from(pub_key.clone()) == producer
+                                producers.contains(&AccountPublicKey::from(pub_key.clone()))
                             })
-                            .and_then(|list| list.into_iter().next())
-                            .map(|(_, table)| {
-                                table
-                                    .into_iter()
-                                    .map(|(index, pub_key, balance)| (index, (pub_key, balance)))
+                            .map(|list| {
+                                list.into_iter()
+                                    .map(|(producer, table)| {
+                                        let table = table
+                                            .into_iter()
+                                            .map(|(index, pub_key, balance)| {
+                                                (index, (pub_key, balance))
+                                            })
+                                            .collect();
+                                        (producer, table)
+                                    })
                                     .collect()
                             });
 
@@ -226,6 +234,10 @@ impl LedgerRequest {
                         );
                         LedgerReadResponse::GetStagedLedgerAuxAndPendingCoinbases(res)
                     }
+                    LedgerReadRequest::GetEpochLedger(ledger_hash) => {
+                        let res = ledger_ctx.get_epoch_ledger(ledger_hash);
+                        LedgerReadResponse::GetEpochLedger(res)
+                    }
                     LedgerReadRequest::ScanStateSummary(ledger_hash) => {
                         let res = ledger_ctx.scan_state_summary(&ledger_hash);
                         LedgerReadResponse::ScanStateSummary(res)
@@ -237,19 +249,43 @@ impl LedgerRequest {
                     LedgerReadRequest::AccountsForRpc(rpc_id, ledger_hash, account_query) => {
                         let res = match &account_query {
                             AccountQuery::SinglePublicKey(public_key) => ledger_ctx
-                                .get_accounts_for_rpc(ledger_hash, Some(public_key.clone())),
-                            AccountQuery::All => ledger_ctx.get_accounts_for_rpc(ledger_hash, None),
+                                .get_account_for_rpc(&ledger_hash, public_key, TokenId::default()),
                             AccountQuery::PubKeyWithTokenId(public_key, token_id_key_hash) => {
-                                let id = AccountId {
-                                    public_key: public_key.clone().try_into().unwrap(),
-                                    token_id: token_id_key_hash.clone().into(),
-                                };
-                                ledger_ctx.get_accounts(ledger_hash, vec![id])
+                                ledger_ctx.get_account_for_rpc(
+                                    &ledger_hash,
+                                    public_key,
+                                    token_id_key_hash.clone().into(),
+                                )
+                            }
+                            AccountQuery::TokenOwner(token_id_key_hash) => ledger_ctx
+                                .get_token_owner(&ledger_hash, token_id_key_hash.clone().into()),
+                            AccountQuery::All | AccountQuery::Ledger => {
+                                ledger_ctx.get_all_accounts(&ledger_hash)
                             }
                         };
 
                         LedgerReadResponse::AccountsForRpc(rpc_id, res, account_query)
                     }
+                    LedgerReadRequest::HardForkConfig(rpc_id, block) => {
+                        let res = ledger_ctx.hard_fork_config(&block);
+                        LedgerReadResponse::HardForkConfig(rpc_id, res)
+                    }
+                    LedgerReadRequest::WatchedAccount(ledger_hash, public_key, _) => {
+                        let res = ledger_ctx.get_default_token_account(ledger_hash, &public_key);
+                        LedgerReadResponse::WatchedAccount(res)
+                    }
+                    LedgerReadRequest::TransactionSimulate(rpc_id, block, command) => {
+                        let res = ledger_ctx.simulate_transaction(&block, &command);
+                        LedgerReadResponse::TransactionSimulate(rpc_id, res)
+                    }
+                    LedgerReadRequest::ZkappEvents(rpc_id, query, best_chain) => {
+                        let res = ledger_ctx.zkapp_events(&query, &best_chain);
+                        LedgerReadResponse::ZkappEvents(rpc_id, res)
+                    }
+                    LedgerReadRequest::ArchiveGet(rpc_id, query) => {
+                        let res = ledger_ctx.archive_get(&query);
+                        LedgerReadResponse::ArchiveGet(rpc_id, res)
+                    }
                 },
             ),
             LedgerRequest::AccountsSet {
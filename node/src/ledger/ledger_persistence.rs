@@ -0,0 +1,411 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+    sync::Arc,
+};
+
+use ledger::{ondisk, Account, BaseLedger, Database, Mask};
+use mina_p2p_messages::{
+    binprot::{
+        self,
+        macros::{BinProtRead, BinProtWrite},
+    },
+    v2::{self, LedgerHash, MinaStateProtocolStateValueStableV2, StateHash},
+};
+use openmina_core::block::{AppliedBlock, ArcBlockWithHash};
+
+use crate::p2p::channels::rpc::StagedLedgerAuxAndPendingCoinbases;
+
+/// A checkpoint is written every `CHECKPOINT_INTERVAL` commits of the
+/// transition frontier, so that we don't dump the whole root snarked
+/// ledger on every new block.
+const CHECKPOINT_INTERVAL: u32 = 10;
+
+const FRONTIER_KEY: &[u8] = b"frontier";
+
+fn block_key(hash: &StateHash) -> Box<[u8]> {
+    format!("block/{hash}").into_bytes().into()
+}
+
+fn snarked_ledger_key(hash: &LedgerHash) -> Box<[u8]> {
+    format!("snarked_ledger/{hash}").into_bytes().into()
+}
+
+pub(super) fn encode<T: binprot::BinProtWrite>(value: &T) -> std::io::Result<Box<[u8]>> {
+    let mut buf = Vec::new();
+    value.binprot_write(&mut buf)?;
+    Ok(buf.into())
+}
+
+pub(super) fn decode<T: binprot::BinProtRead>(bytes: &[u8]) -> std::io::Result<T> {
+    let mut bytes = bytes;
+    T::binprot_read(&mut bytes)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{e:?}")))
+}
+
+#[derive(BinProtRead, BinProtWrite)]
+struct PersistedBlock {
+    block: v2::MinaBlockBlockStableV2,
+    just_emitted_a_proof: bool,
+}
+
+/// Everything needed to rebuild the transition frontier, except for
+/// the blocks and snarked ledgers, which are stored under their own keys.
+#[derive(BinProtRead, BinProtWrite)]
+struct FrontierCheckpoint {
+    /// Hashes of the blocks from the root to the best tip.
+    best_chain: Vec<StateHash>,
+    /// Snarked ledgers required by the root and the best tip.
+    snarked_ledgers: Vec<LedgerHash>,
+    /// Parts used to reconstruct the root staged ledger on top of
+    /// the root snarked ledger.
+    root_staged_ledger: StagedLedgerAuxAndPendingCoinbases,
+}
+
+/// Transition frontier restored from disk.
+pub struct PersistedFrontier {
+    pub best_chain: Vec<AppliedBlock>,
+    pub snarked_ledgers: BTreeMap<LedgerHash, Mask>,
+    pub root_staged_ledger: Arc<StagedLedgerAuxAndPendingCoinbases>,
+}
+
+impl PersistedFrontier {
+    /// Protocol states needed by the root scan state, in the form kept by
+    /// [`crate::transition_frontier::TransitionFrontierState`].
+    pub fn needed_protocol_states(
+        &self,
+    ) -> BTreeMap<StateHash, MinaStateProtocolStateValueStableV2> {
+        self.root_staged_ledger
+            .needed_blocks
+            .iter()
+            .filter_map(|state| Some((state.try_hash().ok()?, state.clone())))
+            .collect()
+    }
+}
+
+/// Best chain restored from disk, used as the initial transition frontier.
+pub struct RestoredFrontier {
+    pub best_chain: Vec<AppliedBlock>,
+    pub needed_protocol_states: BTreeMap<StateHash, MinaStateProtocolStateValueStableV2>,
+}
+
+/// Keeps the transition frontier and the ledgers backing it on disk, so
+/// that the node can resume from them after a restart instead of syncing
+/// everything again from peers.
+pub struct LedgerPersistence {
+    db: ondisk::Database,
+    /// Applied blocks that may end up in the persisted best chain.
+    blocks: BTreeMap<StateHash, AppliedBlock>,
+    /// Protocol states (not in `blocks`) needed by the root scan state.
+    protocol_states: BTreeMap<StateHash, MinaStateProtocolStateValueStableV2>,
+    persisted_blocks: BTreeSet<StateHash>,
+    persisted_snarked_ledgers: BTreeSet<LedgerHash>,
+    has_checkpoint: bool,
+    commits_since_checkpoint: u32,
+}
+
+impl LedgerPersistence {
+    pub fn open<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let mut db = ondisk::Database::create(path)?;
+        // Reclaim space used by entries that were overwritten or removed
+        // during the previous run.
+        db.gc()?;
+
+        Ok(Self {
+            db,
+            blocks: Default::default(),
+            protocol_states: Default::default(),
+            persisted_blocks: Default::default(),
+            persisted_snarked_ledgers: Default::default(),
+            has_checkpoint: false,
+            commits_since_checkpoint: 0,
+        })
+    }
+
+    /// Loads the last checkpoint, if there is one.
+    pub fn load(&mut self) -> std::io::Result<Option<PersistedFrontier>> {
+        let Some(checkpoint) = self.db.get(FRONTIER_KEY)? else {
+            return Ok(None);
+        };
+        let checkpoint: FrontierCheckpoint = decode(&checkpoint)?;
+
+        let mut best_chain = Vec::with_capacity(checkpoint.best_chain.len());
+        for hash in &checkpoint.best_chain {
+            let bytes = self.db.get(&block_key(hash))?.ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("persisted block missing: {hash}"),
+                )
+            })?;
+            let PersistedBlock {
+                block,
+                just_emitted_a_proof,
+            } = decode(&bytes)?;
+            let block = ArcBlockWithHash::try_new(Arc::new(block)).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{e:?}"))
+            })?;
+            best_chain.push(AppliedBlock {
+                block,
+                just_emitted_a_proof,
+            });
+        }
+
+        let mut snarked_ledgers = BTreeMap::new();
+        for hash in &checkpoint.snarked_ledgers {
+            let bytes = self.db.get(&snarked_ledger_key(hash))?.ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("persisted snarked ledger missing: {hash}"),
+                )
+            })?;
+            let accounts: Vec<Account> = decode(&bytes)?;
+            let mut mask = Mask::new_root(Database::create(35));
+            for account in accounts {
+                let account_id = account.id();
+                mask.get_or_create_account(account_id, account)
+                    .map_err(|e| {
+                        std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            format!("failed to restore snarked ledger {hash}: {e:?}"),
+                        )
+                    })?;
+            }
+            let restored_hash = LedgerHash::from_fp(mask.merkle_root());
+            if &restored_hash != hash {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("restored snarked ledger {hash} has hash {restored_hash}"),
+                ));
+            }
+            snarked_ledgers.insert(hash.clone(), mask);
+        }
+
+        self.has_checkpoint = true;
+        self.persisted_blocks = checkpoint.best_chain.iter().cloned().collect();
+        self.persisted_snarked_ledgers = checkpoint.snarked_ledgers.iter().cloned().collect();
+        self.blocks = best_chain
+            .iter()
+            .map(|block| (block.hash().clone(), block.clone()))
+            .collect();
+
+        let frontier = PersistedFrontier {
+            best_chain,
+            snarked_ledgers,
+            root_staged_ledger: checkpoint.root_staged_ledger.into(),
+        };
+        self.protocol_states = frontier.needed_protocol_states();
+
+        Ok(Some(frontier))
+    }
+
+    pub fn block_applied(&mut self, block: AppliedBlock) {
+        self.blocks.insert(block.hash().clone(), block);
+    }
+
+    pub fn protocol_states_received<'a, I>(&mut self, states: I)
+    where
+        I: IntoIterator<Item = &'a MinaStateProtocolStateValueStableV2>,
+    {
+        for state in states {
+            if let Ok(hash) = state.try_hash() {
+                self.protocol_states.insert(hash, state.clone());
+            }
+        }
+    }
+
+    /// Looks up a protocol state among the known blocks and protocol states.
+    fn protocol_state(&self, hash: &StateHash) -> Option<MinaStateProtocolStateValueStableV2> {
+        self.blocks
+            .get(hash)
+            .map(|block| block.header().protocol_state.clone())
+            .or_else(|| self.protocol_states.get(hash).cloned())
+    }
+
+    /// Protocol states needed to reconstruct the root staged ledger.
+    pub fn needed_protocol_states(
+        &self,
+        needed: &BTreeSet<StateHash>,
+    ) -> BTreeMap<StateHash, MinaStateProtocolStateValueStableV2> {
+        needed
+            .iter()
+            .filter_map(|hash| Some((hash.clone(), self.protocol_state(hash)?)))
+            .collect()
+    }
+
+    /// Called after the transition frontier was committed. Drops blocks and
+    /// protocol states that aren't needed anymore and returns `true` if a new
+    /// checkpoint must be written.
+    pub fn committed(
+        &mut self,
+        new_root: &AppliedBlock,
+        new_best_tip: &AppliedBlock,
+        needed_protocol_states: &BTreeSet<StateHash>,
+    ) -> bool {
+        for block in [new_root, new_best_tip] {
+            self.blocks.insert(block.hash().clone(), block.clone());
+        }
+
+        self.protocol_states = self.needed_protocol_states(needed_protocol_states);
+        let root_height = new_root.height();
+        self.blocks.retain(|_, block| block.height() >= root_height);
+
+        self.commits_since_checkpoint += 1;
+        if self.has_checkpoint && self.commits_since_checkpoint < CHECKPOINT_INTERVAL {
+            return false;
+        }
+        self.commits_since_checkpoint = 0;
+        true
+    }
+
+    /// Returns the best chain from the root to the best tip, if all of its
+    /// blocks are known.
+    pub fn best_chain(&self, root: &StateHash, best_tip: &StateHash) -> Option<Vec<AppliedBlock>> {
+        let mut chain = vec![self.blocks.get(best_tip)?.clone()];
+        while chain.last()?.hash() != root {
+            let pred_hash = chain.last()?.pred_hash();
+            chain.push(self.blocks.get(pred_hash)?.clone());
+        }
+        chain.reverse();
+        Some(chain)
+    }
+
+    /// Writes a new checkpoint and removes data that only the previous
+    /// one was referring to.
+    pub fn checkpoint(
+        &mut self,
+        best_chain: &[AppliedBlock],
+        snarked_ledgers: BTreeMap<LedgerHash, Mask>,
+        root_staged_ledger: StagedLedgerAuxAndPendingCoinbases,
+    ) -> std::io::Result<()> {
+        let mut to_set = Vec::new();
+
+        for block in best_chain {
+            if !self.persisted_blocks.contains(block.hash()) {
+                let persisted_block = PersistedBlock {
+                    block: (**block.block()).clone(),
+                    just_emitted_a_proof: block.just_emitted_a_proof,
+                };
+                to_set.push((block_key(block.hash()), encode(&persisted_block)?));
+            }
+        }
+
+        for (hash, mask) in &snarked_ledgers {
+            if !self.persisted_snarked_ledgers.contains(hash) {
+                let accounts = mask
+                    .to_list()
+                    .iter()
+                    .map(v2::MinaBaseAccountBinableArgStableV2::from)
+                    .collect::<Vec<_>>();
+                to_set.push((snarked_ledger_key(hash), encode(&accounts)?));
+            }
+        }
+
+        let checkpoint = FrontierCheckpoint {
+            best_chain: best_chain.iter().map(|b| b.hash().clone()).collect(),
+            snarked_ledgers: snarked_ledgers.keys().cloned().collect(),
+            root_staged_ledger,
+        };
+        to_set.push((FRONTIER_KEY.into(), encode(&checkpoint)?));
+
+        let blocks: BTreeSet<_> = checkpoint.best_chain.into_iter().collect();
+        let snarked_ledgers: BTreeSet<_> = checkpoint.snarked_ledgers.into_iter().collect();
+        let to_remove = self
+            .persisted_blocks
+            .difference(&blocks)
+            .map(block_key)
+            .chain(
+                self.persisted_snarked_ledgers
+                    .difference(&snarked_ledgers)
+                    .map(snarked_ledger_key),
+            )
+            .collect::<Vec<_>>();
+        let removed_snarked_ledger = !self.persisted_snarked_ledgers.is_subset(&snarked_ledgers);
+
+        self.db.set_batch(to_set, to_remove)?;
+        self.has_checkpoint = true;
+        self.persisted_blocks = blocks;
+        self.persisted_snarked_ledgers = snarked_ledgers;
+
+        if removed_snarked_ledger {
+            self.db.gc()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::scan_state::{pending_coinbase::PendingCoinbase, scan_state::ScanState};
+    use openmina_core::constants::constraint_constants;
+
+    use super::*;
+
+    fn empty_root_staged_ledger(ledger_hash: &LedgerHash) -> StagedLedgerAuxAndPendingCoinbases {
+        let constraint_constants = constraint_constants();
+        let mut pending_coinbase =
+            PendingCoinbase::create(constraint_constants.pending_coinbase_depth);
+        pending_coinbase.merkle_root();
+        StagedLedgerAuxAndPendingCoinbases {
+            scan_state: (&ScanState::empty(constraint_constants)).into(),
+            staged_ledger_hash: ledger_hash.clone(),
+            pending_coinbase: (&pending_coinbase).into(),
+            needed_blocks: Default::default(),
+        }
+    }
+
+    fn snarked_ledger(num_accounts: usize) -> (LedgerHash, Mask) {
+        let mut mask = Mask::new_root(Database::create(35));
+        for _ in 0..num_accounts {
+            let account = Account::rand();
+            mask.get_or_create_account(account.id(), account).unwrap();
+        }
+        (LedgerHash::from_fp(mask.merkle_root()), mask)
+    }
+
+    #[test]
+    fn test_checkpoint_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let (hash, mask) = snarked_ledger(10);
+
+        let mut persistence = LedgerPersistence::open(dir.path()).unwrap();
+        assert!(persistence.load().unwrap().is_none());
+        persistence
+            .checkpoint(
+                &[],
+                [(hash.clone(), mask.clone())].into(),
+                empty_root_staged_ledger(&hash),
+            )
+            .unwrap();
+        drop(persistence);
+
+        let mut persistence = LedgerPersistence::open(dir.path()).unwrap();
+        let frontier = persistence.load().unwrap().expect("checkpoint missing");
+        assert!(frontier.best_chain.is_empty());
+        assert_eq!(frontier.snarked_ledgers.len(), 1);
+        let restored = &frontier.snarked_ledgers[&hash];
+        assert_eq!(LedgerHash::from_fp(restored.merkle_root()), hash);
+        assert_eq!(restored.num_accounts(), mask.num_accounts());
+    }
+
+    #[test]
+    fn test_load_rejects_mismatching_snarked_ledger() {
+        let dir = tempfile::tempdir().unwrap();
+        let (hash, _) = snarked_ledger(3);
+        let (_, other_mask) = snarked_ledger(3);
+
+        let mut persistence = LedgerPersistence::open(dir.path()).unwrap();
+        persistence
+            .checkpoint(
+                &[],
+                [(hash.clone(), other_mask)].into(),
+                empty_root_staged_ledger(&hash),
+            )
+            .unwrap();
+        drop(persistence);
+
+        let mut persistence = LedgerPersistence::open(dir.path()).unwrap();
+        let err = persistence.load().err().expect("load must fail");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+}
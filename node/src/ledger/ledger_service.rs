@@ -1,11 +1,15 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     path::Path,
     sync::Arc,
 };
 
 use super::{
+    ledger_archive::{ArchiveBlock, LedgerArchive},
     ledger_manager::{LedgerManager, LedgerRequest},
+    ledger_persistence::{LedgerPersistence, PersistedFrontier, RestoredFrontier},
+    ledger_zkapp_events::{zkapp_events_filter_action_states, ZkappBlockEvents},
     write::BlockApplyResult,
 };
 use ark_ff::fields::arithmetic::InvalidBigInt;
@@ -14,10 +18,11 @@ use ledger::{
         currency::Slot,
         scan_state::{AvailableJobMessage, JobValueBase, JobValueMerge, JobValueWithIndex, Pass},
         transaction_logic::{
+            self,
             local_state::LocalState,
             protocol_state::{protocol_state_view, ProtocolStateView},
             transaction_partially_applied::TransactionPartiallyApplied,
-            valid, Transaction,
+            valid, Transaction, UserCommand,
         },
     },
     sparse_ledger::SparseLedger,
@@ -27,7 +32,7 @@ use ledger::{
         validate_block::block_body_hash,
     },
     verifier::Verifier,
-    Account, AccountId, BaseLedger, Database, Mask, UnregisterBehavior,
+    Account, AccountId, BaseLedger, Database, Mask, TokenId, UnregisterBehavior,
 };
 use mina_hasher::Fp;
 use mina_p2p_messages::{
@@ -49,11 +54,18 @@ use openmina_core::{block::AppliedBlock, constants::constraint_constants};
 use mina_signer::CompressedPubKey;
 use openmina_core::block::ArcBlockWithHash;
 
-use crate::block_producer::StagedLedgerDiffCreateOutput;
+use crate::block_producer::{
+    user_command_hash, StagedLedgerDiffCreateOutput, TransactionSelectionRejectReason,
+    TransactionSelectionRejected,
+};
+use crate::daemon_json::DaemonJson;
 use crate::p2p::channels::rpc::StagedLedgerAuxAndPendingCoinbases;
 use crate::rpc::{
-    RpcScanStateSummaryBlockTransaction, RpcScanStateSummaryScanStateJob,
-    RpcScanStateSummaryScanStateJobKind, RpcSnarkPoolJobSnarkWorkDone,
+    RpcAccountDiff, RpcArchiveData, RpcArchiveGetResponse, RpcArchiveQuery,
+    RpcHardForkConfigGetResponse, RpcScanStateSummaryBlockTransaction,
+    RpcScanStateSummaryScanStateJob, RpcScanStateSummaryScanStateJobKind,
+    RpcSnarkPoolJobSnarkWorkDone, RpcTransactionSimulateResponse, RpcTransactionSimulation,
+    RpcZkappBlockEvents, RpcZkappChainStatus, RpcZkappEventsQuery, RpcZkappEventsResponse,
 };
 use crate::transition_frontier::sync::{
     ledger::staged::StagedLedgerAuxAndPendingCoinbasesValid,
@@ -68,10 +80,18 @@ use super::{
     LedgerAddress, LedgerEvent, LEDGER_DEPTH,
 };
 use super::{
-    read::{LedgerReadId, LedgerReadRequest},
+    read::{HardForkLedgers, LedgerReadId, LedgerReadRequest},
     write::LedgerWriteRequest,
 };
 
+/// Epoch ledgers with more accounts than this aren't served by
+/// `get_epoch_ledger` rpc, as the response wouldn't fit in its size limit.
+const EPOCH_LEDGER_MAX_ACCOUNTS: usize = 1_000_000;
+
+/// Number of epoch ledgers kept in the `get_epoch_ledger` rpc cache, as only
+/// the staking and the next epoch ledgers are served.
+const EPOCH_LEDGERS_CACHE_SIZE: usize = 2;
+
 fn merkle_root(mask: &mut Mask) -> LedgerHash {
     MinaBaseLedgerHash0StableV1(mask.merkle_root().into()).into()
 }
@@ -173,6 +193,14 @@ pub struct LedgerCtx {
     additional_snarked_ledgers: BTreeMap<LedgerHash, Mask>,
     staged_ledgers: StagedLedgersStorage,
     sync: LedgerSyncState,
+    /// On-disk persistence of the transition frontier, if enabled
+    persistence: Option<LedgerPersistence>,
+    /// Archive of the best chain blocks, if enabled
+    archive: Option<LedgerArchive>,
+    /// zkApp events and actions of the applied blocks above the root
+    zkapp_events: BTreeMap<StateHash, ZkappBlockEvents>,
+    /// Epoch ledgers served by `get_epoch_ledger` rpc, most recent last
+    epoch_ledgers: VecDeque<(LedgerHash, Arc<v2::MinaBaseSparseLedgerBaseStableV2>)>,
     event_sender:
         Option<openmina_core::channels::mpsc::UnboundedSender<crate::event_source::Event>>,
 }
@@ -218,6 +246,92 @@ impl LedgerCtx {
         }
     }
 
+    /// Enables persistence of the transition frontier under `path` and
+    /// restores the ledgers of the previously persisted one, if there is any.
+    ///
+    /// Returns the restored best chain, to be used as the initial transition frontier.
+    pub fn persistence_init<P>(&mut self, path: P) -> Result<Option<RestoredFrontier>, String>
+    where
+        P: AsRef<Path>,
+    {
+        let mut persistence = LedgerPersistence::open(path).map_err(|e| e.to_string())?;
+        let frontier = persistence.load().map_err(|e| e.to_string())?;
+        self.persistence = Some(persistence);
+
+        let Some(frontier) = frontier else {
+            return Ok(None);
+        };
+        let needed_protocol_states = frontier.needed_protocol_states();
+        match self.restore_frontier(frontier) {
+            Ok(best_chain) => Ok(Some(RestoredFrontier {
+                best_chain,
+                needed_protocol_states,
+            })),
+            Err(error) => {
+                self.snarked_ledgers.clear();
+                self.staged_ledgers.take();
+                self.sync = Default::default();
+                Err(error)
+            }
+        }
+    }
+
+    /// Enables archiving of the blocks that make it to the best chain
+    /// into the database under `path`.
+    pub fn archive_init<P>(&mut self, path: P) -> Result<(), String>
+    where
+        P: AsRef<Path>,
+    {
+        self.archive = Some(LedgerArchive::open(path).map_err(|e| e.to_string())?);
+        Ok(())
+    }
+
+    /// Rebuilds the staged ledgers of the persisted best chain, by
+    /// reconstructing the root staged ledger and applying the blocks on top.
+    fn restore_frontier(
+        &mut self,
+        frontier: PersistedFrontier,
+    ) -> Result<Vec<AppliedBlock>, String> {
+        let PersistedFrontier {
+            best_chain,
+            snarked_ledgers,
+            root_staged_ledger,
+        } = frontier;
+        let root = best_chain
+            .first()
+            .ok_or_else(|| "persisted best chain is empty".to_owned())?;
+        self.snarked_ledgers.extend(snarked_ledgers);
+
+        let snarked_ledger_hash = root.snarked_ledger_hash().clone();
+        let snarked_ledger = self
+            .snarked_ledgers
+            .get(&snarked_ledger_hash)
+            .ok_or_else(|| format!("persisted root snarked ledger missing: {snarked_ledger_hash}"))?
+            .copy();
+        let (_, staged_ledger) = staged_ledger_reconstruct(
+            snarked_ledger,
+            snarked_ledger_hash,
+            Some(root_staged_ledger),
+        )
+        .map_err(error_to_string)?;
+        self.staged_ledger_reconstruct_result_store(staged_ledger?);
+
+        for blocks in best_chain.windows(2) {
+            let [pred_block, block] = blocks else {
+                unreachable!()
+            };
+            self.block_apply(
+                block.block_with_hash().clone(),
+                pred_block.clone(),
+                Some(SkipVerification::All),
+            )?;
+        }
+        let staged_ledgers = self.sync.staged_ledgers.take();
+        self.staged_ledgers.extend(staged_ledgers);
+
+        Ok(best_chain)
+    }
+
     // TODO(tizoc): Only used for the current workaround to make staged ledger
     // reconstruction async, can be removed when the ledger services are made async
     pub fn set_event_sender(
@@ -257,33 +371,56 @@ impl LedgerCtx {
         self.staged_ledgers.insert_by_recomputing_hash(ledger);
     }
 
-    // TODO(adonagy): Uh-oh, clean this up
-    pub fn get_accounts_for_rpc(
+    /// Account of the public key with the token, as a list of at most one
+    /// account. `None` if the ledger isn't found.
+    pub fn get_account_for_rpc(
         &self,
-        ledger_hash: LedgerHash,
-        requested_public_key: Option<AccountPublicKey>,
-    ) -> Vec<Account> {
-        if let Some((mask, _)) = self.mask(&ledger_hash) {
-            let mut accounts = Vec::new();
-            let mut single_account = Vec::new();
-
-            mask.iter(|account| {
-                accounts.push(account.clone());
-                if let Some(public_key) = requested_public_key.as_ref() {
-                    if public_key == &AccountPublicKey::from(account.public_key.clone()) {
-                        single_account.push(account.clone());
-                    }
-                }
-            });
+        ledger_hash: &LedgerHash,
+        public_key: &AccountPublicKey,
+        token_id: TokenId,
+    ) -> Option<Vec<Account>> {
+        let (mask, _) = self.mask(ledger_hash)?;
+        let Ok(public_key) = CompressedPubKey::try_from(public_key.clone()) else {
+            // not a valid point on the curve, can't be in the ledger
+            return Some(Vec::new());
+        };
+        let id = AccountId::new(public_key, token_id);
+        let account = mask
+            .location_of_account(&id)
+            .and_then(|addr| mask.get(addr));
+        Some(account.map(|account| *account).into_iter().collect())
+    }
 
-            if requested_public_key.is_some() {
-                single_account
-            } else {
-                accounts
-            }
-        } else {
-            vec![]
-        }
+    /// All the accounts of the ledger, in the ledger order.
+    pub fn get_all_accounts(&self, ledger_hash: &LedgerHash) -> Option<Vec<Account>> {
+        let (mask, _) = self.mask(ledger_hash)?;
+        Some(mask.fold(Vec::new(), |mut accounts, account| {
+            accounts.push(account.clone());
+            accounts
+        }))
+    }
+
+    /// Config of the network hard forked at the block, as JSON. The
+    /// accounts are written from the ledgers of the block as they are
+    /// visited.
+    pub fn hard_fork_config(&self, block: &ArcBlockWithHash) -> RpcHardForkConfigGetResponse {
+        let mask = |hash: &LedgerHash| self.mask(hash).map(|(mask, _)| mask);
+        let (Some(staged_ledger), Some(staking_epoch_ledger), Some(next_epoch_ledger)) = (
+            mask(block.merkle_root_hash()),
+            mask(block.staking_epoch_ledger_hash()),
+            mask(block.next_epoch_ledger_hash()),
+        ) else {
+            return Err("ledgers of the target block are not available".to_owned());
+        };
+        let ledgers = HardForkLedgers {
+            staged_ledger: &staged_ledger,
+            staking_epoch_ledger: &staking_epoch_ledger,
+            next_epoch_ledger: &next_epoch_ledger,
+        };
+
+        let mut config = Vec::new();
+        DaemonJson::hard_fork(&mut config, block, ledgers).map_err(|err| err.to_string())?;
+        String::from_utf8(config).map_err(|err| err.to_string())
     }
 
     // TODO(tizoc): explain when `is_synced` is `true` and when it is `false`. Also use something else than a boolean.
@@ -596,6 +733,7 @@ impl LedgerCtx {
             .sync
             .snarked_ledger_mut(snarked_ledger_hash.clone())?
             .copy();
+        self.persistence_protocol_states_received(&parts);
 
         thread::Builder::new()
             .name("staged-ledger-reconstruct".into())
@@ -621,6 +759,7 @@ impl LedgerCtx {
             .sync
             .snarked_ledger_mut(snarked_ledger_hash.clone())?
             .copy();
+        self.persistence_protocol_states_received(&parts);
         let (staged_ledger_hash, result) =
             staged_ledger_reconstruct(snarked_ledger, snarked_ledger_hash, parts)?;
         let result = match result {
@@ -634,6 +773,15 @@ impl LedgerCtx {
         Ok((staged_ledger_hash, result))
     }
 
+    fn persistence_protocol_states_received(
+        &mut self,
+        parts: &Option<Arc<StagedLedgerAuxAndPendingCoinbasesValid>>,
+    ) {
+        if let (Some(persistence), Some(parts)) = (self.persistence.as_mut(), parts) {
+            persistence.protocol_states_received(parts.needed_blocks.iter());
+        }
+    }
+
     pub fn block_apply(
         &mut self,
         block: ArcBlockWithHash,
@@ -673,6 +821,17 @@ impl LedgerCtx {
         let prev_protocol_state: ledger::proofs::block::ProtocolState =
             prev_protocol_state.try_into()?;
 
+        // action states are computed from the ledger before the block is applied.
+        let zkapp_events = ZkappBlockEvents::new(&block, &staged_ledger.ledger());
+
+        let archive_transactions = self.archive.is_some().then(|| {
+            diff.clone().get_transactions(
+                constraint_constants(),
+                coinbase_receiver.clone(),
+                supercharge_coinbase,
+            )
+        });
+
         let result = staged_ledger
             .apply(
                 skip_verification,
@@ -713,10 +872,33 @@ impl LedgerCtx {
             panic!("staged ledger hash mismatch. found: {ledger_hashes:#?}, expected: {expected_ledger_hashes:#?}");
         }
 
+        if let Some(events) = zkapp_events {
+            self.zkapp_events.insert(block.hash().clone(), events);
+        }
+
+        match (self.archive.as_mut(), archive_transactions) {
+            (Some(archive), Some(Ok(transactions))) => archive.block_applied(ArchiveBlock::new(
+                &block,
+                transactions,
+                &staged_ledger.ledger(),
+            )),
+            (_, Some(Err(error))) => openmina_core::error!(openmina_core::log::system_time();
+                kind = "LedgerService::archive",
+                summary = format!("failed to get transactions of block {}: {error:?}", block.hash())),
+            _ => {}
+        }
+
         self.sync
             .staged_ledgers
             .insert(Arc::new(ledger_hashes), staged_ledger);
 
+        if let Some(persistence) = self.persistence.as_mut() {
+            persistence.block_applied(AppliedBlock {
+                block,
+                just_emitted_a_proof,
+            });
+        }
+
         Ok(BlockApplyResult {
             just_emitted_a_proof,
         })
@@ -727,8 +909,8 @@ impl LedgerCtx {
         ledgers_to_keep: BTreeSet<LedgerHash>,
         root_snarked_ledger_updates: TransitionFrontierRootSnarkedLedgerUpdates,
         needed_protocol_states: BTreeMap<StateHash, MinaStateProtocolStateValueStableV2>,
-        new_root: &ArcBlockWithHash,
-        new_best_tip: &ArcBlockWithHash,
+        new_root: &AppliedBlock,
+        new_best_tip: &AppliedBlock,
     ) -> CommitResult {
         openmina_core::debug!(openmina_core::log::system_time();
             kind = "LedgerService::commit",
@@ -738,6 +920,9 @@ impl LedgerCtx {
             new_root_next_epoch_ledger = new_root.next_epoch_ledger_hash().to_string(),
             new_root_snarked_ledger = new_root.snarked_ledger_hash().to_string(),
         );
+        if let Some(persistence) = self.persistence.as_mut() {
+            persistence.protocol_states_received(needed_protocol_states.values());
+        }
         self.recreate_snarked_ledger(
             &root_snarked_ledger_updates,
             &needed_protocol_states,
@@ -839,12 +1024,104 @@ impl LedgerCtx {
             })
             .unwrap_or_default();
 
+        self.persist_frontier(new_root, new_best_tip, &needed_protocol_states);
+
+        if let Some(archive) = self.archive.as_mut() {
+            if let Err(error) =
+                archive.best_tip_changed(new_best_tip.hash(), new_root.height(), &self.zkapp_events)
+            {
+                openmina_core::error!(openmina_core::log::system_time();
+                    kind = "LedgerService::archive",
+                    summary = format!("failed to archive best tip {}: {error}", new_best_tip.hash()));
+            }
+        }
+        self.zkapp_events
+            .retain(|_, events| events.height >= new_root.height());
+
         CommitResult {
             available_jobs,
             needed_protocol_states,
         }
     }
 
+    /// Writes a checkpoint of the transition frontier to disk, if
+    /// persistence is enabled and a checkpoint is due.
+    fn persist_frontier(
+        &mut self,
+        new_root: &AppliedBlock,
+        new_best_tip: &AppliedBlock,
+        needed_protocol_states: &BTreeSet<StateHash>,
+    ) {
+        let Some(mut persistence) = self.persistence.take() else {
+            return;
+        };
+        // Genesis staged ledger can't be reconstructed from its parts,
+        // so we only start persisting once the root moved past it.
+        if persistence.committed(new_root, new_best_tip, needed_protocol_states)
+            && !new_root.is_genesis()
+        {
+            if let Err(error) = self.checkpoint_frontier(
+                &mut persistence,
+                new_root,
+                new_best_tip,
+                needed_protocol_states,
+            ) {
+                openmina_core::error!(openmina_core::log::system_time();
+                    kind = "LedgerService::persist_frontier",
+                    summary = format!("failed to persist frontier: {error}"));
+            } else {
+                openmina_core::debug!(openmina_core::log::system_time();
+                    kind = "LedgerService::persist_frontier",
+                    summary = format!("persisted frontier {}, {}", new_best_tip.height(), new_best_tip.hash()));
+            }
+        }
+        self.persistence = Some(persistence);
+    }
+
+    fn checkpoint_frontier(
+        &mut self,
+        persistence: &mut LedgerPersistence,
+        new_root: &AppliedBlock,
+        new_best_tip: &AppliedBlock,
+        needed_protocol_states: &BTreeSet<StateHash>,
+    ) -> Result<(), String> {
+        let best_chain = persistence
+            .best_chain(new_root.hash(), new_best_tip.hash())
+            .ok_or_else(|| "missing blocks of the best chain".to_owned())?;
+
+        let protocol_states = persistence.needed_protocol_states(needed_protocol_states);
+        let root_staged_ledger = self
+            .staged_ledger_aux_and_pending_coinbase(
+                new_root.staged_ledger_hashes(),
+                protocol_states,
+            )
+            .ok_or_else(|| "missing root staged ledger or its protocol states".to_owned())?;
+
+        let snarked_ledgers = [
+            new_root.snarked_ledger_hash(),
+            new_root.staking_epoch_ledger_hash(),
+            new_root.next_epoch_ledger_hash(),
+            new_best_tip.staking_epoch_ledger_hash(),
+            new_best_tip.next_epoch_ledger_hash(),
+        ]
+        .into_iter()
+        .filter_map(|hash| {
+            let (mask, is_synced) = self.mask(hash)?;
+            is_synced.then(|| (hash.clone(), mask))
+        })
+        .collect::<BTreeMap<_, _>>();
+        if !snarked_ledgers.contains_key(new_root.snarked_ledger_hash()) {
+            return Err(format!(
+                "root snarked ledger missing: {}",
+                new_root.snarked_ledger_hash()
+            ));
+        }
+
+        persistence
+            .checkpoint(&best_chain, snarked_ledgers, (*root_staged_ledger).clone())
+            .map_err(|e| e.to_string())
+    }
+
     pub fn get_num_accounts(
         &mut self,
         ledger_hash: v2::LedgerHash,
@@ -901,6 +1178,37 @@ impl LedgerCtx {
         Some(accounts)
     }
 
+    /// Whole epoch ledger as a sparse ledger, as served by `get_epoch_ledger` rpc.
+    ///
+    /// It is built only once per epoch ledger, and not at all if the ledger
+    /// has more than [`EPOCH_LEDGER_MAX_ACCOUNTS`] accounts.
+    pub fn get_epoch_ledger(
+        &mut self,
+        ledger_hash: v2::LedgerHash,
+    ) -> Option<Arc<v2::MinaBaseSparseLedgerBaseStableV2>> {
+        if let Some((_, ledger)) = self.epoch_ledgers.iter().find(|(h, _)| h == &ledger_hash) {
+            return Some(ledger.clone());
+        }
+
+        let (mask, _) = self
+            .mask(&ledger_hash)
+            .filter(|(_, is_synced)| *is_synced)?;
+        if mask.num_accounts() > EPOCH_LEDGER_MAX_ACCOUNTS {
+            return None;
+        }
+        let mut account_ids = Vec::with_capacity(mask.num_accounts());
+        mask.iter(|account| account_ids.push(account.id()));
+        let sparse_ledger = SparseLedger::of_ledger_subset_exn(mask, &account_ids);
+        let sparse_ledger = Arc::new((&sparse_ledger).into());
+
+        if self.epoch_ledgers.len() >= EPOCH_LEDGERS_CACHE_SIZE {
+            self.epoch_ledgers.pop_front();
+        }
+        self.epoch_ledgers
+            .push_back((ledger_hash, sparse_ledger.clone()));
+        Some(sparse_ledger)
+    }
+
     pub fn get_accounts(
         &mut self,
         ledger_hash: v2::LedgerHash,
@@ -926,6 +1234,195 @@ impl LedgerCtx {
             .collect::<Vec<_>>()
     }
 
+    /// Account of the public key with the default token. `None` if the
+    /// ledger isn't found, `Some(None)` if the account isn't in it.
+    pub fn get_default_token_account(
+        &mut self,
+        ledger_hash: v2::LedgerHash,
+        public_key: &NonZeroCurvePoint,
+    ) -> Option<Option<Box<v2::MinaBaseAccountBinableArgStableV2>>> {
+        let (mask, _) = self.mask(&ledger_hash)?;
+        let Ok(public_key) = CompressedPubKey::try_from(public_key) else {
+            // not a valid point on the curve, can't be in the ledger
+            return Some(None);
+        };
+        let id = AccountId::new(public_key, TokenId::default());
+        let account = mask
+            .location_of_account(&id)
+            .and_then(|addr| mask.get(addr));
+        Some(account.map(|account| Box::new((&*account).into())))
+    }
+
+    /// Applies the command on a throwaway child of the staged ledger of
+    /// `pred_block`, as if it was included in the next block. Proofs and
+    /// signatures aren't verified and the ledger is left untouched.
+    pub fn simulate_transaction(
+        &mut self,
+        pred_block: &ArcBlockWithHash,
+        command: &v2::MinaBaseUserCommandStableV2,
+    ) -> RpcTransactionSimulateResponse {
+        let ledger_hash = pred_block.merkle_root_hash();
+        let (ledger, _) = self
+            .mask(ledger_hash)
+            .ok_or_else(|| format!("staged ledger missing: {ledger_hash}"))?;
+        let command = UserCommand::try_from(command).map_err(error_to_string)?;
+        let protocol_state_view =
+            protocol_state_view(&pred_block.header().protocol_state).map_err(error_to_string)?;
+        let global_slot = Slot::from_u32(pred_block.global_slot_since_genesis() + 1);
+
+        let account_ids = command
+            .accounts_referenced()
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let get_account = |mask: &Mask, id: &AccountId| {
+            mask.location_of_account(id)
+                .and_then(|addr| mask.get(addr))
+                .map(|account| *account)
+        };
+        let before = account_ids
+            .iter()
+            .map(|id| get_account(&ledger, id))
+            .collect::<Vec<_>>();
+
+        // Dropping the child detaches it from the staged ledger.
+        let mut mask = ledger.make_child();
+        let fee = command.fee();
+        let applied = transaction_logic::apply_transactions(
+            constraint_constants(),
+            global_slot,
+            &protocol_state_view,
+            &mut mask,
+            &[Transaction::Command(command)],
+        )?;
+        let status = applied
+            .first()
+            .map(|applied| applied.transaction_status().clone())
+            .ok_or_else(|| "command wasn't applied".to_string())?;
+
+        let account_diffs = account_ids
+            .into_iter()
+            .zip(before)
+            .filter_map(|(account_id, before)| {
+                let after = get_account(&mask, &account_id);
+                (before != after).then_some(RpcAccountDiff {
+                    account_id,
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        Ok(RpcTransactionSimulation {
+            block_hash: pred_block.hash().clone(),
+            status,
+            fee,
+            account_diffs,
+        })
+    }
+
+    /// Events and actions of the zkApp account in the blocks of the
+    /// `best_chain`, and in the archived blocks below its root if the
+    /// archive is enabled, oldest first.
+    pub fn zkapp_events(
+        &mut self,
+        query: &RpcZkappEventsQuery,
+        best_chain: &BTreeMap<u32, StateHash>,
+    ) -> RpcZkappEventsResponse {
+        let public_key = CompressedPubKey::try_from(query.public_key.clone())
+            .map_err(|_| format!("invalid public key: {}", query.public_key))?;
+        let token_id = query
+            .token_id
+            .clone()
+            .map_or_else(TokenId::default, TokenId::from);
+        let account_id = AccountId::new(public_key, token_id);
+        let root_height = best_chain.keys().next().copied().unwrap_or_default();
+        let from = query.from.unwrap_or_default();
+        let to = query.to.unwrap_or(u32::MAX);
+        let chain_status = |block: &ZkappBlockEvents| match block.height > root_height {
+            true => RpcZkappChainStatus::Pending,
+            false => RpcZkappChainStatus::Canonical,
+        };
+
+        let mut blocks = Vec::new();
+        if let Some(archive) = self.archive.as_mut() {
+            if from < root_height {
+                blocks = archive
+                    .zkapp_events(&account_id, from..to.min(root_height))
+                    .map_err(|error| format!("failed to read the archive: {error}"))?;
+            }
+        }
+        let start = from.max(root_height);
+        blocks.extend(
+            best_chain
+                .range(start..to.max(start))
+                .filter_map(|(_, hash)| self.zkapp_events.get(hash))
+                .map(|events| events.clone().filter_account(&account_id)),
+        );
+
+        blocks.retain(|block| {
+            !block.account_updates.is_empty()
+                && query
+                    .status
+                    .map_or(true, |status| status == chain_status(block))
+        });
+        zkapp_events_filter_action_states(
+            &mut blocks,
+            query.from_action_state.as_ref(),
+            query.end_action_state.as_ref(),
+        );
+        Ok(blocks
+            .into_iter()
+            .map(|block| RpcZkappBlockEvents {
+                chain_status: chain_status(&block),
+                block,
+            })
+            .collect())
+    }
+
+    /// Answers the query from the archive, fails if it isn't enabled.
+    pub fn archive_get(&mut self, query: &RpcArchiveQuery) -> RpcArchiveGetResponse {
+        let archive = self
+            .archive
+            .as_mut()
+            .ok_or_else(|| "archive is not enabled".to_owned())?;
+        let data = match query {
+            RpcArchiveQuery::BestTip => archive.best_tip().map(RpcArchiveData::Block),
+            RpcArchiveQuery::Block(hash) => archive.block(hash).map(RpcArchiveData::Block),
+            RpcArchiveQuery::BlockAtHeight(height) => {
+                archive.block_at_height(*height).map(RpcArchiveData::Block)
+            }
+            RpcArchiveQuery::TransactionBlock(hash) => {
+                archive.transaction_block(hash).map(RpcArchiveData::Block)
+            }
+            RpcArchiveQuery::AccountHistory(public_key, token_id) => {
+                let public_key = CompressedPubKey::try_from(public_key.clone())
+                    .map_err(|_| format!("invalid public key: {public_key}"))?;
+                let token_id = token_id
+                    .clone()
+                    .map_or_else(TokenId::default, TokenId::from);
+                archive
+                    .account_history(&AccountId::new(public_key, token_id))
+                    .map(RpcArchiveData::AccountHistory)
+            }
+        };
+        data.map_err(|error| format!("failed to read the archive: {error}"))
+    }
+
+    /// Account that owns the token, as a list of at most one account.
+    /// `None` if the ledger isn't found.
+    pub fn get_token_owner(
+        &self,
+        ledger_hash: &LedgerHash,
+        token_id: TokenId,
+    ) -> Option<Vec<Account>> {
+        let (mask, _) = self.mask(ledger_hash)?;
+        let account = mask
+            .token_owner(token_id)
+            .and_then(|id| mask.location_of_account(&id))
+            .and_then(|addr| mask.get(addr));
+        Some(account.map(|account| *account).into_iter().collect())
+    }
+
     pub fn staged_ledger_aux_and_pending_coinbase(
         &mut self,
         ledger_hash: &MinaBaseStagedLedgerHashStableV1,
@@ -966,6 +1463,7 @@ impl LedgerCtx {
         completed_snarks: BTreeMap<SnarkJobId, Snark>,
         supercharge_coinbase: bool,
         transactions_by_fee: Vec<valid::UserCommand>,
+        snark_fee_budget: Option<u64>,
     ) -> Result<StagedLedgerDiffCreateOutput, String> {
         let mut staged_ledger = self
             .staged_ledger_mut(pred_block.staged_ledger_hashes())
@@ -985,8 +1483,14 @@ impl LedgerCtx {
         let protocol_state_view =
             protocol_state_view(&pred_block.header().protocol_state).map_err(error_to_string)?;
 
+        let used_snark_fees = RefCell::new(BTreeMap::<SnarkJobId, u64>::new());
+        let snark_fee_budget_exceeded = Cell::new(false);
+        let transaction_hashes = transactions_by_fee
+            .iter()
+            .filter_map(user_command_hash)
+            .collect::<Vec<_>>();
         // TODO(binier): include `invalid_txns` in output.
-        let (pre_diff, _invalid_txns) = staged_ledger
+        let (pre_diff, _invalid_txns, diff_creation_log) = staged_ledger
             .create_diff(
                 constraint_constants(),
                 (&global_slot_since_genesis).into(),
@@ -997,15 +1501,47 @@ impl LedgerCtx {
                 transactions_by_fee,
                 |stmt| {
                     let job_id = SnarkJobId::from(stmt);
-                    match completed_snarks.get(&job_id) {
-                        Some(snark) => snark.try_into().ok(),
-                        None => None,
+                    let snark = completed_snarks.get(&job_id)?;
+                    if let Some(budget) = snark_fee_budget {
+                        // Work might be requested multiple times while the
+                        // diff is being created, so only charge it once.
+                        let mut used = used_snark_fees.borrow_mut();
+                        if !used.contains_key(&job_id) {
+                            let fee = snark.fee.as_u64();
+                            let spent = used.values().sum::<u64>();
+                            if spent.saturating_add(fee) > budget {
+                                snark_fee_budget_exceeded.set(true);
+                                return None;
+                            }
+                            used.insert(job_id, fee);
+                        }
                     }
+                    snark.try_into().ok()
                 },
                 supercharge_coinbase,
             )
             .map_err(|err| format!("{err:?}"))?;
 
+        // Commands left out of the diff couldn't be included because of the
+        // missing snark work, which was (at least partly) over the budget.
+        let rejected_transactions = if snark_fee_budget_exceeded.get() {
+            let included = pre_diff
+                .commands()
+                .iter()
+                .filter_map(|cmd| user_command_hash(&cmd.data))
+                .collect::<BTreeSet<_>>();
+            transaction_hashes
+                .into_iter()
+                .filter(|hash| !included.contains(hash))
+                .map(|hash| TransactionSelectionRejected {
+                    hash: Some(hash),
+                    reason: TransactionSelectionRejectReason::SnarkFeeBudget,
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
         // TODO(binier): maybe here, check if block reward is above threshold.
         // https://github.com/minaprotocol/mina/blob/b3d418a8c0ae4370738886c2b26f0ec7bdb49303/src/lib/block_producer/block_producer.ml#L222
 
@@ -1056,6 +1592,8 @@ impl LedgerCtx {
             stake_proof_sparse_ledger: self
                 .stake_proof_sparse_ledger(staking_ledger_hash, &producer, &delegator)
                 .map_err(error_to_string)?,
+            diff_creation_log: diff_creation_log.iter().map(Into::into).collect(),
+            rejected_transactions,
         })
     }
 
@@ -1447,9 +1985,22 @@ fn dump_application_to_file(
 
 #[cfg(test)]
 mod tests {
-    use mina_p2p_messages::v2::MinaBaseLedgerHash0StableV1;
+    use ledger::scan_state::{
+        currency::{Amount, Balance, Fee, Nonce},
+        transaction_logic::{
+            signed_command::{Body, PaymentPayload, SignedCommand, SignedCommandPayload},
+            Memo, TransactionFailure, TransactionStatus,
+        },
+    };
+    use mina_p2p_messages::{
+        bigint::BigInt,
+        v2::{MinaBaseLedgerHash0StableV1, TransactionHash},
+    };
+    use mina_signer::Signature;
 
-    use crate::ledger::hash_node_at_depth;
+    use crate::account::AccountSecretKey;
+    use crate::ledger::{hash_node_at_depth, ZkappAccountUpdateEvents};
+    use crate::testing::recorded_block;
 
     use super::*;
 
@@ -1476,4 +2027,311 @@ mod tests {
             assert_eq!(hash.to_string(), expected_hash);
         });
     }
+
+    fn ledger_ctx_with_accounts(accounts: &[Account]) -> (LedgerCtx, LedgerHash) {
+        let mut mask = Mask::new_root(Database::create(35));
+        for account in accounts {
+            mask.get_or_create_account(account.id(), account.clone())
+                .unwrap();
+        }
+        let ledger_hash = merkle_root(&mut mask);
+        let mut ctx = LedgerCtx::default();
+        ctx.insert_genesis_ledger(mask);
+        (ctx, ledger_hash)
+    }
+
+    #[test]
+    fn test_get_account_for_rpc() {
+        let account = Account::rand();
+        let other = Account::rand();
+        let (ctx, ledger_hash) = ledger_ctx_with_accounts(&[account.clone(), other]);
+        let public_key = AccountPublicKey::from(account.public_key.clone());
+
+        let found = ctx
+            .get_account_for_rpc(&ledger_hash, &public_key, account.token_id.clone())
+            .unwrap();
+        assert_eq!(found, vec![account.clone()]);
+
+        let missing = Account::rand();
+        let missing_key = AccountPublicKey::from(missing.public_key.clone());
+        let found = ctx
+            .get_account_for_rpc(&ledger_hash, &missing_key, missing.token_id)
+            .unwrap();
+        assert!(found.is_empty());
+
+        let unknown_ledger = LedgerHash::from_fp(Fp::from(1u64));
+        assert!(ctx
+            .get_account_for_rpc(&unknown_ledger, &public_key, account.token_id)
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_token_owner_unknown_ledger() {
+        let account = Account::rand();
+        let (ctx, ledger_hash) = ledger_ctx_with_accounts(&[account.clone()]);
+
+        let not_owned = Account::rand().id().derive_token_id();
+        let owner = ctx
+            .get_token_owner(&ledger_hash, not_owned.clone())
+            .unwrap();
+        assert!(owner.is_empty());
+
+        let unknown_ledger = LedgerHash::from_fp(Fp::from(1u64));
+        assert!(ctx.get_token_owner(&unknown_ledger, not_owned).is_none());
+    }
+
+    fn account(i: u64, balance: u64) -> Account {
+        let public_key = AccountSecretKey::deterministic(i).public_key_compressed();
+        Account::create_with(
+            AccountId::new(public_key, TokenId::default()),
+            Balance::from_u64(balance),
+        )
+    }
+
+    /// Recorded block with its staged ledger replaced by `ledger_hash`.
+    fn block_with_ledger(ledger_hash: &LedgerHash) -> ArcBlockWithHash {
+        let mut block = recorded_block();
+        let staged_ledger_hash = &mut block
+            .header
+            .protocol_state
+            .body
+            .blockchain_state
+            .staged_ledger_hash;
+        staged_ledger_hash.non_snark.ledger_hash = ledger_hash.clone();
+        ArcBlockWithHash::try_new(Arc::new(block)).unwrap()
+    }
+
+    fn payment(
+        sender: &Account,
+        receiver: &CompressedPubKey,
+        amount: u64,
+        fee: u64,
+    ) -> v2::MinaBaseUserCommandStableV2 {
+        let payload = SignedCommandPayload::create(
+            Fee::from_u64(fee),
+            sender.public_key.clone(),
+            sender.nonce,
+            None,
+            Memo::empty(),
+            Body::Payment(PaymentPayload {
+                receiver_pk: receiver.clone(),
+                amount: Amount::from_u64(amount),
+            }),
+        );
+        let command = SignedCommand {
+            payload,
+            signer: sender.public_key.clone(),
+            signature: Signature::dummy(),
+        };
+        v2::MinaBaseUserCommandStableV2::SignedCommand(command.into())
+    }
+
+    fn ledger_account(ctx: &LedgerCtx, ledger_hash: &LedgerHash, account: &Account) -> Account {
+        let (mask, _) = ctx.mask(ledger_hash).unwrap();
+        let addr = mask.location_of_account(&account.id()).unwrap();
+        *mask.get(addr).unwrap()
+    }
+
+    #[test]
+    fn test_simulate_transaction() {
+        const MINA: u64 = 1_000_000_000;
+        let sender = account(0, 10 * MINA);
+        let receiver = account(1, 10 * MINA);
+        let (mut ctx, ledger_hash) = ledger_ctx_with_accounts(&[sender.clone(), receiver.clone()]);
+        let pred_block = block_with_ledger(&ledger_hash);
+        let fee = MINA / 100;
+
+        // Successful payment.
+        let command = payment(&sender, &receiver.public_key, MINA, fee);
+        let simulation = ctx.simulate_transaction(&pred_block, &command).unwrap();
+        assert_eq!(&simulation.block_hash, pred_block.hash());
+        assert_eq!(simulation.status, TransactionStatus::Applied);
+        assert_eq!(simulation.fee, Fee::from_u64(fee));
+
+        let diffs = &simulation.account_diffs;
+        assert_eq!(diffs.len(), 2);
+        let diff = |id: AccountId| diffs.iter().find(|diff| diff.account_id == id).unwrap();
+        let sender_diff = diff(sender.id());
+        assert_eq!(sender_diff.before.as_ref(), Some(&sender));
+        let after = sender_diff.after.as_ref().unwrap();
+        assert_eq!(after.balance, Balance::from_u64(10 * MINA - MINA - fee));
+        assert_eq!(after.nonce, Nonce::from_u32(1));
+        let receiver_diff = diff(receiver.id());
+        assert_eq!(receiver_diff.before.as_ref(), Some(&receiver));
+        let after = receiver_diff.after.as_ref().unwrap();
+        assert_eq!(after.balance, Balance::from_u64(10 * MINA + MINA));
+        assert_eq!(after.nonce, receiver.nonce);
+
+        // Failing payment: the amount doesn't cover the account creation
+        // fee of the new receiver, yet the fee is still charged.
+        let new_receiver = AccountSecretKey::deterministic(2).public_key_compressed();
+        let command = payment(&sender, &new_receiver, 1, fee);
+        let simulation = ctx.simulate_transaction(&pred_block, &command).unwrap();
+        assert_eq!(
+            simulation.status,
+            TransactionStatus::Failed(vec![vec![
+                TransactionFailure::AmountInsufficientToCreateAccount
+            ]])
+        );
+        let [sender_diff] = simulation.account_diffs.as_slice() else {
+            panic!("expected only the fee payer to change");
+        };
+        assert_eq!(sender_diff.account_id, sender.id());
+        assert_eq!(sender_diff.before.as_ref(), Some(&sender));
+        let after = sender_diff.after.as_ref().unwrap();
+        assert_eq!(after.balance, Balance::from_u64(10 * MINA - fee));
+        assert_eq!(after.nonce, Nonce::from_u32(1));
+
+        // Rejected payment: the sender can't cover the amount.
+        let command = payment(&sender, &receiver.public_key, 100 * MINA, fee);
+        assert!(ctx.simulate_transaction(&pred_block, &command).is_err());
+
+        // None of the simulations touched the staged ledger.
+        let (mut mask, _) = ctx.mask(&ledger_hash).unwrap();
+        assert_eq!(merkle_root(&mut mask), ledger_hash);
+        assert_eq!(ledger_account(&ctx, &ledger_hash, &sender), sender);
+        assert_eq!(ledger_account(&ctx, &ledger_hash, &receiver), receiver);
+        assert!(mask
+            .location_of_account(&AccountId::new(new_receiver, TokenId::default()))
+            .is_none());
+    }
+
+    fn zkapp_account_update(account_id: &AccountId, action_state: u64) -> ZkappAccountUpdateEvents {
+        ZkappAccountUpdateEvents {
+            public_key: (&account_id.public_key).into(),
+            token_id: (&account_id.token_id).into(),
+            transaction_hash: TransactionHash::from(&[0; 32]),
+            transaction_status: v2::MinaBaseTransactionStatusStableV2::Applied,
+            memo: (&Memo::empty()).into(),
+            sequence_number: 0,
+            account_update_id: 1,
+            events: Vec::new(),
+            actions: vec![vec![Fp::from(action_state).into()]],
+            action_state: Some((
+                Fp::from(action_state).into(),
+                Fp::from(action_state + 1).into(),
+            )),
+        }
+    }
+
+    /// Context with the zkApp events of a best chain of 4 blocks rooted at
+    /// height 1, moving the action state of `zkapp` from `height` to
+    /// `height + 1`. The block at height 2 has events of `other` too.
+    fn ledger_ctx_with_zkapp_events(
+        zkapp: &AccountId,
+        other: &AccountId,
+    ) -> (LedgerCtx, BTreeMap<u32, StateHash>) {
+        let (mut ctx, _) = ledger_ctx_with_accounts(&[]);
+        let mut best_chain = BTreeMap::new();
+        for height in 1..=4 {
+            let hash = StateHash::from_fp(Fp::from(height as u64));
+            let mut account_updates = vec![zkapp_account_update(zkapp, height as u64)];
+            if height == 2 {
+                account_updates.push(zkapp_account_update(other, 100));
+            }
+            let events = ZkappBlockEvents {
+                hash: hash.clone(),
+                pred_hash: StateHash::from_fp(Fp::from(height as u64 - 1)),
+                height,
+                global_slot_since_genesis: height,
+                timestamp: 0,
+                account_updates,
+            };
+            ctx.zkapp_events.insert(hash.clone(), events);
+            best_chain.insert(height, hash);
+        }
+        (ctx, best_chain)
+    }
+
+    fn zkapp_events_query(account_id: &AccountId) -> RpcZkappEventsQuery {
+        RpcZkappEventsQuery {
+            public_key: account_id.public_key.clone().into(),
+            token_id: None,
+            status: None,
+            from: None,
+            to: None,
+            from_action_state: None,
+            end_action_state: None,
+        }
+    }
+
+    #[test]
+    fn test_zkapp_events_query_filters() {
+        use RpcZkappChainStatus::{Canonical, Pending};
+
+        let zkapp = account(0, 0).id();
+        let other = account(1, 0).id();
+        let (mut ctx, best_chain) = ledger_ctx_with_zkapp_events(&zkapp, &other);
+        let mut zkapp_events = |query: RpcZkappEventsQuery| {
+            ctx.zkapp_events(&query, &best_chain)
+                .unwrap()
+                .into_iter()
+                .map(|events| {
+                    let action_states = events
+                        .block
+                        .account_updates
+                        .iter()
+                        .map(|update| update.action_state.clone().unwrap().0)
+                        .collect::<Vec<_>>();
+                    (events.block.height, events.chain_status, action_states)
+                })
+                .collect::<Vec<_>>()
+        };
+        let state = |value: u64| BigInt::from(Fp::from(value));
+
+        assert_eq!(
+            zkapp_events(zkapp_events_query(&zkapp)),
+            [
+                (1, Canonical, vec![state(1)]),
+                (2, Pending, vec![state(2)]),
+                (3, Pending, vec![state(3)]),
+                (4, Pending, vec![state(4)]),
+            ]
+        );
+        // Only the account updates of the account are returned.
+        assert_eq!(
+            zkapp_events(zkapp_events_query(&other)),
+            [(2, Pending, vec![state(100)])]
+        );
+
+        let heights = |events: Vec<(u32, RpcZkappChainStatus, Vec<BigInt>)>| {
+            events
+                .into_iter()
+                .map(|(height, ..)| height)
+                .collect::<Vec<_>>()
+        };
+        let status = |status| RpcZkappEventsQuery {
+            status: Some(status),
+            ..zkapp_events_query(&zkapp)
+        };
+        assert_eq!(heights(zkapp_events(status(Canonical))), [1]);
+        assert_eq!(heights(zkapp_events(status(Pending))), [2, 3, 4]);
+
+        // `from` is inclusive, `to` exclusive.
+        let range = RpcZkappEventsQuery {
+            from: Some(2),
+            to: Some(4),
+            ..zkapp_events_query(&zkapp)
+        };
+        assert_eq!(heights(zkapp_events(range)), [2, 3]);
+
+        let action_states = RpcZkappEventsQuery {
+            from_action_state: Some(state(2)),
+            end_action_state: Some(state(4)),
+            ..zkapp_events_query(&zkapp)
+        };
+        assert_eq!(heights(zkapp_events(action_states)), [2, 3]);
+
+        let other_token = RpcZkappEventsQuery {
+            token_id: Some((&zkapp.derive_token_id()).into()),
+            ..zkapp_events_query(&zkapp)
+        };
+        assert!(zkapp_events(other_token).is_empty());
+    }
+
+    #[test]
+    fn test_archive_get_without_archive() {
+        let (mut ctx, _) = ledger_ctx_with_accounts(&[]);
+        assert!(ctx.archive_get(&RpcArchiveQuery::BestTip).is_err());
+    }
 }
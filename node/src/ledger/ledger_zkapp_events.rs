@@ -0,0 +1,541 @@
+use std::collections::BTreeMap;
+
+use ledger::{
+    scan_state::transaction_logic::{zkapp_command::Event, UserCommand},
+    AccountId, BaseLedger, Mask, ZkAppAccount,
+};
+use mina_hasher::Fp;
+use mina_p2p_messages::{
+    binprot::macros::{BinProtRead, BinProtWrite},
+    v2::{
+        self, BigInt, MinaBaseSignedCommandMemoStableV1, MinaBaseTransactionStatusStableV2,
+        NonZeroCurvePoint, StateHash, TokenIdKeyHash, TransactionHash,
+    },
+};
+use openmina_core::block::ArcBlockWithHash;
+use serde::{Deserialize, Serialize};
+
+/// Events and actions emitted by an account update of a zkApp command.
+#[derive(BinProtRead, BinProtWrite, Serialize, Deserialize, Debug, Clone)]
+pub struct ZkappAccountUpdateEvents {
+    pub public_key: NonZeroCurvePoint,
+    pub token_id: TokenIdKeyHash,
+    pub transaction_hash: TransactionHash,
+    pub transaction_status: MinaBaseTransactionStatusStableV2,
+    pub memo: MinaBaseSignedCommandMemoStableV1,
+    /// Index of the command among the user commands of the block.
+    pub sequence_number: u32,
+    /// Index of the account update in the command, the fee payer being
+    /// the first one.
+    pub account_update_id: u32,
+    pub events: Vec<Vec<BigInt>>,
+    pub actions: Vec<Vec<BigInt>>,
+    /// Action state of the account before and after the actions were
+    /// added. `None` if there are no actions, or if the command failed
+    /// and they weren't added.
+    pub action_state: Option<(BigInt, BigInt)>,
+}
+
+/// Events and actions emitted by the zkApp commands of a block.
+#[derive(BinProtRead, BinProtWrite, Serialize, Deserialize, Debug, Clone)]
+pub struct ZkappBlockEvents {
+    pub hash: StateHash,
+    pub pred_hash: StateHash,
+    pub height: u32,
+    pub global_slot_since_genesis: u32,
+    pub timestamp: u64,
+    /// In the order the commands were applied.
+    pub account_updates: Vec<ZkappAccountUpdateEvents>,
+}
+
+impl ZkappBlockEvents {
+    /// Collects the events and actions of the `block`, `ledger` being the
+    /// staged ledger the block is applied on top of, which is needed for
+    /// the initial action states. `None` if the block has none.
+    pub fn new(block: &ArcBlockWithHash, ledger: &Mask) -> Option<Self> {
+        let mut action_states = BTreeMap::<AccountId, Fp>::new();
+        let mut account_updates = Vec::new();
+
+        for (sequence_number, command) in block.body().commands_iter().enumerate() {
+            let v2::MinaBaseUserCommandStableV2::ZkappCommand(zkapp_command) = &command.data else {
+                continue;
+            };
+            let (Ok(UserCommand::ZkAppCommand(converted)), Ok(transaction_hash)) =
+                (UserCommand::try_from(&command.data), command.data.hash())
+            else {
+                continue;
+            };
+            let applied = matches!(command.status, MinaBaseTransactionStatusStableV2::Applied);
+
+            for (account_update_id, account_update) in
+                converted.all_account_updates_list().iter().enumerate()
+            {
+                let body = &account_update.body;
+                if body.events.is_empty() && body.actions.is_empty() {
+                    continue;
+                }
+                // actions of failed commands are not added to the action state.
+                let action_state = (applied && !body.actions.is_empty()).then(|| {
+                    let account_id = account_update.account_id();
+                    let state = action_states
+                        .entry(account_id)
+                        .or_insert_with_key(|account_id| initial_action_state(ledger, account_id));
+                    let before = *state;
+                    *state = body.actions.push_events(before);
+                    (before.into(), (*state).into())
+                });
+
+                account_updates.push(ZkappAccountUpdateEvents {
+                    public_key: (&body.public_key).into(),
+                    token_id: (&body.token_id).into(),
+                    transaction_hash: transaction_hash.clone(),
+                    transaction_status: command.status.clone(),
+                    memo: zkapp_command.memo.clone(),
+                    sequence_number: sequence_number as u32,
+                    account_update_id: account_update_id as u32,
+                    events: body.events.0.iter().map(event_to_fields).collect(),
+                    actions: body.actions.0.iter().map(event_to_fields).collect(),
+                    action_state,
+                });
+            }
+        }
+
+        if account_updates.is_empty() {
+            return None;
+        }
+        Some(Self {
+            hash: block.hash().clone(),
+            pred_hash: block.pred_hash().clone(),
+            height: block.height(),
+            global_slot_since_genesis: block.global_slot_since_genesis(),
+            timestamp: u64::from(block.timestamp()),
+            account_updates,
+        })
+    }
+
+    /// Keeps only the account updates of the account.
+    pub fn filter_account(mut self, account_id: &AccountId) -> Self {
+        let public_key = NonZeroCurvePoint::from(&account_id.public_key);
+        let token_id = TokenIdKeyHash::from(&account_id.token_id);
+        self.account_updates
+            .retain(|update| update.public_key == public_key && update.token_id == token_id);
+        self
+    }
+}
+
+/// Keeps only the account updates of the `blocks` (ordered from the oldest)
+/// which added actions after the action state `from` and up to the action
+/// state `end`, if provided. Blocks left without account updates are
+/// removed.
+pub fn zkapp_events_filter_action_states(
+    blocks: &mut Vec<ZkappBlockEvents>,
+    from: Option<&BigInt>,
+    end: Option<&BigInt>,
+) {
+    if from.is_none() && end.is_none() {
+        return;
+    }
+    let mut started = from.is_none();
+    let mut ended = false;
+    for block in blocks.iter_mut() {
+        block.account_updates.retain(|update| {
+            let Some((before, after)) = &update.action_state else {
+                return false;
+            };
+            started = started || Some(before) == from;
+            let keep = started && !ended;
+            ended = ended || (keep && Some(after) == end);
+            keep
+        });
+    }
+    blocks.retain(|block| !block.account_updates.is_empty());
+}
+
+fn event_to_fields(event: &Event) -> Vec<BigInt> {
+    event.0.iter().map(BigInt::from).collect()
+}
+
+/// Latest action state of the account, or the empty one if the account
+/// doesn't exist yet or isn't a zkApp.
+fn initial_action_state(ledger: &Mask, account_id: &AccountId) -> Fp {
+    ledger
+        .location_of_account(account_id)
+        .and_then(|addr| ledger.get(addr))
+        .and_then(|account| account.zkapp)
+        .map_or_else(ZkAppAccount::empty_action_state, |zkapp| {
+            zkapp.action_state[0]
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use ledger::{
+        scan_state::{
+            currency::{Amount, Fee, Nonce},
+            transaction_logic::{
+                signed_command::{Body, PaymentPayload, SignedCommand, SignedCommandPayload},
+                zkapp_command::{
+                    AccountUpdate, Actions, CallForest, Events, FeePayer, FeePayerBody,
+                    ZkAppCommand,
+                },
+                Memo, TransactionFailure, TransactionStatus,
+            },
+        },
+        Account, Database, TokenId,
+    };
+    use mina_p2p_messages::list::List;
+    use mina_signer::{CompressedPubKey, Signature};
+    use openmina_node_account::AccountSecretKey;
+
+    use super::*;
+    use crate::testing::recorded_block;
+
+    fn public_key(i: u64) -> CompressedPubKey {
+        AccountSecretKey::deterministic(i).public_key_compressed()
+    }
+
+    fn account_id(i: u64) -> AccountId {
+        AccountId::new(public_key(i), TokenId::default())
+    }
+
+    fn fields(values: &[u64]) -> Event {
+        Event(values.iter().map(|value| Fp::from(*value)).collect())
+    }
+
+    fn account_update(i: u64, events: &[&[u64]], actions: &[&[u64]]) -> AccountUpdate {
+        let mut account_update = AccountUpdate::of_fee_payer(fee_payer(i));
+        account_update.body.increment_nonce = false;
+        account_update.body.events = Events(events.iter().map(|event| fields(event)).collect());
+        account_update.body.actions =
+            Actions(actions.iter().map(|action| fields(action)).collect());
+        account_update
+    }
+
+    fn memo() -> Memo {
+        Memo::from_str("zkapp").unwrap()
+    }
+
+    fn fee_payer(i: u64) -> FeePayer {
+        FeePayer {
+            body: FeePayerBody {
+                public_key: public_key(i),
+                fee: Fee::from_u64(10_000_000),
+                valid_until: None,
+                nonce: Nonce::from_u32(0),
+            },
+            authorization: Signature::dummy(),
+        }
+    }
+
+    /// zkApp command paid by account 0 made of the `account_updates`.
+    fn zkapp_command(
+        account_updates: Vec<AccountUpdate>,
+        status: TransactionStatus,
+    ) -> v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B {
+        let account_updates = account_updates
+            .into_iter()
+            .rev()
+            .fold(CallForest::new(), |forest, account_update| {
+                forest.cons(None, account_update)
+            });
+        let command = ZkAppCommand {
+            fee_payer: fee_payer(0),
+            account_updates,
+            memo: memo(),
+        };
+        v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B {
+            data: v2::MinaBaseUserCommandStableV2::ZkappCommand((&command).into()),
+            status: (&status).into(),
+        }
+    }
+
+    fn payment() -> v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B {
+        let payload = SignedCommandPayload::create(
+            Fee::from_u64(10_000_000),
+            public_key(0),
+            Nonce::from_u32(0),
+            None,
+            Memo::empty(),
+            Body::Payment(PaymentPayload {
+                receiver_pk: public_key(1),
+                amount: Amount::from_u64(1),
+            }),
+        );
+        let command = SignedCommand {
+            payload,
+            signer: public_key(0),
+            signature: Signature::dummy(),
+        };
+        v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B {
+            data: v2::MinaBaseUserCommandStableV2::SignedCommand(command.into()),
+            status: (&TransactionStatus::Applied).into(),
+        }
+    }
+
+    /// Recorded block with the user commands replaced by `commands`.
+    fn block(
+        commands: Vec<v2::StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B>,
+    ) -> ArcBlockWithHash {
+        let mut block = recorded_block();
+        let diff = &mut block.body.staged_ledger_diff.diff;
+        diff.0.commands = commands.into_iter().collect::<List<_>>();
+        diff.1 = None;
+        ArcBlockWithHash::try_new(Arc::new(block)).unwrap()
+    }
+
+    /// Ledger in which account 1 is a zkApp with the `action_state`.
+    fn ledger(action_state: Fp) -> Mask {
+        let mut mask = Mask::new_root(Database::create(35));
+        let mut zkapp = ZkAppAccount::default();
+        zkapp.action_state[0] = action_state;
+        let account = Account {
+            zkapp: Some(Box::new(zkapp)),
+            ..Account::initialize(&account_id(1))
+        };
+        mask.get_or_create_account(account.id(), account).unwrap();
+        mask
+    }
+
+    fn big_ints(values: &[u64]) -> Vec<BigInt> {
+        values.iter().map(|value| Fp::from(*value).into()).collect()
+    }
+
+    #[test]
+    fn test_events_and_actions_of_applied_command() {
+        let initial_action_state = Fp::from(42u64);
+        let ledger = ledger(initial_action_state);
+        let applied = zkapp_command(
+            vec![
+                account_update(1, &[&[1, 2], &[3]], &[&[4]]),
+                account_update(2, &[], &[]),
+                account_update(1, &[], &[&[5, 6], &[7]]),
+            ],
+            TransactionStatus::Applied,
+        );
+        let block = block(vec![payment(), applied.clone()]);
+
+        let events = ZkappBlockEvents::new(&block, &ledger).unwrap();
+        assert_eq!(&events.hash, block.hash());
+        assert_eq!(&events.pred_hash, block.pred_hash());
+        assert_eq!(events.height, block.height());
+        assert_eq!(
+            events.global_slot_since_genesis,
+            block.global_slot_since_genesis()
+        );
+
+        // The fee payer and the account update without events or actions
+        // are left out.
+        let [first, second] = events.account_updates.as_slice() else {
+            panic!("unexpected account updates: {:?}", events.account_updates);
+        };
+        for update in [first, second] {
+            assert_eq!(update.public_key, NonZeroCurvePoint::from(&public_key(1)));
+            assert_eq!(update.token_id, TokenIdKeyHash::from(&TokenId::default()));
+            assert_eq!(update.transaction_hash, applied.data.hash().unwrap());
+            assert_eq!(update.sequence_number, 1);
+            assert_eq!(update.memo, (&memo()).into());
+            assert!(matches!(
+                update.transaction_status,
+                MinaBaseTransactionStatusStableV2::Applied
+            ));
+        }
+        assert_eq!(first.account_update_id, 1);
+        assert_eq!(first.events, vec![big_ints(&[1, 2]), big_ints(&[3])]);
+        assert_eq!(first.actions, vec![big_ints(&[4])]);
+        assert_eq!(second.account_update_id, 3);
+        assert!(second.events.is_empty());
+        assert_eq!(second.actions, vec![big_ints(&[5, 6]), big_ints(&[7])]);
+
+        // The action state continues from the one in the ledger.
+        let first_state = Actions(vec![fields(&[4])]).push_events(initial_action_state);
+        let second_state = Actions(vec![fields(&[5, 6]), fields(&[7])]).push_events(first_state);
+        assert_eq!(
+            first.action_state,
+            Some((initial_action_state.into(), first_state.into()))
+        );
+        assert_eq!(
+            second.action_state,
+            Some((first_state.into(), second_state.into()))
+        );
+    }
+
+    #[test]
+    fn test_actions_of_failed_command_not_added() {
+        let ledger = ledger(Fp::from(42u64));
+        let failed = zkapp_command(
+            vec![account_update(1, &[&[1]], &[&[2]])],
+            TransactionStatus::Failed(vec![
+                vec![],
+                vec![TransactionFailure::UpdateNotPermittedActionState],
+            ]),
+        );
+        let applied = zkapp_command(
+            vec![account_update(1, &[], &[&[3]])],
+            TransactionStatus::Applied,
+        );
+        let block = block(vec![failed, applied]);
+
+        let events = ZkappBlockEvents::new(&block, &ledger).unwrap();
+        let [failed, applied] = events.account_updates.as_slice() else {
+            panic!("unexpected account updates: {:?}", events.account_updates);
+        };
+        assert!(matches!(
+            failed.transaction_status,
+            MinaBaseTransactionStatusStableV2::Failed(_)
+        ));
+        assert_eq!(failed.events, vec![big_ints(&[1])]);
+        assert_eq!(failed.actions, vec![big_ints(&[2])]);
+        assert_eq!(failed.action_state, None);
+
+        let after = Actions(vec![fields(&[3])]).push_events(Fp::from(42u64));
+        assert_eq!(applied.sequence_number, 1);
+        assert_eq!(
+            applied.action_state,
+            Some((Fp::from(42u64).into(), after.into()))
+        );
+    }
+
+    #[test]
+    fn test_new_zkapp_starts_from_empty_action_state() {
+        let ledger = ledger(Fp::from(42u64));
+        let block = block(vec![zkapp_command(
+            vec![account_update(2, &[], &[&[1]])],
+            TransactionStatus::Applied,
+        )]);
+
+        let events = ZkappBlockEvents::new(&block, &ledger).unwrap();
+        let (before, _) = events.account_updates[0].action_state.clone().unwrap();
+        assert_eq!(before, ZkAppAccount::empty_action_state().into());
+    }
+
+    #[test]
+    fn test_block_without_events() {
+        let ledger = ledger(Fp::from(42u64));
+        assert!(ZkappBlockEvents::new(&block(vec![]), &ledger).is_none());
+        let block = block(vec![
+            payment(),
+            zkapp_command(
+                vec![account_update(1, &[], &[])],
+                TransactionStatus::Applied,
+            ),
+        ]);
+        assert!(ZkappBlockEvents::new(&block, &ledger).is_none());
+    }
+
+    #[test]
+    fn test_filter_account() {
+        let ledger = ledger(Fp::from(42u64));
+        let block = block(vec![zkapp_command(
+            vec![
+                account_update(1, &[&[1]], &[]),
+                account_update(2, &[&[2]], &[]),
+                account_update(1, &[&[3]], &[]),
+            ],
+            TransactionStatus::Applied,
+        )]);
+        let events = ZkappBlockEvents::new(&block, &ledger).unwrap();
+
+        let filtered = events.clone().filter_account(&account_id(1));
+        let ids = filtered
+            .account_updates
+            .iter()
+            .map(|update| update.account_update_id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [1, 3]);
+
+        let other_token = AccountId::new(public_key(1), account_id(1).derive_token_id());
+        assert!(events
+            .filter_account(&other_token)
+            .account_updates
+            .is_empty());
+    }
+
+    /// Blocks where the account updates move the action state through
+    /// the `states`, one block per slice.
+    fn blocks_with_action_states(states: &[&[(u64, u64)]]) -> Vec<ZkappBlockEvents> {
+        let ledger = ledger(Fp::from(42u64));
+        let template = ZkappBlockEvents::new(
+            &block(vec![zkapp_command(
+                vec![account_update(1, &[], &[&[1]])],
+                TransactionStatus::Applied,
+            )]),
+            &ledger,
+        )
+        .unwrap();
+        states
+            .iter()
+            .map(|states| {
+                let mut block = template.clone();
+                block.account_updates = states
+                    .iter()
+                    .map(|(before, after)| ZkappAccountUpdateEvents {
+                        action_state: Some((Fp::from(*before).into(), Fp::from(*after).into())),
+                        ..template.account_updates[0].clone()
+                    })
+                    .collect();
+                block
+            })
+            .collect()
+    }
+
+    fn action_states(blocks: &[ZkappBlockEvents]) -> Vec<Vec<(u64, u64)>> {
+        let to_u64 = |state: &BigInt| state.to_decimal().parse::<u64>().unwrap();
+        blocks
+            .iter()
+            .map(|block| {
+                block
+                    .account_updates
+                    .iter()
+                    .map(|update| {
+                        let (before, after) = update.action_state.as_ref().unwrap();
+                        (to_u64(before), to_u64(after))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_action_states() {
+        let states: &[&[(u64, u64)]] = &[&[(1, 2), (2, 3)], &[(3, 4)], &[(4, 5), (5, 6)]];
+        let filtered = |from: Option<u64>, end: Option<u64>| {
+            let mut blocks = blocks_with_action_states(states);
+            let from = from.map(|state| BigInt::from(Fp::from(state)));
+            let end = end.map(|state| BigInt::from(Fp::from(state)));
+            zkapp_events_filter_action_states(&mut blocks, from.as_ref(), end.as_ref());
+            action_states(&blocks)
+        };
+
+        assert_eq!(
+            filtered(None, None),
+            action_states(&blocks_with_action_states(states))
+        );
+        assert_eq!(
+            filtered(Some(3), None),
+            [vec![(3, 4)], vec![(4, 5), (5, 6)]]
+        );
+        assert_eq!(filtered(None, Some(3)), [vec![(1, 2), (2, 3)]]);
+        assert_eq!(
+            filtered(Some(2), Some(5)),
+            [vec![(2, 3)], vec![(3, 4)], vec![(4, 5)]]
+        );
+        // Unknown action states select nothing, or everything up to the end.
+        assert!(filtered(Some(7), None).is_empty());
+        assert_eq!(
+            filtered(None, Some(7)),
+            action_states(&blocks_with_action_states(states))
+        );
+    }
+
+    #[test]
+    fn test_filter_action_states_skips_updates_without_actions() {
+        let mut blocks = blocks_with_action_states(&[&[(1, 2)], &[(2, 3)]]);
+        blocks[0].account_updates[0].action_state = None;
+        let from = BigInt::from(Fp::from(2u64));
+        zkapp_events_filter_action_states(&mut blocks, Some(&from), None);
+        assert_eq!(action_states(&blocks), [vec![(2, 3)]]);
+    }
+}
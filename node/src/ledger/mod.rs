@@ -21,6 +21,15 @@ pub use ledger_effects::*;
 
 mod ledger_service;
 pub use ledger_service::*;
+
+mod ledger_persistence;
+pub use ledger_persistence::*;
+
+mod ledger_archive;
+pub use ledger_archive::*;
+
+mod ledger_zkapp_events;
+pub use ledger_zkapp_events::*;
 pub mod ledger_manager;
 
 pub use ledger::AccountIndex as LedgerAccountIndex;
@@ -47,6 +47,16 @@ impl LedgerReadState {
         self.pending.get_mut(id)
     }
 
+    /// Number of requests sent to the ledger manager, which aren't
+    /// processed by the state machine yet.
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn total_cost(&self) -> usize {
+        self.total_cost
+    }
+
     pub fn is_total_cost_under_limit(&self) -> bool {
         self.total_cost < MAX_TOTAL_COST
     }
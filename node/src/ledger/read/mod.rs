@@ -4,6 +4,7 @@ pub use ledger_read_actions::*;
 
 mod ledger_read_state;
 pub use ledger_read_state::*;
+use openmina_core::block::ArcBlockWithHash;
 use openmina_core::requests::RpcId;
 
 mod ledger_read_reducer;
@@ -15,10 +16,14 @@ use mina_p2p_messages::v2;
 use serde::{Deserialize, Serialize};
 
 use crate::account::AccountPublicKey;
-use crate::block_producer::vrf_evaluator::DelegatorTable;
+use crate::block_producer::vrf_evaluator::DelegatorTables;
 use crate::ledger::LedgerAddress;
 use crate::p2p::channels::rpc::StagedLedgerAuxAndPendingCoinbases;
-use crate::rpc::{AccountQuery, RpcScanStateSummaryScanStateJob};
+use crate::rpc::{
+    AccountQuery, RpcArchiveGetResponse, RpcArchiveQuery, RpcHardForkConfigGetResponse,
+    RpcScanStateSummaryScanStateJob, RpcTransactionSimulateResponse, RpcZkappEventsQuery,
+    RpcZkappEventsResponse,
+};
 
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Clone, Copy)]
 pub enum LedgerReadKind {
@@ -28,38 +33,68 @@ pub enum LedgerReadKind {
     GetChildHashesAtAddr,
     GetChildAccountsAtAddr,
     GetStagedLedgerAuxAndPendingCoinbases,
+    GetEpochLedger,
     ScanStateSummary,
     AccountsForRpc,
+    HardForkConfig,
+    WatchedAccount,
+    TransactionSimulate,
+    ZkappEvents,
+    ArchiveGet,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub enum LedgerReadRequest {
-    /// Delegator table requested by vrf state machine.
-    DelegatorTable(v2::LedgerHash, AccountPublicKey),
+    /// Delegator tables of the producers, requested by vrf state machine.
+    DelegatorTable(v2::LedgerHash, Vec<AccountPublicKey>),
     // p2p rpcs
     GetNumAccounts(v2::LedgerHash),
     GetAccounts(v2::LedgerHash, Vec<AccountId>, Option<RpcId>),
     GetChildHashesAtAddr(v2::LedgerHash, LedgerAddress),
     GetChildAccountsAtAddr(v2::LedgerHash, LedgerAddress),
     GetStagedLedgerAuxAndPendingCoinbases(LedgerReadStagedLedgerAuxAndPendingCoinbases),
+    GetEpochLedger(v2::LedgerHash),
     // rpcs
     ScanStateSummary(v2::MinaBaseStagedLedgerHashStableV1),
     AccountsForRpc(RpcId, v2::LedgerHash, AccountQuery),
+    /// Config of the network hard forked at the block.
+    HardForkConfig(RpcId, ArcBlockWithHash),
+    /// Account watched by the `watched_accounts` state machine, at the block.
+    WatchedAccount(v2::LedgerHash, v2::NonZeroCurvePoint, v2::StateHash),
+    /// Command to apply on top of the block's staged ledger.
+    TransactionSimulate(
+        RpcId,
+        ArcBlockWithHash,
+        Box<v2::MinaBaseUserCommandStableV2>,
+    ),
+    /// zkApp events of the account on the best chain, given by height
+    /// from the root to the best tip.
+    ZkappEvents(RpcId, RpcZkappEventsQuery, BTreeMap<u32, v2::StateHash>),
+    ArchiveGet(RpcId, RpcArchiveQuery),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum LedgerReadResponse {
-    /// Delegator table requested by vrf state machine.
-    DelegatorTable(Option<DelegatorTable>),
+    /// Delegator tables of the producers, requested by vrf state machine.
+    DelegatorTable(Option<DelegatorTables>),
     // p2p rpcs
     GetNumAccounts(Option<(u64, v2::LedgerHash)>),
     GetAccounts(Vec<Account>, Option<RpcId>),
     GetChildHashesAtAddr(Option<(v2::LedgerHash, v2::LedgerHash)>),
     GetChildAccountsAtAddr(Option<Vec<v2::MinaBaseAccountBinableArgStableV2>>),
     GetStagedLedgerAuxAndPendingCoinbases(Option<Arc<StagedLedgerAuxAndPendingCoinbases>>),
+    GetEpochLedger(Option<Arc<v2::MinaBaseSparseLedgerBaseStableV2>>),
     // rpcs
     ScanStateSummary(Result<Vec<Vec<RpcScanStateSummaryScanStateJob>>, String>),
-    AccountsForRpc(RpcId, Vec<Account>, AccountQuery),
+    /// `None` if the ledger is missing.
+    AccountsForRpc(RpcId, Option<Vec<Account>>, AccountQuery),
+    HardForkConfig(RpcId, RpcHardForkConfigGetResponse),
+    /// `None` if the ledger is missing, `Some(None)` if the account isn't
+    /// in the ledger.
+    WatchedAccount(Option<Option<Box<v2::MinaBaseAccountBinableArgStableV2>>>),
+    TransactionSimulate(RpcId, RpcTransactionSimulateResponse),
+    ZkappEvents(RpcId, RpcZkappEventsResponse),
+    ArchiveGet(RpcId, RpcArchiveGetResponse),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -68,6 +103,14 @@ pub struct LedgerReadStagedLedgerAuxAndPendingCoinbases {
     pub protocol_states: BTreeMap<v2::StateHash, v2::MinaStateProtocolStateValueStableV2>,
 }
 
+/// Ledgers of the block the network is hard forked at.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct HardForkLedgers<T> {
+    pub staged_ledger: T,
+    pub staking_epoch_ledger: T,
+    pub next_epoch_ledger: T,
+}
+
 impl LedgerReadRequest {
     pub fn kind(&self) -> LedgerReadKind {
         match self {
@@ -79,8 +122,14 @@ impl LedgerReadRequest {
             Self::GetStagedLedgerAuxAndPendingCoinbases(..) => {
                 LedgerReadKind::GetStagedLedgerAuxAndPendingCoinbases
             }
+            Self::GetEpochLedger(..) => LedgerReadKind::GetEpochLedger,
             Self::ScanStateSummary(..) => LedgerReadKind::ScanStateSummary,
             Self::AccountsForRpc(..) => LedgerReadKind::AccountsForRpc,
+            Self::HardForkConfig(..) => LedgerReadKind::HardForkConfig,
+            Self::WatchedAccount(..) => LedgerReadKind::WatchedAccount,
+            Self::TransactionSimulate(..) => LedgerReadKind::TransactionSimulate,
+            Self::ZkappEvents(..) => LedgerReadKind::ZkappEvents,
+            Self::ArchiveGet(..) => LedgerReadKind::ArchiveGet,
         }
     }
 
@@ -96,9 +145,15 @@ impl LedgerReadRequest {
             }
             Self::GetChildHashesAtAddr(..) => 1,
             Self::GetStagedLedgerAuxAndPendingCoinbases(..) => 100,
+            Self::GetEpochLedger(..) => 100,
             Self::ScanStateSummary(..) => 100,
             // TODO(adonagy): not sure
             Self::AccountsForRpc(..) => 10,
+            Self::HardForkConfig(..) => 100,
+            Self::WatchedAccount(..) => 1,
+            Self::TransactionSimulate(..) => 10,
+            Self::ZkappEvents(..) => 10,
+            Self::ArchiveGet(..) => 10,
         };
         cost.max(1)
     }
@@ -115,8 +170,14 @@ impl LedgerReadResponse {
             Self::GetStagedLedgerAuxAndPendingCoinbases(..) => {
                 LedgerReadKind::GetStagedLedgerAuxAndPendingCoinbases
             }
+            Self::GetEpochLedger(..) => LedgerReadKind::GetEpochLedger,
             Self::ScanStateSummary(..) => LedgerReadKind::ScanStateSummary,
             Self::AccountsForRpc(..) => LedgerReadKind::AccountsForRpc,
+            Self::HardForkConfig(..) => LedgerReadKind::HardForkConfig,
+            Self::WatchedAccount(..) => LedgerReadKind::WatchedAccount,
+            Self::TransactionSimulate(..) => LedgerReadKind::TransactionSimulate,
+            Self::ZkappEvents(..) => LedgerReadKind::ZkappEvents,
+            Self::ArchiveGet(..) => LedgerReadKind::ArchiveGet,
         }
     }
 }
@@ -46,6 +46,8 @@ pub enum LedgerWriteRequest {
         completed_snarks: BTreeMap<SnarkJobId, Snark>,
         supercharge_coinbase: bool,
         transactions_by_fee: Vec<valid::UserCommand>,
+        /// Maximum sum of the fees of the snark work included in the diff.
+        snark_fee_budget: Option<u64>,
     },
     BlockApply {
         block: ArcBlockWithHash,
@@ -44,5 +44,8 @@ pub mod transaction_pool;
 pub mod transition_frontier;
 pub mod watched_accounts;
 
+#[cfg(test)]
+mod testing;
+
 pub type Store<S> = redux::Store<State, S, Action>;
 pub type Effects<S> = redux::Effects<State, S, Action>;
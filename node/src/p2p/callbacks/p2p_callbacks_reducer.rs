@@ -1,16 +1,35 @@
+use std::{
+    collections::BTreeSet,
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
 use ark_ff::fields::arithmetic::InvalidBigInt;
-use mina_p2p_messages::v2::{MinaLedgerSyncLedgerAnswerStableV2, StateHash};
-use openmina_core::{block::BlockWithHash, bug_condition};
+use mina_p2p_messages::{
+    core::InetAddrV1,
+    list::List,
+    rpc::NodeStatusV2,
+    string::CharString,
+    v2::{self, MinaLedgerSyncLedgerAnswerStableV2, StateHash},
+};
+use openmina_core::{
+    block::{AppliedBlock, ArcBlockWithHash, BlockWithHash},
+    bug_condition,
+    consensus::consensus_take,
+};
 use p2p::{
     channels::{
         best_tip::P2pChannelsBestTipAction,
-        rpc::{BestTipWithProof, P2pChannelsRpcAction, P2pRpcRequest, P2pRpcResponse},
+        rpc::{
+            AncestryWithProof, BestTipWithProof, P2pChannelsRpcAction, P2pRpcKind, P2pRpcRequest,
+            P2pRpcResponse,
+        },
         streaming_rpc::P2pStreamingRpcResponseFull,
     },
     disconnection::{P2pDisconnectionAction, P2pDisconnectionReason},
-    PeerId,
+    P2pState, PeerId, P2P_PEER_SCORE_MAX,
 };
-use redux::{ActionMeta, ActionWithMeta, Dispatcher};
+use redux::{ActionMeta, ActionWithMeta, Dispatcher, Timestamp};
 
 use crate::{
     p2p_ready,
@@ -23,12 +42,9 @@ use crate::{
             },
             staged::{PeerStagedLedgerPartsFetchError, TransitionFrontierSyncLedgerStagedAction},
         },
-        PeerBlockFetchError, TransitionFrontierSyncAction,
-    },
-    watched_accounts::{
-        WatchedAccountLedgerInitialState, WatchedAccountsLedgerInitialStateGetError,
+        PeerBlockFetchError, SyncPhase, TransitionFrontierSyncAction, TransitionFrontierSyncState,
     },
-    Action, ConsensusAction, State, WatchedAccountsAction,
+    Action, ConsensusAction, State,
 };
 
 use super::P2pCallbacksAction;
@@ -72,6 +88,10 @@ impl crate::State {
                     bug_condition!("peer: {:?} pending_local_rpc_kind() returned None", peer_id);
                     return;
                 };
+                if rpc_kind == P2pRpcKind::BanNotify {
+                    // banned peer is disconnected by p2p itself.
+                    return;
+                }
 
                 dispatcher.push(
                     TransitionFrontierSyncLedgerSnarkedAction::PeerQueryAddressError {
@@ -235,28 +255,6 @@ impl crate::State {
                         });
                     });
 
-                state
-                    .watched_accounts
-                    .iter()
-                    .filter_map(|(pub_key, a)| match &a.initial_state {
-                        WatchedAccountLedgerInitialState::Pending {
-                            peer_id: account_peer_id,
-                            ..
-                        } => {
-                            if account_peer_id == &peer_id {
-                                Some(WatchedAccountsAction::LedgerInitialStateGetError {
-                                    pub_key: pub_key.clone(),
-                                    error:
-                                        WatchedAccountsLedgerInitialStateGetError::PeerDisconnected,
-                                })
-                            } else {
-                                None
-                            }
-                        }
-                        _ => None,
-                    })
-                    .for_each(|action| dispatcher.push(action));
-
                 dispatcher.push(SnarkPoolCandidateAction::PeerPrune { peer_id });
             }
             P2pCallbacksAction::RpcRespondBestTip { peer_id } => {
@@ -363,6 +361,72 @@ impl crate::State {
                     response,
                 });
             }
+            P2pRpcRequest::BanNotify(banned_until) => {
+                openmina_core::log::warn!(meta.time(); kind = "P2pRpcRequest::BanNotify", peer_id = peer_id.to_string(), banned_until = banned_until);
+                dispatcher.push(P2pChannelsRpcAction::ResponseSend {
+                    peer_id,
+                    id,
+                    response: Some(Box::new(P2pRpcResponse::BanNotify)),
+                });
+            }
+            P2pRpcRequest::Ancestry(consensus_state, hash) => {
+                let best_chain = &state.transition_frontier.best_chain;
+                let response = ancestry_with_proof(best_chain, &consensus_state, &hash)
+                    .unwrap_or_else(|_| {
+                        openmina_core::error!(meta.time(); "P2pRpcRequest::Ancestry: invalid protocol state");
+                        None
+                    })
+                    .map(P2pRpcResponse::Ancestry)
+                    .map(Box::new);
+                dispatcher.push(P2pChannelsRpcAction::ResponseSend {
+                    peer_id,
+                    id,
+                    response,
+                });
+            }
+            P2pRpcRequest::TransitionChainProof(hash) => {
+                let best_chain = &state.transition_frontier.best_chain;
+                let response = transition_chain_proof(best_chain, &hash)
+                    .unwrap_or_else(|_| {
+                        openmina_core::error!(meta.time(); "P2pRpcRequest::TransitionChainProof: invalid protocol state");
+                        None
+                    })
+                    .map(|(hash, body_hashes)| {
+                        P2pRpcResponse::TransitionChainProof(hash, body_hashes)
+                    })
+                    .map(Box::new);
+                dispatcher.push(P2pChannelsRpcAction::ResponseSend {
+                    peer_id,
+                    id,
+                    response,
+                });
+            }
+            P2pRpcRequest::NodeStatus => {
+                let p2p = p2p_ready!(state.p2p, meta.time());
+                let response = build_node_status(state, p2p, meta.time())
+                    .map(Box::new)
+                    .map(P2pRpcResponse::NodeStatus)
+                    .map(Box::new);
+
+                dispatcher.push(P2pChannelsRpcAction::ResponseSend {
+                    peer_id,
+                    id,
+                    response,
+                });
+            }
+            P2pRpcRequest::EpochLedger(hash) => {
+                let is_epoch_ledger = is_epoch_ledger(state.transition_frontier.best_tip(), &hash);
+                if is_epoch_ledger {
+                    // async ledger request will be triggered
+                    // by `LedgerReadAction::FindTodos`.
+                } else {
+                    dispatcher.push(P2pChannelsRpcAction::ResponseSend {
+                        peer_id,
+                        id,
+                        response: None,
+                    });
+                }
+            }
         }
     }
 
@@ -504,6 +568,323 @@ impl crate::State {
                 });
             }
             Some(P2pRpcResponse::InitialPeers(_)) => {}
+            Some(P2pRpcResponse::Ancestry(_)) => {}
+            Some(P2pRpcResponse::TransitionChainProof(..)) => {}
+            Some(P2pRpcResponse::NodeStatus(_)) => {}
+            Some(P2pRpcResponse::EpochLedger(_)) => {}
+            Some(P2pRpcResponse::BanNotify) => {}
         }
     }
 }
+
+fn build_node_status(state: &State, p2p: &P2pState, now: Timestamp) -> Option<NodeStatusV2> {
+    let tf = &state.transition_frontier;
+    let best_tip = tf.best_tip()?;
+
+    let sync_status = node_status_sync_status(&tf.sync);
+    let uptime =
+        Duration::from_nanos(u64::from(now)).saturating_sub(p2p.config.meshsub.initial_time);
+
+    Some(NodeStatusV2 {
+        // we don't know our external address.
+        node_ip_addr: InetAddrV1::from(IpAddr::V4(Ipv4Addr::UNSPECIFIED)).into(),
+        node_peer_id: peer_id_into_mina_rpc(p2p.my_id()),
+        sync_status,
+        peers: p2p
+            .ready_peers_iter()
+            .map(|(peer_id, _)| peer_id_into_mina_rpc(*peer_id))
+            .collect(),
+        block_producers: state
+            .block_producer
+            .config()
+            .into_iter()
+            .flat_map(|config| config.pub_keys().cloned())
+            .collect(),
+        protocol_state_hash: best_tip.hash().clone(),
+        ban_statuses: p2p
+            .peer_scores
+            .scores
+            .keys()
+            .chain(p2p.peer_scores.banned.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|peer_id| {
+                let trust = p2p.peer_scores.score(peer_id, now) as f64 / P2P_PEER_SCORE_MAX as f64;
+                let banned = match p2p.peer_scores.banned.get(peer_id) {
+                    Some(ban) => v2::TrustSystemBannedStatusStableV1::BannedUntil(
+                        (u64::from(ban.until) as f64 / 1_000_000_000.0).into(),
+                    ),
+                    None => v2::TrustSystemBannedStatusStableV1::Unbanned,
+                };
+                let status = v2::TrustSystemPeerStatusStableV1 {
+                    trust: trust.into(),
+                    banned,
+                };
+                (peer_id_into_mina_rpc(*peer_id), status)
+            })
+            .collect(),
+        k_block_hashes_and_timestamps: k_block_hashes_and_timestamps(&tf.best_chain),
+        git_commit: state.config.build.git.commit_hash.as_str().into(),
+        uptime_minutes: (uptime.as_secs() / 60) as i32,
+        block_height_opt: Some(best_tip.height() as i32),
+    })
+}
+
+fn node_status_sync_status(sync: &TransitionFrontierSyncState) -> v2::SyncStatusTStableV1 {
+    match (sync, sync.sync_phase()) {
+        (TransitionFrontierSyncState::Idle, _) => v2::SyncStatusTStableV1::Listening,
+        (_, SyncPhase::Bootstrap) => v2::SyncStatusTStableV1::Bootstrap,
+        (_, SyncPhase::Catchup) => v2::SyncStatusTStableV1::Catchup,
+        (_, SyncPhase::Synced) => v2::SyncStatusTStableV1::Synced,
+    }
+}
+
+fn k_block_hashes_and_timestamps(best_chain: &[AppliedBlock]) -> List<(StateHash, CharString)> {
+    best_chain
+        .iter()
+        .map(|b| {
+            let timestamp_ms = u64::from(b.timestamp()) / 1_000_000;
+            (b.hash().clone(), timestamp_ms.to_string().as_str().into())
+        })
+        .collect()
+}
+
+/// Root of our best chain with the proof that it is an ancestor of our
+/// best tip, if our best tip is better than the peer's one.
+fn ancestry_with_proof(
+    best_chain: &[AppliedBlock],
+    consensus_state: &v2::ConsensusProofOfStakeDataConsensusStateValueStableV2,
+    hash: &StateHash,
+) -> Result<Option<AncestryWithProof>, InvalidBigInt> {
+    let Some(best_tip) = best_chain.last() else {
+        return Ok(None);
+    };
+    // only prove our root if our best tip is better than
+    // the one that the peer has.
+    if !consensus_take(
+        consensus_state,
+        best_tip.consensus_state(),
+        hash,
+        best_tip.hash(),
+    ) {
+        return Ok(None);
+    }
+    let mut chain_iter = best_chain.iter();
+    let Some(root_block) = chain_iter.next() else {
+        return Ok(None);
+    };
+    let body_hashes = chain_iter
+        .map(|b| b.header().protocol_state.body.try_hash())
+        .collect::<Result<_, _>>()?;
+
+    Ok(Some(AncestryWithProof {
+        root: root_block.block().clone(),
+        proof: (body_hashes, best_tip.block().clone()),
+    }))
+}
+
+/// Hash of the block `k` blocks below the one with the `hash` (or of our
+/// root) and body hashes of the blocks above it, up to the requested one.
+fn transition_chain_proof(
+    best_chain: &[AppliedBlock],
+    hash: &StateHash,
+) -> Result<Option<(StateHash, List<v2::MinaBaseStateBodyHashStableV1>)>, InvalidBigInt> {
+    let Some(index) = best_chain.iter().rposition(|b| b.hash() == hash) else {
+        return Ok(None);
+    };
+    let k = best_chain[index].constants().k.as_u32() as usize;
+    let start = index.saturating_sub(k);
+    let body_hashes = best_chain[(start + 1)..=index]
+        .iter()
+        .map(|b| b.header().protocol_state.body.try_hash())
+        .collect::<Result<_, _>>()?;
+
+    Ok(Some((best_chain[start].hash().clone(), body_hashes)))
+}
+
+/// Only the epoch ledgers of our best tip are served.
+fn is_epoch_ledger(best_tip: Option<&ArcBlockWithHash>, hash: &v2::LedgerHash) -> bool {
+    best_tip.map_or(false, |best_tip| {
+        best_tip.staking_epoch_ledger_hash() == hash || best_tip.next_epoch_ledger_hash() == hash
+    })
+}
+
+fn peer_id_into_mina_rpc(peer_id: PeerId) -> v2::NetworkPeerPeerIdStableV1 {
+    #[cfg(not(target_arch = "wasm32"))]
+    let peer_id = peer_id.to_libp2p_string();
+    #[cfg(target_arch = "wasm32")]
+    let peer_id = peer_id.to_string();
+    v2::NetworkPeerPeerIdStableV1(peer_id.into_bytes().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::testing::recorded_block;
+
+    use super::*;
+
+    /// Chain of `len` blocks built on top of a recorded block, each one
+    /// extending the previous one.
+    fn best_chain(len: u32) -> Vec<AppliedBlock> {
+        let root = recorded_block();
+        let root_height = root
+            .header
+            .protocol_state
+            .body
+            .consensus_state
+            .blockchain_length
+            .as_u32();
+
+        let mut chain = Vec::<AppliedBlock>::new();
+        for i in 0..len {
+            let mut block = root.clone();
+            let protocol_state = &mut block.header.protocol_state;
+            protocol_state.body.consensus_state.blockchain_length = (root_height + i).into();
+            if let Some(pred) = chain.last() {
+                protocol_state.previous_state_hash = pred.hash().clone();
+            }
+            chain.push(AppliedBlock {
+                block: ArcBlockWithHash::try_new(Arc::new(block)).unwrap(),
+                just_emitted_a_proof: false,
+            });
+        }
+        chain
+    }
+
+    #[test]
+    fn ancestry_served_to_peers_with_worse_best_tip() {
+        let chain = best_chain(5);
+        let peer_tip = &chain[2];
+
+        let ancestry = ancestry_with_proof(&chain, peer_tip.consensus_state(), peer_tip.hash())
+            .unwrap()
+            .expect("our best tip is better");
+        assert_eq!(ancestry.root.as_ref(), chain[0].block().as_ref());
+        assert_eq!(ancestry.proof.1.as_ref(), chain[4].block().as_ref());
+        let body_hashes = chain[1..]
+            .iter()
+            .map(|b| b.header().protocol_state.body.try_hash().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ancestry.proof.0.iter().cloned().collect::<Vec<_>>(),
+            body_hashes
+        );
+    }
+
+    #[test]
+    fn ancestry_not_served_to_peers_with_better_best_tip() {
+        let chain = best_chain(5);
+        let peer_tip = &chain[4];
+        let ours = &chain[..3];
+
+        let ancestry =
+            ancestry_with_proof(ours, peer_tip.consensus_state(), peer_tip.hash()).unwrap();
+        assert!(ancestry.is_none());
+        let ancestry =
+            ancestry_with_proof(&[], peer_tip.consensus_state(), peer_tip.hash()).unwrap();
+        assert!(ancestry.is_none());
+    }
+
+    #[test]
+    fn transition_chain_proof_from_root() {
+        let chain = best_chain(5);
+
+        let (hash, body_hashes) = transition_chain_proof(&chain, chain[3].hash())
+            .unwrap()
+            .expect("block is in the best chain");
+        // `k` is larger than the chain, so the proof starts at the root.
+        assert_eq!(&hash, chain[0].hash());
+        let expected = chain[1..=3]
+            .iter()
+            .map(|b| b.header().protocol_state.body.try_hash().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(body_hashes.iter().cloned().collect::<Vec<_>>(), expected);
+
+        let (hash, body_hashes) = transition_chain_proof(&chain, chain[0].hash())
+            .unwrap()
+            .unwrap();
+        assert_eq!(&hash, chain[0].hash());
+        assert!(body_hashes.is_empty());
+    }
+
+    #[test]
+    fn transition_chain_proof_of_unknown_block() {
+        let chain = best_chain(5);
+        let unknown = best_chain(6).pop().unwrap();
+
+        let proof = transition_chain_proof(&chain, unknown.hash()).unwrap();
+        assert!(proof.is_none());
+    }
+
+    #[test]
+    fn node_status_k_blocks_and_sync_status() {
+        let chain = best_chain(3);
+
+        let k_blocks = k_block_hashes_and_timestamps(&chain);
+        let hashes = k_blocks.iter().map(|(hash, _)| hash).collect::<Vec<_>>();
+        assert_eq!(hashes, chain.iter().map(|b| b.hash()).collect::<Vec<_>>());
+        let (_, timestamp) = k_blocks.iter().next().unwrap();
+        let timestamp_ms = u64::from(chain[0].timestamp()) / 1_000_000;
+        assert_eq!(timestamp.to_string(), timestamp_ms.to_string());
+
+        assert!(matches!(
+            node_status_sync_status(&TransitionFrontierSyncState::Idle),
+            v2::SyncStatusTStableV1::Listening
+        ));
+    }
+
+    #[test]
+    fn only_best_tip_epoch_ledgers_served() {
+        let chain = best_chain(1);
+        let best_tip = &chain[0].block;
+
+        let staking = best_tip.staking_epoch_ledger_hash();
+        let next = best_tip.next_epoch_ledger_hash();
+        assert!(is_epoch_ledger(Some(best_tip), staking));
+        assert!(is_epoch_ledger(Some(best_tip), next));
+        assert!(!is_epoch_ledger(None, staking));
+        let other = v2::LedgerHash::from_fp(mina_hasher::Fp::from(1u64));
+        assert!(!is_epoch_ledger(Some(best_tip), &other));
+    }
+
+    #[test]
+    fn peer_disconnection_keeps_watched_accounts_pending() {
+        use crate::account::AccountSecretKey;
+        use crate::watched_accounts::{WatchedAccountLedgerInitialState, WatchedAccountState};
+
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let chain = best_chain(1);
+        let pub_key: v2::NonZeroCurvePoint = AccountSecretKey::deterministic(0).public_key().into();
+        // the account is read from our own ledger, not requested from a peer.
+        state.watched_accounts.insert(
+            pub_key.clone(),
+            WatchedAccountState {
+                initial_state: WatchedAccountLedgerInitialState::Pending {
+                    time: Timestamp::ZERO,
+                    block: (&chain[0].block).into(),
+                },
+                blocks: Default::default(),
+            },
+        );
+
+        let mut dispatcher = Dispatcher::new();
+        let action = P2pCallbacksAction::P2pDisconnection {
+            peer_id: PeerId::from_bytes([1; 32]),
+        };
+        State::p2p_callback_reducer(
+            crate::Substate::new(&mut state, &mut dispatcher),
+            ActionMeta::zero_custom(Timestamp::ZERO).with_action(&action),
+        );
+
+        let account = state.watched_accounts.get(&pub_key).unwrap();
+        assert!(matches!(
+            account.initial_state,
+            WatchedAccountLedgerInitialState::Pending { .. }
+        ));
+        let pending = account.ledger_queries_pending().collect::<Vec<_>>();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(&pending[0].hash, chain[0].hash());
+    }
+}
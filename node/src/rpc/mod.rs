@@ -7,14 +7,17 @@ use ledger::scan_state::currency::{Amount, Balance, Fee, Nonce, Slot};
 use ledger::scan_state::transaction_logic::signed_command::SignedCommandPayload;
 use ledger::scan_state::transaction_logic::{self, signed_command, valid, Memo};
 use ledger::transaction_pool::{diff, ValidCommandWithHash};
-use ledger::Account;
+use ledger::{Account, AccountId};
 use mina_p2p_messages::bigint::BigInt;
 use mina_p2p_messages::v2::{
-    MinaBaseSignedCommandPayloadBodyStableV2, MinaBaseTransactionStatusStableV2,
-    MinaBaseUserCommandStableV2, MinaTransactionTransactionStableV2,
-    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse, StateHash, TransactionHash,
+    LedgerHash, MinaBaseAccountBinableArgStableV2, MinaBaseSignedCommandPayloadBodyStableV2,
+    MinaBaseSignedCommandStableV2, MinaBaseTransactionStatusStableV2, MinaBaseUserCommandStableV2,
+    MinaBaseZkappCommandTStableV1WireStableV1, MinaTransactionTransactionStableV2,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse,
+    SnarkWorkerWorkerRpcsVersionedSubmitWorkV2TQuery, StateHash, TokenIdKeyHash, TransactionHash,
 };
-use openmina_core::block::AppliedBlock;
+use mina_signer::CompressedPubKey;
+use openmina_core::block::{AppliedBlock, ArcBlockWithHash};
 use openmina_core::consensus::ConsensusConstants;
 use openmina_node_account::AccountPublicKey;
 use p2p::bootstrap::P2pNetworkKadBootstrapStats;
@@ -28,6 +31,9 @@ mod rpc_reducer;
 mod rpc_effects;
 pub use rpc_effects::*;
 
+mod rpc_fee_estimate;
+pub use rpc_fee_estimate::*;
+
 mod rpc_service;
 pub use rpc_service::*;
 
@@ -43,15 +49,23 @@ use redux::Timestamp;
 use serde::{Deserialize, Serialize};
 
 use crate::external_snark_worker::{
-    ExternalSnarkWorkerError, ExternalSnarkWorkerWorkError, SnarkWorkSpecError,
+    ExternalSnarkWorkerError, ExternalSnarkWorkerHealth, ExternalSnarkWorkerWorkError,
+    SnarkWorkSpecError,
 };
+use crate::ledger::{ArchiveBlock, ZkappBlockEvents};
 use crate::p2p::connection::incoming::P2pConnectionIncomingInitOpts;
 use crate::p2p::connection::outgoing::P2pConnectionOutgoingInitOpts;
 use crate::p2p::PeerId;
 use crate::snark_pool::{JobCommitment, JobSummary};
 use crate::stats::actions::{ActionStatsForBlock, ActionStatsSnapshot};
-use crate::stats::block_producer::{BlockProductionAttempt, BlockProductionAttemptWonSlot};
+use crate::stats::block_producer::{
+    BlockProducerKeyStats, BlockProductionAttempt, BlockProductionAttemptWonSlot,
+};
+use crate::stats::network::ProtocolTraffic;
 use crate::stats::sync::SyncStatsSnapshot;
+use crate::watched_accounts::{
+    WatchedAccountBlockInfo, WatchedAccountBlockState, WatchedAccountState,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum RpcRequest {
@@ -60,32 +74,93 @@ pub enum RpcRequest {
     ActionStatsGet(ActionStatsQuery),
     SyncStatsGet(SyncStatsQuery),
     BlockProducerStatsGet,
+    MetricsGet,
     MessageProgressGet,
     PeersGet,
     P2pConnectionOutgoing(P2pConnectionOutgoingInitOpts),
     P2pConnectionIncoming(P2pConnectionIncomingInitOpts),
     ScanStateSummaryGet(RpcScanStateSummaryGetQuery),
     SnarkPoolGet,
-    SnarkPoolJobGet { job_id: SnarkJobId },
+    SnarkPoolJobGet {
+        job_id: SnarkJobId,
+    },
     SnarkerConfig,
-    SnarkerJobCommit { job_id: SnarkJobId },
-    SnarkerJobSpec { job_id: SnarkJobId },
+    SnarkerJobCommit {
+        job_id: SnarkJobId,
+    },
+    SnarkerJobSpec {
+        job_id: SnarkJobId,
+    },
     SnarkerWorkers,
+    SnarkerWorkerGetWork(RpcSnarkerWorkerConnectionId),
+    SnarkerWorkerSubmitWork(
+        RpcSnarkerWorkerConnectionId,
+        Box<RpcSnarkerWorkerSubmitWorkQuery>,
+    ),
+    SnarkerWorkerFailed(RpcSnarkerWorkerConnectionId, SnarkJobId, String),
     HealthCheck,
     ReadinessCheck,
     DiscoveryRoutingTable,
     DiscoveryBoostrapStats,
     TransactionPoolGet,
-    LedgerAccountsGet(AccountQuery),
+    LedgerAccountsGet(AccountQuery, Option<LedgerHash>),
     TransactionInject(Vec<MinaBaseUserCommandStableV2>),
     TransitionFrontierUserCommandsGet,
     BestChain(MaxLength),
+    /// New best tip every time it changes, until the receiver is dropped.
+    BestTipSubscribe,
     ConsensusConstantsGet,
     TransactionStatusGet(MinaBaseUserCommandStableV2),
+    BlockGet(GetBlockQuery),
+    GenesisBlockGet,
+    PooledUserCommands(PooledCommandsQuery),
+    PooledZkappCommands(PooledCommandsQuery),
+    HardForkConfigGet(Option<StateHash>),
+    WatchedAccountsAdd(AccountPublicKey),
+    WatchedAccountsRemove(AccountPublicKey),
+    /// All the watched accounts if `None`.
+    WatchedAccountsGet(Option<AccountPublicKey>),
+    /// Watched account every time its ledger state is updated. Ends once
+    /// the account is no longer watched.
+    WatchedAccountSubscribe(AccountPublicKey),
+    /// Applies the command on top of the best tip without committing or
+    /// broadcasting it.
+    TransactionSimulate(MinaBaseUserCommandStableV2),
+    FeeEstimate(RpcFeeEstimateQuery),
+    ZkappEventsGet(RpcZkappEventsQuery),
+    ArchiveGet(RpcArchiveQuery),
 }
 
 pub type MaxLength = u32;
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum GetBlockQuery {
+    Hash(StateHash),
+    Height(u32),
+}
+
+/// Filter for the commands in the transaction pool. Commands must match
+/// all the provided filters.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PooledCommandsQuery {
+    /// Fee payer of the command.
+    pub public_key: Option<AccountPublicKey>,
+    pub hashes: Option<Vec<TransactionHash>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RpcFeeEstimateQuery {
+    /// Best chain blocks to sample the fees of, 10 if not provided.
+    pub blocks: Option<u32>,
+    /// Account updates of the zkApp command to estimate the fee of, 1 if
+    /// not provided.
+    pub zkapp_account_updates: Option<u32>,
+    /// Sender of the pending command to estimate the replacement fee of.
+    pub public_key: Option<AccountPublicKey>,
+    /// Nonce of the pending command to estimate the replacement fee of.
+    pub nonce: Option<u32>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RpcInjectPayment {
     fee: u64,
@@ -98,36 +173,113 @@ pub struct RpcInjectPayment {
     signature_field: BigInt,
     signature_scalar: BigInt,
 }
-// MinaBaseUserCommandStableV2
+
 impl TryFrom<RpcInjectPayment> for MinaBaseUserCommandStableV2 {
-    type Error = InvalidBigInt;
+    type Error = RpcInjectCommandError;
 
     fn try_from(value: RpcInjectPayment) -> Result<Self, Self::Error> {
-        let signature = mina_signer::Signature {
-            rx: value.signature_field.try_into()?,
-            s: value.signature_scalar.try_into()?,
-        };
-        println!("Signature: {signature}");
-        let sc = signed_command::SignedCommand {
-            payload: SignedCommandPayload::create(
-                Fee::from_u64(value.fee),
-                value.from.clone().try_into().map_err(|_| InvalidBigInt)?,
-                Nonce::from_u32(value.nonce),
-                Some(Slot::from_u32(value.valid_until)),
-                Memo::from_str(&value.memo).unwrap(),
-                signed_command::Body::Payment(signed_command::PaymentPayload {
-                    receiver_pk: value.to.try_into().map_err(|_| InvalidBigInt)?,
-                    amount: Amount::from_u64(value.amount),
-                }),
-            ),
-            signer: value.from.try_into().map_err(|_| InvalidBigInt)?,
-            signature,
-        };
+        let body = signed_command::Body::Payment(signed_command::PaymentPayload {
+            receiver_pk: rpc_inject_public_key(value.to)?,
+            amount: Amount::from_u64(value.amount),
+        });
+        rpc_inject_signed_command(
+            value.fee,
+            value.from,
+            value.nonce,
+            value.valid_until,
+            &value.memo,
+            (value.signature_field, value.signature_scalar),
+            body,
+        )
+    }
+}
 
-        Ok(MinaBaseUserCommandStableV2::SignedCommand(sc.into()))
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcInjectDelegation {
+    fee: u64,
+    /// New delegate.
+    to: AccountPublicKey,
+    from: AccountPublicKey,
+    memo: String,
+    nonce: u32,
+    valid_until: u32,
+    signature_field: BigInt,
+    signature_scalar: BigInt,
+}
+
+impl TryFrom<RpcInjectDelegation> for MinaBaseUserCommandStableV2 {
+    type Error = RpcInjectCommandError;
+
+    fn try_from(value: RpcInjectDelegation) -> Result<Self, Self::Error> {
+        let body = signed_command::Body::StakeDelegation(
+            signed_command::StakeDelegationPayload::SetDelegate {
+                new_delegate: rpc_inject_public_key(value.to)?,
+            },
+        );
+        rpc_inject_signed_command(
+            value.fee,
+            value.from,
+            value.nonce,
+            value.valid_until,
+            &value.memo,
+            (value.signature_field, value.signature_scalar),
+            body,
+        )
     }
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum RpcInjectCommandError {
+    #[error("invalid signature: {0}")]
+    InvalidSignature(InvalidBigInt),
+    #[error("invalid public key: {0}")]
+    InvalidPublicKey(AccountPublicKey),
+    #[error("memo is longer than {} bytes", Memo::DIGEST_LENGTH)]
+    InvalidMemo,
+}
+
+fn rpc_inject_public_key(key: AccountPublicKey) -> Result<CompressedPubKey, RpcInjectCommandError> {
+    key.clone()
+        .try_into()
+        .map_err(|_| RpcInjectCommandError::InvalidPublicKey(key))
+}
+
+/// Builds the signed command out of the fields shared by
+/// [`RpcInjectPayment`] and [`RpcInjectDelegation`].
+fn rpc_inject_signed_command(
+    fee: u64,
+    from: AccountPublicKey,
+    nonce: u32,
+    valid_until: u32,
+    memo: &str,
+    (signature_field, signature_scalar): (BigInt, BigInt),
+    body: signed_command::Body,
+) -> Result<MinaBaseUserCommandStableV2, RpcInjectCommandError> {
+    let signature = mina_signer::Signature {
+        rx: signature_field
+            .try_into()
+            .map_err(RpcInjectCommandError::InvalidSignature)?,
+        s: signature_scalar
+            .try_into()
+            .map_err(RpcInjectCommandError::InvalidSignature)?,
+    };
+    let from = rpc_inject_public_key(from)?;
+    let sc = signed_command::SignedCommand {
+        payload: SignedCommandPayload::create(
+            Fee::from_u64(fee),
+            from.clone(),
+            Nonce::from_u32(nonce),
+            Some(Slot::from_u32(valid_until)),
+            Memo::from_str(memo).map_err(|_| RpcInjectCommandError::InvalidMemo)?,
+            body,
+        ),
+        signer: from,
+        signature,
+    };
+
+    Ok(MinaBaseUserCommandStableV2::SignedCommand(sc.into()))
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ActionStatsQuery {
     SinceStart,
@@ -171,6 +323,10 @@ pub struct RpcPeerInfo {
     pub connection_status: PeerConnectionStatus,
     pub address: Option<String>,
     pub time: u64,
+    /// Reputation of the peer, lowered when the peer misbehaves.
+    pub score: i32,
+    /// Time (in nanoseconds) until which the peer is banned.
+    pub banned_until: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -326,11 +482,18 @@ pub enum RpcStateGetError {
     FilterError(String),
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, thiserror::Error)]
+pub enum RpcLedgerAccountsError {
+    #[error("ledger not found")]
+    LedgerNotFound,
+}
+
 pub type RpcStateGetResponse = Result<serde_json::Value, RpcStateGetError>;
 pub type RpcStatusGetResponse = Option<RpcNodeStatus>;
 pub type RpcActionStatsGetResponse = Option<ActionStatsResponse>;
 pub type RpcSyncStatsGetResponse = Option<Vec<SyncStatsSnapshot>>;
 pub type RpcBlockProducerStatsGetResponse = Option<RpcBlockProducerStats>;
+pub type RpcMetricsGetResponse = RpcMetrics;
 pub type RpcPeersGetResponse = Vec<RpcPeerInfo>;
 pub type RpcP2pConnectionOutgoingResponse = Result<(), String>;
 pub type RpcScanStateSummaryGetResponse = Result<RpcScanStateSummary, String>;
@@ -339,11 +502,164 @@ pub type RpcSnarkPoolJobGetResponse = Option<RpcSnarkPoolJobFull>;
 pub type RpcSnarkerConfigGetResponse = Option<RpcSnarkerConfig>;
 pub type RpcTransactionPoolResponse = Vec<ValidCommandWithHash>;
 pub type RpcLedgerSlimAccountsResponse = Vec<AccountSlim>;
-pub type RpcLedgerAccountsResponse = Vec<Account>;
+pub type RpcLedgerAccountsResponse = Result<Vec<Account>, RpcLedgerAccountsError>;
 pub type RpcTransitionFrontierUserCommandsResponse = Vec<MinaBaseUserCommandStableV2>;
 pub type RpcBestChainResponse = Vec<AppliedBlock>;
+pub type RpcBestTipSubscribeResponse = AppliedBlock;
 pub type RpcConsensusConstantsGetResponse = ConsensusConstants;
 pub type RpcTransactionStatusGetResponse = TransactionStatus;
+pub type RpcGetBlockResponse = Option<AppliedBlock>;
+pub type RpcGenesisBlockResponse = Option<ArcBlockWithHash>;
+pub type RpcPooledUserCommandsResponse = Vec<MinaBaseSignedCommandStableV2>;
+pub type RpcPooledZkappCommandsResponse = Vec<MinaBaseZkappCommandTStableV1WireStableV1>;
+/// Config of the hard forked network, as daemon.json.
+pub type RpcHardForkConfigGetResponse = Result<String, String>;
+/// `false` if the account was already watched.
+pub type RpcWatchedAccountsAddResponse = bool;
+/// `false` if the account wasn't watched.
+pub type RpcWatchedAccountsRemoveResponse = bool;
+pub type RpcWatchedAccountsGetResponse = Vec<RpcWatchedAccount>;
+pub type RpcWatchedAccountSubscribeResponse = RpcWatchedAccount;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcWatchedAccount {
+    pub public_key: AccountPublicKey,
+    /// Block at which `account` was last read from the ledger.
+    pub block: Option<WatchedAccountBlockInfo>,
+    /// `None` if the account isn't in the ledger or wasn't read yet.
+    pub account: Option<MinaBaseAccountBinableArgStableV2>,
+    /// Recent blocks affecting the account, from the oldest.
+    pub blocks: Vec<WatchedAccountBlockState>,
+}
+
+impl RpcWatchedAccount {
+    pub fn new(public_key: &NonZeroCurvePoint, state: &WatchedAccountState) -> Self {
+        let latest = state.latest();
+        Self {
+            public_key: public_key.clone().into(),
+            block: latest.map(|(block, _)| block.clone()),
+            account: latest.and_then(|(_, account)| account.cloned()),
+            blocks: state.blocks.iter().cloned().collect(),
+        }
+    }
+}
+
+pub type RpcFeeEstimateResponse = RpcFeeEstimate;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcFeeEstimate {
+    pub signed_command: RpcFeeLevels,
+    /// For a zkApp command with the requested number of account updates.
+    pub zkapp_command: RpcFeeLevels,
+    /// `None` if the replacement wasn't requested or there is no pending
+    /// command from the sender at the nonce.
+    pub replace: Option<RpcReplaceFee>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcFeeLevels {
+    /// Enough for the cheaper part of the recent blocks' commands.
+    pub low: Fee,
+    /// Enough for the median recent block command and for the next block,
+    /// given the transaction pool.
+    pub medium: Fee,
+    /// Enough to be among the first half of the next block's commands.
+    pub high: Fee,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcReplaceFee {
+    /// Minimum fee of a replacement, if the commands queued after the
+    /// pending one can still be applied.
+    pub min_fee: Fee,
+    /// Minimum fee of a replacement that also pays for the commands queued
+    /// after the pending one, in case they get dropped.
+    pub min_fee_with_dependents: Fee,
+}
+
+pub type RpcTransactionSimulateResponse = Result<RpcTransactionSimulation, String>;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct RpcZkappEventsQuery {
+    pub public_key: AccountPublicKey,
+    /// Default token if not provided.
+    pub token_id: Option<TokenIdKeyHash>,
+    /// Blocks of any status if not provided.
+    pub status: Option<RpcZkappChainStatus>,
+    /// Lowest block height, inclusive.
+    pub from: Option<u32>,
+    /// Highest block height, exclusive.
+    pub to: Option<u32>,
+    /// Only the actions added after this action state.
+    pub from_action_state: Option<BigInt>,
+    /// Only the actions added up to this action state, inclusive.
+    pub end_action_state: Option<BigInt>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcZkappChainStatus {
+    /// Block above the transition frontier root, which can still be
+    /// replaced by a fork.
+    Pending,
+    /// Block at or below the transition frontier root.
+    Canonical,
+}
+
+pub type RpcZkappEventsResponse = Result<Vec<RpcZkappBlockEvents>, String>;
+
+/// Query of the blocks archived by the node.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub enum RpcArchiveQuery {
+    BestTip,
+    Block(StateHash),
+    /// Block at the height on the best chain.
+    BlockAtHeight(u32),
+    /// Block which included the user command.
+    TransactionBlock(TransactionHash),
+    /// Hashes of the blocks which changed the account, oldest first.
+    /// Default token if not provided.
+    AccountHistory(AccountPublicKey, Option<TokenIdKeyHash>),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum RpcArchiveData {
+    /// `None` if the block isn't archived.
+    Block(Option<ArchiveBlock>),
+    AccountHistory(Vec<StateHash>),
+}
+
+pub type RpcArchiveGetResponse = Result<RpcArchiveData, String>;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcZkappBlockEvents {
+    pub chain_status: RpcZkappChainStatus,
+    #[serde(flatten)]
+    pub block: ZkappBlockEvents,
+}
+
+/// Result of applying a command on a throwaway copy of the best tip
+/// staged ledger. Proofs and signatures aren't verified.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcTransactionSimulation {
+    /// Best tip the command was applied on top of.
+    pub block_hash: StateHash,
+    /// Failures of each account update, starting with the fee payer, if
+    /// the command failed.
+    pub status: transaction_logic::TransactionStatus,
+    /// Fee charged to the fee payer, even if the command failed.
+    pub fee: Fee,
+    /// Accounts changed by the command.
+    pub account_diffs: Vec<RpcAccountDiff>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RpcAccountDiff {
+    pub account_id: AccountId,
+    /// `None` if the account was created by the command.
+    pub before: Option<Account>,
+    pub after: Option<Account>,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, strum_macros::Display)]
 #[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
@@ -372,7 +688,7 @@ pub struct RpcTransactionInjectedPayment {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum RpcTransactionInjectedCommand {
     Payment(RpcTransactionInjectedPayment),
-    Delegation,
+    Delegation(RpcTransactionInjectedPayment),
     Zkapp(valid::UserCommand),
 }
 
@@ -408,9 +724,18 @@ impl From<ValidCommandWithHash> for RpcTransactionInjectedCommand {
                             nonce: signedcmd.nonce(),
                         })
                     }
-                    transaction_logic::signed_command::Body::StakeDelegation(_) => {
-                        todo!("inject stake delegation")
-                    }
+                    transaction_logic::signed_command::Body::StakeDelegation(
+                        signed_command::StakeDelegationPayload::SetDelegate { ref new_delegate },
+                    ) => Self::Delegation(RpcTransactionInjectedPayment {
+                        amount: Amount::from_u64(0),
+                        fee: signedcmd.fee(),
+                        from: signedcmd.fee_payer_pk().clone().into(),
+                        to: new_delegate.clone().into(),
+                        hash: TransactionHash::from(value.hash.as_ref()).to_string(),
+                        is_delegation: true,
+                        memo: signedcmd.payload.common.memo.to_string(),
+                        nonce: signedcmd.nonce(),
+                    }),
                 }
             }
             transaction_logic::valid::UserCommand::ZkAppCommand(_) => {
@@ -486,12 +811,48 @@ pub struct RpcBlockProducerStats {
     pub epoch_end: Option<u32>,
     pub attempts: Vec<BlockProductionAttempt>,
     pub future_won_slots: Vec<BlockProductionAttemptWonSlot>,
+    /// Attempts summary per producer key.
+    pub producers: BTreeMap<AccountPublicKey, BlockProducerKeyStats>,
+}
+
+/// Snapshot of the node's state and stats, exported as metrics.
+#[derive(Serialize, Debug, Clone)]
+pub struct RpcMetrics {
+    /// Action durations since the start of the node. `None` if stats
+    /// are disabled, same for the other stats below.
+    pub action_stats: Option<ActionStatsSnapshot>,
+    /// Latest sync.
+    pub sync_stats: Option<SyncStatsSnapshot>,
+    pub sync_phase: String,
+    pub peers: RpcMetricsPeers,
+    pub transaction_pool: RpcNodeStatusTransactionPool,
+    pub snark_pool: RpcNodeStatusSnarkPool,
+    pub snark_workers: Vec<RpcSnarkWorker>,
+    /// Block production counters per producer key since the start of
+    /// the node. `None` if the node isn't a block producer.
+    pub block_producer: Option<BTreeMap<AccountPublicKey, BlockProducerKeyStats>>,
+    pub ledger: RpcMetricsLedger,
+    pub p2p_traffic: Option<BTreeMap<String, ProtocolTraffic>>,
+}
+
+#[derive(Serialize, Debug, Default, Clone)]
+pub struct RpcMetricsPeers {
+    pub connecting: usize,
+    pub connected: usize,
+    pub disconnected: usize,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct RpcMetricsLedger {
+    pub pending_reads: usize,
+    pub pending_reads_cost: usize,
+    pub pending_writes: usize,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RpcSnarkerConfig {
-    public_key: NonZeroCurvePoint,
-    fee: CurrencyFeeStableV1,
+    pub public_key: NonZeroCurvePoint,
+    pub fee: CurrencyFeeStableV1,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -499,6 +860,7 @@ pub struct RpcSnarkWorker {
     pub time: Option<Timestamp>,
     pub id: Option<String>,
     pub status: RpcSnarkWorkerStatus,
+    pub health: ExternalSnarkWorkerHealth,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -533,6 +895,28 @@ pub enum RpcSnarkWorkerStatus {
 
 pub type RpcSnarkerWorkersResponse = Vec<RpcSnarkWorker>;
 
+/// Id assigned by the snark worker server to each connection of remote
+/// workers.
+pub type RpcSnarkerWorkerConnectionId = u64;
+
+/// `None` if there is no job for the worker at the moment.
+pub type RpcSnarkerWorkerGetWorkResponse = SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse;
+
+pub type RpcSnarkerWorkerSubmitWorkQuery = SnarkWorkerWorkerRpcsVersionedSubmitWorkV2TQuery;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind")]
+pub enum RpcSnarkerWorkerSubmitWorkResponse {
+    /// Work is accepted and will be added to the snark pool once
+    /// it's verified.
+    Ok,
+    /// Worker isn't working on the job (anymore), e.g. because it
+    /// timed out or another snark for it was received meanwhile.
+    NotAssigned,
+}
+
+pub type RpcSnarkerWorkerFailedResponse = ();
+
 impl From<&MinaTransactionTransactionStableV2> for RpcScanStateSummaryBlockTransactionKind {
     fn from(value: &MinaTransactionTransactionStableV2) -> Self {
         match value {
@@ -651,3 +1035,157 @@ pub mod discovery {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: &str = "B62qnLVz8wM7MfJsuYbjFf4UWbwrUBEL5ZdawExxxFhnGXB6siqokyM";
+    const TO: &str = "B62qnJcRzJpdaXvi6ok3iH7BbP3R6oZtT1C9qTyUr9hNHWRf3eUAJxC";
+
+    fn payment() -> RpcInjectPayment {
+        RpcInjectPayment {
+            fee: 10_000_000,
+            amount: 1_000_000_000,
+            to: TO.parse().unwrap(),
+            from: FROM.parse().unwrap(),
+            memo: "memo".to_owned(),
+            nonce: 3,
+            valid_until: 100,
+            signature_field: BigInt::one(),
+            signature_scalar: BigInt::one(),
+        }
+    }
+
+    fn delegation() -> RpcInjectDelegation {
+        RpcInjectDelegation {
+            fee: 10_000_000,
+            to: TO.parse().unwrap(),
+            from: FROM.parse().unwrap(),
+            memo: "memo".to_owned(),
+            nonce: 3,
+            valid_until: 100,
+            signature_field: BigInt::one(),
+            signature_scalar: BigInt::one(),
+        }
+    }
+
+    fn expected_command(body: signed_command::Body) -> MinaBaseUserCommandStableV2 {
+        let from: CompressedPubKey = FROM
+            .parse::<AccountPublicKey>()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let sc = signed_command::SignedCommand {
+            payload: SignedCommandPayload::create(
+                Fee::from_u64(10_000_000),
+                from.clone(),
+                Nonce::from_u32(3),
+                Some(Slot::from_u32(100)),
+                Memo::from_str("memo").unwrap(),
+                body,
+            ),
+            signer: from,
+            signature: mina_signer::Signature {
+                rx: BigInt::one().try_into().unwrap(),
+                s: BigInt::one().try_into().unwrap(),
+            },
+        };
+        MinaBaseUserCommandStableV2::SignedCommand(sc.into())
+    }
+
+    fn to() -> CompressedPubKey {
+        TO.parse::<AccountPublicKey>().unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn payment_conversion() {
+        let command = MinaBaseUserCommandStableV2::try_from(payment()).unwrap();
+        let expected = expected_command(signed_command::Body::Payment(
+            signed_command::PaymentPayload {
+                receiver_pk: to(),
+                amount: Amount::from_u64(1_000_000_000),
+            },
+        ));
+        assert_eq!(command, expected);
+    }
+
+    #[test]
+    fn delegation_conversion() {
+        let command = MinaBaseUserCommandStableV2::try_from(delegation()).unwrap();
+        let expected = expected_command(signed_command::Body::StakeDelegation(
+            signed_command::StakeDelegationPayload::SetDelegate { new_delegate: to() },
+        ));
+        assert_eq!(command, expected);
+    }
+
+    #[test]
+    fn conversion_rejects_long_memo() {
+        let memo = "a".repeat(Memo::DIGEST_LENGTH + 1);
+        let payment = RpcInjectPayment {
+            memo: memo.clone(),
+            ..payment()
+        };
+        assert!(matches!(
+            MinaBaseUserCommandStableV2::try_from(payment),
+            Err(RpcInjectCommandError::InvalidMemo)
+        ));
+        let delegation = RpcInjectDelegation {
+            memo,
+            ..delegation()
+        };
+        assert!(matches!(
+            MinaBaseUserCommandStableV2::try_from(delegation),
+            Err(RpcInjectCommandError::InvalidMemo)
+        ));
+    }
+
+    #[test]
+    fn conversion_rejects_invalid_signature() {
+        // Larger than the field modulus.
+        let invalid = BigInt::from_bytes([0xff; 32]);
+        let payment = RpcInjectPayment {
+            signature_field: invalid.clone(),
+            ..payment()
+        };
+        assert!(matches!(
+            MinaBaseUserCommandStableV2::try_from(payment),
+            Err(RpcInjectCommandError::InvalidSignature(_))
+        ));
+        let delegation = RpcInjectDelegation {
+            signature_scalar: invalid,
+            ..delegation()
+        };
+        assert!(matches!(
+            MinaBaseUserCommandStableV2::try_from(delegation),
+            Err(RpcInjectCommandError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn conversion_rejects_invalid_public_key() {
+        let invalid: AccountPublicKey = NonZeroCurvePoint::from(
+            mina_p2p_messages::v2::NonZeroCurvePointUncompressedStableV1 {
+                x: BigInt::from_bytes([0xff; 32]),
+                is_odd: false,
+            },
+        )
+        .into();
+        let payment = RpcInjectPayment {
+            to: invalid.clone(),
+            ..payment()
+        };
+        assert!(matches!(
+            MinaBaseUserCommandStableV2::try_from(payment),
+            Err(RpcInjectCommandError::InvalidPublicKey(key)) if key == invalid
+        ));
+        let delegation = RpcInjectDelegation {
+            from: invalid.clone(),
+            ..delegation()
+        };
+        assert!(matches!(
+            MinaBaseUserCommandStableV2::try_from(delegation),
+            Err(RpcInjectCommandError::InvalidPublicKey(key)) if key == invalid
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use ledger::transaction_pool::{diff, ValidCommandWithHash};
 use ledger::Account;
 use mina_p2p_messages::v2::MinaBaseUserCommandStableV2;
-use mina_p2p_messages::v2::TokenIdKeyHash;
+use mina_p2p_messages::v2::{LedgerHash, StateHash, TokenIdKeyHash};
 use openmina_core::block::AppliedBlock;
 use openmina_core::snark::SnarkJobId;
 use openmina_core::ActionEvent;
@@ -15,8 +15,10 @@ use crate::p2p::connection::outgoing::{P2pConnectionOutgoingError, P2pConnection
 use crate::p2p::connection::P2pConnectionResponse;
 
 use super::{
-    ActionStatsQuery, RpcId, RpcScanStateSummaryGetQuery, RpcScanStateSummaryScanStateJob,
-    SyncStatsQuery,
+    ActionStatsQuery, GetBlockQuery, PooledCommandsQuery, RpcArchiveGetResponse, RpcArchiveQuery,
+    RpcFeeEstimateQuery, RpcHardForkConfigGetResponse, RpcId, RpcScanStateSummaryGetQuery,
+    RpcScanStateSummaryScanStateJob, RpcSnarkerWorkerConnectionId, RpcSnarkerWorkerSubmitWorkQuery,
+    RpcTransactionSimulateResponse, RpcZkappEventsQuery, RpcZkappEventsResponse, SyncStatsQuery,
 };
 
 pub type RpcActionWithMeta = redux::ActionWithMeta<RpcAction>;
@@ -44,6 +46,9 @@ pub enum RpcAction {
     BlockProducerStatsGet {
         rpc_id: RpcId,
     },
+    MetricsGet {
+        rpc_id: RpcId,
+    },
 
     MessageProgressGet {
         rpc_id: RpcId,
@@ -131,6 +136,21 @@ pub enum RpcAction {
     SnarkerWorkersGet {
         rpc_id: RpcId,
     },
+    SnarkerWorkerGetWork {
+        rpc_id: RpcId,
+        connection_id: RpcSnarkerWorkerConnectionId,
+    },
+    SnarkerWorkerSubmitWork {
+        rpc_id: RpcId,
+        connection_id: RpcSnarkerWorkerConnectionId,
+        work: Box<RpcSnarkerWorkerSubmitWorkQuery>,
+    },
+    SnarkerWorkerFailed {
+        rpc_id: RpcId,
+        connection_id: RpcSnarkerWorkerConnectionId,
+        job_id: SnarkJobId,
+        error: String,
+    },
 
     HealthCheck {
         rpc_id: RpcId,
@@ -153,6 +173,8 @@ pub enum RpcAction {
     LedgerAccountsGetInit {
         rpc_id: RpcId,
         account_query: AccountQuery,
+        /// Ledger to query, best tip ledger if `None`.
+        ledger_hash: Option<LedgerHash>,
     },
     #[action_event(level = info)]
     LedgerAccountsGetPending {
@@ -161,7 +183,8 @@ pub enum RpcAction {
     #[action_event(level = info)]
     LedgerAccountsGetSuccess {
         rpc_id: RpcId,
-        accounts: Vec<Account>,
+        /// `None` if the ledger isn't found.
+        accounts: Option<Vec<Account>>,
         account_query: AccountQuery,
     },
     #[action_event(level = info)]
@@ -197,6 +220,10 @@ pub enum RpcAction {
         rpc_id: RpcId,
         max_length: u32,
     },
+    BestTipSubscribe {
+        rpc_id: RpcId,
+    },
+    BestTipSubscribersNotify,
     ConsensusConstantsGet {
         rpc_id: RpcId,
     },
@@ -206,6 +233,107 @@ pub enum RpcAction {
         tx: MinaBaseUserCommandStableV2,
     },
 
+    BlockGet {
+        rpc_id: RpcId,
+        query: GetBlockQuery,
+    },
+    GenesisBlockGet {
+        rpc_id: RpcId,
+    },
+    PooledUserCommands {
+        rpc_id: RpcId,
+        query: PooledCommandsQuery,
+    },
+    PooledZkappCommands {
+        rpc_id: RpcId,
+        query: PooledCommandsQuery,
+    },
+    HardForkConfigGetInit {
+        rpc_id: RpcId,
+        /// Block to fork at, best tip if `None`.
+        block_hash: Option<StateHash>,
+    },
+    HardForkConfigLedgersGetInit {
+        rpc_id: RpcId,
+    },
+    HardForkConfigGetPending {
+        rpc_id: RpcId,
+        block: Option<AppliedBlock>,
+    },
+    HardForkConfigGetSuccess {
+        rpc_id: RpcId,
+        config: RpcHardForkConfigGetResponse,
+    },
+    WatchedAccountsAdd {
+        rpc_id: RpcId,
+        public_key: AccountPublicKey,
+    },
+    WatchedAccountsRemove {
+        rpc_id: RpcId,
+        public_key: AccountPublicKey,
+    },
+    WatchedAccountsGet {
+        rpc_id: RpcId,
+        public_key: Option<AccountPublicKey>,
+    },
+    WatchedAccountSubscribe {
+        rpc_id: RpcId,
+        public_key: AccountPublicKey,
+    },
+    WatchedAccountSubscribersNotify {
+        public_key: AccountPublicKey,
+    },
+    TransactionSimulateInit {
+        rpc_id: RpcId,
+        command: MinaBaseUserCommandStableV2,
+    },
+    TransactionSimulateLedgerInit {
+        rpc_id: RpcId,
+    },
+    TransactionSimulatePending {
+        rpc_id: RpcId,
+    },
+    TransactionSimulateSuccess {
+        rpc_id: RpcId,
+        result: RpcTransactionSimulateResponse,
+    },
+    FeeEstimate {
+        rpc_id: RpcId,
+        query: RpcFeeEstimateQuery,
+    },
+    ZkappEventsGetInit {
+        rpc_id: RpcId,
+        query: RpcZkappEventsQuery,
+    },
+    ZkappEventsGetLedgerInit {
+        rpc_id: RpcId,
+    },
+    ZkappEventsGetPending {
+        rpc_id: RpcId,
+    },
+    ZkappEventsGetSuccess {
+        rpc_id: RpcId,
+        result: RpcZkappEventsResponse,
+    },
+    ArchiveGetInit {
+        rpc_id: RpcId,
+        query: RpcArchiveQuery,
+    },
+    ArchiveGetLedgerInit {
+        rpc_id: RpcId,
+    },
+    ArchiveGetPending {
+        rpc_id: RpcId,
+    },
+    ArchiveGetSuccess {
+        rpc_id: RpcId,
+        result: RpcArchiveGetResponse,
+    },
+
+    /// Closes the subscription, so that the subscriber's stream ends.
+    SubscriptionEnd {
+        rpc_id: RpcId,
+    },
     Finish {
         rpc_id: RpcId,
     },
@@ -213,9 +341,14 @@ pub enum RpcAction {
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AccountQuery {
+    /// Account of the public key with the default token.
     SinglePublicKey(AccountPublicKey),
     All,
     PubKeyWithTokenId(AccountPublicKey, TokenIdKeyHash),
+    /// Account that owns (created) the token.
+    TokenOwner(TokenIdKeyHash),
+    /// All the accounts as they are in the ledger, in the ledger order.
+    Ledger,
 }
 
 impl redux::EnablingCondition<crate::State> for RpcAction {
@@ -226,6 +359,7 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
             RpcAction::ActionStatsGet { .. } => true,
             RpcAction::SyncStatsGet { .. } => true,
             RpcAction::BlockProducerStatsGet { .. } => true,
+            RpcAction::MetricsGet { .. } => true,
             RpcAction::MessageProgressGet { .. } => true,
             RpcAction::PeersGet { .. } => true,
             RpcAction::P2pConnectionOutgoingInit { rpc_id, .. } => {
@@ -292,6 +426,9 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
             RpcAction::SnarkerJobCommit { .. } => true,
             RpcAction::SnarkerJobSpec { .. } => true,
             RpcAction::SnarkerWorkersGet { .. } => true,
+            RpcAction::SnarkerWorkerGetWork { .. } => true,
+            RpcAction::SnarkerWorkerSubmitWork { .. } => true,
+            RpcAction::SnarkerWorkerFailed { .. } => true,
             RpcAction::HealthCheck { .. } => true,
             RpcAction::ReadinessCheck { .. } => true,
             RpcAction::DiscoveryRoutingTable { .. } => true,
@@ -299,9 +436,92 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
             RpcAction::TransactionPool { .. } => true,
             RpcAction::ConsensusConstantsGet { .. } => true,
             RpcAction::BestChain { .. } => state.transition_frontier.best_tip().is_some(),
-            RpcAction::TransactionStatusGet { .. } => true,
-            RpcAction::LedgerAccountsGetInit { .. } => {
+            RpcAction::BestTipSubscribe { .. } => true,
+            RpcAction::BestTipSubscribersNotify => {
                 state.transition_frontier.best_tip().is_some()
+                    && state.rpc.best_tip_subscriptions().next().is_some()
+            }
+            RpcAction::TransactionStatusGet { .. } => true,
+            RpcAction::BlockGet { .. } => true,
+            RpcAction::GenesisBlockGet { .. } => true,
+            RpcAction::PooledUserCommands { .. } => true,
+            RpcAction::PooledZkappCommands { .. } => true,
+            RpcAction::HardForkConfigGetInit { .. } => true,
+            RpcAction::HardForkConfigLedgersGetInit { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::HardForkConfigGetPending { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::HardForkConfigGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::WatchedAccountsAdd { .. } => true,
+            RpcAction::WatchedAccountsRemove { .. } => true,
+            RpcAction::WatchedAccountsGet { .. } => true,
+            RpcAction::WatchedAccountSubscribe { .. } => true,
+            RpcAction::WatchedAccountSubscribersNotify { public_key } => state
+                .rpc
+                .watched_account_subscriptions(public_key)
+                .next()
+                .is_some(),
+            RpcAction::TransactionSimulateInit { .. } => true,
+            RpcAction::TransactionSimulateLedgerInit { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::TransactionSimulatePending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::TransactionSimulateSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::FeeEstimate { .. } => true,
+            RpcAction::ZkappEventsGetInit { .. } => true,
+            RpcAction::ZkappEventsGetLedgerInit { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::ZkappEventsGetPending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::ZkappEventsGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::ArchiveGetInit { .. } => true,
+            RpcAction::ArchiveGetLedgerInit { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::ArchiveGetPending { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_init()),
+            RpcAction::ArchiveGetSuccess { rpc_id, .. } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
+            RpcAction::LedgerAccountsGetInit { ledger_hash, .. } => {
+                ledger_hash.is_some() || state.transition_frontier.best_tip().is_some()
             }
             RpcAction::LedgerAccountsGetPending { rpc_id, .. } => state
                 .rpc
@@ -336,6 +556,11 @@ impl redux::EnablingCondition<crate::State> for RpcAction {
                 .get(rpc_id)
                 .map_or(false, |v| v.status.is_pending()),
             RpcAction::TransitionFrontierUserCommandsGet { .. } => true,
+            RpcAction::SubscriptionEnd { rpc_id } => state
+                .rpc
+                .requests
+                .get(rpc_id)
+                .map_or(false, |v| v.status.is_pending()),
             RpcAction::Finish { rpc_id } => state
                 .rpc
                 .requests
@@ -1,39 +1,53 @@
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use ledger::scan_state::currency::{Balance, Magnitude};
 use ledger::Account;
 use mina_p2p_messages::rpc_kernel::QueryHeader;
-use mina_p2p_messages::v2::{MinaBaseTransactionStatusStableV2, TransactionHash};
+use mina_p2p_messages::v2::{
+    MinaBaseTransactionStatusStableV2, MinaBaseUserCommandStableV2, NonZeroCurvePoint,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse,
+    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0, TransactionHash,
+};
 use mina_signer::CompressedPubKey;
 use openmina_core::block::ArcBlockWithHash;
 use openmina_core::bug_condition;
+use openmina_core::snark::Snark;
+use openmina_node_account::AccountPublicKey;
 
 use crate::block_producer::BlockProducerWonSlot;
-use crate::external_snark_worker::available_job_to_snark_worker_spec;
+use crate::external_snark_worker::{
+    available_job_to_snark_worker_spec, ExternalSnarkWorkerAction, ExternalSnarkWorkerId,
+    ExternalSnarkWorkerWorkError, SnarkWorkId,
+};
 use crate::ledger::read::{LedgerReadAction, LedgerReadRequest};
 use crate::p2p::connection::incoming::P2pConnectionIncomingAction;
 use crate::p2p::connection::outgoing::P2pConnectionOutgoingAction;
 use crate::p2p::connection::P2pConnectionResponse;
+use crate::p2p::PeerId;
 use crate::rpc::{
     AccountSlim, PeerConnectionStatus, RpcPeerInfo, RpcTransactionInjectResponse,
-    RpcTransactionInjectSuccess, TransactionStatus,
+    RpcTransactionInjectSuccess, RpcWatchedAccount, TransactionStatus,
 };
+use crate::snark::work_verify::{SnarkWorkVerifyAction, SnarkWorkVerifyId};
 use crate::snark_pool::SnarkPoolAction;
 use crate::transition_frontier::sync::ledger::TransitionFrontierSyncLedgerState;
 use crate::transition_frontier::sync::TransitionFrontierSyncState;
-use crate::{p2p_ready, Service, Store, TransactionPoolAction};
+use crate::{p2p_ready, Service, Store, TransactionPoolAction, WatchedAccountsAction};
 
 use super::{
-    ActionStatsQuery, ActionStatsResponse, CurrentMessageProgress, MessagesStats, RpcAction,
-    RpcActionWithMeta, RpcBlockProducerStats, RpcMessageProgressResponse, RpcNodeStatus,
-    RpcNodeStatusTransactionPool, RpcNodeStatusTransitionFrontier,
-    RpcNodeStatusTransitionFrontierBlockSummary, RpcNodeStatusTransitionFrontierSync, RpcRequest,
-    RpcRequestExtraData, RpcScanStateSummary, RpcScanStateSummaryBlock,
-    RpcScanStateSummaryBlockTransaction, RpcScanStateSummaryBlockTransactionKind,
-    RpcScanStateSummaryGetQuery, RpcScanStateSummaryScanStateJob, RpcSnarkPoolJobFull,
-    RpcSnarkPoolJobSnarkWork, RpcSnarkPoolJobSummary, RpcSnarkerJobCommitResponse,
-    RpcSnarkerJobSpecResponse, RpcTransactionInjectFailure, RpcTransactionInjectRejected,
+    fee_estimate, ActionStatsQuery, ActionStatsResponse, CurrentMessageProgress, GetBlockQuery,
+    MessagesStats, PooledCommandsQuery, RpcAction, RpcActionWithMeta, RpcBlockProducerStats,
+    RpcLedgerAccountsError, RpcMessageProgressResponse, RpcMetrics, RpcMetricsLedger,
+    RpcMetricsPeers, RpcNodeStatus, RpcNodeStatusSnarkPool, RpcNodeStatusTransactionPool,
+    RpcNodeStatusTransitionFrontier, RpcNodeStatusTransitionFrontierBlockSummary,
+    RpcNodeStatusTransitionFrontierSync, RpcRequest, RpcRequestExtraData, RpcScanStateSummary,
+    RpcScanStateSummaryBlock, RpcScanStateSummaryBlockTransaction,
+    RpcScanStateSummaryBlockTransactionKind, RpcScanStateSummaryGetQuery,
+    RpcScanStateSummaryScanStateJob, RpcSnarkPoolJobFull, RpcSnarkPoolJobSnarkWork,
+    RpcSnarkPoolJobSummary, RpcSnarkerJobCommitResponse, RpcSnarkerJobSpecResponse,
+    RpcSnarkerWorkerSubmitWorkResponse, RpcTransactionInjectFailure, RpcTransactionInjectRejected,
 };
 
 macro_rules! respond_or_log {
@@ -129,6 +143,7 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
 
                 let stats = store.service.stats()?;
                 let attempts = stats.block_producer().collect_attempts();
+                let producers = stats.block_producer().collect_producer_stats();
                 let future_slot = attempts.last().map_or(0, |v| v.won_slot.global_slot + 1);
 
                 let cur_global_slot = state.cur_global_slot();
@@ -152,10 +167,66 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                             (&won_slot).into()
                         })
                         .collect(),
+                    producers,
                 })
             });
             let _ = store.service.respond_block_producer_stats_get(rpc_id, resp);
         }
+        RpcAction::MetricsGet { rpc_id } => {
+            let state = store.state.get();
+            let mut stats = store.service.stats();
+            let peers = collect_rpc_peers_info(state).into_iter().fold(
+                RpcMetricsPeers::default(),
+                |mut peers, peer| {
+                    match peer.connection_status {
+                        PeerConnectionStatus::Connecting => peers.connecting += 1,
+                        PeerConnectionStatus::Connected => peers.connected += 1,
+                        PeerConnectionStatus::Disconnected => peers.disconnected += 1,
+                    }
+                    peers
+                },
+            );
+            let metrics = RpcMetrics {
+                action_stats: stats
+                    .as_ref()
+                    .map(|stats| stats.collect_action_stats_since_start()),
+                sync_stats: stats
+                    .as_ref()
+                    .and_then(|stats| stats.collect_sync_stats(Some(1)).pop()),
+                sync_phase: state.transition_frontier.sync.sync_phase().to_string(),
+                peers,
+                transaction_pool: RpcNodeStatusTransactionPool {
+                    transactions: state.transaction_pool.size(),
+                },
+                snark_pool: state.snark_pool.jobs_iter().fold(
+                    Default::default(),
+                    |mut acc: RpcNodeStatusSnarkPool, job| {
+                        acc.snarks += job.snark.is_some() as usize;
+                        acc.total_jobs += 1;
+                        acc
+                    },
+                ),
+                snark_workers: state
+                    .external_snark_worker
+                    .iter()
+                    .map(|(id, worker)| (*id, worker).into())
+                    .collect(),
+                block_producer: stats
+                    .as_mut()
+                    .filter(|_| state.block_producer.is_enabled())
+                    .map(|stats| stats.block_producer().producer_totals().clone()),
+                ledger: RpcMetricsLedger {
+                    pending_reads: state.ledger.read.pending_count(),
+                    pending_reads_cost: state.ledger.read.total_cost(),
+                    pending_writes: state.ledger.write.request().is_some() as usize,
+                },
+                p2p_traffic: stats.as_ref().map(|stats| stats.collect_p2p_traffic()),
+            };
+            respond_or_log!(
+                store.service.respond_metrics_get(rpc_id, metrics),
+                meta.time()
+            );
+        }
         RpcAction::MessageProgressGet { rpc_id } => {
             // TODO: move to stats
             let p2p = p2p_ready!(store.state().p2p, meta.time());
@@ -519,12 +590,17 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                 // TODO(binier): differentiate between job not found and job already taken.
                 return;
             }
-            if !store.state().external_snark_worker.has_idle() {
+            let Some(worker_id) = store
+                .state()
+                .external_snark_worker
+                .idle_local_workers()
+                .next()
+            else {
                 let _ = store
                     .service()
                     .respond_snarker_job_commit(rpc_id, RpcSnarkerJobCommitResponse::SnarkerBusy);
                 return;
-            }
+            };
             if store
                 .service()
                 .respond_snarker_job_commit(rpc_id, RpcSnarkerJobCommitResponse::Ok)
@@ -532,7 +608,7 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
             {
                 return;
             }
-            store.dispatch(SnarkPoolAction::CommitmentCreate { job_id });
+            store.dispatch(SnarkPoolAction::CommitmentCreate { job_id, worker_id });
         }
         RpcAction::SnarkerJobSpec { rpc_id, job_id } => {
             let Some(job) = store.state().snark_pool.get(&job_id) else {
@@ -572,12 +648,148 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
             let _ = store.service().respond_snarker_job_spec(rpc_id, input);
         }
         RpcAction::SnarkerWorkersGet { rpc_id } => {
-            let the_only = store.state().external_snark_worker.0.clone();
+            let workers = store
+                .state()
+                .external_snark_worker
+                .iter()
+                .map(|(id, worker)| (*id, worker).into())
+                .collect();
 
             // TODO: handle potential errors
-            let _ = store
-                .service()
-                .respond_snarker_workers(rpc_id, vec![the_only.into()]);
+            let _ = store.service().respond_snarker_workers(rpc_id, workers);
+        }
+        RpcAction::SnarkerWorkerGetWork {
+            rpc_id,
+            connection_id,
+        } => {
+            let worker_id = ExternalSnarkWorkerId::Remote(connection_id);
+            store.dispatch(ExternalSnarkWorkerAction::Started { worker_id });
+
+            let state = store.state();
+            let job_id = state
+                .snark_pool
+                .available_jobs_with_highest_priority(1)
+                .first()
+                .map(|job| job.id.clone());
+            if let Some(job_id) = job_id {
+                store.dispatch(SnarkPoolAction::CommitmentCreate { job_id, worker_id });
+            }
+
+            let state = store.state();
+            let job = state
+                .external_snark_worker
+                .get(&worker_id)
+                .and_then(|worker| worker.working_job_id())
+                .and_then(|job_id| state.snark_pool.get(job_id));
+            let work = match (job, state.config.snarker.as_ref()) {
+                (Some(job), Some(config)) => {
+                    match available_job_to_snark_worker_spec(
+                        job.job.clone(),
+                        &state.transition_frontier,
+                    ) {
+                        Ok(instances) => Some((
+                            SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponseA0 {
+                                instances,
+                                fee: config.fee.clone(),
+                            },
+                            config.public_key.clone().into(),
+                        )),
+                        Err(err) => {
+                            store.dispatch(ExternalSnarkWorkerAction::WorkError {
+                                worker_id,
+                                error: err.into(),
+                            });
+                            None
+                        }
+                    }
+                }
+                _ => {
+                    // No job for the worker, forget about it.
+                    store.dispatch(ExternalSnarkWorkerAction::Kill { worker_id });
+                    None
+                }
+            };
+            respond_or_log!(
+                store.service().respond_snarker_worker_get_work(
+                    rpc_id,
+                    SnarkWorkerWorkerRpcsVersionedGetWorkV2TResponse(work)
+                ),
+                meta.time()
+            );
+        }
+        RpcAction::SnarkerWorkerSubmitWork {
+            rpc_id,
+            connection_id,
+            work,
+        } => {
+            let job_id = SnarkWorkId::from(&work.proofs);
+            let worker_id = ExternalSnarkWorkerId::Remote(connection_id);
+            let state = store.state();
+            let is_assigned = state
+                .external_snark_worker
+                .is_worker_working_on(&worker_id, &job_id);
+            let response = match (is_assigned, &state.config.snarker) {
+                (true, Some(config)) => {
+                    // Proof is added to the snark pool under our key, so
+                    // it must be verified first.
+                    let snark = Snark {
+                        snarker: config.public_key.clone().into(),
+                        fee: config.fee.clone(),
+                        proofs: Arc::new(work.proofs),
+                    };
+                    let req_id = state.snark.work_verify.next_req_id();
+                    store.dispatch(SnarkWorkVerifyAction::Init {
+                        req_id,
+                        batch: vec![snark],
+                        sender: worker_id.to_string(),
+                        on_success: redux::callback!(
+                            on_remote_snark_worker_work_verify_success((_req_id: SnarkWorkVerifyId, sender: String, batch: Vec<Snark>)) -> crate::Action {
+                                ExternalSnarkWorkerAction::WorkResult {
+                                    worker_id: sender.parse().unwrap(),
+                                    result: batch[0].proofs.clone(),
+                                }
+                            }),
+                        on_error: redux::callback!(
+                            on_remote_snark_worker_work_verify_error((_req_id: SnarkWorkVerifyId, sender: String)) -> crate::Action {
+                                ExternalSnarkWorkerAction::WorkError {
+                                    worker_id: sender.parse().unwrap(),
+                                    error: ExternalSnarkWorkerWorkError::VerificationFailed,
+                                }
+                            }),
+                    });
+                    RpcSnarkerWorkerSubmitWorkResponse::Ok
+                }
+                _ => RpcSnarkerWorkerSubmitWorkResponse::NotAssigned,
+            };
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_snarker_worker_submit_work(rpc_id, response),
+                meta.time()
+            );
+        }
+        RpcAction::SnarkerWorkerFailed {
+            rpc_id,
+            connection_id,
+            job_id,
+            error,
+        } => {
+            let worker_id = ExternalSnarkWorkerId::Remote(connection_id);
+            // Only the worker the job was given to can fail it.
+            if store
+                .state()
+                .external_snark_worker
+                .is_worker_working_on(&worker_id, &job_id)
+            {
+                store.dispatch(ExternalSnarkWorkerAction::WorkError {
+                    worker_id,
+                    error: ExternalSnarkWorkerWorkError::Error(error),
+                });
+            }
+            respond_or_log!(
+                store.service().respond_snarker_worker_failed(rpc_id, ()),
+                meta.time()
+            );
         }
         RpcAction::HealthCheck { rpc_id } => {
             let some_peers = store
@@ -691,18 +903,17 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
         RpcAction::LedgerAccountsGetInit {
             rpc_id,
             account_query,
+            ledger_hash,
         } => {
-            let ledger_hash = if let Some(best_tip) = store.state().transition_frontier.best_tip() {
-                best_tip.merkle_root_hash()
-            } else {
-                return;
+            let ledger_hash = match ledger_hash {
+                Some(ledger_hash) => ledger_hash,
+                None => match store.state().transition_frontier.best_tip() {
+                    Some(best_tip) => best_tip.merkle_root_hash().clone(),
+                    None => return,
+                },
             };
             if store.dispatch(LedgerReadAction::Init {
-                request: LedgerReadRequest::AccountsForRpc(
-                    rpc_id,
-                    ledger_hash.clone(),
-                    account_query,
-                ),
+                request: LedgerReadRequest::AccountsForRpc(rpc_id, ledger_hash, account_query),
             }) {
                 store.dispatch(RpcAction::LedgerAccountsGetPending { rpc_id });
             }
@@ -715,10 +926,10 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
         } => {
             // TODO(adonagy): maybe something more effective?
             match account_query {
-                super::AccountQuery::SinglePublicKey(_pk) => todo!(),
                 // all the accounts for the FE in Slim form
                 super::AccountQuery::All => {
                     let mut accounts: BTreeMap<CompressedPubKey, Account> = accounts
+                        .unwrap_or_default()
                         .into_iter()
                         .map(|acc| (acc.public_key.clone(), acc))
                         .collect();
@@ -756,10 +967,13 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                         meta.time()
                     )
                 }
-                // for the graphql endpoint
-                super::AccountQuery::PubKeyWithTokenId(..) => {
+                super::AccountQuery::SinglePublicKey(..)
+                | super::AccountQuery::PubKeyWithTokenId(..)
+                | super::AccountQuery::TokenOwner(..)
+                | super::AccountQuery::Ledger => {
+                    let response = accounts.ok_or(RpcLedgerAccountsError::LedgerNotFound);
                     respond_or_log!(
-                        store.service().respond_ledger_accounts(rpc_id, accounts),
+                        store.service().respond_ledger_accounts(rpc_id, response),
                         meta.time()
                     )
                 }
@@ -779,6 +993,7 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
 
             store.dispatch(TransactionPoolAction::StartVerify {
                 commands: commands.into_iter().collect(),
+                is_sender_local: true,
                 from_rpc: Some(rpc_id),
             });
         }
@@ -851,6 +1066,24 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                 meta.time()
             )
         }
+        RpcAction::BestTipSubscribe { .. } => {}
+        RpcAction::BestTipSubscribersNotify => {
+            let state = store.state();
+            let Some(best_tip) = state.transition_frontier.best_chain.last().cloned() else {
+                return;
+            };
+            let rpc_ids = state.rpc.best_tip_subscriptions().collect::<Vec<_>>();
+            for rpc_id in rpc_ids {
+                let response = best_tip.clone();
+                if store
+                    .service()
+                    .respond_best_tip_subscribe(rpc_id, response)
+                    .is_err()
+                {
+                    store.dispatch(RpcAction::SubscriptionEnd { rpc_id });
+                }
+            }
+        }
         RpcAction::ConsensusConstantsGet { rpc_id } => {
             let response = store.state().config.consensus_constants.clone();
             respond_or_log!(
@@ -910,12 +1143,340 @@ pub fn rpc_effects<S: Service>(store: &mut Store<S>, action: RpcActionWithMeta)
                 )
             }
         }
+        RpcAction::BlockGet { rpc_id, query } => {
+            let best_chain = &store.state().transition_frontier.best_chain;
+            let block = match query {
+                GetBlockQuery::Hash(hash) => best_chain.iter().find(|b| b.hash() == &hash),
+                GetBlockQuery::Height(height) => best_chain.iter().find(|b| b.height() == height),
+            }
+            .cloned();
+
+            respond_or_log!(
+                store.service().respond_block_get(rpc_id, block),
+                meta.time()
+            )
+        }
+        RpcAction::GenesisBlockGet { rpc_id } => {
+            let genesis_block = store
+                .state()
+                .transition_frontier
+                .genesis
+                .block_with_real_or_dummy_proof();
+
+            respond_or_log!(
+                store.service().respond_genesis_block(rpc_id, genesis_block),
+                meta.time()
+            )
+        }
+        RpcAction::PooledUserCommands { rpc_id, query } => {
+            let commands = pooled_commands(store.state(), &query)
+                .filter_map(|cmd| match cmd {
+                    MinaBaseUserCommandStableV2::SignedCommand(cmd) => Some(cmd),
+                    MinaBaseUserCommandStableV2::ZkappCommand(_) => None,
+                })
+                .collect();
+
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_pooled_user_commands(rpc_id, commands),
+                meta.time()
+            )
+        }
+        RpcAction::PooledZkappCommands { rpc_id, query } => {
+            let commands = pooled_commands(store.state(), &query)
+                .filter_map(|cmd| match cmd {
+                    MinaBaseUserCommandStableV2::SignedCommand(_) => None,
+                    MinaBaseUserCommandStableV2::ZkappCommand(cmd) => Some(cmd),
+                })
+                .collect();
+
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_pooled_zkapp_commands(rpc_id, commands),
+                meta.time()
+            )
+        }
+        RpcAction::HardForkConfigGetInit { rpc_id, .. } => {
+            store.dispatch(RpcAction::HardForkConfigLedgersGetInit { rpc_id });
+        }
+        RpcAction::HardForkConfigLedgersGetInit { rpc_id } => {
+            let transition_frontier = &store.state().transition_frontier;
+
+            let Some(block_hash) = None.or_else(|| {
+                let req = store.state().rpc.requests.get(&rpc_id)?;
+                match &req.req {
+                    RpcRequest::HardForkConfigGet(block_hash) => Some(block_hash),
+                    _ => None,
+                }
+            }) else {
+                return;
+            };
+
+            let block = match block_hash {
+                None => transition_frontier.best_tip_breadcrumb(),
+                Some(hash) => transition_frontier
+                    .best_chain
+                    .iter()
+                    .rev()
+                    .find(|b| b.hash() == hash),
+            };
+            let block = match block {
+                Some(v) => v.clone(),
+                None => {
+                    store.dispatch(RpcAction::HardForkConfigGetPending {
+                        rpc_id,
+                        block: None,
+                    });
+                    store.dispatch(RpcAction::HardForkConfigGetSuccess {
+                        rpc_id,
+                        config: Err("target block not found".to_owned()),
+                    });
+                    return;
+                }
+            };
+            if store.dispatch(LedgerReadAction::Init {
+                request: LedgerReadRequest::HardForkConfig(rpc_id, block.block.clone()),
+            }) {
+                store.dispatch(RpcAction::HardForkConfigGetPending {
+                    rpc_id,
+                    block: Some(block),
+                });
+            }
+        }
+        RpcAction::HardForkConfigGetPending { .. } => {}
+        RpcAction::HardForkConfigGetSuccess { rpc_id, config } => {
+            respond_or_log!(
+                store.service().respond_hard_fork_config_get(rpc_id, config),
+                meta.time()
+            )
+        }
+        RpcAction::WatchedAccountsAdd { rpc_id, public_key } => {
+            let added = store.dispatch(WatchedAccountsAction::Add {
+                pub_key: public_key.into(),
+            });
+            respond_or_log!(
+                store.service().respond_watched_accounts_add(rpc_id, added),
+                meta.time()
+            )
+        }
+        RpcAction::WatchedAccountsRemove { rpc_id, public_key } => {
+            let removed = store.dispatch(WatchedAccountsAction::Remove {
+                pub_key: public_key.into(),
+            });
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_watched_accounts_remove(rpc_id, removed),
+                meta.time()
+            )
+        }
+        RpcAction::WatchedAccountsGet { rpc_id, public_key } => {
+            let public_key = public_key.map(NonZeroCurvePoint::from);
+            let accounts = store
+                .state()
+                .watched_accounts
+                .iter()
+                .filter(|(pub_key, _)| public_key.as_ref().map_or(true, |key| key == *pub_key))
+                .map(|(pub_key, state)| RpcWatchedAccount::new(pub_key, state))
+                .collect();
+            respond_or_log!(
+                store
+                    .service()
+                    .respond_watched_accounts_get(rpc_id, accounts),
+                meta.time()
+            )
+        }
+        RpcAction::WatchedAccountSubscribe { rpc_id, public_key } => {
+            let public_key = NonZeroCurvePoint::from(public_key);
+            if !store.state().watched_accounts.contains(&public_key) {
+                store.dispatch(RpcAction::SubscriptionEnd { rpc_id });
+            }
+        }
+        RpcAction::WatchedAccountSubscribersNotify { public_key } => {
+            let state = store.state();
+            let rpc_ids = state
+                .rpc
+                .watched_account_subscriptions(&public_key)
+                .collect::<Vec<_>>();
+            let public_key = NonZeroCurvePoint::from(public_key);
+            let account = state
+                .watched_accounts
+                .get(&public_key)
+                .map(|account| RpcWatchedAccount::new(&public_key, account));
+            for rpc_id in rpc_ids {
+                let is_sent = account.clone().map_or(false, |account| {
+                    store
+                        .service()
+                        .respond_watched_account_subscribe(rpc_id, account)
+                        .is_ok()
+                });
+                if !is_sent {
+                    store.dispatch(RpcAction::SubscriptionEnd { rpc_id });
+                }
+            }
+        }
+        RpcAction::TransactionSimulateInit { rpc_id, .. } => {
+            store.dispatch(RpcAction::TransactionSimulateLedgerInit { rpc_id });
+        }
+        RpcAction::TransactionSimulateLedgerInit { rpc_id } => {
+            let Some(command) = None.or_else(|| {
+                let req = store.state().rpc.requests.get(&rpc_id)?;
+                match &req.req {
+                    RpcRequest::TransactionSimulate(command) => Some(command.clone()),
+                    _ => None,
+                }
+            }) else {
+                return;
+            };
+            let Some(best_tip) = store.state().transition_frontier.best_tip().cloned() else {
+                store.dispatch(RpcAction::TransactionSimulatePending { rpc_id });
+                store.dispatch(RpcAction::TransactionSimulateSuccess {
+                    rpc_id,
+                    result: Err("best tip not available".to_string()),
+                });
+                return;
+            };
+            if store.dispatch(LedgerReadAction::Init {
+                request: LedgerReadRequest::TransactionSimulate(
+                    rpc_id,
+                    best_tip,
+                    Box::new(command),
+                ),
+            }) {
+                store.dispatch(RpcAction::TransactionSimulatePending { rpc_id });
+            }
+        }
+        RpcAction::TransactionSimulatePending { .. } => {}
+        RpcAction::TransactionSimulateSuccess { rpc_id, result } => {
+            respond_or_log!(
+                store.service().respond_transaction_simulate(rpc_id, result),
+                meta.time()
+            )
+        }
+        RpcAction::FeeEstimate { rpc_id, query } => {
+            let estimate = fee_estimate(store.state(), &query);
+            respond_or_log!(
+                store.service().respond_fee_estimate(rpc_id, estimate),
+                meta.time()
+            )
+        }
+        RpcAction::ZkappEventsGetInit { rpc_id, .. } => {
+            store.dispatch(RpcAction::ZkappEventsGetLedgerInit { rpc_id });
+        }
+        RpcAction::ZkappEventsGetLedgerInit { rpc_id } => {
+            let Some(query) = None.or_else(|| {
+                let req = store.state().rpc.requests.get(&rpc_id)?;
+                match &req.req {
+                    RpcRequest::ZkappEventsGet(query) => Some(query.clone()),
+                    _ => None,
+                }
+            }) else {
+                return;
+            };
+            let best_chain = store
+                .state()
+                .transition_frontier
+                .best_chain
+                .iter()
+                .map(|block| (block.height(), block.hash().clone()))
+                .collect();
+            if store.dispatch(LedgerReadAction::Init {
+                request: LedgerReadRequest::ZkappEvents(rpc_id, query, best_chain),
+            }) {
+                store.dispatch(RpcAction::ZkappEventsGetPending { rpc_id });
+            }
+        }
+        RpcAction::ZkappEventsGetPending { .. } => {}
+        RpcAction::ZkappEventsGetSuccess { rpc_id, result } => {
+            respond_or_log!(
+                store.service().respond_zkapp_events_get(rpc_id, result),
+                meta.time()
+            )
+        }
+        RpcAction::ArchiveGetInit { rpc_id, .. } => {
+            store.dispatch(RpcAction::ArchiveGetLedgerInit { rpc_id });
+        }
+        RpcAction::ArchiveGetLedgerInit { rpc_id } => {
+            let Some(query) = None.or_else(|| {
+                let req = store.state().rpc.requests.get(&rpc_id)?;
+                match &req.req {
+                    RpcRequest::ArchiveGet(query) => Some(query.clone()),
+                    _ => None,
+                }
+            }) else {
+                return;
+            };
+            if store.dispatch(LedgerReadAction::Init {
+                request: LedgerReadRequest::ArchiveGet(rpc_id, query),
+            }) {
+                store.dispatch(RpcAction::ArchiveGetPending { rpc_id });
+            }
+        }
+        RpcAction::ArchiveGetPending { .. } => {}
+        RpcAction::ArchiveGetSuccess { rpc_id, result } => {
+            respond_or_log!(
+                store.service().respond_archive_get(rpc_id, result),
+                meta.time()
+            )
+        }
+        RpcAction::SubscriptionEnd { rpc_id } => {
+            store.service().respond_subscription_end(rpc_id);
+            store.dispatch(RpcAction::Finish { rpc_id });
+        }
         RpcAction::Finish { .. } => {}
     }
 }
 
+fn pooled_commands<'a>(
+    state: &'a crate::State,
+    query: &'a PooledCommandsQuery,
+) -> impl 'a + Iterator<Item = MinaBaseUserCommandStableV2> {
+    state
+        .transaction_pool
+        .get_all_transactions()
+        .into_iter()
+        .filter(|tx| {
+            query.public_key.as_ref().map_or(true, |public_key| {
+                public_key == &AccountPublicKey::from(tx.data.fee_payer().public_key)
+            })
+        })
+        .filter(|tx| {
+            query.hashes.as_ref().map_or(true, |hashes| {
+                hashes.contains(&TransactionHash::from(tx.hash.as_ref()))
+            })
+        })
+        .map(|tx| tx.data.into())
+}
+
 fn collect_rpc_peers_info(state: &crate::State) -> Vec<RpcPeerInfo> {
+    let now = state.time();
     state.p2p.ready().map_or_else(Vec::new, |p2p| {
+        let banned_until = |peer_id: &PeerId| {
+            p2p.peer_scores
+                .banned
+                .get(peer_id)
+                .map(|ban| ban.until.into())
+        };
+        // banned peers are usually already removed from the peers list.
+        let banned_peers = p2p
+            .peer_scores
+            .banned
+            .iter()
+            .filter(|(peer_id, _)| !p2p.peers.contains_key(peer_id))
+            .map(|(peer_id, ban)| RpcPeerInfo {
+                peer_id: *peer_id,
+                connection_status: PeerConnectionStatus::Disconnected,
+                address: None,
+                best_tip: None,
+                best_tip_height: None,
+                best_tip_global_slot: None,
+                best_tip_timestamp: None,
+                time: ban.since.into(),
+                score: p2p.peer_scores.score(peer_id, now),
+                banned_until: banned_until(peer_id),
+            });
         p2p.peers
             .iter()
             .map(|(peer_id, state)| {
@@ -948,8 +1509,11 @@ fn collect_rpc_peers_info(state: &crate::State) -> Vec<RpcPeerInfo> {
                     best_tip_global_slot: best_tip.map(|bt| bt.global_slot_since_genesis()),
                     best_tip_timestamp: best_tip.map(|bt| bt.timestamp().into()),
                     time,
+                    score: p2p.peer_scores.score(peer_id, now),
+                    banned_until: banned_until(peer_id),
                 }
             })
+            .chain(banned_peers)
             .collect()
     })
 }
@@ -0,0 +1,199 @@
+use ledger::scan_state::currency::{Fee, Nonce};
+use ledger::scan_state::transaction_logic::{UserCommand, MINIMUM_USER_COMMAND_FEE};
+use ledger::{AccountId, TokenId};
+use openmina_core::constants::constraint_constants;
+
+use super::{RpcFeeEstimate, RpcFeeEstimateQuery, RpcFeeLevels, RpcReplaceFee};
+
+const FEE_ESTIMATE_DEFAULT_BLOCKS: u32 = 10;
+
+/// Suggests fees from the fees per weight unit paid by the commands of the
+/// recent best chain blocks and by the ones the next block would include
+/// given the transaction pool.
+pub fn fee_estimate(state: &crate::State, query: &RpcFeeEstimateQuery) -> RpcFeeEstimate {
+    let pool = &state.transaction_pool;
+    let fee_rate = |cmd: &UserCommand| cmd.fee().as_u64() as f64 / cmd.weight().max(1) as f64;
+
+    let blocks = query.blocks.unwrap_or(FEE_ESTIMATE_DEFAULT_BLOCKS) as usize;
+    let best_chain = &state.transition_frontier.best_chain;
+    let mut recent = best_chain
+        .iter()
+        .skip(best_chain.len().saturating_sub(blocks))
+        .flat_map(|block| block.body().commands_iter())
+        .filter_map(|cmd| UserCommand::try_from(&cmd.data).ok())
+        .map(|cmd| fee_rate(&cmd))
+        .collect::<Vec<_>>();
+    recent.sort_by(f64::total_cmp);
+
+    let capacity = 2usize.pow(constraint_constants().transaction_capacity_log_2 as u32);
+    let rates = FeeRates {
+        recent,
+        next_block: pool
+            .list_includable_transactions(capacity)
+            .iter()
+            .map(|cmd| fee_rate(&cmd.data.forget_check()))
+            .collect(),
+        capacity,
+        pool_min: match pool.is_full() {
+            true => pool.min_fee_rate().map(|rate| rate.to_f64()),
+            false => None,
+        },
+    };
+    let zkapp_weight = 1 + query.zkapp_account_updates.unwrap_or(1) as u64;
+
+    let replace = query
+        .public_key
+        .clone()
+        .zip(query.nonce)
+        .and_then(|(public_key, nonce)| {
+            let sender = AccountId::new(public_key.try_into().ok()?, TokenId::default());
+            pool.replace_fee(&sender, Nonce::from_u32(nonce))
+        })
+        .map(|(min_fee, min_fee_with_dependents)| RpcReplaceFee {
+            min_fee,
+            min_fee_with_dependents,
+        });
+
+    RpcFeeEstimate {
+        signed_command: rates.levels(1),
+        zkapp_command: rates.levels(zkapp_weight),
+        replace,
+    }
+}
+
+/// Fees per weight unit the estimate is based on.
+struct FeeRates {
+    /// Paid by the commands of the recent best chain blocks, ascending.
+    recent: Vec<f64>,
+    /// Paid by the commands the next block would include, descending.
+    next_block: Vec<f64>,
+    /// Commands that fit in a block.
+    capacity: usize,
+    /// Lowest one in the pool, if it's full.
+    pool_min: Option<f64>,
+}
+
+impl FeeRates {
+    fn levels(&self, weight: u64) -> RpcFeeLevels {
+        let fee = |rate: f64| (rate * weight as f64).ceil() as u64;
+        // Competing with the next block only matters if it's full.
+        let next_block_min = match self.next_block.len() >= self.capacity {
+            true => self.next_block.last().copied().unwrap_or(0.0),
+            false => 0.0,
+        };
+        let next_block_half = self
+            .next_block
+            .get(self.capacity / 2)
+            .copied()
+            .unwrap_or(0.0);
+
+        // A full pool only accepts commands paying more than its cheapest one.
+        let min = self
+            .pool_min
+            .map_or(0, |rate| fee(rate) + 1)
+            .max(MINIMUM_USER_COMMAND_FEE.as_u64());
+        let low = fee(percentile(&self.recent, 25)).max(min);
+        let medium = fee(percentile(&self.recent, 50))
+            .max(fee(next_block_min))
+            .max(low);
+        let high = fee(percentile(&self.recent, 90))
+            .max(fee(next_block_half))
+            .max(medium);
+        RpcFeeLevels {
+            low: Fee::from_u64(low),
+            medium: Fee::from_u64(medium),
+            high: Fee::from_u64(high),
+        }
+    }
+}
+
+/// Nearest-rank percentile `p` of the ascending `rates`, 0 if empty.
+fn percentile(rates: &[f64], p: usize) -> f64 {
+    match rates.len() {
+        0 => 0.0,
+        len => rates[(len - 1) * p / 100],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use redux::Timestamp;
+
+    use super::*;
+
+    const MINIMUM: u64 = MINIMUM_USER_COMMAND_FEE.as_u64();
+
+    fn fees(levels: &RpcFeeLevels) -> [u64; 3] {
+        [levels.low, levels.medium, levels.high].map(|fee| fee.as_u64())
+    }
+
+    fn rates(recent: impl IntoIterator<Item = u64>) -> FeeRates {
+        FeeRates {
+            recent: recent.into_iter().map(|rate| rate as f64).collect(),
+            next_block: Vec::new(),
+            capacity: 4,
+            pool_min: None,
+        }
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 50), 0.0);
+        assert_eq!(percentile(&[7.0], 90), 7.0);
+
+        let rates = (1..=11).map(|rate| rate as f64).collect::<Vec<_>>();
+        assert_eq!(percentile(&rates, 0), 1.0);
+        assert_eq!(percentile(&rates, 25), 3.0);
+        assert_eq!(percentile(&rates, 50), 6.0);
+        assert_eq!(percentile(&rates, 90), 10.0);
+        assert_eq!(percentile(&rates, 100), 11.0);
+    }
+
+    #[test]
+    fn test_levels_from_recent_blocks() {
+        let rates = rates((1..=11).map(|rate| rate * MINIMUM));
+        assert_eq!(fees(&rates.levels(1)), [3, 6, 10].map(|n| n * MINIMUM));
+        // Fees scale with the weight of the command.
+        assert_eq!(fees(&rates.levels(3)), [9, 18, 30].map(|n| n * MINIMUM));
+    }
+
+    #[test]
+    fn test_levels_never_below_the_minimum() {
+        assert_eq!(fees(&rates([]).levels(1)), [MINIMUM; 3]);
+        assert_eq!(fees(&rates([1, 2, 3]).levels(2)), [MINIMUM; 3]);
+    }
+
+    #[test]
+    fn test_levels_compete_with_the_next_block() {
+        let mut rates = rates([MINIMUM; 10]);
+
+        // The cheapest command of the next block only matters if it's full.
+        rates.next_block = [8, 6, 4].map(|n| (n * MINIMUM) as f64).to_vec();
+        assert_eq!(fees(&rates.levels(1)), [MINIMUM, MINIMUM, 4 * MINIMUM]);
+
+        rates.next_block = [8, 6, 4, 2].map(|n| (n * MINIMUM) as f64).to_vec();
+        assert_eq!(fees(&rates.levels(1)), [MINIMUM, 2 * MINIMUM, 4 * MINIMUM]);
+    }
+
+    #[test]
+    fn test_levels_outbid_a_full_pool() {
+        let mut rates = rates([MINIMUM; 10]);
+        rates.pool_min = Some(5.5 * MINIMUM as f64);
+        let min = 5 * MINIMUM + MINIMUM / 2 + 1;
+        assert_eq!(fees(&rates.levels(1)), [min; 3]);
+        assert_eq!(fees(&rates.levels(2)), [2 * min - 1; 3]);
+    }
+
+    #[test]
+    fn test_fee_estimate_without_data() {
+        let state = crate::State::for_tests(Timestamp::ZERO);
+        let query = RpcFeeEstimateQuery {
+            zkapp_account_updates: Some(3),
+            ..Default::default()
+        };
+        let estimate = fee_estimate(&state, &query);
+        assert_eq!(fees(&estimate.signed_command), [MINIMUM; 3]);
+        assert_eq!(fees(&estimate.zkapp_command), [MINIMUM; 3]);
+        assert!(estimate.replace.is_none());
+    }
+}
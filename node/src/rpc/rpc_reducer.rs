@@ -12,6 +12,7 @@ impl RpcState {
             RpcAction::ActionStatsGet { .. } => {}
             RpcAction::SyncStatsGet { .. } => {}
             RpcAction::BlockProducerStatsGet { .. } => {}
+            RpcAction::MetricsGet { .. } => {}
             RpcAction::MessageProgressGet { .. } => {}
             RpcAction::PeersGet { .. } => {}
             RpcAction::P2pConnectionOutgoingInit { rpc_id, opts } => {
@@ -101,10 +102,19 @@ impl RpcState {
             RpcAction::SnarkerJobCommit { .. } => {}
             RpcAction::SnarkerJobSpec { .. } => {}
             RpcAction::SnarkerWorkersGet { .. } => {}
+            RpcAction::SnarkerWorkerGetWork { .. } => {}
+            RpcAction::SnarkerWorkerSubmitWork { .. } => {}
+            RpcAction::SnarkerWorkerFailed { .. } => {}
             RpcAction::HealthCheck { .. } => {}
             RpcAction::ReadinessCheck { .. } => {}
             RpcAction::DiscoveryRoutingTable { .. } => {}
             RpcAction::DiscoveryBoostrapStats { .. } => {}
+            RpcAction::SubscriptionEnd { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Success { time: meta.time() };
+            }
             RpcAction::Finish { rpc_id } => {
                 self.requests.remove(rpc_id);
             }
@@ -112,9 +122,10 @@ impl RpcState {
             RpcAction::LedgerAccountsGetInit {
                 rpc_id,
                 account_query,
+                ledger_hash,
             } => {
                 let rpc_state = RpcRequestState {
-                    req: RpcRequest::LedgerAccountsGet(account_query.clone()),
+                    req: RpcRequest::LedgerAccountsGet(account_query.clone(), ledger_hash.clone()),
                     status: RpcRequestStatus::Init { time: meta.time() },
                     data: Default::default(),
                 };
@@ -169,8 +180,119 @@ impl RpcState {
             }
             RpcAction::TransitionFrontierUserCommandsGet { .. } => {}
             RpcAction::BestChain { .. } => {}
+            RpcAction::BestTipSubscribe { rpc_id } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::BestTipSubscribe,
+                    status: RpcRequestStatus::Pending { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::BestTipSubscribersNotify => {}
             RpcAction::ConsensusConstantsGet { .. } => {}
             RpcAction::TransactionStatusGet { .. } => {}
+            RpcAction::BlockGet { .. } => {}
+            RpcAction::GenesisBlockGet { .. } => {}
+            RpcAction::PooledUserCommands { .. } => {}
+            RpcAction::PooledZkappCommands { .. } => {}
+            RpcAction::HardForkConfigGetInit { rpc_id, block_hash } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::HardForkConfigGet(block_hash.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::HardForkConfigLedgersGetInit { .. } => {}
+            RpcAction::HardForkConfigGetPending { rpc_id, block } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+                rpc.data = RpcRequestExtraData::FullBlockOpt(block.clone());
+            }
+            RpcAction::HardForkConfigGetSuccess { rpc_id, .. } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Success { time: meta.time() };
+            }
+            RpcAction::WatchedAccountsAdd { .. } => {}
+            RpcAction::WatchedAccountsRemove { .. } => {}
+            RpcAction::WatchedAccountsGet { .. } => {}
+            RpcAction::WatchedAccountSubscribe { rpc_id, public_key } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::WatchedAccountSubscribe(public_key.clone()),
+                    status: RpcRequestStatus::Pending { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::WatchedAccountSubscribersNotify { .. } => {}
+            RpcAction::TransactionSimulateInit { rpc_id, command } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::TransactionSimulate(command.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::TransactionSimulateLedgerInit { .. } => {}
+            RpcAction::TransactionSimulatePending { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+            }
+            RpcAction::TransactionSimulateSuccess { rpc_id, .. } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Success { time: meta.time() };
+            }
+            RpcAction::FeeEstimate { .. } => {}
+            RpcAction::ZkappEventsGetInit { rpc_id, query } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::ZkappEventsGet(query.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::ZkappEventsGetLedgerInit { .. } => {}
+            RpcAction::ZkappEventsGetPending { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+            }
+            RpcAction::ZkappEventsGetSuccess { rpc_id, .. } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Success { time: meta.time() };
+            }
+            RpcAction::ArchiveGetInit { rpc_id, query } => {
+                let rpc_state = RpcRequestState {
+                    req: RpcRequest::ArchiveGet(query.clone()),
+                    status: RpcRequestStatus::Init { time: meta.time() },
+                    data: Default::default(),
+                };
+                self.requests.insert(*rpc_id, rpc_state);
+            }
+            RpcAction::ArchiveGetLedgerInit { .. } => {}
+            RpcAction::ArchiveGetPending { rpc_id } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Pending { time: meta.time() };
+            }
+            RpcAction::ArchiveGetSuccess { rpc_id, .. } => {
+                let Some(rpc) = self.requests.get_mut(rpc_id) else {
+                    return;
+                };
+                rpc.status = RpcRequestStatus::Success { time: meta.time() };
+            }
             RpcAction::P2pConnectionIncomingAnswerReady { .. } => {}
         }
     }
@@ -6,15 +6,22 @@ use crate::p2p::connection::P2pConnectionResponse;
 use crate::State;
 
 use super::{
-    RpcActionStatsGetResponse, RpcBestChainResponse, RpcBlockProducerStatsGetResponse,
-    RpcDiscoveryBoostrapStatsResponse, RpcDiscoveryRoutingTableResponse, RpcHealthCheckResponse,
-    RpcId, RpcLedgerAccountsResponse, RpcLedgerSlimAccountsResponse, RpcMessageProgressResponse,
-    RpcP2pConnectionOutgoingResponse, RpcPeersGetResponse, RpcReadinessCheckResponse,
-    RpcScanStateSummaryGetResponse, RpcSnarkPoolGetResponse, RpcSnarkPoolJobGetResponse,
-    RpcSnarkerJobCommitResponse, RpcSnarkerJobSpecResponse, RpcSnarkerWorkersResponse,
-    RpcStatusGetResponse, RpcSyncStatsGetResponse, RpcTransactionInjectResponse,
-    RpcTransactionPoolResponse, RpcTransactionStatusGetResponse,
-    RpcTransitionFrontierUserCommandsResponse,
+    RpcActionStatsGetResponse, RpcArchiveGetResponse, RpcBestChainResponse,
+    RpcBestTipSubscribeResponse, RpcBlockProducerStatsGetResponse,
+    RpcDiscoveryBoostrapStatsResponse, RpcDiscoveryRoutingTableResponse, RpcFeeEstimateResponse,
+    RpcGenesisBlockResponse, RpcGetBlockResponse, RpcHardForkConfigGetResponse,
+    RpcHealthCheckResponse, RpcId, RpcLedgerAccountsResponse, RpcLedgerSlimAccountsResponse,
+    RpcMessageProgressResponse, RpcMetricsGetResponse, RpcP2pConnectionOutgoingResponse,
+    RpcPeersGetResponse, RpcPooledUserCommandsResponse, RpcPooledZkappCommandsResponse,
+    RpcReadinessCheckResponse, RpcScanStateSummaryGetResponse, RpcSnarkPoolGetResponse,
+    RpcSnarkPoolJobGetResponse, RpcSnarkerJobCommitResponse, RpcSnarkerJobSpecResponse,
+    RpcSnarkerWorkerFailedResponse, RpcSnarkerWorkerGetWorkResponse,
+    RpcSnarkerWorkerSubmitWorkResponse, RpcSnarkerWorkersResponse, RpcStatusGetResponse,
+    RpcSyncStatsGetResponse, RpcTransactionInjectResponse, RpcTransactionPoolResponse,
+    RpcTransactionSimulateResponse, RpcTransactionStatusGetResponse,
+    RpcTransitionFrontierUserCommandsResponse, RpcWatchedAccountSubscribeResponse,
+    RpcWatchedAccountsAddResponse, RpcWatchedAccountsGetResponse, RpcWatchedAccountsRemoveResponse,
+    RpcZkappEventsResponse,
 };
 
 #[derive(Error, Serialize, Deserialize, Debug, Clone)]
@@ -67,6 +74,11 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcBlockProducerStatsGetResponse,
     ) -> Result<(), RespondError>;
+    fn respond_metrics_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcMetricsGetResponse,
+    ) -> Result<(), RespondError>;
     fn respond_message_progress_stats_get(
         &mut self,
         rpc_id: RpcId,
@@ -127,6 +139,21 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcSnarkerWorkersResponse,
     ) -> Result<(), RespondError>;
+    fn respond_snarker_worker_get_work(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcSnarkerWorkerGetWorkResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_snarker_worker_submit_work(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcSnarkerWorkerSubmitWorkResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_snarker_worker_failed(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcSnarkerWorkerFailedResponse,
+    ) -> Result<(), RespondError>;
     fn respond_health_check(
         &mut self,
         rpc_id: RpcId,
@@ -177,6 +204,12 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcBestChainResponse,
     ) -> Result<(), RespondError>;
+    /// Pushes the update to the subscriber, keeping the subscription open.
+    fn respond_best_tip_subscribe(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcBestTipSubscribeResponse,
+    ) -> Result<(), RespondError>;
     fn respond_consensus_constants(
         &mut self,
         rpc_id: RpcId,
@@ -187,4 +220,72 @@ pub trait RpcService {
         rpc_id: RpcId,
         response: RpcTransactionStatusGetResponse,
     ) -> Result<(), RespondError>;
+    fn respond_block_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcGetBlockResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_genesis_block(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcGenesisBlockResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_pooled_user_commands(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcPooledUserCommandsResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_pooled_zkapp_commands(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcPooledZkappCommandsResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_hard_fork_config_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcHardForkConfigGetResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_watched_accounts_add(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcWatchedAccountsAddResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_watched_accounts_remove(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcWatchedAccountsRemoveResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_watched_accounts_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcWatchedAccountsGetResponse,
+    ) -> Result<(), RespondError>;
+    /// Pushes the update to the subscriber, keeping the subscription open.
+    fn respond_watched_account_subscribe(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcWatchedAccountSubscribeResponse,
+    ) -> Result<(), RespondError>;
+    /// Drops the subscription, ending the subscriber's stream.
+    fn respond_subscription_end(&mut self, rpc_id: RpcId);
+    fn respond_transaction_simulate(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcTransactionSimulateResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_fee_estimate(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcFeeEstimateResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_zkapp_events_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcZkappEventsResponse,
+    ) -> Result<(), RespondError>;
+    fn respond_archive_get(
+        &mut self,
+        rpc_id: RpcId,
+        response: RpcArchiveGetResponse,
+    ) -> Result<(), RespondError>;
 }
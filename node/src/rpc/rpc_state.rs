@@ -2,6 +2,7 @@ use std::collections::BTreeMap;
 
 use mina_p2p_messages::v2;
 use openmina_core::block::AppliedBlock;
+use openmina_node_account::AccountPublicKey;
 use serde::{Deserialize, Serialize};
 
 use super::{AccountQuery, RpcId, RpcRequest};
@@ -82,17 +83,75 @@ impl RpcState {
             })
     }
 
+    pub fn hard_fork_config_rpc_ids(&self) -> impl Iterator<Item = (RpcId, &RpcRequestStatus)> {
+        self.requests
+            .iter()
+            .filter(|(_, req)| matches!(req.req, RpcRequest::HardForkConfigGet(_)))
+            .map(|(id, req)| (*id, &req.status))
+    }
+
+    pub fn transaction_simulate_rpc_ids(&self) -> impl Iterator<Item = (RpcId, &RpcRequestStatus)> {
+        self.requests
+            .iter()
+            .filter(|(_, req)| matches!(req.req, RpcRequest::TransactionSimulate(_)))
+            .map(|(id, req)| (*id, &req.status))
+    }
+
+    pub fn zkapp_events_rpc_ids(&self) -> impl Iterator<Item = (RpcId, &RpcRequestStatus)> {
+        self.requests
+            .iter()
+            .filter(|(_, req)| matches!(req.req, RpcRequest::ZkappEventsGet(_)))
+            .map(|(id, req)| (*id, &req.status))
+    }
+
+    pub fn archive_rpc_ids(&self) -> impl Iterator<Item = (RpcId, &RpcRequestStatus)> {
+        self.requests
+            .iter()
+            .filter(|(_, req)| matches!(req.req, RpcRequest::ArchiveGet(_)))
+            .map(|(id, req)| (*id, &req.status))
+    }
+
     pub fn accounts_request_rpc_ids(
         &self,
-    ) -> impl Iterator<Item = (RpcId, AccountQuery, &RpcRequestStatus)> + '_ {
+    ) -> impl Iterator<
+        Item = (
+            RpcId,
+            AccountQuery,
+            Option<v2::LedgerHash>,
+            &RpcRequestStatus,
+        ),
+    > + '_ {
         self.requests.iter().filter_map(|(id, req)| {
-            if let RpcRequest::LedgerAccountsGet(account) = &req.req {
-                Some((*id, account.clone(), &req.status))
+            if let RpcRequest::LedgerAccountsGet(account, ledger_hash) = &req.req {
+                Some((*id, account.clone(), ledger_hash.clone(), &req.status))
             } else {
                 None
             }
         })
     }
+
+    /// Active best tip subscriptions.
+    pub fn best_tip_subscriptions(&self) -> impl Iterator<Item = RpcId> + '_ {
+        self.requests
+            .iter()
+            .filter(|(_, req)| matches!(req.req, RpcRequest::BestTipSubscribe))
+            .filter(|(_, req)| req.status.is_pending())
+            .map(|(id, _)| *id)
+    }
+
+    /// Active subscriptions to the account `public_key`.
+    pub fn watched_account_subscriptions<'a>(
+        &'a self,
+        public_key: &'a AccountPublicKey,
+    ) -> impl Iterator<Item = RpcId> + 'a {
+        self.requests
+            .iter()
+            .filter(move |(_, req)| {
+                matches!(&req.req, RpcRequest::WatchedAccountSubscribe(key) if key == public_key)
+            })
+            .filter(|(_, req)| req.status.is_pending())
+            .map(|(id, _)| *id)
+    }
 }
 
 impl Default for RpcRequestExtraData {
@@ -9,6 +9,7 @@ pub use crate::rpc::RpcService;
 pub use crate::snark::block_verify_effectful::SnarkBlockVerifyService;
 pub use crate::snark::work_verify_effectful::SnarkWorkVerifyService;
 pub use crate::snark_pool::SnarkPoolService;
+pub use crate::transaction_pool::TransactionPoolService;
 pub use crate::transition_frontier::genesis_effectful::TransitionFrontierGenesisService;
 pub use crate::transition_frontier::sync::ledger::snarked::TransitionFrontierSyncLedgerSnarkedService;
 pub use redux::TimeService;
@@ -26,6 +27,7 @@ pub trait Service:
     + TransitionFrontierGenesisService
     + TransitionFrontierSyncLedgerSnarkedService
     + SnarkPoolService
+    + TransactionPoolService
     + SnarkUserCommandVerifyService
     + BlockProducerVrfEvaluatorService
     + BlockProducerService
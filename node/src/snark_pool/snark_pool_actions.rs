@@ -4,6 +4,7 @@ use openmina_core::snark::{Snark, SnarkJobCommitment, SnarkJobId};
 use openmina_core::ActionEvent;
 use serde::{Deserialize, Serialize};
 
+use crate::external_snark_worker::ExternalSnarkWorkerId;
 use crate::p2p::PeerId;
 
 use super::candidate::SnarkPoolCandidateAction;
@@ -26,6 +27,7 @@ pub enum SnarkPoolAction {
     },
     CommitmentCreate {
         job_id: SnarkJobId,
+        worker_id: ExternalSnarkWorkerId,
     },
     CommitmentAdd {
         commitment: SnarkJobCommitment,
@@ -56,8 +58,10 @@ impl redux::EnablingCondition<crate::State> for SnarkPoolAction {
                 .as_ref()
                 .map_or(false, |v| v.auto_commit),
             SnarkPoolAction::CommitmentCreateMany { .. } => state.config.snarker.is_some(),
-            SnarkPoolAction::CommitmentCreate { job_id } => {
-                state.config.snarker.is_some() && state.snark_pool.should_create_commitment(job_id)
+            SnarkPoolAction::CommitmentCreate { job_id, worker_id } => {
+                state.config.snarker.is_some()
+                    && state.snark_pool.should_create_commitment(job_id)
+                    && state.external_snark_worker.is_idle(worker_id)
             }
             SnarkPoolAction::CommitmentAdd { commitment, .. } => state
                 .snark_pool
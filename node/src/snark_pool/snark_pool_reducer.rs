@@ -73,14 +73,13 @@ impl SnarkPoolState {
 
                 // Dispatch
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
-                if let Some(job_id) = global_state.external_snark_worker.working_job_id() {
+                for (worker_id, job_id) in global_state.external_snark_worker.working_job_ids() {
                     if !global_state.snark_pool.contains(job_id) {
                         // job is no longer needed.
-                        dispatcher.push(ExternalSnarkWorkerAction::CancelWork);
+                        dispatcher.push(ExternalSnarkWorkerAction::CancelWork { worker_id });
                     }
-                } else {
-                    dispatcher.push(SnarkPoolAction::AutoCreateCommitment);
                 }
+                dispatcher.push(SnarkPoolAction::AutoCreateCommitment);
             }
             SnarkPoolAction::AutoCreateCommitment => {
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
@@ -120,38 +119,41 @@ impl SnarkPoolState {
                 };
             }
             SnarkPoolAction::CommitmentCreateMany { job_ids } => {
-                let dispatcher = state_context.into_dispatcher();
-                for job_id in job_ids.iter().cloned() {
-                    dispatcher.push(SnarkPoolAction::CommitmentCreate { job_id });
+                let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
+                // Workers only become busy once the queued actions are
+                // applied, so pick a distinct one for each job up front.
+                let workers = global_state.external_snark_worker.idle_local_workers();
+                for (job_id, worker_id) in job_ids.iter().cloned().zip(workers) {
+                    dispatcher.push(SnarkPoolAction::CommitmentCreate { job_id, worker_id });
                 }
             }
-            SnarkPoolAction::CommitmentCreate { job_id } => {
+            SnarkPoolAction::CommitmentCreate { job_id, worker_id } => {
                 let job_id = job_id.clone();
+                let worker_id = *worker_id;
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
                 let Some(summary) = global_state.snark_pool.job_summary(&job_id) else {
                     return;
                 };
 
-                if global_state.external_snark_worker.is_idle() {
-                    dispatcher.push(ExternalSnarkWorkerAction::SubmitWork {
-                        job_id: job_id.clone(),
-                        summary,
-                    });
+                dispatcher.push(ExternalSnarkWorkerAction::SubmitWork {
+                    worker_id,
+                    job_id: job_id.clone(),
+                    summary,
+                });
 
-                    let timestamp_ms = meta.time_as_nanos() / 1_000_000;
-                    let Some(config) = global_state.config.snarker.as_ref() else {
-                        return;
-                    };
-                    dispatcher.push(SnarkPoolAction::CommitmentAdd {
-                        commitment: SnarkJobCommitment::new(
-                            timestamp_ms,
-                            job_id,
-                            config.fee.clone(),
-                            config.public_key.clone().into(),
-                        ),
-                        sender: global_state.p2p.my_id(),
-                    });
-                }
+                let timestamp_ms = meta.time_as_nanos() / 1_000_000;
+                let Some(config) = global_state.config.snarker.as_ref() else {
+                    return;
+                };
+                dispatcher.push(SnarkPoolAction::CommitmentAdd {
+                    commitment: SnarkJobCommitment::new(
+                        timestamp_ms,
+                        job_id,
+                        config.fee.clone(),
+                        config.public_key.clone().into(),
+                    ),
+                    sender: global_state.p2p.my_id(),
+                });
             }
             SnarkPoolAction::CommitmentAdd { commitment, sender } => {
                 let Some(mut job) = state.remove(&commitment.job_id) else {
@@ -167,14 +169,15 @@ impl SnarkPoolState {
                 // Dispatch
                 let commitment = commitment.clone();
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
-                if let Some(job_id) = global_state.external_snark_worker.working_job_id() {
-                    let Some(config) = global_state.config.snarker.as_ref() else {
-                        return;
-                    };
-                    if &commitment.job_id == job_id
-                        && &commitment.snarker != config.public_key.as_ref()
+                let Some(config) = global_state.config.snarker.as_ref() else {
+                    return;
+                };
+                if &commitment.snarker != config.public_key.as_ref() {
+                    for (worker_id, job_id) in global_state.external_snark_worker.working_job_ids()
                     {
-                        dispatcher.push(ExternalSnarkWorkerAction::CancelWork);
+                        if &commitment.job_id == job_id {
+                            dispatcher.push(ExternalSnarkWorkerAction::CancelWork { worker_id });
+                        }
                     }
                 }
             }
@@ -194,10 +197,11 @@ impl SnarkPoolState {
                 // Dispatch
                 let snark = snark.clone();
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
-                if let Some(job_id) = global_state
+                let snark_job_id = snark.job_id();
+                for (worker_id, job_id) in global_state
                     .external_snark_worker
-                    .working_job_id()
-                    .filter(|job_id| *job_id == &snark.job_id())
+                    .working_job_ids()
+                    .filter(|(_, job_id)| *job_id == &snark_job_id)
                 {
                     if let Some(commitment) = global_state
                         .snark_pool
@@ -205,7 +209,7 @@ impl SnarkPoolState {
                         .and_then(|job| job.commitment.as_ref())
                     {
                         if snark > commitment.commitment {
-                            dispatcher.push(ExternalSnarkWorkerAction::CancelWork);
+                            dispatcher.push(ExternalSnarkWorkerAction::CancelWork { worker_id });
                         }
                     }
                 }
@@ -227,7 +227,14 @@ impl State {
             snark: SnarkState::new(config.snark),
             consensus: ConsensusState::new(),
             transition_frontier: TransitionFrontierState::new(config.transition_frontier),
-            external_snark_worker: ExternalSnarkWorkers::new(now),
+            external_snark_worker: ExternalSnarkWorkers::new(
+                now,
+                config
+                    .global
+                    .snarker
+                    .as_ref()
+                    .map_or(0, |config| config.local_workers),
+            ),
             block_producer: BlockProducerState::new(now, config.block_producer),
             rpc: RpcState::new(),
             transaction_pool: TransactionPoolState::new(config.tx_pool, constants),
@@ -361,7 +368,7 @@ impl P2p {
         P2pCallbacks {
             on_p2p_channels_transaction_libp2p_received: Some(redux::callback!(
                 on_p2p_channels_transaction_libp2p_received(transaction: Box<MinaBaseUserCommandStableV2>) -> crate::Action{
-                    TransactionPoolAction::StartVerify { commands: std::iter::once(*transaction).collect(), from_rpc: None }
+                    TransactionPoolAction::StartVerify { commands: std::iter::once(*transaction).collect(), is_sender_local: false, from_rpc: None }
                 }
             )),
             on_p2p_channels_snark_job_commitment_received: Some(redux::callback!(
@@ -420,8 +427,8 @@ impl P2p {
                 }
             )),
             on_p2p_peer_best_tip_update: Some(redux::callback!(
-                on_p2p_peer_best_tip_update(best_tip: BlockWithHash<Arc<MinaBlockBlockStableV2>>) -> crate::Action{
-                    ConsensusAction::P2pBestTipUpdate{best_tip}
+                on_p2p_peer_best_tip_update((peer_id: PeerId, best_tip: BlockWithHash<Arc<MinaBlockBlockStableV2>>)) -> crate::Action{
+                    ConsensusAction::P2pBestTipUpdate{peer_id, best_tip}
                 }
             )),
             on_p2p_channels_rpc_ready: Some(redux::callback!(
@@ -536,3 +543,60 @@ impl<'a> Iterator for ReadyPeersIter<'a> {
         })
     }
 }
+
+#[cfg(test)]
+impl State {
+    /// State of a freshly started devnet node, for testing reducers.
+    pub(crate) fn for_tests(now: Timestamp) -> Self {
+        use std::time::Duration;
+
+        use crate::config::DEVNET_CONFIG;
+        use crate::p2p::{
+            channels::ChannelId, identity::SecretKey as P2pSecretKey, P2pLimits, P2pMeshsubConfig,
+            P2pTimeouts,
+        };
+        use crate::snark::{get_srs, BlockVerifier, TransactionVerifier};
+        use crate::{BuildEnv, Config, LedgerConfig, SnarkConfig, TransitionFrontierConfig};
+
+        let protocol_constants = DEVNET_CONFIG.protocol_constants().unwrap();
+        let consensus_constants =
+            ConsensusConstants::create(constraint_constants(), &protocol_constants);
+        let srs = get_srs();
+        let config = Config {
+            ledger: LedgerConfig {},
+            snark: SnarkConfig {
+                block_verifier_index: BlockVerifier::make(),
+                block_verifier_srs: srs.clone(),
+                work_verifier_index: TransactionVerifier::make(),
+                work_verifier_srs: srs,
+            },
+            p2p: P2pConfig {
+                libp2p_port: None,
+                listen_port: None,
+                identity_pub_key: P2pSecretKey::deterministic(0).public_key(),
+                initial_peers: Vec::new(),
+                initial_banned_peers: Default::default(),
+                ask_initial_peers_interval: Duration::from_secs(3600),
+                enabled_channels: ChannelId::iter_all().collect(),
+                timeouts: P2pTimeouts::default(),
+                limits: P2pLimits::default(),
+                peer_discovery: false,
+                meshsub: P2pMeshsubConfig::default(),
+            },
+            transition_frontier: TransitionFrontierConfig::new(DEVNET_CONFIG.clone()),
+            block_producer: None,
+            global: GlobalConfig {
+                build: BuildEnv::get().into(),
+                snarker: None,
+                consensus_constants: consensus_constants.clone(),
+                testing_run: true,
+            },
+            tx_pool: ledger::transaction_pool::Config {
+                trust_system: (),
+                pool_max_size: 3000,
+                slot_tx_end: None,
+            },
+        };
+        Self::new(config, &consensus_constants, now)
+    }
+}
@@ -16,9 +16,20 @@ pub mod block_producer {
 }
 use block_producer::BlockProducerStats;
 
+mod stats_network;
+pub mod network {
+    pub use super::stats_network::*;
+}
+use network::{NetworkStats, ProtocolTraffic};
+
+use std::collections::BTreeMap;
+
 use openmina_core::block::{AppliedBlock, ArcBlockWithHash};
 use redux::{ActionMeta, ActionWithMeta, Timestamp};
 
+use crate::p2p::network::P2pNetworkAction;
+use crate::p2p::P2pState;
+
 use crate::transition_frontier::sync::ledger::staged::PeerStagedLedgerPartsFetchError;
 use crate::transition_frontier::sync::ledger::SyncLedgerTargetKind;
 use crate::transition_frontier::sync::TransitionFrontierSyncBlockState;
@@ -31,6 +42,7 @@ pub struct Stats {
     action_stats: ActionStats,
     sync_stats: SyncStats,
     block_producer_stats: BlockProducerStats,
+    network_stats: NetworkStats,
 }
 
 impl Stats {
@@ -40,6 +52,7 @@ impl Stats {
             action_stats: Default::default(),
             sync_stats: Default::default(),
             block_producer_stats: Default::default(),
+            network_stats: Default::default(),
         }
     }
 
@@ -95,6 +108,10 @@ impl Stats {
         self
     }
 
+    pub fn new_p2p_network_action(&mut self, p2p: &P2pState, action: &P2pNetworkAction) {
+        self.network_stats.p2p_network_action(p2p, action);
+    }
+
     pub fn collect_action_stats_since_start(&self) -> ActionStatsSnapshot {
         self.action_stats.since_start.clone()
     }
@@ -110,6 +127,10 @@ impl Stats {
         self.sync_stats.collect_stats(limit)
     }
 
+    pub fn collect_p2p_traffic(&self) -> BTreeMap<String, ProtocolTraffic> {
+        self.network_stats.collect_traffic()
+    }
+
     pub fn get_sync_time(&self) -> Option<Timestamp> {
         self.sync_stats
             .collect_stats(Some(1))
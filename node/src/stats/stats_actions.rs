@@ -80,6 +80,15 @@ impl ActionStatsSnapshot {
         }
         self.0[kind_i].add(duration);
     }
+
+    /// Stats per action kind, excluding the `None` action.
+    pub fn iter(&self) -> impl Iterator<Item = (ActionKind, &ActionStatsForRanges)> {
+        self.0
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(i, v)| (ActionKind::try_from(i as u16).unwrap(), v))
+    }
 }
 
 impl Serialize for ActionStatsSnapshot {
@@ -186,4 +195,21 @@ impl ActionStatsForRanges {
         stats.total_duration += duration;
         stats.max_duration = std::cmp::max(stats.max_duration, duration);
     }
+
+    /// Ranges paired with their inclusive upper bound in nanoseconds,
+    /// `None` for the last, unbounded one.
+    pub fn iter(&self) -> impl Iterator<Item = (Option<u64>, &ActionStatsForRange)> {
+        [
+            (Some(1_000), &self.under_1_us),
+            (Some(10_000), &self.under_10_us),
+            (Some(50_000), &self.under_50_us),
+            (Some(100_000), &self.under_100_us),
+            (Some(500_000), &self.under_500_us),
+            (Some(1_000_000), &self.under_1_ms),
+            (Some(5_000_000), &self.under_5_ms),
+            (Some(50_000_000), &self.under_50_ms),
+            (None, &self.above_50_ms),
+        ]
+        .into_iter()
+    }
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
 use ledger::AccountIndex;
 use mina_p2p_messages::v2;
@@ -6,7 +6,11 @@ use openmina_core::block::AppliedBlock;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    block_producer::{BlockProducerWonSlot, BlockProducerWonSlotDiscardReason, BlockWithoutProof},
+    account::AccountPublicKey,
+    block_producer::{
+        BlockProducerWonSlot, BlockProducerWonSlotDiscardReason, BlockWithoutProof,
+        StagedLedgerDiffCreationLog, TransactionSelectionRejected,
+    },
     core::block::BlockHash,
 };
 
@@ -15,6 +19,9 @@ const MAX_HISTORY: usize = 2048;
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct BlockProducerStats {
     pub(super) attempts: VecDeque<BlockProductionAttempt>,
+    /// Counters since the start of the node, unlike `attempts` they
+    /// aren't affected by the history limit.
+    totals: BTreeMap<AccountPublicKey, BlockProducerKeyStats>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -24,6 +31,13 @@ pub struct BlockProductionAttempt {
     pub times: BlockProductionTimes,
     #[serde(flatten)]
     pub status: BlockProductionStatus,
+    /// Transactions rejected by the transaction selection policy, moved
+    /// to the [`ProducedBlockTransactions`] once the block is produced.
+    #[serde(skip)]
+    rejected_transactions: Vec<TransactionSelectionRejected>,
+    /// Moved to the [`ProducedBlock`] once the block is produced.
+    #[serde(skip)]
+    diff_creation_log: Vec<StagedLedgerDiffCreationLog>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -31,6 +45,7 @@ pub struct BlockProductionAttemptWonSlot {
     pub slot_time: redux::Timestamp,
     pub global_slot: u32,
     pub epoch: u32,
+    pub producer: v2::NonZeroCurvePoint,
     pub delegator: (v2::NonZeroCurvePoint, AccountIndex),
     pub value_with_threshold: Option<(f64, f64)>,
 }
@@ -81,6 +96,26 @@ pub struct ProducedBlock {
     pub coinbase: u64,
     pub fees: u64,
     pub snark_fees: u64,
+    /// Why commands and completed work were left out of the block, one
+    /// log per partition of the scan state.
+    #[serde(default)]
+    pub diff_creation_log: Vec<StagedLedgerDiffCreationLog>,
+}
+
+/// Summary of the attempts made with a producer key.
+///
+/// For [`BlockProducerStats::collect_producer_stats`] these are the
+/// attempts still in history, for [`BlockProducerStats::producer_totals`]
+/// all the attempts since the start of the node, where `canonical` and
+/// `orphaned` count the times a produced block became canonical or got
+/// orphaned.
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct BlockProducerKeyStats {
+    pub won_slots: usize,
+    pub produced: usize,
+    pub canonical: usize,
+    pub orphaned: usize,
+    pub discarded: usize,
 }
 
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
@@ -88,6 +123,9 @@ pub struct ProducedBlockTransactions {
     pub payments: u16,
     pub delegations: u16,
     pub zkapps: u16,
+    /// Transactions rejected by the transaction selection policy.
+    #[serde(default)]
+    pub rejected: Vec<TransactionSelectionRejected>,
 }
 
 impl BlockProducerStats {
@@ -102,12 +140,48 @@ impl BlockProducerStats {
         self.attempts.iter().cloned().collect()
     }
 
+    pub fn collect_producer_stats(&self) -> BTreeMap<AccountPublicKey, BlockProducerKeyStats> {
+        self.attempts
+            .iter()
+            .fold(BTreeMap::new(), |mut producers, attempt| {
+                let stats: &mut BlockProducerKeyStats = producers
+                    .entry(attempt.won_slot.producer.clone().into())
+                    .or_default();
+                stats.won_slots += 1;
+                if attempt.block.is_some() {
+                    stats.produced += 1;
+                }
+                match attempt.status {
+                    BlockProductionStatus::Canonical { .. } => stats.canonical += 1,
+                    BlockProductionStatus::Orphaned { .. } => stats.orphaned += 1,
+                    BlockProductionStatus::Discarded { .. } => stats.discarded += 1,
+                    _ => {}
+                }
+                producers
+            })
+    }
+
+    pub fn producer_totals(&self) -> &BTreeMap<AccountPublicKey, BlockProducerKeyStats> {
+        &self.totals
+    }
+
+    fn totals_mut(&mut self, producer: &v2::NonZeroCurvePoint) -> &mut BlockProducerKeyStats {
+        self.totals.entry(producer.clone().into()).or_default()
+    }
+
+    /// Counters of the producer of the latest attempt.
+    fn latest_attempt_totals_mut(&mut self) -> Option<&mut BlockProducerKeyStats> {
+        let producer = self.attempts.back()?.won_slot.producer.clone();
+        Some(self.totals_mut(&producer))
+    }
+
     pub fn new_best_chain(&mut self, time: redux::Timestamp, chain: &[AppliedBlock]) {
         let (best_tip, chain) = chain.split_last().unwrap();
         let root_block = chain.first().unwrap_or(best_tip);
 
         self.committed(time, best_tip.hash());
 
+        let totals = &mut self.totals;
         self.attempts
             .iter_mut()
             .rev()
@@ -128,8 +202,12 @@ impl BlockProducerStats {
                     return;
                 };
 
+                let producer = &attempt.won_slot.producer;
                 match chain.get(i as usize) {
                     Some(b) if b.hash() == &block.hash => {
+                        if !matches!(attempt.status, BlockProductionStatus::Canonical { .. }) {
+                            totals.entry(producer.clone().into()).or_default().canonical += 1;
+                        }
                         attempt.status = BlockProductionStatus::Canonical {
                             last_observed_confirmations: best_tip
                                 .height()
@@ -137,6 +215,9 @@ impl BlockProducerStats {
                         };
                     }
                     Some(b) => {
+                        if !matches!(attempt.status, BlockProductionStatus::Orphaned { .. }) {
+                            totals.entry(producer.clone().into()).or_default().orphaned += 1;
+                        }
                         attempt.status = BlockProductionStatus::Orphaned {
                             orphaned_by: b.hash().clone(),
                         };
@@ -146,7 +227,8 @@ impl BlockProducerStats {
             });
     }
 
-    fn update<F>(&mut self, kind: &'static str, with: F)
+    /// Updates the latest attempt, returns whether it was in the expected state.
+    fn update<F>(&mut self, kind: &'static str, with: F) -> bool
     where
         F: FnOnce(&mut BlockProductionAttempt) -> bool,
     {
@@ -156,6 +238,7 @@ impl BlockProducerStats {
                     kind = "BlockProducerStatsAttemptsEmpty",
                     summary = "attempts are empty when they aren't expected to be",
                     update_kind = kind);
+                false
             }
             Some(mut attempt) => {
                 let was_correct_state = with(&mut attempt);
@@ -166,6 +249,7 @@ impl BlockProducerStats {
                         summary = format!("update kind `{kind}` is not applicable to state: {attempt:?}"));
                 }
                 self.attempts.push_back(attempt);
+                was_correct_state
             }
         }
     }
@@ -174,6 +258,7 @@ impl BlockProducerStats {
         if self.attempts.len() >= MAX_HISTORY {
             self.attempts.pop_front();
         }
+        self.totals_mut(&won_slot.producer).won_slots += 1;
         self.attempts.push_back(BlockProductionAttempt {
             won_slot: won_slot.into(),
             block: None,
@@ -190,16 +275,23 @@ impl BlockProducerStats {
                 discarded: None,
             },
             status: BlockProductionStatus::Scheduled,
+            rejected_transactions: Vec::new(),
+            diff_creation_log: Vec::new(),
         });
     }
 
-    pub fn staged_ledger_diff_create_start(&mut self, time: redux::Timestamp) {
+    pub fn staged_ledger_diff_create_start(
+        &mut self,
+        time: redux::Timestamp,
+        rejected_transactions: Vec<TransactionSelectionRejected>,
+    ) {
         self.update(
             "staged_ledger_diff_create_start",
             move |attempt| match attempt.status {
                 BlockProductionStatus::Scheduled => {
                     attempt.status = BlockProductionStatus::StagedLedgerDiffCreatePending;
                     attempt.times.staged_ledger_diff_create_start = Some(time);
+                    attempt.rejected_transactions = rejected_transactions;
                     true
                 }
                 _ => false,
@@ -207,13 +299,20 @@ impl BlockProducerStats {
         );
     }
 
-    pub fn staged_ledger_diff_create_end(&mut self, time: redux::Timestamp) {
+    pub fn staged_ledger_diff_create_end(
+        &mut self,
+        time: redux::Timestamp,
+        diff_creation_log: Vec<StagedLedgerDiffCreationLog>,
+        rejected_transactions: Vec<TransactionSelectionRejected>,
+    ) {
         self.update(
             "staged_ledger_diff_create_end",
             move |attempt| match attempt.status {
                 BlockProductionStatus::StagedLedgerDiffCreatePending => {
                     attempt.status = BlockProductionStatus::StagedLedgerDiffCreateSuccess;
                     attempt.times.staged_ledger_diff_create_end = Some(time);
+                    attempt.diff_creation_log = diff_creation_log;
+                    attempt.rejected_transactions.extend(rejected_transactions);
                     true
                 }
                 _ => false,
@@ -227,15 +326,23 @@ impl BlockProducerStats {
         block_hash: &BlockHash,
         block: &BlockWithoutProof,
     ) {
-        self.update("produced", move |attempt| match attempt.status {
+        let updated = self.update("produced", move |attempt| match attempt.status {
             BlockProductionStatus::StagedLedgerDiffCreateSuccess => {
                 attempt.status = BlockProductionStatus::Produced;
                 attempt.times.produced = Some(time);
-                attempt.block = Some((block_hash, block).into());
+                let mut block: ProducedBlock = (block_hash, block).into();
+                block.transactions.rejected = std::mem::take(&mut attempt.rejected_transactions);
+                block.diff_creation_log = std::mem::take(&mut attempt.diff_creation_log);
+                attempt.block = Some(block);
                 true
             }
             _ => false,
         });
+        if updated {
+            if let Some(totals) = self.latest_attempt_totals_mut() {
+                totals.produced += 1;
+            }
+        }
     }
 
     pub fn proof_create_start(&mut self, time: redux::Timestamp) {
@@ -311,6 +418,9 @@ impl BlockProducerStats {
     }
 
     pub fn discarded(&mut self, time: redux::Timestamp, reason: BlockProducerWonSlotDiscardReason) {
+        if let Some(totals) = self.latest_attempt_totals_mut() {
+            totals.discarded += 1;
+        }
         self.update("discarded", move |attempt| {
             attempt.status = BlockProductionStatus::Discarded {
                 discard_reason: reason,
@@ -327,6 +437,7 @@ impl From<&BlockProducerWonSlot> for BlockProductionAttemptWonSlot {
             slot_time: won_slot.slot_time,
             global_slot: won_slot.global_slot(),
             epoch: won_slot.epoch(),
+            producer: won_slot.producer.clone(),
             delegator: won_slot.delegator.clone(),
             value_with_threshold: won_slot.value_with_threshold,
         }
@@ -348,6 +459,7 @@ impl From<(&BlockHash, &BlockWithoutProof)> for ProducedBlock {
             coinbase: block.body.coinbase_sum(),
             fees: block.body.fees_sum(),
             snark_fees: block.body.snark_fees_sum(),
+            diff_creation_log: Vec::new(),
         }
     }
 }
@@ -373,3 +485,216 @@ impl From<&BlockWithoutProof> for ProducedBlockTransactions {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use mina_p2p_messages::{
+        bigint::BigInt,
+        v2::{EpochSeed, LedgerHash, MinaBaseEpochSeedStableV1},
+    };
+    use openmina_node_account::AccountSecretKey;
+    use vrf::VrfWonSlot;
+
+    use crate::{
+        block_producer::{
+            vrf_evaluator::VrfWonSlotWithHash, StagedLedgerDiffCreationLine,
+            StagedLedgerDiffCreationPartition, StagedLedgerDiffCreationReason,
+            StagedLedgerDiffCreationResources, StagedLedgerDiffCreationSummary,
+        },
+        core::block::ArcBlockWithHash,
+        transition_frontier::genesis::{GenesisConfig, NonStakers, TransitionFrontierGenesisState},
+    };
+
+    use super::*;
+
+    fn won_slot(global_slot: u32) -> BlockProducerWonSlot {
+        let producer = AccountSecretKey::genesis_producer().public_key();
+        let won_slot = VrfWonSlot {
+            producer: producer.clone(),
+            winner_account: producer,
+            global_slot,
+            account_index: AccountIndex(0),
+            vrf_output: Box::new(
+                vrf::genesis_vrf(EpochSeed::from(MinaBaseEpochSeedStableV1(BigInt::zero())))
+                    .unwrap(),
+            ),
+            value_with_threshold: None,
+        };
+        let staking_ledger_hash =
+            LedgerHash::from_str("jxTAZfKKDxoX4vtt68pQCWooXoVLjnfBpusaMwewrcZxsL3uWp6").unwrap();
+        BlockProducerWonSlot::from_vrf_won_slot(
+            &VrfWonSlotWithHash::new(won_slot, staking_ledger_hash),
+            redux::Timestamp::ZERO,
+        )
+    }
+
+    fn genesis_block() -> ArcBlockWithHash {
+        let (_, genesis) = GenesisConfig::Counts {
+            whales: 1,
+            fish: 1,
+            non_stakers: NonStakers::None,
+            constants: GenesisConfig::default_constants(1_700_000_000_000),
+        }
+        .load()
+        .unwrap();
+        let (negative_one, genesis_state, genesis_hash) = genesis.protocol_states().unwrap();
+        TransitionFrontierGenesisState::Produced {
+            time: redux::Timestamp::ZERO,
+            negative_one,
+            genesis: genesis_state,
+            genesis_hash,
+            genesis_producer_stake_proof: genesis.genesis_producer_stake_proof.clone(),
+        }
+        .block_with_dummy_proof()
+        .unwrap()
+    }
+
+    fn block_without_proof(block: &ArcBlockWithHash) -> BlockWithoutProof {
+        let header = block.header();
+        BlockWithoutProof {
+            protocol_state: header.protocol_state.clone(),
+            delta_block_chain_proof: header.delta_block_chain_proof.clone(),
+            current_protocol_version: header.current_protocol_version.clone(),
+            proposed_protocol_version_opt: header.proposed_protocol_version_opt.clone(),
+            body: block.body().clone(),
+        }
+    }
+
+    fn diff_creation_log(
+        partition: StagedLedgerDiffCreationPartition,
+    ) -> StagedLedgerDiffCreationLog {
+        let resources = |commands| StagedLedgerDiffCreationResources {
+            commands,
+            commands_fees: commands * 10,
+            completed_works: 0,
+            completed_works_fees: 0,
+            coinbase_work_fees: vec![],
+        };
+        StagedLedgerDiffCreationLog {
+            summary: StagedLedgerDiffCreationSummary {
+                partition,
+                available_slots: 1,
+                required_work_count: 0,
+                start_resources: resources(2),
+                end_resources: resources(1),
+                discarded_commands_no_work: 0,
+                discarded_commands_no_space: 1,
+                discarded_completed_work_insufficient_fees: 0,
+                discarded_completed_work_extra_work: 0,
+            },
+            details: vec![
+                StagedLedgerDiffCreationLine {
+                    reason: StagedLedgerDiffCreationReason::Init,
+                    resources: resources(2),
+                },
+                StagedLedgerDiffCreationLine {
+                    reason: StagedLedgerDiffCreationReason::NoSpace,
+                    resources: resources(1),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_diff_creation_log_stored_with_produced_block() {
+        let block = genesis_block();
+        let mut stats = BlockProducerStats::default();
+        let time = redux::Timestamp::ZERO;
+
+        stats.scheduled(time, &won_slot(1));
+        stats.staged_ledger_diff_create_start(time, vec![]);
+        stats.staged_ledger_diff_create_end(
+            time,
+            vec![
+                diff_creation_log(StagedLedgerDiffCreationPartition::First),
+                diff_creation_log(StagedLedgerDiffCreationPartition::Second),
+            ],
+            vec![],
+        );
+        // not exposed until the block is produced.
+        let attempt = serde_json::to_value(stats.attempts.back().unwrap()).unwrap();
+        assert!(attempt.get("diff_creation_log").is_none());
+        assert!(attempt["block"].is_null());
+
+        stats.produced(time, block.hash(), &block_without_proof(&block));
+        let attempt = stats.attempts.back().unwrap();
+        assert!(attempt.diff_creation_log.is_empty());
+        let produced = attempt.block.as_ref().unwrap();
+        assert_eq!(&produced.hash, block.hash());
+        let partitions = produced
+            .diff_creation_log
+            .iter()
+            .map(|log| log.summary.partition)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            partitions,
+            [
+                StagedLedgerDiffCreationPartition::First,
+                StagedLedgerDiffCreationPartition::Second
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_creation_log_dropped_with_discarded_attempt() {
+        let mut stats = BlockProducerStats::default();
+        let time = redux::Timestamp::ZERO;
+
+        stats.scheduled(time, &won_slot(1));
+        stats.staged_ledger_diff_create_start(time, vec![]);
+        stats.staged_ledger_diff_create_end(
+            time,
+            vec![diff_creation_log(StagedLedgerDiffCreationPartition::First)],
+            vec![],
+        );
+        stats.discarded(time, BlockProducerWonSlotDiscardReason::BestTipSuperior);
+
+        // the next attempt doesn't inherit the log of the discarded one.
+        stats.scheduled(time, &won_slot(2));
+        stats.staged_ledger_diff_create_start(time, vec![]);
+        stats.staged_ledger_diff_create_end(time, vec![], vec![]);
+
+        assert!(stats.attempts.iter().all(|attempt| attempt.block.is_none()));
+        assert!(stats.attempts[1].diff_creation_log.is_empty());
+    }
+
+    #[test]
+    fn test_diff_creation_log_query_by_block_hash() {
+        let block = genesis_block();
+        let mut stats = BlockProducerStats::default();
+        let time = redux::Timestamp::ZERO;
+
+        stats.scheduled(time, &won_slot(1));
+        stats.discarded(time, BlockProducerWonSlotDiscardReason::BestTipSuperior);
+        stats.scheduled(time, &won_slot(2));
+        stats.staged_ledger_diff_create_start(time, vec![]);
+        stats.staged_ledger_diff_create_end(
+            time,
+            vec![diff_creation_log(StagedLedgerDiffCreationPartition::First)],
+            vec![],
+        );
+        stats.produced(time, block.hash(), &block_without_proof(&block));
+
+        // attempts as returned by the block producer stats rpc.
+        let json = serde_json::to_string(&stats.collect_attempts()).unwrap();
+        let attempts: Vec<BlockProductionAttempt> = serde_json::from_str(&json).unwrap();
+
+        let hash = block.hash().to_string();
+        let produced = attempts
+            .into_iter()
+            .filter_map(|attempt| attempt.block)
+            .find(|block| block.hash.to_string() == hash)
+            .unwrap();
+        let [log] = produced.diff_creation_log.as_slice() else {
+            panic!("expected one log, got {:?}", produced.diff_creation_log);
+        };
+        assert_eq!(log.discarded_commands(), 1);
+        assert_eq!(log.summary.start_resources.commands_fees, 20);
+        assert_eq!(
+            log.details[1].reason,
+            StagedLedgerDiffCreationReason::NoSpace
+        );
+    }
+}
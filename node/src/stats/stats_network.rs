@@ -0,0 +1,64 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::p2p::network::select::P2pNetworkSelectAction;
+use crate::p2p::network::yamux::P2pNetworkYamuxAction;
+use crate::p2p::network::P2pNetworkAction;
+use crate::p2p::P2pState;
+
+/// Stream data exchanged before the protocol is negotiated.
+const UNNEGOTIATED_PROTOCOL: &str = "/multistream/1.0.0";
+
+#[derive(Default)]
+pub struct NetworkStats {
+    traffic: BTreeMap<&'static str, ProtocolTraffic>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct ProtocolTraffic {
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+}
+
+impl NetworkStats {
+    /// Accounts the data of the libp2p streams to the stream's protocol.
+    pub fn p2p_network_action(&mut self, p2p: &P2pState, action: &P2pNetworkAction) {
+        let (addr, stream_id, len, incoming) = match action {
+            P2pNetworkAction::Select(P2pNetworkSelectAction::IncomingData {
+                addr,
+                stream_id,
+                data,
+                ..
+            }) => (addr, stream_id, data.len(), true),
+            P2pNetworkAction::Yamux(P2pNetworkYamuxAction::OutgoingData {
+                addr,
+                stream_id,
+                data,
+                ..
+            }) => (addr, stream_id, data.len(), false),
+            _ => return,
+        };
+        let protocol = p2p
+            .network
+            .scheduler
+            .connections
+            .get(addr)
+            .and_then(|conn| conn.streams.get(stream_id)?.select.negotiated.flatten())
+            .map_or(UNNEGOTIATED_PROTOCOL, |protocol| protocol.name_str());
+
+        let traffic = self.traffic.entry(protocol).or_default();
+        if incoming {
+            traffic.bytes_received += len as u64;
+        } else {
+            traffic.bytes_sent += len as u64;
+        }
+    }
+
+    pub fn collect_traffic(&self) -> BTreeMap<String, ProtocolTraffic> {
+        self.traffic
+            .iter()
+            .map(|(protocol, traffic)| (protocol.to_string(), traffic.clone()))
+            .collect()
+    }
+}
@@ -0,0 +1,17 @@
+//! Fixtures shared by the unit tests.
+
+use mina_p2p_messages::v2;
+use p2p::channels::rpc::{AncestryWithProof, P2pRpcResponse};
+
+/// Root block of a recorded ancestry RPC response. Tests adjust its
+/// height, predecessor or contents as needed.
+pub fn recorded_block() -> v2::MinaBlockBlockStableV2 {
+    let response: P2pRpcResponse = serde_json::from_slice(include_bytes!(
+        "../../p2p/tests/files/rpc/ancestry_response.json"
+    ))
+    .unwrap();
+    let P2pRpcResponse::Ancestry(AncestryWithProof { root, .. }) = response else {
+        panic!("unexpected response");
+    };
+    (*root).clone()
+}
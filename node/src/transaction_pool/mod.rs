@@ -1,6 +1,7 @@
 use ledger::{
     scan_state::{
-        currency::{Amount, Nonce, Slot},
+        currency::{Amount, Fee, Nonce, Slot},
+        fee_rate::FeeRate,
         transaction_logic::{verifiable, UserCommand, WithStatus},
     },
     transaction_pool::{
@@ -19,6 +20,7 @@ use snark::{user_command_verify::SnarkUserCommandVerifyId, TransactionVerifier,
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
     sync::Arc,
+    time::Duration,
 };
 use transaction_pool_actions::TransactionPoolActionWithMetaRef;
 
@@ -26,6 +28,9 @@ pub mod transaction_pool_actions;
 
 pub use transaction_pool_actions::{TransactionPoolAction, TransactionPoolEffectfulAction};
 
+mod transaction_pool_service;
+pub use transaction_pool_service::*;
+
 use crate::{BlockProducerAction, RpcAction};
 
 type PendingId = u32;
@@ -36,6 +41,10 @@ pub struct TransactionPoolState {
     pending_actions: BTreeMap<PendingId, TransactionPoolAction>,
     pending_id: PendingId,
     best_tip_hash: Option<v2::LedgerHash>,
+    /// Locally generated commands persisted before a restart, added back
+    /// to the pool once the best tip is known.
+    restored_commands: Vec<v2::MinaBaseUserCommandStableV2>,
+    last_rebroadcast: Option<redux::Timestamp>,
     /// For debug only
     #[serde(skip)]
     file: Option<std::fs::File>,
@@ -48,6 +57,8 @@ impl Clone for TransactionPoolState {
             pending_actions: self.pending_actions.clone(),
             pending_id: self.pending_id,
             best_tip_hash: self.best_tip_hash.clone(),
+            restored_commands: self.restored_commands.clone(),
+            last_rebroadcast: self.last_rebroadcast,
             file: None,
         }
     }
@@ -60,10 +71,16 @@ impl TransactionPoolState {
             pending_actions: Default::default(),
             pending_id: 0,
             best_tip_hash: None,
+            restored_commands: Vec::new(),
+            last_rebroadcast: None,
             file: None,
         }
     }
 
+    pub fn restore(&mut self, commands: Vec<v2::MinaBaseUserCommandStableV2>) {
+        self.restored_commands = commands;
+    }
+
     pub fn size(&self) -> usize {
         self.pool.size()
     }
@@ -84,6 +101,20 @@ impl TransactionPoolState {
         self.pool.get_pending_amount_and_nonce()
     }
 
+    pub fn replace_fee(&self, sender: &AccountId, nonce: Nonce) -> Option<(Fee, Fee)> {
+        self.pool.replace_fee(sender, nonce)
+    }
+
+    /// Commands must pay more per weight unit than the cheapest command of
+    /// a full pool to enter it.
+    pub fn is_full(&self) -> bool {
+        self.pool.size() >= self.pool.config.pool_max_size
+    }
+
+    pub fn min_fee_rate(&self) -> Option<FeeRate> {
+        self.pool.min_fee_rate()
+    }
+
     fn next_pending_id(&mut self) -> PendingId {
         let id = self.pending_id;
         self.pending_id = self.pending_id.wrapping_add(1);
@@ -96,6 +127,15 @@ impl TransactionPoolState {
         id
     }
 
+    fn locally_generated_persist(&self) -> TransactionPoolEffectfulAction {
+        let commands = self
+            .pool
+            .locally_generated_uncommitted()
+            .map(|cmd| (&cmd.data.forget_check()).into())
+            .collect();
+        TransactionPoolEffectfulAction::LocallyGeneratedPersist { commands }
+    }
+
     #[allow(dead_code)]
     fn save_actions(state: &mut crate::Substate<Self>) {
         let substate = state.get_substate_mut().unwrap();
@@ -140,14 +180,28 @@ impl TransactionPoolState {
         let substate = state.get_substate_mut().unwrap();
 
         match action {
-            TransactionPoolAction::StartVerify { commands, from_rpc } => {
-                let Ok(commands) = commands
+            TransactionPoolAction::StartVerify {
+                commands,
+                is_sender_local: _,
+                from_rpc,
+            } => {
+                let commands = match commands
                     .iter()
                     .map(UserCommand::try_from)
                     .collect::<Result<Vec<_>, _>>()
-                else {
-                    // ignore all commands if one is invalid
-                    return;
+                {
+                    Ok(commands) => commands,
+                    Err(err) => {
+                        // ignore all commands if one is invalid
+                        if let Some(rpc_id) = from_rpc {
+                            let dispatcher = state.into_dispatcher();
+                            dispatcher.push(RpcAction::TransactionInjectFailure {
+                                rpc_id: *rpc_id,
+                                errors: vec![format!("invalid command: {err}")],
+                            });
+                        }
+                        return;
+                    }
                 };
 
                 let account_ids = commands
@@ -173,8 +227,11 @@ impl TransactionPoolState {
                 pending_id,
                 from_rpc,
             } => {
-                let TransactionPoolAction::StartVerify { commands, .. } =
-                    substate.pending_actions.remove(pending_id).unwrap()
+                let TransactionPoolAction::StartVerify {
+                    commands,
+                    is_sender_local,
+                    ..
+                } = substate.pending_actions.remove(pending_id).unwrap()
                 else {
                     panic!()
                 };
@@ -202,7 +259,7 @@ impl TransactionPoolState {
                         dispatcher.push(TransactionPoolAction::ApplyVerifiedDiff {
                             best_tip_hash,
                             diff,
-                            is_sender_local: from_rpc.is_some(),
+                            is_sender_local,
                             from_rpc: *from_rpc,
                         });
                     }
@@ -258,6 +315,16 @@ impl TransactionPoolState {
                 {
                     bug_condition!("transaction pool::on_new_best_tip failed: {:?}", e);
                 }
+
+                let restored_commands = std::mem::take(&mut substate.restored_commands);
+                if !restored_commands.is_empty() {
+                    let dispatcher = state.into_dispatcher();
+                    dispatcher.push(TransactionPoolAction::StartVerify {
+                        commands: restored_commands.into_iter().collect(),
+                        is_sender_local: true,
+                        from_rpc: None,
+                    });
+                }
             }
             TransactionPoolAction::ApplyVerifiedDiff {
                 best_tip_hash,
@@ -305,26 +372,28 @@ impl TransactionPoolState {
                     accounts,
                     is_sender_local,
                 ) {
-                    Ok((ApplyDecision::Accept, accepted, rejected)) => {
-                        if let Some(rpc_id) = from_rpc {
-                            let dispatcher = state.into_dispatcher();
+                    Ok((decision, accepted, rejected)) => {
+                        let persist = is_sender_local.then(|| substate.locally_generated_persist());
+                        let dispatcher = state.into_dispatcher();
 
-                            dispatcher.push(RpcAction::TransactionInjectSuccess {
-                                rpc_id,
-                                response: accepted.clone(),
-                            });
-                            dispatcher
-                                .push(TransactionPoolAction::Rebroadcast { accepted, rejected });
-                        }
-                    }
-                    Ok((ApplyDecision::Reject, accepted, rejected)) => {
                         if let Some(rpc_id) = from_rpc {
-                            let dispatcher = state.into_dispatcher();
-
-                            dispatcher.push(RpcAction::TransactionInjectRejected {
-                                rpc_id,
-                                response: rejected.clone(),
-                            });
+                            match decision {
+                                ApplyDecision::Accept => {
+                                    dispatcher.push(RpcAction::TransactionInjectSuccess {
+                                        rpc_id,
+                                        response: accepted.clone(),
+                                    })
+                                }
+                                ApplyDecision::Reject => {
+                                    dispatcher.push(RpcAction::TransactionInjectRejected {
+                                        rpc_id,
+                                        response: rejected.clone(),
+                                    })
+                                }
+                            }
+                        }
+                        if let Some(persist) = persist {
+                            dispatcher.push(persist);
                             dispatcher
                                 .push(TransactionPoolAction::Rebroadcast { accepted, rejected });
                         }
@@ -415,6 +484,27 @@ impl TransactionPoolState {
                     });
                 }
             }
+            TransactionPoolAction::RebroadcastLocallyGenerated => {
+                // committed commands are kept around in case they get
+                // dropped from the best chain by a reorg.
+                const REBROADCAST_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+                let now = meta.time();
+                let rebroadcastable = substate.pool.get_rebroadcastable(|time| {
+                    now.checked_sub(*time)
+                        .map_or(false, |d| d > REBROADCAST_TIMEOUT)
+                });
+                substate.last_rebroadcast = Some(now);
+                let persist = substate.locally_generated_persist();
+
+                let dispatcher = state.into_dispatcher();
+                dispatcher.push(persist);
+                for cmd in rebroadcastable.into_iter().flatten() {
+                    dispatcher.push(P2pChannelsTransactionAction::Libp2pBroadcast {
+                        transaction: Box::new((&cmd).into()),
+                        nonce: 0,
+                    });
+                }
+            }
             TransactionPoolAction::CollectTransactionsByFee => {
                 let transaction_capacity =
                     2u64.pow(constraint_constants().transaction_capacity_log_2 as u32);
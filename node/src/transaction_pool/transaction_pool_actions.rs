@@ -1,4 +1,7 @@
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
 
 use ledger::{
     transaction_pool::{
@@ -17,7 +20,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::ledger::LedgerService;
 
-use super::PendingId;
+use super::{PendingId, TransactionPoolService};
 
 pub type TransactionPoolActionWithMeta = redux::ActionWithMeta<TransactionPoolAction>;
 pub type TransactionPoolActionWithMetaRef<'a> = redux::ActionWithMeta<&'a TransactionPoolAction>;
@@ -27,6 +30,9 @@ pub type TransactionPoolActionWithMetaRef<'a> = redux::ActionWithMeta<&'a Transa
 pub enum TransactionPoolAction {
     StartVerify {
         commands: List<v2::MinaBaseUserCommandStableV2>,
+        /// Commands were generated locally, submitted through rpc or
+        /// restored after a restart.
+        is_sender_local: bool,
         from_rpc: Option<RpcId>,
     },
     StartVerifyWithAccounts {
@@ -63,16 +69,35 @@ pub enum TransactionPoolAction {
         accounts: BTreeMap<AccountId, Account>,
         pending_id: PendingId,
     },
-    /// Rebroadcast locally generated pool items every 10 minutes. Do so for 50
-    /// minutes - at most 5 rebroadcasts - before giving up.
+    /// Broadcast locally generated commands that were just applied to the pool.
     Rebroadcast {
         accepted: Vec<ValidCommandWithHash>,
         rejected: Vec<(ValidCommandWithHash, diff::Error)>,
     },
+    /// Rebroadcast locally generated pool items every 10 minutes, until they
+    /// are included in the best chain or expire through `valid_until`.
+    RebroadcastLocallyGenerated,
     CollectTransactionsByFee,
 }
 
-impl redux::EnablingCondition<crate::State> for TransactionPoolAction {}
+impl redux::EnablingCondition<crate::State> for TransactionPoolAction {
+    fn is_enabled(&self, state: &crate::State, time: redux::Timestamp) -> bool {
+        match self {
+            TransactionPoolAction::RebroadcastLocallyGenerated => {
+                const INTERVAL: Duration = Duration::from_secs(10 * 60);
+                let pool = &state.transaction_pool;
+                // wait for the restored commands to be added back to the pool,
+                // otherwise they would be lost when persisting.
+                pool.best_tip_hash.is_some()
+                    && pool.restored_commands.is_empty()
+                    && pool.last_rebroadcast.map_or(true, |last| {
+                        time.checked_sub(last).map_or(false, |d| d >= INTERVAL)
+                    })
+            }
+            _ => true,
+        }
+    }
+}
 
 type TransactionPoolEffectfulActionCallback = Callback<(
     BTreeMap<AccountId, Account>,
@@ -89,6 +114,9 @@ pub enum TransactionPoolEffectfulAction {
         pending_id: Option<PendingId>,
         from_rpc: Option<RpcId>,
     },
+    LocallyGeneratedPersist {
+        commands: Vec<v2::MinaBaseUserCommandStableV2>,
+    },
 }
 
 impl redux::EnablingCondition<crate::State> for TransactionPoolEffectfulAction {}
@@ -97,7 +125,7 @@ impl TransactionPoolEffectfulAction {
     pub fn effects<Store, S>(self, store: &mut Store)
     where
         Store: snark::SnarkStore<S>,
-        Store::Service: LedgerService,
+        Store::Service: LedgerService + TransactionPoolService,
     {
         match self {
             TransactionPoolEffectfulAction::FetchAccounts {
@@ -139,6 +167,9 @@ impl TransactionPoolEffectfulAction {
 
                 store.dispatch_callback(on_result, (accounts, pending_id, from_rpc));
             }
+            TransactionPoolEffectfulAction::LocallyGeneratedPersist { commands } => {
+                store.service().locally_generated_persist(commands);
+            }
         }
     }
 }
@@ -0,0 +1,7 @@
+use mina_p2p_messages::v2;
+
+pub trait TransactionPoolService: redux::Service {
+    /// Persists the locally generated commands which aren't yet included in
+    /// the best chain, so that they can be restored after a restart.
+    fn locally_generated_persist(&mut self, commands: Vec<v2::MinaBaseUserCommandStableV2>);
+}
@@ -6,11 +6,11 @@ use std::{
     str::FromStr,
 };
 
-use crate::{account::AccountSecretKey, daemon_json::EpochData};
+use crate::{account::AccountSecretKey, block_producer::calc_epoch_seed, daemon_json::EpochData};
 use ark_ff::fields::arithmetic::InvalidBigInt;
 use ledger::{
     proofs::caching::{ensure_path_exists, openmina_cache_path},
-    scan_state::currency::Balance,
+    scan_state::{currency::Balance, transaction_logic::local_state::LocalState},
     BaseLedger,
 };
 use mina_hasher::Fp;
@@ -22,7 +22,10 @@ use mina_p2p_messages::{
     },
     v2::{self, PROTOCOL_CONSTANTS},
 };
-use openmina_core::constants::{constraint_constants, DEFAULT_GENESIS_TIMESTAMP_MILLISECONDS};
+use openmina_core::{
+    block::genesis::genesis_and_negative_one_protocol_states,
+    constants::{constraint_constants, ForkConstants, DEFAULT_GENESIS_TIMESTAMP_MILLISECONDS},
+};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -30,6 +33,8 @@ use crate::{
     ProtocolConstants,
 };
 
+use super::{empty_block_body_hash, empty_pending_coinbase_hash};
+
 pub use GenesisConfig as TransitionFrontierGenesisConfig;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -65,6 +70,8 @@ pub enum NonStakers {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct GenesisConfigLoaded {
+    /// Where the chain is restarted from, if it's a hard fork.
+    pub fork: Option<ForkConstants>,
     pub constants: ProtocolConstants,
     pub genesis_ledger_hash: v2::LedgerHash,
     pub genesis_total_currency: v2::CurrencyAmountStableV1,
@@ -77,6 +84,43 @@ pub struct GenesisConfigLoaded {
     pub next_epoch_seed: v2::EpochSeed,
 }
 
+impl GenesisConfigLoaded {
+    /// Protocol states of the block before genesis and of the genesis
+    /// block, with the hash of the genesis block.
+    pub fn protocol_states(
+        &self,
+    ) -> Result<
+        (
+            v2::MinaStateProtocolStateValueStableV2,
+            v2::MinaStateProtocolStateValueStableV2,
+            v2::StateHash,
+        ),
+        InvalidBigInt,
+    > {
+        let genesis_vrf = ::vrf::genesis_vrf(self.staking_epoch_seed.clone()).unwrap();
+        let genesis_vrf_hash = genesis_vrf.hash();
+
+        genesis_and_negative_one_protocol_states(
+            self.fork.as_ref(),
+            self.constants.clone(),
+            self.genesis_ledger_hash.clone(),
+            self.genesis_total_currency.clone(),
+            self.staking_epoch_ledger_hash.clone(),
+            self.staking_epoch_total_currency.clone(),
+            self.next_epoch_ledger_hash.clone(),
+            self.next_epoch_total_currency.clone(),
+            AccountSecretKey::genesis_producer().public_key().into(),
+            empty_pending_coinbase_hash(),
+            (&LocalState::dummy()).into(),
+            empty_block_body_hash(),
+            genesis_vrf.into(),
+            self.staking_epoch_seed.clone(),
+            self.next_epoch_seed.clone(),
+            calc_epoch_seed(&self.next_epoch_seed, genesis_vrf_hash),
+        )
+    }
+}
+
 fn bp_num_delegators(i: usize) -> usize {
     (i + 1) * 2
 }
@@ -167,6 +211,7 @@ impl GenesisConfig {
                 let next_epoch_seed = v2::EpochSeed::zero();
 
                 let load_result = GenesisConfigLoaded {
+                    fork: constraint_constants().fork.clone(),
                     constants: constants.clone(),
                     genesis_ledger_hash: genesis_ledger_hash.clone(),
                     genesis_total_currency,
@@ -195,6 +240,7 @@ impl GenesisConfig {
                 let next_epoch_seed = v2::EpochSeed::zero();
 
                 let load_result = GenesisConfigLoaded {
+                    fork: constraint_constants().fork.clone(),
                     constants: constants.clone(),
                     genesis_ledger_hash: genesis_ledger_hash.clone(),
                     genesis_total_currency,
@@ -323,7 +369,13 @@ impl GenesisConfig {
                     genesis_producer_stake_proof = create_genesis_producer_stake_proof(&mask);
                 }
 
+                let fork = config
+                    .proof
+                    .as_ref()
+                    .and_then(|proof| proof.fork.clone())
+                    .or_else(|| constraint_constants().fork.clone());
                 let result = GenesisConfigLoaded {
+                    fork,
                     constants,
                     genesis_ledger_hash,
                     genesis_total_currency: total_currency,
@@ -626,6 +678,7 @@ impl PrebuiltGenesisConfig {
             )?;
 
         let load_result = GenesisConfigLoaded {
+            fork: constraint_constants().fork.clone(),
             constants: self.constants,
             genesis_ledger_hash: self.ledger_hash,
             genesis_total_currency,
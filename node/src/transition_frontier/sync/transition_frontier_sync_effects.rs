@@ -427,7 +427,8 @@ where
 }
 
 /// Copies (if necessary) the genesis ledger into the sync ledger state
-/// for the staking epoch ledger to use as a starting point.
+/// for the staking epoch ledger to use as a starting point. If we already
+/// have the target ledger (e.g. restored from disk), that one is used instead.
 fn prepare_staking_epoch_ledger_for_sync<S>(
     store: &mut Store<S>,
     best_tip: &ArcBlockWithHash,
@@ -438,13 +439,16 @@ where
     let target = SyncLedgerTarget::staking_epoch(best_tip).snarked_ledger_hash;
     let origin = best_tip.genesis_ledger_hash().clone();
 
-    store
-        .service()
-        .copy_snarked_ledger_contents_for_sync(vec![origin], target, false)
+    store.service().copy_snarked_ledger_contents_for_sync(
+        vec![target.clone(), origin],
+        target,
+        false,
+    )
 }
 
 /// Copies (if necessary) the staking ledger into the sync ledger state
-/// for the next epoch ledger to use as a starting point.
+/// for the next epoch ledger to use as a starting point. If we already
+/// have the target ledger (e.g. restored from disk), that one is used instead.
 fn prepare_next_epoch_ledger_for_sync<S>(
     store: &mut Store<S>,
     best_tip: &ArcBlockWithHash,
@@ -460,9 +464,11 @@ where
     let target = next_epoch_sync.snarked_ledger_hash;
     let origin = SyncLedgerTarget::staking_epoch(best_tip).snarked_ledger_hash;
 
-    store
-        .service()
-        .copy_snarked_ledger_contents_for_sync(vec![origin], target, false)
+    store.service().copy_snarked_ledger_contents_for_sync(
+        vec![target.clone(), origin],
+        target,
+        false,
+    )
 }
 
 /// Copies (if necessary) the next epoch ledger into the sync ledger state
@@ -480,9 +486,12 @@ where
         .root_block()
         .expect("Sync root block cannot be missing");
 
-    // Attempt in order: previous root, next epoch ledger, staking ledger
-    let mut candidate_origins: Vec<LedgerHash> =
-        previous_root_snarked_ledger_hash.into_iter().collect();
+    let target = root_block.snarked_ledger_hash().clone();
+
+    // Attempt in order: target itself, previous root, next epoch ledger, staking ledger
+    let mut candidate_origins: Vec<LedgerHash> = std::iter::once(target.clone())
+        .chain(previous_root_snarked_ledger_hash)
+        .collect();
     if let Some(next_epoch) = SyncLedgerTarget::next_epoch(best_tip, root_block) {
         candidate_origins.push(next_epoch.snarked_ledger_hash.clone());
     }
@@ -492,8 +501,6 @@ where
             .clone(),
     );
 
-    let target = root_block.snarked_ledger_hash().clone();
-
     store
         .service()
         .copy_snarked_ledger_contents_for_sync(candidate_origins, target, false)
@@ -4,9 +4,10 @@ use crate::block_producer::BlockProducerAction;
 use crate::consensus::ConsensusAction;
 use crate::ledger::LEDGER_DEPTH;
 use crate::p2p::channels::best_tip::P2pChannelsBestTipAction;
+use crate::rpc::RpcAction;
 use crate::snark_pool::{SnarkPoolAction, SnarkWork};
 use crate::stats::sync::SyncingLedger;
-use crate::{Store, TransactionPoolAction};
+use crate::{Store, TransactionPoolAction, WatchedAccountsAction};
 
 use super::genesis::TransitionFrontierGenesisAction;
 use super::sync::ledger::snarked::{
@@ -311,6 +312,17 @@ fn synced_effects<S: crate::Service>(
             diff,
         });
     }
+    store.dispatch(RpcAction::BestTipSubscribersNotify);
+
+    for pub_key in store.state().watched_accounts.accounts() {
+        store.dispatch(WatchedAccountsAction::LedgerInitialStateGetInit {
+            pub_key: pub_key.clone(),
+        });
+        store.dispatch(WatchedAccountsAction::TransactionsIncludedInBlock {
+            pub_key,
+            block: best_tip.block.clone(),
+        });
+    }
 }
 
 // Handling of the actions related to the synchronization of a target ledger
@@ -8,6 +8,8 @@ use mina_p2p_messages::v2::{
 use openmina_core::block::{AppliedBlock, ArcBlockWithHash};
 use serde::{Deserialize, Serialize};
 
+use crate::ledger::RestoredFrontier;
+
 use super::genesis::TransitionFrontierGenesisState;
 use super::sync::TransitionFrontierSyncState;
 use super::TransitionFrontierConfig;
@@ -45,6 +47,13 @@ impl TransitionFrontierState {
         }
     }
 
+    /// Transition frontier restored from disk at startup. The node still
+    /// needs to sync with peers, but it will only have to sync the delta.
+    pub fn restore(&mut self, restored: RestoredFrontier) {
+        self.best_chain = restored.best_chain;
+        self.needed_protocol_states = restored.needed_protocol_states;
+    }
+
     pub fn best_tip(&self) -> Option<&ArcBlockWithHash> {
         self.best_chain.last().map(|b| &b.block)
     }
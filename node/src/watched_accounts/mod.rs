@@ -11,6 +11,7 @@ use mina_p2p_messages::v2::{
     NonZeroCurvePoint, NonZeroCurvePointUncompressedStableV1, StagedLedgerDiffDiffDiffStableV2,
     StagedLedgerDiffDiffPreDiffWithAtMostTwoCoinbaseStableV2B,
 };
+use openmina_core::block::ArcBlockWithHash;
 
 pub fn is_transaction_affecting_account(
     pub_key: &NonZeroCurvePoint,
@@ -49,3 +50,15 @@ pub fn account_relevant_transactions_in_diff_iter<'a>(
             status: tx.status.clone(),
         })
 }
+
+/// Whether the block has transactions affecting the account or pays it
+/// a coinbase or snark work fees.
+pub fn is_block_affecting_account(pub_key: &NonZeroCurvePoint, block: &ArcBlockWithHash) -> bool {
+    &block.consensus_state().coinbase_receiver == pub_key
+        || block
+            .completed_works_iter()
+            .any(|work| &work.prover == pub_key)
+        || account_relevant_transactions_in_diff_iter(pub_key, block.staged_ledger_diff())
+            .next()
+            .is_some()
+}
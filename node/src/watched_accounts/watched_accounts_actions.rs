@@ -6,8 +6,6 @@ use mina_p2p_messages::v2::{
 use openmina_core::block::BlockWithHash;
 use serde::{Deserialize, Serialize};
 
-use crate::p2p::PeerId;
-
 use super::{
     WatchedAccountBlockInfo, WatchedAccountBlockState, WatchedAccountLedgerInitialState,
     WatchedAccountsLedgerInitialStateGetError,
@@ -21,13 +19,15 @@ pub enum WatchedAccountsAction {
     Add {
         pub_key: NonZeroCurvePoint,
     },
+    Remove {
+        pub_key: NonZeroCurvePoint,
+    },
     LedgerInitialStateGetInit {
         pub_key: NonZeroCurvePoint,
     },
     LedgerInitialStateGetPending {
         pub_key: NonZeroCurvePoint,
         block: WatchedAccountBlockInfo,
-        peer_id: PeerId,
     },
     LedgerInitialStateGetError {
         pub_key: NonZeroCurvePoint,
@@ -38,8 +38,11 @@ pub enum WatchedAccountsAction {
     },
     LedgerInitialStateGetSuccess {
         pub_key: NonZeroCurvePoint,
+        block_hash: StateHash,
         data: Option<Box<MinaBaseAccountBinableArgStableV2>>,
     },
+    /// Block has transactions affecting the account or pays it a coinbase
+    /// or snark work fees.
     TransactionsIncludedInBlock {
         pub_key: NonZeroCurvePoint,
         block: BlockWithHash<Arc<MinaBlockBlockStableV2>>,
@@ -51,12 +54,11 @@ pub enum WatchedAccountsAction {
     BlockLedgerQueryPending {
         pub_key: NonZeroCurvePoint,
         block_hash: StateHash,
-        peer_id: PeerId,
     },
     BlockLedgerQuerySuccess {
         pub_key: NonZeroCurvePoint,
         block_hash: StateHash,
-        ledger_account: Box<MinaBaseAccountBinableArgStableV2>,
+        ledger_account: Option<Box<MinaBaseAccountBinableArgStableV2>>,
     },
 }
 
@@ -64,15 +66,15 @@ fn should_request_ledger_initial_state(state: &crate::State, pub_key: &NonZeroCu
     state
         .watched_accounts
         .get(pub_key)
-        .filter(|_| state.consensus.best_tip.is_some())
+        .filter(|_| state.transition_frontier.best_tip().is_some())
         .map_or(false, |a| match &a.initial_state {
             WatchedAccountLedgerInitialState::Idle { .. } => true,
             WatchedAccountLedgerInitialState::Error { .. } => true,
             WatchedAccountLedgerInitialState::Pending { block, .. } => {
-                let Some(best_tip) = state.consensus.best_tip() else {
+                let Some(best_tip) = state.transition_frontier.best_tip() else {
                     return false;
                 };
-                &block.hash != best_tip.hash
+                &block.hash != best_tip.hash()
             }
             // TODO(binier)
             WatchedAccountLedgerInitialState::Success { .. } => false,
@@ -87,6 +89,7 @@ impl redux::EnablingCondition<crate::State> for WatchedAccountsAction {
     fn is_enabled(&self, state: &crate::State, time: redux::Timestamp) -> bool {
         match self {
             WatchedAccountsAction::Add { pub_key } => state.watched_accounts.get(pub_key).is_none(),
+            WatchedAccountsAction::Remove { pub_key } => state.watched_accounts.contains(pub_key),
             WatchedAccountsAction::LedgerInitialStateGetInit { pub_key } => {
                 should_request_ledger_initial_state(state, pub_key)
             }
@@ -110,22 +113,19 @@ impl redux::EnablingCondition<crate::State> for WatchedAccountsAction {
                     }
                     _ => false,
                 }),
-            WatchedAccountsAction::LedgerInitialStateGetSuccess { pub_key, .. } => {
-                state.watched_accounts.get(pub_key).map_or(false, |a| {
-                    matches!(
-                        &a.initial_state,
-                        WatchedAccountLedgerInitialState::Pending { .. }
-                    )
-                })
-            }
+            WatchedAccountsAction::LedgerInitialStateGetSuccess {
+                pub_key,
+                block_hash,
+                ..
+            } => state.watched_accounts.get(pub_key).map_or(false, |a| {
+                a.initial_state
+                    .block()
+                    .map_or(false, |block| &block.hash == block_hash)
+            }),
             WatchedAccountsAction::TransactionsIncludedInBlock { pub_key, block } => {
-                let diff = &block.body().staged_ledger_diff.diff;
-                state
-                    .watched_accounts
-                    .get(pub_key)
-                    .map_or(false, |v| v.initial_state.is_success())
-                    && super::account_relevant_transactions_in_diff_iter(pub_key, diff)
-                        .any(|_| true)
+                state.watched_accounts.get(pub_key).map_or(false, |v| {
+                    v.initial_state.is_success() && v.block_find_by_hash(block.hash()).is_none()
+                }) && super::is_block_affecting_account(pub_key, block)
             }
             WatchedAccountsAction::BlockLedgerQueryInit {
                 pub_key,
@@ -149,23 +149,11 @@ impl redux::EnablingCondition<crate::State> for WatchedAccountsAction {
                     return false;
                 };
 
-                let should_req_for_block = acc
-                    .block_find_by_hash(block_hash)
+                acc.block_find_by_hash(block_hash)
                     .filter(|b| {
                         matches!(b, WatchedAccountBlockState::TransactionsInBlockBody { .. })
                     })
-                    .is_some();
-
-                // TODO(binier)
-                let p2p_rpc_is_pending = false;
-                // let p2p_rpc_is_pending = None
-                //     .or_else(|| {
-                //         let peer = state.p2p.get_ready_peer(&self.peer_id)?;
-                //         peer.rpc.outgoing.get(self.p2p_rpc_id)
-                //     })
-                //     .map_or(false, |v| v.is_init() || v.is_pending());
-
-                should_req_for_block && p2p_rpc_is_pending
+                    .is_some()
             }
             WatchedAccountsAction::BlockLedgerQuerySuccess {
                 pub_key,
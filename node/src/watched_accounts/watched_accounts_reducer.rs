@@ -1,7 +1,9 @@
+use crate::rpc::RpcAction;
+
 use super::{
     account_relevant_transactions_in_diff_iter, WatchedAccountBlockInfo, WatchedAccountBlockState,
     WatchedAccountLedgerInitialState, WatchedAccountState, WatchedAccountsAction,
-    WatchedAccountsActionWithMetaRef, WatchedAccountsState,
+    WatchedAccountsActionWithMetaRef, WatchedAccountsState, WATCHED_ACCOUNT_BLOCKS_LIMIT,
 };
 
 impl WatchedAccountsState {
@@ -30,82 +32,46 @@ impl WatchedAccountsState {
                 let dispatcher = state_context.into_dispatcher();
                 dispatcher.push(WatchedAccountsAction::LedgerInitialStateGetInit { pub_key });
             }
-            WatchedAccountsAction::LedgerInitialStateGetInit { pub_key: _ }
-            | WatchedAccountsAction::LedgerInitialStateGetRetry { pub_key: _ } => {
-                // TODO(binier)
-                // let Some((peer_id, p2p_rpc_id)) = store.state().p2p.get_free_peer_id_for_rpc() else { return };
-                // let block = {
-                //     let Some(block) = store.state().consensus.best_tip() else { return };
-                //     WatchedAccountBlockInfo {
-                //         level: block.height() as u32,
-                //         hash: block.hash.clone(),
-                //         pred_hash: block.header.protocol_state.previous_state_hash.clone(),
-                //         staged_ledger_hash: block
-                //             .header
-                //             .protocol_state
-                //             .body
-                //             .blockchain_state
-                //             .staged_ledger_hash
-                //             .non_snark
-                //             .ledger_hash
-                //             .clone(),
-                //     }
-                // };
-
-                // let token_id = MinaBaseAccountIdDigestStableV1(BigInt::one());
-
-                // dispatcher.push(P2pRpcOutgoingInitAction {
-                //     peer_id: peer_id.clone(),
-                //     rpc_id: p2p_rpc_id,
-                //     request: P2pRpcRequest::LedgerQuery((
-                //         block.staged_ledger_hash.0.clone(),
-                //         MinaLedgerSyncLedgerQueryStableV1::WhatAccountWithPath(
-                //             pub_key.clone(),
-                //             token_id.into(),
-                //         ),
-                //     )),
-                //     requestor: P2pRpcRequestor::WatchedAccount(
-                //         P2pRpcRequestorWatchedAccount::LedgerInitialGet(pub_key.clone()),
-                //     ),
-                // });
-                // dispatcher.push(WatchedAccountsLedgerInitialStateGetPendingAction {
-                //     pub_key,
-                //     block,
-                //     peer_id,
-                //     p2p_rpc_id,
-                // });
+            WatchedAccountsAction::Remove { pub_key } => {
+                state.remove(pub_key);
+
+                let public_key = pub_key.clone().into();
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(RpcAction::WatchedAccountSubscribersNotify { public_key });
             }
-            WatchedAccountsAction::LedgerInitialStateGetPending {
-                pub_key,
-                block,
-                peer_id,
-            } => {
+            WatchedAccountsAction::LedgerInitialStateGetInit { pub_key }
+            | WatchedAccountsAction::LedgerInitialStateGetRetry { pub_key } => {
+                let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
+                let Some(best_tip) = global_state.transition_frontier.best_tip() else {
+                    return;
+                };
+                dispatcher.push(WatchedAccountsAction::LedgerInitialStateGetPending {
+                    pub_key: pub_key.clone(),
+                    block: best_tip.into(),
+                });
+            }
+            WatchedAccountsAction::LedgerInitialStateGetPending { pub_key, block } => {
                 let Some(account) = state.get_mut(pub_key) else {
                     return;
                 };
                 account.blocks.clear();
 
+                // Account is read from the ledger by `LedgerReadAction::FindTodos`.
                 account.initial_state = WatchedAccountLedgerInitialState::Pending {
                     time: meta.time(),
                     block: block.clone(),
-                    peer_id: *peer_id,
                 };
             }
             WatchedAccountsAction::LedgerInitialStateGetError { pub_key, error } => {
                 let Some(account) = state.get_mut(pub_key) else {
                     return;
                 };
-                let peer_id = match &account.initial_state {
-                    WatchedAccountLedgerInitialState::Pending { peer_id, .. } => *peer_id,
-                    _ => return,
-                };
                 account.initial_state = WatchedAccountLedgerInitialState::Error {
                     time: meta.time(),
                     error: error.clone(),
-                    peer_id,
                 };
             }
-            WatchedAccountsAction::LedgerInitialStateGetSuccess { pub_key, data } => {
+            WatchedAccountsAction::LedgerInitialStateGetSuccess { pub_key, data, .. } => {
                 let Some(account) = state.get_mut(pub_key) else {
                     return;
                 };
@@ -117,6 +83,10 @@ impl WatchedAccountsState {
                     block: block.clone(),
                     data: data.clone(),
                 };
+
+                let public_key = pub_key.clone().into();
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(RpcAction::WatchedAccountSubscribersNotify { public_key });
             }
             WatchedAccountsAction::TransactionsIncludedInBlock { pub_key, block } => {
                 let transactions = account_relevant_transactions_in_diff_iter(
@@ -128,86 +98,47 @@ impl WatchedAccountsState {
                 let Some(account) = state.get_mut(pub_key) else {
                     return;
                 };
+                let block_info = WatchedAccountBlockInfo::from(block);
+                // Blocks at the same or higher level were on a fork that
+                // is no longer the best chain.
+                while account
+                    .blocks
+                    .back()
+                    .map_or(false, |b| b.block().level >= block_info.level)
+                {
+                    account.blocks.pop_back();
+                }
                 account
                     .blocks
                     .push_back(WatchedAccountBlockState::TransactionsInBlockBody {
-                        block: WatchedAccountBlockInfo {
-                            level: block
-                                .block
-                                .header
-                                .protocol_state
-                                .body
-                                .consensus_state
-                                .blockchain_length
-                                .0
-                                 .0,
-                            hash: block.hash.clone(),
-                            pred_hash: block
-                                .block
-                                .header
-                                .protocol_state
-                                .previous_state_hash
-                                .clone(),
-                            staged_ledger_hash: block
-                                .block
-                                .header
-                                .protocol_state
-                                .body
-                                .blockchain_state
-                                .staged_ledger_hash
-                                .non_snark
-                                .ledger_hash
-                                .clone(),
-                        },
+                        block: block_info,
                         transactions,
                     });
+                while account.blocks.len() > WATCHED_ACCOUNT_BLOCKS_LIMIT {
+                    account.blocks.pop_front();
+                }
 
                 let pub_key = pub_key.clone();
-                let block_hash = block.hash.clone();
+                let block_hash = block.hash().clone();
                 let dispatcher = state_context.into_dispatcher();
                 dispatcher.push(WatchedAccountsAction::BlockLedgerQueryInit {
                     pub_key,
                     block_hash,
                 });
             }
-            WatchedAccountsAction::BlockLedgerQueryInit { .. } => {
-                // TODO(binier)
-                // let Some((peer_id, p2p_rpc_id)) = store.state().p2p.get_free_peer_id_for_rpc() else { return };
-                // let ledger_hash = {
-                //     let Some(acc) = store.state().watched_accounts.get(&action.pub_key) else { return };
-                //     let Some(block) = acc.block_find_by_hash(&action.block_hash) else { return };
-                //     block.block().staged_ledger_hash.0.clone()
-                // };
-                // let token_id = MinaBaseAccountIdDigestStableV1(BigInt::one());
-
-                // store.dispatch(P2pRpcOutgoingInitAction {
-                //     peer_id: peer_id.clone(),
-                //     rpc_id: p2p_rpc_id,
-                //     request: P2pRpcRequest::LedgerQuery((
-                //         ledger_hash,
-                //         MinaLedgerSyncLedgerQueryStableV1::WhatAccountWithPath(
-                //             action.pub_key.clone(),
-                //             token_id.into(),
-                //         ),
-                //     )),
-                //     requestor: P2pRpcRequestor::WatchedAccount(
-                //         P2pRpcRequestorWatchedAccount::BlockLedgerGet(
-                //             action.pub_key.clone(),
-                //             action.block_hash.clone(),
-                //         ),
-                //     ),
-                // });
-                // store.dispatch(WatchedAccountsBlockLedgerQueryPendingAction {
-                //     pub_key: action.pub_key,
-                //     block_hash: action.block_hash,
-                //     peer_id,
-                //     p2p_rpc_id,
-                // });
+            WatchedAccountsAction::BlockLedgerQueryInit {
+                pub_key,
+                block_hash,
+            } => {
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(WatchedAccountsAction::BlockLedgerQueryPending {
+                    pub_key: pub_key.clone(),
+                    block_hash: block_hash.clone(),
+                });
             }
             WatchedAccountsAction::BlockLedgerQueryPending {
                 pub_key,
                 block_hash,
-                ..
             } => {
                 let Some(account) = state.get_mut(pub_key) else {
                     return;
@@ -215,6 +146,7 @@ impl WatchedAccountsState {
                 let Some(block_state) = account.block_find_by_hash_mut(block_hash) else {
                     return;
                 };
+                // Account is read from the ledger by `LedgerReadAction::FindTodos`.
                 *block_state = match block_state {
                     WatchedAccountBlockState::TransactionsInBlockBody {
                         block,
@@ -249,7 +181,366 @@ impl WatchedAccountsState {
                     },
                     _ => return,
                 };
+
+                let public_key = pub_key.clone().into();
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(RpcAction::WatchedAccountSubscribersNotify { public_key });
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mina_p2p_messages::v2::{MinaBaseAccountBinableArgStableV2, NonZeroCurvePoint};
+    use openmina_core::block::ArcBlockWithHash;
+    use redux::{ActionMeta, Dispatcher, EnablingCondition, Timestamp};
+
+    use crate::{
+        account::AccountSecretKey, testing::recorded_block,
+        watched_accounts::WatchedAccountsLedgerInitialStateGetError, State,
+    };
+
+    use super::*;
+
+    fn pub_key(i: u32) -> NonZeroCurvePoint {
+        AccountSecretKey::deterministic(i.into())
+            .public_key()
+            .into()
+    }
+
+    /// Blocks at the given heights, built from a recorded block.
+    fn blocks(heights: impl IntoIterator<Item = u32>) -> Vec<ArcBlockWithHash> {
+        heights
+            .into_iter()
+            .map(|height| {
+                let mut block = recorded_block();
+                block
+                    .header
+                    .protocol_state
+                    .body
+                    .consensus_state
+                    .blockchain_length = height.into();
+                ArcBlockWithHash::try_new(Arc::new(block)).unwrap()
+            })
+            .collect()
+    }
+
+    fn ledger_account(balance: u64) -> Box<MinaBaseAccountBinableArgStableV2> {
+        let mut account = ledger::Account::empty();
+        account.balance = ledger::scan_state::currency::Balance::from_u64(balance);
+        Box::new((&account).into())
+    }
+
+    fn reduce(state: &mut State, time: Timestamp, action: WatchedAccountsAction) {
+        let mut dispatcher = Dispatcher::new();
+        let substate = crate::Substate::new(state, &mut dispatcher);
+        WatchedAccountsState::reducer(substate, ActionMeta::zero_custom(time).with_action(&action));
+    }
+
+    /// Watched account with its initial state read at `block`.
+    fn watched(state: &mut State, pub_key: &NonZeroCurvePoint, block: &ArcBlockWithHash) {
+        let time = Timestamp::ZERO;
+        let pub_key = pub_key.clone();
+        reduce(
+            state,
+            time,
+            WatchedAccountsAction::Add {
+                pub_key: pub_key.clone(),
+            },
+        );
+        reduce(
+            state,
+            time,
+            WatchedAccountsAction::LedgerInitialStateGetPending {
+                pub_key: pub_key.clone(),
+                block: block.into(),
+            },
+        );
+        reduce(
+            state,
+            time,
+            WatchedAccountsAction::LedgerInitialStateGetSuccess {
+                pub_key,
+                block_hash: block.hash().clone(),
+                data: Some(ledger_account(100)),
+            },
+        );
+    }
+
+    fn balance(account: Option<&MinaBaseAccountBinableArgStableV2>) -> u64 {
+        account.unwrap().balance.0.as_u64()
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let (key, other) = (pub_key(0), pub_key(1));
+        let add = |pub_key: &NonZeroCurvePoint| WatchedAccountsAction::Add {
+            pub_key: pub_key.clone(),
+        };
+        let remove = |pub_key: &NonZeroCurvePoint| WatchedAccountsAction::Remove {
+            pub_key: pub_key.clone(),
+        };
+
+        assert!(add(&key).is_enabled(&state, Timestamp::ZERO));
+        assert!(!remove(&key).is_enabled(&state, Timestamp::ZERO));
+        reduce(&mut state, Timestamp::ZERO, add(&key));
+        reduce(&mut state, Timestamp::ZERO, add(&other));
+        assert_eq!(state.watched_accounts.accounts(), {
+            let mut keys = vec![key.clone(), other.clone()];
+            keys.sort();
+            keys
+        });
+        let account = state.watched_accounts.get(&key).unwrap();
+        assert!(matches!(
+            account.initial_state,
+            WatchedAccountLedgerInitialState::Idle { .. }
+        ));
+        assert!(account.blocks.is_empty());
+        assert!(account.latest().is_none());
+        // already watched, and the initial state isn't read without a best tip.
+        assert!(!add(&key).is_enabled(&state, Timestamp::ZERO));
+        assert!(!WatchedAccountsAction::LedgerInitialStateGetInit {
+            pub_key: key.clone()
+        }
+        .is_enabled(&state, Timestamp::ZERO));
+
+        assert!(remove(&key).is_enabled(&state, Timestamp::ZERO));
+        reduce(&mut state, Timestamp::ZERO, remove(&key));
+        assert!(!state.watched_accounts.contains(&key));
+        assert!(state.watched_accounts.contains(&other));
+        assert!(!remove(&key).is_enabled(&state, Timestamp::ZERO));
+    }
+
+    #[test]
+    fn initial_state() {
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let key = pub_key(0);
+        let [block] = blocks([10]).try_into().unwrap();
+        let time = Timestamp::ZERO;
+
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::Add {
+                pub_key: key.clone(),
+            },
+        );
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::LedgerInitialStateGetPending {
+                pub_key: key.clone(),
+                block: (&block).into(),
+            },
+        );
+        let account = state.watched_accounts.get(&key).unwrap();
+        let pending = account.ledger_queries_pending().collect::<Vec<_>>();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(&pending[0].hash, block.hash());
+        assert_eq!(&pending[0].staged_ledger_hash, block.merkle_root_hash());
+
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::LedgerInitialStateGetError {
+                pub_key: key.clone(),
+                error: WatchedAccountsLedgerInitialStateGetError::LedgerNotFound,
+            },
+        );
+        let retry = WatchedAccountsAction::LedgerInitialStateGetRetry {
+            pub_key: key.clone(),
+        };
+        assert!(!retry.is_enabled(&state, time));
+        assert!(retry.is_enabled(&state, time + 3_000_000_000));
+        let account = state.watched_accounts.get(&key).unwrap();
+        assert_eq!(account.ledger_queries_pending().count(), 0);
+
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::LedgerInitialStateGetPending {
+                pub_key: key.clone(),
+                block: (&block).into(),
+            },
+        );
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::LedgerInitialStateGetSuccess {
+                pub_key: key.clone(),
+                block_hash: block.hash().clone(),
+                data: Some(ledger_account(100)),
+            },
+        );
+        let account = state.watched_accounts.get(&key).unwrap();
+        assert!(account.initial_state.is_success());
+        assert_eq!(balance(account.initial_state.data()), 100);
+        let (latest_block, latest_account) = account.latest().unwrap();
+        assert_eq!(&latest_block.hash, block.hash());
+        assert_eq!(balance(latest_account), 100);
+        assert_eq!(account.ledger_queries_pending().count(), 0);
+    }
+
+    #[test]
+    fn update_in_new_block() {
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let key = pub_key(0);
+        let [genesis, block] = blocks([10, 11]).try_into().unwrap();
+        let time = Timestamp::ZERO;
+        watched(&mut state, &key, &genesis);
+
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::TransactionsIncludedInBlock {
+                pub_key: key.clone(),
+                block: block.clone(),
+            },
+        );
+        let account = state.watched_accounts.get(&key).unwrap();
+        let block_state = account.block_find_by_hash(block.hash()).unwrap();
+        assert!(matches!(
+            block_state,
+            WatchedAccountBlockState::TransactionsInBlockBody { .. }
+        ));
+        assert_eq!(block_state.block().level, 11);
+        // the recorded block has no transactions of the account.
+        assert!(block_state.transactions().is_empty());
+        assert!(WatchedAccountsAction::BlockLedgerQueryInit {
+            pub_key: key.clone(),
+            block_hash: block.hash().clone(),
+        }
+        .is_enabled(&state, time));
+
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::BlockLedgerQueryPending {
+                pub_key: key.clone(),
+                block_hash: block.hash().clone(),
+            },
+        );
+        let account = state.watched_accounts.get(&key).unwrap();
+        let pending = account.ledger_queries_pending().collect::<Vec<_>>();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(&pending[0].hash, block.hash());
+        // not read yet, so the latest state is still the initial one.
+        assert_eq!(&account.latest().unwrap().0.hash, genesis.hash());
+
+        reduce(
+            &mut state,
+            time,
+            WatchedAccountsAction::BlockLedgerQuerySuccess {
+                pub_key: key.clone(),
+                block_hash: block.hash().clone(),
+                ledger_account: Some(ledger_account(150)),
+            },
+        );
+        let account = state.watched_accounts.get(&key).unwrap();
+        let block_state = account.block_find_by_hash(block.hash()).unwrap();
+        assert_eq!(balance(block_state.ledger_account()), 150);
+        let (latest_block, latest_account) = account.latest().unwrap();
+        assert_eq!(&latest_block.hash, block.hash());
+        assert_eq!(balance(latest_account), 150);
+        assert_eq!(account.ledger_queries_pending().count(), 0);
+        // not applicable once the account was read.
+        assert!(!WatchedAccountsAction::BlockLedgerQuerySuccess {
+            pub_key: key.clone(),
+            block_hash: block.hash().clone(),
+            ledger_account: None,
+        }
+        .is_enabled(&state, time));
+    }
+
+    #[test]
+    fn blocks_of_a_fork_are_replaced() {
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let key = pub_key(0);
+        let [genesis, b11, b12, fork_b11] = blocks([10, 11, 12, 11]).try_into().unwrap();
+        // same height, different block.
+        let mut fork_b11 = fork_b11.block.as_ref().clone();
+        fork_b11.header.protocol_state.previous_state_hash = b12.hash().clone();
+        let fork_b11 = ArcBlockWithHash::try_new(Arc::new(fork_b11)).unwrap();
+        assert_ne!(fork_b11.hash(), b11.hash());
+        watched(&mut state, &key, &genesis);
+
+        for block in [&b11, &b12, &fork_b11] {
+            reduce(
+                &mut state,
+                Timestamp::ZERO,
+                WatchedAccountsAction::TransactionsIncludedInBlock {
+                    pub_key: key.clone(),
+                    block: block.clone(),
+                },
+            );
+        }
+        let account = state.watched_accounts.get(&key).unwrap();
+        let hashes = account
+            .blocks
+            .iter()
+            .map(|b| b.block().hash.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(hashes, [fork_b11.hash().clone()]);
+    }
+
+    #[test]
+    fn blocks_history_is_limited() {
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let key = pub_key(0);
+        let limit = WATCHED_ACCOUNT_BLOCKS_LIMIT as u32;
+        let blocks = blocks(10..10 + limit + 2);
+        watched(&mut state, &key, &blocks[0]);
+
+        for block in &blocks[1..] {
+            reduce(
+                &mut state,
+                Timestamp::ZERO,
+                WatchedAccountsAction::TransactionsIncludedInBlock {
+                    pub_key: key.clone(),
+                    block: block.clone(),
+                },
+            );
+        }
+        let account = state.watched_accounts.get(&key).unwrap();
+        assert_eq!(account.blocks.len(), WATCHED_ACCOUNT_BLOCKS_LIMIT);
+        assert_eq!(account.blocks.front().unwrap().block().level, 12);
+        assert_eq!(
+            &account.blocks.back().unwrap().block().hash,
+            blocks.last().unwrap().hash()
+        );
+    }
+
+    #[test]
+    fn updates_of_unwatched_accounts_are_ignored() {
+        let mut state = State::for_tests(Timestamp::ZERO);
+        let (key, other) = (pub_key(0), pub_key(1));
+        let [genesis, block] = blocks([10, 11]).try_into().unwrap();
+        watched(&mut state, &key, &genesis);
+
+        reduce(
+            &mut state,
+            Timestamp::ZERO,
+            WatchedAccountsAction::TransactionsIncludedInBlock {
+                pub_key: other.clone(),
+                block: block.clone(),
+            },
+        );
+        reduce(
+            &mut state,
+            Timestamp::ZERO,
+            WatchedAccountsAction::BlockLedgerQuerySuccess {
+                pub_key: key.clone(),
+                block_hash: block.hash().clone(),
+                ledger_account: Some(ledger_account(1)),
+            },
+        );
+        assert!(!state.watched_accounts.contains(&other));
+        let account = state.watched_accounts.get(&key).unwrap();
+        assert!(account.blocks.is_empty());
+        assert_eq!(balance(account.latest().unwrap().1), 100);
+    }
+}
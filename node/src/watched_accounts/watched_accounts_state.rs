@@ -4,9 +4,11 @@ use mina_p2p_messages::v2::{
     LedgerHash, MinaBaseAccountBinableArgStableV2, MinaBaseTransactionStatusStableV2,
     MinaBaseUserCommandStableV2, NonZeroCurvePoint, StateHash, TransactionHash,
 };
+use openmina_core::block::ArcBlockWithHash;
 use serde::{Deserialize, Serialize};
 
-use crate::p2p::PeerId;
+/// Maximum number of blocks kept in the history of a watched account.
+pub const WATCHED_ACCOUNT_BLOCKS_LIMIT: usize = 256;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WatchedAccountBlockInfo {
@@ -16,6 +18,17 @@ pub struct WatchedAccountBlockInfo {
     pub staged_ledger_hash: LedgerHash,
 }
 
+impl From<&ArcBlockWithHash> for WatchedAccountBlockInfo {
+    fn from(block: &ArcBlockWithHash) -> Self {
+        Self {
+            level: block.height(),
+            hash: block.hash().clone(),
+            pred_hash: block.pred_hash().clone(),
+            staged_ledger_hash: block.merkle_root_hash().clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Transaction {
     pub hash: Option<TransactionHash>,
@@ -25,9 +38,8 @@ pub struct Transaction {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum WatchedAccountsLedgerInitialStateGetError {
-    PeerDisconnected,
-    // TransportError(P2pRpcOutgoingError),
-    P2pRpcError(mina_p2p_messages::core::Info),
+    /// Ledger of the block is no longer (or not yet) available.
+    LedgerNotFound,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -39,12 +51,10 @@ pub enum WatchedAccountLedgerInitialState {
     Pending {
         time: redux::Timestamp,
         block: WatchedAccountBlockInfo,
-        peer_id: PeerId,
     },
     Error {
         time: redux::Timestamp,
         error: WatchedAccountsLedgerInitialStateGetError,
-        peer_id: PeerId,
     },
     Success {
         time: redux::Timestamp,
@@ -93,7 +103,8 @@ pub enum WatchedAccountBlockState {
         block: WatchedAccountBlockInfo,
         /// Transactions included in the block ordered by nonce from low to high.
         transactions: Vec<Transaction>,
-        ledger_account: Box<MinaBaseAccountBinableArgStableV2>,
+        /// `None` if the account isn't in the ledger of the block.
+        ledger_account: Option<Box<MinaBaseAccountBinableArgStableV2>>,
     },
 }
 
@@ -118,7 +129,7 @@ impl WatchedAccountBlockState {
         match self {
             Self::TransactionsInBlockBody { .. } => None,
             Self::LedgerAccountGetPending { .. } => None,
-            Self::LedgerAccountGetSuccess { ledger_account, .. } => Some(ledger_account),
+            Self::LedgerAccountGetSuccess { ledger_account, .. } => ledger_account.as_deref(),
         }
     }
 }
@@ -147,6 +158,40 @@ impl WatchedAccountState {
             .rev()
             .find(|b| &b.block().hash == hash)
     }
+
+    /// Blocks at which the account needs to be read from the ledger.
+    pub fn ledger_queries_pending(&self) -> impl '_ + Iterator<Item = &'_ WatchedAccountBlockInfo> {
+        let blocks = self.blocks.iter().filter_map(|b| match b {
+            WatchedAccountBlockState::LedgerAccountGetPending { block, .. } => Some(block),
+            _ => None,
+        });
+        self.initial_state.block().into_iter().chain(blocks)
+    }
+
+    /// Latest known state of the account and the block it was read at.
+    /// Account is `None` if it isn't in the ledger of that block.
+    pub fn latest(
+        &self,
+    ) -> Option<(
+        &WatchedAccountBlockInfo,
+        Option<&MinaBaseAccountBinableArgStableV2>,
+    )> {
+        self.blocks
+            .iter()
+            .rev()
+            .find_map(|b| match b {
+                WatchedAccountBlockState::LedgerAccountGetSuccess { block, .. } => {
+                    Some((block, b.ledger_account()))
+                }
+                _ => None,
+            })
+            .or_else(|| match &self.initial_state {
+                WatchedAccountLedgerInitialState::Success { block, data, .. } => {
+                    Some((block, data.as_deref()))
+                }
+                _ => None,
+            })
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -175,6 +220,10 @@ impl WatchedAccountsState {
         self.list.insert(key, value);
     }
 
+    pub fn remove(&mut self, key: &NonZeroCurvePoint) -> Option<WatchedAccountState> {
+        self.list.remove(key)
+    }
+
     pub fn iter(
         &self,
     ) -> impl '_ + Iterator<Item = (&'_ NonZeroCurvePoint, &'_ WatchedAccountState)> {
@@ -239,9 +239,14 @@ impl Cluster {
                 .unwrap()
         });
 
-        let (block_producer_sec_key, block_producer_config) = testing_config
+        let (block_producer_sec_keys, block_producer_config) = testing_config
             .block_producer
-            .map(|v| (v.sec_key, v.config))
+            .map(|v| {
+                let sec_keys = std::iter::once(v.sec_key)
+                    .chain(v.extra_sec_keys)
+                    .collect::<Vec<_>>();
+                (sec_keys, v.config)
+            })
             .unzip();
 
         let initial_peers = testing_config
@@ -281,6 +286,7 @@ impl Cluster {
                 listen_port: Some(http_port),
                 identity_pub_key: p2p_sec_key.public_key(),
                 initial_peers,
+                initial_banned_peers: Default::default(),
                 ask_initial_peers_interval: testing_config.ask_initial_peers_interval,
                 enabled_channels: ChannelId::iter_all().collect(),
                 peer_discovery: true,
@@ -318,9 +324,9 @@ impl Cluster {
                 }
             });
 
-        if let Some(keypair) = block_producer_sec_key {
+        if let Some(keypairs) = block_producer_sec_keys {
             let provers = BlockProver::make(None, None);
-            service_builder.block_producer_init(provers, keypair);
+            service_builder.block_producer_init(provers, keypairs);
         }
 
         let real_service = service_builder
@@ -11,7 +11,7 @@ use time::OffsetDateTime;
 
 use crate::node::OcamlStep;
 use crate::{
-    cluster::{Cluster, ClusterNodeId, ClusterOcamlNodeId},
+    cluster::{Cluster, ClusterConfig, ClusterNodeId, ClusterOcamlNodeId},
     network_debugger::Debugger,
     node::{
         DaemonJson, DaemonJsonGenConfig, Node, NodeTestingConfig, NonDeterministicEvent, OcamlNode,
@@ -41,6 +41,10 @@ impl<'a> ClusterRunner<'a> {
         }
     }
 
+    pub fn cluster_config(&self) -> &ClusterConfig {
+        &self.cluster.config
+    }
+
     pub fn node(&self, node_id: ClusterNodeId) -> Option<&Node> {
         self.cluster.node(node_id)
     }
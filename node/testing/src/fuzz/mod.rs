@@ -0,0 +1,404 @@
+//! Deterministic fuzzing of the node state machine, based on the recordings
+//! made by [`node::recorder::Recorder`].
+//!
+//! Input actions of the recording are mutated and replayed against the
+//! mocked services, while the invariants are checked after every action.
+//! Once a failure is found, the mutated inputs are minimized and recorded
+//! again, so that the failing run can be replayed with `openmina replay`.
+
+mod mutation;
+pub use mutation::Mutation;
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use node::{
+    core::channels::mpsc,
+    p2p::identity::SecretKey as P2pSecretKey,
+    recorder::{Recorder, StateWithInputActionsReader},
+    snark::BlockVerifier,
+    Action, ActionWithMeta, State, Store,
+};
+use openmina_node_invariants::{InvariantResult, Invariants};
+use openmina_node_native::NodeService;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{cluster::ClusterNodeId, service::NodeTestingService};
+
+/// Input action of the recording (`CheckTimeouts` or a new event), with the
+/// time at which it gets dispatched.
+#[derive(Debug, Clone)]
+pub struct FuzzInput {
+    pub time: redux::Timestamp,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub enum FuzzFailure {
+    InvariantViolation {
+        invariant: &'static str,
+        violation: String,
+    },
+    Panic {
+        location: Option<String>,
+        message: String,
+    },
+}
+
+impl FuzzFailure {
+    /// Whether the failures are caused by the same bug, used to make sure
+    /// that minimization doesn't switch to a different one.
+    fn is_same(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::InvariantViolation { invariant: a, .. },
+                Self::InvariantViolation { invariant: b, .. },
+            ) => a == b,
+            (Self::Panic { location: a, .. }, Self::Panic { location: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+
+    fn from_panic(payload: Box<dyn Any + Send>) -> Self {
+        let location = LAST_PANIC_LOCATION.with(|v| v.borrow_mut().take());
+        match payload.downcast::<Self>() {
+            Ok(failure) => *failure,
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                Self::Panic { location, message }
+            }
+        }
+    }
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvariantViolation {
+                invariant,
+                violation,
+            } => write!(f, "Invariant({invariant}) violated! violation: {violation}"),
+            Self::Panic {
+                location: Some(location),
+                message,
+            } => write!(f, "panicked at {location}: {message}"),
+            Self::Panic {
+                location: None,
+                message,
+            } => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplayFuzzerConfig {
+    /// Directory of the recording made by the recorder.
+    pub recording_dir: PathBuf,
+    /// Directory where the minimized reproducing recording is written.
+    pub output_dir: PathBuf,
+    pub seed: u64,
+    pub iterations: usize,
+    /// Maximum number of mutations applied to the recording in an iteration.
+    pub mutations: usize,
+    /// Maximum number of runs used to minimize the failing inputs.
+    pub minimize_runs: usize,
+}
+
+pub struct ReplayFuzzer {
+    config: ReplayFuzzerConfig,
+    rng_seed: [u8; 32],
+    p2p_sec_key: P2pSecretKey,
+    initial_state: State,
+    inputs: Vec<FuzzInput>,
+}
+
+impl ReplayFuzzer {
+    pub fn new(config: ReplayFuzzerConfig) -> anyhow::Result<Self> {
+        let reader = StateWithInputActionsReader::new(&config.recording_dir);
+        let initial_state = reader
+            .read_initial_state()
+            .map_err(|err| anyhow::anyhow!("failed to read initial state. err: {err}"))?;
+        let rng_seed = initial_state.rng_seed;
+        let p2p_sec_key = initial_state.p2p_sec_key;
+        let mut state = initial_state.state.into_owned();
+        // TODO(binier): we shouldn't have to do this, but serialized
+        // index/srs doesn't match deserialized one.
+        state.snark.block_verify.verifier_index = BlockVerifier::make();
+        state.snark.block_verify.verifier_srs = node::snark::get_srs();
+
+        let inputs = reader
+            .read_actions()
+            .flat_map(|(_, actions)| actions)
+            .filter_map(|action| action.as_action_with_meta().ok())
+            .map(|action| {
+                let (action, meta) = action.split();
+                FuzzInput {
+                    time: meta.time(),
+                    action,
+                }
+            })
+            .collect::<Vec<_>>();
+        if inputs.is_empty() {
+            anyhow::bail!("no input actions in the recording");
+        }
+
+        Ok(Self {
+            config,
+            rng_seed,
+            p2p_sec_key,
+            initial_state: state,
+            inputs,
+        })
+    }
+
+    /// Runs the fuzzer until a failure is found or the iterations run out.
+    ///
+    /// Returns the found failure, together with the path of the minimized
+    /// reproducing recording.
+    pub fn run(&self) -> anyhow::Result<Option<(FuzzFailure, PathBuf)>> {
+        let prev_hook = panic::take_hook();
+        panic::set_hook(Box::new(|info| {
+            let location = info.location().map(|l| l.to_string());
+            LAST_PANIC_LOCATION.with(|v| *v.borrow_mut() = location);
+        }));
+        let res = self.run_iterations();
+        panic::set_hook(prev_hook);
+        res
+    }
+
+    fn run_iterations(&self) -> anyhow::Result<Option<(FuzzFailure, PathBuf)>> {
+        if let Err(failure) = self.replay(&self.inputs, None) {
+            anyhow::bail!("unmutated recording already fails: {failure}");
+        }
+
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        for iteration in 0..self.config.iterations {
+            let mut inputs = self.inputs.clone();
+            let mutations = rng.gen_range(1..=self.config.mutations.max(1));
+            let changes = (0..mutations)
+                .filter_map(|_| {
+                    let mutation = Mutation::ALL[rng.gen_range(0..Mutation::ALL.len())];
+                    mutation.apply(&mut inputs, &mut rng)
+                })
+                .collect::<Vec<_>>();
+            if changes.is_empty() {
+                continue;
+            }
+
+            let Err(failure) = self.replay(&inputs, None) else {
+                continue;
+            };
+            eprintln!("iteration {iteration} failed: {failure}");
+            for change in &changes {
+                eprintln!("  {change}");
+            }
+
+            let inputs = self.minimize(inputs, &failure);
+            eprintln!("minimized to {} input actions", inputs.len());
+            let failure = match self.replay(&inputs, Some(&self.config.output_dir)) {
+                Err(failure) => failure,
+                Ok(()) => anyhow::bail!("minimized inputs no longer reproduce the failure"),
+            };
+            return Ok(Some((failure, self.config.output_dir.join("recorder"))));
+        }
+        Ok(None)
+    }
+
+    fn minimize(&self, inputs: Vec<FuzzInput>, failure: &FuzzFailure) -> Vec<FuzzInput> {
+        minimize(inputs, failure, self.config.minimize_runs, |inputs| {
+            self.replay(inputs, None)
+        })
+    }
+
+    /// Replays the inputs from the initial state of the recording. If
+    /// `record_dir` is set, the run is recorded there.
+    fn replay(&self, inputs: &[FuzzInput], record_dir: Option<&Path>) -> Result<(), FuzzFailure> {
+        let state = self.initial_state.clone();
+        let initial_time = state.time();
+
+        let mut real =
+            NodeService::for_replay(self.rng_seed, initial_time, self.p2p_sec_key.clone(), None);
+        if let Some(dir) = record_dir {
+            real.recorder = Recorder::only_input_actions(dir);
+        }
+        let (_shutdown_tx, shutdown_rx) = mpsc::channel(1);
+        let mut service =
+            NodeTestingService::new(real, ClusterNodeId::new_unchecked(0), shutdown_rx);
+        service.set_replay();
+
+        panic::catch_unwind(AssertUnwindSafe(move || {
+            let mut store = Store::new(node::reducer, effects, service, initial_time.into(), state);
+            store.service.recorder().initial_state(
+                self.rng_seed,
+                self.p2p_sec_key.clone(),
+                store.state.get(),
+            );
+
+            let mut time = initial_time;
+            for input in inputs {
+                if let Some(passed) = input.time.checked_sub(time) {
+                    store.service.advance_time(passed.as_nanos() as u64);
+                    time = input.time;
+                }
+                store.dispatch(input.action.clone());
+            }
+        }))
+        .map_err(FuzzFailure::from_panic)
+    }
+}
+
+/// Removes chunks of the inputs for as long as the same failure is
+/// still reproduced by `replay`, running it at most `max_runs` times.
+fn minimize<F>(
+    mut inputs: Vec<FuzzInput>,
+    failure: &FuzzFailure,
+    max_runs: usize,
+    mut replay: F,
+) -> Vec<FuzzInput>
+where
+    F: FnMut(&[FuzzInput]) -> Result<(), FuzzFailure>,
+{
+    let mut runs = 0;
+    let mut chunk = inputs.len() / 2;
+    while chunk > 0 && runs < max_runs {
+        let mut reduced = false;
+        let mut start = 0;
+        while start < inputs.len() && runs < max_runs {
+            let end = (start + chunk).min(inputs.len());
+            let candidate = [&inputs[..start], &inputs[end..]].concat();
+            runs += 1;
+            match replay(&candidate) {
+                Err(f) if f.is_same(failure) => {
+                    inputs = candidate;
+                    reduced = true;
+                }
+                _ => start += chunk,
+            }
+        }
+        if !reduced {
+            chunk /= 2;
+        }
+    }
+    inputs
+}
+
+fn effects(store: &mut Store<NodeTestingService>, action: ActionWithMeta) {
+    for (invariant, res) in Invariants::check_all(store, &action) {
+        if let InvariantResult::Violation(violation) = res {
+            panic::panic_any(FuzzFailure::InvariantViolation {
+                invariant: invariant.to_str(),
+                violation,
+            });
+        }
+    }
+
+    node::effects(store, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use node::CheckTimeoutsAction;
+
+    use super::*;
+
+    /// Inputs that differ only by time, which is used to identify them.
+    fn inputs(len: u64) -> Vec<FuzzInput> {
+        (0..len)
+            .map(|i| FuzzInput {
+                time: redux::Timestamp::new(i),
+                action: Action::CheckTimeouts(CheckTimeoutsAction {}),
+            })
+            .collect()
+    }
+
+    fn ids(inputs: &[FuzzInput]) -> Vec<u64> {
+        inputs.iter().map(|input| u64::from(input.time)).collect()
+    }
+
+    fn violation(invariant: &'static str) -> FuzzFailure {
+        FuzzFailure::InvariantViolation {
+            invariant,
+            violation: String::new(),
+        }
+    }
+
+    /// Fails with `failure` if all the `needed` inputs are there.
+    fn replay_needing(
+        needed: &'static [u64],
+        failure: FuzzFailure,
+    ) -> impl FnMut(&[FuzzInput]) -> Result<(), FuzzFailure> {
+        move |inputs| {
+            let ids = ids(inputs);
+            if needed.iter().all(|id| ids.contains(id)) {
+                Err(failure.clone())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimize_to_needed_inputs() {
+        let failure = violation("a");
+        let replay = replay_needing(&[3, 11, 12], failure.clone());
+        let minimized = minimize(inputs(20), &failure, 1000, replay);
+        assert_eq!(ids(&minimized), [3, 11, 12]);
+    }
+
+    #[test]
+    fn test_minimize_keeps_same_failure() {
+        let failure = violation("a");
+        // removing input 5 triggers a different failure, which must not be
+        // taken as a reproduction.
+        let replay = |inputs: &[FuzzInput]| {
+            let ids = ids(inputs);
+            match (ids.contains(&2), ids.contains(&5)) {
+                (true, true) => Err(violation("a")),
+                (true, false) => Err(violation("b")),
+                _ => Ok(()),
+            }
+        };
+        let minimized = minimize(inputs(8), &failure, 1000, replay);
+        assert_eq!(ids(&minimized), [2, 5]);
+    }
+
+    #[test]
+    fn test_minimize_run_limit() {
+        let failure = violation("a");
+        let mut runs = 0;
+        let mut replay = replay_needing(&[0], failure.clone());
+        let minimized = minimize(inputs(64), &failure, 3, |inputs| {
+            runs += 1;
+            replay(inputs)
+        });
+        assert_eq!(runs, 3);
+        assert!(minimized.len() < 64);
+        assert_eq!(ids(&minimized)[0], 0);
+    }
+
+    #[test]
+    fn test_same_failure() {
+        let panic_at = |location: &str| FuzzFailure::Panic {
+            location: Some(location.to_owned()),
+            message: String::new(),
+        };
+        assert!(violation("a").is_same(&violation("a")));
+        assert!(!violation("a").is_same(&violation("b")));
+        assert!(panic_at("a.rs:1").is_same(&panic_at("a.rs:1")));
+        assert!(!panic_at("a.rs:1").is_same(&panic_at("a.rs:2")));
+        assert!(!violation("a").is_same(&panic_at("a")));
+    }
+}
@@ -0,0 +1,299 @@
+use std::time::Duration;
+
+use node::{
+    event_source::Event,
+    ledger::LedgerEvent,
+    p2p::{
+        channels::{rpc::RpcChannelMsg, ChannelMsg},
+        MioEvent, P2pChannelEvent, P2pEvent,
+    },
+    Action, EventSourceAction,
+};
+use rand::{rngs::StdRng, Rng};
+
+use super::FuzzInput;
+
+/// Maximum delay (in seconds) by which the inputs are shifted after a
+/// dropped response.
+const MAX_TIMEOUT_DELAY_SECS: u64 = 120;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Mutation {
+    /// Corrupt the data received from a peer, or make the service report the
+    /// received channel message as malformed.
+    MalformedP2pMessage,
+    /// Swap the order in which two responses are received.
+    ReorderResponses,
+    /// Drop a response and delay the following inputs, so that whatever
+    /// waits for the response times out.
+    Timeout,
+}
+
+impl Mutation {
+    pub const ALL: [Mutation; 3] = [
+        Mutation::MalformedP2pMessage,
+        Mutation::ReorderResponses,
+        Mutation::Timeout,
+    ];
+
+    /// Applies the mutation to random inputs. Returns the description of the
+    /// change, or `None` if there was no input the mutation applies to.
+    pub fn apply(self, inputs: &mut Vec<FuzzInput>, rng: &mut StdRng) -> Option<String> {
+        match self {
+            Self::MalformedP2pMessage => {
+                let index = choose_index(inputs, rng, is_p2p_message)?;
+                match event_mut(&mut inputs[index])? {
+                    Event::P2p(P2pEvent::MioEvent(MioEvent::IncomingDataDidReceive(
+                        _,
+                        Ok(data),
+                    ))) => {
+                        let mut bytes = data.0.to_vec();
+                        let change = corrupt_bytes(&mut bytes, rng);
+                        data.0 = bytes.into();
+                        Some(format!("{change} in data received at input {index}"))
+                    }
+                    Event::P2p(P2pEvent::Channel(P2pChannelEvent::Received(_, msg))) => {
+                        *msg = Err("malformed message".to_owned());
+                        Some(format!("malformed channel message at input {index}"))
+                    }
+                    _ => None,
+                }
+            }
+            Self::ReorderResponses => {
+                let a = choose_index(inputs, rng, is_response)?;
+                let b = choose_index(inputs, rng, is_response)?;
+                if a == b {
+                    return None;
+                }
+                let action = inputs[a].action.clone();
+                inputs[a].action = std::mem::replace(&mut inputs[b].action, action);
+                Some(format!("swapped responses at inputs {a} and {b}"))
+            }
+            Self::Timeout => {
+                let index = choose_index(inputs, rng, is_response)?;
+                inputs.remove(index);
+                let delay = Duration::from_secs(rng.gen_range(1..=MAX_TIMEOUT_DELAY_SECS));
+                for input in &mut inputs[index..] {
+                    let time = u64::from(input.time).saturating_add(delay.as_nanos() as u64);
+                    input.time = redux::Timestamp::new(time);
+                }
+                Some(format!(
+                    "dropped response at input {index}, following inputs delayed by {delay:?}"
+                ))
+            }
+        }
+    }
+}
+
+fn event(input: &FuzzInput) -> Option<&Event> {
+    match &input.action {
+        Action::EventSource(EventSourceAction::NewEvent { event }) => Some(event),
+        _ => None,
+    }
+}
+
+fn event_mut(input: &mut FuzzInput) -> Option<&mut Event> {
+    match &mut input.action {
+        Action::EventSource(EventSourceAction::NewEvent { event }) => Some(event),
+        _ => None,
+    }
+}
+
+fn is_p2p_message(input: &FuzzInput) -> bool {
+    matches!(
+        event(input),
+        Some(Event::P2p(
+            P2pEvent::MioEvent(MioEvent::IncomingDataDidReceive(_, Ok(_)))
+                | P2pEvent::Channel(P2pChannelEvent::Received(_, Ok(_)))
+        ))
+    )
+}
+
+/// Events which are responses to requests made by the node.
+fn is_response(input: &FuzzInput) -> bool {
+    match event(input) {
+        Some(Event::Ledger(LedgerEvent::Read(..) | LedgerEvent::Write(..))) => true,
+        Some(Event::Snark(_)) => true,
+        Some(Event::P2p(P2pEvent::Channel(P2pChannelEvent::Received(_, Ok(msg))))) => {
+            matches!(msg, ChannelMsg::Rpc(RpcChannelMsg::Response(..)))
+        }
+        _ => false,
+    }
+}
+
+fn choose_index<F>(inputs: &[FuzzInput], rng: &mut StdRng, filter: F) -> Option<usize>
+where
+    F: Fn(&FuzzInput) -> bool,
+{
+    let candidates = inputs
+        .iter()
+        .enumerate()
+        .filter(|(_, input)| filter(input))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    match candidates.len() {
+        0 => None,
+        len => Some(candidates[rng.gen_range(0..len)]),
+    }
+}
+
+fn corrupt_bytes(bytes: &mut Vec<u8>, rng: &mut StdRng) -> String {
+    match rng.gen_range(0..3) {
+        0 if !bytes.is_empty() => {
+            let i = rng.gen_range(0..bytes.len());
+            bytes[i] ^= rng.gen_range(1..=u8::MAX);
+            format!("flipped bits of byte {i}")
+        }
+        1 if !bytes.is_empty() => {
+            let len = rng.gen_range(0..bytes.len());
+            bytes.truncate(len);
+            format!("truncated to {len} bytes")
+        }
+        _ => {
+            let len = rng.gen_range(1..=64);
+            bytes.extend((0..len).map(|_| rng.gen::<u8>()));
+            format!("appended {len} random bytes")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use node::p2p::{ConnectionAddr, PeerId};
+    use node::CheckTimeoutsAction;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    fn input(time_secs: u64, event: Event) -> FuzzInput {
+        FuzzInput {
+            time: redux::Timestamp::new(time_secs * 1_000_000_000),
+            action: Action::EventSource(EventSourceAction::NewEvent { event }),
+        }
+    }
+
+    fn check_timeouts(time_secs: u64) -> FuzzInput {
+        FuzzInput {
+            time: redux::Timestamp::new(time_secs * 1_000_000_000),
+            action: Action::CheckTimeouts(CheckTimeoutsAction {}),
+        }
+    }
+
+    fn data_received(time_secs: u64, data: &[u8]) -> FuzzInput {
+        let addr = ConnectionAddr {
+            sock_addr: "127.0.0.1:8302".parse().unwrap(),
+            incoming: true,
+        };
+        let event = MioEvent::IncomingDataDidReceive(addr, Ok(data.to_vec().into()));
+        input(time_secs, Event::P2p(P2pEvent::MioEvent(event)))
+    }
+
+    fn rpc_response(time_secs: u64, id: u64) -> FuzzInput {
+        let msg = ChannelMsg::Rpc(RpcChannelMsg::Response(id, None));
+        let event = P2pChannelEvent::Received(PeerId::from_bytes([1; 32]), Ok(msg));
+        input(time_secs, Event::P2p(P2pEvent::Channel(event)))
+    }
+
+    fn received_data(input: &FuzzInput) -> Option<&[u8]> {
+        match event(input)? {
+            Event::P2p(P2pEvent::MioEvent(MioEvent::IncomingDataDidReceive(_, Ok(data)))) => {
+                Some(&data.0)
+            }
+            _ => None,
+        }
+    }
+
+    fn response_id(input: &FuzzInput) -> Option<u64> {
+        match event(input)? {
+            Event::P2p(P2pEvent::Channel(P2pChannelEvent::Received(
+                _,
+                Ok(ChannelMsg::Rpc(RpcChannelMsg::Response(id, _))),
+            ))) => Some(*id),
+            _ => None,
+        }
+    }
+
+    /// Applies the mutation with the first seed for which it changes the inputs.
+    fn apply_until_changed(mutation: Mutation, inputs: &mut Vec<FuzzInput>) -> String {
+        (0..100)
+            .find_map(|seed| mutation.apply(inputs, &mut StdRng::seed_from_u64(seed)))
+            .expect("mutation never applied")
+    }
+
+    #[test]
+    fn test_not_applicable_mutations() {
+        let mut inputs = vec![check_timeouts(0), check_timeouts(1)];
+        let mut rng = StdRng::seed_from_u64(0);
+        for mutation in Mutation::ALL {
+            assert!(mutation.apply(&mut inputs, &mut rng).is_none());
+        }
+        assert_eq!(inputs.len(), 2);
+    }
+
+    #[test]
+    fn test_malformed_received_data() {
+        let data = [1, 2, 3, 4, 5, 6, 7, 8];
+        for seed in 0..20 {
+            let mut inputs = vec![check_timeouts(0), data_received(1, &data)];
+            let change = Mutation::MalformedP2pMessage
+                .apply(&mut inputs, &mut StdRng::seed_from_u64(seed))
+                .unwrap();
+            assert!(change.ends_with("at input 1"), "{change}");
+            assert_ne!(received_data(&inputs[1]).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_malformed_channel_message() {
+        let mut inputs = vec![rpc_response(0, 1)];
+        Mutation::MalformedP2pMessage
+            .apply(&mut inputs, &mut StdRng::seed_from_u64(0))
+            .unwrap();
+        assert!(matches!(
+            event(&inputs[0]),
+            Some(Event::P2p(P2pEvent::Channel(P2pChannelEvent::Received(
+                _,
+                Err(_)
+            ))))
+        ));
+    }
+
+    #[test]
+    fn test_reorder_responses() {
+        let mut inputs = vec![rpc_response(0, 1), check_timeouts(1), rpc_response(2, 2)];
+        apply_until_changed(Mutation::ReorderResponses, &mut inputs);
+
+        let ids = inputs.iter().map(response_id).collect::<Vec<_>>();
+        assert_eq!(ids, [Some(2), None, Some(1)]);
+        // only the actions are swapped, inputs are still dispatched in order.
+        let times = inputs.iter().map(|i| u64::from(i.time)).collect::<Vec<_>>();
+        assert!(times.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_reorder_single_response() {
+        let mut inputs = vec![rpc_response(0, 1), check_timeouts(1)];
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            assert!(Mutation::ReorderResponses
+                .apply(&mut inputs, &mut rng)
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_timeout() {
+        for seed in 0..20 {
+            let mut inputs = vec![check_timeouts(0), rpc_response(1, 1), check_timeouts(2)];
+            Mutation::Timeout
+                .apply(&mut inputs, &mut StdRng::seed_from_u64(seed))
+                .unwrap();
+
+            assert_eq!(inputs.len(), 2);
+            assert!(inputs.iter().all(|input| response_id(input).is_none()));
+            assert_eq!(u64::from(inputs[0].time), 0);
+            let delay = u64::from(inputs[1].time) - 2_000_000_000;
+            assert!((1..=MAX_TIMEOUT_DELAY_SECS).contains(&(delay / 1_000_000_000)));
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use clap::Parser;
 
+use std::path::PathBuf;
+
 use openmina_node_testing::cluster::{Cluster, ClusterConfig};
+use openmina_node_testing::fuzz::{ReplayFuzzer, ReplayFuzzerConfig};
 use openmina_node_testing::scenario::Scenario;
 use openmina_node_testing::scenarios::Scenarios;
 use openmina_node_testing::{exit_with_error, server, setup};
@@ -20,6 +23,8 @@ pub enum Command {
 
     ScenariosGenerate(CommandScenariosGenerate),
     ScenariosRun(CommandScenariosRun),
+
+    ReplayFuzz(CommandReplayFuzz),
 }
 
 #[derive(Debug, clap::Args)]
@@ -46,6 +51,29 @@ pub struct CommandScenariosRun {
     pub name: String,
 }
 
+/// Fuzz the node by mutating input actions of a recording and replaying them.
+///
+/// On failure, the minimized reproducing recording is written to
+/// `<output>/recorder`.
+#[derive(Debug, clap::Args)]
+pub struct CommandReplayFuzz {
+    /// Directory of the recording.
+    #[arg(long, short)]
+    pub dir: PathBuf,
+    #[arg(long, short, default_value = "./fuzz-failure")]
+    pub output: PathBuf,
+    #[arg(long, default_value = "0")]
+    pub seed: u64,
+    #[arg(long, default_value = "1000")]
+    pub iterations: usize,
+    /// Maximum number of mutations in an iteration.
+    #[arg(long, default_value = "3")]
+    pub mutations: usize,
+    /// Maximum number of replays used to minimize the failing inputs.
+    #[arg(long, default_value = "200")]
+    pub minimize_runs: usize,
+}
+
 impl Command {
     pub fn run(self) -> Result<(), crate::CommandError> {
         let rt = setup();
@@ -146,6 +174,25 @@ impl Command {
                     }
                 })
             }
+            Self::ReplayFuzz(cmd) => {
+                let fuzzer = ReplayFuzzer::new(ReplayFuzzerConfig {
+                    recording_dir: cmd.dir,
+                    output_dir: cmd.output,
+                    seed: cmd.seed,
+                    iterations: cmd.iterations,
+                    mutations: cmd.mutations,
+                    minimize_runs: cmd.minimize_runs,
+                })?;
+                match fuzzer.run()? {
+                    Some((failure, recording)) => {
+                        anyhow::bail!("{failure}\nreproducing recording: {}", recording.display())
+                    }
+                    None => {
+                        eprintln!("no failures found");
+                        Ok(())
+                    }
+                }
+            }
         }
     }
 }
@@ -45,6 +45,9 @@ pub enum Recorder {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RustNodeBlockProducerTestingConfig {
     pub sec_key: AccountSecretKey,
+    /// Secret keys for `config.extra_pub_keys`.
+    #[serde(default)]
+    pub extra_sec_keys: Vec<AccountSecretKey>,
     pub config: BlockProducerConfig,
 }
 
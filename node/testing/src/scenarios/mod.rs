@@ -29,6 +29,7 @@ use crate::scenario::{Scenario, ScenarioId, ScenarioStep};
 
 use self::multi_node::basic_connectivity_initial_joining::MultiNodeBasicConnectivityInitialJoining;
 use self::multi_node::basic_connectivity_peer_discovery::MultiNodeBasicConnectivityPeerDiscovery;
+use self::multi_node::block_producer_several_keys::MultiNodeBlockProducerSeveralKeys;
 use self::multi_node::pubsub_advanced::MultiNodePubsubPropagateBlock;
 use self::multi_node::sync_4_block_producers::MultiNodeSync4BlockProducers;
 use self::multi_node::vrf_correct_ledgers::MultiNodeVrfGetCorrectLedgers;
@@ -38,6 +39,7 @@ use self::multi_node::vrf_epoch_bounds_evaluation::MultiNodeVrfEpochBoundsEvalua
 use self::p2p::pubsub::P2pReceiveBlock;
 use self::record_replay::block_production::RecordReplayBlockProduction;
 use self::record_replay::bootstrap::RecordReplayBootstrap;
+use self::simulation::hard_fork::SimulationHardFork;
 use self::simulation::small::SimulationSmall;
 use self::simulation::small_forever_real_time::SimulationSmallForeverRealTime;
 use self::solo_node::sync_to_genesis::SoloNodeSyncToGenesis;
@@ -58,6 +60,7 @@ pub enum Scenarios {
     SoloNodeBasicConnectivityInitialJoining(SoloNodeBasicConnectivityInitialJoining),
     SoloNodeBasicConnectivityAcceptIncoming(SoloNodeBasicConnectivityAcceptIncoming),
     MultiNodeSync4BlockProducers(MultiNodeSync4BlockProducers),
+    MultiNodeBlockProducerSeveralKeys(MultiNodeBlockProducerSeveralKeys),
     MultiNodeVrfGetCorrectLedgers(MultiNodeVrfGetCorrectLedgers),
     MultiNodeVrfGetCorrectSlots(MultiNodeVrfGetCorrectSlots),
     MultiNodeVrfEpochBoundsEvaluation(MultiNodeVrfEpochBoundsEvaluation),
@@ -66,6 +69,7 @@ pub enum Scenarios {
     MultiNodeBasicConnectivityPeerDiscovery(MultiNodeBasicConnectivityPeerDiscovery),
     SimulationSmall(SimulationSmall),
     SimulationSmallForeverRealTime(SimulationSmallForeverRealTime),
+    SimulationHardFork(SimulationHardFork),
     P2pReceiveBlock(P2pReceiveBlock),
     P2pSignaling(P2pSignaling),
     MultiNodePubsubPropagateBlock(MultiNodePubsubPropagateBlock),
@@ -91,6 +95,7 @@ impl Scenarios {
             Self::MultiNodeBasicConnectivityPeerDiscovery(_) => cfg!(feature = "p2p-webrtc"),
             Self::SimulationSmall(_) => true,
             Self::SimulationSmallForeverRealTime(_) => true,
+            Self::SimulationHardFork(_) => true,
             Self::MultiNodePubsubPropagateBlock(_) => true, // in progress
             Self::P2pSignaling(_) => cfg!(feature = "p2p-webrtc"),
             _ => false,
@@ -108,6 +113,7 @@ impl Scenarios {
     pub fn parent(self) -> Option<Self> {
         match self {
             Self::MultiNodeSync4BlockProducers(_) => Some(SoloNodeSyncToGenesis.into()),
+            Self::MultiNodeBlockProducerSeveralKeys(_) => Some(SoloNodeSyncToGenesis.into()),
             Self::MultiNodeVrfGetCorrectLedgers(_) => Some(SoloNodeSyncToGenesisCustom.into()),
             Self::MultiNodeVrfGetCorrectSlots(_) => Some(SoloNodeSyncToGenesisCustom.into()),
             Self::MultiNodeVrfEpochBoundsEvaluation(_) => Some(SoloNodeSyncToGenesisCustom.into()),
@@ -136,6 +142,7 @@ impl Scenarios {
                 SoloNodeBasicConnectivityAcceptIncoming::DOCS
             }
             Self::MultiNodeSync4BlockProducers(_) => MultiNodeSync4BlockProducers::DOCS,
+            Self::MultiNodeBlockProducerSeveralKeys(_) => MultiNodeBlockProducerSeveralKeys::DOCS,
             Self::MultiNodeVrfGetCorrectLedgers(_) => MultiNodeVrfGetCorrectLedgers::DOCS,
             Self::MultiNodeVrfGetCorrectSlots(_) => MultiNodeVrfGetCorrectSlots::DOCS,
             Self::MultiNodeVrfEpochBoundsEvaluation(_) => MultiNodeVrfEpochBoundsEvaluation::DOCS,
@@ -150,6 +157,7 @@ impl Scenarios {
             }
             Self::SimulationSmall(_) => SimulationSmall::DOCS,
             Self::SimulationSmallForeverRealTime(_) => SimulationSmallForeverRealTime::DOCS,
+            Self::SimulationHardFork(_) => SimulationHardFork::DOCS,
             Self::P2pReceiveBlock(_) => P2pReceiveBlock::DOCS,
             Self::P2pSignaling(_) => P2pSignaling::DOCS,
             Self::MultiNodePubsubPropagateBlock(_) => MultiNodePubsubPropagateBlock::DOCS,
@@ -179,6 +187,7 @@ impl Scenarios {
             Self::SoloNodeBasicConnectivityInitialJoining(v) => v.run(runner).await,
             Self::SoloNodeBasicConnectivityAcceptIncoming(v) => v.run(runner).await,
             Self::MultiNodeSync4BlockProducers(v) => v.run(runner).await,
+            Self::MultiNodeBlockProducerSeveralKeys(v) => v.run(runner).await,
             Self::MultiNodeVrfGetCorrectLedgers(v) => v.run(runner).await,
             Self::MultiNodeVrfGetCorrectSlots(v) => v.run(runner).await,
             Self::MultiNodeVrfEpochBoundsEvaluation(v) => v.run(runner).await,
@@ -187,6 +196,7 @@ impl Scenarios {
             Self::MultiNodeBasicConnectivityPeerDiscovery(v) => v.run(runner).await,
             Self::SimulationSmall(v) => v.run(runner).await,
             Self::SimulationSmallForeverRealTime(v) => v.run(runner).await,
+            Self::SimulationHardFork(v) => v.run(runner).await,
             Self::P2pReceiveBlock(v) => v.run(runner).await,
             Self::P2pSignaling(v) => v.run(runner).await,
             Self::MultiNodePubsubPropagateBlock(v) => v.run(runner).await,
@@ -0,0 +1,98 @@
+use std::{collections::BTreeSet, time::Duration};
+
+use mina_p2p_messages::v2::NonZeroCurvePoint;
+use node::BlockProducerConfig;
+
+use crate::{
+    node::{RustNodeBlockProducerTestingConfig, RustNodeTestingConfig},
+    scenario::{ListenerNode, ScenarioStep},
+    scenarios::ClusterRunner,
+};
+
+const GLOBAL_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+const STEP_DURATION: Duration = Duration::from_secs(5);
+
+/// Run a single block producer node with several producer keys.
+///
+/// 1. Create a block producer node running the keys of 2 block producers.
+/// 2. Connect it to the initial node and wait for it to sync.
+/// 3. Produce blocks until the best chain has blocks created by both keys.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct MultiNodeBlockProducerSeveralKeys;
+
+impl MultiNodeBlockProducerSeveralKeys {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let (initial_node, node_config) = runner
+            .nodes_iter()
+            .next()
+            .map(|(id, node)| (id, node.config().clone()))
+            .unwrap();
+
+        let mut block_producers = runner.block_producer_sec_keys(initial_node).into_iter();
+        let (sec_key, _) = block_producers.next().unwrap();
+        let (extra_sec_key, _) = block_producers.next().unwrap();
+
+        let expected_producers: BTreeSet<NonZeroCurvePoint> =
+            [sec_key.public_key(), extra_sec_key.public_key()]
+                .into_iter()
+                .map(Into::into)
+                .collect();
+
+        let producer_node = runner.add_rust_node(RustNodeTestingConfig {
+            block_producer: Some(RustNodeBlockProducerTestingConfig {
+                config: BlockProducerConfig {
+                    pub_key: sec_key.public_key().into(),
+                    extra_pub_keys: vec![extra_sec_key.public_key().into()],
+                    custom_coinbase_receiver: None,
+                    proposed_protocol_version: None,
+                    transaction_selection: Default::default(),
+                },
+                sec_key,
+                extra_sec_keys: vec![extra_sec_key],
+            }),
+            ..node_config
+        });
+
+        // TODO(binier): proper way to wait for all nodes to be ready.
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        runner
+            .exec_step(ScenarioStep::ConnectNodes {
+                dialer: producer_node,
+                listener: ListenerNode::Rust(initial_node),
+            })
+            .await
+            .unwrap();
+
+        runner
+            .run_until_nodes_synced(Duration::from_secs(3 * 60), &[producer_node])
+            .await
+            .unwrap();
+        eprintln!("block producer node with several keys synced up.");
+
+        runner
+            .produce_blocks_until(
+                producer_node,
+                "SEVERAL KEYS",
+                GLOBAL_TIMEOUT,
+                STEP_DURATION,
+                false,
+                |state, _, _| {
+                    let producers = state
+                        .transition_frontier
+                        .best_chain
+                        .iter()
+                        .map(|block| block.producer().clone())
+                        .filter(|producer| expected_producers.contains(producer))
+                        .collect::<BTreeSet<_>>();
+                    eprintln!(
+                        "producer keys in the best chain: {}/{}",
+                        producers.len(),
+                        expected_producers.len()
+                    );
+                    producers == expected_producers
+                },
+            )
+            .await;
+    }
+}
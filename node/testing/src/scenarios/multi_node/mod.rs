@@ -1,3 +1,4 @@
+pub mod block_producer_several_keys;
 pub mod sync_4_block_producers;
 
 pub mod basic_connectivity_initial_joining;
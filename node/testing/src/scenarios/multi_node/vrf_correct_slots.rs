@@ -49,10 +49,13 @@ impl MultiNodeVrfGetCorrectSlots {
             block_producer: Some(RustNodeBlockProducerTestingConfig {
                 config: BlockProducerConfig {
                     pub_key: sec_key.public_key().into(),
+                    extra_pub_keys: Vec::new(),
                     custom_coinbase_receiver: None,
                     proposed_protocol_version: None,
+                    transaction_selection: Default::default(),
                 },
                 sec_key,
+                extra_sec_keys: Vec::new(),
             }),
             snark_worker: None,
             timeouts: P2pTimeouts::default(),
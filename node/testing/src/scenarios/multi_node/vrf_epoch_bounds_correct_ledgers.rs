@@ -65,10 +65,13 @@ impl MultiNodeVrfEpochBoundsCorrectLedger {
             block_producer: Some(RustNodeBlockProducerTestingConfig {
                 config: BlockProducerConfig {
                     pub_key: sec_key.public_key().into(),
+                    extra_pub_keys: Vec::new(),
                     custom_coinbase_receiver: None,
                     proposed_protocol_version: None,
+                    transaction_selection: Default::default(),
                 },
                 sec_key: sec_key.clone(),
+                extra_sec_keys: Vec::new(),
             }),
             ..rust_config.clone()
         });
@@ -81,6 +84,7 @@ impl MultiNodeVrfEpochBoundsCorrectLedger {
                 )),
                 strategy: SnarkerStrategy::Sequential,
                 auto_commit: true,
+                local_workers: 1,
             }),
             ..rust_config
         });
@@ -139,8 +143,9 @@ impl MultiNodeVrfEpochBoundsCorrectLedger {
         let initial_balance = if let Some(pending_evaluation) = vrf_evaluator.current_evaluation() {
             let (_, balance) = pending_evaluation
                 .epoch_data
-                .delegator_table
-                .get(&AccountIndex(1))
+                .delegator_tables
+                .values()
+                .find_map(|table| table.get(&AccountIndex(1)))
                 .expect("Account not found");
             eprintln!("Initial balance: {balance}");
             *balance
@@ -235,8 +240,9 @@ impl MultiNodeVrfEpochBoundsCorrectLedger {
         let new_balance = if let Some(pending_evaluation) = vrf_evaluator.current_evaluation() {
             let (_, balance) = pending_evaluation
                 .epoch_data
-                .delegator_table
-                .get(&AccountIndex(1))
+                .delegator_tables
+                .values()
+                .find_map(|table| table.get(&AccountIndex(1)))
                 .expect("Account not found");
             eprintln!("New balance: {balance}");
             *balance
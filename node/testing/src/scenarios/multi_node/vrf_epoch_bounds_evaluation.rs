@@ -51,10 +51,13 @@ impl MultiNodeVrfEpochBoundsEvaluation {
             block_producer: Some(RustNodeBlockProducerTestingConfig {
                 config: BlockProducerConfig {
                     pub_key: sec_key.public_key().into(),
+                    extra_pub_keys: Vec::new(),
                     custom_coinbase_receiver: None,
                     proposed_protocol_version: None,
+                    transaction_selection: Default::default(),
                 },
                 sec_key: sec_key.clone(),
+                extra_sec_keys: Vec::new(),
             }),
             ..rust_config.clone()
         });
@@ -67,6 +70,7 @@ impl MultiNodeVrfEpochBoundsEvaluation {
                 )),
                 strategy: SnarkerStrategy::Sequential,
                 auto_commit: true,
+                local_workers: 1,
             }),
             ..rust_config
         });
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+use mina_p2p_messages::v2::{BlockTimeTimeStableV1, LedgerHash, PROTOCOL_CONSTANTS};
+use node::daemon_json::DaemonJson;
+use node::ledger::read::HardForkLedgers;
+use node::transition_frontier::genesis::{GenesisConfig, NonStakers};
+use time::OffsetDateTime;
+
+use crate::{
+    cluster::{Cluster, ClusterNodeId},
+    node::Node,
+    scenarios::{ClusterRunner, RunCfgAdvanceTime},
+    simulator::{Simulator, SimulatorConfig, SimulatorRunUntil},
+};
+
+/// Hard fork simulation.
+///
+/// Runs the network until blockchain length 10, then forks it at the
+/// best tip of the first node and runs the network restarted from the
+/// fork until it produces 10 more blocks.
+///
+/// - **whale** block producers: **2**.
+/// - **fish** block producers: **2**.
+/// - seed nodes: **1**.
+/// - normal nodes: **1**.
+#[derive(documented::Documented, Default, Clone, Copy)]
+pub struct SimulationHardFork;
+
+impl SimulationHardFork {
+    pub async fn run(self, mut runner: ClusterRunner<'_>) {
+        let initial_time = redux::Timestamp::global_now();
+        let mut constants = PROTOCOL_CONSTANTS.clone();
+        constants.genesis_state_timestamp =
+            BlockTimeTimeStableV1((u64::from(initial_time) / 1_000_000).into());
+        let genesis_cfg = GenesisConfig::Counts {
+            whales: 2,
+            fish: 2,
+            non_stakers: NonStakers::None,
+            constants,
+        };
+        let mut simulator = Simulator::new(initial_time, Self::simulator_config(genesis_cfg));
+        simulator.run(&mut runner).await;
+
+        let node = runner
+            .node(ClusterNodeId::new_unchecked(0))
+            .expect("node to fork from not found");
+        let fork_time = node.state().time();
+        let mut config = hard_fork_config(node);
+        let fork = config
+            .proof
+            .as_ref()
+            .and_then(|proof| proof.fork.clone())
+            .expect("hard fork config without fork constants");
+        eprintln!(
+            "hard forking at block {} (length: {}, slot: {})",
+            node.state().transition_frontier.best_tip().unwrap().hash(),
+            fork.blockchain_length,
+            fork.global_slot_since_genesis,
+        );
+        let genesis_timestamp =
+            OffsetDateTime::from_unix_timestamp_nanos(u64::from(fork_time) as i128).unwrap();
+        config
+            .genesis
+            .as_mut()
+            .expect("hard fork config without genesis constants")
+            .set_genesis_state_timestamp(genesis_timestamp);
+
+        // Nodes of the pre-fork network are left behind, the forked one is
+        // started in a cluster of its own. Its genesis block continues the
+        // chain at the fork set in the config.
+        let mut cluster = Cluster::new(runner.cluster_config().clone());
+        let mut runner = ClusterRunner::new(&mut cluster, |_| {});
+        let genesis_cfg = GenesisConfig::DaemonJson(Box::new(config));
+        let mut simulator = Simulator::new(fork_time, Self::simulator_config(genesis_cfg));
+        simulator.run(&mut runner).await;
+    }
+
+    fn simulator_config(genesis: GenesisConfig) -> SimulatorConfig {
+        SimulatorConfig {
+            genesis: genesis.into(),
+            seed_nodes: 1,
+            normal_nodes: 1,
+            snark_workers: 1,
+            block_producers: 4,
+            advance_time: RunCfgAdvanceTime::Rand(10..=200),
+            run_until: SimulatorRunUntil::BlockchainLength(10),
+            run_until_timeout: Duration::from_secs(30 * 60),
+            recorder: Default::default(),
+        }
+    }
+}
+
+/// Config of the network forked at the best tip of the `node`.
+fn hard_fork_config(node: &Node) -> DaemonJson {
+    let block = node
+        .state()
+        .transition_frontier
+        .best_tip()
+        .expect("node has no best tip");
+    let mask = |hash: &LedgerHash| {
+        node.service()
+            .ledger(hash)
+            .unwrap_or_else(|| panic!("ledger {hash} not found"))
+    };
+    let (staged_ledger, staking_epoch_ledger, next_epoch_ledger) = (
+        mask(block.merkle_root_hash()),
+        mask(block.staking_epoch_ledger_hash()),
+        mask(block.next_epoch_ledger_hash()),
+    );
+    let ledgers = HardForkLedgers {
+        staged_ledger: &staged_ledger,
+        staking_epoch_ledger: &staking_epoch_ledger,
+        next_epoch_ledger: &next_epoch_ledger,
+    };
+
+    let mut config = Vec::new();
+    DaemonJson::hard_fork(&mut config, block, ledgers).expect("failed to write hard fork config");
+    serde_json::from_slice(&config).expect("invalid hard fork config")
+}
@@ -1,2 +1,3 @@
+pub mod hard_fork;
 pub mod small;
 pub mod small_forever_real_time;
@@ -21,7 +21,7 @@ use node::block_producer::vrf_evaluator::VrfEvaluatorInput;
 use node::block_producer::BlockProducerEvent;
 use node::core::channels::mpsc;
 use node::core::snark::{Snark, SnarkJobId};
-use node::external_snark_worker::ExternalSnarkWorkerEvent;
+use node::external_snark_worker::{ExternalSnarkWorkerEvent, ExternalSnarkWorkerId};
 use node::p2p::service_impl::webrtc_with_libp2p::P2pServiceWebrtcWithLibp2p;
 use node::p2p::P2pCryptoService;
 use node::recorder::Recorder;
@@ -37,6 +37,7 @@ use node::snark::work_verify::{SnarkWorkVerifyId, SnarkWorkVerifyService};
 use node::snark::{BlockVerifier, SnarkEvent, TransactionVerifier, VerifierSRS};
 use node::snark_pool::SnarkPoolService;
 use node::stats::Stats;
+use node::transaction_pool::TransactionPoolService;
 use node::transition_frontier::genesis::GenesisConfig;
 use node::{
     event_source::Event,
@@ -464,6 +465,12 @@ impl SnarkPoolService for NodeTestingService {
     }
 }
 
+impl TransactionPoolService for NodeTestingService {
+    fn locally_generated_persist(&mut self, commands: Vec<v2::MinaBaseUserCommandStableV2>) {
+        self.real.locally_generated_persist(commands)
+    }
+}
+
 impl BlockProducerVrfEvaluatorService for NodeTestingService {
     fn evaluate(&mut self, data: VrfEvaluatorInput) {
         BlockProducerVrfEvaluatorService::evaluate(&mut self.real, data)
@@ -485,7 +492,16 @@ impl BlockProducerService for NodeTestingService {
             let dummy_proof = (*ledger::dummy::dummy_blockchain_proof()).clone();
             BlockProducerEvent::BlockProve(block_hash, Ok(dummy_proof.into())).into()
         }
-        let keypair = self.real.block_producer().unwrap().keypair();
+        let keypair = match self.real.block_producer().unwrap().keypair(&input) {
+            Ok(keypair) => keypair,
+            Err(error) => {
+                let _ = self
+                    .real
+                    .event_sender()
+                    .send(BlockProducerEvent::BlockProve(block_hash, Err(error)).into());
+                return;
+            }
+        };
 
         match self.proof_kind() {
             ProofKind::Dummy => {
@@ -506,14 +522,7 @@ impl BlockProducerService for NodeTestingService {
                 }
             }
             ProofKind::Full => {
-                // TODO(binier): handle if block is genesis based on fork constants.
-                let is_genesis = input
-                    .next_state
-                    .body
-                    .consensus_state
-                    .blockchain_length
-                    .as_u32()
-                    == 1;
+                let is_genesis = openmina_node_native::block_producer::is_genesis(&input);
                 let res = GENESIS_PROOF.with_borrow_mut(|cached_genesis| {
                     if let Some((_, proof)) = cached_genesis
                         .as_ref()
@@ -546,6 +555,7 @@ impl BlockProducerService for NodeTestingService {
 impl ExternalSnarkWorkerService for NodeTestingService {
     fn start(
         &mut self,
+        worker_id: ExternalSnarkWorkerId,
         public_key: NonZeroCurvePoint,
         fee: CurrencyFeeStableV1,
     ) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
@@ -557,16 +567,17 @@ impl ExternalSnarkWorkerService for NodeTestingService {
             })?,
         );
         self.set_snarker_sok_digest((&sok_message.digest()).into());
-        let _ = self
-            .real
-            .event_sender()
-            .send(ExternalSnarkWorkerEvent::Started.into());
+        let _ = self.real.event_sender().send(Event::ExternalSnarkWorker(
+            worker_id,
+            ExternalSnarkWorkerEvent::Started,
+        ));
         Ok(())
         // self.real.start(path, public_key, fee)
     }
 
     fn submit(
         &mut self,
+        worker_id: ExternalSnarkWorkerId,
         spec: SnarkWorkSpec,
     ) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
         let sok_digest = self.snarker_sok_digest.clone().unwrap();
@@ -596,28 +607,34 @@ impl ExternalSnarkWorkerService for NodeTestingService {
                 make_dummy_proof(v2),
             )),
         };
-        let _ = self
-            .real
-            .event_sender()
-            .send(ExternalSnarkWorkerEvent::WorkResult(Arc::new(res)).into());
+        let _ = self.real.event_sender().send(Event::ExternalSnarkWorker(
+            worker_id,
+            ExternalSnarkWorkerEvent::WorkResult(Arc::new(res)),
+        ));
         Ok(())
         // self.real.submit(spec)
     }
 
-    fn cancel(&mut self) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
-        let _ = self
-            .real
-            .event_sender()
-            .send(ExternalSnarkWorkerEvent::WorkCancelled.into());
+    fn cancel(
+        &mut self,
+        worker_id: ExternalSnarkWorkerId,
+    ) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
+        let _ = self.real.event_sender().send(Event::ExternalSnarkWorker(
+            worker_id,
+            ExternalSnarkWorkerEvent::WorkCancelled,
+        ));
         Ok(())
         // self.real.cancel()
     }
 
-    fn kill(&mut self) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
-        let _ = self
-            .real
-            .event_sender()
-            .send(ExternalSnarkWorkerEvent::Killed.into());
+    fn kill(
+        &mut self,
+        worker_id: ExternalSnarkWorkerId,
+    ) -> Result<(), node::external_snark_worker::ExternalSnarkWorkerError> {
+        let _ = self.real.event_sender().send(Event::ExternalSnarkWorker(
+            worker_id,
+            ExternalSnarkWorkerEvent::Killed,
+        ));
         Ok(())
         // self.real.kill()
     }
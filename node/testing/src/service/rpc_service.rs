@@ -19,6 +19,7 @@ impl RpcService for super::NodeTestingService {
         respond_block_producer_stats_get,
         node::rpc::RpcBlockProducerStatsGetResponse
     );
+    to_real!(respond_metrics_get, node::rpc::RpcMetricsGetResponse);
 
     to_real!(
         respond_action_stats_get,
@@ -60,6 +61,18 @@ impl RpcService for super::NodeTestingService {
         respond_snarker_workers,
         node::rpc::RpcSnarkerWorkersResponse,
     );
+    to_real!(
+        respond_snarker_worker_get_work,
+        node::rpc::RpcSnarkerWorkerGetWorkResponse,
+    );
+    to_real!(
+        respond_snarker_worker_submit_work,
+        node::rpc::RpcSnarkerWorkerSubmitWorkResponse,
+    );
+    to_real!(
+        respond_snarker_worker_failed,
+        node::rpc::RpcSnarkerWorkerFailedResponse,
+    );
     to_real!(
         respond_snarker_config_get,
         node::rpc::RpcSnarkerConfigGetResponse,
@@ -98,6 +111,10 @@ impl RpcService for super::NodeTestingService {
         node::rpc::RpcTransitionFrontierUserCommandsResponse,
     );
     to_real!(respond_best_chain, node::rpc::RpcBestChainResponse,);
+    to_real!(
+        respond_best_tip_subscribe,
+        node::rpc::RpcBestTipSubscribeResponse,
+    );
     to_real!(
         respond_consensus_constants,
         node::rpc::RpcConsensusConstantsGetResponse,
@@ -106,4 +123,45 @@ impl RpcService for super::NodeTestingService {
         respond_transaction_status,
         node::rpc::RpcTransactionStatusGetResponse,
     );
+    to_real!(respond_block_get, node::rpc::RpcGetBlockResponse,);
+    to_real!(respond_genesis_block, node::rpc::RpcGenesisBlockResponse,);
+    to_real!(
+        respond_pooled_user_commands,
+        node::rpc::RpcPooledUserCommandsResponse,
+    );
+    to_real!(
+        respond_pooled_zkapp_commands,
+        node::rpc::RpcPooledZkappCommandsResponse,
+    );
+    to_real!(
+        respond_hard_fork_config_get,
+        node::rpc::RpcHardForkConfigGetResponse,
+    );
+    to_real!(
+        respond_watched_accounts_add,
+        node::rpc::RpcWatchedAccountsAddResponse,
+    );
+    to_real!(
+        respond_watched_accounts_remove,
+        node::rpc::RpcWatchedAccountsRemoveResponse,
+    );
+    to_real!(
+        respond_watched_accounts_get,
+        node::rpc::RpcWatchedAccountsGetResponse,
+    );
+    to_real!(
+        respond_watched_account_subscribe,
+        node::rpc::RpcWatchedAccountSubscribeResponse,
+    );
+
+    fn respond_subscription_end(&mut self, rpc_id: RpcId) {
+        self.real.respond_subscription_end(rpc_id)
+    }
+    to_real!(
+        respond_transaction_simulate,
+        node::rpc::RpcTransactionSimulateResponse,
+    );
+    to_real!(respond_fee_estimate, node::rpc::RpcFeeEstimateResponse);
+    to_real!(respond_zkapp_events_get, node::rpc::RpcZkappEventsResponse);
+    to_real!(respond_archive_get, node::rpc::RpcArchiveGetResponse);
 }
@@ -153,6 +153,7 @@ impl Simulator {
                     )),
                     strategy: SnarkerStrategy::Sequential,
                     auto_commit: true,
+                    local_workers: 1,
                 }),
                 ..node_config.clone()
             };
@@ -190,10 +191,13 @@ impl Simulator {
                 block_producer: Some(RustNodeBlockProducerTestingConfig {
                     config: BlockProducerConfig {
                         pub_key: sec_key.public_key().into(),
+                        extra_pub_keys: Vec::new(),
                         custom_coinbase_receiver: None,
                         proposed_protocol_version: None,
+                        transaction_selection: Default::default(),
                     },
                     sec_key,
+                    extra_sec_keys: Vec::new(),
                 }),
                 ..node_config.clone()
             };
@@ -262,10 +266,12 @@ impl Simulator {
                             consensus_state.epoch_count.as_u32() >= *epoch
                         }
                         SimulatorRunUntil::BlockchainLength(height) => {
-                            let start_height = node::core::constants::constraint_constants()
-                                .fork
-                                .as_ref()
-                                .map_or(0, |c| c.blockchain_length);
+                            // Chain restarted from a hard fork doesn't start
+                            // from the height 1.
+                            let start_height = node
+                                .state()
+                                .genesis_block()
+                                .map_or(0, |genesis| genesis.height() - 1);
                             best_tip.height() >= start_height + *height
                         }
                     };
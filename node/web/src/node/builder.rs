@@ -120,11 +120,13 @@ impl NodeBuilder {
     pub fn block_producer(&mut self, provers: BlockProver, key: AccountSecretKey) -> &mut Self {
         let config = BlockProducerConfig {
             pub_key: key.public_key().into(),
+            extra_pub_keys: Vec::new(),
             custom_coinbase_receiver: None,
             proposed_protocol_version: None,
+            transaction_selection: Default::default(),
         };
         self.block_producer = Some(config);
-        self.service.block_producer_init(provers, key);
+        self.service.block_producer_init(provers, vec![key]);
         self
     }
 
@@ -165,6 +167,7 @@ impl NodeBuilder {
             )),
             strategy,
             auto_commit: true,
+            local_workers: 1,
         };
         self.snarker = Some(config);
         self
@@ -230,6 +233,7 @@ impl NodeBuilder {
                 listen_port: None,
                 identity_pub_key: p2p_sec_key.public_key(),
                 initial_peers,
+                initial_banned_peers: Default::default(),
                 ask_initial_peers_interval: Duration::from_secs(3600),
                 enabled_channels: ChannelId::iter_all().collect(),
                 peer_discovery: !self.p2p_no_discovery,
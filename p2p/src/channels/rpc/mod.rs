@@ -11,12 +11,14 @@ use std::{sync::Arc, time::Duration};
 use binprot_derive::{BinProtRead, BinProtWrite};
 use mina_p2p_messages::{
     list::List,
+    rpc::NodeStatusV2,
     rpc_kernel::QueryID,
     v2::{
-        LedgerHash, MerkleAddressBinableArgStableV1, MinaBasePendingCoinbaseStableV2,
-        MinaBaseStateBodyHashStableV1, MinaLedgerSyncLedgerAnswerStableV2,
-        MinaLedgerSyncLedgerQueryStableV1, MinaStateProtocolStateValueStableV2, StateHash,
-        TransactionSnarkScanStateStableV2,
+        ConsensusProofOfStakeDataConsensusStateValueStableV2, LedgerHash,
+        MerkleAddressBinableArgStableV1, MinaBasePendingCoinbaseStableV2,
+        MinaBaseSparseLedgerBaseStableV2, MinaBaseStateBodyHashStableV1,
+        MinaLedgerSyncLedgerAnswerStableV2, MinaLedgerSyncLedgerQueryStableV1,
+        MinaStateProtocolStateValueStableV2, StateHash, TransactionSnarkScanStateStableV2,
     },
 };
 use openmina_core::{
@@ -52,6 +54,11 @@ pub enum P2pRpcKind {
     Block,
     Snark,
     InitialPeers,
+    Ancestry,
+    TransitionChainProof,
+    NodeStatus,
+    EpochLedger,
+    BanNotify,
 }
 
 impl P2pRpcKind {
@@ -65,6 +72,11 @@ impl P2pRpcKind {
             Self::Block => config.block,
             Self::Snark => config.snark,
             Self::InitialPeers => config.initial_peers,
+            Self::Ancestry => config.ancestry,
+            Self::TransitionChainProof => config.transition_chain_proof,
+            Self::NodeStatus => config.node_status,
+            Self::EpochLedger => config.epoch_ledger,
+            Self::BanNotify => config.ban_notify,
         }
     }
 
@@ -76,6 +88,11 @@ impl P2pRpcKind {
             Self::Block => true,
             Self::Snark => false,
             Self::InitialPeers => true,
+            Self::Ancestry => true,
+            Self::TransitionChainProof => true,
+            Self::NodeStatus => true,
+            Self::EpochLedger => true,
+            Self::BanNotify => true,
         }
     }
 }
@@ -88,6 +105,16 @@ pub enum P2pRpcRequest {
     Block(StateHash),
     Snark(SnarkJobId),
     InitialPeers,
+    /// Peer's best tip consensus state and its hash.
+    Ancestry(
+        Box<ConsensusProofOfStakeDataConsensusStateValueStableV2>,
+        StateHash,
+    ),
+    TransitionChainProof(StateHash),
+    NodeStatus,
+    EpochLedger(LedgerHash),
+    /// Lets the peer know that we banned it, until the time (in nanoseconds).
+    BanNotify(u64),
 }
 
 impl P2pRpcRequest {
@@ -101,6 +128,11 @@ impl P2pRpcRequest {
             Self::Block(_) => P2pRpcKind::Block,
             Self::Snark(_) => P2pRpcKind::Snark,
             Self::InitialPeers => P2pRpcKind::InitialPeers,
+            Self::Ancestry(..) => P2pRpcKind::Ancestry,
+            Self::TransitionChainProof(_) => P2pRpcKind::TransitionChainProof,
+            Self::NodeStatus => P2pRpcKind::NodeStatus,
+            Self::EpochLedger(_) => P2pRpcKind::EpochLedger,
+            Self::BanNotify(_) => P2pRpcKind::BanNotify,
         }
     }
 }
@@ -147,13 +179,17 @@ impl std::fmt::Display for P2pRpcRequest {
                 write!(f, "ledger: {ledger_hash}")
             }
             Self::StagedLedgerAuxAndPendingCoinbasesAtBlock(block_hash)
-            | Self::Block(block_hash) => {
+            | Self::Block(block_hash)
+            | Self::Ancestry(_, block_hash)
+            | Self::TransitionChainProof(block_hash) => {
                 write!(f, ", {block_hash}")
             }
             Self::Snark(job_id) => {
                 write!(f, ", {job_id}")
             }
-            Self::InitialPeers => Ok(()),
+            Self::InitialPeers | Self::NodeStatus => Ok(()),
+            Self::EpochLedger(ledger_hash) => write!(f, ", ledger: {ledger_hash}"),
+            Self::BanNotify(banned_until) => write!(f, ", until: {banned_until}"),
         }
     }
 }
@@ -164,6 +200,14 @@ pub struct BestTipWithProof {
     pub proof: (List<MinaBaseStateBodyHashStableV1>, ArcBlock),
 }
 
+/// Answer to the `get_ancestry` rpc. Same as [`BestTipWithProof`], but
+/// proves the root block from our best tip instead of the other way around.
+#[derive(BinProtWrite, BinProtRead, Serialize, Deserialize, Debug, Clone)]
+pub struct AncestryWithProof {
+    pub root: ArcBlock,
+    pub proof: (List<MinaBaseStateBodyHashStableV1>, ArcBlock),
+}
+
 /// Pieces required to reconstruct staged ledger from snarked ledger.
 #[derive(BinProtWrite, BinProtRead, Serialize, Deserialize, Debug, Clone)]
 pub struct StagedLedgerAuxAndPendingCoinbases {
@@ -181,6 +225,13 @@ pub enum P2pRpcResponse {
     Block(ArcBlock),
     Snark(Snark),
     InitialPeers(List<P2pConnectionOutgoingInitOpts>),
+    Ancestry(AncestryWithProof),
+    /// Hash of the block `k` blocks below the requested one (or of the
+    /// root) and body hashes of the blocks up to the requested one.
+    TransitionChainProof(StateHash, List<MinaBaseStateBodyHashStableV1>),
+    NodeStatus(Box<NodeStatusV2>),
+    EpochLedger(Arc<MinaBaseSparseLedgerBaseStableV2>),
+    BanNotify,
 }
 
 impl P2pRpcResponse {
@@ -194,6 +245,11 @@ impl P2pRpcResponse {
             Self::Block(_) => P2pRpcKind::Block,
             Self::Snark(_) => P2pRpcKind::Snark,
             Self::InitialPeers(_) => P2pRpcKind::InitialPeers,
+            Self::Ancestry(_) => P2pRpcKind::Ancestry,
+            Self::TransitionChainProof(..) => P2pRpcKind::TransitionChainProof,
+            Self::NodeStatus(_) => P2pRpcKind::NodeStatus,
+            Self::EpochLedger(_) => P2pRpcKind::EpochLedger,
+            Self::BanNotify => P2pRpcKind::BanNotify,
         }
     }
 }
@@ -293,6 +349,68 @@ mod libp2p {
                     .collect();
                 let r = RpcResult(Ok(NeedsLength(r)));
 
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
+                Some((ResponseHeader { id: id as _ }, v.into()))
+            }
+            P2pRpcResponse::Ancestry(r) => {
+                type Method = rpc::GetAncestryV2;
+                type Payload = ResponsePayload<<Method as RpcMethod>::Response>;
+
+                let AncestryWithProof {
+                    root,
+                    proof: (middle, best_tip),
+                } = r;
+
+                let r = RpcResult(Ok(NeedsLength(Some(rpc::ProofCarryingDataWithHashV1 {
+                    data: root.as_ref().clone(),
+                    proof: (
+                        middle.into_iter().map(|hash| hash.0).collect(),
+                        best_tip.as_ref().clone(),
+                    ),
+                }))));
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
+                Some((ResponseHeader { id: id as _ }, v.into()))
+            }
+            P2pRpcResponse::TransitionChainProof(hash, body_hashes) => {
+                type Method = rpc::GetTransitionChainProofV1ForV2;
+                type Payload = ResponsePayload<<Method as RpcMethod>::Response>;
+
+                let body_hashes = body_hashes.into_iter().map(|hash| hash.0).collect();
+                let r = RpcResult(Ok(NeedsLength(Some((hash.0.clone(), body_hashes)))));
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
+                Some((ResponseHeader { id: id as _ }, v.into()))
+            }
+            P2pRpcResponse::NodeStatus(status) => {
+                type Method = rpc::GetNodeStatusV2;
+                type Payload = ResponsePayload<<Method as RpcMethod>::Response>;
+
+                let r = RpcResult(Ok(NeedsLength(RpcResult(Ok(*status)))));
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
+                Some((ResponseHeader { id: id as _ }, v.into()))
+            }
+            P2pRpcResponse::EpochLedger(ledger) => {
+                type Method = rpc::GetEpochLedgerV2;
+                type Payload = ResponsePayload<<Method as RpcMethod>::Response>;
+
+                let r = RpcResult(Ok(NeedsLength(RpcResult(Ok(ledger.as_ref().clone())))));
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
+                Some((ResponseHeader { id: id as _ }, v.into()))
+            }
+            P2pRpcResponse::BanNotify => {
+                type Method = rpc::BanNotifyV1;
+                type Payload = ResponsePayload<<Method as RpcMethod>::Response>;
+
+                let r = RpcResult(Ok(NeedsLength(())));
+
                 let mut v = vec![];
                 <Payload as BinProtWrite>::binprot_write(&r, &mut v).unwrap_or_default();
                 Some((ResponseHeader { id: id as _ }, v.into()))
@@ -300,6 +418,45 @@ mod libp2p {
         }
     }
 
+    /// Response to send to the libp2p peer when we don't have the requested
+    /// data. OCaml node expects an empty answer instead of no answer at all.
+    pub fn internal_empty_response_into_libp2p(
+        kind: P2pRpcKind,
+        id: P2pRpcId,
+    ) -> Option<(ResponseHeader, Data)> {
+        use binprot::BinProtWrite;
+
+        fn encode<M: RpcMethod>(response: M::Response, id: P2pRpcId) -> (ResponseHeader, Data) {
+            let r = RpcResult(Ok(NeedsLength(response)));
+
+            let mut v = vec![];
+            <ResponsePayload<M::Response> as BinProtWrite>::binprot_write(&r, &mut v)
+                .unwrap_or_default();
+            (ResponseHeader { id: id as _ }, v.into())
+        }
+
+        match kind {
+            P2pRpcKind::BestTipWithProof => Some(encode::<rpc::GetBestTipV2>(None, id)),
+            P2pRpcKind::StagedLedgerAuxAndPendingCoinbasesAtBlock => {
+                Some(encode::<rpc::GetStagedLedgerAuxAndPendingCoinbasesAtHashV2>(None, id))
+            }
+            P2pRpcKind::Block => Some(encode::<rpc::GetTransitionChainV2>(None, id)),
+            P2pRpcKind::Ancestry => Some(encode::<rpc::GetAncestryV2>(None, id)),
+            P2pRpcKind::TransitionChainProof => {
+                Some(encode::<rpc::GetTransitionChainProofV1ForV2>(None, id))
+            }
+            P2pRpcKind::EpochLedger => Some(encode::<rpc::GetEpochLedgerV2>(
+                RpcResult(Err("epoch ledger not found".into())),
+                id,
+            )),
+            P2pRpcKind::BanNotify => Some(encode::<rpc::BanNotifyV1>((), id)),
+            P2pRpcKind::LedgerQuery
+            | P2pRpcKind::Snark
+            | P2pRpcKind::InitialPeers
+            | P2pRpcKind::NodeStatus => None,
+        }
+    }
+
     pub fn internal_request_into_libp2p(
         request: P2pRpcRequest,
         id: P2pRpcId,
@@ -395,6 +552,92 @@ mod libp2p {
                     v.into(),
                 ))
             }
+            P2pRpcRequest::Ancestry(consensus_state, hash) => {
+                type Method = rpc::GetAncestryV2;
+                type Payload = QueryPayload<<Method as RpcMethod>::Query>;
+
+                let query = rpc::WithHashV1 {
+                    data: *consensus_state,
+                    hash: hash.0.clone(),
+                };
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&NeedsLength(query), &mut v)
+                    .unwrap_or_default();
+                Some((
+                    QueryHeader {
+                        tag: Method::NAME.into(),
+                        version: Method::VERSION,
+                        id: id as _,
+                    },
+                    v.into(),
+                ))
+            }
+            P2pRpcRequest::TransitionChainProof(hash) => {
+                type Method = rpc::GetTransitionChainProofV1ForV2;
+                type Payload = QueryPayload<<Method as RpcMethod>::Query>;
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&NeedsLength(hash.0.clone()), &mut v)
+                    .unwrap_or_default();
+                Some((
+                    QueryHeader {
+                        tag: Method::NAME.into(),
+                        version: Method::VERSION,
+                        id: id as _,
+                    },
+                    v.into(),
+                ))
+            }
+            P2pRpcRequest::NodeStatus => {
+                type Method = rpc::GetNodeStatusV2;
+                type Payload = QueryPayload<<Method as RpcMethod>::Query>;
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&NeedsLength(()), &mut v)
+                    .unwrap_or_default();
+                Some((
+                    QueryHeader {
+                        tag: Method::NAME.into(),
+                        version: Method::VERSION,
+                        id: id as _,
+                    },
+                    v.into(),
+                ))
+            }
+            P2pRpcRequest::EpochLedger(hash) => {
+                type Method = rpc::GetEpochLedgerV2;
+                type Payload = QueryPayload<<Method as RpcMethod>::Query>;
+
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&NeedsLength(hash.0.clone()), &mut v)
+                    .unwrap_or_default();
+                Some((
+                    QueryHeader {
+                        tag: Method::NAME.into(),
+                        version: Method::VERSION,
+                        id: id as _,
+                    },
+                    v.into(),
+                ))
+            }
+            P2pRpcRequest::BanNotify(banned_until) => {
+                type Method = rpc::BanNotifyV1;
+                type Payload = QueryPayload<<Method as RpcMethod>::Query>;
+
+                let banned_until = banned_until as f64 / 1_000_000_000.0;
+                let mut v = vec![];
+                <Payload as BinProtWrite>::binprot_write(&NeedsLength(banned_until.into()), &mut v)
+                    .unwrap_or_default();
+                Some((
+                    QueryHeader {
+                        tag: Method::NAME.into(),
+                        version: Method::VERSION,
+                        id: id as _,
+                    },
+                    v.into(),
+                ))
+            }
         }
     }
 }
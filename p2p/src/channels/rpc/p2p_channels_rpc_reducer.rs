@@ -1,16 +1,17 @@
 use std::collections::VecDeque;
 
 use openmina_core::{block::BlockWithHash, bug_condition, error, Substate};
-use redux::ActionWithMeta;
+use redux::{ActionWithMeta, Dispatcher};
 
 use crate::{
-    channels::rpc_effectful::P2pChannelsRpcEffectfulAction, P2pNetworkRpcAction, P2pPeerAction,
-    P2pState,
+    channels::rpc_effectful::P2pChannelsRpcEffectfulAction, disconnection::P2pDisconnectionAction,
+    P2pNetworkRpcAction, P2pPeerAction, P2pState, PeerId,
 };
 
 use super::{
-    P2pChannelsRpcAction, P2pChannelsRpcState, P2pRpcLocalState, P2pRpcRemotePendingRequestState,
-    P2pRpcRemoteState, P2pRpcResponse, MAX_P2P_RPC_REMOTE_CONCURRENT_REQUESTS,
+    P2pChannelsRpcAction, P2pChannelsRpcState, P2pRpcKind, P2pRpcLocalState,
+    P2pRpcRemotePendingRequestState, P2pRpcRemoteState, P2pRpcResponse,
+    MAX_P2P_RPC_REMOTE_CONCURRENT_REQUESTS,
 };
 
 impl P2pChannelsRpcState {
@@ -116,9 +117,14 @@ impl P2pChannelsRpcState {
                 Ok(())
             }
             P2pChannelsRpcAction::Timeout { id, .. } => {
+                let is_ban_notify =
+                    rpc_state.pending_local_rpc_kind() == Some(P2pRpcKind::BanNotify);
                 let (dispatcher, state) = state_context.into_dispatcher_and_state();
                 let p2p_state: &P2pState = state.substate()?;
 
+                if is_ban_notify {
+                    Self::disconnect_banned_peer(dispatcher, p2p_state, peer_id);
+                }
                 if let Some(callback) = &p2p_state.callbacks.on_p2p_channels_rpc_timeout {
                     dispatcher.push_callback(callback.clone(), (peer_id, id));
                 }
@@ -144,6 +150,7 @@ impl P2pChannelsRpcState {
                     );
                     return Ok(());
                 };
+                let is_ban_notify = request.kind() == P2pRpcKind::BanNotify;
                 *local = P2pRpcLocalState::Responded {
                     time: meta.time(),
                     id: *id,
@@ -161,6 +168,9 @@ impl P2pChannelsRpcState {
 
                     dispatcher.push(P2pPeerAction::BestTipUpdate { peer_id, best_tip });
                 }
+                if is_ban_notify {
+                    Self::disconnect_banned_peer(dispatcher, p2p_state, peer_id);
+                }
 
                 if let Some(callback) = &p2p_state.callbacks.on_p2p_channels_rpc_response_received {
                     dispatcher.push_callback(callback.clone(), (peer_id, rpc_id, response));
@@ -214,25 +224,34 @@ impl P2pChannelsRpcState {
                     return Ok(());
                 };
 
-                if let Some(pos) = remote.pending_requests.iter().position(|r| r.id == id) {
-                    remote.pending_requests.remove(pos);
-                    remote.last_responded = meta.time();
-                }
+                let request_kind = remote
+                    .pending_requests
+                    .iter()
+                    .position(|r| r.id == id)
+                    .and_then(|pos| remote.pending_requests.remove(pos))
+                    .map(|req| {
+                        remote.last_responded = meta.time();
+                        req.request.kind()
+                    });
 
                 let dispatcher = state_context.into_dispatcher();
 
                 #[cfg(feature = "p2p-libp2p")]
                 if is_libp2p {
-                    if let Some(response) = response {
-                        if let Some((response, data)) =
+                    let response = match response {
+                        Some(response) => {
                             super::libp2p::internal_response_into_libp2p(*response, id)
-                        {
-                            dispatcher.push(P2pNetworkRpcAction::OutgoingResponse {
-                                peer_id,
-                                response,
-                                data,
-                            });
                         }
+                        None => request_kind.and_then(|kind| {
+                            super::libp2p::internal_empty_response_into_libp2p(kind, id)
+                        }),
+                    };
+                    if let Some((response, data)) = response {
+                        dispatcher.push(P2pNetworkRpcAction::OutgoingResponse {
+                            peer_id,
+                            response,
+                            data,
+                        });
                     }
 
                     return Ok(());
@@ -247,4 +266,22 @@ impl P2pChannelsRpcState {
             }
         }
     }
+
+    /// Banned peer got notified about the ban (or didn't respond in time),
+    /// so it can be disconnected now.
+    fn disconnect_banned_peer<Action, State>(
+        dispatcher: &mut Dispatcher<Action, State>,
+        p2p_state: &P2pState,
+        peer_id: PeerId,
+    ) where
+        State: crate::P2pStateTrait,
+        Action: crate::P2pActionTrait<State>,
+    {
+        if let Some(ban) = p2p_state.peer_scores.banned.get(&peer_id) {
+            dispatcher.push(P2pDisconnectionAction::Init {
+                peer_id,
+                reason: ban.reason.clone(),
+            });
+        }
+    }
 }
@@ -47,6 +47,10 @@ impl P2pState {
             return Err(RejectionReason::ConnectingToSelf);
         }
 
+        if self.is_peer_banned(&peer_id) {
+            return Err(RejectionReason::PeerBanned);
+        }
+
         if self.is_peer_connected_or_connecting(&peer_id) {
             // Both nodes trying to connect to each other at the same time.
             // Choose connection arbitrarily based on peer id.
@@ -68,6 +72,10 @@ impl P2pState {
             return Err(RejectionReason::ConnectingToSelf);
         }
 
+        if self.is_peer_banned(&peer_id) {
+            return Err(RejectionReason::PeerBanned);
+        }
+
         if self.already_has_max_ready_peers() {
             return Err(RejectionReason::PeerCapacityFull);
         }
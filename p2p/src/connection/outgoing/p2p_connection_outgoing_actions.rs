@@ -93,6 +93,7 @@ impl redux::EnablingCondition<P2pState> for P2pConnectionOutgoingAction {
             P2pConnectionOutgoingAction::Init { opts, .. } => {
                 !state.already_has_min_peers() &&
                 &state.my_id() != opts.peer_id() &&
+                !state.is_peer_banned(opts.peer_id()) &&
                 state
                     .peers
                     .get(opts.peer_id())
@@ -100,6 +101,7 @@ impl redux::EnablingCondition<P2pState> for P2pConnectionOutgoingAction {
             }
             P2pConnectionOutgoingAction::Reconnect { opts, .. } => {
                 !state.already_has_min_peers()
+                    && !state.is_peer_banned(opts.peer_id())
                     && state.peers.get(opts.peer_id()).map_or(false, |peer| {
                         peer.can_reconnect(time, &state.config.timeouts)
                     })
@@ -33,6 +33,10 @@ pub enum P2pDisconnectionReason {
     TransitionFrontierSyncLedgerSnarkedNumAccountsRejected,
     #[error("failed to verify snark pool diff")]
     SnarkPoolVerifyError,
+    #[error("block with invalid proof")]
+    InvalidBlockProof,
+    #[error("invalid gossip message: {0}")]
+    InvalidGossipMessage(String),
     #[error("duplicate connection")]
     DuplicateConnection,
     #[error("timeout")]
@@ -40,3 +44,30 @@ pub enum P2pDisconnectionReason {
     #[error("rpc protocol not supported")]
     Unsupported,
 }
+
+impl P2pDisconnectionReason {
+    /// By how much the peer's score is lowered when it gets disconnected
+    /// for this reason. See [`crate::P2pPeerScoresState`].
+    pub fn score_penalty(&self) -> i32 {
+        match self {
+            Self::P2pChannelMsgUnexpected(_) | Self::P2pChannelReceiveFailed(_) => 25,
+            Self::P2pChannelSendFailed(_) | Self::P2pChannelClosed(_) => 0,
+            Self::Libp2pIncomingRejected(reason) => match reason {
+                RejectionReason::PeerIdAndPublicKeyMismatch
+                | RejectionReason::TargetPeerIdNotMe => 25,
+                RejectionReason::PeerCapacityFull
+                | RejectionReason::AlreadyConnected
+                | RejectionReason::ConnectingToSelf
+                | RejectionReason::PeerBanned => 0,
+            },
+            Self::TransitionFrontierRpcTimeout(_)
+            | Self::TransitionFrontierStreamingRpcTimeout(_) => 5,
+            Self::TransitionFrontierSyncLedgerSnarkedNumAccountsRejected
+            | Self::SnarkPoolVerifyError
+            | Self::InvalidGossipMessage(_) => 50,
+            // relaying nodes verify blocks before propagating them.
+            Self::InvalidBlockProof => crate::P2P_PEER_SCORE_MAX,
+            Self::DuplicateConnection | Self::Timeout | Self::Unsupported => 0,
+        }
+    }
+}
@@ -22,6 +22,19 @@ impl P2pDisconnectedState {
 
         match action {
             P2pDisconnectionAction::Init { peer_id, reason } => {
+                // banned peer is only waiting to be disconnected.
+                let should_ban = !p2p_state.is_peer_banned(&peer_id)
+                    && p2p_state
+                        .peer_scores
+                        .penalize(peer_id, reason.score_penalty(), meta.time())
+                    && p2p_state.config.timeouts.peer_ban.is_some();
+                if should_ban {
+                    // peer is disconnected once it is notified about the ban.
+                    let dispatcher = state_context.into_dispatcher();
+                    dispatcher.push(P2pPeerAction::Ban { peer_id, reason });
+                    return Ok(());
+                }
+
                 #[cfg(feature = "p2p-libp2p")]
                 if p2p_state.is_libp2p_peer(&peer_id) {
                     if let Some((&addr, _)) = p2p_state
@@ -8,6 +8,8 @@ use crate::{P2pPeerStatus, P2pState, PeerId};
 pub enum P2pDisconnectionEffectfulAction {
     /// Initialize disconnection.
     Init { peer_id: PeerId },
+    /// Persist the list of banned peers, so that bans survive restarts.
+    BanListPersist,
 }
 
 impl redux::EnablingCondition<P2pState> for P2pDisconnectionEffectfulAction {
@@ -18,6 +20,7 @@ impl redux::EnablingCondition<P2pState> for P2pDisconnectionEffectfulAction {
                     !matches!(peer.status, P2pPeerStatus::Disconnected { .. })
                 })
             }
+            P2pDisconnectionEffectfulAction::BanListPersist => true,
         }
     }
 }
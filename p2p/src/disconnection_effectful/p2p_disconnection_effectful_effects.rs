@@ -15,6 +15,10 @@ impl P2pDisconnectionEffectfulAction {
                 store.service().disconnect(peer_id);
                 store.dispatch(P2pDisconnectionAction::Finish { peer_id });
             }
+            P2pDisconnectionEffectfulAction::BanListPersist => {
+                let bans = store.state().peer_scores.banned.clone();
+                store.service().ban_list_persist(&bans);
+            }
         }
     }
 }
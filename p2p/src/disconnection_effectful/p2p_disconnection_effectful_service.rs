@@ -1,5 +1,11 @@
-use crate::PeerId;
+use std::collections::BTreeMap;
+
+use crate::{P2pPeerBan, PeerId};
 
 pub trait P2pDisconnectionService: redux::Service {
     fn disconnect(&mut self, peer_id: PeerId);
+
+    /// Persist the list of banned peers, so that it can be passed as
+    /// [`crate::P2pConfig::initial_banned_peers`] after a restart.
+    fn ban_list_persist(&mut self, bans: &BTreeMap<PeerId, P2pPeerBan>);
 }
@@ -7,6 +7,7 @@ use redux::{Dispatcher, Timestamp};
 
 use crate::{
     channels::{snark::P2pChannelsSnarkAction, transaction::P2pChannelsTransactionAction},
+    disconnection::{P2pDisconnectionAction, P2pDisconnectionReason},
     peer::P2pPeerAction,
     Data, P2pConfig, P2pNetworkYamuxAction, PeerId,
 };
@@ -140,7 +141,16 @@ impl P2pNetworkPubsubState {
                 message,
                 seen_limit,
             } => {
-                pubsub_state.reduce_incoming_message(peer_id, message, seen_limit)?;
+                if let Err(error) =
+                    pubsub_state.reduce_incoming_message(peer_id, message, seen_limit)
+                {
+                    let dispatcher = state_context.into_dispatcher();
+                    dispatcher.push(P2pDisconnectionAction::Init {
+                        peer_id,
+                        reason: P2pDisconnectionReason::InvalidGossipMessage(error),
+                    });
+                    return Ok(());
+                }
 
                 let (dispatcher, global_state) = state_context.into_dispatcher_and_state();
                 let state: &Self = global_state.substate()?;
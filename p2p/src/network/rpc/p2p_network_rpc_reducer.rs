@@ -15,7 +15,7 @@ use redux::Dispatcher;
 
 use crate::{
     channels::rpc::{
-        BestTipWithProof, P2pChannelsRpcAction, P2pRpcRequest, P2pRpcResponse,
+        AncestryWithProof, BestTipWithProof, P2pChannelsRpcAction, P2pRpcRequest, P2pRpcResponse,
         StagedLedgerAuxAndPendingCoinbases,
     },
     connection::outgoing::P2pConnectionOutgoingInitOpts,
@@ -93,10 +93,16 @@ impl P2pNetworkRpcState {
                                 {
                                     RpcMessage::Handshake
                                 }
-                                Ok(MessageHeader::Query(header)) => RpcMessage::Query {
-                                    header,
-                                    bytes: slice.to_vec().into(),
-                                },
+                                Ok(MessageHeader::Query(header)) => {
+                                    if let Err(err) = check_rpc_query_limit(&header, len, limits) {
+                                        rpc_state.error = Some(err);
+                                        break;
+                                    }
+                                    RpcMessage::Query {
+                                        header,
+                                        bytes: slice.to_vec().into(),
+                                    }
+                                }
                                 Ok(MessageHeader::Response(header)) => RpcMessage::Response {
                                     header,
                                     bytes: slice.to_vec().into(),
@@ -331,8 +337,16 @@ impl P2pNetworkRpcState {
 
     fn check_rpc_limit(&self, len: usize, limits: &P2pLimits) -> Result<(), P2pNetworkRpcError> {
         let (limit, kind): (_, &[u8]) = if self.is_incoming {
-            // only requests are allowed
-            (limits.rpc_query(), b"<query>")
+            // only requests are allowed, the limit of the particular rpc
+            // is checked once its header is read.
+            let query = limits.rpc_query();
+            let ancestry_query = limits.rpc_get_ancestry_query();
+            let limit = if ancestry_query > query {
+                ancestry_query
+            } else {
+                query
+            };
+            (limit, b"<query>")
         } else if let Some(QueryHeader { tag, .. }) = self.pending.as_ref() {
             use mina_p2p_messages::rpc::*;
             match tag.as_ref() {
@@ -353,6 +367,15 @@ impl P2pNetworkRpcState {
                     limits.rpc_get_some_initial_peers(),
                     GetSomeInitialPeersV1ForV2::NAME,
                 ),
+                GetAncestryV2::NAME => (limits.rpc_get_ancestry(), GetAncestryV2::NAME),
+                GetTransitionChainProofV1ForV2::NAME => (
+                    limits.rpc_get_transition_chain_proof(),
+                    GetTransitionChainProofV1ForV2::NAME,
+                ),
+                GetNodeStatusV2::NAME => (limits.rpc_get_node_status(), GetNodeStatusV2::NAME),
+                GetEpochLedgerV2::NAME => (limits.rpc_get_epoch_ledger(), GetEpochLedgerV2::NAME),
+                // acknowledgement without any data.
+                BanNotifyV1::NAME => (limits.rpc_query(), BanNotifyV1::NAME),
                 _ => (Limit::Some(0), b"<unimplemented>"),
             }
         } else {
@@ -367,6 +390,28 @@ impl P2pNetworkRpcState {
     }
 }
 
+fn check_rpc_query_limit(
+    QueryHeader { tag, .. }: &QueryHeader,
+    len: usize,
+    limits: &P2pLimits,
+) -> Result<(), P2pNetworkRpcError> {
+    use mina_p2p_messages::rpc::*;
+    let limit = match tag.as_ref() {
+        GetAncestryV2::NAME => limits.rpc_get_ancestry_query(),
+        _ => limits.rpc_query(),
+    };
+    if len > limit {
+        let kind = String::from_utf8_lossy(tag.as_ref());
+        Err(P2pNetworkRpcError::Limit(
+            format!("<query {kind}>"),
+            len,
+            limit,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 fn dispatch_rpc_query<'a, State, Action>(
     peer_id: PeerId,
     QueryHeader { tag, version, id }: &'a QueryHeader,
@@ -434,6 +479,60 @@ where
                 request: Box::new(P2pRpcRequest::InitialPeers),
             });
         }
+        (rpc::GetAncestryV2::NAME, rpc::GetAncestryV2::VERSION) => {
+            let rpc::WithHashV1 {
+                data: consensus_state,
+                hash,
+            } = rpc::GetAncestryV2::query_payload(&mut bytes)?;
+            let hash = v2::StateHash::from(v2::DataHashLibStateHashStableV1(hash));
+
+            dispatcher.push(P2pChannelsRpcAction::RequestReceived {
+                peer_id,
+                id,
+                request: Box::new(P2pRpcRequest::Ancestry(Box::new(consensus_state), hash)),
+            });
+        }
+        (
+            rpc::GetTransitionChainProofV1ForV2::NAME,
+            rpc::GetTransitionChainProofV1ForV2::VERSION,
+        ) => {
+            let hash = rpc::GetTransitionChainProofV1ForV2::query_payload(&mut bytes)?;
+            let hash = v2::StateHash::from(v2::DataHashLibStateHashStableV1(hash));
+
+            dispatcher.push(P2pChannelsRpcAction::RequestReceived {
+                peer_id,
+                id,
+                request: Box::new(P2pRpcRequest::TransitionChainProof(hash)),
+            });
+        }
+        (rpc::GetNodeStatusV2::NAME, rpc::GetNodeStatusV2::VERSION) => {
+            let () = rpc::GetNodeStatusV2::query_payload(&mut bytes)?;
+            dispatcher.push(P2pChannelsRpcAction::RequestReceived {
+                peer_id,
+                id,
+                request: Box::new(P2pRpcRequest::NodeStatus),
+            });
+        }
+        (rpc::GetEpochLedgerV2::NAME, rpc::GetEpochLedgerV2::VERSION) => {
+            let hash = rpc::GetEpochLedgerV2::query_payload(&mut bytes)?;
+            let hash = v2::LedgerHash::from(v2::MinaBaseLedgerHash0StableV1(hash));
+
+            dispatcher.push(P2pChannelsRpcAction::RequestReceived {
+                peer_id,
+                id,
+                request: Box::new(P2pRpcRequest::EpochLedger(hash)),
+            });
+        }
+        (rpc::BanNotifyV1::NAME, rpc::BanNotifyV1::VERSION) => {
+            let banned_until = f64::from(rpc::BanNotifyV1::query_payload(&mut bytes)?);
+            let banned_until = (banned_until * 1_000_000_000.0) as u64;
+
+            dispatcher.push(P2pChannelsRpcAction::RequestReceived {
+                peer_id,
+                id,
+                request: Box::new(P2pRpcRequest::BanNotify(banned_until)),
+            });
+        }
         (name, version) => return Err(RpcQueryError::Unimplemented(name, version)),
     }
     Ok(())
@@ -549,6 +648,85 @@ where
                 });
             }
         }
+        (rpc::GetAncestryV2::NAME, rpc::GetAncestryV2::VERSION) => {
+            let response = rpc::GetAncestryV2::response_payload(&mut bytes)?
+                .map(|resp| AncestryWithProof {
+                    root: resp.data.into(),
+                    proof: (
+                        resp.proof
+                            .0
+                            .into_iter()
+                            .map(v2::MinaBaseStateBodyHashStableV1)
+                            .collect(),
+                        resp.proof.1.into(),
+                    ),
+                })
+                .map(P2pRpcResponse::Ancestry)
+                .map(Box::new);
+
+            dispatcher.push(P2pChannelsRpcAction::ResponseReceived {
+                peer_id,
+                id,
+                response,
+            });
+        }
+        (
+            rpc::GetTransitionChainProofV1ForV2::NAME,
+            rpc::GetTransitionChainProofV1ForV2::VERSION,
+        ) => {
+            let response = rpc::GetTransitionChainProofV1ForV2::response_payload(&mut bytes)?
+                .map(|(hash, body_hashes)| {
+                    P2pRpcResponse::TransitionChainProof(
+                        v2::DataHashLibStateHashStableV1(hash).into(),
+                        body_hashes
+                            .into_iter()
+                            .map(v2::MinaBaseStateBodyHashStableV1)
+                            .collect(),
+                    )
+                })
+                .map(Box::new);
+
+            dispatcher.push(P2pChannelsRpcAction::ResponseReceived {
+                peer_id,
+                id,
+                response,
+            });
+        }
+        (rpc::GetNodeStatusV2::NAME, rpc::GetNodeStatusV2::VERSION) => {
+            let response = Result::from(rpc::GetNodeStatusV2::response_payload(&mut bytes)?)
+                .map_err(|e| RpcResponseError::Other {
+                    rpc_id: rpc::GetNodeStatusV2::rpc_id(),
+                    error: e.to_string(),
+                })?;
+            let response = Some(Box::new(P2pRpcResponse::NodeStatus(Box::new(response))));
+
+            dispatcher.push(P2pChannelsRpcAction::ResponseReceived {
+                peer_id,
+                id,
+                response,
+            });
+        }
+        (rpc::GetEpochLedgerV2::NAME, rpc::GetEpochLedgerV2::VERSION) => {
+            // peer responds with an error if it doesn't have the ledger.
+            let response = Result::from(rpc::GetEpochLedgerV2::response_payload(&mut bytes)?)
+                .ok()
+                .map(|ledger| Box::new(P2pRpcResponse::EpochLedger(Arc::new(ledger))));
+
+            dispatcher.push(P2pChannelsRpcAction::ResponseReceived {
+                peer_id,
+                id,
+                response,
+            });
+        }
+        (rpc::BanNotifyV1::NAME, rpc::BanNotifyV1::VERSION) => {
+            let () = rpc::BanNotifyV1::response_payload(&mut bytes)?;
+
+            dispatcher.push(P2pChannelsRpcAction::ResponseReceived {
+                peer_id,
+                id,
+                response: Some(Box::new(P2pRpcResponse::BanNotify)),
+            });
+        }
         _ => {}
     }
     Ok(())
@@ -569,3 +747,31 @@ enum RpcResponseError {
     #[error("rpc response {rpc_id} error: {error}")]
     Other { rpc_id: String, error: String },
 }
+
+#[cfg(test)]
+mod tests {
+    use mina_p2p_messages::rpc::{GetAncestryV2, GetBestTipV2};
+
+    use super::*;
+
+    fn query_header(tag: &[u8]) -> QueryHeader {
+        QueryHeader {
+            tag: tag.into(),
+            version: 2,
+            id: 0,
+        }
+    }
+
+    #[test]
+    fn test_rpc_query_limits() {
+        let limits = P2pLimits::default();
+        let ancestry = query_header(GetAncestryV2::NAME);
+        let best_tip = query_header(GetBestTipV2::NAME);
+
+        assert!(check_rpc_query_limit(&best_tip, 96, &limits).is_ok());
+        assert!(check_rpc_query_limit(&ancestry, 1024, &limits).is_ok());
+        // only get_ancestry query is allowed to carry a consensus state.
+        assert!(check_rpc_query_limit(&best_tip, 1024, &limits).is_err());
+        assert!(check_rpc_query_limit(&ancestry, 4096, &limits).is_err());
+    }
+}
@@ -1,9 +1,13 @@
-use std::{collections::BTreeSet, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
     channels::ChannelId, connection::outgoing::P2pConnectionOutgoingInitOpts, identity::PublicKey,
+    P2pPeerBan, PeerId,
 };
 
 pub const DEVNET_SEEDS: &[&str] = &[
@@ -22,6 +26,8 @@ pub struct P2pConfig {
     pub identity_pub_key: PublicKey,
     /// A list addresses of seed nodes.
     pub initial_peers: Vec<P2pConnectionOutgoingInitOpts>,
+    /// Peers banned during the previous run, that are still to be banned.
+    pub initial_banned_peers: BTreeMap<PeerId, P2pPeerBan>,
 
     /// The time interval that must elapse before the next peer discovery request.
     /// The node periodically polls peers for their connections to keep our list up to date.
@@ -75,6 +81,15 @@ pub struct P2pTimeouts {
     pub block: Option<Duration>,
     pub snark: Option<Duration>,
     pub initial_peers: Option<Duration>,
+    pub ancestry: Option<Duration>,
+    pub transition_chain_proof: Option<Duration>,
+    pub node_status: Option<Duration>,
+    pub epoch_ledger: Option<Duration>,
+    /// For how long we wait for the banned peer to acknowledge the ban
+    /// before disconnecting it.
+    pub ban_notify: Option<Duration>,
+    /// For how long a peer stays banned. `None` disables bans.
+    pub peer_ban: Option<Duration>,
     pub kademlia_bootstrap: Option<Duration>,
     pub kademlia_initial_bootstrap: Option<Duration>,
     pub select: Option<Duration>,
@@ -124,6 +139,15 @@ impl Default for P2pTimeouts {
             block: from_env_or("BLOCK_TIMEOUT", Some(Duration::from_secs(5))),
             snark: from_env_or("SNARK_TIMEOUT", Some(Duration::from_secs(5))),
             initial_peers: from_env_or("INITIAL_PEERS_TIMEOUT", Some(Duration::from_secs(5))),
+            ancestry: from_env_or("ANCESTRY_TIMEOUT", Some(Duration::from_secs(10))),
+            transition_chain_proof: from_env_or(
+                "TRANSITION_CHAIN_PROOF_TIMEOUT",
+                Some(Duration::from_secs(5)),
+            ),
+            node_status: from_env_or("NODE_STATUS_TIMEOUT", Some(Duration::from_secs(5))),
+            epoch_ledger: from_env_or("EPOCH_LEDGER_TIMEOUT", Some(Duration::from_secs(120))),
+            ban_notify: from_env_or("BAN_NOTIFY_TIMEOUT", Some(Duration::from_secs(2))),
+            peer_ban: from_env_or("PEER_BAN_DURATION", Some(Duration::from_secs(24 * 60 * 60))),
             kademlia_bootstrap: from_env_or(
                 "KADEMLIA_BOOTSTRAP_TIMEOUT",
                 Some(Duration::from_secs(60)),
@@ -146,6 +170,10 @@ impl P2pTimeouts {
             staged_ledger_aux_and_pending_coinbases_at_block: None,
             block: None,
             snark: None,
+            ancestry: None,
+            transition_chain_proof: None,
+            node_status: None,
+            epoch_ledger: None,
             ..Default::default()
         }
     }
@@ -231,6 +259,17 @@ macro_rules! impls {
             }
         }
 
+        impl std::cmp::PartialOrd<Limit<$ty>> for Limit<$ty> {
+            fn partial_cmp(&self, other: &Limit<$ty>) -> Option<std::cmp::Ordering> {
+                match (self, other) {
+                    (Limit::Some(this), Limit::Some(other)) => this.partial_cmp(other),
+                    (Limit::Unlimited, Limit::Unlimited) => Some(std::cmp::Ordering::Equal),
+                    (Limit::Unlimited, _) => Some(std::cmp::Ordering::Greater),
+                    (_, Limit::Unlimited) => Some(std::cmp::Ordering::Less),
+                }
+            }
+        }
+
         impl std::cmp::PartialOrd<Limit<$ty>> for $ty {
             fn partial_cmp(&self, other: &Limit<$ty>) -> Option<std::cmp::Ordering> {
                 match other {
@@ -260,11 +299,16 @@ pub struct P2pLimits {
 
     rpc_service_message: Limit<usize>,
     rpc_query: Limit<usize>,
+    rpc_get_ancestry_query: Limit<usize>,
     rpc_get_best_tip: Limit<usize>,
     rpc_answer_sync_ledger_query: Limit<usize>,
     rpc_get_staged_ledger: Limit<usize>,
     rpc_get_transition_chain: Limit<usize>,
     rpc_get_some_initial_peers: Limit<usize>,
+    rpc_get_ancestry: Limit<usize>,
+    rpc_get_transition_chain_proof: Limit<usize>,
+    rpc_get_node_status: Limit<usize>,
+    rpc_get_epoch_ledger: Limit<usize>,
 }
 
 macro_rules! limit {
@@ -358,6 +402,10 @@ impl P2pLimits {
         #[doc = "RPC query"]
         rpc_query
     );
+    limit!(
+        #[doc = "RPC get_ancestry query"]
+        rpc_get_ancestry_query
+    );
     limit!(
         #[doc = "RPC get_best_tip"]
         rpc_get_best_tip
@@ -378,6 +426,22 @@ impl P2pLimits {
         #[doc = "RPC some_initial_peers"]
         rpc_get_some_initial_peers
     );
+    limit!(
+        #[doc = "RPC get_ancestry"]
+        rpc_get_ancestry
+    );
+    limit!(
+        #[doc = "RPC get_transition_chain_proof"]
+        rpc_get_transition_chain_proof
+    );
+    limit!(
+        #[doc = "RPC get_node_status"]
+        rpc_get_node_status
+    );
+    limit!(
+        #[doc = "RPC get_epoch_ledger"]
+        rpc_get_epoch_ledger
+    );
 }
 
 impl Default for P2pLimits {
@@ -395,11 +459,16 @@ impl Default for P2pLimits {
 
         let rpc_service_message = Limit::Some(7); // 7 for handshake, 1 for heartbeat
         let rpc_query = Limit::Some(256); // max is 96
+        let rpc_get_ancestry_query = Limit::Some(2048); // carries a whole consensus state
         let rpc_get_best_tip = Limit::Some(3_500_000); // 3182930 as observed, may vary
         let rpc_answer_sync_ledger_query = Limit::Some(200_000); // 124823 as observed
         let rpc_get_staged_ledger = Limit::Some(400_000_000); // 59286608 as observed, may go higher
         let rpc_get_transition_chain = Limit::Some(3_500_000); // 2979112 as observed
         let rpc_get_some_initial_peers = Limit::Some(32_000); // TODO: calculate
+        let rpc_get_ancestry = rpc_get_best_tip; // same blocks as in get_best_tip
+        let rpc_get_transition_chain_proof = Limit::Some(32_000); // at most k + 1 hashes
+        let rpc_get_node_status = Limit::Some(1_000_000); // TODO: calculate
+        let rpc_get_epoch_ledger = rpc_get_staged_ledger;
 
         Self {
             max_peers,
@@ -415,11 +484,16 @@ impl Default for P2pLimits {
 
             rpc_service_message,
             rpc_query,
+            rpc_get_ancestry_query,
             rpc_get_best_tip,
             rpc_answer_sync_ledger_query,
             rpc_get_staged_ledger,
             rpc_get_transition_chain,
             rpc_get_some_initial_peers,
+            rpc_get_ancestry,
+            rpc_get_transition_chain_proof,
+            rpc_get_node_status,
+            rpc_get_epoch_ledger,
         }
     }
 }
@@ -439,5 +513,9 @@ mod tests {
         let unlimited = Limit::Unlimited;
         assert!(0 < unlimited);
         assert!(usize::MAX < unlimited);
+
+        assert!(Limit::Some(9) < limit);
+        assert!(limit < unlimited);
+        assert!(unlimited <= Limit::<usize>::Unlimited);
     }
 }
@@ -9,7 +9,8 @@ use crate::{
     },
     disconnection::P2pDisconnectedState,
     P2pAction, P2pNetworkKadKey, P2pNetworkKademliaAction, P2pNetworkPnetAction,
-    P2pNetworkRpcAction, P2pNetworkSelectAction, P2pNetworkState, P2pPeerState, P2pState, PeerId,
+    P2pNetworkRpcAction, P2pNetworkSelectAction, P2pNetworkState, P2pPeerAction, P2pPeerState,
+    P2pState, PeerId,
 };
 use openmina_core::{bug_condition, Substate};
 use redux::{ActionMeta, ActionWithMeta, Dispatcher, Timestamp};
@@ -80,6 +81,7 @@ impl P2pState {
         let time = meta.time();
 
         state.p2p_connection_timeouts_dispatch(dispatcher, time)?;
+        state.p2p_expired_bans_dispatch(dispatcher, time)?;
         dispatcher.push(P2pConnectionOutgoingAction::RandomInit);
 
         state.p2p_try_reconnect_disconnected_peers(dispatcher, time)?;
@@ -123,6 +125,21 @@ impl P2pState {
         Ok(())
     }
 
+    fn p2p_expired_bans_dispatch<State, Action>(
+        &self,
+        dispatcher: &mut Dispatcher<Action, State>,
+        time: Timestamp,
+    ) -> Result<(), String>
+    where
+        State: crate::P2pStateTrait,
+        Action: crate::P2pActionTrait<State>,
+    {
+        self.peer_scores
+            .expired_bans(time)
+            .for_each(|peer_id| dispatcher.push(P2pPeerAction::Unban { peer_id: *peer_id }));
+        Ok(())
+    }
+
     fn p2p_try_reconnect_disconnected_peers<State, Action>(
         &self,
         dispatcher: &mut Dispatcher<Action, State>,
@@ -32,7 +32,7 @@ use crate::{
         P2pNetworkState,
     },
     Limit, P2pConfig, P2pLimits, P2pNetworkKadState, P2pNetworkPubsubState,
-    P2pNetworkSchedulerState, P2pTimeouts, PeerId,
+    P2pNetworkSchedulerState, P2pPeerScoresState, P2pTimeouts, PeerId,
 };
 use mina_p2p_messages::v2::{MinaBaseUserCommandStableV2, MinaBlockBlockStableV2};
 
@@ -42,6 +42,7 @@ pub struct P2pState {
     pub config: P2pConfig,
     pub network: P2pNetworkState,
     pub peers: BTreeMap<PeerId, P2pPeerState>,
+    pub peer_scores: P2pPeerScoresState,
     pub callbacks: P2pCallbacks,
 }
 
@@ -71,10 +72,12 @@ impl P2pState {
             );
         }
 
+        let peer_scores = P2pPeerScoresState::new(config.initial_banned_peers.clone());
+
         let initial_peers = config
             .initial_peers
             .iter()
-            .filter(|peer| peer.peer_id() != &my_id);
+            .filter(|peer| peer.peer_id() != &my_id && !peer_scores.is_banned(peer.peer_id()));
 
         let known_peers = if cfg!(feature = "p2p-libp2p") {
             initial_peers
@@ -119,6 +122,7 @@ impl P2pState {
             config,
             network,
             peers,
+            peer_scores,
             callbacks,
         }
     }
@@ -150,7 +154,10 @@ impl P2pState {
     }
 
     pub fn disconnected_peers(&self) -> impl '_ + Iterator<Item = P2pConnectionOutgoingInitOpts> {
-        self.peers.iter().filter_map(|(_, state)| {
+        self.peers.iter().filter_map(|(peer_id, state)| {
+            if self.is_peer_banned(peer_id) {
+                return None;
+            }
             if let P2pPeerState {
                 status: P2pPeerStatus::Disconnected { .. },
                 dial_opts: Some(opts),
@@ -204,6 +211,10 @@ impl P2pState {
             .map_or(false, |p| p.status.is_connected_or_connecting())
     }
 
+    pub fn is_peer_banned(&self, peer_id: &PeerId) -> bool {
+        self.peer_scores.is_banned(peer_id)
+    }
+
     pub fn is_libp2p_peer(&self, peer_id: &PeerId) -> bool {
         self.peers.get(peer_id).map_or(false, |p| p.is_libp2p())
     }
@@ -525,7 +536,8 @@ pub struct P2pCallbacks {
         OptionalCallback<(RpcId, PeerId, P2pConnectionResponse)>,
 
     /// Callback for [`P2pPeerAction::BestTipUpdate`]
-    pub on_p2p_peer_best_tip_update: OptionalCallback<BlockWithHash<Arc<MinaBlockBlockStableV2>>>,
+    pub on_p2p_peer_best_tip_update:
+        OptionalCallback<(PeerId, BlockWithHash<Arc<MinaBlockBlockStableV2>>)>,
 
     /// Callback for [`P2pChannelsRpcAction::Ready`]
     pub on_p2p_channels_rpc_ready: OptionalCallback<PeerId>,
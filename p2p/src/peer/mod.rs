@@ -1,4 +1,7 @@
 mod p2p_peer_actions;
 pub use p2p_peer_actions::*;
 
+mod p2p_peer_scores_state;
+pub use p2p_peer_scores_state::*;
+
 mod p2p_peer_reducer;
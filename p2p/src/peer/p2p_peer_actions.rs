@@ -1,7 +1,10 @@
 use openmina_core::{block::ArcBlockWithHash, ActionEvent};
 use serde::{Deserialize, Serialize};
 
-use crate::{connection::outgoing::P2pConnectionOutgoingInitOpts, P2pState, PeerId};
+use crate::{
+    connection::outgoing::P2pConnectionOutgoingInitOpts, disconnection::P2pDisconnectionReason,
+    P2pState, PeerId,
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone, ActionEvent)]
 #[action_event(level = debug, fields(display(peer_id), debug(dial_opts), best_tip = display(&best_tip.hash), incoming))]
@@ -21,6 +24,15 @@ pub enum P2pPeerAction {
     },
     /// Remove peer from state
     Remove { peer_id: PeerId },
+    /// Peer's score dropped too low, ban it.
+    #[action_event(level = warn, fields(display(peer_id), display(reason)))]
+    Ban {
+        peer_id: PeerId,
+        reason: P2pDisconnectionReason,
+    },
+    /// Peer's ban is expired.
+    #[action_event(level = info)]
+    Unban { peer_id: PeerId },
 }
 
 impl P2pPeerAction {
@@ -30,6 +42,8 @@ impl P2pPeerAction {
             Self::Ready { peer_id, .. } => peer_id,
             Self::BestTipUpdate { peer_id, .. } => peer_id,
             Self::Remove { peer_id } => peer_id,
+            Self::Ban { peer_id, .. } => peer_id,
+            Self::Unban { peer_id } => peer_id,
         }
     }
 }
@@ -39,6 +53,7 @@ impl redux::EnablingCondition<P2pState> for P2pPeerAction {
         match self {
             Self::Discovered { peer_id, .. } => {
                 peer_id != &state.my_id()
+                    && !state.is_peer_banned(peer_id)
                     && state
                         .peers
                         .get(peer_id)
@@ -58,6 +73,10 @@ impl redux::EnablingCondition<P2pState> for P2pPeerAction {
                 state.peers.len() > state.config.limits.min_peers_in_state()
                     && state.peers.contains_key(peer_id)
             }
+            Self::Ban { peer_id, .. } => {
+                state.config.timeouts.peer_ban.is_some() && !state.is_peer_banned(peer_id)
+            }
+            Self::Unban { peer_id } => state.is_peer_banned(peer_id),
         }
     }
 }
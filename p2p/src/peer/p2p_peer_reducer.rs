@@ -1,7 +1,12 @@
 use openmina_core::{bug_condition, Substate};
 use redux::{ActionWithMeta, Timestamp};
 
-use crate::{P2pPeerState, P2pPeerStatus, P2pPeerStatusReady, P2pState};
+use crate::{
+    channels::rpc::{P2pChannelsRpcAction, P2pRpcRequest},
+    disconnection::P2pDisconnectionAction,
+    disconnection_effectful::P2pDisconnectionEffectfulAction,
+    P2pPeerState, P2pPeerStatus, P2pPeerStatusReady, P2pState,
+};
 
 use super::P2pPeerAction;
 
@@ -67,7 +72,7 @@ impl P2pPeerState {
                 let p2p_state: &P2pState = state.substate()?;
 
                 if let Some(callback) = &p2p_state.callbacks.on_p2p_peer_best_tip_update {
-                    dispatcher.push_callback(callback.clone(), best_tip);
+                    dispatcher.push_callback(callback.clone(), (peer_id, best_tip));
                 }
                 Ok(())
             }
@@ -80,6 +85,44 @@ impl P2pPeerState {
 
                 Ok(())
             }
+            P2pPeerAction::Ban { peer_id, reason } => {
+                let Some(duration) = p2p_state.config.timeouts.peer_ban else {
+                    bug_condition!("Peer bans are disabled for `P2pPeerAction::Ban`");
+                    return Ok(());
+                };
+                let banned_until = p2p_state
+                    .peer_scores
+                    .ban(peer_id, reason.clone(), meta.time(), duration)
+                    .until;
+
+                // Let the peer know for how long it is banned, if there is no
+                // other request in progress. It gets disconnected once it
+                // acknowledges the ban or the request times out.
+                let ban_notify_id = p2p_state
+                    .get_ready_peer(&peer_id)
+                    .filter(|peer| peer.channels.rpc.can_send_request())
+                    .map(|peer| peer.channels.next_local_rpc_id());
+
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(P2pDisconnectionEffectfulAction::BanListPersist);
+                match ban_notify_id {
+                    Some(id) => dispatcher.push(P2pChannelsRpcAction::RequestSend {
+                        peer_id,
+                        id,
+                        request: Box::new(P2pRpcRequest::BanNotify(u64::from(banned_until))),
+                        on_init: None,
+                    }),
+                    None => dispatcher.push(P2pDisconnectionAction::Init { peer_id, reason }),
+                }
+                Ok(())
+            }
+            P2pPeerAction::Unban { peer_id } => {
+                p2p_state.peer_scores.unban(&peer_id);
+
+                let dispatcher = state_context.into_dispatcher();
+                dispatcher.push(P2pDisconnectionEffectfulAction::BanListPersist);
+                Ok(())
+            }
         }
     }
 }
@@ -0,0 +1,159 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use redux::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::{disconnection::P2pDisconnectionReason, PeerId};
+
+/// Score of a peer that hasn't misbehaved (recently).
+pub const P2P_PEER_SCORE_MAX: i32 = 100;
+/// Peer gets banned once its score drops to this value or below.
+pub const P2P_PEER_SCORE_BAN_THRESHOLD: i32 = 0;
+/// How many points the score recovers per minute without penalties.
+const P2P_PEER_SCORE_RECOVERY_PER_MINUTE: u64 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct P2pPeerScoresState {
+    /// Scores of peers that were penalized. Peers that aren't here
+    /// have the maximum score.
+    pub scores: BTreeMap<PeerId, P2pPeerScore>,
+    /// Currently banned peers.
+    pub banned: BTreeMap<PeerId, P2pPeerBan>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct P2pPeerScore {
+    /// Score at the time of the last penalty.
+    pub value: i32,
+    /// Time of the last penalty.
+    pub time: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct P2pPeerBan {
+    pub reason: P2pDisconnectionReason,
+    pub since: Timestamp,
+    pub until: Timestamp,
+}
+
+impl P2pPeerScore {
+    /// Score at `now`, including the recovery since the last penalty.
+    pub fn value_at(&self, now: Timestamp) -> i32 {
+        let minutes = now.checked_sub(self.time).unwrap_or_default().as_secs() / 60;
+        let recovered = minutes
+            .saturating_mul(P2P_PEER_SCORE_RECOVERY_PER_MINUTE)
+            .min(P2P_PEER_SCORE_MAX as u64) as i32;
+        self.value.saturating_add(recovered).min(P2P_PEER_SCORE_MAX)
+    }
+}
+
+impl P2pPeerScoresState {
+    pub fn new(banned: BTreeMap<PeerId, P2pPeerBan>) -> Self {
+        Self {
+            scores: Default::default(),
+            banned,
+        }
+    }
+
+    pub fn score(&self, peer_id: &PeerId, now: Timestamp) -> i32 {
+        self.scores
+            .get(peer_id)
+            .map_or(P2P_PEER_SCORE_MAX, |score| score.value_at(now))
+    }
+
+    pub fn is_banned(&self, peer_id: &PeerId) -> bool {
+        self.banned.contains_key(peer_id)
+    }
+
+    /// Bans that are expired at `now`, but not yet lifted.
+    pub fn expired_bans(&self, now: Timestamp) -> impl '_ + Iterator<Item = &PeerId> {
+        self.banned
+            .iter()
+            .filter(move |(_, ban)| ban.until <= now)
+            .map(|(peer_id, _)| peer_id)
+    }
+
+    /// Lowers the score of the peer by `penalty`.
+    ///
+    /// Returns `true` if the peer should be banned as a result.
+    pub fn penalize(&mut self, peer_id: PeerId, penalty: i32, now: Timestamp) -> bool {
+        if penalty <= 0 {
+            return false;
+        }
+        let value = self.score(&peer_id, now).saturating_sub(penalty);
+        self.scores
+            .insert(peer_id, P2pPeerScore { value, time: now });
+        value <= P2P_PEER_SCORE_BAN_THRESHOLD
+    }
+
+    pub fn ban(
+        &mut self,
+        peer_id: PeerId,
+        reason: P2pDisconnectionReason,
+        now: Timestamp,
+        duration: Duration,
+    ) -> &P2pPeerBan {
+        // once the ban is lifted, the peer starts over.
+        self.scores.remove(&peer_id);
+        let until = u64::from(now).saturating_add(duration.as_nanos() as u64);
+        self.banned.insert(
+            peer_id,
+            P2pPeerBan {
+                reason,
+                since: now,
+                until: Timestamp::new(until),
+            },
+        );
+        &self.banned[&peer_id]
+    }
+
+    pub fn unban(&mut self, peer_id: &PeerId) {
+        self.banned.remove(peer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use redux::Timestamp;
+
+    use super::*;
+    use crate::identity::SecretKey;
+
+    fn minutes(n: u64) -> u64 {
+        Duration::from_secs(n * 60).as_nanos() as u64
+    }
+
+    #[test]
+    fn test_score_recovery_and_ban() {
+        let peer_id = SecretKey::rand().public_key().peer_id();
+        let mut scores = P2pPeerScoresState::default();
+        let now = Timestamp::new(minutes(1000));
+
+        assert_eq!(scores.score(&peer_id, now), P2P_PEER_SCORE_MAX);
+        assert!(!scores.penalize(peer_id, 0, now));
+        assert!(!scores.penalize(peer_id, 60, now));
+        assert_eq!(scores.score(&peer_id, now), 40);
+
+        let later = Timestamp::new(minutes(1010));
+        assert_eq!(scores.score(&peer_id, later), 50);
+        let much_later = Timestamp::new(minutes(2000));
+        assert_eq!(scores.score(&peer_id, much_later), P2P_PEER_SCORE_MAX);
+
+        assert!(scores.penalize(peer_id, 60, later));
+
+        let reason = P2pDisconnectionReason::InvalidGossipMessage(String::new());
+        scores.ban(peer_id, reason, later, Duration::from_secs(60));
+        assert!(scores.is_banned(&peer_id));
+        assert_eq!(scores.score(&peer_id, later), P2P_PEER_SCORE_MAX);
+        assert_eq!(scores.expired_bans(later).count(), 0);
+        assert_eq!(
+            scores.expired_bans(Timestamp::new(minutes(1011))).next(),
+            Some(&peer_id)
+        );
+
+        scores.unban(&peer_id);
+        assert!(!scores.is_banned(&peer_id));
+    }
+}
@@ -1,9 +1,14 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
 use crate::{
     channels::{ChannelId, ChannelMsg, MsgId, P2pChannelsService},
     connection::{outgoing::P2pConnectionOutgoingInitOpts, P2pConnectionService},
     disconnection_effectful::P2pDisconnectionService,
     identity::SecretKey,
-    P2pChannelEvent, P2pEvent, PeerId,
+    P2pChannelEvent, P2pEvent, P2pPeerBan, PeerId,
 };
 
 #[cfg(feature = "p2p-libp2p")]
@@ -18,6 +23,8 @@ pub struct P2pServiceCtx {
     pub webrtc: super::webrtc::P2pServiceCtx,
     #[cfg(feature = "p2p-libp2p")]
     pub mio: MioService,
+    /// File where the list of banned peers is persisted.
+    pub ban_list_path: Option<PathBuf>,
 }
 
 pub trait P2pServiceWebrtcWithLibp2p: P2pServiceWebrtc {
@@ -30,9 +37,14 @@ pub trait P2pServiceWebrtcWithLibp2p: P2pServiceWebrtc {
             #[cfg(feature = "p2p-libp2p")]
             mio: MioService::pending(sec_key.clone().try_into().expect("valid keypair")),
             webrtc: <Self as P2pServiceWebrtc>::init(sec_key, spawner),
+            ban_list_path: None,
         }
     }
 
+    fn ban_list_path(&self) -> Option<&Path> {
+        None
+    }
+
     #[cfg(feature = "p2p-libp2p")]
     fn resolve_name(
         &mut self,
@@ -123,6 +135,34 @@ impl<T: P2pServiceWebrtcWithLibp2p> P2pDisconnectionService for T {
             openmina_core::error!(openmina_core::log::system_time(); "`disconnect` shouldn't be used for libp2p peers");
         }
     }
+
+    fn ban_list_persist(&mut self, bans: &BTreeMap<PeerId, P2pPeerBan>) {
+        let Some(path) = self.ban_list_path() else {
+            return;
+        };
+        if let Err(error) = ban_list_save(path, bans) {
+            openmina_core::warn!(openmina_core::log::system_time(); summary = "failed to persist banned peers", path = display(path.display()), error = display(error));
+        }
+    }
+}
+
+/// Loads the list of banned peers persisted by
+/// [`P2pDisconnectionService::ban_list_persist`]. Missing file means
+/// that there are no banned peers.
+pub fn ban_list_load(path: &Path) -> std::io::Result<BTreeMap<PeerId, P2pPeerBan>> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Default::default()),
+        Err(error) => Err(error),
+    }
+}
+
+fn ban_list_save(path: &Path, bans: &BTreeMap<PeerId, P2pPeerBan>) -> std::io::Result<()> {
+    // write to a temporary file first, so that the list isn't corrupted
+    // if we get killed in the middle of writing.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, serde_json::to_vec(bans)?)?;
+    std::fs::rename(tmp_path, path)
 }
 
 impl<T: P2pServiceWebrtcWithLibp2p> P2pChannelsService for T {
@@ -199,6 +239,7 @@ impl P2pServiceCtx {
                 cmd_sender: mpsc::unbounded_channel().0,
                 peers: Default::default(),
             },
+            ban_list_path: None,
         }
     }
 }
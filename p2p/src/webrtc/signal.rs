@@ -47,6 +47,8 @@ pub enum RejectionReason {
     AlreadyConnected,
     #[error("self connection detected")]
     ConnectingToSelf,
+    #[error("peer is banned")]
+    PeerBanned,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -65,6 +67,7 @@ impl RejectionReason {
             Self::PeerCapacityFull => false,
             Self::AlreadyConnected => true,
             Self::ConnectingToSelf => false,
+            Self::PeerBanned => true,
         }
     }
 }
@@ -354,6 +354,7 @@ impl Cluster {
             listen_port: Some(listen_port),
             identity_pub_key: secret_key.public_key(),
             initial_peers,
+            initial_banned_peers: Default::default(),
             ask_initial_peers_interval: Duration::from_secs(5),
             enabled_channels: p2p::channels::ChannelId::for_libp2p().collect(),
             peer_discovery: config.discovery,
@@ -100,6 +100,12 @@ impl RustNode {
             .unwrap_or(RustNodeEvent::Idle)
     }
 
+    /// Moves the node's time forward by `duration`, triggering timeouts.
+    pub fn advance_time(&mut self, duration: Duration) {
+        self.store.service.advance_time(duration);
+        self.store.dispatch(IdleAction);
+    }
+
     pub fn state(&self) -> &P2pState {
         &self.store.state().0
     }
@@ -0,0 +1,223 @@
+use std::{future::ready, time::Duration};
+
+use p2p::{
+    channels::rpc::{P2pChannelsRpcAction, P2pRpcRequest, P2pRpcResponse},
+    disconnection::{P2pDisconnectionAction, P2pDisconnectionReason},
+    peer::P2pPeerAction,
+    P2pTimeouts, PeerId,
+};
+use p2p_testing::{
+    cluster::{Cluster, ClusterBuilder, ClusterEvent},
+    event::{allow_disconnections, RustNodeEvent},
+    futures::TryStreamExt,
+    rust_node::{RustNodeConfig, RustNodeId},
+    stream::ClusterStreamExt,
+    utils::{
+        peer_ids, rust_nodes_from_config, try_run_cluster, try_wait_for_nodes_to_connect,
+        wait_for_all_nodes_to_listen,
+    },
+};
+
+const PEER_BAN: Duration = Duration::from_secs(60 * 60);
+
+/// Starts two nodes and connects the second one to the first one.
+async fn connected_nodes() -> anyhow::Result<(Cluster, [RustNodeId; 2], [PeerId; 2])> {
+    let mut cluster = ClusterBuilder::default()
+        .ports_with_len(10)
+        .total_duration(Duration::from_secs(20))
+        .is_error(allow_disconnections)
+        .start()
+        .await?;
+
+    let config = RustNodeConfig::default().with_timeouts(P2pTimeouts {
+        peer_ban: Some(PEER_BAN),
+        reconnect_timeout: None,
+        ..Default::default()
+    });
+    let nodes = rust_nodes_from_config(&mut cluster, config)?;
+    let [node1, node2] = nodes;
+    let peer_ids = peer_ids(&cluster, nodes);
+    let [peer_id1, peer_id2] = peer_ids;
+
+    let listening =
+        wait_for_all_nodes_to_listen(&mut cluster, [node1, node2], Duration::from_secs(2)).await;
+    assert!(listening);
+
+    cluster.connect(node2, node1)?;
+    let connected = try_wait_for_nodes_to_connect(
+        &mut cluster,
+        [(node1, peer_id2), (node2, peer_id1)],
+        Duration::from_secs(5),
+    )
+    .await?;
+    assert!(connected);
+
+    Ok((cluster, nodes, peer_ids))
+}
+
+async fn wait_for_event<F>(cluster: &mut Cluster, mut f: F) -> Result<bool, ClusterEvent>
+where
+    F: FnMut(RustNodeId, &RustNodeEvent) -> bool,
+{
+    cluster
+        .try_stream()
+        .take_during(Duration::from_secs(5))
+        .try_any(|event| ready(matches!(&event, ClusterEvent::Rust { id, event } if f(*id, event))))
+        .await
+}
+
+async fn wait_for_rpc_ready(
+    cluster: &mut Cluster,
+    node: RustNodeId,
+    peer_id: PeerId,
+) -> Result<bool, ClusterEvent> {
+    let is_ready = |cluster: &Cluster| {
+        cluster
+            .rust_node(node)
+            .state()
+            .get_ready_peer(&peer_id)
+            .map_or(false, |peer| peer.channels.rpc.can_send_request())
+    };
+    if is_ready(cluster) {
+        return Ok(true);
+    }
+    wait_for_event(cluster, |id, event| {
+        id == node
+            && matches!(event, RustNodeEvent::RpcChannelReady { peer_id: p } if p == &peer_id)
+    })
+    .await
+}
+
+/// Tests that the banned peer gets notified about the ban, and gets
+/// disconnected once it acknowledges it.
+#[tokio::test]
+async fn ban_notify_before_disconnect() -> anyhow::Result<()> {
+    let (mut cluster, [node1, node2], [peer_id1, peer_id2]) = connected_nodes().await?;
+    assert!(wait_for_rpc_ready(&mut cluster, node1, peer_id2).await?);
+
+    let reason = P2pDisconnectionReason::InvalidGossipMessage("test".to_owned());
+    assert!(cluster
+        .rust_node_mut(node1)
+        .dispatch_action(P2pPeerAction::Ban {
+            peer_id: peer_id2,
+            reason,
+        }));
+    assert!(cluster.rust_node(node1).state().is_peer_banned(&peer_id2));
+    assert!(
+        cluster
+            .rust_node(node1)
+            .state()
+            .get_ready_peer(&peer_id2)
+            .is_some(),
+        "peer should be disconnected only after it is notified"
+    );
+
+    let mut notify_id = None;
+    let notified = wait_for_event(&mut cluster, |id, event| match event {
+        RustNodeEvent::RpcChannelRequestReceived {
+            peer_id,
+            id: rpc_id,
+            request: P2pRpcRequest::BanNotify(_),
+        } if id == node2 && peer_id == &peer_id1 => {
+            notify_id = Some(*rpc_id);
+            true
+        }
+        _ => false,
+    })
+    .await?;
+    assert!(notified, "banned peer should be notified");
+
+    assert!(cluster
+        .rust_node_mut(node2)
+        .dispatch_action(P2pChannelsRpcAction::ResponseSend {
+            peer_id: peer_id1,
+            id: notify_id.unwrap(),
+            response: Some(Box::new(P2pRpcResponse::BanNotify)),
+        }));
+
+    let disconnected = wait_for_event(&mut cluster, |id, event| {
+        id == node1
+            && matches!(event, RustNodeEvent::PeerDisconnected { peer_id, .. } if peer_id == &peer_id2)
+    })
+    .await?;
+    assert!(disconnected, "banned peer should be disconnected");
+
+    Ok(())
+}
+
+/// Tests that the ban is lifted once it expires.
+#[tokio::test]
+async fn ban_expiry() -> anyhow::Result<()> {
+    let (mut cluster, [node1, _], [_, peer_id2]) = connected_nodes().await?;
+
+    let reason = P2pDisconnectionReason::InvalidGossipMessage("test".to_owned());
+    assert!(cluster
+        .rust_node_mut(node1)
+        .dispatch_action(P2pPeerAction::Ban {
+            peer_id: peer_id2,
+            reason,
+        }));
+    assert!(cluster.rust_node(node1).state().is_peer_banned(&peer_id2));
+
+    cluster.rust_node_mut(node1).advance_time(PEER_BAN / 2);
+    assert!(cluster.rust_node(node1).state().is_peer_banned(&peer_id2));
+
+    cluster.rust_node_mut(node1).advance_time(PEER_BAN / 2);
+    assert!(!cluster.rust_node(node1).state().is_peer_banned(&peer_id2));
+
+    Ok(())
+}
+
+/// Tests that the score recovers over time, so that a peer misbehaving
+/// rarely isn't banned.
+#[tokio::test]
+async fn score_decay() -> anyhow::Result<()> {
+    let (mut cluster, [node1, node2], [peer_id1, peer_id2]) = connected_nodes().await?;
+
+    let disconnect = |cluster: &mut Cluster| {
+        let reason = P2pDisconnectionReason::InvalidGossipMessage("test".to_owned());
+        assert_eq!(reason.score_penalty(), 50);
+        assert!(cluster
+            .rust_node_mut(node1)
+            .dispatch_action(P2pDisconnectionAction::Init {
+                peer_id: peer_id2,
+                reason,
+            }));
+    };
+
+    disconnect(&mut cluster);
+    let score = cluster.rust_node(node1).state().peer_scores.scores[&peer_id2];
+    assert_eq!(score.value, 50);
+    assert!(!cluster.rust_node(node1).state().is_peer_banned(&peer_id2));
+
+    // recovers 1 point per minute.
+    cluster
+        .rust_node_mut(node1)
+        .advance_time(Duration::from_secs(10 * 60));
+
+    // wait for the second node to notice the disconnection.
+    for _ in 0..50 {
+        let peer = cluster.rust_node(node2).state().peers.get(&peer_id1);
+        if peer.map_or(true, |peer| !peer.status.is_connected_or_connecting()) {
+            break;
+        }
+        try_run_cluster(&mut cluster, Duration::from_millis(100)).await?;
+    }
+
+    cluster.connect(node2, node1)?;
+    let connected = try_wait_for_nodes_to_connect(
+        &mut cluster,
+        [(node1, peer_id2), (node2, peer_id1)],
+        Duration::from_secs(5),
+    )
+    .await?;
+    assert!(connected);
+
+    // without the recovery, the score would drop to the ban threshold.
+    disconnect(&mut cluster);
+    let score = cluster.rust_node(node1).state().peer_scores.scores[&peer_id2];
+    assert_eq!(score.value, 10);
+    assert!(!cluster.rust_node(node1).state().is_peer_banned(&peer_id2));
+
+    Ok(())
+}
@@ -113,6 +113,10 @@ async fn rust_to_rust() {
         // "block", // overflow
         "ledger_query",
         // "staged_ledger_aux_and_pending_coinbases_at_block" // error
+        "ancestry",
+        "transition_chain_proof",
+        "node_status",
+        "epoch_ledger",
     );
 
     for (query, response) in rpcs {
@@ -147,6 +151,10 @@ rpc_test!(
     staged_ledger_aux_and_pending_coinbases_at_block,
     11540..11550
 );
+rpc_test!(ancestry, 11550..11560);
+rpc_test!(transition_chain_proof, 11560..11570);
+rpc_test!(node_status, 11570..11580);
+rpc_test!(epoch_ledger, 11580..11590);
 
 async fn rust_to_rust_rpc(
     query: P2pRpcRequest,
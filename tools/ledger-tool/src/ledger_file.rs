@@ -0,0 +1,209 @@
+use std::{fs, path::Path};
+
+use ledger::{BaseLedger, Database, Mask};
+use mina_p2p_messages::v2;
+use node::{
+    account::AccountPublicKey,
+    core::constants::constraint_constants,
+    daemon_json::{Account, DaemonJson, Ledger},
+};
+use serde_json::Value;
+
+/// Reads a ledger file. It can be a whole daemon.json, its `ledger`
+/// section or a plain list of accounts, as exported by the mina daemon.
+pub fn read(path: &Path) -> anyhow::Result<Ledger> {
+    let data = fs::read(path)?;
+    let value = serde_json::from_slice::<Value>(&data)?;
+    if value.is_array() {
+        Ok(from_accounts(serde_json::from_value(value)?, None))
+    } else if value.get("ledger").is_some() {
+        serde_json::from_value::<DaemonJson>(value)?
+            .ledger
+            .ok_or_else(|| anyhow::anyhow!("no ledger in {}", path.display()))
+    } else {
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+pub fn write(path: Option<&Path>, ledger: &Ledger) -> anyhow::Result<()> {
+    let data = serde_json::to_string_pretty(ledger)?;
+    match path {
+        Some(path) => fs::write(path, data)?,
+        None => println!("{data}"),
+    }
+    Ok(())
+}
+
+/// Ledger made of exactly the `accounts`, without the genesis winner.
+pub fn from_accounts(accounts: Vec<Account>, hash: Option<&v2::LedgerHash>) -> Ledger {
+    Ledger {
+        accounts: Some(accounts),
+        num_accounts: None,
+        balances: None,
+        hash: hash.map(ToString::to_string),
+        s3_data_hash: None,
+        name: None,
+        add_genesis_winner: Some(false),
+    }
+}
+
+/// Accounts of the ledger as the node loads them, in the ledger order.
+pub fn accounts(ledger: &Ledger) -> anyhow::Result<Vec<ledger::Account>> {
+    if ledger.accounts.is_none() {
+        anyhow::bail!("only ledgers with the accounts listed are supported");
+    }
+    ledger
+        .accounts_with_genesis_winner()
+        .iter()
+        .map(|account| Ok(account.to_account()?))
+        .collect()
+}
+
+pub fn build_mask(accounts: &[ledger::Account]) -> anyhow::Result<Mask> {
+    let db = Database::create(constraint_constants().ledger_depth as u8);
+    let mut mask = Mask::new_root(db);
+    for account in accounts {
+        mask.get_or_create_account(account.id(), account.clone())
+            .map_err(|err| {
+                let public_key = AccountPublicKey::from(account.public_key.clone());
+                anyhow::anyhow!("failed to add account {public_key}: {err:?}")
+            })?;
+    }
+    Ok(mask)
+}
+
+pub fn merkle_root(accounts: &[ledger::Account]) -> anyhow::Result<v2::LedgerHash> {
+    let mut mask = build_mask(accounts)?;
+    Ok(v2::MinaBaseLedgerHash0StableV1(mask.merkle_root().into()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use ledger::{
+        scan_state::currency::{Amount, Balance, Slot, SlotSpan},
+        AccountId, Timing, TokenId,
+    };
+    use node::account::AccountSecretKey;
+
+    use super::*;
+
+    fn test_accounts() -> Vec<ledger::Account> {
+        let mut accounts = (0..3)
+            .map(|i| {
+                let public_key = AccountSecretKey::deterministic(i).public_key_compressed();
+                let id = AccountId::new(public_key, TokenId::default());
+                ledger::Account::create_with(id, Balance::from_u64((i + 1) * 1_500_000_000))
+            })
+            .collect::<Vec<_>>();
+        accounts[1].delegate = Some(accounts[0].public_key.clone());
+        accounts[2].timing = Timing::Timed {
+            initial_minimum_balance: Balance::from_u64(1_000_000_000),
+            cliff_time: Slot::from_u32(100),
+            cliff_amount: Amount::from_u64(500_000_000),
+            vesting_period: SlotSpan::from_u32(10),
+            vesting_increment: Amount::from_u64(1_000),
+        };
+        accounts
+    }
+
+    fn read_written(data: &[u8]) -> Ledger {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.json");
+        fs::write(&path, data).unwrap();
+        read(&path).unwrap()
+    }
+
+    #[test]
+    fn test_read_formats() {
+        let expected = test_accounts();
+        let ledger = from_accounts(expected.iter().map(Into::into).collect(), None);
+
+        let list = serde_json::to_vec(ledger.accounts.as_ref().unwrap()).unwrap();
+        let daemon_json = serde_json::to_vec(&serde_json::json!({ "ledger": ledger })).unwrap();
+        let ledger_section = serde_json::to_vec(&ledger).unwrap();
+        for (name, data) in [
+            ("list", list),
+            ("daemon-json", daemon_json),
+            ("ledger-section", ledger_section),
+        ] {
+            let ledger = read_written(&data);
+            assert_eq!(accounts(&ledger).unwrap(), expected, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.json");
+        assert!(read(&path).is_err());
+
+        let no_ledger = serde_json::to_vec(&serde_json::json!({ "ledger": null })).unwrap();
+        fs::write(&path, no_ledger).unwrap();
+        assert!(read(&path).is_err());
+
+        // ledgers given by name or hash only can't be built.
+        let ledger =
+            read_written(br#"{"hash": "jxoEGUu9wC5vtLuPNNzsXx2nrU6bVTJ5tQohxZp53FYoXdvSvuB"}"#);
+        assert!(accounts(&ledger).is_err());
+    }
+
+    #[test]
+    fn test_genesis_winner() {
+        let expected = test_accounts();
+        let list = serde_json::to_vec(
+            &expected
+                .iter()
+                .map(node::daemon_json::Account::from)
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+        // a list of accounts is taken as is.
+        let ledger = read_written(&list);
+        assert_eq!(accounts(&ledger).unwrap(), expected);
+
+        // a ledger section adds the genesis winner, unless it's disabled.
+        let mut ledger = serde_json::from_slice::<Value>(&list).unwrap();
+        ledger = serde_json::json!({ "accounts": ledger });
+        let ledger = read_written(&serde_json::to_vec(&ledger).unwrap());
+        let with_winner = accounts(&ledger).unwrap();
+        assert_eq!(with_winner.len(), expected.len() + 1);
+        assert_eq!(
+            AccountPublicKey::from(with_winner[0].public_key.clone()).to_string(),
+            "B62qiy32p8kAKnny8ZFwoMhYpBppM1DWVCqAPBYNcXnsAHhnfAAuXgg"
+        );
+        assert_eq!(&with_winner[1..], &expected[..]);
+    }
+
+    #[test]
+    fn test_export_round_trip() {
+        let expected = test_accounts();
+        let hash = merkle_root(&expected).unwrap();
+        let exported = from_accounts(expected.iter().map(Into::into).collect(), Some(&hash));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ledger.json");
+        write(Some(&path), &exported).unwrap();
+        let ledger = read(&path).unwrap();
+
+        assert_eq!(ledger.hash, Some(hash.to_string()));
+        let accounts = accounts(&ledger).unwrap();
+        assert_eq!(accounts, expected);
+        assert_eq!(merkle_root(&accounts).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_merkle_root() {
+        let accounts = test_accounts();
+        let hash = merkle_root(&accounts).unwrap();
+        // hash depends on the order of the accounts.
+        let reversed = accounts.iter().rev().cloned().collect::<Vec<_>>();
+        assert_ne!(merkle_root(&reversed).unwrap(), hash);
+
+        let mut mask = build_mask(&accounts).unwrap();
+        assert_eq!(mask.num_accounts(), accounts.len());
+        assert_eq!(
+            v2::LedgerHash::from(v2::MinaBaseLedgerHash0StableV1(mask.merkle_root().into())),
+            hash
+        );
+    }
+}
@@ -1,25 +1,145 @@
+mod ledger_file;
+
 use std::{
+    collections::BTreeMap,
     fs::{self, File},
     path::PathBuf,
 };
 
+use ledger::FpExt;
+use mina_p2p_messages::v2;
+use node::{account::AccountPublicKey, daemon_json};
 use reqwest::Url;
-
+use serde_json::Value;
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
-struct Args {
-    #[structopt(short, long)]
-    input: Option<PathBuf>,
-    #[structopt(long)]
-    url: Option<Url>,
-    #[structopt(short, long)]
-    output: PathBuf,
+enum Command {
+    /// Converts a genesis ledger from daemon.json to the binprot format
+    /// suitable for OpenMina.
+    Convert {
+        #[structopt(short, long)]
+        input: Option<PathBuf>,
+        #[structopt(long)]
+        url: Option<Url>,
+        #[structopt(short, long)]
+        output: PathBuf,
+    },
+    /// Prints the merkle root of a ledger file.
+    Hash { input: PathBuf },
+    /// Checks that a ledger file has the declared hash, the one in the
+    /// file if `--hash` isn't given.
+    Check {
+        input: PathBuf,
+        #[structopt(long)]
+        hash: Option<v2::LedgerHash>,
+    },
+    /// Prints the accounts that differ between two ledger files.
+    Diff { left: PathBuf, right: PathBuf },
+    /// Exports a ledger known by the node to the daemon.json format.
+    Export {
+        /// Url of the node's http server.
+        #[structopt(long)]
+        url: Url,
+        #[structopt(long)]
+        ledger_hash: v2::LedgerHash,
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Exports the staking ledger of the node's best tip to the
+    /// daemon.json format.
+    StakingLedger {
+        /// Url of the node's http server.
+        #[structopt(long)]
+        url: Url,
+        /// Export the next epoch ledger instead.
+        #[structopt(long)]
+        next: bool,
+        /// Only export the accounts delegating to the public key.
+        #[structopt(long)]
+        delegate: Option<AccountPublicKey>,
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Keeps only the accounts delegating to the public key.
+    Filter {
+        input: PathBuf,
+        #[structopt(long)]
+        delegate: AccountPublicKey,
+        #[structopt(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 fn main() -> anyhow::Result<()> {
-    let Args { input, url, output } = Args::from_args();
+    match Command::from_args() {
+        Command::Convert { input, url, output } => convert(input, url, output),
+        Command::Hash { input } => {
+            let ledger = ledger_file::read(&input)?;
+            let hash = ledger_file::merkle_root(&ledger_file::accounts(&ledger)?)?;
+            println!("{hash}");
+            Ok(())
+        }
+        Command::Check { input, hash } => {
+            let ledger = ledger_file::read(&input)?;
+            let expected = match hash {
+                Some(hash) => hash,
+                None => ledger
+                    .hash
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("no hash in the file, use `--hash`"))?
+                    .parse()?,
+            };
+            let computed = ledger_file::merkle_root(&ledger_file::accounts(&ledger)?)?;
+            if computed != expected {
+                anyhow::bail!("ledger hash mismatch, expected: {expected}, computed: {computed}");
+            }
+            println!("ok: {computed}");
+            Ok(())
+        }
+        Command::Diff { left, right } => diff(left, right),
+        Command::Export {
+            url,
+            ledger_hash,
+            output,
+        } => {
+            let accounts = fetch_ledger(&url, &ledger_hash)?;
+            let accounts = accounts.iter().map(Into::into).collect();
+            ledger_file::write(
+                output.as_deref(),
+                &ledger_file::from_accounts(accounts, Some(&ledger_hash)),
+            )
+        }
+        Command::StakingLedger {
+            url,
+            next,
+            delegate,
+            output,
+        } => {
+            let ledger_hash = fetch_epoch_ledger_hash(&url, next)?;
+            eprintln!("ledger hash: {ledger_hash}");
+            let accounts = fetch_ledger(&url, &ledger_hash)?;
+            let ledger = match delegate {
+                Some(delegate) => filter_by_delegate(accounts, &delegate),
+                None => {
+                    let accounts = accounts.iter().map(Into::into).collect();
+                    ledger_file::from_accounts(accounts, Some(&ledger_hash))
+                }
+            };
+            ledger_file::write(output.as_deref(), &ledger)
+        }
+        Command::Filter {
+            input,
+            delegate,
+            output,
+        } => {
+            let accounts = ledger_file::accounts(&ledger_file::read(&input)?)?;
+            ledger_file::write(output.as_deref(), &filter_by_delegate(accounts, &delegate))
+        }
+    }
+}
 
+fn convert(input: Option<PathBuf>, url: Option<Url>, output: PathBuf) -> anyhow::Result<()> {
     let data = if let Some(input) = input {
         fs::read(input)?
     } else if let Some(url) = url {
@@ -28,7 +148,7 @@ fn main() -> anyhow::Result<()> {
         anyhow::bail!("must provide either `--input` or `--url`");
     };
 
-    let daemon_json = serde_json::from_slice::<node::daemon_json::DaemonJson>(&data)?;
+    let daemon_json = serde_json::from_slice::<daemon_json::DaemonJson>(&data)?;
 
     let prebuilt_config =
         node::transition_frontier::genesis::PrebuiltGenesisConfig::try_from(daemon_json)?;
@@ -37,3 +157,143 @@ fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+fn diff(left: PathBuf, right: PathBuf) -> anyhow::Result<()> {
+    // Accounts are compared in the daemon.json format written by us, so
+    // that equal values written differently in the files don't differ.
+    let read = |path: &PathBuf| -> anyhow::Result<BTreeMap<(String, String), Value>> {
+        ledger_file::accounts(&ledger_file::read(path)?)?
+            .iter()
+            .map(|account| {
+                let key = (
+                    AccountPublicKey::from(account.public_key.clone()).to_string(),
+                    account.token_id.0.to_decimal(),
+                );
+                Ok((
+                    key,
+                    serde_json::to_value(daemon_json::Account::from(account))?,
+                ))
+            })
+            .collect()
+    };
+    let left = read(&left)?;
+    let right = read(&right)?;
+
+    let mut differences = 0;
+    for (key, left_account) in &left {
+        let (public_key, token_id) = key;
+        let Some(right_account) = right.get(key) else {
+            println!("- {public_key} (token: {token_id})");
+            differences += 1;
+            continue;
+        };
+        let (Value::Object(left_fields), Value::Object(right_fields)) =
+            (left_account, right_account)
+        else {
+            unreachable!("accounts are serialized as objects");
+        };
+        let changed = left_fields
+            .iter()
+            .filter(|(field, value)| right_fields.get(*field) != Some(*value))
+            .map(|(field, _)| field.as_str())
+            .chain(
+                right_fields
+                    .keys()
+                    .filter(|field| !left_fields.contains_key(*field))
+                    .map(String::as_str),
+            )
+            .collect::<Vec<_>>();
+        if changed.is_empty() {
+            continue;
+        }
+        differences += 1;
+        println!("~ {public_key} (token: {token_id})");
+        for field in changed {
+            let value = |fields: &serde_json::Map<String, Value>| {
+                fields
+                    .get(field)
+                    .map_or("none".to_owned(), Value::to_string)
+            };
+            println!(
+                "    {field}: {} -> {}",
+                value(left_fields),
+                value(right_fields)
+            );
+        }
+    }
+    for (public_key, token_id) in right.keys().filter(|key| !left.contains_key(*key)) {
+        println!("+ {public_key} (token: {token_id})");
+        differences += 1;
+    }
+
+    eprintln!("{differences} accounts differ");
+    Ok(())
+}
+
+fn filter_by_delegate(
+    accounts: Vec<ledger::Account>,
+    delegate: &AccountPublicKey,
+) -> daemon_json::Ledger {
+    let accounts = accounts
+        .iter()
+        .filter(|account| {
+            account.delegate.as_ref().map_or(false, |key| {
+                &AccountPublicKey::from(key.clone()) == delegate
+            })
+        })
+        .collect::<Vec<_>>();
+    let stake = accounts
+        .iter()
+        .map(|account| account.balance.as_u64())
+        .sum::<u64>();
+    eprintln!(
+        "{} accounts delegating to {delegate}, stake: {}",
+        accounts.len(),
+        ledger::scan_state::currency::Amount::from_u64(stake).to_mina_string()
+    );
+    // Hash of the whole ledger doesn't apply to a part of it.
+    let accounts = accounts.into_iter().map(Into::into).collect();
+    ledger_file::from_accounts(accounts, None)
+}
+
+fn fetch_ledger(url: &Url, ledger_hash: &v2::LedgerHash) -> anyhow::Result<Vec<ledger::Account>> {
+    let url = url.join(&format!("ledger/{ledger_hash}/accounts"))?;
+    let data = reqwest::blocking::get(url)?.error_for_status()?.bytes()?;
+    let accounts = serde_json::from_slice::<Vec<ledger::Account>>(&data)?;
+    if accounts.is_empty() {
+        anyhow::bail!("ledger {ledger_hash} isn't known by the node");
+    }
+
+    let computed = ledger_file::merkle_root(&accounts)?;
+    if &computed != ledger_hash {
+        anyhow::bail!("node returned ledger with a wrong hash: {computed}");
+    }
+    Ok(accounts)
+}
+
+fn fetch_epoch_ledger_hash(url: &Url, next: bool) -> anyhow::Result<v2::LedgerHash> {
+    let epoch_data = if next {
+        "nextEpochData"
+    } else {
+        "stakingEpochData"
+    };
+    let query = format!(
+        "{{ bestChain(maxLength: 1) {{ protocolState {{ consensusState {{ \
+         {epoch_data} {{ ledger {{ hash }} }} }} }} }} }}"
+    );
+    let data = reqwest::blocking::Client::new()
+        .post(url.join("graphql")?)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(serde_json::to_vec(&serde_json::json!({ "query": query }))?)
+        .send()?
+        .error_for_status()?
+        .bytes()?;
+    let response = serde_json::from_slice::<Value>(&data)?;
+    let hash = response
+        .pointer(&format!(
+            "/data/bestChain/0/protocolState/consensusState/{epoch_data}/ledger/hash"
+        ))
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow::anyhow!("unexpected response from the node: {response}"))?;
+    Ok(hash.parse()?)
+}